@@ -0,0 +1,130 @@
+use dioxus::prelude::*;
+use nostr_sdk::{Timestamp, ToBech32};
+use crate::routes::Route;
+use crate::stores::activity_digest::{self, DigestWindow, DIGEST_DISMISSED};
+use crate::utils::activity_digest::ActivityDigest;
+use crate::utils::format_sats_with_separator;
+use crate::utils::time::format_relative_time_ex;
+
+/// "While you were away" card shown on Home after an absence
+///
+/// Summarizes notifications, DMs and popular posts from follows since the last
+/// visit. On a first visit it explains what the card will do next time.
+#[component]
+pub fn ActivityDigestCard() -> Element {
+    let window = activity_digest::get_digest_window();
+    let mut digest = use_signal(|| None::<ActivityDigest>);
+
+    use_effect(move || {
+        if let Some(DigestWindow::Since { since, until }) = activity_digest::get_digest_window() {
+            spawn(async move {
+                match activity_digest::compute_digest(since, until).await {
+                    Ok(result) => digest.set(Some(result)),
+                    Err(e) => log::warn!("Failed to compute activity digest: {}", e),
+                }
+            });
+        }
+    });
+
+    if *DIGEST_DISMISSED.read() {
+        return rsx! {};
+    }
+
+    let body = match window {
+        Some(DigestWindow::FirstVisit) => rsx! {
+            h3 { class: "font-bold mb-1", "Welcome to nostr.blue" }
+            p {
+                class: "text-sm text-muted-foreground",
+                "Next time you come back, this card will summarize the mentions, replies, zaps and messages you missed."
+            }
+        },
+        Some(DigestWindow::Since { since, .. }) => {
+            let away = format_relative_time_ex(Timestamp::from(since), true, true);
+            match digest.read().as_ref() {
+                None => rsx! {
+                    h3 { class: "font-bold mb-1", "While you were away" }
+                    p { class: "text-sm text-muted-foreground", "Catching up on activity since your last visit {away}..." }
+                },
+                Some(d) if d.is_empty() => rsx! {
+                    h3 { class: "font-bold mb-1", "While you were away" }
+                    p { class: "text-sm text-muted-foreground", "Nothing new since your last visit {away}. You're all caught up." }
+                },
+                Some(d) => {
+                    let top_posts: Vec<(String, String, usize)> = d.top_posts
+                        .iter()
+                        .map(|(post, count)| {
+                            let note_id = post.id.to_bech32().unwrap_or_else(|_| post.id.to_hex());
+                            let preview: String = post.content.chars().take(100).collect();
+                            (note_id, preview, *count)
+                        })
+                        .collect();
+                    let zap_sats = format_sats_with_separator(d.zap_sats);
+
+                    rsx! {
+                        h3 { class: "font-bold mb-1", "While you were away" }
+                        p { class: "text-xs text-muted-foreground mb-3", "Since your last visit {away}" }
+
+                        div {
+                            class: "grid grid-cols-2 sm:grid-cols-3 gap-2 text-sm",
+                            DigestStat { label: "Mentions", value: d.mentions.to_string() }
+                            DigestStat { label: "Replies", value: d.replies.to_string() }
+                            DigestStat { label: "Reactions", value: d.reactions.to_string() }
+                            DigestStat { label: "Zaps", value: format!("{} ({} sats)", d.zaps, zap_sats) }
+                            DigestStat { label: "Messages", value: d.direct_messages.to_string() }
+                        }
+
+                        if d.mentions + d.replies + d.reactions + d.zaps > 0 {
+                            Link {
+                                to: Route::Notifications {},
+                                class: "inline-block mt-3 text-sm text-primary hover:underline",
+                                "View notifications"
+                            }
+                        }
+
+                        if !top_posts.is_empty() {
+                            div {
+                                class: "mt-3",
+                                p { class: "text-sm font-semibold mb-1", "Popular from people you follow" }
+                                for (note_id, preview, count) in top_posts {
+                                    Link {
+                                        key: "{note_id}",
+                                        to: Route::Note { note_id: note_id.clone(), from_voice: None },
+                                        class: "block py-2 text-sm border-t border-border hover:bg-accent/50 transition-colors",
+                                        div { class: "line-clamp-2", "{preview}" }
+                                        div { class: "text-xs text-muted-foreground", "{count} interactions" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        // Recent visit or session not started yet
+        _ => return rsx! {},
+    };
+
+    rsx! {
+        div {
+            class: "relative border-b border-border p-4 bg-blue-50 dark:bg-blue-900/20",
+            button {
+                class: "absolute top-2 right-2 p-1 rounded-full hover:bg-accent transition text-muted-foreground",
+                title: "Dismiss",
+                onclick: move |_| activity_digest::dismiss_digest(),
+                "✕"
+            }
+            {body}
+        }
+    }
+}
+
+#[component]
+fn DigestStat(label: &'static str, value: String) -> Element {
+    rsx! {
+        div {
+            class: "p-2 rounded-lg bg-background border border-border",
+            div { class: "font-semibold", "{value}" }
+            div { class: "text-xs text-muted-foreground", "{label}" }
+        }
+    }
+}
@@ -2,7 +2,11 @@ use dioxus::prelude::*;
 use nostr_sdk::{Event as NostrEvent, PublicKey};
 use nostr_sdk::prelude::NostrDatabaseExt;
 use crate::routes::Route;
-use crate::stores::nostr_client::get_client;
+use crate::components::NoteMenu;
+use crate::stores::nostr_client::{get_client, DELETED_EVENTS};
+use crate::stores::relay_metadata;
+use crate::utils::nip19::encode_naddr;
+use dioxus_primitives::toast::{consume_toast, ToastOptions};
 use crate::utils::article_meta::{
     get_title, get_summary, get_image, get_published_at,
     get_hashtags, get_identifier, calculate_read_time
@@ -23,6 +27,8 @@ pub fn ArticleCard(event: NostrEvent) -> Element {
     let author_pubkey = event.pubkey.to_string();
     let author_pubkey_for_fetch = author_pubkey.clone();
 
+    let toast = consume_toast();
+
     // State for author profile
     let mut author_metadata = use_signal(|| None::<nostr_sdk::Metadata>);
 
@@ -96,6 +102,11 @@ pub fn ArticleCard(event: NostrEvent) -> Element {
         nip19_coord.to_bech32().ok()
     });
 
+    // Shareable naddr with relay hints (where the user publishes)
+    let share_naddr = identifier.as_deref().and_then(|id| {
+        encode_naddr(event.kind, event.pubkey, id, &relay_metadata::share_relay_hints(&[])).ok()
+    });
+
     // Show first 3 hashtags
     let displayed_tags: Vec<String> = hashtags.iter().take(3).cloned().collect();
 
@@ -118,9 +129,49 @@ pub fn ArticleCard(event: NostrEvent) -> Element {
         }
     };
 
+    // Deleted by the author from this session; drop it from the list
+    let event_id = event.id.to_hex();
+    if DELETED_EVENTS.read().contains(&event_id) {
+        return rsx! {};
+    }
+
     rsx! {
         div {
-            class: "group bg-card rounded-lg border border-border overflow-hidden hover:border-primary/50 transition-all duration-200 hover:shadow-lg",
+            class: "relative group bg-card rounded-lg border border-border hover:border-primary/50 transition-all duration-200 hover:shadow-lg",
+
+            // Overflow menu, kept outside the link so its clicks don't navigate
+            div {
+                class: "absolute top-2 right-2 z-20 rounded-full bg-background/90",
+                NoteMenu {
+                    author_pubkey: author_pubkey.clone(),
+                    event_id: event_id.clone(),
+                    event_kind: event.kind,
+                    identifier: identifier.clone()
+                }
+            }
+
+            // Copy as naddr
+            if let Some(naddr) = share_naddr {
+                button {
+                    class: "absolute top-2 right-12 z-10 px-2 py-1 text-xs rounded-md bg-background/90 border border-border opacity-0 group-hover:opacity-100 focus:opacity-100 hover:bg-accent transition",
+                    title: "Copy as naddr",
+                    onclick: move |e: MouseEvent| {
+                        e.stop_propagation();
+                        e.prevent_default();
+                        if let Some(window) = web_sys::window() {
+                            let _ = window.navigator().clipboard().write_text(&naddr);
+                            toast.success(
+                                "Copied!".to_string(),
+                                ToastOptions::new()
+                                    .description("Shareable naddr copied to clipboard")
+                                    .duration(Duration::from_secs(2))
+                                    .permanent(false),
+                            );
+                        }
+                    },
+                    "🔗 Copy naddr"
+                }
+            }
 
             // Link wrapper - only clickable if has identifier
             if let Some(naddr) = naddr_opt {
@@ -131,7 +182,7 @@ pub fn ArticleCard(event: NostrEvent) -> Element {
                     // Cover image
                     if let Some(img_url) = image_url {
                         div {
-                            class: "aspect-video w-full bg-muted overflow-hidden",
+                            class: "aspect-video w-full bg-muted overflow-hidden rounded-t-lg",
                             img {
                                 src: "{img_url}",
                                 alt: "{title}",
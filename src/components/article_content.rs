@@ -18,6 +18,7 @@ pub fn ArticleContent(content: String) -> Element {
                    [&_ul]:my-4 [&_ul]:pl-6 [&_ul]:list-disc
                    [&_ol]:my-4 [&_ol]:pl-6 [&_ol]:list-decimal
                    [&_li]:my-2
+                   [&_li:has(>input)]:list-none [&_li:has(>input)]:-ml-6 [&_input]:mr-2
                    [&_blockquote]:border-l-4 [&_blockquote]:border-primary [&_blockquote]:pl-4 [&_blockquote]:my-4 [&_blockquote]:italic
                    [&_code]:bg-muted [&_code]:px-1 [&_code]:py-0.5 [&_code]:rounded [&_code]:text-sm
                    [&_pre]:bg-muted [&_pre]:p-4 [&_pre]:rounded-lg [&_pre]:overflow-x-auto [&_pre]:my-4
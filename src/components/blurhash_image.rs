@@ -0,0 +1,78 @@
+use dioxus::prelude::*;
+use crate::utils::blurhash;
+
+/// An image with a placeholder shown until it loads
+///
+/// The placeholder is the decoded `blurhash` when there is a valid one, a
+/// neutral skeleton otherwise. With known dimensions the box keeps the
+/// image's aspect ratio before it loads, so the layout doesn't jump. Give
+/// it a `key` of the URL where the URL can change in place. An image that
+/// fails to load is hidden along with its placeholder.
+#[component]
+pub fn BlurhashImage(
+    url: String,
+    #[props(default)] blurhash: Option<String>,
+    #[props(default)] dim: Option<(u32, u32)>,
+    #[props(default = "Image".to_string())] alt: String,
+    /// Classes for the wrapper
+    #[props(default)] class: String,
+    /// Classes for the image
+    #[props(default = "max-w-full h-auto".to_string())] img_class: String,
+) -> Element {
+    let mut loaded = use_signal(|| false);
+    let mut failed = use_signal(|| false);
+
+    if *failed.read() {
+        return rsx! {};
+    }
+
+    let placeholder = blurhash.as_deref().and_then(blurhash::placeholder_data_url);
+    let is_loaded = *loaded.read();
+
+    let aspect_style = match dim {
+        Some((width, height)) if width > 0 && height > 0 => format!("aspect-ratio: {} / {};", width, height),
+        _ => String::new(),
+    };
+    // Without dimensions, hold some space until the image arrives
+    let min_height = if is_loaded || !aspect_style.is_empty() { "" } else { "min-h-48" };
+    let wrapper_style = if is_loaded { String::new() } else { aspect_style };
+    let image_class = if is_loaded {
+        format!("{} transition-opacity duration-300", img_class)
+    } else {
+        format!("{} relative opacity-0", img_class)
+    };
+
+    let url_for_error = url.clone();
+
+    rsx! {
+        div {
+            class: "relative {min_height} {class}",
+            style: "{wrapper_style}",
+
+            if !is_loaded {
+                if let Some(placeholder) = placeholder {
+                    img {
+                        src: "{placeholder}",
+                        alt: "",
+                        aria_hidden: "true",
+                        class: "absolute inset-0 w-full h-full object-cover",
+                    }
+                } else {
+                    div { class: "absolute inset-0 bg-muted animate-pulse" }
+                }
+            }
+
+            img {
+                src: "{url}",
+                alt: "{alt}",
+                class: "{image_class}",
+                loading: "lazy",
+                onload: move |_| loaded.set(true),
+                onerror: move |_| {
+                    log::warn!("Failed to load image: {}", url_for_error);
+                    failed.set(true);
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use dioxus::prelude::*;
 use crate::stores::cashu;
-use crate::stores::cashu::PaymentRequestProgress;
+use crate::stores::cashu::{AmountMismatchPolicy, PaymentRequestProgress};
+use crate::utils::time::now_secs;
+
+/// How long a Nostr request waits for payment, as (seconds, label)
+const EXPIRY_OPTIONS: [(u64, &str); 3] = [(300, "5 minutes"), (900, "15 minutes"), (3600, "1 hour")];
 
 #[component]
 pub fn CashuCreateRequestModal(
@@ -15,6 +19,10 @@ pub fn CashuCreateRequestModal(
     let mut error_message = use_signal(|| Option::<String>::None);
     let mut copied = use_signal(|| false);
     let mut copy_error = use_signal(|| Option::<String>::None);
+    let mut expiry_secs = use_signal(|| EXPIRY_OPTIONS[0].0);
+    let mut reject_wrong_amount = use_signal(|| false);
+    let mut expires_at = use_signal(|| Option::<u64>::None);
+    let mut now = use_signal(now_secs);
 
     let progress = cashu::PAYMENT_REQUEST_PROGRESS.read();
     let balance = *cashu::WALLET_BALANCE.read();
@@ -29,6 +37,12 @@ pub fn CashuCreateRequestModal(
         let amount_str = amount_input.read().clone();
         let description = description_input.read().clone();
         let use_nostr = *use_nostr_transport.read();
+        let timeout_secs = *expiry_secs.read();
+        let on_mismatch = if *reject_wrong_amount.read() {
+            AmountMismatchPolicy::Reject
+        } else {
+            AmountMismatchPolicy::Accept
+        };
 
         // Parse amount (optional)
         let amount: Option<u64> = if amount_str.is_empty() {
@@ -64,7 +78,7 @@ pub fn CashuCreateRequestModal(
                         // Start waiting in background
                         let request_id = info.request_id.clone();
                         spawn(async move {
-                            match cashu::wait_for_nostr_payment(request_id.clone(), 300).await {
+                            match cashu::wait_for_nostr_payment(request_id.clone(), timeout_secs, on_mismatch).await {
                                 Ok(amount) => {
                                     log::info!("Received payment of {} sats", amount);
                                 }
@@ -76,6 +90,19 @@ pub fn CashuCreateRequestModal(
                                 }
                             }
                         });
+
+                        // Tick the expiry countdown while this request is current
+                        let deadline = now_secs() + timeout_secs;
+                        expires_at.set(Some(deadline));
+                        let request_id = info.request_id.clone();
+                        spawn(async move {
+                            while current_request_id.peek().as_deref() == Some(request_id.as_str())
+                                && now_secs() <= deadline
+                            {
+                                now.set(now_secs());
+                                gloo_timers::future::TimeoutFuture::new(1000).await;
+                            }
+                        });
                     }
 
                     is_creating.set(false);
@@ -136,12 +163,25 @@ pub fn CashuCreateRequestModal(
     };
 
     // Check if payment was received
-    let payment_received = matches!(&*progress, Some(PaymentRequestProgress::Received { .. }));
-    let received_amount = if let Some(PaymentRequestProgress::Received { amount }) = &*progress {
-        Some(*amount)
+    let payment_received = matches!(
+        &*progress,
+        Some(PaymentRequestProgress::Received { .. } | PaymentRequestProgress::ReceivedWrongAmount { .. })
+    );
+    let received_amount = match &*progress {
+        Some(PaymentRequestProgress::Received { amount })
+        | Some(PaymentRequestProgress::ReceivedWrongAmount { amount, .. }) => Some(*amount),
+        _ => None,
+    };
+    let short_of = if let Some(PaymentRequestProgress::ReceivedWrongAmount { expected, .. }) = &*progress {
+        Some(*expected)
     } else {
         None
     };
+    let countdown = expires_at.read().map(|deadline| {
+        let secs = deadline.saturating_sub(*now.read());
+        format!("{}:{:02}", secs / 60, secs % 60)
+    });
+    let has_amount = !amount_input.read().is_empty();
 
     rsx! {
         // Modal backdrop
@@ -189,6 +229,12 @@ pub fn CashuCreateRequestModal(
                                 "{amount} sats received"
                             }
                         }
+                        if let Some(expected) = short_of {
+                            p {
+                                class: "mt-2 text-sm text-yellow-600 dark:text-yellow-400",
+                                "The payer sent less than the {expected} sats requested"
+                            }
+                        }
                         button {
                             class: "mt-6 w-full py-3 bg-green-500 hover:bg-green-600 text-white rounded-lg font-semibold transition",
                             onclick: move |_| handle_close(()),
@@ -241,6 +287,24 @@ pub fn CashuCreateRequestModal(
                                             }
                                             span { "Waiting for payment via Nostr..." }
                                         }
+                                        if let Some(countdown) = countdown.as_ref() {
+                                            p {
+                                                class: "mt-1 text-xs",
+                                                "Expires in {countdown}"
+                                            }
+                                        }
+                                    },
+                                    Some(PaymentRequestProgress::Rejected { amount, expected }) => rsx! {
+                                        div {
+                                            class: "text-yellow-600 dark:text-yellow-400",
+                                            "Received a short payment of {amount} sats (requested {expected}). Still waiting for the full amount..."
+                                        }
+                                    },
+                                    Some(PaymentRequestProgress::Expired) => rsx! {
+                                        div {
+                                            class: "text-red-500",
+                                            "This request expired before a payment arrived"
+                                        }
                                     },
                                     Some(PaymentRequestProgress::Error { message }) => rsx! {
                                         div {
@@ -265,6 +329,7 @@ pub fn CashuCreateRequestModal(
                                 }
                                 request_string.set(None);
                                 current_request_id.set(None);
+                                expires_at.set(None);
                                 amount_input.set(String::new());
                                 description_input.set(String::new());
                             },
@@ -355,6 +420,42 @@ pub fn CashuCreateRequestModal(
                             }
                         }
 
+                        // Nostr request options: expiry and short payments
+                        if *use_nostr_transport.read() {
+                            div {
+                                class: "mb-6 space-y-3",
+                                div {
+                                    label {
+                                        class: "block text-sm font-medium mb-2",
+                                        "Expires after"
+                                    }
+                                    select {
+                                        class: "w-full px-4 py-2 bg-background border border-border rounded-lg",
+                                        value: "{expiry_secs}",
+                                        onchange: move |e| {
+                                            if let Ok(secs) = e.value().parse::<u64>() {
+                                                expiry_secs.set(secs);
+                                            }
+                                        },
+                                        for (secs, label) in EXPIRY_OPTIONS {
+                                            option { key: "{secs}", value: "{secs}", "{label}" }
+                                        }
+                                    }
+                                }
+                                if has_amount {
+                                    label {
+                                        class: "flex items-center gap-2 text-sm",
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: *reject_wrong_amount.read(),
+                                            onchange: move |e| reject_wrong_amount.set(e.checked()),
+                                        }
+                                        "Keep waiting if a payment is short"
+                                    }
+                                }
+                            }
+                        }
+
                         // Error message
                         if let Some(err) = error_message.read().as_ref() {
                             div {
@@ -0,0 +1,175 @@
+//! Cashu Keyset Migration Banner
+//!
+//! Checks each mint for rotated keysets when the wallet opens. Proofs left on
+//! an inactive keyset get a banner that moves them to the active keyset after
+//! the user confirms the proof count, amount and estimated fee.
+
+use dioxus::prelude::*;
+
+use crate::stores::cashu::{self, MigrationBanner};
+
+#[component]
+pub fn CashuKeysetMigration() -> Element {
+    // Mints with proofs on rotated keysets
+    let mut banners = use_signal(Vec::<(String, MigrationBanner)>::new);
+    let mut checked = use_signal(|| false);
+    // Mint whose migration is awaiting confirmation or running
+    let mut confirming = use_signal(|| None::<String>);
+    let mut migrating = use_signal(|| false);
+    let mut message = use_signal(|| None::<Result<String, String>>);
+
+    // Check once the wallet is ready
+    use_effect(move || {
+        if *checked.peek() || !cashu::WALLET_STATUS.read().is_ready() {
+            return;
+        }
+        checked.set(true);
+        spawn(async move {
+            let mut found = Vec::new();
+            for mint in cashu::get_mints() {
+                if let Some(banner) = cashu::check_keyset_rotation(&mint).await {
+                    found.push((mint, banner));
+                }
+            }
+            banners.set(found);
+        });
+    });
+
+    let migrate = move |mint: String| {
+        migrating.set(true);
+        message.set(None);
+        spawn(async move {
+            let result = cashu::migrate_inactive_proofs(&mint).await;
+            message.set(Some(match result {
+                Ok(done) => Ok(format!(
+                    "Migrated {} proofs ({} sats, {} sat fee)",
+                    done.proofs_migrated, done.value_migrated, done.fee_paid
+                )),
+                Err(e) => Err(format!("Migration stopped: {}", e)),
+            }));
+
+            // Whatever is still on the old keyset keeps its banner
+            let remaining = cashu::check_keyset_rotation(&mint).await;
+            banners.with_mut(|banners| {
+                banners.retain(|(m, _)| m != &mint);
+                if let Some(banner) = remaining {
+                    banners.push((mint.clone(), banner));
+                }
+            });
+            confirming.set(None);
+            migrating.set(false);
+        });
+    };
+
+    if banners.read().is_empty() && message.read().is_none() {
+        return rsx! {};
+    }
+
+    let progress = *cashu::KEYSET_MIGRATION_PROGRESS.read();
+
+    rsx! {
+        div {
+            class: "mt-6 space-y-2",
+
+            if let Some(msg) = message.read().as_ref() {
+                match msg {
+                    Ok(text) => rsx! {
+                        div {
+                            class: "p-3 rounded-lg bg-green-100 dark:bg-green-900 text-sm text-green-800 dark:text-green-200",
+                            "{text}"
+                        }
+                    },
+                    Err(text) => rsx! {
+                        div {
+                            class: "p-3 rounded-lg bg-red-100 dark:bg-red-900 text-sm text-red-800 dark:text-red-200",
+                            "{text}"
+                        }
+                    },
+                }
+            }
+
+            for (mint, banner) in banners.read().clone() {
+                div {
+                    key: "{mint}",
+                    class: "p-4 rounded-lg border border-yellow-300 dark:border-yellow-800 bg-yellow-50 dark:bg-yellow-900/20 text-sm",
+                    p {
+                        class: "font-medium",
+                        "🔄 {mint_hostname(&mint)} rotated its keys"
+                    }
+                    match banner {
+                        MigrationBanner::NotWorthMigrating { proofs, value } => rsx! {
+                            p {
+                                class: "text-muted-foreground mt-1",
+                                "{proofs} proofs ({value} sats) are on an old keyset, but moving them would cost as much as they're worth."
+                            }
+                        },
+                        MigrationBanner::Migrate { proofs, value, fee } => rsx! {
+                            p {
+                                class: "text-muted-foreground mt-1",
+                                "{proofs} proofs ({value} sats) are on an old keyset. Move them to the mint's current keyset so they stay spendable."
+                            }
+                            if confirming.read().as_deref() == Some(mint.as_str()) {
+                                if *migrating.read() {
+                                    if let Some((done, total)) = progress {
+                                        div {
+                                            class: "mt-3",
+                                            div {
+                                                class: "h-2 rounded-full bg-accent overflow-hidden",
+                                                div {
+                                                    class: "h-full bg-yellow-500 transition-all",
+                                                    style: "width: {done * 100 / total.max(1)}%",
+                                                }
+                                            }
+                                            p {
+                                                class: "text-xs text-muted-foreground mt-1",
+                                                "Migrated {done} of {total} proofs..."
+                                            }
+                                        }
+                                    }
+                                } else {
+                                    div {
+                                        class: "mt-3 flex items-center gap-2 flex-wrap",
+                                        span { "Migrate {proofs} proofs ({value} sats)? Estimated fee: {fee} sats." }
+                                        button {
+                                            class: "px-3 py-1 bg-yellow-500 hover:bg-yellow-600 text-white rounded-lg transition",
+                                            onclick: {
+                                                let mint = mint.clone();
+                                                move |_| migrate(mint.clone())
+                                            },
+                                            "Confirm"
+                                        }
+                                        button {
+                                            class: "px-3 py-1 hover:bg-accent rounded-lg transition",
+                                            onclick: move |_| confirming.set(None),
+                                            "Cancel"
+                                        }
+                                    }
+                                }
+                            } else {
+                                button {
+                                    class: "mt-3 px-3 py-1 bg-yellow-500 hover:bg-yellow-600 text-white rounded-lg transition disabled:opacity-50",
+                                    disabled: *migrating.read(),
+                                    onclick: {
+                                        let mint = mint.clone();
+                                        move |_| confirming.set(Some(mint.clone()))
+                                    },
+                                    "Migrate proofs"
+                                }
+                            }
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Hostname of a mint URL for display
+fn mint_hostname(url: &str) -> String {
+    url.trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .split('/')
+        .next()
+        .unwrap_or(url)
+        .to_string()
+}
@@ -1,6 +1,8 @@
 use dioxus::prelude::*;
 use crate::stores::cashu;
 use crate::stores::cashu::DiscoveredMint;
+use crate::stores::labels::{self, LabelTarget};
+use crate::components::{LabelBadges, LabelPicker};
 use crate::utils::format::truncate_pubkey;
 
 #[component]
@@ -136,7 +138,7 @@ pub fn CashuMintDiscoveryModal(
 
                             p {
                                 class: "text-sm text-muted-foreground mb-4",
-                                "Mints discovered via NIP-87. Sorted by recommendation count."
+                                "Mints discovered via NIP-87. Sorted by recommendation count. Labels from people you follow are listed first."
                             }
 
                             for (mint, display_name) in mints_with_display.read().iter() {
@@ -205,6 +207,15 @@ pub fn CashuMintDiscoveryModal(
                                                 }
                                             }
 
+                                            // Community labels (NIP-32), people you follow first
+                                            div {
+                                                class: "mt-2",
+                                                LabelBadges {
+                                                    target: LabelTarget::Url(normalized_url.clone()),
+                                                    namespace: labels::MINT_NAMESPACE.to_string(),
+                                                }
+                                            }
+
                                             // Description
                                             if let Some(desc) = &mint.description {
                                                 p {
@@ -260,6 +271,17 @@ pub fn CashuMintDiscoveryModal(
                                                 }
                                             }
 
+                                            div {
+                                                class: "mt-2",
+                                                LabelPicker {
+                                                    target: LabelTarget::Url(normalized_url.clone()),
+                                                    namespace: labels::MINT_NAMESPACE.to_string(),
+                                                    options: labels::MINT_LABELS.iter()
+                                                        .map(|(value, description)| (value.to_string(), description.to_string()))
+                                                        .collect::<Vec<_>>(),
+                                                }
+                                            }
+
                                             // Show individual recommendations with comments
                                             if !mint.recommendations.is_empty() {
                                                 {
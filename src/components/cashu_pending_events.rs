@@ -0,0 +1,121 @@
+//! Cashu Pending Events Component
+//!
+//! Lists wallet events still waiting to reach the relays (offline or failed
+//! publishes) with a "Retry now" button, and lets the user delete events that
+//! ran out of automatic retries.
+
+use dioxus::prelude::*;
+use nostr_sdk::Timestamp;
+
+use crate::stores::cashu::events::{get_pending_events_detailed, remove_pending_event, retry_pending_event_now};
+use crate::utils::time::format_relative_time;
+
+#[component]
+pub fn CashuPendingEvents() -> Element {
+    // Id of the event being retried or deleted
+    let mut busy = use_signal(|| None::<String>);
+    let mut error = use_signal(|| None::<String>);
+
+    let pending = get_pending_events_detailed();
+    if pending.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            class: "mt-6",
+            h3 {
+                class: "text-lg font-bold mb-1",
+                "Pending Events"
+            }
+            p {
+                class: "text-sm text-muted-foreground mb-3",
+                "Wallet events that haven't reached your relays yet. They are retried automatically."
+            }
+
+            if let Some(err) = error.read().as_ref() {
+                div {
+                    class: "mb-3 p-3 rounded-lg bg-red-100 dark:bg-red-900 text-sm text-red-800 dark:text-red-200",
+                    "{err}"
+                }
+            }
+
+            div {
+                class: "space-y-2",
+                for event in pending {
+                    div {
+                        key: "{event.id}",
+                        class: if event.needs_attention {
+                            "flex items-center justify-between gap-3 p-3 rounded-lg border border-red-300 dark:border-red-800 bg-red-50 dark:bg-red-900/20"
+                        } else {
+                            "flex items-center justify-between gap-3 p-3 rounded-lg border border-border bg-accent/30"
+                        },
+                        div {
+                            class: "min-w-0",
+                            p {
+                                class: "text-sm font-medium capitalize",
+                                "{event.kind_label} event"
+                            }
+                            p {
+                                class: "text-xs text-muted-foreground",
+                                {format!(
+                                    "Queued {} • {} failed attempt(s)",
+                                    format_relative_time(Timestamp::from(Timestamp::now().as_secs().saturating_sub(event.age_secs))),
+                                    event.retry_count
+                                )}
+                                if let Some(secs) = event.next_retry_in_secs {
+                                    " • next try in {secs}s"
+                                }
+                                if event.needs_attention {
+                                    " • automatic retries stopped"
+                                }
+                            }
+                        }
+                        div {
+                            class: "flex items-center gap-2 flex-shrink-0",
+                            button {
+                                class: "px-3 py-1 text-sm bg-accent hover:bg-accent/80 rounded-lg transition disabled:opacity-50",
+                                disabled: busy.read().is_some(),
+                                onclick: {
+                                    let id = event.id.clone();
+                                    move |_| {
+                                        let id = id.clone();
+                                        busy.set(Some(id.clone()));
+                                        error.set(None);
+                                        spawn(async move {
+                                            if let Err(e) = retry_pending_event_now(&id).await {
+                                                error.set(Some(e));
+                                            }
+                                            busy.set(None);
+                                        });
+                                    }
+                                },
+                                if busy.read().as_deref() == Some(event.id.as_str()) { "Retrying..." } else { "Retry now" }
+                            }
+                            if event.needs_attention {
+                                button {
+                                    class: "px-3 py-1 text-sm text-red-600 hover:bg-red-100 dark:hover:bg-red-900/40 rounded-lg transition disabled:opacity-50",
+                                    disabled: busy.read().is_some(),
+                                    onclick: {
+                                        let id = event.id.clone();
+                                        move |_| {
+                                            let id = id.clone();
+                                            busy.set(Some(id.clone()));
+                                            spawn(async move {
+                                                if let Err(e) = remove_pending_event(&id).await {
+                                                    error.set(Some(e));
+                                                }
+                                                busy.set(None);
+                                            });
+                                        }
+                                    },
+                                    "Delete"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -87,7 +87,7 @@ pub fn CashuReceiveModal(
                         }
                         textarea {
                             class: "w-full px-4 py-3 bg-background border border-border rounded-lg font-mono text-sm min-h-[120px]",
-                            placeholder: "cashuA...",
+                            placeholder: "cashuB... or cashu:cashuB...",
                             value: token_string.read().clone(),
                             oninput: move |evt| token_string.set(evt.value())
                         }
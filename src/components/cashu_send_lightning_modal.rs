@@ -232,7 +232,11 @@ pub fn CashuSendLightningModal(
                                 payment_result.set(Some((paid, preimage, fee)));
                                 is_paying.set(false);
                                 melt_status.set(None);
-                                *MELT_PROGRESS.write() = None;
+                                // A pending payment keeps its progress; the background poll resolves it
+                                let still_pending = matches!(*MELT_PROGRESS.read(), Some(MeltProgress::PaymentPending { .. }));
+                                if !still_pending {
+                                    *MELT_PROGRESS.write() = None;
+                                }
 
                                 if paid {
                                     spawn(async move {
@@ -375,9 +379,35 @@ pub fn CashuSendLightningModal(
                                     class: "flex items-start gap-3",
                                     div { class: "text-2xl", "..." }
                                     div {
-                                        p {
-                                            class: "text-sm font-semibold text-yellow-800 dark:text-yellow-200",
-                                            "Payment pending or unpaid"
+                                        match &*melt_progress {
+                                            Some(MeltProgress::PaymentPending { amount, .. }) => rsx! {
+                                                p {
+                                                    class: "text-sm font-semibold text-yellow-800 dark:text-yellow-200",
+                                                    "Payment of {amount} sats pending"
+                                                }
+                                                p {
+                                                    class: "text-xs text-yellow-700 dark:text-yellow-300 mt-1",
+                                                    "The mint is still routing this payment. Your funds stay locked and are restored automatically if it fails. You can close this window."
+                                                }
+                                            },
+                                            Some(MeltProgress::Completed { total_paid, .. }) => rsx! {
+                                                p {
+                                                    class: "text-sm font-semibold text-green-800 dark:text-green-200",
+                                                    "Payment settled ({total_paid} sats)"
+                                                }
+                                            },
+                                            Some(MeltProgress::Failed { error }) => rsx! {
+                                                p {
+                                                    class: "text-sm font-semibold text-red-800 dark:text-red-200",
+                                                    "{error}"
+                                                }
+                                            },
+                                            _ => rsx! {
+                                                p {
+                                                    class: "text-sm font-semibold text-yellow-800 dark:text-yellow-200",
+                                                    "Payment pending or unpaid"
+                                                }
+                                            },
                                         }
                                     }
                                 }
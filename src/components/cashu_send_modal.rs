@@ -11,7 +11,8 @@ pub fn CashuSendModal(
     on_close: EventHandler<()>,
 ) -> Element {
     let mut amount = use_signal(|| String::new());
-    let mints = cashu::get_mints();
+    // Most reliable mints first, so the default pick is the least likely to fail
+    let mints = cashu::get_mints_by_reliability();
     let mut selected_mint = use_signal(|| mints.first().cloned().unwrap_or_default());
     let mut is_sending = use_signal(|| false);
     let mut error_message = use_signal(|| Option::<String>::None);
@@ -39,7 +40,7 @@ pub fn CashuSendModal(
 
     // Keep selected_mint in sync with available mints
     use_effect(move || {
-        let current_mints = cashu::get_mints();
+        let current_mints = cashu::get_mints_by_reliability();
         let current_selection = selected_mint.read().clone();
 
         // If no mint is selected, set it to the first available (only if one exists)
@@ -250,7 +251,10 @@ pub fn CashuSendModal(
                                 for mint_url in mints.iter() {
                                     option {
                                         value: mint_url.clone(),
-                                        "{shorten_url(mint_url, 35)}"
+                                        match cashu::get_mint_reliability(mint_url).filter(|r| r.has_enough_data()) {
+                                            Some(r) => rsx! { "{shorten_url(mint_url, 35)} · {(r.success_rate * 100.0).round()}% success" },
+                                            None => rsx! { "{shorten_url(mint_url, 35)}" },
+                                        }
                                     }
                                 }
                             }
@@ -9,6 +9,15 @@ pub fn CashuSetupWizard(
 ) -> Element {
     let mut creating = use_signal(|| false);
     let mut error_msg = use_signal(|| None::<String>);
+    // Import an existing P2PK key instead of generating one
+    let mut import_existing = use_signal(|| false);
+    let mut privkey_input = use_signal(String::new);
+
+    let tab_class = |active: bool| if active {
+        "flex-1 px-4 py-2 text-sm font-medium rounded-md bg-background shadow-sm"
+    } else {
+        "flex-1 px-4 py-2 text-sm font-medium rounded-md text-muted-foreground hover:text-foreground"
+    };
 
     rsx! {
         div {
@@ -52,16 +61,60 @@ pub fn CashuSetupWizard(
                         "Try Again"
                     }
                 } else {
+                    div {
+                        class: "flex gap-1 p-1 mb-6 bg-accent rounded-lg",
+                        button {
+                            class: tab_class(!*import_existing.read()),
+                            onclick: move |_| import_existing.set(false),
+                            "Generate new"
+                        }
+                        button {
+                            class: tab_class(*import_existing.read()),
+                            onclick: move |_| import_existing.set(true),
+                            "Import existing"
+                        }
+                    }
+
+                    if *import_existing.read() {
+                        div {
+                            class: "text-left mb-4",
+                            label {
+                                class: "block text-sm font-medium mb-2",
+                                "Wallet private key (hex)"
+                            }
+                            input {
+                                r#type: "password",
+                                class: "w-full px-3 py-2 bg-background border border-border rounded-lg font-mono text-sm focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                placeholder: "64 hex characters from your previous NIP-60 wallet",
+                                autocomplete: "off",
+                                value: "{privkey_input}",
+                                oninput: move |e| privkey_input.set(e.value()),
+                            }
+                            p {
+                                class: "text-xs text-muted-foreground mt-2",
+                                "This is the wallet's P2PK key, not your Nostr key. Tokens locked to it stay spendable."
+                            }
+                        }
+                    }
+
                     button {
-                        class: "px-6 py-3 bg-blue-500 hover:bg-blue-600 text-white font-semibold rounded-lg transition",
+                        class: "px-6 py-3 bg-blue-500 hover:bg-blue-600 text-white font-semibold rounded-lg transition disabled:opacity-50 disabled:cursor-not-allowed",
+                        disabled: *import_existing.read() && privkey_input.read().trim().is_empty(),
                         onclick: move |_| {
+                            let import_key = import_existing.read().then(|| privkey_input.read().clone());
                             creating.set(true);
                             error_msg.set(None); // Clear any previous error before starting
                             spawn(async move {
-                                match cashu::create_wallet(vec![DEFAULT_MINT_URL.to_string()]).await {
+                                let mints = vec![DEFAULT_MINT_URL.to_string()];
+                                let result = match import_key {
+                                    Some(privkey) => cashu::create_wallet_with_privkey(&privkey, mints).await,
+                                    None => cashu::create_wallet(mints).await,
+                                };
+                                match result {
                                     Ok(_) => {
                                         log::info!("Wallet created successfully");
                                         error_msg.set(None); // Clear error on success
+                                        privkey_input.set(String::new());
                                         creating.set(false);
                                         on_complete.call(());
                                     }
@@ -73,7 +126,7 @@ pub fn CashuSetupWizard(
                                 }
                             });
                         },
-                        "Create Wallet with Default Mint"
+                        if *import_existing.read() { "Import Wallet with Default Mint" } else { "Create Wallet with Default Mint" }
                     }
                     p {
                         class: "text-xs text-muted-foreground mt-4",
@@ -5,7 +5,7 @@ use crate::stores::pending_comments::{
     PendingComment, CommentStatus, add_pending_comment, update_pending_status,
 };
 use crate::components::{MediaUploader, EmojiPicker, GifPicker, MentionAutocomplete};
-use crate::utils::{SignerValidationResult, get_current_user_pubkey};
+use crate::utils::{nip22, SignerValidationResult, get_current_user_pubkey};
 use nostr_sdk::{Event as NostrEvent, EventBuilder, Kind, Timestamp};
 use nostr_sdk::prelude::*;
 use dioxus_core::spawn_forever;
@@ -245,21 +245,10 @@ pub fn CommentComposer(
                 }
             };
 
-            // Build NIP-22 comment using EventBuilder::comment
-            // This automatically creates the proper K/k, E/e, P/p tags
-            // If we have a parent comment, we're replying to a comment (root = original event)
-            // If no parent, we're commenting directly on the event (root = None)
-            let (comment_to, root) = if let Some(parent_ref) = parent.as_ref() {
-                // Replying to a comment: comment_to = parent comment, root = original event
-                (parent_ref, Some(&target_event))
-            } else {
-                // Top-level comment: comment_to = original event, root = None
-                (&target_event, None)
-            };
-
-            // Build comment using EventBuilder::comment with From<&Event> conversion
-            // This automatically extracts id, kind, and author pubkey for proper NIP-22 tags
-            let builder = EventBuilder::comment(content_for_publish, comment_to, root);
+            // Build NIP-22 comment: root scope is always the original event,
+            // parent is the comment being answered (or the event itself)
+            let builder = EventBuilder::new(Kind::Comment, content_for_publish)
+                .tags(nip22::build_nip22_comment_tags(&target_event, parent.as_ref()));
 
             match client.send_event_builder(builder).await {
                 Ok(send_output) => {
@@ -0,0 +1,34 @@
+use dioxus::prelude::*;
+use dioxus_primitives::toast::{consume_toast, ToastOptions};
+use std::time::Duration;
+use crate::stores::cashu;
+
+/// Announces automatic dust sweeps with a toast
+///
+/// Mounted once in the layout so a sweep that finishes after the receive
+/// modal closed is still reported.
+#[component]
+pub fn DustSweepToast() -> Element {
+    let toast = consume_toast();
+
+    use_effect(move || {
+        let Some(result) = cashu::DUST_SWEEP_RESULT.read().clone() else {
+            return;
+        };
+        *cashu::DUST_SWEEP_RESULT.write() = None;
+
+        let proofs = if result.proofs_consolidated == 1 { "proof" } else { "proofs" };
+        toast.success(
+            format!("Swept {} dust {}", result.proofs_consolidated, proofs),
+            ToastOptions::new()
+                .description(format!(
+                    "{} sats consolidated into {} sats, {} sat fee",
+                    result.input_value, result.output_value, result.fee_paid
+                ))
+                .duration(Duration::from_secs(5))
+                .permanent(false),
+        );
+    });
+
+    rsx! {}
+}
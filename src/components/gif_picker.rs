@@ -1,6 +1,7 @@
 use dioxus::prelude::*;
-use crate::stores::gif_store::{GIF_RESULTS, GIF_LOADING, RECENT_GIFS, load_initial_gifs, load_more_gifs, add_recent_gif, search_gifs, GifResultsStoreStoreExt, RecentGifsStoreStoreExt};
+use crate::stores::gif_store::{RECENT_GIFS, GifMetadata, add_recent_gif, search_gifs, trending_gifs, RecentGifsStoreStoreExt};
 use crate::components::gif_upload_modal::GifUploadModal;
+use crate::hooks::use_infinite_scroll::use_infinite_scroll;
 
 #[derive(Props, Clone, PartialEq)]
 pub struct GifPickerProps {
@@ -21,15 +22,86 @@ pub fn GifPicker(props: GifPickerProps) -> Element {
     let mut picker_left = use_signal(|| 0.0);
     let mut picker_right = use_signal(|| 0.0);
 
-    // Read GIF state from global store
-    let gif_results = GIF_RESULTS.read();
-    let gif_loading = GIF_LOADING.read();
+    // Results of the current search (trending when the query is empty)
+    let mut gif_results = use_signal(|| Vec::<GifMetadata>::new());
+    let mut gif_loading = use_signal(|| false);
+    let mut has_more = use_signal(|| false);
+    let mut next_offset = use_signal(|| None::<usize>);
+    let mut gif_error = use_signal(|| None::<String>);
+    // Bumped per search so late responses for an older query are dropped
+    let mut search_seq = use_signal(|| 0u64);
     let recent_gifs = RECENT_GIFS.read();
 
+    // Load the first page for `query`
+    let mut load_first_page = move |query: String| {
+        let seq = search_seq.peek().wrapping_add(1);
+        search_seq.set(seq);
+        gif_loading.set(true);
+        gif_error.set(None);
+
+        spawn(async move {
+            let result = if query.trim().is_empty() {
+                trending_gifs().await.map(|gifs| (gifs, None))
+            } else {
+                search_gifs(query, 0).await.map(|page| (page.gifs, page.next_offset))
+            };
+            if *search_seq.peek() != seq {
+                return;
+            }
+
+            match result {
+                Ok((gifs, offset)) => {
+                    gif_results.set(gifs);
+                    next_offset.set(offset);
+                    has_more.set(offset.is_some());
+                }
+                Err(e) => {
+                    log::error!("Failed to load GIFs: {}", e);
+                    gif_results.set(Vec::new());
+                    has_more.set(false);
+                    gif_error.set(Some(e));
+                }
+            }
+            gif_loading.set(false);
+        });
+    };
+
+    // Append the next page of the current search
+    let mut load_more = move || {
+        let Some(offset) = *next_offset.peek() else {
+            return;
+        };
+        let seq = *search_seq.peek();
+        let query = search_query.peek().clone();
+        gif_loading.set(true);
+
+        spawn(async move {
+            let result = search_gifs(query, offset).await;
+            if *search_seq.peek() != seq {
+                return;
+            }
+
+            match result {
+                Ok(page) => {
+                    gif_results.write().extend(page.gifs);
+                    next_offset.set(page.next_offset);
+                    has_more.set(page.next_offset.is_some());
+                }
+                Err(e) => {
+                    log::error!("Failed to load more GIFs: {}", e);
+                    has_more.set(false);
+                    gif_error.set(Some(e));
+                }
+            }
+            gif_loading.set(false);
+        });
+    };
+
     // Debounced search effect
     use_effect(move || {
         let query = search_query.read().clone();
-        if initialized.read().clone() {
+        // The first open loads trending itself
+        if *initialized.peek() {
             spawn(async move {
                 // Wait 300ms for debouncing
                 #[cfg(target_family = "wasm")]
@@ -41,7 +113,10 @@ pub fn GifPicker(props: GifPickerProps) -> Element {
                     tokio::time::sleep(std::time::Duration::from_millis(300)).await;
                 }
 
-                search_gifs(query).await;
+                // Skip if the user kept typing
+                if *search_query.peek() == query {
+                    load_first_page(query);
+                }
             });
         }
     });
@@ -66,9 +141,7 @@ pub fn GifPicker(props: GifPickerProps) -> Element {
                     // Load GIFs on first open
                     if !current && !*initialized.read() {
                         initialized.set(true);
-                        spawn(async move {
-                            load_initial_gifs().await;
-                        });
+                        load_first_page(String::new());
                     }
 
                     // Calculate position when opening
@@ -231,7 +304,7 @@ pub fn GifPicker(props: GifPickerProps) -> Element {
                             class: "p-4",
 
                             // Loading state
-                            if *gif_loading && gif_results.data().read().is_empty() {
+                            if *gif_loading.read() && gif_results.read().is_empty() {
                                 div {
                                     class: "flex flex-col items-center justify-center py-16 text-gray-500 dark:text-gray-400",
                                     div {
@@ -249,7 +322,14 @@ pub fn GifPicker(props: GifPickerProps) -> Element {
                             }
 
                             // Empty state
-                            if !*gif_loading && gif_results.data().read().is_empty() {
+                            if let Some(err) = gif_error.read().as_ref() {
+                                div {
+                                    class: "mb-3 p-3 rounded-lg bg-red-50 dark:bg-red-900/30 text-sm text-red-700 dark:text-red-300",
+                                    "{err}"
+                                }
+                            }
+
+                            if !*gif_loading.read() && gif_results.read().is_empty() && gif_error.read().is_none() {
                                 div {
                                     class: "flex flex-col items-center justify-center py-16 text-gray-500 dark:text-gray-400",
                                     span {
@@ -268,10 +348,10 @@ pub fn GifPicker(props: GifPickerProps) -> Element {
                             }
 
                             // GIF grid
-                            if !gif_results.data().read().is_empty() {
+                            if !gif_results.read().is_empty() {
                                 div {
                                     class: "grid grid-cols-6 gap-2",
-                                    for (idx, gif) in gif_results.data().read().iter().enumerate() {
+                                    for (idx, gif) in gif_results.read().iter().enumerate() {
                                         {
                                             let gif_url = gif.url.clone();
                                             let gif_url_for_click = gif.url.clone();
@@ -310,46 +390,30 @@ pub fn GifPicker(props: GifPickerProps) -> Element {
                                     }
                                 }
                             }
-                        }
-                    }
 
-                    // Footer with Load More and Upload buttons
-                    if !gif_results.data().read().is_empty() {
-                        div {
-                            class: "p-4 border-t border-gray-200 dark:border-gray-700 bg-gray-50 dark:bg-gray-750 rounded-b-xl",
-                            div {
-                                class: "flex gap-2",
-                                // Load More button
-                                button {
-                                    class: "flex-1 px-4 py-3 bg-gradient-to-r from-blue-500 to-blue-600 hover:from-blue-600 hover:to-blue-700 text-white rounded-lg text-sm font-semibold transition-all disabled:opacity-50 disabled:cursor-not-allowed shadow-sm hover:shadow-md flex items-center justify-center gap-2",
-                                    disabled: *gif_loading,
-                                    onclick: move |_| {
-                                        spawn(async move {
-                                            load_more_gifs().await;
-                                        });
-                                    },
-                                    if *gif_loading {
-                                        span {
-                                            class: "inline-block w-4 h-4 border-2 border-white border-t-transparent rounded-full animate-spin"
-                                        }
-                                        "Loading..."
-                                    } else {
-                                        span { "⬇️" }
-                                        "Load More"
-                                    }
-                                }
-                                // Upload GIF button
-                                button {
-                                    class: "flex-1 px-4 py-3 bg-gradient-to-r from-green-500 to-green-600 hover:from-green-600 hover:to-green-700 text-white rounded-lg text-sm font-semibold transition-all shadow-sm hover:shadow-md flex items-center justify-center gap-2",
-                                    onclick: move |_| {
-                                        show_upload_modal.set(true);
-                                    },
-                                    span { "⬆️" }
-                                    "Upload GIF"
+                            // Next page loads as the sentinel scrolls into view
+                            if *has_more.read() {
+                                GifScrollSentinel {
+                                    has_more,
+                                    loading: gif_loading,
+                                    on_load_more: move |_| load_more(),
                                 }
                             }
                         }
                     }
+
+                    // Footer with Upload button
+                    div {
+                        class: "p-4 border-t border-gray-200 dark:border-gray-700 bg-gray-50 dark:bg-gray-750 rounded-b-xl",
+                        button {
+                            class: "w-full px-4 py-3 bg-gradient-to-r from-green-500 to-green-600 hover:from-green-600 hover:to-green-700 text-white rounded-lg text-sm font-semibold transition-all shadow-sm hover:shadow-md flex items-center justify-center gap-2",
+                            onclick: move |_| {
+                                show_upload_modal.set(true);
+                            },
+                            span { "⬆️" }
+                            "Upload GIF"
+                        }
+                    }
                 }
 
                 // Upload modal (only rendered when picker is open)
@@ -365,3 +429,24 @@ pub fn GifPicker(props: GifPickerProps) -> Element {
         }
     }
 }
+
+/// Infinite scroll sentinel for the GIF grid
+///
+/// A separate component so the observer is created and dropped with the
+/// picker rather than outliving its DOM.
+#[component]
+fn GifScrollSentinel(has_more: Signal<bool>, loading: Signal<bool>, on_load_more: EventHandler<()>) -> Element {
+    let sentinel_id = use_infinite_scroll(move || on_load_more.call(()), has_more, loading);
+
+    rsx! {
+        div {
+            id: "{sentinel_id}",
+            class: "py-4 flex justify-center",
+            if *loading.read() {
+                span {
+                    class: "inline-block w-5 h-5 border-2 border-blue-500 border-t-transparent rounded-full animate-spin"
+                }
+            }
+        }
+    }
+}
@@ -36,6 +36,9 @@ pub fn GifUploadModal(props: GifUploadModalProps) -> Element {
     let mut uploading = use_signal(|| false);
     let mut error = use_signal(|| None::<String>);
     let mut success = use_signal(|| false);
+    // nostr.build file hash of an upload whose GIF event failed to publish
+    let mut orphaned_upload = use_signal(|| None::<String>);
+    let mut deleting = use_signal(|| false);
     // Track upload ID for timer cancellation (prevents race condition)
     let mut current_upload_id = use_signal(|| None::<uuid::Uuid>);
 
@@ -63,6 +66,7 @@ pub fn GifUploadModal(props: GifUploadModalProps) -> Element {
         caption.set(String::new());
         error.set(None);
         success.set(false);
+        orphaned_upload.set(None);
         clear_file_input(&input_id.read());
     };
 
@@ -134,6 +138,7 @@ pub fn GifUploadModal(props: GifUploadModalProps) -> Element {
 
             uploading.set(true);
             error.set(None);
+            orphaned_upload.set(None);
 
             spawn(async move {
                 // Calculate hash for later use
@@ -168,6 +173,7 @@ pub fn GifUploadModal(props: GifUploadModalProps) -> Element {
                 match upload_result {
                     Ok((url, hash, dimensions)) => {
                         log::info!("File uploaded successfully: {}", url);
+                        let uploaded_hash = hash.clone();
 
                         // Now publish the NIP-94 event
                         let dims = dimensions.map(|(w, h)| (w, h));
@@ -229,6 +235,9 @@ pub fn GifUploadModal(props: GifUploadModalProps) -> Element {
                             Err(e) => {
                                 log::error!("Failed to publish GIF event: {}", e);
                                 error.set(Some(format!("Upload succeeded but failed to publish: {}", e)));
+                                if server == UploadServer::NostrBuild {
+                                    orphaned_upload.set(Some(uploaded_hash));
+                                }
                                 uploading.set(false);
                             }
                         }
@@ -467,6 +476,29 @@ pub fn GifUploadModal(props: GifUploadModalProps) -> Element {
                                 "{err}"
                             }
                         }
+
+                        // Offer to remove an upload that didn't make it into a GIF event
+                        if let Some(file_hash) = orphaned_upload.read().clone() {
+                            button {
+                                class: "w-full px-4 py-2 text-sm border border-red-500/30 text-red-700 dark:text-red-300 hover:bg-red-50 dark:hover:bg-red-900/20 rounded-lg transition disabled:opacity-50",
+                                disabled: *deleting.read(),
+                                onclick: move |_| {
+                                    let file_hash = file_hash.clone();
+                                    deleting.set(true);
+                                    spawn(async move {
+                                        match nip96_store::delete_file(nip96_store::NOSTR_BUILD_API_URL, &file_hash).await {
+                                            Ok(()) => {
+                                                orphaned_upload.set(None);
+                                                error.set(None);
+                                            }
+                                            Err(e) => error.set(Some(e)),
+                                        }
+                                        deleting.set(false);
+                                    });
+                                },
+                                if *deleting.read() { "Deleting..." } else { "Delete uploaded file from nostr.build" }
+                            }
+                        }
                     }
                 }
 
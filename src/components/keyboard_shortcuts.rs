@@ -0,0 +1,178 @@
+use dioxus::prelude::*;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use crate::routes::Route;
+use crate::utils::keyboard_nav::{self, Shortcut};
+
+/// Event ID of the note selected with j/k
+pub static SELECTED_FEED_ITEM: GlobalSignal<Option<String>> = Signal::global(|| None);
+
+/// Attribute marking notes j/k can select; its value is the event ID
+pub const FEED_ITEM_ATTR: &str = "data-feed-item";
+
+/// ID of the search box `/` focuses
+pub const SEARCH_INPUT_ID: &str = "global-search-input";
+
+type KeydownCallback = Closure<dyn FnMut(web_sys::KeyboardEvent)>;
+
+/// Removes the keydown listener when the layout unmounts
+#[derive(Clone)]
+struct KeydownListenerGuard {
+    callback: Signal<Option<KeydownCallback>>,
+}
+
+impl Drop for KeydownListenerGuard {
+    fn drop(&mut self) {
+        if let Some(callback) = self.callback.write().take() {
+            if let Some(window) = web_sys::window() {
+                let _ = window.remove_event_listener_with_callback(
+                    "keydown",
+                    callback.as_ref().unchecked_ref()
+                );
+            }
+        }
+    }
+}
+
+/// Global keyboard shortcuts, mounted once in the layout
+///
+/// j/k select the next/previous note on screen, Enter opens it, n opens the
+/// composer and / focuses search. Nothing fires while typing in a field.
+#[component]
+pub fn KeyboardShortcuts() -> Element {
+    let navigator = navigator();
+    let mut keydown_callback = use_signal(|| None::<KeydownCallback>);
+
+    use_hook(move || {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+
+        let callback = Closure::wrap(Box::new(move |event: web_sys::KeyboardEvent| {
+            let has_modifier = event.ctrl_key() || event.meta_key() || event.alt_key();
+            let Some(shortcut) = keyboard_nav::shortcut_for_key(&event.key(), has_modifier) else {
+                return;
+            };
+            if is_typing(&event) {
+                return;
+            }
+
+            match shortcut {
+                Shortcut::NextItem => move_selection(true),
+                Shortcut::PreviousItem => move_selection(false),
+                Shortcut::OpenItem => {
+                    // Enter on a focused link or button keeps its usual meaning
+                    if !is_on_body(&event) {
+                        return;
+                    }
+                    let Some(note_id) = selected_item_on_screen() else {
+                        return;
+                    };
+                    navigator.push(Route::Note { note_id, from_voice: None });
+                }
+                Shortcut::NewNote => {
+                    navigator.push(Route::NoteNew { quote: None });
+                }
+                Shortcut::FocusSearch => {
+                    // Don't type the "/" into the search box
+                    event.prevent_default();
+                    if !focus_search_input() {
+                        navigator.push(Route::Search { q: String::new() });
+                    }
+                }
+            }
+        }) as Box<dyn FnMut(web_sys::KeyboardEvent)>);
+
+        window
+            .add_event_listener_with_callback("keydown", callback.as_ref().unchecked_ref())
+            .ok();
+        keydown_callback.set(Some(callback));
+    });
+
+    use_hook(move || KeydownListenerGuard { callback: keydown_callback });
+
+    rsx! {}
+}
+
+fn is_typing(event: &web_sys::KeyboardEvent) -> bool {
+    let Some(element) = event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::HtmlElement>().ok())
+    else {
+        return false;
+    };
+    let input_type = element.get_attribute("type");
+    keyboard_nav::is_typing_target(&element.tag_name(), input_type.as_deref(), element.is_content_editable())
+}
+
+/// Whether nothing in particular has keyboard focus
+fn is_on_body(event: &web_sys::KeyboardEvent) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+        .is_none_or(|element| matches!(element.tag_name().to_ascii_lowercase().as_str(), "body" | "html" | "article"))
+}
+
+/// Notes on the current page, top to bottom
+fn feed_items() -> Vec<web_sys::Element> {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return Vec::new();
+    };
+    let Ok(nodes) = document.query_selector_all(&format!("[{}]", FEED_ITEM_ATTR)) else {
+        return Vec::new();
+    };
+    (0..nodes.length())
+        .filter_map(|i| nodes.item(i))
+        .filter_map(|node| node.dyn_into::<web_sys::Element>().ok())
+        .collect()
+}
+
+fn item_id(element: &web_sys::Element) -> Option<String> {
+    element.get_attribute(FEED_ITEM_ATTR)
+}
+
+/// The selected note, if it's still on the page
+fn selected_item_on_screen() -> Option<String> {
+    let selected = SELECTED_FEED_ITEM.peek().clone()?;
+    feed_items()
+        .iter()
+        .any(|element| item_id(element).as_deref() == Some(selected.as_str()))
+        .then_some(selected)
+}
+
+fn move_selection(forward: bool) {
+    let items = feed_items();
+    let selected = SELECTED_FEED_ITEM.peek().clone();
+    let current = selected.and_then(|selected| {
+        items
+            .iter()
+            .position(|element| item_id(element).as_deref() == Some(selected.as_str()))
+    });
+    let Some(index) = keyboard_nav::step_index(current, items.len(), forward) else {
+        return;
+    };
+
+    let element = &items[index];
+    *SELECTED_FEED_ITEM.write() = item_id(element);
+
+    let options = web_sys::ScrollIntoViewOptions::new();
+    options.set_block(web_sys::ScrollLogicalPosition::Center);
+    options.set_behavior(web_sys::ScrollBehavior::Smooth);
+    element.scroll_into_view_with_scroll_into_view_options(&options);
+}
+
+/// Focus the search box if one is on screen
+fn focus_search_input() -> bool {
+    let Some(input) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(SEARCH_INPUT_ID))
+        .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok())
+    else {
+        return false;
+    };
+    // Hidden when the right sidebar is collapsed
+    if input.offset_parent().is_none() {
+        return false;
+    }
+    input.focus().is_ok()
+}
@@ -0,0 +1,98 @@
+use dioxus::prelude::*;
+use crate::stores::labels::{self, LabelTarget};
+use crate::stores::nostr_client::HAS_SIGNER;
+
+/// Community NIP-32 labels on a mint or note, as small chips
+///
+/// Labels from people you follow lead and say so. Nothing renders until
+/// someone has labeled the target.
+#[component]
+pub fn LabelBadges(target: LabelTarget, namespace: String) -> Element {
+    let target_for_effect = target.clone();
+    use_effect(use_reactive!(|target_for_effect| {
+        labels::request_labels(target_for_effect);
+    }));
+
+    let summaries = labels::get_label_summary(&target, &namespace);
+    if summaries.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            class: "flex flex-wrap gap-1",
+            for summary in summaries {
+                span {
+                    key: "{summary.value}",
+                    class: if summary.value == "trusted" {
+                        "px-2 py-0.5 text-xs rounded bg-green-500/15 text-green-700 dark:text-green-400"
+                    } else {
+                        "px-2 py-0.5 text-xs rounded bg-red-500/15 text-red-700 dark:text-red-400"
+                    },
+                    title: "{summary.count} label(s), {summary.trusted_count} from people you follow",
+                    "{summary.value} · {summary.count}"
+                    if summary.trusted_count > 0 {
+                        " ({summary.trusted_count} you follow)"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Pick a label from `options` and publish it for `target`
+#[component]
+pub fn LabelPicker(target: LabelTarget, namespace: String, options: Vec<(String, String)>) -> Element {
+    let mut selected = use_signal(String::new);
+    let mut is_saving = use_signal(|| false);
+    let mut status = use_signal(|| None::<String>);
+
+    if !*HAS_SIGNER.read() {
+        return rsx! {};
+    }
+
+    let publish = move |_| {
+        let label = selected.read().clone();
+        if label.is_empty() {
+            return;
+        }
+        let target = target.clone();
+        let namespace = namespace.clone();
+        is_saving.set(true);
+        spawn(async move {
+            match labels::publish_label(&namespace, &label, target).await {
+                Ok(()) => {
+                    selected.set(String::new());
+                    status.set(Some(format!("Labeled as {}", label)));
+                }
+                Err(e) => status.set(Some(e)),
+            }
+            is_saving.set(false);
+        });
+    };
+
+    rsx! {
+        div {
+            class: "flex items-center gap-2 text-xs",
+            onclick: move |e: MouseEvent| e.stop_propagation(),
+            select {
+                class: "px-2 py-1 bg-background border border-border rounded",
+                value: "{selected}",
+                onchange: move |evt| selected.set(evt.value()),
+                option { value: "", "Label…" }
+                for (value, description) in options.iter() {
+                    option { key: "{value}", value: "{value}", "{description}" }
+                }
+            }
+            button {
+                class: "px-2 py-1 rounded border border-border hover:bg-accent disabled:opacity-50",
+                disabled: *is_saving.read() || selected.read().is_empty(),
+                onclick: publish,
+                "Publish"
+            }
+            if let Some(message) = status.read().as_ref() {
+                span { class: "text-muted-foreground", "{message}" }
+            }
+        }
+    }
+}
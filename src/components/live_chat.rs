@@ -1,29 +1,12 @@
 use dioxus::prelude::*;
-use dioxus_core::Task;
-use nostr_sdk::{Event, Filter, Kind, PublicKey, EventBuilder, Tag};
-use nostr::{TagKind};
-use crate::stores::nostr_client::{get_client, fetch_events_aggregated, HAS_SIGNER};
-use crate::stores::profiles;
+use nostr_sdk::Event;
+use crate::stores::nostr_client::HAS_SIGNER;
+use crate::stores::{live_chat, profiles};
 use crate::utils::profile_prefetch;
 use crate::routes::Route;
 use crate::components::{EmojiPicker, RichContent};
-use std::time::Duration;
 use wasm_bindgen::prelude::*;
 
-/// Guard struct that cancels polling task on drop
-#[derive(Clone)]
-struct PollTaskGuard {
-    task: Signal<Option<Task>>,
-}
-
-impl Drop for PollTaskGuard {
-    fn drop(&mut self) {
-        if let Some(task) = self.task.read().as_ref() {
-            task.cancel();
-        }
-    }
-}
-
 #[wasm_bindgen(inline_js = r#"
 export function scrollChatToBottom(elementId) {
     const element = document.getElementById(elementId);
@@ -40,26 +23,34 @@ export function isScrolledNearBottom(elementId, threshold) {
     const clientHeight = element.clientHeight;
     return scrollHeight - scrollTop - clientHeight < threshold;
 }
-
-export function isPageVisible() {
-    return !document.hidden;
-}
 "#)]
 extern "C" {
     fn scrollChatToBottom(element_id: &str);
     fn isScrolledNearBottom(element_id: &str, threshold: f64) -> bool;
-    fn isPageVisible() -> bool;
 }
 
 #[component]
 pub fn LiveChat(
     stream_author_pubkey: String,
     stream_d_tag: String,
+    /// The stream is over: history stays readable but sending is disabled
+    #[props(default = false)]
+    ended: bool,
 ) -> Element {
-    let mut messages = use_signal(|| Vec::<Event>::new());
-    let mut loading = use_signal(|| false);
+    let coordinate = live_chat::stream_coordinate(&stream_author_pubkey, &stream_d_tag);
+    let coordinate_for_drop = coordinate.clone();
+    let coordinate_for_subscribe = coordinate.clone();
+    let mut chat = use_hook(move || live_chat::subscribe_chat(coordinate_for_subscribe));
+
+    use_drop(move || {
+        spawn_forever(async move {
+            live_chat::unsubscribe_chat(&coordinate_for_drop).await;
+        });
+    });
+
     let mut message_input = use_signal(|| String::new());
     let mut sending = use_signal(|| false);
+    let mut send_error = use_signal(|| None::<String>);
     // Make has_signer reactive - read from the store when needed instead of capturing once
     let has_signer = use_memo(move || *HAS_SIGNER.read());
 
@@ -69,110 +60,19 @@ pub fn LiveChat(
         format!("live-chat-messages-{}", timestamp)
     };
 
-    // Create the 'a' tag for this livestream (for send operations only)
-    let a_tag = format!("30311:{}:{}", stream_author_pubkey, stream_d_tag);
-    let a_tag_for_send_keydown = a_tag.clone();
-    let a_tag_for_send_click = a_tag.clone();
+    let coordinate_for_send_keydown = coordinate.clone();
+    let coordinate_for_send_click = coordinate.clone();
 
     // Clone chat_container_id for auto-scroll effect
     let chat_id_for_auto_scroll = chat_container_id.clone();
 
-    // Fetch chat messages - depends on stream props
-    use_effect(use_reactive((&stream_author_pubkey, &stream_d_tag), move |(author, dtag)| {
-        let tag = format!("30311:{}:{}", author, dtag);
-        spawn(async move {
-            loading.set(true);
-
-            // Parse the 'a' tag to create proper filter
-            let parts: Vec<&str> = tag.split(':').collect();
-            if parts.len() == 3 {
-                let _kind_num = parts[0].parse::<u16>().unwrap_or(30311);
-                if let Ok(_pubkey) = PublicKey::parse(parts[1]) {
-                    let _identifier = parts[2];
-
-                    // Fetch Kind 1311 chat messages that reference this livestream
-                    let filter = Filter::new()
-                        .kind(Kind::from(1311))
-                        .custom_tag(
-                            nostr_sdk::SingleLetterTag::lowercase(nostr_sdk::Alphabet::A),
-                            tag.as_str()
-                        )
-                        .limit(200);
-
-                    match fetch_events_aggregated(filter, Duration::from_secs(10)).await {
-                        Ok(events) => {
-                            let mut sorted_messages = events;
-                            sorted_messages.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-                            messages.set(sorted_messages);
-                            log::info!("Loaded {} chat messages", messages.read().len());
-                        }
-                        Err(e) => {
-                            log::error!("Failed to fetch chat messages: {}", e);
-                        }
-                    }
-                }
-            }
-
-            loading.set(false);
-        });
-    }));
-
-    // Auto-refresh messages every 5 seconds with cancellable polling
-    // Track the spawned polling task so we can cancel it when needed
-    let mut poll_task = use_signal(|| None::<Task>);
-
-    use_effect(use_reactive((&stream_author_pubkey, &stream_d_tag), move |(author, dtag)| {
-        // Cancel the previous polling task if it exists
-        // Use peek() to avoid creating reactive dependency on poll_task
-        if let Some(task) = poll_task.peek().as_ref() {
-            task.cancel();
-        }
-
-        let tag = format!("30311:{}:{}", author, dtag);
-        // Start new polling loop and store its handle
-        let new_task = spawn(async move {
-            loop {
-                gloo_timers::future::TimeoutFuture::new(5000).await;
-
-                // Only poll if page is visible (optimization)
-                if !isPageVisible() {
-                    continue;
-                }
-
-                let parts: Vec<&str> = tag.split(':').collect();
-                if parts.len() == 3 {
-                    let _kind_num = parts[0].parse::<u16>().unwrap_or(30311);
-                    if let Ok(_pubkey) = PublicKey::parse(parts[1]) {
-                        let _identifier = parts[2];
-
-                        let filter = Filter::new()
-                            .kind(Kind::from(1311))
-                            .custom_tag(
-                                nostr_sdk::SingleLetterTag::lowercase(nostr_sdk::Alphabet::A),
-                                tag.as_str()
-                            )
-                            .limit(200);
-
-                        if let Ok(events) = fetch_events_aggregated(filter, Duration::from_secs(10)).await {
-                            let mut sorted_messages = events;
-                            sorted_messages.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-                            messages.set(sorted_messages);
-                        }
-                    }
-                }
-            }
-        });
-
-        poll_task.set(Some(new_task));
-    }));
-
     // Track if this is the first load to force scroll to bottom
     let mut is_first_load = use_signal(|| true);
 
     // Auto-scroll to bottom when messages change
     use_effect(move || {
-        // Track messages length reactively
-        let msg_count = messages.read().len();
+        // Track messages reactively
+        let msg_count = chat.read().as_ref().map_or(0, |messages| messages.len());
         let container_id = chat_id_for_auto_scroll.clone();
 
         // Small delay to ensure DOM has updated with new messages
@@ -191,12 +91,9 @@ pub fn LiveChat(
         });
     });
 
-    // Cancel polling task on unmount using use_hook with Drop
-    use_hook(move || PollTaskGuard { task: poll_task });
-
     // Fetch profiles for chat message authors using batch prefetch
     use_effect(move || {
-        let msg_count = messages.read().len();
+        let msg_count = chat.read().as_ref().map_or(0, |messages| messages.len());
 
         spawn(async move {
             if msg_count == 0 {
@@ -204,7 +101,7 @@ pub fn LiveChat(
             }
 
             // Get current messages snapshot (non-reactive)
-            let current_messages = messages.peek().clone();
+            let current_messages = chat.peek().clone().unwrap_or_default();
 
             // Use optimized batch prefetch for all message authors
             profile_prefetch::prefetch_event_authors(&current_messages).await;
@@ -212,53 +109,28 @@ pub fn LiveChat(
     });
 
     // Helper to perform the message send (used by both keyboard and button)
-    let perform_send = move |content: String, tag_clone: String| {
+    let perform_send = move |content: String, coordinate: String| {
         spawn(async move {
-            match get_client() {
-                Some(client) => {
-                    let tag = Tag::custom(TagKind::a(), vec![tag_clone.clone()]);
-                    let builder = EventBuilder::new(Kind::from(1311), content.clone()).tag(tag);
-                    match client.send_event_builder(builder).await {
-                        Ok(event_id) => {
-                            log::info!("Chat message sent: {:?}", event_id);
-                            message_input.set(String::new());
-                            sending.set(false);
-
-                            // Spawn background refetch without blocking the UI
-                            let tag_clone_bg = tag_clone.clone();
-                            spawn(async move {
-                                let parts: Vec<&str> = tag_clone_bg.split(':').collect();
-                                if parts.len() == 3 {
-                                    if let Ok(_pubkey) = PublicKey::parse(parts[1]) {
-                                        let filter = Filter::new()
-                                            .kind(Kind::from(1311))
-                                            .custom_tag(
-                                                nostr_sdk::SingleLetterTag::lowercase(nostr_sdk::Alphabet::A),
-                                                tag_clone_bg.as_str()
-                                            )
-                                            .limit(200);
-                                        if let Ok(events) = fetch_events_aggregated(filter, Duration::from_secs(10)).await {
-                                            let mut sorted_messages = events;
-                                            sorted_messages.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-                                            messages.set(sorted_messages);
-                                        }
-                                    }
-                                }
-                            });
-                        }
-                        Err(e) => {
-                            log::error!("Failed to send chat message: {}", e);
-                        }
-                    }
+            send_error.set(None);
+            match live_chat::send_chat_message(&content, &coordinate).await {
+                Ok(event) => {
+                    log::info!("Chat message sent: {}", event.id);
+                    message_input.set(String::new());
+                    // Show it right away; the relay echo is deduped
+                    let mut chat = chat.write();
+                    live_chat::merge_chat_messages(chat.get_or_insert_with(Vec::new), [event]);
                 }
-                None => {
-                    log::error!("Client not initialized");
+                Err(e) => {
+                    log::error!("{}", e);
+                    send_error.set(Some(e));
                 }
             }
             sending.set(false);
         });
     };
 
+    let can_send = *has_signer.read() && !ended;
+
     rsx! {
         div {
             class: "h-full flex flex-col bg-background border-l border-border",
@@ -276,34 +148,53 @@ pub fn LiveChat(
             div {
                 id: "{chat_container_id}",
                 class: "flex-1 overflow-y-auto p-4 space-y-3 hide-scrollbar",
-                if *loading.read() {
-                    div {
-                        class: "flex items-center justify-center h-full text-muted-foreground",
-                        "Loading messages..."
-                    }
-                } else if messages.read().is_empty() {
-                    div {
-                        class: "flex items-center justify-center h-full text-muted-foreground text-center",
+                match chat.read().as_ref() {
+                    None => rsx! {
                         div {
-                            "No messages yet."
-                            br {}
-                            "Be the first to chat!"
+                            class: "flex items-center justify-center h-full text-muted-foreground",
+                            "Loading messages..."
                         }
-                    }
-                } else {
-                    for message in messages.read().iter() {
-                        ChatMessage {
-                            key: "{message.id}",
-                            event: message.clone()
+                    },
+                    Some(messages) if messages.is_empty() => rsx! {
+                        div {
+                            class: "flex items-center justify-center h-full text-muted-foreground text-center",
+                            if ended {
+                                "No messages."
+                            } else {
+                                div {
+                                    "No messages yet."
+                                    br {}
+                                    "Be the first to chat!"
+                                }
+                            }
                         }
-                    }
+                    },
+                    Some(messages) => rsx! {
+                        for message in messages.iter() {
+                            ChatMessage {
+                                key: "{message.id}",
+                                event: message.clone()
+                            }
+                        }
+                    },
                 }
             }
 
             // Message input
-            if *has_signer.read() {
+            if ended {
+                div {
+                    class: "p-4 border-t border-border text-center text-sm text-muted-foreground",
+                    "This stream has ended. Chat is closed."
+                }
+            } else if *has_signer.read() {
                 div {
                     class: "p-4 border-t border-border",
+                    if let Some(error) = send_error.read().as_ref() {
+                        p {
+                            class: "mb-2 text-xs text-red-500",
+                            "{error}"
+                        }
+                    }
                     div {
                         class: "flex items-center gap-2",
                         // Emoji picker
@@ -325,11 +216,11 @@ pub fn LiveChat(
                                 if e.key() == Key::Enter && !e.modifiers().shift() {
                                     e.prevent_default();
                                     let content = message_input.read().clone();
-                                    if content.trim().is_empty() || *sending.read() || !*has_signer.read() {
+                                    if content.trim().is_empty() || *sending.read() || !can_send {
                                         return;
                                     }
                                     sending.set(true);
-                                    perform_send(content, a_tag_for_send_keydown.clone());
+                                    perform_send(content, coordinate_for_send_keydown.clone());
                                 }
                             }
                         }
@@ -338,11 +229,11 @@ pub fn LiveChat(
                             disabled: *sending.read() || message_input.read().trim().is_empty(),
                             onclick: move |_| {
                                 let content = message_input.read().clone();
-                                if content.trim().is_empty() || *sending.read() || !*has_signer.read() {
+                                if content.trim().is_empty() || *sending.read() || !can_send {
                                     return;
                                 }
                                 sending.set(true);
-                                perform_send(content, a_tag_for_send_click.clone());
+                                perform_send(content, coordinate_for_send_click.clone());
                             },
                             if *sending.read() {
                                 "Sending..."
@@ -1,5 +1,6 @@
 use dioxus::prelude::*;
 use wasm_bindgen::prelude::*;
+use super::live_stream_utils::MediaSessionInfo;
 
 /// Cleanup guard that destroys player on drop
 #[derive(Clone)]
@@ -24,6 +25,12 @@ pub struct LiveStreamPlayerProps {
     /// Auto-play the stream (default: true)
     #[props(default = true)]
     pub autoplay: bool,
+    /// Hide the video and keep only the audio playing
+    #[props(default)]
+    pub audio_only: bool,
+    /// Lock screen / notification metadata for the MediaSession API
+    #[props(default = None)]
+    pub media_session: Option<MediaSessionInfo>,
 }
 
 // Inline JavaScript for Video.js integration
@@ -138,9 +145,59 @@ export async function initVideoJsPlayer(videoId, url, autoplay) {
     return player;
 }
 
+// Switch between video and audio only. The media element keeps playing
+// either way; coming back to video jumps to the live edge.
+export async function setVideoJsAudioOnly(videoId, audioOnly) {
+    const player = window.videojsPlayers.get(videoId);
+    if (!player || player.audioOnlyMode() === audioOnly) {
+        return;
+    }
+
+    await player.audioOnlyMode(audioOnly);
+
+    if (!audioOnly && player.liveTracker && player.liveTracker.isLive()) {
+        player.liveTracker.seekToLiveEdge();
+    }
+    if (player.paused()) {
+        player.play().catch((e) => console.warn('Resume after audio only failed:', e));
+    }
+}
+
+// Lock screen controls and metadata (MediaSession API)
+export function setVideoJsMediaSession(videoId, title, artist, album, artwork) {
+    const player = window.videojsPlayers.get(videoId);
+    if (!player || !('mediaSession' in navigator)) {
+        return;
+    }
+
+    navigator.mediaSession.metadata = new MediaMetadata({
+        title: title,
+        artist: artist,
+        album: album,
+        artwork: artwork ? [{ src: artwork, sizes: '512x512' }] : [],
+    });
+
+    navigator.mediaSession.setActionHandler('play', () => player.play());
+    navigator.mediaSession.setActionHandler('pause', () => player.pause());
+    navigator.mediaSession.setActionHandler('stop', () => player.pause());
+    window.videojsMediaSessionOwner = videoId;
+}
+
+function clearMediaSession(videoId) {
+    if (!('mediaSession' in navigator) || window.videojsMediaSessionOwner !== videoId) {
+        return;
+    }
+    navigator.mediaSession.metadata = null;
+    for (const action of ['play', 'pause', 'stop']) {
+        navigator.mediaSession.setActionHandler(action, null);
+    }
+    window.videojsMediaSessionOwner = null;
+}
+
 // Destroy Video.js player
 export function destroyVideoJsPlayer(videoId) {
     const player = window.videojsPlayers.get(videoId);
+    clearMediaSession(videoId);
 
     if (player) {
         console.log('Destroying Video.js player:', videoId);
@@ -158,6 +215,11 @@ extern "C" {
     async fn initVideoJsPlayer(video_id: &str, url: &str, autoplay: bool) -> Result<JsValue, JsValue>;
 
     fn destroyVideoJsPlayer(video_id: &str);
+
+    #[wasm_bindgen(catch)]
+    async fn setVideoJsAudioOnly(video_id: &str, audio_only: bool) -> Result<JsValue, JsValue>;
+
+    fn setVideoJsMediaSession(video_id: &str, title: &str, artist: &str, album: &str, artwork: &str);
 }
 
 /// LiveStreamPlayer component - Universal video player using Video.js
@@ -168,6 +230,8 @@ pub fn LiveStreamPlayer(props: LiveStreamPlayerProps) -> Element {
     let stream_url = props.stream_url.clone();
     let poster = props.poster.clone();
     let autoplay = props.autoplay;
+    let audio_only = props.audio_only;
+    let media_session = props.media_session.clone();
 
     // Validate stream URL
     let stream_url_for_validation = stream_url.clone();
@@ -231,6 +295,31 @@ pub fn LiveStreamPlayer(props: LiveStreamPlayerProps) -> Element {
         }
     });
 
+    // Audio only mode and lock screen metadata, applied once the player is up
+    let video_id_for_modes = video_id.read().clone();
+    use_effect(use_reactive((&audio_only, &media_session), move |(audio_only, media_session)| {
+        if *loading.read() || error.read().is_some() {
+            return;
+        }
+        let video_id = video_id_for_modes.clone();
+
+        if let Some(info) = &media_session {
+            setVideoJsMediaSession(
+                &video_id,
+                &info.title,
+                &info.artist,
+                &info.album,
+                info.artwork.as_deref().unwrap_or(""),
+            );
+        }
+
+        spawn(async move {
+            if let Err(e) = setVideoJsAudioOnly(&video_id, audio_only).await {
+                log::warn!("Failed to switch audio only mode: {:?}", e);
+            }
+        });
+    }));
+
     // Handle retry
     let handle_retry = move |_| {
         error.set(None);
@@ -272,7 +361,11 @@ pub fn LiveStreamPlayer(props: LiveStreamPlayerProps) -> Element {
 
     rsx! {
         div {
-            class: "relative w-full aspect-video bg-black rounded-lg overflow-hidden",
+            class: if audio_only {
+                "relative w-full bg-black rounded-lg overflow-hidden"
+            } else {
+                "relative w-full aspect-video bg-black rounded-lg overflow-hidden"
+            },
 
             // Video.js video element
             video {
@@ -35,6 +35,39 @@ pub fn parse_nip53_live_event(event: &NostrEvent) -> Option<LiveEvent> {
     }
 }
 
+/// What the OS shows for a playing stream (lock screen, notification shade)
+#[derive(Clone, Debug, PartialEq)]
+pub struct MediaSessionInfo {
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+    pub artwork: Option<String>,
+}
+
+/// MediaSession metadata for a NIP-53 stream: its title, by the host
+///
+/// `host_name` is the host's display name when their profile is loaded.
+pub fn media_session_info(event: &NostrEvent, host_name: Option<&str>) -> Option<MediaSessionInfo> {
+    let live_event = parse_nip53_live_event(event)?;
+
+    let title = live_event.title
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty())
+        .unwrap_or_else(|| "Live stream".to_string());
+    let artist = host_name
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .unwrap_or("Live on Nostr")
+        .to_string();
+
+    Some(MediaSessionInfo {
+        title,
+        artist,
+        album: "nostr.blue Live".to_string(),
+        artwork: live_event.image.map(|(url, _dims)| url.to_string()),
+    })
+}
+
 /// Extract host from live event with case-insensitive fallback.
 ///
 /// The nostr-sdk LiveEventMarker parser is case-sensitive and only matches "Host".
@@ -146,3 +179,42 @@ fn verify_host_proof(
         Err(_) => false,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::{EventBuilder, Keys, Kind, Tag};
+
+    fn stream_event(tags: &[&[&str]]) -> NostrEvent {
+        let tags: Vec<Tag> = tags
+            .iter()
+            .map(|t| Tag::parse(t.iter().map(|s| s.to_string()).collect::<Vec<_>>()).unwrap())
+            .collect();
+        EventBuilder::new(Kind::from(30311), "")
+            .tags(tags)
+            .sign_with_keys(&Keys::generate())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_media_session_info_from_stream() {
+        let event = stream_event(&[
+            &["d", "morning-show"],
+            &["title", " Morning Show "],
+            &["image", "https://example.com/cover.jpg"],
+            &["streaming", "https://example.com/live.m3u8"],
+            &["status", "live"],
+        ]);
+        let info = media_session_info(&event, Some("Alice")).unwrap();
+        assert_eq!(info.title, "Morning Show");
+        assert_eq!(info.artist, "Alice");
+        assert_eq!(info.artwork.as_deref(), Some("https://example.com/cover.jpg"));
+
+        // Untitled stream from a host whose profile isn't loaded
+        let bare = stream_event(&[&["d", "untitled"], &["status", "live"]]);
+        let info = media_session_info(&bare, Some("  ")).unwrap();
+        assert_eq!(info.title, "Live stream");
+        assert_eq!(info.artist, "Live on Nostr");
+        assert_eq!(info.artwork, None);
+    }
+}
@@ -0,0 +1,92 @@
+use dioxus::prelude::*;
+use crate::routes::{Route, article_new::ARTICLE_SEED};
+use crate::utils::note_split::{split_into_thread, DEFAULT_PART_LENGTH, LONG_NOTE_THRESHOLD};
+
+#[derive(Props, Clone, PartialEq)]
+pub struct LongNoteOptionsProps {
+    /// Current composer content
+    pub content: String,
+    /// Whether the note will be published as a thread instead of a single note
+    pub thread_mode: Signal<bool>,
+    #[props(default = false)]
+    pub disabled: bool,
+}
+
+/// Warning and alternatives shown by composers when a note gets unusually long:
+/// split it into a numbered thread (with a preview of the parts) or move it to
+/// the article editor as NIP-23 long-form content.
+#[component]
+pub fn LongNoteOptions(mut props: LongNoteOptionsProps) -> Element {
+    let navigator = navigator();
+    let char_count = props.content.chars().count();
+
+    if char_count <= LONG_NOTE_THRESHOLD {
+        return rsx! {};
+    }
+
+    let is_thread = *props.thread_mode.read();
+    let parts = if is_thread {
+        split_into_thread(&props.content, DEFAULT_PART_LENGTH)
+    } else {
+        Vec::new()
+    };
+    let part_count = parts.len();
+    let content_for_article = props.content.clone();
+
+    rsx! {
+        div {
+            class: "mt-3 p-3 rounded-lg border border-yellow-500/40 bg-yellow-500/10 text-sm",
+
+            p {
+                class: "text-yellow-700 dark:text-yellow-400",
+                "This note is unusually long ({char_count} characters). Many clients truncate long notes."
+            }
+
+            div {
+                class: "mt-2 flex flex-wrap gap-2",
+
+                button {
+                    class: if is_thread {
+                        "px-3 py-1 rounded-full bg-primary text-primary-foreground transition"
+                    } else {
+                        "px-3 py-1 rounded-full border border-border hover:bg-accent transition"
+                    },
+                    disabled: props.disabled,
+                    onclick: move |_| {
+                        let current = *props.thread_mode.read();
+                        props.thread_mode.set(!current);
+                    },
+                    if is_thread { "Post as single note" } else { "Split into thread" }
+                }
+
+                button {
+                    class: "px-3 py-1 rounded-full border border-border hover:bg-accent transition",
+                    disabled: props.disabled,
+                    onclick: move |_| {
+                        *ARTICLE_SEED.write() = Some(content_for_article.clone());
+                        navigator.push(Route::ArticleNew {});
+                    },
+                    "Publish as article"
+                }
+            }
+
+            // Thread preview
+            if is_thread {
+                div {
+                    class: "mt-3 space-y-2",
+                    p {
+                        class: "text-muted-foreground",
+                        "Will be published as {part_count} connected notes:"
+                    }
+                    for (index, part) in parts.into_iter().enumerate() {
+                        div {
+                            key: "{index}",
+                            class: "p-2 rounded border border-border bg-background whitespace-pre-wrap break-words",
+                            "{part}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,21 @@
 use dioxus::prelude::*;
-use crate::utils::markdown::render_markdown;
+#[cfg(target_family = "wasm")]
+use wasm_bindgen::JsCast;
+use crate::utils::markdown::{apply_markdown_format, render_markdown, MarkdownFormat};
+
+/// Id of the editor's textarea, for reading and restoring the selection
+const TEXTAREA_ID: &str = "markdown-editor-textarea";
+
+/// Toolbar buttons: label, tooltip, format
+const TOOLBAR: [(&str, &str, MarkdownFormat); 7] = [
+    ("B", "Bold", MarkdownFormat::Bold),
+    ("I", "Italic", MarkdownFormat::Italic),
+    ("🔗", "Link", MarkdownFormat::Link),
+    ("H", "Heading", MarkdownFormat::Heading),
+    ("•", "List", MarkdownFormat::List),
+    ("❝", "Quote", MarkdownFormat::Quote),
+    ("</>", "Code", MarkdownFormat::Code),
+];
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum EditorMode {
@@ -24,6 +40,14 @@ pub fn MarkdownEditor(mut props: MarkdownEditorProps) -> Element {
     // Render markdown preview
     let html_content = use_memo(move || render_markdown(&props.content.read()));
 
+    let mut apply_format = move |format: MarkdownFormat| {
+        let text = props.content.read().clone();
+        let (start, end) = selection(&text);
+        let edit = apply_markdown_format(&text, start, end, format);
+        props.content.set(edit.text.clone());
+        restore_selection(&edit.text, edit.selection_start, edit.selection_end);
+    };
+
     rsx! {
         div {
             class: "flex flex-col h-full",
@@ -69,6 +93,24 @@ pub fn MarkdownEditor(mut props: MarkdownEditorProps) -> Element {
                 }
             }
 
+            // Formatting toolbar
+            if *mode.read() != EditorMode::Preview {
+                div {
+                    class: "flex gap-1 px-2 py-1 border-b border-border",
+                    for (label, tooltip, format) in TOOLBAR {
+                        button {
+                            key: "{tooltip}",
+                            class: "px-2 py-1 min-w-8 text-sm font-mono rounded hover:bg-accent transition",
+                            title: "{tooltip}",
+                            // Keep the textarea's selection while clicking
+                            onmousedown: move |e| e.prevent_default(),
+                            onclick: move |_| apply_format(format),
+                            "{label}"
+                        }
+                    }
+                }
+            }
+
             // Editor area
             div {
                 class: "flex-1 overflow-hidden",
@@ -77,6 +119,7 @@ pub fn MarkdownEditor(mut props: MarkdownEditorProps) -> Element {
                 match *mode.read() {
                     EditorMode::Edit => rsx! {
                         textarea {
+                            id: TEXTAREA_ID,
                             class: "w-full h-full p-4 bg-background border-0 resize-none focus:outline-none focus:ring-0 font-mono text-sm",
                             placeholder: "{props.placeholder}",
                             value: "{props.content}",
@@ -97,6 +140,7 @@ pub fn MarkdownEditor(mut props: MarkdownEditorProps) -> Element {
                             div {
                                 class: "w-1/2 border-r border-border",
                                 textarea {
+                                    id: TEXTAREA_ID,
                                     class: "w-full h-full p-4 bg-background border-0 resize-none focus:outline-none focus:ring-0 font-mono text-sm",
                                     placeholder: "{props.placeholder}",
                                     value: "{props.content}",
@@ -140,3 +184,50 @@ pub fn MarkdownEditor(mut props: MarkdownEditorProps) -> Element {
         }
     }
 }
+
+/// The textarea's selection as UTF-8 byte offsets into `text`
+///
+/// Falls back to the end of the text when the textarea isn't available.
+fn selection(text: &str) -> (usize, usize) {
+    #[cfg(target_family = "wasm")]
+    {
+        use super::mention_autocomplete::utf16_to_utf8_index;
+
+        if let Some(textarea) = find_textarea() {
+            let start = textarea.selection_start().ok().flatten().unwrap_or(0) as usize;
+            let end = textarea.selection_end().ok().flatten().unwrap_or(0) as usize;
+            return (utf16_to_utf8_index(text, start), utf16_to_utf8_index(text, end));
+        }
+    }
+    (text.len(), text.len())
+}
+
+/// Focus the textarea and select `start..end` (UTF-8 byte offsets into `text`)
+///
+/// Runs after the next render so the new value is in the DOM first.
+#[cfg(target_family = "wasm")]
+fn restore_selection(text: &str, start: usize, end: usize) {
+    use super::mention_autocomplete::utf8_to_utf16_index;
+
+    let start = utf8_to_utf16_index(text, start) as u32;
+    let end = utf8_to_utf16_index(text, end) as u32;
+    spawn(async move {
+        gloo_timers::future::TimeoutFuture::new(0).await;
+        if let Some(textarea) = find_textarea() {
+            let _ = textarea.focus();
+            let _ = textarea.set_selection_range(start, end);
+        }
+    });
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn restore_selection(_text: &str, _start: usize, _end: usize) {}
+
+#[cfg(target_family = "wasm")]
+fn find_textarea() -> Option<web_sys::HtmlTextAreaElement> {
+    web_sys::window()?
+        .document()?
+        .get_element_by_id(TEXTAREA_ID)?
+        .dyn_into::<web_sys::HtmlTextAreaElement>()
+        .ok()
+}
@@ -3,11 +3,15 @@ use dioxus::events::FormData;
 use wasm_bindgen::JsCast;
 use web_sys::HtmlInputElement;
 use crate::stores::blossom_store;
+use crate::utils::file_metadata::UploadedMedia;
 
 #[derive(Props, Clone, PartialEq)]
 pub struct MediaUploaderProps {
     /// Callback when upload completes successfully
     pub on_upload: EventHandler<String>,
+    /// Optional callback with the uploaded file's NIP-94 metadata
+    #[props(default)]
+    pub on_upload_media: Option<EventHandler<UploadedMedia>>,
     /// Optional label for the upload button
     #[props(default = "Upload Media".to_string())]
     pub button_label: String,
@@ -59,16 +63,20 @@ pub fn MediaUploader(props: MediaUploaderProps) -> Element {
         if let Some((_filename, data, mime_type)) = selected_file.read().clone() {
             let quality_val = *quality.read();
             let on_upload = props.on_upload.clone();
+            let on_upload_media = props.on_upload_media;
             let input_id_for_clear = input_id_for_upload.clone();
 
             uploading.set(true);
             error.set(None);
 
             spawn(async move {
-                match blossom_store::upload_image(data, mime_type, quality_val).await {
-                    Ok(url) => {
-                        log::info!("Upload successful: {}", url);
-                        on_upload.call(url);
+                match blossom_store::upload_media(data, mime_type, quality_val).await {
+                    Ok(media) => {
+                        log::info!("Upload successful: {}", media.url);
+                        on_upload.call(media.url.clone());
+                        if let Some(on_upload_media) = on_upload_media {
+                            on_upload_media.call(media);
+                        }
                         selected_file.set(None);
                         uploading.set(false);
                         // Clear the file input value
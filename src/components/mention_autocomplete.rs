@@ -373,7 +373,7 @@ fn insert_mention(
 }
 
 /// Convert UTF-16 code unit index (from DOM) to UTF-8 byte index (for Rust string slicing)
-fn utf16_to_utf8_index(text: &str, utf16_index: usize) -> usize {
+pub(crate) fn utf16_to_utf8_index(text: &str, utf16_index: usize) -> usize {
     let mut utf16_count = 0;
     let mut utf8_byte_index = 0;
 
@@ -390,8 +390,7 @@ fn utf16_to_utf8_index(text: &str, utf16_index: usize) -> usize {
 }
 
 /// Convert UTF-8 byte index (from Rust string) to UTF-16 code unit index (for DOM)
-#[allow(dead_code)]
-fn utf8_to_utf16_index(text: &str, utf8_index: usize) -> usize {
+pub(crate) fn utf8_to_utf16_index(text: &str, utf8_index: usize) -> usize {
     let mut utf16_count = 0;
     let mut utf8_byte_index = 0;
 
@@ -42,6 +42,7 @@ pub mod profile_editor_modal;
 pub mod emoji_picker;
 pub mod reaction_picker;
 pub mod reaction_button;
+pub mod reaction_breakdown;
 pub mod reaction_defaults_modal;
 pub mod gif_picker;
 pub mod mention_autocomplete;
@@ -70,22 +71,36 @@ pub mod cashu_create_request_modal;
 pub mod cashu_pay_request_modal;
 pub mod cashu_terms_modal;
 pub mod cashu_token_card;
+pub mod cashu_pending_events;
+pub mod cashu_keyset_migration;
+pub mod dust_sweep_toast;
 pub mod nwc_setup_modal;
 pub mod report_modal;
 pub mod add_to_list_modal;
 pub mod dvm_selector_modal;
 pub mod gif_upload_modal;
+pub mod long_note_options;
+pub mod activity_digest_card;
+pub mod open_in_app;
+pub mod keyboard_shortcuts;
+pub mod blurhash_image;
+pub mod zap_goal;
+pub mod user_status;
+pub mod labels;
+pub mod profile_badges;
+pub mod note_translation;
+pub mod relay_selector;
 
 // pub use note::NoteDisplay;
 pub use note_card::{NoteCard, NoteCardSkeleton};
 pub use note_composer::NoteComposer;
-pub use rich_content::RichContent;
+pub use rich_content::{RichContent, QuotedEventCard};
 pub use reply_composer::ReplyComposer;
 pub use comment_composer::CommentComposer;
 pub use confirm_modal::ConfirmModal;
 pub use trending_notes::TrendingNotes;
 pub use search_input::SearchInput;
-pub use threaded_comment::ThreadedComment;
+pub use threaded_comment::{ThreadedComment, ThreadSortSelect};
 pub use article_card::{ArticleCard, ArticleCardSkeleton};
 pub use article_content::ArticleContent;
 pub use photo_card::PhotoCard;
@@ -115,6 +130,7 @@ pub use profile_editor_modal::ProfileEditorModal;
 pub use emoji_picker::EmojiPicker;
 pub use reaction_picker::InlineReactionPicker;
 pub use reaction_button::ReactionButton;
+pub use reaction_breakdown::ReactionBreakdown;
 pub use reaction_defaults_modal::ReactionDefaultsModal;
 pub use gif_picker::GifPicker;
 pub use mention_autocomplete::MentionAutocomplete;
@@ -141,10 +157,24 @@ pub use cashu_create_request_modal::CashuCreateRequestModal;
 pub use cashu_pay_request_modal::CashuPayRequestModal;
 pub use cashu_terms_modal::CashuTermsModal;
 pub use cashu_token_card::CashuTokenCard;
+pub use cashu_pending_events::CashuPendingEvents;
+pub use cashu_keyset_migration::CashuKeysetMigration;
+pub use dust_sweep_toast::DustSweepToast;
 pub use nwc_setup_modal::NwcSetupModal;
 pub use report_modal::ReportModal;
 pub use add_to_list_modal::AddToListModal;
 pub use poll_creator_modal::PollCreatorModal;
 pub use dvm_selector_modal::DvmSelectorModal;
+pub use long_note_options::LongNoteOptions;
+pub use activity_digest_card::ActivityDigestCard;
+pub use open_in_app::OpenInApp;
+pub use keyboard_shortcuts::KeyboardShortcuts;
+pub use blurhash_image::BlurhashImage;
+pub use zap_goal::{ZapGoalProgress, ZapGoalCard, LinkedZapGoal, ProfileGoals};
+pub use user_status::{UserStatusLine, UserStatusEditor};
+pub use labels::{LabelBadges, LabelPicker};
+pub use profile_badges::ProfileBadges;
+pub use note_translation::NoteTranslation;
+pub use relay_selector::RelaySelector;
 pub mod dialog;
 pub mod toast;
@@ -1,7 +1,7 @@
 use dioxus::prelude::*;
 use dioxus::web::WebEventExt;
 use crate::routes::Route;
-use crate::stores::music_player::{self, MUSIC_PLAYER};
+use crate::stores::music_player::{self, CrossfadePhase, RepeatMode, MUSIC_PLAYER};
 use crate::components::icons;
 use js_sys::eval;
 use wasm_bindgen::JsCast;
@@ -16,6 +16,67 @@ fn format_time(seconds: f64) -> String {
     format!("{}:{:02}", mins, secs)
 }
 
+/// Secondary audio element the next track fades in on
+const CROSSFADE_AUDIO_ID: &str = "global-music-player-audio-next";
+
+/// JS helper that ramps an audio element's volume over a fade
+///
+/// Fading the element volume rather than routing it through Web Audio keeps
+/// playback working for media hosts that don't send CORS headers: a
+/// cross-origin element connected to an AudioContext without them goes silent.
+const FADE_HELPER_JS: &str = r#"
+    function nbFade(el, from, to, secs) {
+        clearInterval(el.__nbFade);
+        let start = performance.now();
+        el.volume = from;
+        el.__nbFade = setInterval(function() {
+            let t = Math.min((performance.now() - start) / (secs * 1000), 1);
+            el.volume = from + (to - from) * t;
+            if (t >= 1) clearInterval(el.__nbFade);
+        }, 50);
+    }
+    function nbStopFade(el, volume) {
+        clearInterval(el.__nbFade);
+        el.volume = volume;
+    }
+"#;
+
+/// Start fading the main audio out and `media_url` in on the secondary element
+fn start_crossfade(audio_id: &str, media_url: String, fade_secs: f64) {
+    spawn(async move {
+        let audio_id_json = serde_json::to_string(&audio_id).unwrap_or_else(|_| "\"global-music-player-audio\"".to_string());
+        let next_id_json = serde_json::to_string(CROSSFADE_AUDIO_ID).unwrap_or_default();
+        let media_url_json = serde_json::to_string(&media_url).unwrap_or_else(|_| "\"\"".to_string());
+
+        let script = format!(
+            r#"
+            (function() {{
+                {helpers}
+                let main = document.getElementById({audio_id});
+                let next = document.getElementById({next_id});
+                if (!main || !next) return;
+
+                let volume = main.volume;
+                next.src = {media_url};
+                next.currentTime = 0;
+
+                nbFade(main, volume, 0, {fade});
+                nbFade(next, 0, volume, {fade});
+
+                next.play().catch(e => console.log('Crossfade play failed:', e));
+                window.__nbCrossfade = {{ url: {media_url}, nextId: {next_id}, volume: volume }};
+            }})();
+            "#,
+            helpers = FADE_HELPER_JS,
+            audio_id = audio_id_json,
+            next_id = next_id_json,
+            media_url = media_url_json,
+            fade = fade_secs
+        );
+        let _ = eval(&script);
+    });
+}
+
 
 /// Persistent music player that stays at bottom of screen
 #[component]
@@ -30,6 +91,11 @@ pub fn PersistentMusicPlayer() -> Element {
         if let Some(ref track) = state.current_track {
             let media_url = track.media_url.clone();
             let is_playing = state.is_playing;
+            let crossfade_phase = match state.crossfade {
+                CrossfadePhase::Idle => "idle",
+                CrossfadePhase::Fading { .. } => "fading",
+                CrossfadePhase::Handoff => "handoff",
+            };
 
             spawn(async move {
                 // Properly escape strings using JSON serialization to prevent injection
@@ -40,9 +106,38 @@ pub fn PersistentMusicPlayer() -> Element {
                 let script = format!(
                     r#"
                     (function() {{
+                        {helpers}
                         let audio = document.getElementById({audio_id});
                         if (!audio) return;
 
+                        // Finish or cancel a crossfade started on the secondary element
+                        let xf = window.__nbCrossfade;
+                        if (xf && {crossfade_phase} !== "fading") {{
+                            window.__nbCrossfade = null;
+                            let next = document.getElementById(xf.nextId);
+
+                            if ({crossfade_phase} === "handoff" && xf.url === {media_url} && next) {{
+                                // Continue the faded-in track on the main element from where it is
+                                audio.src = {media_url};
+                                audio.load();
+                                audio.addEventListener('loadedmetadata', function() {{
+                                    audio.currentTime = next.currentTime;
+                                }}, {{ once: true }});
+                                audio.addEventListener('playing', function() {{
+                                    nbStopFade(audio, xf.volume);
+                                    next.pause();
+                                    nbStopFade(next, 0);
+                                }}, {{ once: true }});
+                            }} else {{
+                                // Manual skip or pause: drop the fade immediately
+                                if (next) {{
+                                    next.pause();
+                                    nbStopFade(next, 0);
+                                }}
+                                nbStopFade(audio, xf.volume);
+                            }}
+                        }}
+
                         if (audio.src !== {media_url}) {{
                             audio.src = {media_url};
                             audio.load();
@@ -55,9 +150,11 @@ pub fn PersistentMusicPlayer() -> Element {
                         }}
                     }})();
                     "#,
+                    helpers = FADE_HELPER_JS,
                     audio_id = audio_id_json,
                     media_url = media_url_json,
-                    is_playing = is_playing_literal
+                    is_playing = is_playing_literal,
+                    crossfade_phase = serde_json::to_string(crossfade_phase).unwrap_or_default()
                 );
                 let _ = eval(&script);
             });
@@ -78,6 +175,8 @@ pub fn PersistentMusicPlayer() -> Element {
                 (function() {{
                     let audio = document.getElementById({audio_id});
                     if (audio) audio.volume = {volume};
+                    // A running crossfade settles at the new volume
+                    if (window.__nbCrossfade) window.__nbCrossfade.volume = {volume};
                 }})();
                 "#,
                 audio_id = audio_id_json,
@@ -117,13 +216,14 @@ pub fn PersistentMusicPlayer() -> Element {
                     }
                 },
                 onended: move |_| {
-                    music_player::next_track();
+                    music_player::track_ended();
                 }
             }
         };
     }
 
     let track = state.current_track.as_ref().unwrap();
+    let crossfade_enabled = state.crossfade_secs > 0;
 
     let progress = if state.duration > 0.0 {
         (state.current_time / state.duration * 100.0).min(100.0)
@@ -138,12 +238,17 @@ pub fn PersistentMusicPlayer() -> Element {
             preload: "metadata",
             style: "display: none;",
             src: "{track.media_url}",
+            // Repeat-one loops natively, so onended only fires when advancing
+            r#loop: state.repeat == RepeatMode::One,
             ontimeupdate: move |evt| {
                 if let Some(target) = evt.data.as_web_event().target() {
                     if let Some(audio) = target.dyn_ref::<web_sys::HtmlAudioElement>() {
                         let current_time = audio.current_time();
                         if !current_time.is_nan() {
                             music_player::set_current_time(current_time);
+                            if let Some((next_url, fade_secs)) = music_player::begin_crossfade() {
+                                start_crossfade(audio_id, next_url, fade_secs);
+                            }
                         }
                     }
                 }
@@ -159,7 +264,16 @@ pub fn PersistentMusicPlayer() -> Element {
                 }
             },
             onended: move |_| {
-                music_player::next_track();
+                music_player::track_ended();
+            }
+        }
+
+        // Secondary audio element the next track fades in on
+        if crossfade_enabled {
+            audio {
+                id: "{CROSSFADE_AUDIO_ID}",
+                preload: "auto",
+                style: "display: none;",
             }
         }
 
@@ -216,6 +330,18 @@ pub fn PersistentMusicPlayer() -> Element {
                     div {
                         class: "flex items-center gap-1",
 
+                        // Shuffle button
+                        button {
+                            class: if state.shuffle {
+                                "h-8 w-8 p-0 inline-flex items-center justify-center rounded-md text-primary hover:bg-accent transition-colors"
+                            } else {
+                                "h-8 w-8 p-0 inline-flex items-center justify-center rounded-md text-muted-foreground hover:bg-accent hover:text-accent-foreground transition-colors"
+                            },
+                            title: if state.shuffle { "Shuffle on" } else { "Shuffle off" },
+                            onclick: move |_| music_player::toggle_shuffle(),
+                            dangerous_inner_html: icons::SHUFFLE
+                        }
+
                         // Previous button
                         button {
                             class: "h-8 w-8 p-0 inline-flex items-center justify-center rounded-md hover:bg-accent hover:text-accent-foreground transition-colors",
@@ -240,6 +366,26 @@ pub fn PersistentMusicPlayer() -> Element {
                             onclick: move |_| music_player::next_track(),
                             dangerous_inner_html: icons::SKIP_FORWARD
                         }
+
+                        // Repeat button (all -> one -> off)
+                        button {
+                            class: if state.repeat == RepeatMode::Off {
+                                "h-8 w-8 p-0 inline-flex items-center justify-center rounded-md text-muted-foreground hover:bg-accent hover:text-accent-foreground transition-colors"
+                            } else {
+                                "h-8 w-8 p-0 inline-flex items-center justify-center rounded-md text-primary hover:bg-accent transition-colors"
+                            },
+                            title: match state.repeat {
+                                RepeatMode::All => "Repeat all",
+                                RepeatMode::One => "Repeat one",
+                                RepeatMode::Off => "Repeat off",
+                            },
+                            onclick: move |_| music_player::cycle_repeat_mode(),
+                            dangerous_inner_html: if state.repeat == RepeatMode::One {
+                                icons::REPEAT_ONE
+                            } else {
+                                icons::REPEAT
+                            }
+                        }
                     }
 
                     // Progress bar with time stamps
@@ -1,21 +1,29 @@
 use dioxus::prelude::*;
-use nostr_sdk::{Event as NostrEvent, PublicKey, Filter, Kind, ToBech32, Timestamp};
+use nostr_sdk::{Event as NostrEvent, EventId, PublicKey, Filter, Kind, ToBech32, Timestamp};
 use nostr_sdk::nips::nip19::Nip19Event;
 use crate::routes::Route;
 use crate::stores::nostr_client::{self, HAS_SIGNER, get_client, publish_repost, delete_repost};
-use crate::hooks::use_reaction;
+use crate::hooks::{use_reaction, toast_publish_error, OptimisticToggle, ReactionState};
 use crate::stores::bookmarks;
 use crate::stores::signer::SIGNER_INFO;
 use crate::services::aggregation::InteractionCounts;
-use crate::components::{RichContent, ReplyComposer, ZapModal, NoteMenu, ReactionButton, ConfirmModal};
+use crate::components::{RichContent, QuotedEventCard, ReplyComposer, ZapModal, NoteMenu, ReactionButton, ConfirmModal, UserStatusLine, LabelBadges, NoteTranslation};
+use crate::stores::labels::{self, LabelTarget};
+use dioxus_primitives::toast::consume_toast;
+use crate::components::keyboard_shortcuts::SELECTED_FEED_ITEM;
 use crate::components::icons::{MessageCircleIcon, Repeat2Icon, BookmarkIcon, ZapIcon, ShareIcon};
+use crate::utils::event::{is_expired, is_protected};
 use crate::utils::format_sats_compact;
+use crate::utils::repost::strip_quote_mention;
+use crate::utils::reply_tags::thread_root;
 use std::time::Duration;
 
 #[component]
 pub fn NoteCard(
     event: NostrEvent,
     #[props(default = None)] repost_info: Option<(PublicKey, Timestamp)>,
+    /// Quoted event ID and relay hint when this note is a quote repost
+    #[props(default = None)] quote: Option<(EventId, Option<String>)>,
     #[props(default = None)] precomputed_counts: Option<InteractionCounts>,
     #[props(default = true)] collapsible: bool,
 ) -> Element {
@@ -34,7 +42,9 @@ pub fn NoteCard(
     let event_id_counts = event_id.clone();
 
     // State for interactions
-    let mut is_reposting = use_signal(|| false);
+    let mut repost_state = use_signal(ReactionState::default);
+    let toast = consume_toast();
+    let toast_undo = toast.clone();
     let mut is_reposted = use_signal(|| false);
     let mut user_repost_id = use_signal(|| None::<String>);
     let mut show_undo_repost_confirm = use_signal(|| false);
@@ -482,12 +492,41 @@ pub fn NoteCard(
     let nav = use_navigator();
     let event_id_nav = event_id.clone();
 
+    // Selected with j/k; a memo so only the cards gaining or losing the selection re-render
+    let event_id_selected = event_id.clone();
+    let is_selected = use_memo(move || {
+        SELECTED_FEED_ITEM.read().as_deref() == Some(event_id_selected.as_str())
+    });
+
+    // Deleted by the author from this session, or past its NIP-40 expiration;
+    // drop it from the feed
+    if nostr_client::DELETED_EVENTS.read().contains(&event_id) || is_expired(&event) {
+        return rsx! {};
+    }
+
+    // Muted posts and replies in muted threads collapse; unmuting brings them back
+    let thread_root_id = (event.kind == Kind::TextNote)
+        .then(|| thread_root(&event).map(|root| root.id.to_hex()))
+        .flatten();
+    let (post_muted, in_muted_thread) = {
+        let muted_threads = nostr_client::MUTED_THREADS.read();
+        (
+            *is_muted.read() && muted_threads.contains(&event_id),
+            thread_root_id.as_ref().is_some_and(|root| muted_threads.contains(root)),
+        )
+    };
+
     // Check if content should be hidden
-    let is_hidden = (*is_muted.read() || *is_author_blocked.read()) && !*show_hidden_anyway.read();
+    let is_hidden = (post_muted || in_muted_thread || *is_author_blocked.read()) && !*show_hidden_anyway.read();
 
     rsx! {
         article {
-            class: "border-b border-border p-4 hover:bg-accent/50 transition-colors cursor-pointer",
+            class: if *is_selected.read() {
+                "border-b border-border p-4 hover:bg-accent/50 transition-colors cursor-pointer ring-2 ring-inset ring-primary"
+            } else {
+                "border-b border-border p-4 hover:bg-accent/50 transition-colors cursor-pointer"
+            },
+            "data-feed-item": "{event_id}",
             onclick: move |_| {
                 if !is_hidden {
                     nav.push(Route::Note { note_id: event_id_nav.clone(), from_voice: None });
@@ -502,8 +541,10 @@ pub fn NoteCard(
                         class: "flex-1 text-muted-foreground text-sm",
                         if *is_author_blocked.read() {
                             "Post from blocked user"
-                        } else if *is_muted.read() {
+                        } else if post_muted {
                             "Muted post"
+                        } else if in_muted_thread {
+                            "Reply in a muted thread"
                         }
                     }
                     button {
@@ -588,21 +629,54 @@ pub fn NoteCard(
                                 class: "text-muted-foreground text-sm",
                                 "{timestamp}"
                             }
+                            if is_protected(&event) {
+                                span {
+                                    class: "text-muted-foreground text-xs",
+                                    title: "Protected (NIP-70): only the author can publish this note",
+                                    "🔒 protected"
+                                }
+                            }
                         }
                         // Menu button
                         NoteMenu {
                             author_pubkey: author_pubkey.clone(),
-                            event_id: event_id.clone()
+                            event_id: event_id.clone(),
+                            event_kind: event.kind,
+                            thread_root: thread_root_id.clone()
                         }
                     }
+                    UserStatusLine {
+                        pubkey: author_pubkey.clone(),
+                        compact: true
+                    }
+                    LabelBadges {
+                        target: LabelTarget::Event(event.id),
+                        namespace: labels::CONTENT_NAMESPACE.to_string(),
+                    }
 
                     // Post content
                     div {
                         class: "mb-3",
-                        RichContent {
-                            content: content.clone(),
-                            tags: event.tags.iter().cloned().collect(),
-                            collapsible: collapsible
+                        if let Some((quoted_id, relay_hint)) = quote.clone() {
+                            // Quote repost: commentary, then the quoted event as a card
+                            RichContent {
+                                content: strip_quote_mention(&content, &quoted_id),
+                                tags: event.tags.iter().cloned().collect(),
+                                collapsible: collapsible
+                            }
+                            QuotedEventCard {
+                                event_id: quoted_id,
+                                relay_hint: relay_hint
+                            }
+                        } else {
+                            RichContent {
+                                content: content.clone(),
+                                tags: event.tags.iter().cloned().collect(),
+                                collapsible: collapsible
+                            }
+                        }
+                        NoteTranslation {
+                            content: content.clone()
                         }
                     }
 
@@ -643,10 +717,10 @@ pub fn NoteCard(
                             // Repost button (toggles dropdown)
                             button {
                                 class: "{repost_button_class} hover:bg-green-500/10 gap-1 px-2 py-1.5 rounded",
-                                disabled: !has_signer || *is_reposting.read(),
+                                disabled: !has_signer || repost_state.read().pending,
                                 onclick: move |e: MouseEvent| {
                                     e.stop_propagation();
-                                    if has_signer && !*is_reposting.read() {
+                                    if has_signer && !repost_state.read().pending {
                                         show_repost_menu.toggle();
                                     }
                                 },
@@ -694,25 +768,32 @@ pub fn NoteCard(
                                                 // Show confirmation modal for undo
                                                 show_undo_repost_confirm.set(true);
                                             } else {
-                                                // Create new repost
+                                                // Create new repost, shown right away
+                                                if !repost_state.write().try_begin() {
+                                                    return;
+                                                }
+                                                let previous = OptimisticToggle { active: false, count: *repost_count.peek() };
+                                                let shown = previous.switched(true);
+                                                is_reposted.set(shown.active);
+                                                repost_count.set(shown.count);
+
                                                 let event_id_clone = event_id_repost.clone();
                                                 let author_pubkey_clone = author_pubkey_repost.clone();
-
-                                                is_reposting.set(true);
+                                                let toast_api = toast.clone();
 
                                                 spawn(async move {
                                                     match publish_repost(event_id_clone, author_pubkey_clone, None).await {
                                                         Ok(repost_id) => {
                                                             log::info!("Reposted event, repost ID: {}", repost_id);
-                                                            is_reposted.set(true);
                                                             user_repost_id.set(Some(repost_id));
-                                                            let current_count = *repost_count.peek();
-                                                            repost_count.set(current_count + 1);
-                                                            is_reposting.set(false);
+                                                            repost_state.write().finish(Ok(()));
                                                         }
                                                         Err(e) => {
                                                             log::error!("Failed to repost event: {}", e);
-                                                            is_reposting.set(false);
+                                                            toast_publish_error(&toast_api, format!("Failed to repost: {}", e));
+                                                            let settled = repost_state.write().settle(previous, shown, Err(e));
+                                                            is_reposted.set(settled.active);
+                                                            repost_count.set(settled.count);
                                                         }
                                                     }
                                                 });
@@ -902,20 +983,29 @@ pub fn NoteCard(
                 on_confirm: move |_| {
                     show_undo_repost_confirm.set(false);
                     if let Some(repost_id) = user_repost_id.read().clone() {
-                        is_reposting.set(true);
+                        if !repost_state.write().try_begin() {
+                            return;
+                        }
+                        let previous = OptimisticToggle { active: true, count: *repost_count.peek() };
+                        let shown = previous.switched(false);
+                        is_reposted.set(shown.active);
+                        repost_count.set(shown.count);
+                        user_repost_id.set(None);
+                        let toast_api = toast_undo.clone();
+
                         spawn(async move {
-                            match delete_repost(repost_id).await {
+                            match delete_repost(repost_id.clone()).await {
                                 Ok(()) => {
                                     log::info!("Repost deleted successfully");
-                                    is_reposted.set(false);
-                                    let current_count = *repost_count.peek();
-                                    repost_count.set(current_count.saturating_sub(1));
-                                    user_repost_id.set(None);
-                                    is_reposting.set(false);
+                                    repost_state.write().finish(Ok(()));
                                 }
                                 Err(e) => {
                                     log::error!("Failed to delete repost: {}", e);
-                                    is_reposting.set(false);
+                                    toast_publish_error(&toast_api, format!("Failed to undo repost: {}", e));
+                                    let settled = repost_state.write().settle(previous, shown, Err(e));
+                                    is_reposted.set(settled.active);
+                                    repost_count.set(settled.count);
+                                    user_repost_id.set(Some(repost_id));
                                 }
                             }
                         });
@@ -1,6 +1,9 @@
 use dioxus::prelude::*;
-use crate::stores::{nostr_client::publish_note, auth_store};
-use crate::components::{MediaUploader, EmojiPicker, GifPicker, MentionAutocomplete, PollCreatorModal};
+use crate::stores::{nostr_client::{publish_note, publish_note_to, publish_thread}, auth_store};
+use crate::components::{MediaUploader, EmojiPicker, GifPicker, MentionAutocomplete, PollCreatorModal, LongNoteOptions, RichContent, RelaySelector};
+use crate::utils::note_split::{split_into_thread, DEFAULT_PART_LENGTH};
+use crate::utils::text_length::{grapheme_count, exceeds_relay_soft_limit};
+use crate::utils::event::{content_warning_tag, expiration_tag, protected_tag, EXPIRATION_CHOICES};
 use crate::components::icons::{CameraIcon, BarChartIcon};
 
 const MAX_LENGTH: usize = 5000;
@@ -12,15 +15,31 @@ pub fn NoteComposer() -> Element {
     let mut is_focused = use_signal(|| false);
     let mut show_image_uploader = use_signal(|| false);
     let mut show_poll_modal = use_signal(|| false);
+    let mut thread_mode = use_signal(|| false);
+    // NIP-36 content warning with an optional reason
+    let mut has_content_warning = use_signal(|| false);
+    let mut content_warning_reason = use_signal(|| String::new());
+    // Render the note as it will appear before posting
+    let mut show_preview = use_signal(|| false);
+    // NIP-40 expiration, in seconds from posting
+    let mut expire_after = use_signal(|| None::<u64>);
+    // NIP-70: relays should only accept the note from us
+    let mut protected = use_signal(|| false);
+    // Relays picked with "post to"; None publishes everywhere as usual
+    let mut post_to = use_signal(|| None::<Vec<String>>);
 
     // Check if user is authenticated (can publish) using auth_store
     let is_authenticated = use_memo(move || auth_store::AUTH_STATE.read().is_authenticated);
 
-    let char_count = content.read().chars().count();
+    let char_count = grapheme_count(&content.read());
+    let byte_count = content.read().len();
+    let over_relay_soft_limit = exceeds_relay_soft_limit(&content.read());
     let remaining = MAX_LENGTH.saturating_sub(char_count);
-    let is_over_limit = char_count > MAX_LENGTH;
-    let show_warning = remaining < 100 && !is_over_limit;
-    let can_publish = char_count > 0 && !is_over_limit && !*is_publishing.read();
+    // Thread mode splits the note into parts, so the single-note limit doesn't apply
+    let is_over_limit = char_count > MAX_LENGTH && !*thread_mode.read();
+    let show_warning = remaining < 100 && !is_over_limit && !*thread_mode.read();
+    let no_relays_selected = post_to.read().as_ref().is_some_and(|relays| relays.is_empty());
+    let can_publish = char_count > 0 && !is_over_limit && !no_relays_selected && !*is_publishing.read();
 
     // Determine counter color
     let counter_color = if is_over_limit {
@@ -36,18 +55,47 @@ pub fn NoteComposer() -> Element {
     let handle_publish = move |_| {
         let content_value = content.read().clone();
 
-        if content_value.is_empty() || is_over_limit {
+        if content_value.is_empty() || is_over_limit || no_relays_selected {
             return;
         }
 
         is_publishing.set(true);
+        let as_thread = *thread_mode.read();
+        let relays = post_to.read().clone();
+        let mut extra_tags = if *has_content_warning.read() {
+            vec![content_warning_tag(&content_warning_reason.read())]
+        } else {
+            Vec::new()
+        };
+        if let Some(secs) = *expire_after.read() {
+            extra_tags.push(expiration_tag(nostr_sdk::Timestamp::now().as_u64(), secs));
+        }
+        if *protected.read() {
+            extra_tags.push(protected_tag());
+        }
 
         spawn(async move {
-            match publish_note(content_value, Vec::new()).await {
+            let result = if as_thread {
+                let parts = split_into_thread(&content_value, DEFAULT_PART_LENGTH);
+                publish_thread(parts, extra_tags, relays).await.map(|ids| ids.join(", "))
+            } else if let Some(relays) = relays {
+                publish_note_to(content_value, extra_tags, relays).await
+            } else {
+                publish_note(content_value, extra_tags).await
+            };
+
+            match result {
                 Ok(event_id) => {
                     log::info!("Note published successfully: {}", event_id);
                     content.set(String::new());
                     show_image_uploader.set(false);
+                    thread_mode.set(false);
+                    has_content_warning.set(false);
+                    content_warning_reason.set(String::new());
+                    show_preview.set(false);
+                    expire_after.set(None);
+                    protected.set(false);
+                    post_to.set(None);
                     is_publishing.set(false);
                 }
                 Err(e) => {
@@ -61,6 +109,13 @@ pub fn NoteComposer() -> Element {
     let handle_cancel = move |_| {
         content.set(String::new());
         show_image_uploader.set(false);
+        thread_mode.set(false);
+        has_content_warning.set(false);
+        content_warning_reason.set(String::new());
+        show_preview.set(false);
+        expire_after.set(None);
+        protected.set(false);
+        post_to.set(None);
         is_focused.set(false);
     };
 
@@ -155,6 +210,21 @@ pub fn NoteComposer() -> Element {
                             cursor_position: cursor_position
                         }
 
+                        // Preview of how mentions, hashtags and links will render
+                        if *show_preview.read() && char_count > 0 {
+                            div {
+                                class: "mt-3 p-3 border border-border rounded-lg bg-accent/20",
+                                div {
+                                    class: "text-xs font-medium text-muted-foreground mb-2",
+                                    "Preview"
+                                }
+                                RichContent {
+                                    content: content.read().clone(),
+                                    tags: Vec::new()
+                                }
+                            }
+                        }
+
                         // Media uploader (conditionally shown)
                         if *show_image_uploader.read() {
                             div {
@@ -166,6 +236,28 @@ pub fn NoteComposer() -> Element {
                             }
                         }
 
+                        // Content warning reason (shown while the warning is on)
+                        if *has_content_warning.read() {
+                            div {
+                                class: "mt-3",
+                                input {
+                                    r#type: "text",
+                                    class: "w-full px-3 py-2 text-sm bg-background border border-border rounded-lg focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                    placeholder: "Reason for the content warning (optional)",
+                                    value: "{content_warning_reason}",
+                                    disabled: *is_publishing.read(),
+                                    oninput: move |e| content_warning_reason.set(e.value())
+                                }
+                            }
+                        }
+
+                        // Long note warning with thread/article alternatives
+                        LongNoteOptions {
+                            content: content.read().clone(),
+                            thread_mode: thread_mode,
+                            disabled: *is_publishing.read()
+                        }
+
                         // Actions (only show when focused or has content)
                         if *is_focused.read() || char_count > 0 {
                             div {
@@ -212,14 +304,93 @@ pub fn NoteComposer() -> Element {
                                         BarChartIcon { class: "w-5 h-5".to_string() }
                                     }
 
+                                    // Content warning toggle
+                                    button {
+                                        class: if *has_content_warning.read() {
+                                            "p-2 rounded-full bg-primary text-primary-foreground transition"
+                                        } else {
+                                            "p-2 rounded-full hover:bg-accent transition"
+                                        },
+                                        title: "Add content warning",
+                                        onclick: move |_| {
+                                            let current = *has_content_warning.read();
+                                            has_content_warning.set(!current);
+                                        },
+                                        disabled: *is_publishing.read(),
+                                        "⚠️"
+                                    }
+
+                                    // NIP-40 expiration
+                                    select {
+                                        class: if expire_after.read().is_some() {
+                                            "px-2 py-1 text-xs font-medium rounded-full bg-primary text-primary-foreground transition"
+                                        } else {
+                                            "px-2 py-1 text-xs font-medium rounded-full bg-transparent hover:bg-accent transition"
+                                        },
+                                        title: "Expire after",
+                                        disabled: *is_publishing.read(),
+                                        value: expire_after.read().map(|secs| secs.to_string()).unwrap_or_default(),
+                                        onchange: move |e| expire_after.set(e.value().parse::<u64>().ok()),
+                                        option { value: "", "⏳ Never expires" }
+                                        for (secs, label) in EXPIRATION_CHOICES.iter() {
+                                            option { value: "{secs}", "⏳ {label}" }
+                                        }
+                                    }
+
+                                    // NIP-70 protected note
+                                    button {
+                                        class: if *protected.read() {
+                                            "p-2 rounded-full bg-primary text-primary-foreground transition"
+                                        } else {
+                                            "p-2 rounded-full hover:bg-accent transition"
+                                        },
+                                        title: "Protected: relays only accept this note from you, others can't rebroadcast it",
+                                        onclick: move |_| protected.toggle(),
+                                        disabled: *is_publishing.read(),
+                                        "🔒"
+                                    }
+
+                                    // Preview toggle
+                                    button {
+                                        class: if *show_preview.read() {
+                                            "px-2 py-1 text-xs font-medium rounded-full bg-primary text-primary-foreground transition"
+                                        } else {
+                                            "px-2 py-1 text-xs font-medium rounded-full hover:bg-accent transition"
+                                        },
+                                        title: "Preview how the note will look",
+                                        onclick: move |_| show_preview.toggle(),
+                                        disabled: char_count == 0,
+                                        if *show_preview.read() { "Edit" } else { "Preview" }
+                                    }
+
+                                    // Relays to post to
+                                    RelaySelector {
+                                        selection: post_to,
+                                        disabled: *is_publishing.read()
+                                    }
+
                                     // Character counter
                                     div {
                                         class: "text-sm {counter_color} ml-2",
                                         if is_over_limit {
                                             span { "Over limit by {char_count - MAX_LENGTH}" }
+                                        } else if *thread_mode.read() {
+                                            span { "{char_count} characters (thread)" }
                                         } else {
                                             span { "{char_count} / {MAX_LENGTH}" }
                                         }
+                                        if over_relay_soft_limit {
+                                            span {
+                                                class: "ml-2 text-yellow-500",
+                                                title: "Some relays reject notes this large",
+                                                "· {byte_count} bytes, may be too large for some relays"
+                                            }
+                                        } else if byte_count > char_count {
+                                            span {
+                                                class: "ml-2 text-gray-400",
+                                                "· {byte_count} bytes"
+                                            }
+                                        }
                                     }
                                 }
 
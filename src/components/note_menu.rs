@@ -1,7 +1,10 @@
 use dioxus::prelude::*;
 use crate::components::icons::MoreHorizontalIcon;
-use crate::components::{ReportModal, AddToListModal};
+use crate::components::{ReportModal, AddToListModal, ConfirmModal};
 use crate::stores::nostr_client::{self, HAS_SIGNER};
+use crate::stores::{auth_store, contacts, relay_metadata};
+use crate::utils::event::request_deletion;
+use crate::utils::nip19::encode_nevent;
 use nostr_sdk::prelude::*;
 use nostr_sdk::nips::nip19::ToBech32;
 use dioxus_primitives::toast::{consume_toast, ToastOptions};
@@ -13,6 +16,15 @@ pub struct NoteMenuProps {
     pub author_pubkey: String,
     /// Event ID of the note
     pub event_id: String,
+    /// Kind of the note, tagged on deletion requests
+    #[props(default = Kind::TextNote)]
+    pub event_kind: Kind,
+    /// `d` identifier when the note is addressable (e.g. an article)
+    #[props(default)]
+    pub identifier: Option<String>,
+    /// Root of the thread the note replies in; None when it starts one
+    #[props(default)]
+    pub thread_root: Option<String>,
 }
 
 #[component]
@@ -23,6 +35,7 @@ pub fn NoteMenu(props: NoteMenuProps) -> Element {
     let mut is_updating_follow = use_signal(|| false);
     let mut show_report_modal = use_signal(|| false);
     let mut show_add_to_list_modal = use_signal(|| false);
+    let mut show_delete_confirm = use_signal(|| false);
 
     // Get toast API at component level
     let toast = consume_toast();
@@ -36,25 +49,35 @@ pub fn NoteMenu(props: NoteMenuProps) -> Element {
     let event_id = props.event_id.clone();
     let event_id_list = event_id.clone();
     let event_id_mute = event_id.clone();
+    let thread_id = props.thread_root.clone().unwrap_or_else(|| event_id.clone());
+    let thread_muted = nostr_client::MUTED_THREADS.read().contains(&thread_id);
+    let toast_thread = toast.clone();
     let event_id_report = event_id.clone();
     let event_id_modal_report = event_id.clone();
     let event_id_modal_list = event_id.clone();
     let event_id_copy = event_id.clone();
+    let event_id_share = event_id.clone();
+    let author_pubkey_share = author_pubkey.clone();
+    let toast_share = toast.clone();
+    let toast_delete = toast.clone();
+
+    // Only the author can ask for their own note to be deleted
+    let is_own_note = auth_store::get_pubkey().as_deref() == Some(props.author_pubkey.as_str());
+    let event_id_delete = event_id.clone();
+    let event_kind = props.event_kind;
+    let coordinate = props.identifier.as_ref().and_then(|identifier| {
+        PublicKey::from_hex(&props.author_pubkey)
+            .ok()
+            .map(|pubkey| Coordinate::new(props.event_kind, pubkey).identifier(identifier))
+    });
 
     // Check follow status on mount
     use_effect(use_reactive(&author_pubkey_follow_check, move |pubkey| {
-        spawn(async move {
-            match nostr_client::is_following(pubkey).await {
-                Ok(following) => {
-                    is_following.set(following);
-                    is_loading_follow_state.set(false);
-                }
-                Err(e) => {
-                    log::warn!("Failed to check follow status: {}", e);
-                    is_loading_follow_state.set(false);
-                }
-            }
-        });
+        contacts::ensure_contact_list();
+        if contacts::CONTACT_LIST.read().is_some() {
+            is_following.set(contacts::is_following(&pubkey));
+            is_loading_follow_state.set(false);
+        }
     }));
 
     rsx! {
@@ -110,9 +133,9 @@ pub fn NoteMenu(props: NoteMenuProps) -> Element {
 
                             spawn(async move {
                                 let result = if currently_following {
-                                    nostr_client::unfollow_user(pubkey.clone()).await
+                                    contacts::unfollow(&pubkey).await
                                 } else {
-                                    nostr_client::follow_user(pubkey.clone()).await
+                                    contacts::follow(&pubkey, None, None).await
                                 };
 
                                 match result {
@@ -204,6 +227,47 @@ pub fn NoteMenu(props: NoteMenuProps) -> Element {
                         }
                     }
 
+                    // Copy shareable nevent with relay hints
+                    button {
+                        class: "w-full text-left px-4 py-2 hover:bg-accent transition-colors flex items-center gap-2",
+                        onclick: move |e: MouseEvent| {
+                            e.stop_propagation();
+                            is_open.set(false);
+
+                            let Ok(event_id) = EventId::from_hex(&event_id_share) else {
+                                return;
+                            };
+                            let author = PublicKey::from_hex(&author_pubkey_share).ok();
+                            let toast_api = toast_share.clone();
+
+                            spawn(async move {
+                                // Relays the note was actually seen on, else where the user writes
+                                let seen_relays = nostr_client::event_seen_relays(&event_id).await;
+                                let relays = relay_metadata::share_relay_hints(&seen_relays);
+
+                                match encode_nevent(event_id, &relays, author) {
+                                    Ok(nevent) => {
+                                        if let Some(window) = web_sys::window() {
+                                            let _ = window.navigator().clipboard().write_text(&nevent);
+                                            toast_api.success(
+                                                "Copied!".to_string(),
+                                                ToastOptions::new()
+                                                    .description("Shareable nevent copied to clipboard")
+                                                    .duration(Duration::from_secs(2))
+                                                    .permanent(false),
+                                            );
+                                        }
+                                    }
+                                    Err(e) => log::warn!("{}", e),
+                                }
+                            });
+                        },
+                        span {
+                            class: "text-sm",
+                            "Copy as nevent"
+                        }
+                    }
+
                     // Divider
                     div {
                         class: "h-px bg-border my-1"
@@ -231,6 +295,46 @@ pub fn NoteMenu(props: NoteMenuProps) -> Element {
                         }
                     }
 
+                    // Mute or unmute the whole thread (replies stop notifying)
+                    if props.event_kind == Kind::TextNote {
+                        button {
+                            class: "w-full text-left px-4 py-2 hover:bg-accent transition-colors flex items-center gap-2 text-muted-foreground",
+                            onclick: move |e: MouseEvent| {
+                                e.stop_propagation();
+                                is_open.set(false);
+
+                                let thread_id = thread_id.clone();
+                                let toast = toast_thread.clone();
+                                spawn(async move {
+                                    let result = if thread_muted {
+                                        nostr_client::unmute_post(thread_id).await
+                                    } else {
+                                        nostr_client::mute_post(thread_id).await
+                                    };
+                                    match result {
+                                        Ok(_) => {
+                                            let title = if thread_muted { "Thread unmuted" } else { "Thread muted" };
+                                            toast.success(
+                                                title.to_string(),
+                                                ToastOptions::new().duration(Duration::from_secs(3)).permanent(false),
+                                            );
+                                        }
+                                        Err(e) => {
+                                            toast.error(
+                                                "Couldn't update muted threads".to_string(),
+                                                ToastOptions::new().description(e).duration(Duration::from_secs(5)).permanent(false),
+                                            );
+                                        }
+                                    }
+                                });
+                            },
+                            span {
+                                class: "text-sm",
+                                if thread_muted { "Unmute thread" } else { "Mute thread" }
+                            }
+                        }
+                    }
+
                     // Block user
                     button {
                         class: "w-full text-left px-4 py-2 hover:bg-accent transition-colors flex items-center gap-2 text-muted-foreground",
@@ -253,6 +357,23 @@ pub fn NoteMenu(props: NoteMenuProps) -> Element {
                         }
                     }
 
+                    // Delete own post
+                    if is_own_note {
+                        button {
+                            class: "w-full text-left px-4 py-2 hover:bg-accent transition-colors flex items-center gap-2 text-red-500 hover:text-red-600",
+                            disabled: !*HAS_SIGNER.read(),
+                            onclick: move |e: MouseEvent| {
+                                e.stop_propagation();
+                                show_delete_confirm.set(true);
+                                is_open.set(false);
+                            },
+                            span {
+                                class: "text-sm",
+                                "Delete post"
+                            }
+                        }
+                    }
+
                     // Report post
                     button {
                         class: "w-full text-left px-4 py-2 hover:bg-accent transition-colors flex items-center gap-2 text-red-500 hover:text-red-600",
@@ -282,6 +403,35 @@ pub fn NoteMenu(props: NoteMenuProps) -> Element {
             }
         }
 
+        // Delete confirmation
+        if *show_delete_confirm.read() {
+            ConfirmModal {
+                title: "Delete post?".to_string(),
+                message: "This asks relays to delete the post. Some relays or clients may still keep a copy.".to_string(),
+                confirm_text: Some("Delete".to_string()),
+                cancel_text: Some("Cancel".to_string()),
+                on_cancel: move |_| show_delete_confirm.set(false),
+                on_confirm: move |_| {
+                    show_delete_confirm.set(false);
+                    let event_id = event_id_delete.clone();
+                    let coordinate = coordinate.clone();
+                    let toast_api = toast_delete.clone();
+                    spawn(async move {
+                        if let Err(e) = request_deletion(&event_id, event_kind, coordinate, "").await {
+                            log::error!("Failed to delete post: {}", e);
+                            toast_api.error(
+                                "Delete failed".to_string(),
+                                ToastOptions::new()
+                                    .description(e)
+                                    .duration(Duration::from_secs(4))
+                                    .permanent(false),
+                            );
+                        }
+                    });
+                }
+            }
+        }
+
         // Add to List Modal
         if *show_add_to_list_modal.read() {
             AddToListModal {
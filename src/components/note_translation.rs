@@ -0,0 +1,99 @@
+use dioxus::prelude::*;
+use crate::stores::{dvm_store, nostr_client, settings_store};
+
+/// What the translate action is showing
+#[derive(Clone, Debug, PartialEq)]
+enum TranslationState {
+    Idle,
+    Waiting,
+    Done { language: String, text: String },
+    Failed(String),
+}
+
+/// "Translate" action under a note, showing the DVM's translation inline
+///
+/// The target language comes from settings. Hidden without a signer, since
+/// job requests have to be signed.
+#[component]
+pub fn NoteTranslation(content: String) -> Element {
+    let mut state = use_signal(|| TranslationState::Idle);
+
+    if !*nostr_client::HAS_SIGNER.read() || content.trim().is_empty() {
+        return rsx! {};
+    }
+
+    let language = settings_store::SETTINGS.read().translation_language.clone();
+    let language_name = dvm_store::translation_language_name(&language).to_string();
+
+    let translate = move |e: MouseEvent| {
+        e.stop_propagation();
+        if *state.read() == TranslationState::Waiting {
+            return;
+        }
+        state.set(TranslationState::Waiting);
+
+        let content = content.clone();
+        let language = language.clone();
+        spawn(async move {
+            match dvm_store::request_translation(&content, &language).await {
+                Ok(text) => state.set(TranslationState::Done { language, text }),
+                Err(e) => state.set(TranslationState::Failed(e)),
+            }
+        });
+    };
+
+    rsx! {
+        div {
+            class: "mt-1 text-sm",
+            {match state.read().clone() {
+                TranslationState::Idle => rsx! {
+                    button {
+                        class: "text-blue-500 hover:underline",
+                        onclick: translate,
+                        "Translate to {language_name}"
+                    }
+                },
+                TranslationState::Waiting => rsx! {
+                    div {
+                        class: "flex items-center gap-2 text-muted-foreground",
+                        span { class: "inline-block animate-spin", "🔄" }
+                        "Translating to {language_name}..."
+                    }
+                },
+                TranslationState::Done { language, text } => rsx! {
+                    div {
+                        class: "mt-1 p-3 bg-muted rounded-lg",
+                        onclick: move |e: MouseEvent| e.stop_propagation(),
+                        div {
+                            class: "flex items-center justify-between mb-1 text-xs text-muted-foreground",
+                            span { "Translated to {dvm_store::translation_language_name(&language)}" }
+                            button {
+                                class: "hover:underline",
+                                onclick: move |e: MouseEvent| {
+                                    e.stop_propagation();
+                                    state.set(TranslationState::Idle);
+                                },
+                                "Show original only"
+                            }
+                        }
+                        p {
+                            class: "whitespace-pre-wrap break-words",
+                            "{text}"
+                        }
+                    }
+                },
+                TranslationState::Failed(error) => rsx! {
+                    div {
+                        class: "flex items-center gap-2 text-muted-foreground",
+                        span { "{error}" }
+                        button {
+                            class: "text-blue-500 hover:underline",
+                            onclick: translate,
+                            "Try again"
+                        }
+                    }
+                },
+            }}
+        }
+    }
+}
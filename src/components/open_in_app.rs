@@ -0,0 +1,70 @@
+use dioxus::prelude::*;
+use nostr_sdk::Event as NostrEvent;
+use crate::stores::app_handlers::{self, HandlerLink};
+
+/// "Open in <client>" links for events of kinds the app doesn't display well
+///
+/// Lists the web clients that announce support for the kind (NIP-89). Renders
+/// nothing for natively supported kinds or when no client handles the kind.
+#[component]
+pub fn OpenInApp(event: NostrEvent) -> Element {
+    let mut links = use_signal(Vec::<HandlerLink>::new);
+    let supported = app_handlers::is_natively_supported(event.kind);
+
+    use_effect(use_reactive!(|event| {
+        links.set(Vec::new());
+        if app_handlers::is_natively_supported(event.kind) {
+            return;
+        }
+
+        spawn(async move {
+            let (entity, bech32) = match app_handlers::event_entity(&event) {
+                Ok(entity) => entity,
+                Err(e) => {
+                    log::warn!("Can't link event to other apps: {}", e);
+                    return;
+                }
+            };
+            match app_handlers::fetch_handlers(event.kind).await {
+                Ok(handlers) => links.set(app_handlers::handler_links(&handlers, entity, &bech32)),
+                Err(e) => log::warn!("Failed to fetch handlers for kind {}: {}", event.kind, e),
+            }
+        });
+    }));
+
+    if supported || links.read().is_empty() {
+        return rsx! {};
+    }
+
+    let kind = event.kind.as_u16();
+
+    rsx! {
+        div {
+            class: "mx-4 my-3 p-3 rounded-lg border border-border bg-muted/40",
+            p {
+                class: "text-sm text-muted-foreground mb-2",
+                "This is a kind {kind} event, which other apps can display better:"
+            }
+            div {
+                class: "flex flex-wrap gap-2",
+                for link in links.read().iter() {
+                    a {
+                        key: "{link.url}",
+                        href: "{link.url}",
+                        target: "_blank",
+                        rel: "noopener noreferrer",
+                        class: "inline-flex items-center gap-2 px-3 py-1.5 rounded-full border border-border bg-background text-sm hover:bg-accent transition",
+                        if let Some(picture) = &link.picture {
+                            img {
+                                src: "{picture}",
+                                alt: "",
+                                class: "w-5 h-5 rounded-full object-cover",
+                            }
+                        }
+                        "Open in {link.name}"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,12 +1,12 @@
 use dioxus::prelude::*;
-use nostr_sdk::{Event, PublicKey, Filter, Kind, FromBech32};
+use nostr_sdk::{Event, PublicKey, Filter, Kind, FromBech32, Tag};
 use crate::routes::Route;
-use crate::stores::nostr_client::{publish_note, publish_repost, get_client, HAS_SIGNER};
+use crate::stores::nostr_client::{self, publish_note, publish_repost, get_client, HAS_SIGNER};
 use crate::hooks::use_reaction;
 use crate::stores::bookmarks;
 use crate::stores::signer::SIGNER_INFO;
 use crate::components::icons::{MessageCircleIcon, Repeat2Icon, BookmarkIcon, ZapIcon};
-use crate::components::{ZapModal, ReactionButton};
+use crate::components::{ZapModal, ReactionButton, BlurhashImage};
 use crate::utils::format_sats_compact;
 use std::time::Duration;
 
@@ -20,9 +20,14 @@ pub struct ImageMeta {
 
 /// Parse imeta tags from NIP-68 picture events
 pub fn parse_imeta_tags(event: &Event) -> Vec<ImageMeta> {
+    parse_imeta(event.tags.iter())
+}
+
+/// Parse NIP-92 imeta tags from any list of tags
+pub fn parse_imeta<'a>(tags: impl IntoIterator<Item = &'a Tag>) -> Vec<ImageMeta> {
     let mut images = Vec::new();
 
-    for tag in event.tags.iter() {
+    for tag in tags {
         let tag_vec = tag.clone().to_vec();
         if tag_vec.first().map(|s| s.as_str()) == Some("imeta") {
             let mut image = ImageMeta {
@@ -82,16 +87,14 @@ pub fn PhotoCard(event: Event) -> Element {
     let author_pubkey = event.pubkey.to_string();
     let author_pubkey_for_fetch = author_pubkey.clone();
     let author_pubkey_like = author_pubkey.clone();
-    let author_pubkey_comment = author_pubkey.clone();
-    let author_pubkey_comment_btn = author_pubkey.clone();
+    let event_for_comment = event.clone();
+    let event_for_comment_btn = event.clone();
     let created_at = event.created_at;
     let event_id = event.id.to_string();
     let event_id_like = event_id.clone();
     let event_id_bookmark = event_id.clone();
     let event_id_memo = event_id.clone();
     let event_id_counts = event_id.clone();
-    let event_id_comment = event_id.clone();
-    let event_id_comment_btn = event_id.clone();
     let event_id_link = event_id.clone();
     let event_id_repost = event_id.clone();
     let author_pubkey_repost = author_pubkey.clone();
@@ -346,6 +349,8 @@ pub fn PhotoCard(event: Event) -> Element {
     let picture_url = author_metadata.read().as_ref()
         .and_then(|m| m.picture.clone());
 
+    let current_image = images[*current_image_index.read()].clone();
+
     // Navigation to photo detail page
     let nav = use_navigator();
     let event_id_nav = event_id.clone();
@@ -396,11 +401,14 @@ pub fn PhotoCard(event: Event) -> Element {
             // Image display
             div {
                 class: "relative bg-black",
-                img {
-                    class: "w-full max-h-[600px] object-contain",
-                    src: "{images[*current_image_index.read()].url}",
-                    alt: "{images[*current_image_index.read()].alt.as_deref().unwrap_or(\"Photo\")}",
-                    loading: "lazy"
+                BlurhashImage {
+                    key: "{current_image.url}",
+                    url: current_image.url.clone(),
+                    blurhash: current_image.blurhash.clone(),
+                    dim: current_image.dim,
+                    alt: current_image.alt.clone().unwrap_or_else(|| "Photo".to_string()),
+                    class: "w-full max-h-[600px] flex justify-center",
+                    img_class: "w-full max-h-[600px] object-contain",
                 }
 
                 // Multiple images carousel indicators
@@ -699,18 +707,14 @@ pub fn PhotoCard(event: Event) -> Element {
                         onkeydown: move |evt| {
                             if evt.key() == Key::Enter && !comment_text.read().is_empty() && !*is_posting_comment.read() {
                                 let text = comment_text.read().clone();
-                                let event_id_clone = event_id_comment.clone();
-                                let author_clone = author_pubkey_comment.clone();
+                                let parent = event_for_comment.clone();
 
                                 is_posting_comment.set(true);
                                 comment_text.set(String::new());
 
                                 spawn(async move {
-                                    // Create reply tags (e tag for event, p tag for author)
-                                    let tags = vec![
-                                        vec!["e".to_string(), event_id_clone],
-                                        vec!["p".to_string(), author_clone],
-                                    ];
+                                    // NIP-10 reply tags with relay hints
+                                    let tags = nostr_client::reply_tags(&parent).await;
 
                                     match publish_note(text, tags).await {
                                         Ok(_) => {
@@ -737,18 +741,14 @@ pub fn PhotoCard(event: Event) -> Element {
                                 }
 
                                 let text = comment_text.read().clone();
-                                let event_id_clone = event_id_comment_btn.clone();
-                                let author_clone = author_pubkey_comment_btn.clone();
+                                let parent = event_for_comment_btn.clone();
 
                                 is_posting_comment.set(true);
                                 comment_text.set(String::new());
 
                                 spawn(async move {
-                                    // Create reply tags (e tag for event, p tag for author)
-                                    let tags = vec![
-                                        vec!["e".to_string(), event_id_clone],
-                                        vec!["p".to_string(), author_clone],
-                                    ];
+                                    // NIP-10 reply tags with relay hints
+                                    let tags = nostr_client::reply_tags(&parent).await;
 
                                     match publish_note(text, tags).await {
                                         Ok(_) => {
@@ -1,17 +1,24 @@
 use dioxus::prelude::*;
 use nostr_sdk::{
-    Event as NostrEvent, EventId, Filter, Kind, Timestamp, PublicKey,
+    Event as NostrEvent, Timestamp, PublicKey,
     nips::nip88::{Poll, PollResponse, PollType},
-    TagStandard,
 };
 use crate::routes::Route;
 use crate::stores::nostr_client;
+use crate::stores::polls::{fetch_poll_votes, PollTally};
 use crate::components::PollTimer;
 use std::collections::HashMap;
 use std::time::Duration;
 
+/// Poll with voting UI and results
+///
+/// `live_results` (from `polls::subscribe_poll_results`) replaces the results
+/// fetched on load once it has data, so new votes show up without a refresh.
 #[component]
-pub fn PollCard(event: NostrEvent) -> Element {
+pub fn PollCard(
+    event: NostrEvent,
+    #[props(default)] live_results: Option<Signal<HashMap<String, usize>>>,
+) -> Element {
     // Clone values for closures
     let author_pubkey = event.pubkey.to_string();
     let author_pubkey_for_metadata = author_pubkey.clone();
@@ -91,13 +98,23 @@ pub fn PollCard(event: NostrEvent) -> Element {
 
     // Calculate poll results
     let results = use_memo(move || {
+        if let Some(live) = live_results {
+            let live = live.read();
+            if !live.is_empty() {
+                return live.clone();
+            }
+        }
+
         let poll = match poll_data.read().clone() {
             Some(p) => p,
             None => return HashMap::new(),
         };
 
-        let vote_events = votes.read().clone();
-        calculate_poll_results(&poll, vote_events)
+        let mut tally = PollTally::from_poll(&poll);
+        for vote in votes.read().iter() {
+            tally.add_vote(vote);
+        }
+        tally.counts()
     });
 
     // Submit vote with optimistic UI update
@@ -334,112 +351,6 @@ pub fn PollCard(event: NostrEvent) -> Element {
     }
 }
 
-// Helper function to fetch poll votes
-// NIP-88: Votes should be fetched from the relays specified in the poll
-async fn fetch_poll_votes(
-    poll_id: EventId,
-    ends_at: Option<Timestamp>,
-    poll_relays: Vec<nostr_sdk::RelayUrl>,
-) -> Result<Vec<NostrEvent>, String> {
-    let client = nostr_client::get_client().ok_or("Client not initialized")?;
-
-    let mut filter = Filter::new()
-        .kind(Kind::PollResponse)
-        .event(poll_id);
-
-    if let Some(until) = ends_at {
-        filter = filter.until(until);
-    }
-
-    // If poll specifies relays, fetch from those relays specifically
-    // Otherwise fall back to user's default relays
-    let events = if !poll_relays.is_empty() {
-        // Track which relays we actually add (to clean up later)
-        let mut added_relays = Vec::new();
-        for relay_url in &poll_relays {
-            // add_relay returns Ok if added, Err if already present or failed
-            if client.add_relay(relay_url.as_str()).await.is_ok() {
-                added_relays.push(relay_url.clone());
-            }
-        }
-
-        // Ensure relays are connected before fetching
-        nostr_client::ensure_relays_ready(&client).await;
-
-        // Fetch from poll-specified relays
-        let relay_urls: Vec<nostr_sdk::Url> = poll_relays.iter()
-            .filter_map(|r| nostr_sdk::Url::parse(r.as_str()).ok())
-            .collect();
-
-        let result = if !relay_urls.is_empty() {
-            client
-                .fetch_events_from(relay_urls, filter.clone(), Duration::from_secs(10))
-                .await
-                .map_err(|e| format!("Failed to fetch votes from poll relays: {}", e))
-        } else {
-            // Fallback if URL parsing failed
-            client
-                .fetch_events(filter, Duration::from_secs(10))
-                .await
-                .map_err(|e| format!("Failed to fetch votes: {}", e))
-        };
-
-        // Cleanup: remove only the relays we added
-        for relay_url in added_relays {
-            if let Err(e) = client.remove_relay(relay_url.as_str()).await {
-                log::debug!("Could not remove poll relay {}: {}", relay_url, e);
-            }
-        }
-
-        result?
-    } else {
-        // No poll relays specified, use default relays
-        // Ensure default relays are ready before fetching
-        nostr_client::ensure_relays_ready(&client).await;
-        client
-            .fetch_events(filter, Duration::from_secs(10))
-            .await
-            .map_err(|e| format!("Failed to fetch votes: {}", e))?
-    };
-
-    Ok(deduplicate_votes(events.into_iter().collect()))
-}
-
-// Deduplicate votes: one vote per pubkey, latest timestamp wins
-fn deduplicate_votes(events: Vec<NostrEvent>) -> Vec<NostrEvent> {
-    let mut map: HashMap<String, NostrEvent> = HashMap::new();
-
-    for event in events {
-        let key = event.pubkey.to_string();
-        if !map.contains_key(&key) || event.created_at > map[&key].created_at {
-            map.insert(key, event);
-        }
-    }
-
-    map.into_values().collect()
-}
-
-// Calculate poll results: option_id -> vote count
-fn calculate_poll_results(poll: &Poll, vote_events: Vec<NostrEvent>) -> HashMap<String, usize> {
-    let mut counts: HashMap<String, usize> = HashMap::new();
-
-    // Initialize all options with 0
-    for option in &poll.options {
-        counts.insert(option.id.clone(), 0);
-    }
-
-    // Count votes
-    for vote_event in vote_events {
-        for tag in vote_event.tags.iter() {
-            if let Some(TagStandard::PollResponse(option_id)) = tag.as_standardized() {
-                *counts.entry(option_id.clone()).or_insert(0) += 1;
-            }
-        }
-    }
-
-    counts
-}
-
 // Format timestamp as relative time
 fn format_time_ago(timestamp: Timestamp) -> String {
     let now = Timestamp::now();
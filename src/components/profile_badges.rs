@@ -0,0 +1,50 @@
+use dioxus::prelude::*;
+use crate::stores::profiles::{self, ProfileBadge};
+
+/// NIP-58 badges the user accepted, as a row of icons
+///
+/// Renders nothing until badges load, or when there are none.
+#[component]
+pub fn ProfileBadges(pubkey: String) -> Element {
+    let mut badges = use_signal(Vec::<ProfileBadge>::new);
+
+    use_effect(use_reactive!(|pubkey| {
+        badges.set(Vec::new());
+        spawn(async move {
+            match profiles::fetch_profile_badges(pubkey).await {
+                Ok(fetched) => badges.set(fetched),
+                Err(e) => log::warn!("Failed to load badges: {}", e),
+            }
+        });
+    }));
+
+    if badges.read().is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            class: "flex flex-wrap items-center gap-2 mt-3",
+            for badge in badges.read().iter().cloned() {
+                div {
+                    key: "{badge.address}",
+                    class: "w-9 h-9 rounded-full overflow-hidden bg-accent flex items-center justify-center",
+                    title: match &badge.description {
+                        Some(description) => format!("{}: {}", badge.name, description),
+                        None => badge.name.clone(),
+                    },
+                    if let Some(src) = badge.thumb.clone().or(badge.image.clone()) {
+                        img {
+                            src: "{src}",
+                            alt: "{badge.name}",
+                            class: "w-full h-full object-cover",
+                            loading: "lazy",
+                        }
+                    } else {
+                        span { class: "text-lg", "🏅" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use dioxus::prelude::*;
 use crate::stores::{nostr_client, profiles, auth_store};
 use crate::components::MediaUploader;
+use crate::stores::profiles::ProfileFields;
 use nostr_sdk::Metadata;
 
 #[derive(Props, Clone, PartialEq)]
@@ -20,6 +21,11 @@ pub fn ProfileEditorModal(mut props: ProfileEditorModalProps) -> Element {
     let mut website = use_signal(|| String::new());
     let mut nip05 = use_signal(|| String::new());
     let mut lud16 = use_signal(|| String::new());
+    let mut pronouns = use_signal(|| String::new());
+    let mut birthday = use_signal(|| String::new());
+    let mut bot = use_signal(|| false);
+    // Kind 0 being edited, so fields the form doesn't show survive a save
+    let mut loaded_metadata = use_signal(|| None::<Metadata>);
 
     let mut saving = use_signal(|| false);
     let mut error = use_signal(|| None::<String>);
@@ -30,22 +36,33 @@ pub fn ProfileEditorModal(mut props: ProfileEditorModalProps) -> Element {
     // Load current profile when modal opens
     use_effect(use_reactive(&*props.show.read(), move |is_shown| {
         if is_shown {
+            loaded_metadata.set(None);
             spawn(async move {
                 if let Some(pubkey) = auth_store::get_pubkey() {
-                    // Fetch profile from cache or relays
-                    match profiles::fetch_profile(pubkey.clone()).await {
-                        Ok(profile) => {
-                            name.set(profile.name.unwrap_or_default());
-                            display_name.set(profile.display_name.unwrap_or_default());
-                            about.set(profile.about.unwrap_or_default());
-                            picture.set(profile.picture.unwrap_or_default());
-                            banner.set(profile.banner.unwrap_or_default());
-                            website.set(profile.website.unwrap_or_default());
-                            nip05.set(profile.nip05.unwrap_or_default());
-                            lud16.set(profile.lud16.unwrap_or_default());
+                    // Fetch the full metadata from relays
+                    match profiles::fetch_metadata_for_edit(&pubkey).await {
+                        Ok(Some(metadata)) => {
+                            let fields = ProfileFields::from_metadata(&metadata);
+                            name.set(fields.name);
+                            display_name.set(fields.display_name);
+                            about.set(fields.about);
+                            picture.set(fields.picture);
+                            banner.set(fields.banner);
+                            website.set(fields.website);
+                            nip05.set(fields.nip05);
+                            lud16.set(fields.lud16);
+                            pronouns.set(fields.pronouns);
+                            birthday.set(fields.birthday);
+                            bot.set(fields.bot);
+                            loaded_metadata.set(Some(metadata));
+                        }
+                        // Never published one: saving creates it
+                        Ok(None) => {
+                            loaded_metadata.set(Some(Metadata::new()));
                         }
                         Err(e) => {
                             log::error!("Failed to load profile for editing: {}", e);
+                            error.set(Some(format!("Couldn't load your profile: {}", e)));
                         }
                     }
                 }
@@ -60,27 +77,31 @@ pub fn ProfileEditorModal(mut props: ProfileEditorModalProps) -> Element {
         success.set(false);
 
         spawn(async move {
-            let mut metadata = Metadata::new()
-                .name(name.read().clone())
-                .display_name(display_name.read().clone())
-                .about(about.read().clone())
-                .nip05(nip05.read().clone())
-                .lud16(lud16.read().clone());
-
-            // Only add URLs if they're valid
-            if let Ok(url) = nostr_sdk::Url::parse(&picture.read().clone()) {
-                metadata = metadata.picture(url);
-            }
-            if let Ok(url) = nostr_sdk::Url::parse(&banner.read().clone()) {
-                metadata = metadata.banner(url);
-            }
-            if let Ok(url) = nostr_sdk::Url::parse(&website.read().clone()) {
-                metadata = metadata.website(url);
-            }
-
-            match nostr_client::publish_metadata(metadata).await {
+            let Some(current) = loaded_metadata.read().clone() else {
+                error.set(Some("Your profile hasn't loaded yet".to_string()));
+                saving.set(false);
+                return;
+            };
+
+            let fields = ProfileFields {
+                name: name.read().clone(),
+                display_name: display_name.read().clone(),
+                about: about.read().clone(),
+                picture: picture.read().clone(),
+                banner: banner.read().clone(),
+                website: website.read().clone(),
+                nip05: nip05.read().clone(),
+                lud16: lud16.read().clone(),
+                bot: *bot.read(),
+                birthday: birthday.read().clone(),
+                pronouns: pronouns.read().clone(),
+            };
+            let metadata = fields.apply(current);
+
+            match nostr_client::publish_metadata(metadata.clone()).await {
                 Ok(_) => {
                     log::info!("Profile updated successfully");
+                    loaded_metadata.set(Some(metadata));
                     success.set(true);
 
                     // Close modal after a short delay
@@ -328,6 +349,57 @@ pub fn ProfileEditorModal(mut props: ProfileEditorModalProps) -> Element {
                         }
                     }
 
+                    // Pronouns and birthday (NIP-24)
+                    div {
+                        class: "grid grid-cols-1 sm:grid-cols-2 gap-4",
+                        div {
+                            label {
+                                class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2",
+                                "Pronouns"
+                            }
+                            input {
+                                class: "w-full px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700 text-gray-900 dark:text-white focus:ring-2 focus:ring-blue-500 focus:border-transparent",
+                                r#type: "text",
+                                placeholder: "they/them",
+                                value: "{pronouns}",
+                                oninput: move |evt| pronouns.set(evt.value())
+                            }
+                        }
+                        div {
+                            label {
+                                class: "block text-sm font-medium text-gray-700 dark:text-gray-300 mb-2",
+                                "Birthday"
+                            }
+                            input {
+                                class: "w-full px-4 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700 text-gray-900 dark:text-white focus:ring-2 focus:ring-blue-500 focus:border-transparent",
+                                r#type: "date",
+                                value: "{birthday}",
+                                oninput: move |evt| birthday.set(evt.value())
+                            }
+                        }
+                    }
+
+                    // Bot account (NIP-24)
+                    label {
+                        class: "flex items-center gap-3 cursor-pointer",
+                        input {
+                            r#type: "checkbox",
+                            class: "w-4 h-4",
+                            checked: *bot.read(),
+                            onchange: move |evt| bot.set(evt.checked())
+                        }
+                        div {
+                            div {
+                                class: "text-sm font-medium text-gray-700 dark:text-gray-300",
+                                "Automated account"
+                            }
+                            p {
+                                class: "text-xs text-gray-500 dark:text-gray-400",
+                                "Marks this profile as a bot"
+                            }
+                        }
+                    }
+
                     // Error message
                     if let Some(err) = error.read().as_ref() {
                         div {
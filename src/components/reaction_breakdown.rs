@@ -0,0 +1,138 @@
+//! Popover listing who reacted to a note with which emoji
+
+use dioxus::prelude::*;
+use nostr_sdk::{EventId, PublicKey};
+use std::collections::{HashMap, HashSet};
+use crate::components::icons::HeartIcon;
+use crate::hooks::ReactionEmoji;
+use crate::stores::profiles::{self, Profile};
+use crate::stores::reactions_store::{self, ReactionGroup};
+
+/// Reactor avatars shown per emoji
+const AVATARS_PER_EMOJI: usize = 5;
+
+/// `like_count` is the count shown on the reaction button; it's updated to
+/// the breakdown's total so the two never disagree
+#[component]
+pub fn ReactionBreakdown(event_id: String, mut like_count: Signal<usize>) -> Element {
+    let mut groups = use_signal(|| None::<Result<Vec<ReactionGroup>, String>>);
+    let mut reactors = use_signal(HashMap::<PublicKey, Profile>::new);
+
+    use_effect(use_reactive!(|event_id| {
+        groups.set(None);
+        let Ok(id) = EventId::from_hex(&event_id) else {
+            groups.set(Some(Err("Invalid event ID".to_string())));
+            return;
+        };
+
+        spawn(async move {
+            let result = reactions_store::get_reaction_breakdown(id).await;
+            if let Ok(list) = &result {
+                like_count.set(reactions_store::positive_total(list));
+            }
+            let shown: HashSet<PublicKey> = result
+                .iter()
+                .flatten()
+                .flat_map(|group| group.pubkeys.iter().take(AVATARS_PER_EMOJI).copied())
+                .collect();
+            groups.set(Some(result));
+
+            if let Ok(found) = profiles::fetch_profiles_batch_native(shown).await {
+                reactors.set(found);
+            }
+        });
+    }));
+
+    let content = match &*groups.read() {
+        None => rsx! {
+            p { class: "px-3 py-2 text-sm text-muted-foreground", "Loading reactions..." }
+        },
+        Some(Err(e)) => rsx! {
+            p { class: "px-3 py-2 text-sm text-muted-foreground", "Couldn't load reactions: {e}" }
+        },
+        Some(Ok(list)) if list.is_empty() => rsx! {
+            p { class: "px-3 py-2 text-sm text-muted-foreground", "No reactions yet" }
+        },
+        Some(Ok(list)) => rsx! {
+            ul {
+                class: "py-1 max-h-72 overflow-y-auto",
+                for group in list.iter() {
+                    li {
+                        key: "{group.emoji.content()}",
+                        class: "flex items-center gap-3 px-3 py-1.5",
+                        {reaction_label(&group.emoji)}
+                        span { class: "text-sm font-medium w-8", "{group.count}" }
+                        div {
+                            class: "flex -space-x-2",
+                            for pubkey in group.pubkeys.iter().take(AVATARS_PER_EMOJI) {
+                                img {
+                                    key: "{pubkey}",
+                                    class: "w-6 h-6 rounded-full object-cover border-2 border-background bg-muted",
+                                    src: "{reactor_avatar(&reactors.read(), pubkey)}",
+                                    title: "{reactor_name(&reactors.read(), pubkey)}",
+                                    alt: "",
+                                    loading: "lazy",
+                                }
+                            }
+                            if group.count > AVATARS_PER_EMOJI {
+                                span {
+                                    class: "pl-3 text-xs text-muted-foreground self-center",
+                                    "+{group.count - AVATARS_PER_EMOJI}"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        },
+    };
+
+    rsx! {
+        div {
+            class: "min-w-48 max-w-xs bg-background border border-border rounded-lg shadow-lg",
+            onclick: move |e: MouseEvent| e.stop_propagation(),
+            {content}
+        }
+    }
+}
+
+fn reaction_label(emoji: &ReactionEmoji) -> Element {
+    match emoji {
+        ReactionEmoji::Like => rsx! {
+            span {
+                class: "w-6 flex justify-center text-red-500",
+                title: "Like",
+                HeartIcon { class: "w-5 h-5".to_string(), filled: true }
+            }
+        },
+        ReactionEmoji::Unlike => rsx! {
+            span { class: "w-6 text-center text-lg", title: "Dislike", "👎" }
+        },
+        ReactionEmoji::Custom { shortcode, url } => rsx! {
+            img {
+                class: "w-6 h-6 object-contain",
+                src: "{url}",
+                alt: ":{shortcode}:",
+                title: ":{shortcode}:",
+                loading: "lazy",
+            }
+        },
+        ReactionEmoji::Standard(emoji) => rsx! {
+            span { class: "w-6 text-center text-lg", "{emoji}" }
+        },
+    }
+}
+
+fn reactor_avatar(found: &HashMap<PublicKey, Profile>, pubkey: &PublicKey) -> String {
+    match found.get(pubkey) {
+        Some(profile) => profile.get_avatar_url(),
+        None => format!("https://api.dicebear.com/7.x/identicon/svg?seed={}", pubkey.to_hex()),
+    }
+}
+
+fn reactor_name(found: &HashMap<PublicKey, Profile>, pubkey: &PublicKey) -> String {
+    match found.get(pubkey) {
+        Some(profile) => profile.get_display_name(),
+        None => crate::utils::truncate_pubkey(&pubkey.to_hex()),
+    }
+}
@@ -2,9 +2,10 @@
 //! Encapsulates the like button, reaction picker, and click-outside-to-close behavior
 
 use dioxus::prelude::*;
-use crate::hooks::{UseReaction, ReactionState, ReactionEmoji, format_count};
+use crate::hooks::{UseReaction, ReactionEmoji, format_count};
 use crate::components::InlineReactionPicker;
 use crate::components::ReactionDefaultsModal;
+use crate::components::ReactionBreakdown;
 use crate::components::icons::HeartIcon;
 use crate::stores::reactions_store::get_default_reaction;
 
@@ -30,6 +31,9 @@ pub fn ReactionButton(props: ReactionButtonProps) -> Element {
     let mut show_picker = use_signal(|| false);
     let mut show_defaults_modal = use_signal(|| false);
     let mut custom_emoji_failed = use_signal(|| false);
+    // Who reacted with what: shown on hover, or by tapping the count
+    let mut show_breakdown = use_signal(|| false);
+    let mut hovering = use_signal(|| false);
 
     // Reset custom emoji failed state when reaction changes
     let user_reaction_for_effect = props.reaction.user_reaction.clone();
@@ -45,7 +49,7 @@ pub fn ReactionButton(props: ReactionButtonProps) -> Element {
 
     let is_liked = *props.reaction.is_liked.read();
     let like_count = *props.reaction.like_count.read();
-    let is_pending = matches!(*props.reaction.state.read(), ReactionState::Pending);
+    let is_pending = props.reaction.state.read().pending;
     let user_reaction = props.reaction.user_reaction.read().clone();
 
     let base_class = if is_liked {
@@ -63,9 +67,28 @@ pub fn ReactionButton(props: ReactionButtonProps) -> Element {
     // Determine what to display based on user's reaction
     let icon_class = props.icon_class.clone();
 
+    let count_color = if is_liked { "text-red-500" } else { "text-muted-foreground hover:text-foreground" };
+    let event_id = props.reaction.event_id.clone();
+
     rsx! {
         div {
-            class: "relative",
+            class: "relative flex items-center",
+            onmouseenter: move |_| {
+                hovering.set(true);
+                if like_count == 0 {
+                    return;
+                }
+                spawn(async move {
+                    gloo_timers::future::TimeoutFuture::new(600).await;
+                    if *hovering.peek() && !*show_picker.peek() {
+                        show_breakdown.set(true);
+                    }
+                });
+            },
+            onmouseleave: move |_| {
+                hovering.set(false);
+                show_breakdown.set(false);
+            },
 
             // Like button - click for quick like, right-click for reaction picker
             button {
@@ -94,6 +117,7 @@ pub fn ReactionButton(props: ReactionButtonProps) -> Element {
                     e.prevent_default();
                     e.stop_propagation();
                     if props.has_signer {
+                        show_breakdown.set(false);
                         let current = *show_picker.peek();
                         if !current {
                             // Calculate viewport-aware position when opening
@@ -169,10 +193,26 @@ pub fn ReactionButton(props: ReactionButtonProps) -> Element {
                         }
                     }
                 }
-                if like_count > 0 {
-                    span {
-                        class: "{props.count_class}",
-                        { format_count(like_count) }
+            }
+            if like_count > 0 {
+                button {
+                    class: "{props.count_class} {count_color} pr-1 hover:underline transition",
+                    aria_label: "Show reactions",
+                    onclick: move |e: MouseEvent| {
+                        e.stop_propagation();
+                        let current = *show_breakdown.peek();
+                        show_breakdown.set(!current);
+                    },
+                    { format_count(like_count) }
+                }
+            }
+
+            if *show_breakdown.read() && !*show_picker.read() {
+                div {
+                    class: "absolute bottom-full left-0 pb-2 z-50",
+                    ReactionBreakdown {
+                        event_id: event_id.clone(),
+                        like_count: props.reaction.like_count,
                     }
                 }
             }
@@ -1,14 +1,19 @@
 //! Inline reaction picker for notes and posts
-//! Shows user's preferred reaction emojis
+//! Shows user's preferred reaction emojis and their NIP-30 custom emojis
 
 use dioxus::prelude::*;
 use std::collections::HashSet;
 use crate::hooks::ReactionEmoji;
+use crate::stores::emoji_store;
 use crate::stores::reactions_store::{PREFERRED_REACTIONS, PreferredReaction};
 use crate::components::icons::SettingsIcon;
 
+/// Maximum custom emojis shown after the preferred reactions
+const MAX_CUSTOM_EMOJIS: usize = 24;
+
 /// Inline reaction picker that appears on hover/click
-/// Shows user's preferred reactions with optional settings button
+/// Shows user's preferred reactions, then their custom emojis (kind 10030 / 30030),
+/// with optional settings button
 #[derive(Props, Clone, PartialEq)]
 pub struct InlineReactionPickerProps {
     /// Called when a reaction is selected
@@ -24,6 +29,17 @@ pub fn InlineReactionPicker(props: InlineReactionPickerProps) -> Element {
     // Track failed image URLs for fallback display
     let mut failed_images: Signal<HashSet<String>> = use_signal(HashSet::new);
 
+    // Custom emojis not already among the preferred reactions
+    let custom_emojis: Vec<emoji_store::CustomEmoji> = emoji_store::all_custom_emojis()
+        .into_iter()
+        .filter(|emoji| {
+            !preferred_reactions.iter().any(|reaction| {
+                matches!(reaction, PreferredReaction::Custom { shortcode, .. } if *shortcode == emoji.shortcode)
+            })
+        })
+        .take(MAX_CUSTOM_EMOJIS)
+        .collect();
+
     rsx! {
         div {
             class: "flex items-center gap-0.5 bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-full shadow-lg px-2 py-1",
@@ -82,6 +98,47 @@ pub fn InlineReactionPicker(props: InlineReactionPickerProps) -> Element {
                 }
             }
 
+            // User's custom emojis
+            if !custom_emojis.is_empty() {
+                div {
+                    class: "ml-1 pl-1 border-l border-gray-200 dark:border-gray-600 flex items-center gap-0.5 max-w-[12rem] overflow-x-auto",
+                    for emoji in custom_emojis {
+                        {
+                            let title_text = format!(":{}:", emoji.shortcode);
+                            let url_for_error = emoji.image_url.clone();
+                            let has_error = failed_images.read().contains(&emoji.image_url);
+                            let reaction = ReactionEmoji::Custom {
+                                shortcode: emoji.shortcode.clone(),
+                                url: emoji.image_url.clone(),
+                            };
+                            rsx! {
+                                button {
+                                    key: "inline-set-{emoji.shortcode}",
+                                    class: "hover:scale-125 transition-transform p-0.5 flex items-center justify-center shrink-0",
+                                    title: "{title_text}",
+                                    onclick: move |_| {
+                                        props.on_reaction.call(reaction.clone());
+                                    },
+                                    if has_error {
+                                        span { class: "text-xs text-gray-500", "{title_text}" }
+                                    } else {
+                                        img {
+                                            src: "{emoji.image_url}",
+                                            alt: "{title_text}",
+                                            class: "w-5 h-5 object-contain",
+                                            loading: "lazy",
+                                            onerror: move |_| {
+                                                failed_images.write().insert(url_for_error.clone());
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
             // Settings button (if callback provided)
             if let Some(on_settings) = props.on_settings.clone() {
                 div {
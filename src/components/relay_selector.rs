@@ -0,0 +1,140 @@
+use dioxus::prelude::*;
+use crate::stores::relay_metadata;
+
+#[derive(Props, Clone, PartialEq)]
+pub struct RelaySelectorProps {
+    /// Relays to publish to; None publishes to all relays as usual
+    pub selection: Signal<Option<Vec<String>>>,
+    #[props(default = false)]
+    pub disabled: bool,
+}
+
+/// "Post to" picker for composers
+///
+/// Restricting a note starts from the user's write relays; relays can be
+/// unticked, and others (a community or private relay) added by URL.
+#[component]
+pub fn RelaySelector(mut props: RelaySelectorProps) -> Element {
+    let mut open = use_signal(|| false);
+    let mut new_relay = use_signal(String::new);
+
+    let selected = props.selection.read().clone();
+    let label = match &selected {
+        None => "All relays".to_string(),
+        Some(relays) if relays.len() == 1 => relays[0].trim_start_matches("wss://").to_string(),
+        Some(relays) => format!("{} relays", relays.len()),
+    };
+
+    // Write relays first, then anything added by hand
+    let mut choices = relay_metadata::publish_relay_choices();
+    if let Some(relays) = &selected {
+        for relay in relays {
+            if !choices.contains(relay) {
+                choices.push(relay.clone());
+            }
+        }
+    }
+
+    let mut add_relay = move || {
+        let url = new_relay.read().clone();
+        let Some(url) = relay_metadata::resolve_publish_relays(&[url]).pop() else {
+            return;
+        };
+        let mut relays = props.selection.read().clone().unwrap_or_else(relay_metadata::publish_relay_choices);
+        if !relays.contains(&url) {
+            relays.push(url);
+        }
+        props.selection.set(Some(relays));
+        new_relay.set(String::new());
+    };
+
+    rsx! {
+        div {
+            class: "relative",
+            button {
+                class: if selected.is_some() {
+                    "px-2 py-1 text-xs font-medium rounded-full bg-primary text-primary-foreground transition"
+                } else {
+                    "px-2 py-1 text-xs font-medium rounded-full hover:bg-accent transition"
+                },
+                title: "Choose which relays get this note",
+                disabled: props.disabled,
+                onclick: move |_| open.toggle(),
+                "📡 {label}"
+            }
+
+            if *open.read() {
+                div {
+                    class: "absolute left-0 top-full mt-2 z-20 w-72 p-3 bg-background border border-border rounded-lg shadow-lg text-sm space-y-2",
+
+                    div {
+                        class: "flex items-center justify-between",
+                        span { class: "font-medium", "Post to" }
+                        if selected.is_some() {
+                            button {
+                                class: "text-xs text-blue-500 hover:underline",
+                                onclick: move |_| props.selection.set(None),
+                                "Use all relays"
+                            }
+                        }
+                    }
+
+                    for relay in choices {
+                        label {
+                            key: "{relay}",
+                            class: "flex items-center gap-2 cursor-pointer",
+                            input {
+                                r#type: "checkbox",
+                                checked: selected.as_ref().is_none_or(|relays| relays.contains(&relay)),
+                                onchange: {
+                                    let relay = relay.clone();
+                                    move |evt: FormEvent| {
+                                        let mut relays = props.selection.read().clone().unwrap_or_else(relay_metadata::publish_relay_choices);
+                                        if evt.checked() {
+                                            if !relays.contains(&relay) {
+                                                relays.push(relay.clone());
+                                            }
+                                        } else {
+                                            relays.retain(|r| r != &relay);
+                                        }
+                                        props.selection.set(Some(relays));
+                                    }
+                                }
+                            }
+                            span { class: "truncate", "{relay}" }
+                        }
+                    }
+
+                    div {
+                        class: "flex gap-2 pt-2 border-t border-border",
+                        input {
+                            r#type: "text",
+                            class: "flex-1 min-w-0 px-2 py-1 text-xs bg-background border border-border rounded focus:outline-none focus:ring-2 focus:ring-blue-500",
+                            placeholder: "wss://relay.example.com",
+                            value: "{new_relay}",
+                            oninput: move |e| new_relay.set(e.value()),
+                            onkeydown: move |e: KeyboardEvent| {
+                                if e.key() == Key::Enter {
+                                    e.prevent_default();
+                                    add_relay();
+                                }
+                            }
+                        }
+                        button {
+                            class: "px-2 py-1 text-xs font-medium rounded hover:bg-accent transition",
+                            onclick: move |_| add_relay(),
+                            "Add"
+                        }
+                    }
+
+                    if selected.as_ref().is_some_and(|relays| relays.is_empty()) {
+                        p {
+                            class: "text-xs text-red-500",
+                            "Pick at least one relay"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
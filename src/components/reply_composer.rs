@@ -1,13 +1,14 @@
 use dioxus::prelude::*;
 use std::time::Duration;
-use crate::stores::nostr_client::{publish_note, HAS_SIGNER};
+use crate::stores::nostr_client::{self, publish_note, HAS_SIGNER};
 use crate::stores::pending_comments::{
     PendingComment, CommentStatus, add_pending_comment, update_pending_status,
 };
 use crate::components::{MediaUploader, EmojiPicker, GifPicker, RichContent, MentionAutocomplete, PollCreatorModal};
 use crate::components::icons::{CameraIcon, BarChartIcon};
+use crate::utils::reply_tags::thread_root;
 use crate::utils::thread_tree::invalidate_thread_tree_cache;
-use crate::utils::{SignerValidationResult, get_current_user_pubkey};
+use crate::utils::{SignerValidationResult, blockquote_seed, get_current_user_pubkey};
 use nostr_sdk::{Event as NostrEvent, Kind, Timestamp};
 use nostr_sdk::prelude::*;
 use dioxus_core::spawn_forever;
@@ -63,6 +64,7 @@ pub fn ReplyComposer(
         author_pubkey.clone()
     };
     let reply_content = reply_to.content.clone();
+    let quote_source = reply_to.clone();
     let reply_tags: Vec<_> = reply_to.tags.iter().cloned().collect();
     let reply_id = reply_to.id.to_hex();
 
@@ -213,27 +215,14 @@ pub fn ReplyComposer(
         is_publishing.set(true);
 
         let event_id = reply_id.clone();
-        let author_pk = author_pubkey.clone();
-
-        // Clone the tags from reply_to before moving into async block
-        let parent_tags = reply_to.tags.clone();
         let reply_to_event = reply_to.clone();
 
         // Generate unique local ID for tracking this pending comment
         let local_id = uuid::Uuid::new_v4().to_string();
 
-        // Check if the event we're replying to has a root marker
+        // The thread root the event we're replying to belongs to, if any,
         // to determine if this is a top-level reply or nested reply
-        let parent_root = parent_tags.iter().find_map(|tag| {
-            let tag_vec = tag.clone().to_vec();
-            if tag_vec.len() >= 4
-                && tag_vec[0] == "e"
-                && tag_vec[3] == "root" {
-                Some(tag_vec[1].clone())
-            } else {
-                None
-            }
-        });
+        let parent_root = thread_root(&reply_to_event).map(|root| root.id.to_hex());
 
         // Determine the root event ID for optimistic update and cache invalidation
         let thread_root_id = if let Some(root_id) = &parent_root {
@@ -289,39 +278,12 @@ pub fn ReplyComposer(
         let local_id_clone = local_id.clone();
         let content_for_publish = content_value.clone();
         let thread_root_id_clone = thread_root_id.clone();
+        let parent_event = reply_to_event.clone();
 
         // Use spawn_forever so the task survives component unmount
         spawn_forever(async move {
-            // Build tags for reply following NIP-10 properly
-            let mut tags = Vec::new();
-
-            if let Some(root_id) = parent_root {
-                // This is a nested reply (replying to a reply)
-                // Add root marker for the thread root
-                tags.push(vec!["e".to_string(), root_id, "".to_string(), "root".to_string()]);
-                // Add reply marker for the immediate parent
-                tags.push(vec!["e".to_string(), event_id.clone(), "".to_string(), "reply".to_string()]);
-            } else {
-                // This is a direct reply to root
-                // Use only root marker (not reply)
-                tags.push(vec!["e".to_string(), event_id.clone(), "".to_string(), "root".to_string()]);
-            }
-
-            // Collect all p tags from parent event plus the parent's author
-            // Start with the parent's author
-            tags.push(vec!["p".to_string(), author_pk.clone()]);
-
-            // Add all p tags from the parent event (to notify everyone in thread)
-            for tag in parent_tags.iter() {
-                let tag_vec = tag.clone().to_vec();
-                if tag_vec.len() >= 2 && tag_vec[0] == "p" {
-                    let pubkey = tag_vec[1].clone();
-                    // Don't duplicate the author we already added
-                    if pubkey != author_pk {
-                        tags.push(vec!["p".to_string(), pubkey]);
-                    }
-                }
-            }
+            // NIP-10 marked e tags and p tags, with relay hints
+            let tags = nostr_client::reply_tags(&parent_event).await;
 
             match publish_note(content_for_publish, tags).await {
                 Ok(published_event_id) => {
@@ -392,8 +354,30 @@ pub fn ReplyComposer(
                 div {
                     class: "p-4 bg-gray-50 dark:bg-gray-900 border-b border-border",
                     div {
-                        class: "text-sm text-gray-600 dark:text-gray-400 mb-2",
-                        "Replying to @{short_author}"
+                        class: "flex items-center justify-between mb-2",
+                        div {
+                            class: "text-sm text-gray-600 dark:text-gray-400",
+                            "Replying to @{short_author}"
+                        }
+                        if has_signer {
+                            button {
+                                class: "px-2 py-1 text-xs rounded-full hover:bg-accent transition",
+                                title: "Quote the note's text in your reply",
+                                disabled: *is_publishing.read(),
+                                onclick: move |_| {
+                                    match blockquote_seed(&quote_source) {
+                                        Ok(seed) => {
+                                            let current = content.read().clone();
+                                            if !current.starts_with(&seed) {
+                                                content.set(format!("{}{}", seed, current));
+                                            }
+                                        }
+                                        Err(e) => log::warn!("Failed to quote note: {}", e),
+                                    }
+                                },
+                                "❝ Quote text"
+                            }
+                        }
                     }
                     div {
                         class: "text-sm text-gray-700 dark:text-gray-300 line-clamp-3 overflow-hidden",
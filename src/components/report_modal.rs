@@ -1,5 +1,8 @@
 use dioxus::prelude::*;
+use nostr_sdk::EventId;
 use crate::stores::nostr_client;
+use crate::stores::labels::{self, LabelTarget};
+use crate::components::LabelPicker;
 
 #[derive(Props, Clone, PartialEq)]
 pub struct ReportModalProps {
@@ -20,6 +23,7 @@ pub fn ReportModal(props: ReportModalProps) -> Element {
     let event_id = props.event_id.clone();
     let author_pubkey = props.author_pubkey.clone();
     let on_close = props.on_close.clone();
+    let label_target = EventId::from_hex(&props.event_id).ok().map(LabelTarget::Event);
 
     // Report types from NIP-56
     let report_types = vec![
@@ -143,6 +147,23 @@ pub fn ReportModal(props: ReportModalProps) -> Element {
                             }
                         }
 
+                        // Public NIP-32 label other users see on the note
+                        if let Some(target) = label_target.clone() {
+                            div {
+                                label {
+                                    class: "block text-sm font-medium mb-2",
+                                    "Label for the community (optional)"
+                                }
+                                LabelPicker {
+                                    target,
+                                    namespace: labels::CONTENT_NAMESPACE.to_string(),
+                                    options: labels::CONTENT_LABELS.iter()
+                                        .map(|(value, description)| (value.to_string(), description.to_string()))
+                                        .collect::<Vec<_>>(),
+                                }
+                            }
+                        }
+
                         // Error message
                         if let Some(err) = error_msg.read().as_ref() {
                             div {
@@ -1,5 +1,6 @@
 use dioxus::prelude::*;
 use crate::utils::content_parser::{parse_content, ContentToken};
+use crate::utils::event::content_warning;
 use crate::routes::Route;
 use nostr_sdk::{Tag, FromBech32, Metadata, PublicKey, Filter, Kind, Event, EventId};
 use nostr_sdk::nips::nip19::Nip19;
@@ -7,7 +8,8 @@ use crate::stores::nostr_client;
 use crate::services::wavlake::WavlakeAPI;
 use crate::stores::music_player::{self, MusicTrack};
 use crate::components::icons;
-use crate::components::{PhotoCard, VideoCard, VoiceMessageCard, PollCard, CashuTokenCard};
+use crate::components::{PhotoCard, VideoCard, VoiceMessageCard, PollCard, CashuTokenCard, BlurhashImage};
+use crate::components::photo_card::{parse_imeta, ImageMeta};
 use crate::components::live_stream_card::LiveStreamCard;
 
 #[component]
@@ -17,14 +19,53 @@ pub fn RichContent(
     #[props(default = false)] collapsible: bool,
 ) -> Element {
     let tokens = parse_content(&content, &tags);
+    let images = parse_imeta(tags.iter());
     let mut is_expanded = use_signal(|| false);
+    // NIP-36: revealing is per note and only lasts while it's on screen
+    let mut is_revealed = use_signal(|| false);
+
+    if let Some(warning) = content_warning(&tags).filter(|_| !*is_revealed.read()) {
+        let label = match &warning.reason {
+            Some(reason) => format!("Show sensitive content ({})", reason),
+            None => "Show sensitive content".to_string(),
+        };
+
+        // Only a blurred text preview: media and embeds load once revealed
+        let preview: String = content.chars().take(280).collect();
+
+        return rsx! {
+            div {
+                class: "relative rounded-lg overflow-hidden",
+                div {
+                    class: "whitespace-pre-wrap break-words max-h-[12em] overflow-hidden blur-md select-none pointer-events-none",
+                    aria_hidden: "true",
+                    "{preview}"
+                }
+                div {
+                    class: "absolute inset-0 flex flex-col items-center justify-center gap-2 bg-background/40 min-h-[6em]",
+                    span {
+                        class: "text-sm font-medium text-muted-foreground",
+                        "⚠️ Content warning"
+                    }
+                    button {
+                        class: "px-4 py-1.5 text-sm font-medium border border-border rounded-md bg-background hover:bg-accent transition-colors",
+                        onclick: move |e: MouseEvent| {
+                            e.stop_propagation();
+                            is_revealed.set(true);
+                        },
+                        "{label}"
+                    }
+                }
+            }
+        };
+    }
 
     // Estimate if content is long enough to need collapsing
     // Count characters and media items to estimate content height
     let is_long_content = if collapsible {
         let char_count = content.chars().count();
         let media_count = tokens.iter().filter(|t| {
-            matches!(t, ContentToken::Image(_) | ContentToken::Video(_) |
+            matches!(t, ContentToken::Image(_) | ContentToken::AnimatedImage(_) | ContentToken::Video(_) |
                      ContentToken::WavlakeTrack(_) | ContentToken::WavlakeAlbum(_) |
                      ContentToken::TwitterTweet(_) | ContentToken::TwitchStream(_) |
                      ContentToken::TwitchClip(_) | ContentToken::TwitchVod(_) |
@@ -49,7 +90,7 @@ pub fn RichContent(
                         "whitespace-pre-wrap break-words space-y-2 max-h-[24em] overflow-hidden"
                     },
                     for token in tokens.iter() {
-                        {render_token(token)}
+                        {render_token(token, &images)}
                     }
                 }
                 // Show More button - only visible when collapsed
@@ -73,14 +114,14 @@ pub fn RichContent(
             div {
                 class: "whitespace-pre-wrap break-words space-y-2",
                 for token in tokens.iter() {
-                    {render_token(token)}
+                    {render_token(token, &images)}
                 }
             }
         }
     }
 }
 
-fn render_token(token: &ContentToken) -> Element {
+fn render_token(token: &ContentToken, images: &[ImageMeta]) -> Element {
     match token {
         ContentToken::Text(text) => rsx! {
             span { "{text}" }
@@ -98,19 +139,37 @@ fn render_token(token: &ContentToken) -> Element {
         },
 
         ContentToken::Image(url) => {
-            let url_for_error = url.clone();
+            let meta = images.iter().find(|image| &image.url == url);
             rsx! {
                 div {
                     class: "my-2 rounded-lg overflow-hidden border border-border",
                     onclick: move |e: MouseEvent| e.stop_propagation(),
-                    img {
-                        src: "{url}",
-                        alt: "Image",
-                        class: "max-w-full h-auto",
-                        loading: "lazy",
-                        onerror: move |_| {
-                            log::warn!("Failed to load image: {}", url_for_error);
-                        }
+                    BlurhashImage {
+                        url: url.clone(),
+                        blurhash: meta.and_then(|image| image.blurhash.clone()),
+                        dim: meta.and_then(|image| image.dim),
+                        alt: meta.and_then(|image| image.alt.clone()).unwrap_or_else(|| "Image".to_string()),
+                    }
+                }
+            }
+        },
+
+        // Animates on its own in an <img>; marked so it isn't mistaken for a still
+        ContentToken::AnimatedImage(url) => {
+            let meta = images.iter().find(|image| &image.url == url);
+            rsx! {
+                div {
+                    class: "relative my-2 rounded-lg overflow-hidden border border-border",
+                    onclick: move |e: MouseEvent| e.stop_propagation(),
+                    BlurhashImage {
+                        url: url.clone(),
+                        blurhash: meta.and_then(|image| image.blurhash.clone()),
+                        dim: meta.and_then(|image| image.dim),
+                        alt: meta.and_then(|image| image.alt.clone()).unwrap_or_else(|| "Animated image".to_string()),
+                    }
+                    span {
+                        class: "absolute top-2 left-2 px-1.5 py-0.5 rounded bg-black/60 text-white text-[10px] font-bold tracking-wide pointer-events-none",
+                        "GIF"
                     }
                 }
             }
@@ -237,6 +296,17 @@ fn render_token(token: &ContentToken) -> Element {
         ContentToken::CashuToken(token) => rsx! {
             CashuTokenCard { token: token.clone() }
         },
+
+        // NIP-30 custom emoji
+        ContentToken::CustomEmoji(shortcode, url) => rsx! {
+            img {
+                class: "inline-block h-6 w-6 object-contain align-text-bottom",
+                src: "{url}",
+                alt: ":{shortcode}:",
+                title: ":{shortcode}:",
+                loading: "lazy",
+            }
+        },
     }
 }
 
@@ -365,63 +435,11 @@ fn EventMentionRenderer(mention: String) -> Element {
         if let Some(event_id) = event_id_result {
             let relay_hints_clone = relay_hints.clone();
             spawn(async move {
-                let event_filter = Filter::new()
-                    .id(event_id)
-                    .limit(1);
-
-                // Try relay hints first if available, then fall back to aggregated fetch
-                let fetch_result = if !relay_hints_clone.is_empty() {
-                    // Use relay hints from nevent
-                    if let Some(client) = nostr_client::get_client() {
-                        let relay_urls: Vec<nostr_sdk::Url> = relay_hints_clone.iter()
-                            .filter_map(|r| nostr_sdk::Url::parse(r).ok())
-                            .collect();
-
-                        if !relay_urls.is_empty() {
-                            nostr_client::ensure_relays_ready(&client).await;
-                            client.fetch_events_from(relay_urls, event_filter.clone(), std::time::Duration::from_secs(5)).await
-                                .map(|events| events.into_iter().collect::<Vec<_>>())
-                                .ok()
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                };
-
-                // Fall back to aggregated fetch if relay hints didn't work
-                let events = match fetch_result {
-                    Some(events) if !events.is_empty() => events,
-                    _ => {
-                        nostr_client::fetch_events_aggregated(
-                            event_filter,
-                            std::time::Duration::from_secs(5)
-                        ).await.unwrap_or_default()
-                    }
-                };
-
-                if let Some(event) = events.into_iter().next() {
+                if let Some(event) = fetch_embedded_event(event_id, relay_hints_clone).await {
                     let author_pubkey = event.pubkey;
                     embedded_event.set(Some(event));
-
-                    // Fetch author metadata using Outbox
-                    let metadata_filter = Filter::new()
-                        .author(author_pubkey)
-                        .kind(Kind::Metadata)
-                        .limit(1);
-
-                    if let Ok(metadata_events) = nostr_client::fetch_events_aggregated_outbox(
-                        metadata_filter,
-                        std::time::Duration::from_secs(5)
-                    ).await {
-                        if let Some(metadata_event) = metadata_events.into_iter().next() {
-                            if let Ok(meta) = serde_json::from_str::<Metadata>(&metadata_event.content) {
-                                author_metadata.set(Some(meta));
-                            }
-                        }
+                    if let Some(meta) = fetch_author_metadata(author_pubkey).await {
+                        author_metadata.set(Some(meta));
                     }
                 }
             });
@@ -435,42 +453,7 @@ fn EventMentionRenderer(mention: String) -> Element {
         let metadata_clone = author_metadata.read().clone();
 
         if has_event {
-            let event = event_clone.unwrap();
-            let event_kind = event.kind.as_u16();
-
-            // Route to appropriate card based on event kind
-            match event_kind {
-                20 => {
-                    // Photo (kind 20)
-                    rsx! {
-                        PhotoCard { event: event }
-                    }
-                }
-                22 => {
-                    // Video (kind 22)
-                    rsx! {
-                        VideoCard { event: event }
-                    }
-                }
-                1040 => {
-                    // Voice Message (kind 1040)
-                    rsx! {
-                        VoiceMessageCard { event: event }
-                    }
-                }
-                1068 => {
-                    // Poll (kind 1068)
-                    rsx! {
-                        PollCard { event: event }
-                    }
-                }
-                _ => {
-                    // Default: render as embedded note
-                    rsx! {
-                        {render_embedded_note(&event, metadata_clone.as_ref())}
-                    }
-                }
-            }
+            render_embedded_event(event_clone.unwrap(), metadata_clone.as_ref())
         } else {
             // Loading state - show link
             let event_str = event_id.to_hex();
@@ -500,6 +483,143 @@ fn EventMentionRenderer(mention: String) -> Element {
     }
 }
 
+/// Fetch an embedded event, trying its relay hints before the usual relays
+async fn fetch_embedded_event(event_id: EventId, relay_hints: Vec<String>) -> Option<Event> {
+    let event_filter = Filter::new()
+        .id(event_id)
+        .limit(1);
+
+    // Try relay hints first if available, then fall back to aggregated fetch
+    let relay_urls: Vec<nostr_sdk::Url> = relay_hints.iter()
+        .filter_map(|r| nostr_sdk::Url::parse(r).ok())
+        .collect();
+
+    let fetch_result = match nostr_client::get_client() {
+        Some(client) if !relay_urls.is_empty() => {
+            nostr_client::ensure_relays_ready(&client).await;
+            client.fetch_events_from(relay_urls, event_filter.clone(), std::time::Duration::from_secs(5)).await
+                .map(|events| events.into_iter().collect::<Vec<_>>())
+                .ok()
+        }
+        _ => None,
+    };
+
+    // Fall back to aggregated fetch if relay hints didn't work
+    let events = match fetch_result {
+        Some(events) if !events.is_empty() => events,
+        _ => {
+            nostr_client::fetch_events_aggregated(
+                event_filter,
+                std::time::Duration::from_secs(5)
+            ).await.unwrap_or_default()
+        }
+    };
+
+    events.into_iter().next()
+}
+
+/// Fetch an embedded event author's metadata using Outbox
+async fn fetch_author_metadata(author_pubkey: PublicKey) -> Option<Metadata> {
+    let metadata_filter = Filter::new()
+        .author(author_pubkey)
+        .kind(Kind::Metadata)
+        .limit(1);
+
+    let metadata_events = nostr_client::fetch_events_aggregated_outbox(
+        metadata_filter,
+        std::time::Duration::from_secs(5)
+    ).await.ok()?;
+
+    let metadata_event = metadata_events.into_iter().next()?;
+    serde_json::from_str::<Metadata>(&metadata_event.content).ok()
+}
+
+/// Render an embedded event with the card matching its kind
+fn render_embedded_event(event: Event, metadata: Option<&Metadata>) -> Element {
+    // Route to appropriate card based on event kind
+    match event.kind.as_u16() {
+        20 => {
+            // Photo (kind 20)
+            rsx! {
+                PhotoCard { event: event }
+            }
+        }
+        22 => {
+            // Video (kind 22)
+            rsx! {
+                VideoCard { event: event }
+            }
+        }
+        1040 => {
+            // Voice Message (kind 1040)
+            rsx! {
+                VoiceMessageCard { event: event }
+            }
+        }
+        1068 => {
+            // Poll (kind 1068)
+            rsx! {
+                PollCard { event: event }
+            }
+        }
+        _ => {
+            // Default: render as embedded note
+            rsx! {
+                {render_embedded_note(&event, metadata)}
+            }
+        }
+    }
+}
+
+/// The event quoted by a quote repost (`q` tag), embedded under the commentary
+///
+/// Shows a placeholder when the quoted event can't be found.
+#[component]
+pub fn QuotedEventCard(event_id: EventId, relay_hint: Option<String>) -> Element {
+    // None while loading, Some(None) when the event couldn't be fetched
+    let mut quoted_event = use_signal(|| None::<Option<Event>>);
+    let mut author_metadata = use_signal(|| None::<Metadata>);
+
+    use_effect(use_reactive((&event_id, &relay_hint), move |(event_id, relay_hint)| {
+        quoted_event.set(None);
+        spawn(async move {
+            let event = fetch_embedded_event(event_id, relay_hint.into_iter().collect()).await;
+            let author = event.as_ref().map(|event| event.pubkey);
+            quoted_event.set(Some(event));
+
+            if let Some(author) = author {
+                if let Some(meta) = fetch_author_metadata(author).await {
+                    author_metadata.set(Some(meta));
+                }
+            }
+        });
+    }));
+
+    let quoted = quoted_event.read().clone();
+    rsx! {
+        div {
+            class: "mt-3",
+            onclick: move |e: MouseEvent| e.stop_propagation(),
+            match quoted {
+                Some(Some(event)) => render_embedded_event(event, author_metadata.read().as_ref()),
+                Some(None) => rsx! {
+                    div {
+                        class: "border border-dashed border-border rounded-lg p-3 text-sm text-muted-foreground",
+                        "Quoted note unavailable"
+                    }
+                },
+                None => rsx! {
+                    div {
+                        class: "border border-border rounded-lg p-3 space-y-2 animate-pulse",
+                        div { class: "h-3 w-1/3 bg-muted rounded" }
+                        div { class: "h-3 w-2/3 bg-muted rounded" }
+                    }
+                },
+            }
+        }
+    }
+}
+
 fn render_embedded_note(event: &Event, metadata: Option<&Metadata>) -> Element {
     let event_id = event.id.to_hex();
     let content = &event.content;
@@ -164,6 +164,7 @@ pub fn SearchInput() -> Element {
             class: "relative",
 
             input {
+                id: crate::components::keyboard_shortcuts::SEARCH_INPUT_ID,
                 r#type: "text",
                 placeholder: "Search Nostr...",
                 value: "{query}",
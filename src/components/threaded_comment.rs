@@ -2,9 +2,9 @@ use dioxus::prelude::*;
 use dioxus::events::MediaData;
 use dioxus::web::WebEventExt;
 use wasm_bindgen::JsCast;
-use crate::utils::{ThreadNode, ThreadNodeSource, event::is_voice_message};
+use crate::utils::{ThreadNode, ThreadNodeSource, SortMode, event::is_voice_message};
 use crate::stores::pending_comments::{CommentStatus, remove_pending_comment, retry_pending_comment};
-use crate::components::{RichContent, ReplyComposer, ZapModal, ReactionButton};
+use crate::components::{RichContent, ReplyComposer, CommentComposer, ZapModal, ReactionButton};
 use crate::routes::Route;
 use crate::stores::nostr_client::{self, publish_repost, HAS_SIGNER, get_client};
 use crate::stores::voice_messages_store;
@@ -20,8 +20,37 @@ use std::time::Duration;
 
 const MAX_DEPTH: usize = 8; // Limit nesting to prevent excessive indentation
 
+/// Picker for the order of replies in a thread
 #[component]
-pub fn ThreadedComment(node: ThreadNode, depth: usize) -> Element {
+pub fn ThreadSortSelect(mode: Signal<SortMode>) -> Element {
+    rsx! {
+        div {
+            class: "flex items-center justify-end gap-2 px-4 py-2 text-sm text-muted-foreground",
+            label { r#for: "thread-sort", "Sort replies" }
+            select {
+                id: "thread-sort",
+                class: "px-2 py-1 text-sm bg-background border border-border rounded-lg",
+                value: "{mode.read().as_str()}",
+                onchange: move |evt| mode.set(SortMode::from_value(&evt.value())),
+                for option_mode in SortMode::ALL {
+                    option { value: "{option_mode.as_str()}", "{option_mode.label()}" }
+                }
+            }
+        }
+    }
+}
+
+/// A comment and its replies
+///
+/// `comment_root` is the article, video or photo a NIP-22 thread hangs off;
+/// with it, replies are kind 1111 comments scoped to that root instead of
+/// kind 1 replies.
+#[component]
+pub fn ThreadedComment(
+    node: ThreadNode,
+    depth: usize,
+    #[props(default)] comment_root: Option<nostr_sdk::Event>,
+) -> Element {
     let event = &node.event;
     let children = &node.children;
 
@@ -56,6 +85,8 @@ pub fn ThreadedComment(node: ThreadNode, depth: usize) -> Element {
     let has_signer = *HAS_SIGNER.read();
     let mut show_reply_modal = use_signal(|| false);
     let mut show_zap_modal = use_signal(|| false);
+    let mut show_children = use_signal(|| !node.collapsed);
+    let hidden_reply_count = node.count_descendants();
 
     // Reaction hook - handles like state with optimistic updates and toggle support
     let reaction = use_reaction(
@@ -708,13 +739,25 @@ pub fn ThreadedComment(node: ThreadNode, depth: usize) -> Element {
             }
 
             // Recursively render children
-            if !children.is_empty() && depth < MAX_DEPTH {
+            if !children.is_empty() && !*show_children.read() {
+                button {
+                    class: "ml-4 mt-1 text-xs text-blue-500 hover:underline",
+                    onclick: move |_| show_children.set(true),
+                    if hidden_reply_count == 1 {
+                        "Show 1 reply"
+                    } else {
+                        "Show {hidden_reply_count} replies"
+                    }
+                }
+            } else if !children.is_empty() && depth < MAX_DEPTH {
                 div {
                     class: "space-y-1 mt-1",
                     for child in children {
                         ThreadedComment {
+                            key: "{child.event.id}",
                             node: child.clone(),
-                            depth: depth + 1
+                            depth: depth + 1,
+                            comment_root: comment_root.clone()
                         }
                     }
                 }
@@ -733,16 +776,31 @@ pub fn ThreadedComment(node: ThreadNode, depth: usize) -> Element {
 
         // Reply composer modal
         if *show_reply_modal.read() {
-            ReplyComposer {
-                reply_to: event.clone(),
-                on_close: move |_| {
-                    show_reply_modal.set(false);
-                },
-                on_success: move |_| {
-                    show_reply_modal.set(false);
-                    // Update reply count
-                    let current = *reply_count.read();
-                    reply_count.set(current + 1);
+            if let Some(root) = comment_root.clone() {
+                CommentComposer {
+                    comment_on: root,
+                    parent_comment: Some(event.clone()),
+                    on_close: move |_| {
+                        show_reply_modal.set(false);
+                    },
+                    on_success: move |_| {
+                        show_reply_modal.set(false);
+                        let current = *reply_count.read();
+                        reply_count.set(current + 1);
+                    }
+                }
+            } else {
+                ReplyComposer {
+                    reply_to: event.clone(),
+                    on_close: move |_| {
+                        show_reply_modal.set(false);
+                    },
+                    on_success: move |_| {
+                        show_reply_modal.set(false);
+                        // Update reply count
+                        let current = *reply_count.read();
+                        reply_count.set(current + 1);
+                    }
                 }
             }
         }
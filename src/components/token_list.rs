@@ -27,6 +27,8 @@ fn MintRow(mint_url: String, tokens_for_mint: Rc<Vec<TokenData>>, is_expanded: b
         .map(|t| t.proofs.len())
         .sum();
 
+    let reliability = cashu::get_mint_reliability(&mint_url).filter(|r| r.has_enough_data());
+
     rsx! {
         div {
             key: "{mint_url}",
@@ -48,6 +50,24 @@ fn MintRow(mint_url: String, tokens_for_mint: Rc<Vec<TokenData>>, is_expanded: b
                         class: "text-xs text-muted-foreground mt-1",
                         "{proof_count} proofs"
                     }
+                    if let Some(reliability) = &reliability {
+                        div {
+                            class: if reliability.success_rate >= 0.9 {
+                                "text-xs text-green-600 dark:text-green-400 mt-0.5"
+                            } else if reliability.success_rate >= 0.7 {
+                                "text-xs text-yellow-600 dark:text-yellow-400 mt-0.5"
+                            } else {
+                                "text-xs text-red-600 dark:text-red-400 mt-0.5"
+                            },
+                            title: "Based on {reliability.sample_count} recent operations",
+                            "{(reliability.success_rate * 100.0).round()}% success · {reliability.avg_latency.as_millis()} ms"
+                        }
+                    } else {
+                        div {
+                            class: "text-xs text-muted-foreground mt-0.5",
+                            "Reliability: insufficient data"
+                        }
+                    }
                 }
 
                 div {
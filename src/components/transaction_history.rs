@@ -1,13 +1,21 @@
 use dioxus::prelude::*;
-use crate::stores::cashu::{WALLET_HISTORY, WalletHistoryStoreStoreExt};
+use crate::stores::cashu::{
+    WALLET_HISTORY, PENDING_MINT_QUOTES, PENDING_MELT_QUOTES, PENDING_NOSTR_EVENTS,
+    WalletHistoryStoreStoreExt, PendingMintQuotesStoreStoreExt, PendingMeltQuotesStoreStoreExt,
+    ActivitySource, ActivityStatus, HistoryDirection, build_activity_feed,
+};
 use crate::utils::format_sats_with_separator;
-use nostr_sdk::nips::nip60::TransactionDirection;
 
 #[component]
 pub fn TransactionHistory() -> Element {
-    let history = WALLET_HISTORY.read();
-
-    if history.data().read().is_empty() {
+    // Read the signals so the feed rebuilds when any source changes
+    let _ = WALLET_HISTORY.read().data().read().len();
+    let _ = PENDING_MINT_QUOTES.read().data().read().len();
+    let _ = PENDING_MELT_QUOTES.read().data().read().len();
+    let _ = PENDING_NOSTR_EVENTS.read().len();
+    let feed = build_activity_feed();
+
+    if feed.is_empty() {
         return rsx! {
             div {
                 class: "bg-card border border-border rounded-lg p-8 text-center",
@@ -31,24 +39,39 @@ pub fn TransactionHistory() -> Element {
         div {
             class: "bg-card border border-border rounded-lg overflow-hidden",
 
-            // Timeline of transactions
+            // Timeline of wallet activity
             div {
                 class: "divide-y divide-border",
-                for (_i, item) in history.data().read().iter().enumerate() {
+
+                for item in feed.iter() {
                     {
-                        let is_incoming = matches!(item.direction, TransactionDirection::In);
-                        let direction_icon = if is_incoming { "⬇️" } else { "⬆️" };
-                        let direction_text = if is_incoming { "Received" } else { "Sent" };
+                        let is_incoming = item.direction == HistoryDirection::In;
+                        let direction_icon = match (&item.status, is_incoming) {
+                            (ActivityStatus::Pending, _) => "⏳",
+                            (ActivityStatus::Failed, _) => "⚠️",
+                            (ActivityStatus::Confirmed, true) => "⬇️",
+                            (ActivityStatus::Confirmed, false) => "⬆️",
+                        };
                         let direction_color = if is_incoming { "text-green-500" } else { "text-orange-500" };
                         let amount_prefix = if is_incoming { "+" } else { "-" };
+                        let badge_class = match item.status {
+                            ActivityStatus::Confirmed => "text-xs px-2 py-0.5 rounded-full bg-green-500/10 text-green-600",
+                            ActivityStatus::Pending => "text-xs px-2 py-0.5 rounded-full bg-yellow-500/10 text-yellow-600",
+                            ActivityStatus::Failed => "text-xs px-2 py-0.5 rounded-full bg-red-500/10 text-red-600",
+                        };
+                        let row_class = match item.status {
+                            ActivityStatus::Confirmed => "px-4 py-4 hover:bg-accent/50 transition",
+                            ActivityStatus::Pending => "px-4 py-4 bg-yellow-500/5",
+                            ActivityStatus::Failed => "px-4 py-4 bg-red-500/5",
+                        };
 
                         // Format timestamp
                         let timestamp_str = format_timestamp(item.created_at);
 
                         rsx! {
                             div {
-                                key: "{item.event_id}",
-                                class: "px-4 py-4 hover:bg-accent/50 transition",
+                                key: "{item.key}",
+                                class: row_class,
 
                                 div {
                                     class: "flex items-start justify-between",
@@ -67,8 +90,15 @@ pub fn TransactionHistory() -> Element {
                                         div {
                                             class: "flex-1 min-w-0",
                                             div {
-                                                class: "font-semibold {direction_color}",
-                                                "{direction_text}"
+                                                class: "flex items-center gap-2",
+                                                span {
+                                                    class: "font-semibold {direction_color}",
+                                                    "{item.description}"
+                                                }
+                                                span {
+                                                    class: badge_class,
+                                                    "{item.status.label()}"
+                                                }
                                             }
                                             div {
                                                 class: "text-sm text-muted-foreground mt-1",
@@ -76,25 +106,27 @@ pub fn TransactionHistory() -> Element {
                                             }
 
                                             // Event references
-                                            if !item.created_tokens.is_empty() || !item.destroyed_tokens.is_empty() || !item.redeemed_events.is_empty() {
-                                                div {
-                                                    class: "text-xs text-muted-foreground mt-2 space-y-1",
-
-                                                    if !item.created_tokens.is_empty() {
-                                                        div {
-                                                            "✨ Created {item.created_tokens.len()} token event(s)"
+                                            if let ActivitySource::History(history) = &item.source {
+                                                if !history.created_tokens.is_empty() || !history.destroyed_tokens.is_empty() || !history.redeemed_events.is_empty() {
+                                                    div {
+                                                        class: "text-xs text-muted-foreground mt-2 space-y-1",
+
+                                                        if !history.created_tokens.is_empty() {
+                                                            div {
+                                                                "✨ Created {history.created_tokens.len()} token event(s)"
+                                                            }
                                                         }
-                                                    }
 
-                                                    if !item.destroyed_tokens.is_empty() {
-                                                        div {
-                                                            "🗑️ Destroyed {item.destroyed_tokens.len()} token event(s)"
+                                                        if !history.destroyed_tokens.is_empty() {
+                                                            div {
+                                                                "🗑️ Destroyed {history.destroyed_tokens.len()} token event(s)"
+                                                            }
                                                         }
-                                                    }
 
-                                                    if !item.redeemed_events.is_empty() {
-                                                        div {
-                                                            "⚡ Redeemed {item.redeemed_events.len()} event(s)"
+                                                        if !history.redeemed_events.is_empty() {
+                                                            div {
+                                                                "⚡ Redeemed {history.redeemed_events.len()} event(s)"
+                                                            }
                                                         }
                                                     }
                                                 }
@@ -103,15 +135,17 @@ pub fn TransactionHistory() -> Element {
                                     }
 
                                     // Right side: amount
-                                    div {
-                                        class: "text-right flex-shrink-0",
-                                        div {
-                                            class: "font-bold text-lg {direction_color}",
-                                            "{amount_prefix}{format_sats_with_separator(item.amount)}"
-                                        }
+                                    if let Some(amount) = item.amount {
                                         div {
-                                            class: "text-sm text-muted-foreground",
-                                            "{item.unit}"
+                                            class: "text-right flex-shrink-0",
+                                            div {
+                                                class: "font-bold text-lg {direction_color}",
+                                                "{amount_prefix}{format_sats_with_separator(amount)}"
+                                            }
+                                            div {
+                                                class: "text-sm text-muted-foreground",
+                                                "sat"
+                                            }
                                         }
                                     }
                                 }
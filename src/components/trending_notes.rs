@@ -1,6 +1,6 @@
 use dioxus::prelude::*;
 use crate::services::trending::{TrendingNote, get_trending_notes, truncate_content};
-use crate::stores::profiles;
+use crate::stores::{profiles, settings_store};
 use crate::routes::Route;
 use crate::utils::truncate_pubkey;
 
@@ -16,7 +16,8 @@ pub fn TrendingNotes() -> Element {
             loading.set(true);
             error.set(false);
 
-            match get_trending_notes(Some(10)).await {
+            let config = settings_store::SETTINGS.read().trending.clone();
+            match get_trending_notes(Some(10), &config).await {
                 Ok(notes) => {
                     trending_notes.set(notes.clone());
                     loading.set(false);
@@ -0,0 +1,121 @@
+use dioxus::prelude::*;
+use nostr_sdk::{FromBech32, PublicKey, Timestamp};
+use std::time::Duration;
+use crate::stores::user_status::{self, STATUS_MUSIC};
+
+/// A user's current NIP-38 statuses, one line each
+///
+/// Accepts an npub or hex pubkey. Expired statuses are never shown.
+#[component]
+pub fn UserStatusLine(pubkey: String, #[props(default)] compact: bool) -> Element {
+    let hex = PublicKey::from_bech32(&pubkey)
+        .or_else(|_| PublicKey::from_hex(&pubkey))
+        .map(|pk| pk.to_hex())
+        .ok();
+
+    let hex_for_effect = hex.clone();
+    use_effect(use_reactive!(|hex_for_effect| {
+        if let Some(hex) = hex_for_effect {
+            user_status::request_statuses(&hex);
+        }
+    }));
+
+    let statuses = hex.as_deref().map(user_status::get_statuses).unwrap_or_default();
+    if statuses.is_empty() {
+        return rsx! {};
+    }
+
+    let text_class = if compact { "text-xs" } else { "text-sm" };
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-0.5 text-muted-foreground {text_class} min-w-0",
+            for status in statuses {
+                div {
+                    key: "{status.status_type}",
+                    class: "flex items-center gap-1 min-w-0",
+                    span { if status.status_type == STATUS_MUSIC { "🎵" } else { "💬" } }
+                    if let Some(reference) = status.reference.clone() {
+                        a {
+                            href: "{reference}",
+                            target: "_blank",
+                            rel: "noopener noreferrer",
+                            class: "truncate hover:underline",
+                            onclick: move |e: MouseEvent| e.stop_propagation(),
+                            "{status.content}"
+                        }
+                    } else {
+                        span { class: "truncate", "{status.content}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Set or clear your own general status
+#[component]
+pub fn UserStatusEditor() -> Element {
+    let mut text = use_signal(String::new);
+    // Hours until the status expires; 0 keeps it until cleared
+    let mut expires_in_hours = use_signal(|| 0u64);
+    let mut is_saving = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    let mut save = move |content: String| {
+        is_saving.set(true);
+        error.set(None);
+        let hours = *expires_in_hours.read();
+        spawn(async move {
+            let expiration = (hours > 0 && !content.trim().is_empty())
+                .then(|| Timestamp::now() + Duration::from_secs(hours * 3600));
+            match user_status::set_status(&content, expiration).await {
+                Ok(()) => text.set(String::new()),
+                Err(e) => error.set(Some(e)),
+            }
+            is_saving.set(false);
+        });
+    };
+
+    rsx! {
+        div {
+            class: "mt-3 space-y-2",
+            div {
+                class: "flex flex-wrap items-center gap-2",
+                input {
+                    class: "flex-1 min-w-[12rem] px-3 py-1.5 text-sm bg-background border border-border rounded-lg",
+                    r#type: "text",
+                    maxlength: "140",
+                    placeholder: "What are you up to?",
+                    value: "{text}",
+                    oninput: move |evt| text.set(evt.value()),
+                }
+                select {
+                    class: "px-2 py-1.5 text-sm bg-background border border-border rounded-lg",
+                    value: "{expires_in_hours}",
+                    onchange: move |evt| expires_in_hours.set(evt.value().parse().unwrap_or(0)),
+                    option { value: "0", "Don't clear" }
+                    option { value: "1", "Clear after 1 hour" }
+                    option { value: "4", "Clear after 4 hours" }
+                    option { value: "24", "Clear after 24 hours" }
+                    option { value: "168", "Clear after a week" }
+                }
+                button {
+                    class: "px-3 py-1.5 text-sm font-medium rounded-lg bg-primary text-primary-foreground hover:opacity-90 disabled:opacity-50",
+                    disabled: *is_saving.read() || text.read().trim().is_empty(),
+                    onclick: move |_| save(text.read().clone()),
+                    "Set status"
+                }
+                button {
+                    class: "px-3 py-1.5 text-sm rounded-lg border border-border hover:bg-accent disabled:opacity-50",
+                    disabled: *is_saving.read(),
+                    onclick: move |_| save(String::new()),
+                    "Clear"
+                }
+            }
+            if let Some(err) = error.read().as_ref() {
+                p { class: "text-sm text-red-600 dark:text-red-400", "{err}" }
+            }
+        }
+    }
+}
@@ -34,6 +34,16 @@ pub fn VoiceMessageCard(event: NostrEvent) -> Element {
     let mut is_reposting = use_signal(|| false);
     let has_signer = *HAS_SIGNER.read();
 
+    // Waveform amplitudes (0-100); None until the audio has been played once
+    // and decoded, or if it can't be decoded, in which case the plain
+    // progress bar is shown
+    let mut waveform = use_signal(|| voice_messages_store::cached_waveform(&audio_url).flatten());
+    let mut waveform_requested = use_signal(|| false);
+    use_effect(use_reactive(&audio_url, move |url| {
+        waveform.set(voice_messages_store::cached_waveform(&url).flatten());
+        waveform_requested.set(false);
+    }));
+
     // Reaction hook - handles like state with optimistic updates and toggle support
     let reaction = use_reaction(
         event_id_str.clone(),
@@ -211,8 +221,23 @@ pub fn VoiceMessageCard(event: NostrEvent) -> Element {
     }));
 
     // Handle play/pause toggle
+    // The waveform needs the whole file, so it's only downloaded once the
+    // user starts playback rather than for every card in the feed
+    let audio_url_for_waveform = audio_url.clone();
     let toggle_play = move |_| {
         voice_messages_store::toggle_voice_message(event_id);
+
+        if waveform.peek().is_some() || *waveform_requested.peek() {
+            return;
+        }
+        waveform_requested.set(true);
+        let url = audio_url_for_waveform.clone();
+        spawn(async move {
+            match voice_messages_store::load_waveform(&url).await {
+                Ok(amplitudes) => waveform.set(Some(amplitudes)),
+                Err(e) => log::debug!("No waveform for {}: {}", url, e),
+            }
+        });
     };
 
     // Control audio element based on playback state
@@ -347,6 +372,35 @@ pub fn VoiceMessageCard(event: NostrEvent) -> Element {
         0.0
     };
 
+    // Click on the waveform (or progress bar) to seek
+    let audio_id_seek = audio_id.clone();
+    let handle_seek = move |evt: Event<MouseData>| {
+        // Seeking shouldn't open the detail page
+        evt.stop_propagation();
+
+        let web_event = evt.data.as_web_event();
+        let Some(bar) = web_event
+            .current_target()
+            .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+        else {
+            return;
+        };
+        let rect = bar.get_bounding_client_rect();
+        let time = voice_messages_store::seek_time(web_event.client_x() as f64 - rect.left(), rect.width(), duration_val);
+
+        let audio = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.get_element_by_id(&audio_id_seek))
+            .and_then(|element| element.dyn_into::<web_sys::HtmlAudioElement>().ok());
+        if let Some(audio) = audio {
+            audio.set_current_time(time);
+            current_time.set(time);
+            if voice_messages_store::is_playing(&event_id) {
+                voice_messages_store::set_current_time(time);
+            }
+        }
+    };
+
     // Get author display info
     let author_name = author_metadata.read().as_ref()
         .and_then(|m| m.display_name.clone().or_else(|| m.name.clone()))
@@ -503,12 +557,34 @@ pub fn VoiceMessageCard(event: NostrEvent) -> Element {
                     div {
                         class: "flex-1",
 
-                        // Progress bar
-                        div {
-                            class: "w-full h-1 bg-muted rounded-full overflow-hidden mb-1",
+                        if let Some(amplitudes) = waveform.read().as_ref() {
+                            // Waveform, played part highlighted
                             div {
-                                class: "h-full bg-primary transition-all",
-                                style: "width: {progress_percent}%"
+                                class: "w-full h-8 flex items-center gap-px mb-1 cursor-pointer",
+                                onclick: handle_seek,
+                                for (index, amplitude) in amplitudes.iter().enumerate() {
+                                    {
+                                        let played = (index as f64 + 0.5) / amplitudes.len() as f64 * 100.0 <= progress_percent;
+                                        let height = (*amplitude).max(4);
+                                        rsx! {
+                                            div {
+                                                key: "{index}",
+                                                class: if played { "flex-1 rounded-full bg-primary" } else { "flex-1 rounded-full bg-muted-foreground/30" },
+                                                style: "height: {height}%"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        } else {
+                            // Progress bar
+                            div {
+                                class: "w-full h-1 bg-muted rounded-full overflow-hidden mb-1 cursor-pointer",
+                                onclick: handle_seek,
+                                div {
+                                    class: "h-full bg-primary transition-all",
+                                    style: "width: {progress_percent}%"
+                                }
                             }
                         }
 
@@ -26,6 +26,11 @@ pub fn WalletBalanceCard(
     let has_pending = balances.pending > 0;
     let formatted_pending = format_sats_with_separator(balances.pending);
 
+    // Per-unit breakdown, only shown once the wallet holds non-sat tokens
+    let mut unit_balances: Vec<(String, u64)> = cashu::get_balance_per_unit().into_iter().collect();
+    unit_balances.sort_by(|a, b| a.0.cmp(&b.0));
+    let has_other_units = unit_balances.iter().any(|(unit, _)| unit != "sat");
+
     rsx! {
         div {
             class: "bg-gradient-to-br from-blue-500 to-purple-600 rounded-xl p-6 text-white shadow-lg",
@@ -56,6 +61,21 @@ pub fn WalletBalanceCard(
                         span { "Pending: {formatted_pending} sats" }
                     }
                 }
+
+                // Separate balance line per currency unit
+                if has_other_units {
+                    div {
+                        class: "mt-3 space-y-1 text-sm",
+                        for (unit, amount) in unit_balances {
+                            div {
+                                key: "{unit}",
+                                class: "flex justify-between opacity-90",
+                                span { "{unit.to_uppercase()}" }
+                                span { class: "font-semibold", "{format_sats_with_separator(amount)}" }
+                            }
+                        }
+                    }
+                }
             }
 
             // Action buttons row 1: Lightning
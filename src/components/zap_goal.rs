@@ -0,0 +1,207 @@
+use dioxus::prelude::*;
+use nostr_sdk::{EventId, PublicKey, FromBech32};
+use crate::components::ZapModal;
+use crate::stores::nostr_client::HAS_SIGNER;
+use crate::stores::profiles;
+use crate::stores::zap_goals::{self, ZapGoal};
+use crate::utils::format_sats_with_separator;
+
+/// Progress bar for a NIP-75 zap goal
+///
+/// The bar stops at 100%; an overfunded goal says by how much in the label.
+#[component]
+pub fn ZapGoalProgress(raised_msats: u64, target_msats: u64) -> Element {
+    let percent = zap_goals::progress_percent(raised_msats, target_msats);
+    let raised_sats = format_sats_with_separator(raised_msats / 1000);
+    let target_sats = format_sats_with_separator(target_msats / 1000);
+    let overage_msats = raised_msats.saturating_sub(target_msats);
+    let bar_class = if overage_msats > 0 { "bg-green-500" } else { "bg-yellow-500" };
+
+    rsx! {
+        div {
+            class: "space-y-1",
+            div {
+                class: "h-2.5 w-full rounded-full bg-muted overflow-hidden",
+                role: "progressbar",
+                aria_valuemin: "0",
+                aria_valuemax: "100",
+                aria_valuenow: "{percent.min(100)}",
+                div {
+                    class: "h-full rounded-full transition-all {bar_class}",
+                    style: "width: {percent.min(100)}%",
+                }
+            }
+            div {
+                class: "flex justify-between text-xs text-muted-foreground",
+                span { "⚡ {raised_sats} of {target_sats} sats" }
+                if overage_msats > 0 {
+                    span {
+                        class: "text-green-600 dark:text-green-400 font-medium",
+                        "{percent}% · {format_sats_with_separator(overage_msats / 1000)} sats over"
+                    }
+                } else {
+                    span { "{percent}%" }
+                }
+            }
+        }
+    }
+}
+
+/// A zap goal with its description, progress and a zap button
+#[component]
+pub fn ZapGoalCard(goal: ZapGoal) -> Element {
+    let mut raised = use_signal(|| None::<u64>);
+    let mut show_zap_modal = use_signal(|| false);
+    let has_signer = *HAS_SIGNER.read();
+
+    let goal_for_fetch = goal.clone();
+    use_effect(use_reactive!(|goal_for_fetch| {
+        spawn(async move {
+            match zap_goals::fetch_goal_raised(&goal_for_fetch).await {
+                Ok(msats) => raised.set(Some(msats)),
+                Err(e) => {
+                    log::warn!("Failed to load zaps for goal {}: {}", goal_for_fetch.id, e);
+                    raised.set(Some(0));
+                }
+            }
+        });
+    }));
+
+    let author_hex = goal.author.to_hex();
+    let author_metadata = profiles::get_profile(&author_hex);
+    let author_name = author_metadata
+        .as_ref()
+        .and_then(|m| m.display_name.clone().or_else(|| m.name.clone()))
+        .unwrap_or_else(|| crate::utils::truncate_pubkey(&author_hex));
+    let title = goal.summary.clone().unwrap_or_else(|| goal.description.clone());
+    let goal_for_refresh = goal.clone();
+
+    rsx! {
+        div {
+            class: "rounded-lg border border-border bg-background p-3 space-y-2",
+            onclick: move |e: MouseEvent| e.stop_propagation(),
+            div {
+                class: "flex items-start gap-3",
+                if let Some(image) = &goal.image {
+                    img {
+                        src: "{image}",
+                        alt: "",
+                        class: "w-12 h-12 rounded-md object-cover flex-shrink-0",
+                        loading: "lazy",
+                    }
+                }
+                div {
+                    class: "flex-1 min-w-0",
+                    p {
+                        class: "text-xs font-medium uppercase tracking-wide text-muted-foreground",
+                        "🎯 Zap goal"
+                    }
+                    p {
+                        class: "font-semibold break-words",
+                        "{title}"
+                    }
+                    if goal.summary.is_some() && !goal.description.is_empty() {
+                        p {
+                            class: "text-sm text-muted-foreground whitespace-pre-wrap break-words",
+                            "{goal.description}"
+                        }
+                    }
+                }
+                if has_signer && goal.is_open(nostr_sdk::Timestamp::now()) {
+                    button {
+                        class: "px-3 py-1 text-sm font-medium rounded-full bg-yellow-500 hover:bg-yellow-600 text-white transition flex-shrink-0",
+                        onclick: move |_| show_zap_modal.set(true),
+                        "⚡ Zap"
+                    }
+                }
+            }
+
+            match *raised.read() {
+                Some(raised_msats) => rsx! {
+                    ZapGoalProgress { raised_msats, target_msats: goal.target_msats }
+                },
+                None => rsx! {
+                    div { class: "h-2.5 w-full rounded-full bg-muted animate-pulse" }
+                },
+            }
+
+            if *show_zap_modal.read() {
+                ZapModal {
+                    recipient_pubkey: author_hex.clone(),
+                    recipient_name: author_name.clone(),
+                    lud16: author_metadata.as_ref().and_then(|m| m.lud16.clone()),
+                    lud06: author_metadata.as_ref().and_then(|m| m.lud06.clone()),
+                    event_id: Some(goal.id.to_hex()),
+                    on_close: move |_| {
+                        show_zap_modal.set(false);
+                        // Pick up the zap we may have just sent
+                        let goal = goal_for_refresh.clone();
+                        spawn(async move {
+                            if let Ok(msats) = zap_goals::fetch_goal_raised(&goal).await {
+                                raised.set(Some(msats));
+                            }
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The goal an event is or links to (via a `goal` tag), if any
+#[component]
+pub fn LinkedZapGoal(goal_id: EventId) -> Element {
+    let mut goal = use_signal(|| None::<ZapGoal>);
+
+    use_effect(use_reactive!(|goal_id| {
+        goal.set(None);
+        spawn(async move {
+            match zap_goals::fetch_goal_by_id(goal_id).await {
+                Ok(found) => goal.set(Some(found)),
+                Err(e) => log::warn!("Failed to load zap goal {}: {}", goal_id, e),
+            }
+        });
+    }));
+
+    match goal.read().clone() {
+        Some(goal) => rsx! {
+            ZapGoalCard { goal }
+        },
+        None => rsx! {},
+    }
+}
+
+/// A profile's open zap goals
+#[component]
+pub fn ProfileGoals(pubkey: String) -> Element {
+    let mut goals = use_signal(Vec::<ZapGoal>::new);
+
+    use_effect(use_reactive!(|pubkey| {
+        goals.set(Vec::new());
+        let Ok(author) = PublicKey::from_bech32(&pubkey).or_else(|_| PublicKey::from_hex(&pubkey)) else {
+            return;
+        };
+        spawn(async move {
+            match zap_goals::fetch_active_goals(author).await {
+                Ok(found) => goals.set(found),
+                Err(e) => log::warn!("Failed to load zap goals: {}", e),
+            }
+        });
+    }));
+
+    if goals.read().is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            class: "mt-4 space-y-2",
+            for goal in goals.read().iter() {
+                ZapGoalCard {
+                    key: "{goal.id}",
+                    goal: goal.clone()
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use dioxus::prelude::*;
 use nostr_sdk::{PublicKey, EventId, RelayUrl};
-use crate::services::lnurl;
+use crate::services::{btc_price, lnurl};
 use crate::stores::nostr_client::get_client;
-use crate::stores::{signer, nwc_store, settings_store};
+use crate::stores::{cashu, signer, nwc_store, settings_store, zap_funding};
+use crate::stores::zap_funding::ZapFundingSource;
 use qrcode::QrCode;
 use qrcode::render::svg;
 use wasm_bindgen::prelude::*;
@@ -64,18 +65,40 @@ pub struct ZapModalProps {
 
 #[component]
 pub fn ZapModal(props: ZapModalProps) -> Element {
-    let mut zap_amount = use_signal(|| 21u64);
+    let preset_amounts = settings_store::SETTINGS.read().zap_presets.clone();
+    let first_preset = preset_amounts.first().copied().unwrap_or(21);
+    let mut zap_amount = use_signal(move || first_preset);
     let mut custom_amount = use_signal(|| String::new());
-    let mut zap_message = use_signal(|| String::new());
+    let mut zap_message = use_signal(|| settings_store::SETTINGS.read().default_zap_comment.clone());
+    let mut anonymous = use_signal(|| settings_store::SETTINGS.read().anonymous_zaps);
     let mut loading = use_signal(|| false);
     let mut error_msg = use_signal(|| None::<String>);
     let mut invoice = use_signal(|| None::<String>);
     let mut qr_code_svg = use_signal(|| None::<String>);
     let webln_available = is_webln_available();
     let toast = consume_toast();
-
-    // Preset amounts in sats
-    let preset_amounts = vec![21, 100, 500, 1000, 5000, 10000];
+    let mut funding_source = use_signal(zap_funding::initial_source);
+    // Offer a choice only when both wallets can pay
+    let show_source_selector = nwc_store::is_connected() && zap_funding::is_cashu_available();
+
+    // Fiat equivalent, when a currency is set and the price loads
+    let fiat_currency = settings_store::SETTINGS.read().fiat_currency.clone();
+    let mut btc_price = use_signal(|| None::<f64>);
+    use_effect(use_reactive!(|fiat_currency| {
+        btc_price.set(None);
+        if fiat_currency.is_empty() {
+            return;
+        }
+        spawn(async move {
+            match btc_price::get_btc_price(&fiat_currency).await {
+                Ok(price) => btc_price.set(Some(price)),
+                Err(e) => log::warn!("No fiat price for zap amount: {}", e),
+            }
+        });
+    }));
+    let fiat_amount = btc_price
+        .read()
+        .map(|price| btc_price::format_fiat(btc_price::sats_to_fiat(*zap_amount.read(), price), &fiat_currency));
 
     let handle_zap = move |_| {
         let recipient_pubkey_str = props.recipient_pubkey.clone();
@@ -85,6 +108,12 @@ pub fn ZapModal(props: ZapModalProps) -> Element {
         let message = zap_message.read().clone();
         let event_id_str = props.event_id.clone();
         let toast_api = toast.clone();
+        let is_anonymous = *anonymous.read();
+        // Nutzaps are signed with our key, so anonymous zaps go over Lightning
+        let source = if is_anonymous { None } else { *funding_source.read() };
+        if let Some(source) = source {
+            zap_funding::save_last_source(source);
+        }
 
         loading.set(true);
         error_msg.set(None);
@@ -92,13 +121,17 @@ pub fn ZapModal(props: ZapModalProps) -> Element {
         qr_code_svg.set(None);
 
         spawn(async move {
-            // Get signer
-            let signer_type = match signer::get_signer() {
-                Some(s) => s,
-                None => {
-                    error_msg.set(Some("No signer available. Please connect a signer first.".to_string()));
-                    loading.set(false);
-                    return;
+            // Get signer; anonymous zaps sign with a throwaway key instead
+            let signer_type = if is_anonymous {
+                None
+            } else {
+                match signer::get_signer() {
+                    Some(s) => Some(s),
+                    None => {
+                        error_msg.set(Some("No signer available. Please connect a signer first.".to_string()));
+                        loading.set(false);
+                        return;
+                    }
                 }
             };
 
@@ -126,6 +159,35 @@ pub fn ZapModal(props: ZapModalProps) -> Element {
                 None
             };
 
+            // Nutzap from the Cashu wallet; falls back to Lightning only while
+            // nothing was spent (no nutzaps accepted, no shared mint with funds)
+            if source == Some(ZapFundingSource::Cashu) {
+                match cashu::send_nutzap(recipient_pubkey, amount, message.clone(), event_id).await {
+                    Ok(nutzap_id) => {
+                        log::info!("Nutzap sent: {}", nutzap_id);
+                        loading.set(false);
+                        toast_api.success(
+                            "Zap sent!".to_string(),
+                            ToastOptions::new()
+                                .description("Nutzap sent from your Cashu wallet")
+                                .duration(Duration::from_secs(2))
+                                .permanent(false),
+                        );
+                        props.on_close.call(());
+                        return;
+                    }
+                    Err(cashu::NutzapError::NotSent(e)) => {
+                        log::warn!("Nutzap not sent, falling back to Lightning: {}", e);
+                    }
+                    Err(cashu::NutzapError::Locked(e)) => {
+                        log::error!("Nutzap failed after the ecash was locked: {}", e);
+                        error_msg.set(Some(format!("Nutzap failed after the ecash was sent: {}", e)));
+                        loading.set(false);
+                        return;
+                    }
+                }
+            }
+
             // Get relays from client
             let relays = if let Some(client) = get_client() {
                 client
@@ -172,7 +234,15 @@ pub fn ZapModal(props: ZapModalProps) -> Element {
 
             // Sign the zap request based on signer type
             let zap_request = match signer_type {
-                signer::SignerType::Keys(ref keys) => {
+                None => match lnurl::sign_anonymous_zap_request(builder) {
+                    Ok(event) => event,
+                    Err(e) => {
+                        error_msg.set(Some(e));
+                        loading.set(false);
+                        return;
+                    }
+                },
+                Some(signer::SignerType::Keys(ref keys)) => {
                     match builder.sign_with_keys(keys) {
                         Ok(event) => event,
                         Err(e) => {
@@ -183,7 +253,7 @@ pub fn ZapModal(props: ZapModalProps) -> Element {
                     }
                 }
                 #[cfg(target_family = "wasm")]
-                signer::SignerType::BrowserExtension(ref signer) => {
+                Some(signer::SignerType::BrowserExtension(ref signer)) => {
                     #[allow(unused_imports)]
                     use nostr::signer::NostrSigner;
                     match builder.sign(signer.as_ref()).await {
@@ -195,7 +265,7 @@ pub fn ZapModal(props: ZapModalProps) -> Element {
                         }
                     }
                 }
-                signer::SignerType::NostrConnect(ref nostr_connect) => {
+                Some(signer::SignerType::NostrConnect(ref nostr_connect)) => {
                     #[allow(unused_imports)]
                     use nostr::signer::NostrSigner;
                     match builder.sign(nostr_connect.as_ref()).await {
@@ -508,7 +578,10 @@ pub fn ZapModal(props: ZapModalProps) -> Element {
                                         } else {
                                             "px-4 py-2 rounded bg-secondary text-secondary-foreground hover:bg-secondary/80"
                                         },
-                                        onclick: move |_| zap_amount.set(amount),
+                                        onclick: move |_| {
+                                            zap_amount.set(amount);
+                                            custom_amount.set(String::new());
+                                        },
                                         "{amount}"
                                     }
                                 }
@@ -533,6 +606,13 @@ pub fn ZapModal(props: ZapModalProps) -> Element {
                                     "sats"
                                 }
                             }
+
+                            if let Some(fiat) = fiat_amount {
+                                p {
+                                    class: "text-sm text-muted-foreground",
+                                    "{fiat}"
+                                }
+                            }
                         }
 
                         // Message
@@ -551,6 +631,78 @@ pub fn ZapModal(props: ZapModalProps) -> Element {
                             }
                         }
 
+                        // Anonymous zap
+                        label {
+                            class: "flex items-start gap-2 cursor-pointer",
+                            input {
+                                r#type: "checkbox",
+                                class: "mt-1",
+                                checked: *anonymous.read(),
+                                onchange: move |e| anonymous.set(e.checked())
+                            }
+                            div {
+                                span {
+                                    class: "text-sm font-medium",
+                                    "Zap anonymously"
+                                }
+                                p {
+                                    class: "text-xs text-muted-foreground",
+                                    "The recipient won't see who sent it. Paid over Lightning; the message stays public."
+                                }
+                            }
+                        }
+
+                        // Funding source
+                        if show_source_selector && !*anonymous.read() {
+                            div {
+                                class: "space-y-2",
+                                label {
+                                    class: "block text-sm font-medium",
+                                    "Pay with"
+                                }
+                                div {
+                                    class: "grid grid-cols-2 gap-2",
+                                    for option in [ZapFundingSource::Nwc, ZapFundingSource::Cashu] {
+                                        button {
+                                            key: "{option.as_str()}",
+                                            class: if *funding_source.read() == Some(option) {
+                                                "px-3 py-2 rounded text-sm bg-primary text-primary-foreground font-medium"
+                                            } else {
+                                                "px-3 py-2 rounded text-sm bg-secondary text-secondary-foreground hover:bg-secondary/80"
+                                            },
+                                            onclick: move |_| funding_source.set(Some(option)),
+                                            "{option.label()}"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        // NWC budget warning
+                        if nwc_store::is_connected() && (*anonymous.read() || *funding_source.read() != Some(ZapFundingSource::Cashu)) {
+                            match nwc_store::budget_warning_for(*zap_amount.read()) {
+                                nwc_store::BudgetWarning::Exceeds { remaining_msats } => rsx! {
+                                    div {
+                                        class: "bg-red-500/10 border border-red-500/20 text-red-500 p-3 rounded text-sm",
+                                        "This zap exceeds your wallet connection's remaining budget ({remaining_msats / 1000} sats)."
+                                    }
+                                },
+                                nwc_store::BudgetWarning::Low { remaining_after_msats } => rsx! {
+                                    div {
+                                        class: "bg-yellow-500/10 border border-yellow-500/20 text-yellow-600 dark:text-yellow-400 p-3 rounded text-sm",
+                                        "Low budget: only {remaining_after_msats / 1000} sats will be left on your wallet connection."
+                                    }
+                                },
+                                nwc_store::BudgetWarning::Unknown => rsx! {
+                                    p {
+                                        class: "text-xs text-muted-foreground",
+                                        "Wallet budget: unknown"
+                                    }
+                                },
+                                nwc_store::BudgetWarning::None => rsx! {},
+                            }
+                        }
+
                         // Error message
                         if let Some(err) = error_msg.read().as_ref() {
                             div {
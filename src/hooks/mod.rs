@@ -2,6 +2,6 @@ pub mod use_infinite_scroll;
 pub mod use_lists;
 pub mod use_reaction;
 
-pub use use_infinite_scroll::use_infinite_scroll;
+pub use use_infinite_scroll::{use_infinite_scroll, use_page_dedup};
 pub use use_lists::{use_user_lists, delete_list, UserList};
-pub use use_reaction::{use_reaction, UseReaction, ReactionState, ReactionEmoji, format_count};
+pub use use_reaction::{use_reaction, UseReaction, ReactionState, ReactionEmoji, OptimisticToggle, format_count, toast_publish_error};
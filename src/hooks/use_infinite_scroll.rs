@@ -1,6 +1,60 @@
 use dioxus::prelude::*;
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+/// Drops items already shown when paginated relay responses overlap
+///
+/// Keys of every accepted item are remembered, so an item returned again on a
+/// later page (or twice in one page) is filtered out before appending. Only a
+/// page the relays returned empty marks the feed as at its end: a page of
+/// duplicates still moves the cursor back and may be followed by new items.
+pub struct PageDedup<T, K> {
+    key: Rc<dyn Fn(&T) -> K>,
+    seen: HashSet<K>,
+    at_end: bool,
+}
+
+impl<T, K: Eq + Hash> PageDedup<T, K> {
+    pub fn new(key: impl Fn(&T) -> K + 'static) -> Self {
+        Self {
+            key: Rc::new(key),
+            seen: HashSet::new(),
+            at_end: false,
+        }
+    }
+
+    /// Start over (e.g. on refresh) with `items` as the first page
+    pub fn reset(&mut self, items: &[T]) {
+        self.seen = items.iter().map(|item| (self.key)(item)).collect();
+        self.at_end = false;
+    }
+
+    /// Keep only the items of `page` not seen before
+    pub fn filter_page(&mut self, page: Vec<T>) -> Vec<T> {
+        self.at_end = page.is_empty();
+        page.into_iter()
+            .filter(|item| self.seen.insert((self.key)(item)))
+            .collect()
+    }
+
+    /// True once the relays returned an empty page
+    pub fn at_end(&self) -> bool {
+        self.at_end
+    }
+}
+
+/// Page deduplication state for a paginated feed, keyed by `key`
+///
+/// Pair with `use_infinite_scroll`: call `reset` with the first page, pass
+/// every following page through `filter_page` before appending, and stop
+/// pagination once `at_end` is set.
+pub fn use_page_dedup<T: 'static, K: Eq + Hash + 'static>(
+    key: impl Fn(&T) -> K + 'static,
+) -> Signal<PageDedup<T, K>> {
+    use_signal(move || PageDedup::new(key))
+}
 
 /// Infinite scroll hook that automatically triggers loading when sentinel element enters viewport
 ///
@@ -276,3 +330,36 @@ where
 
     sentinel_id
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlapping_pages_are_deduplicated() {
+        let mut dedup = PageDedup::new(|item: &(u32, &str)| item.0);
+        let mut items = vec![(1, "a"), (2, "b"), (3, "c")];
+        dedup.reset(&items);
+        assert!(!dedup.at_end());
+
+        // Second page overlaps the first and repeats an item within itself
+        let fresh = dedup.filter_page(vec![(3, "c"), (4, "d"), (4, "d"), (5, "e")]);
+        assert_eq!(fresh, vec![(4, "d"), (5, "e")]);
+        assert!(!dedup.at_end());
+        items.extend(fresh);
+
+        let ids: Vec<u32> = items.iter().map(|item| item.0).collect();
+        assert_eq!(ids, vec![1, 2, 3, 4, 5]);
+
+        // A page of only duplicates adds nothing but isn't the end of the feed
+        assert!(dedup.filter_page(vec![(4, "d"), (5, "e")]).is_empty());
+        assert!(!dedup.at_end());
+
+        // An empty page from the relays is
+        assert!(dedup.filter_page(vec![]).is_empty());
+        assert!(dedup.at_end());
+
+        dedup.reset(&items[..1]);
+        assert!(!dedup.at_end());
+    }
+}
@@ -8,27 +8,88 @@
 //! - NIP-30 custom emoji reactions
 
 use dioxus::prelude::*;
-use nostr_sdk::{Filter, Kind};
+use dioxus_primitives::toast::{consume_toast, ToastOptions, Toasts};
 use std::time::Duration;
 
-use crate::stores::nostr_client::{get_client, publish_reaction, HAS_SIGNER};
+use crate::stores::nostr_client::{publish_reaction, HAS_SIGNER};
+use crate::stores::reactions_store::{self, MAX_REACTIONS_FETCH};
 use crate::stores::signer::SIGNER_INFO;
 use crate::services::aggregation::{invalidate_interaction_counts, InteractionCounts};
 
-/// Maximum reactions to fetch per event
-const MAX_REACTIONS_FETCH: usize = 500;
-
 /// State of the reaction action
-#[derive(Clone, Debug, PartialEq)]
-pub enum ReactionState {
-    /// No action in progress
-    Idle,
-    /// Publishing reaction
-    Pending,
-    /// Action completed successfully
-    Success,
-    /// Action failed with error message
-    Error(String),
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ReactionState {
+    /// A publish is in flight; taps are ignored until it resolves
+    pub pending: bool,
+    /// Why the last publish failed
+    pub error: Option<String>,
+}
+
+impl ReactionState {
+    /// Mark a publish as started
+    ///
+    /// Returns false if one is already in flight, so a double tap can't
+    /// publish two events.
+    pub fn try_begin(&mut self) -> bool {
+        if self.pending {
+            return false;
+        }
+        self.pending = true;
+        self.error = None;
+        true
+    }
+
+    /// Mark the publish as resolved
+    pub fn finish(&mut self, result: Result<(), String>) {
+        self.pending = false;
+        self.error = result.err();
+    }
+
+    /// Mark the publish as resolved and return the toggle to show: the
+    /// optimistic one if it was published, the one from before the tap if not
+    pub fn settle(
+        &mut self,
+        previous: OptimisticToggle,
+        shown: OptimisticToggle,
+        result: Result<(), String>,
+    ) -> OptimisticToggle {
+        let settled = if result.is_ok() { shown } else { previous };
+        self.finish(result);
+        settled
+    }
+}
+
+/// Tell the user an interaction couldn't be published
+pub fn toast_publish_error(toast: &Toasts, message: String) {
+    toast.error(
+        "Couldn't publish".to_string(),
+        ToastOptions::new()
+            .description(message)
+            .duration(Duration::from_secs(4))
+            .permanent(false),
+    );
+}
+
+/// An on/off interaction and its count (a like or a repost)
+///
+/// The UI shows the new value as soon as the user taps and keeps the old
+/// one to roll back to if publishing fails.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub struct OptimisticToggle {
+    pub active: bool,
+    pub count: usize,
+}
+
+impl OptimisticToggle {
+    /// The toggle after switching to `active`
+    pub fn switched(self, active: bool) -> Self {
+        let count = match (self.active, active) {
+            (false, true) => self.count.saturating_add(1),
+            (true, false) => self.count.saturating_sub(1),
+            _ => self.count,
+        };
+        Self { active, count }
+    }
 }
 
 /// Represents an emoji for reactions (standard or custom per NIP-30)
@@ -67,6 +128,8 @@ impl ReactionEmoji {
 /// Return type for the use_reaction hook
 #[derive(Clone)]
 pub struct UseReaction {
+    /// Hex ID of the event being reacted to
+    pub event_id: String,
     /// Whether the current user has liked this event
     pub is_liked: Signal<bool>,
     /// Total positive reaction count
@@ -85,7 +148,8 @@ impl PartialEq for UseReaction {
     fn eq(&self, other: &Self) -> bool {
         // Compare signals by their current values for memoization
         // EventHandlers are not compared (they're always considered equal for this purpose)
-        *self.is_liked.read() == *other.is_liked.read()
+        self.event_id == other.event_id
+            && *self.is_liked.read() == *other.is_liked.read()
             && *self.like_count.read() == *other.like_count.read()
             && *self.state.read() == *other.state.read()
             && *self.user_reaction.read() == *other.user_reaction.read()
@@ -111,7 +175,7 @@ impl PartialEq for UseReaction {
 /// );
 ///
 /// button {
-///     disabled: matches!(*reaction.state.read(), ReactionState::Pending),
+///     disabled: reaction.state.read().pending,
 ///     onclick: move |_| reaction.toggle_like.call(()),
 ///     HeartIcon { filled: *reaction.is_liked.read() }
 /// }
@@ -121,6 +185,8 @@ pub fn use_reaction(
     event_author: String,
     precomputed_counts: Option<&InteractionCounts>,
 ) -> UseReaction {
+    let reaction_event_id = event_id.clone();
+
     // Extract precomputed values from InteractionCounts
     let precomputed_count = precomputed_counts.map(|c| c.likes);
     let precomputed_is_liked = precomputed_counts.and_then(|c| c.user_liked);
@@ -149,7 +215,7 @@ pub fn use_reaction(
     // Signals for reaction state
     let mut is_liked = use_signal(|| precomputed_is_liked.unwrap_or(false));
     let mut like_count = use_signal(|| precomputed_count.unwrap_or(0));
-    let mut state = use_signal(|| ReactionState::Idle);
+    let mut state = use_signal(ReactionState::default);
     let mut user_reaction: Signal<Option<ReactionEmoji>> = use_signal(|| precomputed_user_reaction.clone());
 
     // Watch for late-arriving precomputed data (batch fetch may complete after component mount)
@@ -201,30 +267,21 @@ pub fn use_reaction(
         }
 
         spawn(async move {
-            let client = match get_client() {
-                Some(c) => c,
-                None => return,
-            };
-
             let event_id_parsed = match nostr_sdk::EventId::from_hex(&event_id_for_fetch) {
                 Ok(id) => id,
                 Err(_) => return,
             };
 
-            // Fetch reactions for this event
-            let filter = Filter::new()
-                .kind(Kind::Reaction)
-                .event(event_id_parsed)
-                .limit(MAX_REACTIONS_FETCH);
-
-            if let Ok(reactions) = client.fetch_events(filter, Duration::from_secs(5)).await {
+            // Same fetch and counting as the reaction breakdown, so both agree
+            if let Ok(reactions) = reactions_store::fetch_reactions(event_id_parsed).await {
                 // Parse current user's pubkey once for efficient comparison
                 let current_user_pk: Option<nostr_sdk::PublicKey> = SIGNER_INFO
                     .read()
                     .as_ref()
                     .and_then(|info| nostr_sdk::PublicKey::from_hex(&info.public_key).ok());
 
-                let mut positive_count = 0usize;
+                let positive_count =
+                    reactions_store::positive_total(&reactions_store::group_reactions(&reactions));
                 let mut user_liked = false;
                 let mut user_unliked = false;
                 let mut user_emoji: Option<ReactionEmoji> = None;
@@ -250,7 +307,6 @@ pub fn use_reaction(
                         // We'll handle this by not counting them as positive
                     } else {
                         // Positive reaction (+, emoji, etc.)
-                        positive_count += 1;
                         if is_from_user {
                             user_liked = true;
                             user_unliked = false; // Reset - new positive reaction overrides previous unlike
@@ -310,48 +366,43 @@ pub fn use_reaction(
         });
     }));
 
+    let toast = consume_toast();
+
     // Clone for handler
     let event_id_handler = event_id.clone();
     let event_author_handler = event_author.clone();
+    let toast_like = toast.clone();
 
     // Toggle like handler with optimistic updates
     let toggle_like = use_callback(move |_: ()| {
         // Check preconditions
         if !*HAS_SIGNER.read() {
-            state.set(ReactionState::Error("No signer available".to_string()));
+            state.write().error = Some("No signer available".to_string());
             return;
         }
 
-        if matches!(*state.peek(), ReactionState::Pending) {
+        if !state.write().try_begin() {
             return; // Already processing
         }
 
         // Capture current state for potential rollback
-        let was_liked = *is_liked.peek();
-        let prev_count = *like_count.peek();
+        let previous = OptimisticToggle { active: *is_liked.peek(), count: *like_count.peek() };
+        let prev_reaction = user_reaction.peek().clone();
+        let was_liked = previous.active;
 
         // Determine action: like (+) or unlike (-)
         let content = if was_liked { "-" } else { "+" };
 
-        // Save previous user reaction for rollback
-        let prev_reaction = user_reaction.peek().clone();
-
         // Optimistic update
-        state.set(ReactionState::Pending);
-        is_liked.set(!was_liked);
-        if was_liked {
-            // Unliking - decrement count and clear reaction
-            like_count.set(prev_count.saturating_sub(1));
-            user_reaction.set(None);
-        } else {
-            // Liking - increment count and set to Like
-            like_count.set(prev_count.saturating_add(1));
-            user_reaction.set(Some(ReactionEmoji::Like));
-        }
+        let shown = previous.switched(!was_liked);
+        is_liked.set(shown.active);
+        like_count.set(shown.count);
+        user_reaction.set(if was_liked { None } else { Some(ReactionEmoji::Like) });
 
         let event_id_clone = event_id_handler.clone();
         let event_author_clone = event_author_handler.clone();
         let content_str = content.to_string();
+        let toast_api = toast_like.clone();
 
         spawn(async move {
             match publish_reaction(event_id_clone.clone(), event_author_clone, content_str, None).await {
@@ -362,35 +413,25 @@ pub fn use_reaction(
                         event_id_clone,
                         reaction_id
                     );
-                    state.set(ReactionState::Success);
+                    state.write().finish(Ok(()));
 
                     // Invalidate cache so next fetch gets fresh data
                     invalidate_interaction_counts(&event_id_clone);
-
-                    // Reset to Idle after a short delay
-                    #[cfg(not(target_arch = "wasm32"))]
-                    {
-                        tokio::time::sleep(Duration::from_millis(500)).await;
-                    }
-                    #[cfg(target_arch = "wasm32")]
-                    {
-                        gloo_timers::future::TimeoutFuture::new(500).await;
+                    if let Ok(id) = nostr_sdk::EventId::from_hex(&event_id_clone) {
+                        reactions_store::invalidate_reactions(&id);
                     }
-                    state.set(ReactionState::Idle);
                 }
                 Err(e) => {
                     log::error!("Failed to {} event: {}", if was_liked { "unlike" } else { "like" }, e);
 
+                    let message = format!("Failed to {}: {}", if was_liked { "unlike" } else { "like" }, e);
+                    toast_publish_error(&toast_api, message.clone());
+
                     // Rollback optimistic update
-                    is_liked.set(was_liked);
-                    like_count.set(prev_count);
+                    let settled = state.write().settle(previous, shown, Err(message));
+                    is_liked.set(settled.active);
+                    like_count.set(settled.count);
                     user_reaction.set(prev_reaction);
-
-                    state.set(ReactionState::Error(format!(
-                        "Failed to {}: {}",
-                        if was_liked { "unlike" } else { "like" },
-                        e
-                    )));
                 }
             }
         });
@@ -404,50 +445,39 @@ pub fn use_reaction(
     let react_with = use_callback(move |emoji: ReactionEmoji| {
         // Check preconditions
         if !*HAS_SIGNER.read() {
-            state.set(ReactionState::Error("No signer available".to_string()));
+            state.write().error = Some("No signer available".to_string());
+            return;
+        }
+
+        // Determine if this is a positive reaction (not unlike)
+        let is_positive = !matches!(emoji, ReactionEmoji::Unlike);
+
+        // Skip publishing unlike when user hasn't liked - no action needed
+        if !is_positive && !*is_liked.peek() {
             return;
         }
 
-        if matches!(*state.peek(), ReactionState::Pending) {
+        if !state.write().try_begin() {
             return; // Already processing
         }
 
         // Capture current state for potential rollback
-        let prev_liked = *is_liked.peek();
-        let prev_count = *like_count.peek();
+        let previous = OptimisticToggle { active: *is_liked.peek(), count: *like_count.peek() };
         let prev_reaction = user_reaction.peek().clone();
 
         // Get content and emoji tag from the ReactionEmoji
         let content = emoji.content();
         let emoji_tag = emoji.emoji_tag();
 
-        // Determine if this is a positive reaction (not unlike)
-        let is_positive = !matches!(emoji, ReactionEmoji::Unlike);
-
-        // Skip publishing unlike when user hasn't liked - no action needed
-        if !is_positive && !prev_liked {
-            return;
-        }
-
-        // Optimistic update
-        state.set(ReactionState::Pending);
-        if is_positive && !prev_liked {
-            // Adding a positive reaction when not already liked
-            is_liked.set(true);
-            like_count.set(prev_count.saturating_add(1));
-            user_reaction.set(Some(emoji.clone()));
-        } else if is_positive && prev_liked {
-            // Changing reaction - just update the emoji, count stays same
-            user_reaction.set(Some(emoji.clone()));
-        } else if !is_positive && prev_liked {
-            // Removing reaction (unlike)
-            is_liked.set(false);
-            like_count.set(prev_count.saturating_sub(1));
-            user_reaction.set(None);
-        }
+        // Optimistic update; changing the emoji keeps the count
+        let shown = previous.switched(is_positive);
+        is_liked.set(shown.active);
+        like_count.set(shown.count);
+        user_reaction.set(is_positive.then(|| emoji.clone()));
 
         let event_id_clone = event_id_react.clone();
         let event_author_clone = event_author_react.clone();
+        let toast_api = toast.clone();
 
         spawn(async move {
             match publish_reaction(event_id_clone.clone(), event_author_clone, content.clone(), emoji_tag).await {
@@ -458,40 +488,32 @@ pub fn use_reaction(
                         content,
                         reaction_id
                     );
-                    state.set(ReactionState::Success);
+                    state.write().finish(Ok(()));
 
                     // Invalidate cache so next fetch gets fresh data
                     invalidate_interaction_counts(&event_id_clone);
-
-                    // Reset to Idle after a short delay
-                    #[cfg(not(target_arch = "wasm32"))]
-                    {
-                        tokio::time::sleep(Duration::from_millis(500)).await;
-                    }
-                    #[cfg(target_arch = "wasm32")]
-                    {
-                        gloo_timers::future::TimeoutFuture::new(500).await;
+                    if let Ok(id) = nostr_sdk::EventId::from_hex(&event_id_clone) {
+                        reactions_store::invalidate_reactions(&id);
                     }
-                    state.set(ReactionState::Idle);
                 }
                 Err(e) => {
                     log::error!("Failed to react with '{}': {}", content, e);
 
+                    let message = format!("Failed to react: {}", e);
+                    toast_publish_error(&toast_api, message.clone());
+
                     // Rollback optimistic update
-                    is_liked.set(prev_liked);
-                    like_count.set(prev_count);
+                    let settled = state.write().settle(previous, shown, Err(message));
+                    is_liked.set(settled.active);
+                    like_count.set(settled.count);
                     user_reaction.set(prev_reaction);
-
-                    state.set(ReactionState::Error(format!(
-                        "Failed to react: {}",
-                        e
-                    )));
                 }
             }
         });
     });
 
     UseReaction {
+        event_id: reaction_event_id,
         is_liked,
         like_count,
         state,
@@ -511,3 +533,44 @@ pub fn format_count(count: usize) -> String {
         String::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_optimistic_like_rolls_back_on_failure() {
+        let mut state = ReactionState::default();
+        let before = OptimisticToggle { active: false, count: 4 };
+
+        assert!(state.try_begin());
+        let shown = before.switched(true);
+        assert_eq!(shown, OptimisticToggle { active: true, count: 5 });
+
+        // Publishing failed: the count goes back and the error is kept
+        let settled = state.settle(before, shown, Err("relay rejected".to_string()));
+        assert_eq!(settled, OptimisticToggle { active: false, count: 4 });
+        assert!(!state.pending);
+        assert_eq!(state.error.as_deref(), Some("relay rejected"));
+
+        // Publishing succeeded: the optimistic value stays
+        assert!(state.try_begin());
+        assert_eq!(state.settle(before, shown, Ok(())), shown);
+        assert_eq!(state, ReactionState::default());
+
+        // Unliking never goes below zero; switching to the same value is a no-op
+        assert_eq!(OptimisticToggle { active: true, count: 0 }.switched(false).count, 0);
+        assert_eq!(shown.switched(true), shown);
+    }
+
+    #[test]
+    fn test_double_tap_is_ignored_while_pending() {
+        let mut state = ReactionState::default();
+        assert!(state.try_begin());
+        assert!(!state.try_begin());
+
+        state.finish(Ok(()));
+        assert_eq!(state, ReactionState::default());
+        assert!(state.try_begin());
+    }
+}
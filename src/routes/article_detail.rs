@@ -7,7 +7,7 @@ use crate::utils::article_meta::{
     get_title, get_summary, get_image, get_published_at,
     get_hashtags, calculate_read_time
 };
-use crate::utils::{build_thread_tree, merge_pending_into_tree};
+use crate::utils::{build_thread_tree, merge_pending_into_tree, nip22};
 use crate::stores::pending_comments::get_pending_comments;
 use std::time::Duration;
 
@@ -30,6 +30,8 @@ pub fn ArticleDetail(naddr: String) -> Element {
 
     let has_signer = *crate::stores::nostr_client::HAS_SIGNER.read();
 
+    let edit_naddr = naddr.clone();
+
     // Decode naddr and fetch article - wait for client to be initialized
     use_effect(move || {
         let naddr_str = naddr.clone();
@@ -109,27 +111,15 @@ pub fn ArticleDetail(naddr: String) -> Element {
         let article_data = article.read();
 
         if let Some(event) = article_data.as_ref() {
-            let event_id = event.id;
+            let event = event.clone();
 
             spawn(async move {
                 loading_comments.set(true);
 
-                // Fetch Kind 1111 (NIP-22 Comment) events that reference this article
-                let filter = Filter::new()
-                    .kind(Kind::Comment)
-                    .event(event_id)
-                    .limit(500);
-
-                match crate::stores::nostr_client::fetch_events_aggregated(filter, Duration::from_secs(10)).await {
-                    Ok(mut comment_events) => {
-                        comment_events.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-                        log::info!("Loaded {} NIP-22 comments", comment_events.len());
-                        comments.set(comment_events);
-                    }
-                    Err(e) => {
-                        log::error!("Failed to fetch comments: {}", e);
-                    }
-                }
+                // NIP-22 comments scoped to this article, at any depth
+                let comment_events = nip22::fetch_comments(&event).await;
+                log::info!("Loaded {} NIP-22 comments", comment_events.len());
+                comments.set(comment_events);
 
                 loading_comments.set(false);
             });
@@ -266,6 +256,9 @@ pub fn ArticleDetail(naddr: String) -> Element {
                             .to_string();
 
                         let timestamp = format_timestamp(published_at);
+                        let is_own_article = crate::stores::signer::SIGNER_INFO.read()
+                            .as_ref()
+                            .is_some_and(|info| info.public_key == author_pubkey);
 
                         rsx! {
                             article {
@@ -323,6 +316,14 @@ pub fn ArticleDetail(naddr: String) -> Element {
                                             }
                                         }
                                     }
+
+                                    if is_own_article {
+                                        Link {
+                                            to: Route::ArticleEdit { naddr: edit_naddr.clone() },
+                                            class: "px-4 py-2 text-sm border border-border rounded-lg hover:bg-accent transition",
+                                            "Edit"
+                                        }
+                                    }
                                 }
 
                                 // Hashtags
@@ -491,7 +492,7 @@ pub fn ArticleDetail(naddr: String) -> Element {
                                         // Only build thread tree after loading completes to avoid caching empty results
                                         {
                                             let comment_vec = comments.read().clone();
-                                            let confirmed_tree = build_thread_tree(comment_vec, &event.id);
+                                            let confirmed_tree = build_thread_tree(comment_vec, &event.id, None);
                                             // Merge pending comments for optimistic display
                                             let pending = get_pending_comments(&event.id);
                                             let thread_tree = merge_pending_into_tree(confirmed_tree, pending, &event.id);
@@ -511,8 +512,10 @@ pub fn ArticleDetail(naddr: String) -> Element {
                                                         class: "divide-y divide-border",
                                                         for node in thread_tree {
                                                             ThreadedComment {
+                                                                key: "{node.event.id}",
                                                                 node: node.clone(),
-                                                                depth: 0
+                                                                depth: 0,
+                                                                comment_root: Some(event.clone())
                                                             }
                                                         }
                                                     }
@@ -531,18 +534,10 @@ pub fn ArticleDetail(naddr: String) -> Element {
                                         on_success: move |_| {
                                             show_comment_composer.set(false);
                                             // Refresh comments
-                                            let event_id = event.id;
+                                            let event = event.clone();
                                             spawn(async move {
                                                 loading_comments.set(true);
-                                                let filter = Filter::new()
-                                                    .kind(Kind::Comment)
-                                                    .event(event_id)
-                                                    .limit(500);
-
-                                                if let Ok(mut comment_events) = crate::stores::nostr_client::fetch_events_aggregated(filter, Duration::from_secs(10)).await {
-                                                    comment_events.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-                                                    comments.set(comment_events);
-                                                }
+                                                comments.set(nip22::fetch_comments(&event).await);
                                                 loading_comments.set(false);
                                             });
                                         }
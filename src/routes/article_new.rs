@@ -1,18 +1,131 @@
 use dioxus::prelude::*;
-use crate::stores::auth_store;
+use crate::stores::article_draft::{self, ArticleDraft, AUTOSAVE_INTERVAL_MS, NEW_ARTICLE_DRAFT_ID};
+use crate::stores::{auth_store, nostr_client};
 use crate::components::MarkdownEditor;
+use crate::utils::addressable::{self, EditTarget};
+use crate::utils::article_meta;
+
+/// Content handed over from the note composer ("Publish as article")
+pub static ARTICLE_SEED: GlobalSignal<Option<String>> = Signal::global(|| None);
 
 #[component]
 pub fn ArticleNew() -> Element {
+    rsx! { ArticleEditor { naddr: None } }
+}
+
+/// Edit a published article, keeping its d-tag so relays replace it
+#[component]
+pub fn ArticleEdit(naddr: String) -> Element {
+    rsx! { ArticleEditor { naddr: Some(naddr) } }
+}
+
+#[component]
+fn ArticleEditor(naddr: Option<String>) -> Element {
     let navigator = navigator();
+    // Edits autosave separately from the new-article draft
+    let draft_id = use_hook(|| match &naddr {
+        Some(naddr) => format!("edit:{}", naddr),
+        None => NEW_ARTICLE_DRAFT_ID.to_string(),
+    });
     let mut title = use_signal(|| String::new());
     let mut summary = use_signal(|| String::new());
-    let content = use_signal(|| String::new());
+    let mut content = use_signal(|| ARTICLE_SEED.write().take().unwrap_or_default());
     let mut identifier = use_signal(|| String::new());
     let mut cover_image = use_signal(|| String::new());
     let mut hashtags = use_signal(|| String::new());
     let mut is_publishing = use_signal(|| false);
     let mut error_message = use_signal(|| Option::<String>::None);
+    // Draft left by an earlier session, waiting for the user to restore or discard it
+    let mut saved_draft = use_signal(|| article_draft::load_draft(&draft_id));
+    let mut target = use_signal(EditTarget::default);
+    // published_at of the article being edited
+    let mut original_published_at = use_signal(|| None::<u64>);
+    let mut loading_existing = use_signal(|| naddr.is_some());
+
+    // Load the article being edited
+    let naddr_for_load = naddr.clone();
+    let load_draft_id = draft_id.clone();
+    use_effect(move || {
+        let Some(naddr) = naddr_for_load.clone() else {
+            return;
+        };
+        // Loaded once; a later run would overwrite a restored draft
+        if !*nostr_client::CLIENT_INITIALIZED.read() || !*loading_existing.peek() {
+            return;
+        }
+        let my_pubkey = auth_store::get_pubkey().unwrap_or_default();
+        let draft_id = load_draft_id.clone();
+
+        spawn(async move {
+            let result = async {
+                let address = addressable::decode_edit_naddr(&naddr, 30023, &my_pubkey)?;
+                nostr_client::fetch_article_by_coordinate(address.pubkey, address.identifier)
+                    .await?
+                    .ok_or_else(|| "Article not found".to_string())
+            }
+            .await;
+
+            match result.and_then(|event| {
+                let identifier = article_meta::get_identifier(&event).ok_or("Article has no identifier")?;
+                Ok((event, identifier))
+            }) {
+                Ok((event, loaded_identifier)) => {
+                    title.set(article_meta::get_title(&event));
+                    summary.set(article_meta::get_summary(&event).unwrap_or_default());
+                    content.set(event.content.clone());
+                    identifier.set(loaded_identifier.clone());
+                    cover_image.set(article_meta::get_image(&event).unwrap_or_default());
+                    hashtags.set(article_meta::get_hashtags(&event).join(", "));
+                    original_published_at.set(Some(article_meta::get_published_at(&event)));
+                    target.set(EditTarget::Existing { identifier: loaded_identifier });
+
+                    // A draft saved before this version was published is stale
+                    let saved_at = saved_draft.peek().as_ref().map(|draft| draft.saved_at);
+                    if saved_at.is_some_and(|saved_at| saved_at <= event.created_at.as_secs()) {
+                        saved_draft.set(None);
+                        article_draft::clear_draft(&draft_id);
+                    }
+                }
+                Err(e) => error_message.set(Some(format!("Can't edit this article: {}", e))),
+            }
+            loading_existing.set(false);
+        });
+    });
+
+    // Autosave every few seconds while the editor is open
+    let autosave_draft_id = draft_id.clone();
+    use_hook(move || {
+        spawn(async move {
+            let mut last_saved: Option<ArticleDraft> = None;
+            loop {
+                gloo_timers::future::TimeoutFuture::new(AUTOSAVE_INTERVAL_MS).await;
+
+                // Don't overwrite the old draft before the user decided about it
+                if saved_draft.peek().is_some() || *is_publishing.peek() || *loading_existing.peek() {
+                    continue;
+                }
+
+                let draft = ArticleDraft {
+                    draft_id: autosave_draft_id.clone(),
+                    title: title.peek().clone(),
+                    summary: summary.peek().clone(),
+                    content: content.peek().clone(),
+                    identifier: identifier.peek().clone(),
+                    cover_image: cover_image.peek().clone(),
+                    hashtags: hashtags.peek().clone(),
+                    saved_at: 0,
+                };
+                if last_saved.as_ref().is_some_and(|last| last.same_content(&draft)) {
+                    continue;
+                }
+
+                match article_draft::save_draft(draft.clone()) {
+                    Ok(()) => last_saved = Some(draft),
+                    Err(e) => log::warn!("Failed to autosave article draft: {}", e),
+                }
+            }
+        })
+    });
 
     // Check if user is authenticated
     let is_authenticated = use_memo(move || auth_store::AUTH_STATE.read().is_authenticated);
@@ -20,10 +133,14 @@ pub fn ArticleNew() -> Element {
     // Validation
     let title_chars = title.read().chars().count();
     let content_chars = content.read().chars().count();
+    let is_edit = target.read().is_edit();
+    // An edit whose article failed to load must not publish under a new d-tag
     let can_publish = title_chars > 0
         && content_chars > 0
         && identifier.read().len() > 0
-        && !*is_publishing.read();
+        && !*is_publishing.read()
+        && !*loading_existing.read()
+        && (naddr.is_none() || is_edit);
 
     // Handle close
     let handle_close = move |_| {
@@ -31,6 +148,7 @@ pub fn ArticleNew() -> Element {
     };
 
     // Handle publishing
+    let publish_draft_id = draft_id.clone();
     let handle_publish = move |_| {
         if !can_publish {
             return;
@@ -39,7 +157,16 @@ pub fn ArticleNew() -> Element {
         let title_val = title.read().clone();
         let summary_val = summary.read().clone();
         let content_val = content.read().clone();
-        let identifier_val = identifier.read().clone();
+        let identifier_val = match target.read().identifier_for_publish(|| identifier.read().clone()) {
+            Ok(identifier_val) => identifier_val,
+            Err(e) => {
+                error_message.set(Some(e));
+                return;
+            }
+        };
+        let published_at = *original_published_at.read();
+        let draft_id = publish_draft_id.clone();
+        let naddr = naddr.clone();
         let cover_image_val = cover_image.read().clone();
         let hashtags_val = hashtags.read().clone();
 
@@ -61,11 +188,16 @@ pub fn ArticleNew() -> Element {
                 identifier_val,
                 cover_image_val,
                 tags_vec,
+                published_at,
             ).await {
                 Ok(event_id) => {
                     log::info!("Article published successfully: {}", event_id);
+                    article_draft::clear_draft(&draft_id);
                     is_publishing.set(false);
-                    navigator.push(crate::routes::Route::Articles {});
+                    match naddr {
+                        Some(naddr) => navigator.push(crate::routes::Route::ArticleDetail { naddr }),
+                        None => navigator.push(crate::routes::Route::Articles {}),
+                    };
                 }
                 Err(e) => {
                     log::error!("Failed to publish article: {}", e);
@@ -76,18 +208,13 @@ pub fn ArticleNew() -> Element {
         });
     };
 
-    // Auto-generate identifier from title if empty
+    // Auto-generate identifier from title if empty (never for an edit)
     use_effect(move || {
+        if target.read().is_edit() || *loading_existing.read() {
+            return;
+        }
         if identifier.read().is_empty() && !title.read().is_empty() {
-            let slug = title.read()
-                .to_lowercase()
-                .chars()
-                .map(|c| if c.is_alphanumeric() { c } else { '-' })
-                .collect::<String>()
-                .split('-')
-                .filter(|s| !s.is_empty())
-                .collect::<Vec<_>>()
-                .join("-");
+            let slug = crate::utils::slugify(&title.read());
 
             if !slug.is_empty() {
                 identifier.set(slug);
@@ -129,7 +256,7 @@ pub fn ArticleNew() -> Element {
                         }
                         h1 {
                             class: "text-2xl font-bold",
-                            "Write Article"
+                            if is_edit { "Edit Article" } else { "Write Article" }
                         }
                     }
 
@@ -144,6 +271,8 @@ pub fn ArticleNew() -> Element {
 
                         if *is_publishing.read() {
                             "Publishing..."
+                        } else if is_edit {
+                            "Update"
                         } else {
                             "Publish"
                         }
@@ -155,6 +284,53 @@ pub fn ArticleNew() -> Element {
             div {
                 class: "max-w-6xl mx-auto px-4 py-8",
 
+                // Restore draft prompt, once an edited article has loaded so
+                // the load can't overwrite the restored draft
+                if let Some(draft) = saved_draft.read().clone().filter(|_| !*loading_existing.read()) {
+                    div {
+                        class: "mb-4 p-4 bg-blue-50 dark:bg-blue-900/20 border border-blue-200 dark:border-blue-800 rounded-lg flex items-center justify-between gap-4",
+                        span {
+                            class: "text-sm",
+                            if draft.title.is_empty() {
+                                "You have an unsaved article draft."
+                            } else {
+                                "You have an unsaved draft of \"{draft.title}\"."
+                            }
+                        }
+                        div {
+                            class: "flex gap-2 shrink-0",
+                            button {
+                                class: "px-4 py-1 text-sm bg-blue-500 hover:bg-blue-600 text-white rounded-full transition",
+                                onclick: move |_| {
+                                    if let Some(draft) = saved_draft.write().take() {
+                                        title.set(draft.title);
+                                        summary.set(draft.summary);
+                                        content.set(draft.content);
+                                        // An edit keeps the loaded d-tag
+                                        if !target.peek().is_edit() {
+                                            identifier.set(draft.identifier);
+                                        }
+                                        cover_image.set(draft.cover_image);
+                                        hashtags.set(draft.hashtags);
+                                    }
+                                },
+                                "Restore draft"
+                            }
+                            button {
+                                class: "px-4 py-1 text-sm border border-border hover:bg-accent rounded-full transition",
+                                onclick: {
+                                    let draft_id = draft_id.clone();
+                                    move |_| {
+                                        saved_draft.set(None);
+                                        article_draft::clear_draft(&draft_id);
+                                    }
+                                },
+                                "Discard"
+                            }
+                        }
+                    }
+                }
+
                 // Error message
                 if let Some(err) = error_message.read().as_ref() {
                     div {
@@ -193,11 +369,16 @@ pub fn ArticleNew() -> Element {
                             class: "w-full px-4 py-2 bg-background border border-border rounded-lg focus:outline-none focus:ring-2 focus:ring-blue-500",
                             placeholder: "unique-article-identifier",
                             value: "{identifier}",
+                            disabled: is_edit,
                             oninput: move |e| identifier.set(e.value()),
                         }
                         p {
                             class: "mt-1 text-xs text-muted-foreground",
-                            "Unique identifier for this article. Auto-generated from title."
+                            if is_edit {
+                                "The identifier can't change when editing: a new one would publish a separate article."
+                            } else {
+                                "Unique identifier for this article. Auto-generated from title."
+                            }
                         }
                     }
 
@@ -16,6 +16,63 @@ pub fn Bookmarks() -> Element {
     let mut loaded_count = use_signal(|| 0usize);
     const BATCH_SIZE: usize = 50;
 
+    // Bookmark sets: None shows every bookmark
+    let mut selected_set = use_signal(|| None::<String>);
+    let mut set_events = use_signal(|| Vec::<NostrEvent>::new());
+    let mut set_loading = use_signal(|| false);
+    let mut new_set_name = use_signal(String::new);
+    let mut creating_set = use_signal(|| false);
+
+    // Load the user's bookmark sets
+    use_effect(move || {
+        if !*nostr_client::CLIENT_INITIALIZED.read() || !auth_store::is_authenticated() {
+            return;
+        }
+        spawn(async move {
+            if let Err(e) = bookmarks::list_bookmark_sets().await {
+                log::error!("Failed to load bookmark sets: {}", e);
+            }
+        });
+    });
+
+    // Load the events of the selected set
+    use_effect(move || {
+        let Some(set_d) = selected_set.read().clone() else {
+            return;
+        };
+        let Some(set) = bookmarks::BOOKMARK_SETS.read().iter().find(|set| set.identifier == set_d).cloned() else {
+            return;
+        };
+
+        set_loading.set(true);
+        spawn(async move {
+            match bookmarks::fetch_bookmark_set_events(&set).await {
+                Ok(events) => set_events.set(events),
+                Err(e) => error.set(Some(e)),
+            }
+            set_loading.set(false);
+        });
+    });
+
+    let mut create_set = move |_: ()| {
+        let name = new_set_name.read().trim().to_string();
+        if name.is_empty() || *creating_set.read() {
+            return;
+        }
+        creating_set.set(true);
+        spawn(async move {
+            match bookmarks::create_bookmark_set(name).await {
+                Ok(set) => {
+                    new_set_name.set(String::new());
+                    set_events.set(Vec::new());
+                    selected_set.set(Some(set.identifier));
+                }
+                Err(e) => error.set(Some(e)),
+            }
+            creating_set.set(false);
+        });
+    };
+
     // Load initial batch of bookmarks on mount
     use_effect(move || {
         let client_initialized = *nostr_client::CLIENT_INITIALIZED.read();
@@ -128,6 +185,52 @@ pub fn Bookmarks() -> Element {
                         "🔖 Bookmarks"
                     }
                 }
+
+                // Set selector
+                if auth.is_authenticated {
+                    div {
+                        class: "px-4 pb-3 flex flex-wrap items-center gap-2",
+                        button {
+                            class: if selected_set.read().is_none() { "px-3 py-1 rounded-full text-sm bg-primary text-primary-foreground" } else { "px-3 py-1 rounded-full text-sm bg-accent hover:bg-accent/80" },
+                            onclick: move |_| selected_set.set(None),
+                            "All bookmarks"
+                        }
+                        for set in bookmarks::BOOKMARK_SETS.read().iter().cloned() {
+                            button {
+                                key: "{set.identifier}",
+                                class: if selected_set.read().as_deref() == Some(set.identifier.as_str()) { "px-3 py-1 rounded-full text-sm bg-primary text-primary-foreground" } else { "px-3 py-1 rounded-full text-sm bg-accent hover:bg-accent/80" },
+                                onclick: {
+                                    let identifier = set.identifier.clone();
+                                    move |_| {
+                                        set_events.set(Vec::new());
+                                        selected_set.set(Some(identifier.clone()));
+                                    }
+                                },
+                                "{set.title} ({set.items.len()})"
+                            }
+                        }
+                        div {
+                            class: "flex items-center gap-1",
+                            input {
+                                class: "px-3 py-1 text-sm rounded-full border border-border bg-background w-32",
+                                placeholder: "New set",
+                                value: "{new_set_name}",
+                                oninput: move |e| new_set_name.set(e.value()),
+                                onkeydown: move |e| {
+                                    if e.key() == Key::Enter {
+                                        create_set(());
+                                    }
+                                }
+                            }
+                            button {
+                                onclick: move |_| create_set(()),
+                                class: "px-2 py-1 text-sm rounded-full bg-accent hover:bg-accent/80 disabled:opacity-50",
+                                disabled: *creating_set.read() || new_set_name.read().trim().is_empty(),
+                                "+"
+                            }
+                        }
+                    }
+                }
             }
 
             // Not authenticated
@@ -159,8 +262,39 @@ pub fn Bookmarks() -> Element {
                     }
                 }
 
-                // Loading state
-                if !*nostr_client::CLIENT_INITIALIZED.read() || (*loading.read() && bookmarked_events.read().is_empty()) {
+                if selected_set.read().is_some() {
+                    // Selected bookmark set
+                    if *set_loading.read() && set_events.read().is_empty() {
+                        ClientInitializing {}
+                    } else if set_events.read().is_empty() {
+                        div {
+                            class: "text-center py-12",
+                            div {
+                                class: "text-6xl mb-4",
+                                "🗂️"
+                            }
+                            h3 {
+                                class: "text-xl font-semibold mb-2",
+                                "This set is empty"
+                            }
+                            p {
+                                class: "text-muted-foreground",
+                                "Add posts to it from All bookmarks"
+                            }
+                        }
+                    } else {
+                        div {
+                            class: "space-y-4 p-4",
+                            for event in set_events.read().iter() {
+                                NoteCard {
+                                    key: "{event.id}",
+                                    event: event.clone(),
+                                    collapsible: true
+                                }
+                            }
+                        }
+                    }
+                } else if !*nostr_client::CLIENT_INITIALIZED.read() || (*loading.read() && bookmarked_events.read().is_empty()) {
                     // Show client initializing animation during:
                     // 1. Client initialization
                     // 2. Initial bookmarks load (loading + no bookmarks, regardless of error state)
@@ -193,10 +327,15 @@ pub fn Bookmarks() -> Element {
                             "Showing {bookmarked_events.read().len()} of {bookmarks::get_bookmarks_count()} bookmarked post(s)"
                         }
                         for event in bookmarked_events.read().iter() {
-                            NoteCard {
+                            div {
                                 key: "{event.id}",
-                                event: event.clone(),
-                                collapsible: true
+                                NoteCard {
+                                    event: event.clone(),
+                                    collapsible: true
+                                }
+                                if !bookmarks::BOOKMARK_SETS.read().is_empty() {
+                                    AddToSetSelect { event_id: event.id.to_hex() }
+                                }
                             }
                         }
 
@@ -227,3 +366,43 @@ pub fn Bookmarks() -> Element {
         }
     }
 }
+
+/// "Add to set" picker shown under a bookmarked note
+#[component]
+fn AddToSetSelect(event_id: String) -> Element {
+    let mut status = use_signal(|| None::<String>);
+
+    rsx! {
+        div {
+            class: "flex items-center gap-2 mt-1 text-sm text-muted-foreground",
+            select {
+                class: "px-2 py-1 rounded border border-border bg-background",
+                value: "",
+                onchange: move |e| {
+                    let set_d = e.value();
+                    if set_d.is_empty() {
+                        return;
+                    }
+                    let item = bookmarks::BookmarkItem::Event(event_id.clone());
+                    spawn(async move {
+                        match bookmarks::add_to_set(&set_d, item).await {
+                            Ok(()) => status.set(Some("Added".to_string())),
+                            Err(e) => status.set(Some(e)),
+                        }
+                    });
+                },
+                option { value: "", "Add to set…" }
+                for set in bookmarks::BOOKMARK_SETS.read().iter() {
+                    option {
+                        key: "{set.identifier}",
+                        value: "{set.identifier}",
+                        "{set.title}"
+                    }
+                }
+            }
+            if let Some(status) = status.read().as_ref() {
+                span { "{status}" }
+            }
+        }
+    }
+}
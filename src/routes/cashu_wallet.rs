@@ -274,6 +274,9 @@ pub fn CashuWallet() -> Element {
                         on_pay_request: move |_| show_pay_request_modal.set(true),
                     }
 
+                    // Proofs left on rotated keysets
+                    crate::components::CashuKeysetMigration {}
+
                     // Tokens section
                     div {
                         class: "mt-6",
@@ -311,6 +314,9 @@ pub fn CashuWallet() -> Element {
                         }
                         crate::components::TransactionHistory {}
                     }
+
+                    // Events still waiting to be published
+                    crate::components::CashuPendingEvents {}
                 }
             }
 
@@ -25,7 +25,7 @@ pub fn DMs() -> Element {
         error.set(None);
 
         spawn(async move {
-            match dms::init_dms().await {
+            match dms::refresh_dms().await {
                 Ok(_) => {
                     log::info!("DMs loaded successfully");
                 }
@@ -50,7 +50,7 @@ pub fn DMs() -> Element {
                 // Only refresh if user is authenticated
                 if auth_store::is_authenticated() {
                     log::debug!("Auto-refreshing DMs...");
-                    let _ = dms::init_dms().await;
+                    let _ = dms::refresh_dms().await;
                 }
             }
         });
@@ -64,7 +64,7 @@ pub fn DMs() -> Element {
 
         refreshing.set(true);
         spawn(async move {
-            match dms::init_dms().await {
+            match dms::refresh_dms().await {
                 Ok(_) => {
                     log::info!("DMs refreshed successfully");
                 }
@@ -325,6 +325,8 @@ fn ConversationListItem(
         .map(|m| time::format_relative_time(m.created_at()))
         .unwrap_or_else(|| "".to_string());
 
+    let unread = dms::unread_count(&conversation.pubkey);
+
     let bg_class = if selected {
         "bg-accent"
     } else {
@@ -369,10 +371,10 @@ fn ConversationListItem(
                 }
 
                 // Unread indicator
-                if conversation.unread_count > 0 {
+                if unread > 0 {
                     div {
                         class: "w-6 h-6 bg-blue-500 rounded-full flex items-center justify-center text-white text-xs font-bold flex-shrink-0",
-                        "{conversation.unread_count}"
+                        "{unread}"
                     }
                 }
             }
@@ -435,6 +437,8 @@ fn ConversationView(pubkey: String) -> Element {
 
                 log::info!("Decrypted {} messages", decrypted.len());
                 decrypted_messages.set(decrypted);
+                // Open conversations stay read as new messages arrive
+                dms::mark_conversation_read(&pk);
             } else {
                 log::warn!("No conversation found for: {}", pk);
             }
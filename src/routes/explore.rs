@@ -1,13 +1,18 @@
 //! Explore/Discover Page
 //!
 //! Displays a feed of notes recommended by a Data Vending Machine (DVM).
-//! Users can select which DVM provider to use via a gear icon.
+//! Users can select which DVM provider to use via a gear icon. Trending
+//! hashtags from recent notes are shown above the feed.
 
 use dioxus::prelude::*;
-use crate::stores::{nostr_client, dvm_store};
+use crate::stores::{nostr_client, dvm_store, settings_store};
 use crate::stores::dvm_store::{DVM_FEED_EVENTS, DVM_FEED_LOADING, DVM_FEED_ERROR, DVM_PROVIDERS, SELECTED_DVM_PROVIDER};
 use crate::components::{NoteCard, ClientInitializing, DvmSelectorModal};
+use crate::routes::Route;
 use crate::services::aggregation::{InteractionCounts, fetch_interaction_counts_batch};
+use crate::services::trending;
+use crate::utils::content_parser::{note_media, MediaKind};
+use crate::utils::event::content_warning;
 use nostr_sdk::PublicKey;
 use std::collections::HashMap;
 use std::time::Duration;
@@ -16,12 +21,16 @@ use std::time::Duration;
 #[component]
 pub fn Explore() -> Element {
     let mut show_selector = use_signal(|| false);
+    // Show only notes with images or videos, as a grid of thumbnails
+    let mut media_grid = use_signal(|| false);
     let mut refresh_trigger = use_signal(|| 0);
 
     // Interaction counts cache (event_id -> counts) for batch optimization
     let mut interaction_counts = use_signal(|| HashMap::<String, InteractionCounts>::new());
     let mut interactions_loaded = use_signal(|| false);
 
+    let mut trending_hashtags = use_signal(Vec::<(String, usize)>::new);
+
     let feed_loading = *DVM_FEED_LOADING.read();
     let feed_error = DVM_FEED_ERROR.read().clone();
     let feed_events = DVM_FEED_EVENTS.read().clone();
@@ -47,6 +56,12 @@ pub fn Explore() -> Element {
             }
         });
 
+        // Sample recent notes for trending hashtags
+        let window_hours = settings_store::SETTINGS.peek().trending.window_hours();
+        spawn(async move {
+            trending_hashtags.set(trending::get_trending_hashtags(window_hours).await);
+        });
+
         // Request content feed
         let provider = *SELECTED_DVM_PROVIDER.peek();
         spawn(async move {
@@ -135,6 +150,17 @@ pub fn Explore() -> Element {
                                 "🔄"
                             }
                         }
+                        // Feed / media grid toggle
+                        button {
+                            class: if *media_grid.read() {
+                                "p-2 bg-accent rounded-full transition"
+                            } else {
+                                "p-2 hover:bg-accent rounded-full transition"
+                            },
+                            onclick: move |_| media_grid.toggle(),
+                            title: if *media_grid.read() { "Show all notes" } else { "Show media only" },
+                            if *media_grid.read() { "☰" } else { "▦" }
+                        }
                         // Settings/DVM selector button
                         button {
                             class: "p-2 hover:bg-accent rounded-full transition",
@@ -146,6 +172,29 @@ pub fn Explore() -> Element {
                 }
             }
 
+            // Trending hashtags
+            if !trending_hashtags.read().is_empty() {
+                div {
+                    class: "px-4 py-3 border-b border-border",
+                    p {
+                        class: "text-xs font-medium text-muted-foreground mb-2",
+                        "Trending topics"
+                    }
+                    div {
+                        class: "flex flex-wrap gap-2",
+                        for (tag, count) in trending_hashtags.read().iter().cloned() {
+                            Link {
+                                key: "{tag}",
+                                to: Route::Hashtag { tag: tag.clone() },
+                                class: "px-3 py-1 rounded-full bg-accent/50 hover:bg-accent text-sm transition",
+                                title: "{count} people posted with #{tag}",
+                                "#{tag}"
+                            }
+                        }
+                    }
+                }
+            }
+
             // Content
             if !*nostr_client::CLIENT_INITIALIZED.read() {
                 ClientInitializing {}
@@ -210,11 +259,18 @@ pub fn Explore() -> Element {
                         }
                     }
                 }
+            } else if *media_grid.read() {
+                MediaGrid {
+                    events: feed_events.iter()
+                        .filter(|event| !settings_store::is_event_muted_by_keyword(event))
+                        .cloned()
+                        .collect::<Vec<_>>()
+                }
             } else {
                 // Feed content
                 div {
                     class: "divide-y divide-border",
-                    for event in feed_events.iter() {
+                    for event in feed_events.iter().filter(|event| !settings_store::is_event_muted_by_keyword(event)) {
                         NoteCard {
                             key: "{event.id.to_hex()}",
                             event: event.clone(),
@@ -242,3 +298,93 @@ pub fn Explore() -> Element {
     }
 }
 
+/// Explore notes with images or videos, one thumbnail tile per note
+///
+/// Muted notes and blocked authors are left out, as in the note feed. Notes
+/// with a NIP-36 content warning get a placeholder tile and don't load their
+/// media; the warning can be lifted on the note itself.
+#[component]
+fn MediaGrid(events: Vec<nostr_sdk::Event>) -> Element {
+    let nav = navigator();
+
+    // Muted note IDs and blocked pubkeys (hex), from the mute list
+    let hidden = use_resource(|| async move {
+        let muted_posts = nostr_client::get_muted_posts().await.unwrap_or_default();
+        let blocked_users = nostr_client::get_blocked_users().await.unwrap_or_default();
+        (muted_posts, blocked_users)
+    });
+    let Some((muted_posts, blocked_users)) = hidden.read().clone() else {
+        return rsx! {};
+    };
+
+    let tiles: Vec<_> = events
+        .iter()
+        .filter(|event| {
+            !muted_posts.contains(&event.id.to_hex()) && !blocked_users.contains(&event.pubkey.to_hex())
+        })
+        .filter_map(|event| {
+            let media = note_media(&event.content, event.tags.as_slice());
+            let (url, kind) = media.first()?.clone();
+            let sensitive = content_warning(event.tags.iter()).is_some();
+            Some((event.id.to_hex(), url, kind, media.len(), sensitive))
+        })
+        .collect();
+
+    if tiles.is_empty() {
+        return rsx! {
+            div {
+                class: "p-6 text-center text-muted-foreground text-sm",
+                "No images or videos in this feed yet."
+            }
+        };
+    }
+
+    rsx! {
+        div {
+            class: "grid grid-cols-2 sm:grid-cols-3 gap-1 p-1",
+            for (note_id, url, kind, count, sensitive) in tiles {
+                button {
+                    key: "{note_id}",
+                    class: "relative aspect-square overflow-hidden bg-muted hover:opacity-90 transition",
+                    onclick: {
+                        let note_id = note_id.clone();
+                        move |_| {
+                            nav.push(Route::Note { note_id: note_id.clone(), from_voice: None });
+                        }
+                    },
+                    if sensitive {
+                        span {
+                            class: "absolute inset-0 flex flex-col items-center justify-center gap-1 text-xs font-medium text-muted-foreground",
+                            span { class: "text-2xl", "⚠️" }
+                            "Content warning"
+                        }
+                    } else if kind == MediaKind::Video {
+                        video {
+                            class: "w-full h-full object-cover",
+                            src: "{url}",
+                            preload: "metadata",
+                            muted: true,
+                        }
+                        span {
+                            class: "absolute inset-0 flex items-center justify-center text-white text-3xl drop-shadow",
+                            "▶"
+                        }
+                    } else {
+                        img {
+                            class: "w-full h-full object-cover",
+                            src: "{url}",
+                            loading: "lazy",
+                            alt: "",
+                        }
+                    }
+                    if count > 1 {
+                        span {
+                            class: "absolute top-1 right-1 px-1.5 py-0.5 text-xs font-medium rounded bg-black/60 text-white",
+                            "⧉ {count}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
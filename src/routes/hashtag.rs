@@ -1,7 +1,7 @@
 use dioxus::prelude::*;
-use crate::stores::nostr_client;
+use crate::stores::{auth_store, interests, nostr_client, settings_store};
 use crate::components::{NoteCard, ClientInitializing};
-use crate::hooks::use_infinite_scroll;
+use crate::hooks::{use_infinite_scroll, use_page_dedup};
 use nostr_sdk::{Event, Filter, Kind, Timestamp};
 use std::time::Duration;
 
@@ -14,9 +14,27 @@ pub fn Hashtag(tag: String) -> Element {
     let mut refresh_trigger = use_signal(|| 0);
     let mut has_more = use_signal(|| true);
     let mut oldest_timestamp = use_signal(|| None::<u64>);
+    let mut dedup = use_page_dedup(|event: &Event| event.id);
+
+    let mut updating_follow = use_signal(|| false);
 
     let tag_clone = tag.clone();
     let tag_for_load = tag.clone();
+    let tag_for_follow = tag.clone();
+
+    // Followed hashtags come from the interest list; load it once
+    use_effect(move || {
+        if !*nostr_client::CLIENT_INITIALIZED.read() || !auth_store::is_authenticated() {
+            return;
+        }
+        if interests::INTEREST_LIST.peek().is_none() {
+            spawn(async move {
+                if let Err(e) = interests::load_interest_list().await {
+                    log::warn!("Failed to load followed hashtags: {}", e);
+                }
+            });
+        }
+    });
 
     // Load initial feed
     use_effect(move || {
@@ -44,6 +62,7 @@ pub fn Hashtag(tag: String) -> Element {
                     // Always enable has_more on initial/refresh load
                     // Only disable when load_more gets 0 results from relay
                     has_more.set(true);
+                    dedup.write().reset(&feed_events);
                     events.set(feed_events);
                     loading.set(false);
                 }
@@ -73,36 +92,17 @@ pub fn Hashtag(tag: String) -> Element {
         spawn(async move {
             match load_hashtag_feed(&hashtag, Some(until)).await {
                 Ok(new_events) => {
-                    // Stop pagination only when relay returns 0 events
-                    if new_events.is_empty() {
-                        has_more.set(false);
-                        loading.set(false);
-                        return;
-                    }
-
-                    // Deduplicate: build set of existing event IDs
-                    let existing_ids: std::collections::HashSet<_> = events.read().iter()
-                        .map(|e| e.id)
-                        .collect();
-                    let current = events.read().clone();
-
-                    // Filter out duplicates
-                    let unique_events: Vec<_> = new_events.iter()
-                        .filter(|e| !existing_ids.contains(&e.id))
-                        .cloned()
-                        .collect();
-
                     // Update oldest timestamp from all new events (not just unique)
-                    // This ensures we advance pagination even if all were duplicates
                     if let Some(last_event) = new_events.last() {
                         oldest_timestamp.set(Some(last_event.created_at.as_secs()));
                     }
 
-                    // Append unique events
-                    if !unique_events.is_empty() {
-                        let mut updated = current;
-                        updated.extend(unique_events);
-                        events.set(updated);
+                    // Drop events already shown; only an empty page from the relays ends the feed
+                    let unique_events = dedup.write().filter_page(new_events);
+                    if dedup.read().at_end() {
+                        has_more.set(false);
+                    } else {
+                        events.write().extend(unique_events);
                     }
 
                     loading.set(false);
@@ -123,6 +123,8 @@ pub fn Hashtag(tag: String) -> Element {
     );
 
 
+    let is_following = interests::is_following_hashtag(&tag);
+
     rsx! {
         div {
             class: "min-h-screen",
@@ -143,20 +145,57 @@ pub fn Hashtag(tag: String) -> Element {
                             "{tag}"
                         }
                     }
-                    button {
-                        class: "p-2 hover:bg-accent rounded-full transition disabled:opacity-50",
-                        disabled: *loading.read(),
-                        onclick: move |_| {
-                            let current = *refresh_trigger.read();
-                            refresh_trigger.set(current + 1);
-                        },
-                        title: "Refresh feed",
-                        if *loading.read() && events.read().is_empty() {
-                            span {
-                                class: "inline-block w-5 h-5 border-2 border-current border-t-transparent rounded-full animate-spin"
+                    div {
+                        class: "flex items-center gap-2",
+                        if auth_store::is_authenticated() {
+                            button {
+                                class: if is_following {
+                                    "px-4 py-1.5 text-sm font-medium rounded-full border border-border hover:bg-accent transition disabled:opacity-50"
+                                } else {
+                                    "px-4 py-1.5 text-sm font-medium rounded-full bg-primary text-primary-foreground hover:bg-primary/90 transition disabled:opacity-50"
+                                },
+                                disabled: *updating_follow.read()
+                                    || interests::INTEREST_LIST.read().is_none()
+                                    || !*nostr_client::HAS_SIGNER.read(),
+                                onclick: move |_| {
+                                    let hashtag = tag_for_follow.clone();
+                                    updating_follow.set(true);
+                                    spawn(async move {
+                                        let result = if is_following {
+                                            interests::unfollow_hashtag(&hashtag).await
+                                        } else {
+                                            interests::follow_hashtag(&hashtag).await
+                                        };
+                                        if let Err(e) = result {
+                                            log::error!("Failed to update followed hashtags: {}", e);
+                                        }
+                                        updating_follow.set(false);
+                                    });
+                                },
+                                if *updating_follow.read() {
+                                    "..."
+                                } else if is_following {
+                                    "Unfollow"
+                                } else {
+                                    "Follow"
+                                }
+                            }
+                        }
+                        button {
+                            class: "p-2 hover:bg-accent rounded-full transition disabled:opacity-50",
+                            disabled: *loading.read(),
+                            onclick: move |_| {
+                                let current = *refresh_trigger.read();
+                                refresh_trigger.set(current + 1);
+                            },
+                            title: "Refresh feed",
+                            if *loading.read() && events.read().is_empty() {
+                                span {
+                                    class: "inline-block w-5 h-5 border-2 border-current border-t-transparent rounded-full animate-spin"
+                                }
+                            } else {
+                                "🔄"
                             }
-                        } else {
-                            "🔄"
                         }
                     }
                 }
@@ -198,7 +237,7 @@ pub fn Hashtag(tag: String) -> Element {
             if !events.read().is_empty() {
                 div {
                     class: "divide-y divide-border",
-                    for event in events.read().iter() {
+                    for event in events.read().iter().filter(|event| !settings_store::is_event_muted_by_keyword(event)) {
                         NoteCard {
                             key: "{event.id}",
                             event: event.clone(),
@@ -1,37 +1,26 @@
 use dioxus::prelude::*;
-use crate::stores::{auth_store, nostr_client};
+use crate::stores::{auth_store, interests, nostr_client, settings_store};
+use crate::stores::home_feed::{self, FeedType};
+use crate::stores::feed_cache;
 use crate::routes::Route;
-use crate::components::{NoteCard, NoteComposer, ArticleCard, ClientInitializing};
+use crate::components::{NoteCard, NoteComposer, ArticleCard, ClientInitializing, ActivityDigestCard};
 use crate::hooks::use_infinite_scroll;
 use crate::utils::{DataState, FeedItem, extract_reposted_event};
 use crate::services::aggregation::{InteractionCounts, fetch_interaction_counts_batch, sync_interaction_counts};
-use nostr_sdk::{Filter, Kind, Timestamp, PublicKey};
+use nostr_sdk::{Event, Filter, Kind, PublicKey, Timestamp};
 use std::time::Duration;
 use std::collections::HashMap;
 
-#[derive(Clone, Copy, PartialEq, Debug)]
-enum FeedType {
-    Following,          // Top level posts only
-    FollowingWithReplies, // All posts including replies
-    Global,             // Global feed from all users
-}
-
-impl FeedType {
-    fn label(&self) -> &'static str {
-        match self {
-            FeedType::Following => "Following",
-            FeedType::FollowingWithReplies => "Following + Replies",
-            FeedType::Global => "Global",
-        }
-    }
-}
-
 #[component]
 pub fn Home() -> Element {
     // State for feed items using type-state machine pattern
     let mut feed_state = use_signal(|| DataState::<Vec<FeedItem>>::Pending);
     let mut refresh_trigger = use_signal(|| 0);
-    let mut feed_type = use_signal(|| FeedType::Following);
+    let mut feed_type = use_signal(home_feed::load_feed_type);
+    // Relay browsed by the relay feed, and the address being typed in the menu
+    let mut feed_relay = use_signal(home_feed::load_feed_relay);
+    let mut relay_input = use_signal(|| home_feed::load_feed_relay().unwrap_or_default());
+    let mut feed_hint = use_signal(|| None::<&'static str>);
     let mut show_dropdown = use_signal(|| false);
 
     // Pagination state for infinite scroll
@@ -59,6 +48,18 @@ pub fn Home() -> Element {
     // Track active subscription IDs for cleanup
     let mut subscription_ids = use_signal(|| Vec::<nostr_sdk::SubscriptionId>::new());
 
+    // Notes kept from the last session, shown until relays answer
+    let mut cached_items = use_signal(|| Vec::<FeedItem>::new());
+
+    // Load the offline copy of the feed; doesn't need the client
+    use_effect(move || {
+        let current_feed_type = *feed_type.read();
+        cached_items.set(Vec::new());
+        spawn(async move {
+            cached_items.set(feed_cache::load_feed_cache(current_feed_type).await);
+        });
+    });
+
     // Load feed on mount and when refresh is triggered or feed type changes
     use_effect(move || {
         // Watch refresh trigger and feed type
@@ -105,134 +106,70 @@ pub fn Home() -> Element {
             // Note: Profile cache NOT cleared - 5-min TTL handles staleness
             // Clearing was causing slow avatar loading on page navigation
 
-            spawn(async move {
-                match current_feed_type {
-                    FeedType::Following => {
-                        match load_following_feed(None).await {
-                            Ok((feed_items, _raw_count)) => {
-                                // Track oldest timestamp for pagination
-                                if let Some(last_item) = feed_items.last() {
-                                    oldest_timestamp.set(Some(last_item.sort_timestamp().as_secs()));
-                                }
+            let relay = feed_relay.read().clone();
+            feed_hint.set(None);
 
-                                // Always assume there's more content on initial load
-                                // Only disable pagination when we explicitly get 0 results from a "load more" request
-                                // This prevents disabling infinite scroll on first login when database is empty
-                                has_more.set(true);
-
-                                // Display feed immediately (NoteCard shows fallback until metadata loads)
-                                feed_state.set(DataState::Loaded(feed_items.clone()));
-
-                                // Batch fetch interaction counts for all events
-                                // Use negentropy sync for subsequent refreshes (incremental updates)
-                                let items_for_counts = feed_items.clone();
-                                spawn(async move {
-                                    let event_ids: Vec<_> = items_for_counts.iter().map(|item| item.event().id).collect();
-                                    let counts = if is_first_load {
-                                        // First load: full fetch (no local data to reconcile)
-                                        fetch_interaction_counts_batch(event_ids, Duration::from_secs(5)).await
-                                    } else {
-                                        // Subsequent refresh: use negentropy for incremental sync
-                                        sync_interaction_counts(event_ids, Duration::from_secs(5)).await
-                                    };
-                                    if let Ok(counts) = counts {
-                                        interaction_counts.set(counts);
-                                        interactions_loaded.set(true);
-                                    }
-                                });
-
-                                // Spawn non-blocking background prefetch for metadata
-                                spawn(async move {
-                                    prefetch_author_metadata(&feed_items).await;
-                                });
-                            }
-                            Err(e) => {
-                                feed_state.set(DataState::Error(e));
-                            }
-                        }
+            spawn(async move {
+                // An empty contact list shows the global feed instead; say so.
+                // Checked alongside the feed load so it doesn't delay it.
+                let check_follows = async move {
+                    if !current_feed_type.is_following() {
+                        return;
                     }
-                    FeedType::FollowingWithReplies => {
-                        match load_following_with_replies(None).await {
-                            Ok(feed_items) => {
-                                // Track oldest timestamp for pagination
-                                if let Some(last_item) = feed_items.last() {
-                                    oldest_timestamp.set(Some(last_item.sort_timestamp().as_secs()));
-                                }
-
-                                // Always assume there's more content on initial load
-                                // Only disable pagination when we explicitly get 0 results from a "load more" request
-                                has_more.set(true);
-
-                                // Display feed immediately (NoteCard shows fallback until metadata loads)
-                                feed_state.set(DataState::Loaded(feed_items.clone()));
-
-                                // Batch fetch interaction counts for all events
-                                // Use negentropy sync for subsequent refreshes (incremental updates)
-                                let items_for_counts = feed_items.clone();
-                                spawn(async move {
-                                    let event_ids: Vec<_> = items_for_counts.iter().map(|item| item.event().id).collect();
-                                    let counts = if is_first_load {
-                                        // First load: full fetch (no local data to reconcile)
-                                        fetch_interaction_counts_batch(event_ids, Duration::from_secs(5)).await
-                                    } else {
-                                        // Subsequent refresh: use negentropy for incremental sync
-                                        sync_interaction_counts(event_ids, Duration::from_secs(5)).await
-                                    };
-                                    if let Ok(counts) = counts {
-                                        interaction_counts.set(counts);
-                                        interactions_loaded.set(true);
-                                    }
-                                });
-
-                                // Spawn non-blocking background prefetch for metadata
-                                spawn(async move {
-                                    prefetch_author_metadata(&feed_items).await;
-                                });
-                            }
-                            Err(e) => {
-                                feed_state.set(DataState::Error(e));
-                            }
+                    if let Some(pubkey) = auth_store::get_pubkey() {
+                        if matches!(nostr_client::fetch_contacts(pubkey).await, Ok(contacts) if contacts.is_empty()) {
+                            feed_hint.set(Some(home_feed::EMPTY_FOLLOWS_HINT));
                         }
                     }
-                    FeedType::Global => {
-                        match load_global_feed(None).await {
-                            Ok(feed_items) => {
-                                // Track oldest timestamp for pagination
-                                if let Some(last_item) = feed_items.last() {
-                                    oldest_timestamp.set(Some(last_item.sort_timestamp().as_secs()));
-                                }
-
-                                // Always assume there's more content on initial load
-                                has_more.set(true);
+                };
+                let ((), loaded) = futures::join!(check_follows, load_feed(current_feed_type, relay, None));
+
+                match loaded {
+                    Ok(feed_items) => {
+                        // Track oldest timestamp for pagination
+                        if let Some(last_item) = feed_items.last() {
+                            oldest_timestamp.set(Some(last_item.sort_timestamp().as_secs()));
+                        }
 
-                                // Display feed immediately
-                                feed_state.set(DataState::Loaded(feed_items.clone()));
+                        // Always assume there's more content on initial load
+                        // Only disable pagination when we explicitly get 0 results from a "load more" request
+                        // This prevents disabling infinite scroll on first login when database is empty
+                        has_more.set(true);
 
-                                // Batch fetch interaction counts for all events
-                                let items_for_counts = feed_items.clone();
-                                let is_first_load = !*interactions_loaded.peek();
-                                spawn(async move {
-                                    let event_ids: Vec<_> = items_for_counts.iter().map(|item| item.event().id).collect();
-                                    let counts = if is_first_load {
-                                        fetch_interaction_counts_batch(event_ids, Duration::from_secs(5)).await
-                                    } else {
-                                        sync_interaction_counts(event_ids, Duration::from_secs(5)).await
-                                    };
-                                    if let Ok(counts) = counts {
-                                        interaction_counts.set(counts);
-                                        interactions_loaded.set(true);
-                                    }
-                                });
+                        // Display feed immediately (NoteCard shows fallback until metadata loads)
+                        feed_state.set(DataState::Loaded(feed_items.clone()));
 
-                                // Spawn non-blocking background prefetch for metadata
-                                spawn(async move {
-                                    prefetch_author_metadata(&feed_items).await;
-                                });
-                            }
-                            Err(e) => {
-                                feed_state.set(DataState::Error(e));
+                        // Batch fetch interaction counts for all events
+                        // Use negentropy sync for subsequent refreshes (incremental updates)
+                        let items_for_counts = feed_items.clone();
+                        spawn(async move {
+                            let event_ids: Vec<_> = items_for_counts.iter().map(|item| item.event().id).collect();
+                            let counts = if is_first_load {
+                                // First load: full fetch (no local data to reconcile)
+                                fetch_interaction_counts_batch(event_ids, Duration::from_secs(5)).await
+                            } else {
+                                // Subsequent refresh: use negentropy for incremental sync
+                                sync_interaction_counts(event_ids, Duration::from_secs(5)).await
+                            };
+                            if let Ok(counts) = counts {
+                                interaction_counts.set(counts);
+                                interactions_loaded.set(true);
                             }
-                        }
+                        });
+
+                        // Refresh the offline copy with what relays returned
+                        let items_for_cache = feed_items.clone();
+                        spawn(async move {
+                            feed_cache::save_feed_cache(current_feed_type, &items_for_cache).await;
+                        });
+
+                        // Spawn non-blocking background prefetch for metadata
+                        spawn(async move {
+                            prefetch_author_metadata(&feed_items).await;
+                        });
+                    }
+                    Err(e) => {
+                        feed_state.set(DataState::Error(e));
                     }
                 }
             });
@@ -277,6 +214,11 @@ pub fn Home() -> Element {
             return;
         }
 
+        // Live updates follow the contact list and hashtags; the relay feed refreshes manually
+        if current_feed_type == FeedType::Relay {
+            return;
+        }
+
         // Wait until feed is loaded before starting real-time subscription
         // Use reference pattern matching to avoid cloning the entire feed
         let since_timestamp = match &*feed_state.read() {
@@ -311,21 +253,24 @@ pub fn Home() -> Element {
                 Ok(c) => c,
                 Err(e) => {
                     log::error!("Failed to fetch contacts for real-time subscription: {}", e);
-                    return;
+                    Vec::new()
                 }
             };
 
-            if contacts.is_empty() {
-                log::info!("No contacts to subscribe to for real-time updates");
-                return;
-            }
-
             // Parse contact pubkeys
             let authors: Vec<PublicKey> = contacts.iter()
                 .filter_map(|contact| PublicKey::parse(contact).ok())
                 .collect();
 
-            if authors.is_empty() {
+            // The hashtag feed also follows its hashtags (loaded with the feed)
+            let hashtags: Vec<String> = if current_feed_type == FeedType::FollowingAndHashtags {
+                interests::INTEREST_LIST.peek().as_ref().map(|list| list.hashtags.clone()).unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+
+            if authors.is_empty() && hashtags.is_empty() {
+                log::info!("No contacts or hashtags to subscribe to for real-time updates");
                 return;
             }
 
@@ -339,15 +284,25 @@ pub fn Home() -> Element {
             const BATCH_SIZE: usize = 50;
             const BATCH_DELAY_MS: u64 = 100; // 100ms delay between batches
 
-            let total_authors = authors.len();
-            let num_batches = (total_authors + BATCH_SIZE - 1) / BATCH_SIZE;
+            let mut filters: Vec<Filter> = authors.chunks(BATCH_SIZE)
+                .map(|author_batch| {
+                    Filter::new()
+                        .kinds(vec![Kind::TextNote, Kind::Repost])
+                        .authors(author_batch.to_vec())
+                        .since(since_timestamp)
+                        .limit(0) // limit=0 means only new events
+                })
+                .collect();
+            if let Some(filter) = home_feed::hashtag_filter(&hashtags, None) {
+                filters.push(filter.since(since_timestamp).limit(0));
+            }
+            let num_batches = filters.len();
 
-            log::info!("Starting batched real-time subscription for {} followed users in {} batches using gossip",
-                contacts.len(), num_batches);
+            log::info!("Starting batched real-time subscription for {} followed users and {} hashtags in {} batches using gossip",
+                authors.len(), hashtags.len(), num_batches);
 
             // Subscribe to batches with staggered timing
-            for (batch_idx, author_batch) in authors.chunks(BATCH_SIZE).enumerate() {
-                let batch_authors = author_batch.to_vec();
+            for (batch_idx, filter) in filters.into_iter().enumerate() {
                 let client = client.clone();
                 let batch_num = batch_idx + 1;
 
@@ -366,14 +321,7 @@ pub fn Home() -> Element {
                     }
                 }
 
-                let filter = Filter::new()
-                    .kinds(vec![Kind::TextNote, Kind::Repost])
-                    .authors(batch_authors.clone())
-                    .since(since_timestamp)
-                    .limit(0); // limit=0 means only new events
-
-                log::info!("Subscribing to batch {}/{} ({} authors)",
-                    batch_num, num_batches, batch_authors.len());
+                log::info!("Subscribing to batch {}/{}", batch_num, num_batches);
 
                 match client.subscribe(filter, None).await {
                     Ok(output) => {
@@ -420,18 +368,18 @@ pub fn Home() -> Element {
                                     } else if event.kind == Kind::TextNote {
                                         // Check if this matches our feed type
                                         let should_add = match current_feed_type {
-                                            FeedType::Following => {
+                                            FeedType::Following | FeedType::FollowingAndHashtags => {
                                                 // Only top-level posts (no e tags)
                                                 !event.tags.iter().any(|tag| tag.kind() == nostr_sdk::TagKind::e())
                                             }
-                                            FeedType::FollowingWithReplies | FeedType::Global => {
+                                            FeedType::FollowingWithReplies | FeedType::Global | FeedType::Relay => {
                                                 // All posts including replies
                                                 true
                                             }
                                         };
 
                                         if should_add {
-                                            Some(FeedItem::OriginalPost((*event).clone()))
+                                            Some(FeedItem::from_note((*event).clone()))
                                         } else {
                                             None
                                         }
@@ -510,11 +458,8 @@ pub fn Home() -> Element {
             log::info!("load_more spawn executing - until: {:?}, feed_type: {:?}", until, current_feed_type);
 
             // Fetch items based on feed type
-            let fetch_result: Result<Vec<FeedItem>, String> = match current_feed_type {
-                FeedType::Following => load_following_feed(until).await.map(|(items, _)| items),
-                FeedType::FollowingWithReplies => load_following_with_replies(until).await,
-                FeedType::Global => load_global_feed(until).await,
-            };
+            let relay = feed_relay.peek().clone();
+            let fetch_result = load_feed(current_feed_type, relay, until).await;
 
             match fetch_result {
                 Ok(new_items) => {
@@ -583,6 +528,11 @@ pub fn Home() -> Element {
     // Read auth state for rendering
     let auth = auth_store::AUTH_STATE.read();
 
+    let feed_label = match (*feed_type.read(), feed_relay.read().as_deref()) {
+        (FeedType::Relay, Some(relay)) => relay.trim_start_matches("wss://").trim_start_matches("ws://").to_string(),
+        (feed_type, _) => feed_type.label().to_string(),
+    };
+
     rsx! {
         div {
             class: "min-h-screen",
@@ -603,7 +553,7 @@ pub fn Home() -> Element {
                                     let current = *show_dropdown.read();
                                     show_dropdown.set(!current);
                                 },
-                                "{feed_type.read().label()}"
+                                "{feed_label}"
                                 span {
                                     class: "text-sm",
                                     if *show_dropdown.read() { "▲" } else { "▼" }
@@ -613,77 +563,78 @@ pub fn Home() -> Element {
                             // Dropdown menu
                             if *show_dropdown.read() {
                                 div {
-                                    class: "absolute top-full left-0 mt-2 bg-card border border-border rounded-lg shadow-lg min-w-[200px] overflow-hidden z-30",
-
-                                    button {
-                                        class: "w-full px-4 py-3 text-left hover:bg-accent transition flex items-center justify-between",
-                                        onclick: move |_| {
-                                            feed_type.set(FeedType::Following);
-                                            show_dropdown.set(false);
-                                        },
-                                        div {
+                                    class: "absolute top-full left-0 mt-2 bg-card border border-border rounded-lg shadow-lg min-w-[240px] overflow-hidden z-30 divide-y divide-border",
+
+                                    for option in FeedType::ALL.into_iter().filter(|option| *option != FeedType::Relay) {
+                                        button {
+                                            key: "{option.as_str()}",
+                                            class: "w-full px-4 py-3 text-left hover:bg-accent transition flex items-center justify-between",
+                                            onclick: move |_| {
+                                                home_feed::save_feed(option, None);
+                                                feed_type.set(option);
+                                                show_dropdown.set(false);
+                                            },
                                             div {
-                                                class: "font-medium",
-                                                "Following"
+                                                div {
+                                                    class: "font-medium",
+                                                    "{option.label()}"
+                                                }
+                                                div {
+                                                    class: "text-xs text-muted-foreground",
+                                                    "{option.description()}"
+                                                }
                                             }
-                                            div {
-                                                class: "text-xs text-muted-foreground",
-                                                "Top level posts only"
+                                            if *feed_type.read() == option {
+                                                span { "✓" }
                                             }
                                         }
-                                        if *feed_type.read() == FeedType::Following {
-                                            span { "✓" }
-                                        }
                                     }
 
-                                    div {
-                                        class: "border-t border-border"
-                                    }
-
-                                    button {
-                                        class: "w-full px-4 py-3 text-left hover:bg-accent transition flex items-center justify-between",
-                                        onclick: move |_| {
-                                            feed_type.set(FeedType::FollowingWithReplies);
-                                            show_dropdown.set(false);
+                                    // Single relay: type an address and go
+                                    form {
+                                        class: "px-4 py-3 space-y-2",
+                                        onsubmit: move |evt| {
+                                            evt.prevent_default();
+                                            if let Some(url) = home_feed::normalize_relay_url(&relay_input.read()) {
+                                                home_feed::save_feed(FeedType::Relay, Some(&url));
+                                                relay_input.set(url.clone());
+                                                feed_relay.set(Some(url));
+                                                feed_type.set(FeedType::Relay);
+                                                show_dropdown.set(false);
+                                            }
                                         },
                                         div {
+                                            class: "flex items-center justify-between",
                                             div {
-                                                class: "font-medium",
-                                                "Following + Replies"
+                                                div {
+                                                    class: "font-medium",
+                                                    "{FeedType::Relay.label()}"
+                                                }
+                                                div {
+                                                    class: "text-xs text-muted-foreground",
+                                                    "{FeedType::Relay.description()}"
+                                                }
                                             }
-                                            div {
-                                                class: "text-xs text-muted-foreground",
-                                                "All posts including replies"
+                                            if *feed_type.read() == FeedType::Relay {
+                                                span { "✓" }
                                             }
                                         }
-                                        if *feed_type.read() == FeedType::FollowingWithReplies {
-                                            span { "✓" }
-                                        }
-                                    }
-
-                                    div {
-                                        class: "border-t border-border"
-                                    }
-
-                                    button {
-                                        class: "w-full px-4 py-3 text-left hover:bg-accent transition flex items-center justify-between",
-                                        onclick: move |_| {
-                                            feed_type.set(FeedType::Global);
-                                            show_dropdown.set(false);
-                                        },
                                         div {
-                                            div {
-                                                class: "font-medium",
-                                                "Global"
+                                            class: "flex gap-2",
+                                            input {
+                                                class: "flex-1 min-w-0 px-2 py-1 text-sm bg-background border border-border rounded",
+                                                r#type: "text",
+                                                placeholder: "wss://relay.example.com",
+                                                value: "{relay_input}",
+                                                oninput: move |evt| relay_input.set(evt.value()),
                                             }
-                                            div {
-                                                class: "text-xs text-muted-foreground",
-                                                "Posts from everyone"
+                                            button {
+                                                class: "px-3 py-1 text-sm rounded bg-primary text-primary-foreground disabled:opacity-50",
+                                                r#type: "submit",
+                                                disabled: home_feed::normalize_relay_url(&relay_input.read()).is_none(),
+                                                "Go"
                                             }
                                         }
-                                        if *feed_type.read() == FeedType::Global {
-                                            span { "✓" }
-                                        }
                                     }
                                 }
                             }
@@ -717,6 +668,18 @@ pub fn Home() -> Element {
                 }
             }
 
+            // "While you were away" digest
+            if auth.is_authenticated {
+                ActivityDigestCard {}
+            }
+
+            if let Some(hint) = *feed_hint.read() {
+                div {
+                    class: "px-4 py-3 text-sm text-muted-foreground bg-muted/50 border-b border-border",
+                    "{hint}"
+                }
+            }
+
             // Post Composer (only shown when authenticated)
             if auth.is_authenticated {
                 NoteComposer {}
@@ -746,6 +709,17 @@ pub fn Home() -> Element {
                 if !auth.is_authenticated {
                     // Show login section
                     LoginSection {}
+                } else if !cached_items.read().is_empty()
+                    && (!*nostr_client::CLIENT_INITIALIZED.read()
+                        || feed_state.read().is_pending()
+                        || feed_state.read().is_loading()
+                        || feed_state.read().error().is_some())
+                {
+                    // Show the cached feed until relays answer
+                    CachedFeed {
+                        items: cached_items,
+                        offline: feed_state.read().error().is_some()
+                    }
                 } else if !*nostr_client::CLIENT_INITIALIZED.read() {
                     // Show client initializing animation during client initialization
                     ClientInitializing {}
@@ -817,9 +791,13 @@ pub fn Home() -> Element {
                                 // Get the underlying event and repost info
                                 let event = feed_item.event();
                                 let repost_info = feed_item.repost_info();
+                                let quote = feed_item.quote_info();
 
-                                // Check if this is a long-form article (NIP-23)
-                                if event.kind == Kind::LongFormTextNote {
+                                // Hide notes matching a muted keyword
+                                if settings_store::is_event_muted_by_keyword(event) {
+                                    rsx! {}
+                                } else if event.kind == Kind::LongFormTextNote {
+                                    // Long-form article (NIP-23)
                                     rsx! {
                                         ArticleCard {
                                             key: "{event.id}",
@@ -832,6 +810,7 @@ pub fn Home() -> Element {
                                             key: "{event.id}",
                                             event: event.clone(),
                                             repost_info: repost_info,
+                                            quote: quote,
                                             precomputed_counts: interaction_counts.read().get(&event.id.to_hex()).cloned(),
                                             collapsible: true
                                         }
@@ -869,6 +848,55 @@ pub fn Home() -> Element {
     }
 }
 
+/// Home feed notes from the offline cache, marked as such
+#[component]
+fn CachedFeed(items: Signal<Vec<FeedItem>>, offline: bool) -> Element {
+    rsx! {
+        div {
+            class: "px-4 py-2 border-b border-border bg-muted/50 text-sm text-muted-foreground flex items-center gap-2",
+            span {
+                class: "px-2 py-0.5 text-xs font-medium rounded-full bg-accent",
+                "Cached"
+            }
+            if offline {
+                "Offline — showing cached notes"
+            } else {
+                span {
+                    class: "inline-block w-3 h-3 border-2 border-current border-t-transparent rounded-full animate-spin"
+                }
+                "Showing cached notes while connecting…"
+            }
+        }
+
+        for feed_item in items.read().iter() {
+            {
+                let event = feed_item.event();
+
+                if settings_store::is_event_muted_by_keyword(event) {
+                    rsx! {}
+                } else if event.kind == Kind::LongFormTextNote {
+                    rsx! {
+                        ArticleCard {
+                            key: "{event.id}",
+                            event: event.clone()
+                        }
+                    }
+                } else {
+                    rsx! {
+                        NoteCard {
+                            key: "{event.id}",
+                            event: event.clone(),
+                            repost_info: feed_item.repost_info(),
+                            quote: feed_item.quote_info(),
+                            collapsible: true
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 fn HelpModal(on_close: EventHandler<()>) -> Element {
     rsx! {
@@ -1362,7 +1390,9 @@ fn ProfileSection() -> Element {
                     onclick: move |_| {
                         let nav = navigator();
                         spawn(async move {
-                            auth_store::logout().await;
+                            if let Err(e) = auth_store::logout(false).await {
+                                log::error!("Logout failed: {}", e);
+                            }
                             nav.push(Route::Home {});
                         });
                     },
@@ -1483,9 +1513,60 @@ async fn append_paginated_items(
     pagination_loading.set(false);
 }
 
-// Helper function to load following feed
-// Returns (feed_items, raw_count_before_filtering) tuple
-async fn load_following_feed(until: Option<u64>) -> Result<(Vec<FeedItem>, usize), String> {
+/// Load a page of the given feed, older than `until` when paginating
+async fn load_feed(feed_type: FeedType, relay: Option<String>, until: Option<u64>) -> Result<Vec<FeedItem>, String> {
+    match feed_type {
+        FeedType::Following => load_following_feed(until, false).await,
+        FeedType::FollowingWithReplies => load_following_feed(until, true).await,
+        FeedType::FollowingAndHashtags => load_following_and_hashtags_feed(until).await,
+        FeedType::Global => load_global_feed(until).await,
+        FeedType::Relay => {
+            let relay = relay.ok_or("Pick a relay to browse")?;
+            load_relay_feed(&relay, until).await
+        }
+    }
+}
+
+/// Turn fetched notes and reposts into feed items, newest first
+///
+/// Replies are dropped unless `include_replies` is set; reposts are kept
+/// whatever they repost.
+fn events_to_feed_items(events: impl IntoIterator<Item = Event>, include_replies: bool) -> Vec<FeedItem> {
+    let mut feed_items: Vec<FeedItem> = Vec::new();
+
+    for event in events {
+        if event.kind == Kind::Repost {
+            // Parse repost to extract original event
+            match extract_reposted_event(&event) {
+                Ok(original) => {
+                    feed_items.push(FeedItem::Repost {
+                        original,
+                        reposted_by: event.pubkey,
+                        repost_timestamp: event.created_at,
+                    });
+                }
+                Err(e) => {
+                    log::warn!("Failed to parse repost event {}: {}", event.id, e);
+                }
+            }
+        } else if event.kind == Kind::TextNote {
+            let is_reply = event.tags.iter().any(|tag| tag.kind() == nostr_sdk::TagKind::e());
+            if include_replies || !is_reply {
+                feed_items.push(FeedItem::from_note(event));
+            }
+        }
+    }
+
+    // Sort by timestamp (repost time for reposts, created_at for originals)
+    feed_items.sort_by(|a, b| b.sort_timestamp().cmp(&a.sort_timestamp()));
+    feed_items
+}
+
+// Helper function to load the following feed, with or without replies
+//
+// Falls back to the global feed when the user follows nobody or nothing
+// from their follows turns up.
+async fn load_following_feed(until: Option<u64>, include_replies: bool) -> Result<Vec<FeedItem>, String> {
     // TODO: Consider implementing progressive loading with client.stream_events() for better UX
     // This would display events as they arrive instead of waiting for all results
 
@@ -1493,62 +1574,23 @@ async fn load_following_feed(until: Option<u64>) -> Result<(Vec<FeedItem>, usize
     let pubkey_str = auth_store::get_pubkey()
         .ok_or("Not authenticated")?;
 
-    log::info!("Loading following feed for {} (until: {:?})", pubkey_str, until);
+    log::info!("Loading following feed for {} (until: {:?}, replies: {})", pubkey_str, until, include_replies);
 
-    // OPTIMIZATION: Fetch contacts AND global feed in parallel
-    // If contacts fails or is empty, global feed is already ready
-    let contacts_future = nostr_client::fetch_contacts(pubkey_str.clone());
-    let global_future = load_global_feed(until);
-
-    let (contacts_result, global_result) = futures::join!(contacts_future, global_future);
-
-    // Handle contacts fetch result
-    let contacts = match contacts_result {
+    // The contact list comes first: the filter is built from it
+    let contacts = match nostr_client::fetch_contacts(pubkey_str.clone()).await {
         Ok(contacts) => contacts,
         Err(e) => {
             log::warn!("Failed to fetch contacts: {}, falling back to global feed", e);
-            // Global feed was fetched in parallel, use it
-            let global = global_result?;
-            let count = global.len();
-            return Ok((global, count));
+            return load_global_feed(until).await;
         }
     };
 
-    // If user doesn't follow anyone, show global feed (already fetched)
-    if contacts.is_empty() {
-        log::info!("User doesn't follow anyone, showing global feed");
-        let global = global_result?;
-        let count = global.len();
-        return Ok((global, count));
-    }
-
     log::info!("User follows {} accounts", contacts.len());
 
-    // Parse contact pubkeys
-    let mut authors = Vec::new();
-    for contact in contacts.iter() {
-        if let Ok(pk) = PublicKey::parse(contact) {
-            authors.push(pk);
-        }
-    }
-
-    if authors.is_empty() {
-        log::warn!("No valid contact pubkeys, falling back to global feed");
-        let global = load_global_feed(until).await?;
-        let count = global.len();
-        return Ok((global, count));
-    }
-
-    // Create filter for posts AND reposts from followed users
-    let mut filter = Filter::new()
-        .kinds(vec![Kind::TextNote, Kind::Repost])
-        .authors(authors)
-        .limit(100);
-
-    // Add until for pagination, no since filter to allow going back indefinitely
-    if let Some(until_ts) = until {
-        filter = filter.until(Timestamp::from(until_ts));
-    }
+    let Some(filter) = home_feed::following_filter(&contacts, include_replies, until) else {
+        log::info!("No valid contact pubkeys, showing global feed");
+        return load_global_feed(until).await;
+    };
 
     log::info!("Fetching events from {} followed accounts", filter.authors.as_ref().map(|a| a.len()).unwrap_or(0));
 
@@ -1556,215 +1598,78 @@ async fn load_following_feed(until: Option<u64>) -> Result<(Vec<FeedItem>, usize
     match nostr_client::fetch_events_aggregated(filter, Duration::from_secs(10)).await {
         Ok(events) => {
             let raw_count = events.len();
-            log::info!("Loaded {} events (including reposts) from following feed", raw_count);
-
-            // Process events into FeedItems
-            let mut feed_items: Vec<FeedItem> = Vec::new();
-
-            for event in events.into_iter() {
-                if event.kind == Kind::Repost {
-                    // Parse repost to extract original event
-                    match extract_reposted_event(&event) {
-                        Ok(original) => {
-                            // Include all reposts (regardless of whether original was a reply)
-                            feed_items.push(FeedItem::Repost {
-                                original,
-                                reposted_by: event.pubkey,
-                                repost_timestamp: event.created_at,
-                            });
-                        }
-                        Err(e) => {
-                            log::warn!("Failed to parse repost event {}: {}", event.id, e);
-                        }
-                    }
-                } else if event.kind == Kind::TextNote {
-                    // Filter out replies - only include top-level posts
-                    use nostr_sdk::TagKind;
-                    let is_reply = event.tags.iter().any(|tag| tag.kind() == TagKind::e());
-                    if !is_reply {
-                        feed_items.push(FeedItem::OriginalPost(event));
-                    }
-                }
-            }
-
-            // Sort by timestamp (repost time for reposts, created_at for originals)
-            feed_items.sort_by(|a, b| b.sort_timestamp().cmp(&a.sort_timestamp()));
-
+            let feed_items = events_to_feed_items(events, include_replies);
             log::info!("After processing: {} feed items (raw: {})", feed_items.len(), raw_count);
 
             // If no events found, fall back to global feed
             if feed_items.is_empty() {
                 log::info!("No posts from followed users, showing global feed");
-                let global = load_global_feed(until).await?;
-                let count = global.len();
-                return Ok((global, count));
+                return load_global_feed(until).await;
             }
 
-            Ok((feed_items, raw_count))
+            Ok(feed_items)
         }
         Err(e) => {
             log::error!("Failed to fetch following feed: {}, falling back to global", e);
-            let global = load_global_feed(until).await?;
-            let count = global.len();
-            Ok((global, count))
+            load_global_feed(until).await
         }
     }
 }
 
-// Helper function to load following feed with replies
-async fn load_following_with_replies(until: Option<u64>) -> Result<Vec<FeedItem>, String> {
-    // Get current user's pubkey
+// Helper function to load followed accounts together with followed hashtags
+//
+// Each half is fetched with its own filter and the pages merged so that the
+// next page continues both from where this one ends. Falls back to the
+// global feed when neither turns anything up.
+async fn load_following_and_hashtags_feed(until: Option<u64>) -> Result<Vec<FeedItem>, String> {
     let pubkey_str = auth_store::get_pubkey()
         .ok_or("Not authenticated")?;
 
-    log::info!("Loading following feed with replies for {} (until: {:?})", pubkey_str, until);
-
-    // Fetch the user's contact list (people they follow)
-    let contacts = match nostr_client::fetch_contacts(pubkey_str.clone()).await {
-        Ok(contacts) => contacts,
+    let contacts = nostr_client::fetch_contacts(pubkey_str).await.unwrap_or_else(|e| {
+        log::warn!("Failed to fetch contacts: {}", e);
+        Vec::new()
+    });
+    let hashtags = match interests::load_interest_list().await {
+        Ok(list) => list.hashtags,
         Err(e) => {
-            log::warn!("Failed to fetch contacts: {}, falling back to global feed", e);
-            return load_global_feed(until).await;
+            log::warn!("Failed to load followed hashtags: {}", e);
+            Vec::new()
         }
     };
 
-    // If user doesn't follow anyone, show global feed
-    if contacts.is_empty() {
-        log::info!("User doesn't follow anyone, showing global feed");
-        return load_global_feed(until).await;
-    }
-
-    log::info!("User follows {} accounts", contacts.len());
+    log::info!("Loading following + hashtags feed ({} accounts, {} hashtags, until: {:?})",
+        contacts.len(), hashtags.len(), until);
 
-    // Parse contact pubkeys
-    let mut authors = Vec::new();
-    for contact in contacts.iter() {
-        if let Ok(pk) = PublicKey::parse(contact) {
-            authors.push(pk);
+    let mut pages = Vec::new();
+    for filter in home_feed::following_and_hashtags_filters(&contacts, &hashtags, until) {
+        let limit = filter.limit;
+        match nostr_client::fetch_events_aggregated(filter, Duration::from_secs(10)).await {
+            Ok(fetched) => pages.push((fetched, limit)),
+            Err(e) => log::warn!("Failed to fetch part of the following + hashtags feed: {}", e),
         }
     }
+    let events = home_feed::merge_source_pages(pages);
 
-    if authors.is_empty() {
-        log::warn!("No valid contact pubkeys, falling back to global feed");
+    let feed_items = events_to_feed_items(events, false);
+    if feed_items.is_empty() {
+        log::info!("Nothing from followed accounts or hashtags, showing global feed");
         return load_global_feed(until).await;
     }
-
-    // Create filter for all posts AND reposts from followed users (including replies)
-    // Unlike load_following_feed, we include ALL posts (even replies)
-    let mut filter = Filter::new()
-        .kinds(vec![Kind::TextNote, Kind::Repost])
-        .authors(authors)
-        .limit(150); // Increased limit since we're getting more content
-
-    // Add until for pagination, or since for initial load
-    if let Some(until_ts) = until {
-        filter = filter.until(Timestamp::from(until_ts));
-    } else {
-        let since = Timestamp::now() - Duration::from_secs(86400); // 24 hours ago
-        filter = filter.since(since);
-    }
-
-    log::info!("Fetching all events (including replies and reposts) from {} followed accounts", filter.authors.as_ref().map(|a| a.len()).unwrap_or(0));
-
-    // Fetch events using aggregated pattern (database-first)
-    match nostr_client::fetch_events_aggregated(filter, Duration::from_secs(10)).await {
-        Ok(events) => {
-            log::info!("Loaded {} events (including replies and reposts) from following feed", events.len());
-
-            // Process events into FeedItems
-            let mut feed_items: Vec<FeedItem> = Vec::new();
-
-            for event in events.into_iter() {
-                if event.kind == Kind::Repost {
-                    // Parse repost to extract original event
-                    match extract_reposted_event(&event) {
-                        Ok(original) => {
-                            // Include all reposts (regardless of whether original was a reply)
-                            feed_items.push(FeedItem::Repost {
-                                original,
-                                reposted_by: event.pubkey,
-                                repost_timestamp: event.created_at,
-                            });
-                        }
-                        Err(e) => {
-                            log::warn!("Failed to parse repost event {}: {}", event.id, e);
-                        }
-                    }
-                } else if event.kind == Kind::TextNote {
-                    // Include ALL posts (including replies)
-                    feed_items.push(FeedItem::OriginalPost(event));
-                }
-            }
-
-            // Sort by timestamp (repost time for reposts, created_at for originals)
-            feed_items.sort_by(|a, b| b.sort_timestamp().cmp(&a.sort_timestamp()));
-
-            // If no events found, fall back to global feed
-            if feed_items.is_empty() {
-                log::info!("No events from followed users, showing global feed");
-                return load_global_feed(until).await;
-            }
-
-            Ok(feed_items)
-        }
-        Err(e) => {
-            log::error!("Failed to fetch following feed with replies: {}, falling back to global", e);
-            load_global_feed(until).await
-        }
-    }
+    Ok(feed_items)
 }
 
 // Helper function to load global feed
 async fn load_global_feed(until: Option<u64>) -> Result<Vec<FeedItem>, String> {
     log::info!("Loading global feed (until: {:?})...", until);
 
-    // Create filter for recent text notes and reposts (kind 1 and kind 6)
-    let mut filter = Filter::new()
-        .kinds(vec![Kind::TextNote, Kind::Repost])
-        .limit(50);
-
-    // Add until for pagination, or since for initial load
-    if let Some(until_ts) = until {
-        filter = filter.until(Timestamp::from(until_ts));
-    } else {
-        let since = Timestamp::now() - Duration::from_secs(86400); // 24 hours ago
-        filter = filter.since(since);
-    }
-
+    let filter = home_feed::global_filter(until);
     log::info!("Fetching events with filter: {:?}", filter);
 
     // Fetch events using aggregated pattern (database-first)
     match nostr_client::fetch_events_aggregated(filter, Duration::from_secs(10)).await {
         Ok(events) => {
             log::info!("Loaded {} events", events.len());
-
-            // Process events into FeedItems
-            let mut feed_items: Vec<FeedItem> = Vec::new();
-
-            for event in events.into_iter() {
-                if event.kind == Kind::Repost {
-                    // Parse repost to extract original event
-                    match extract_reposted_event(&event) {
-                        Ok(original) => {
-                            feed_items.push(FeedItem::Repost {
-                                original,
-                                reposted_by: event.pubkey,
-                                repost_timestamp: event.created_at,
-                            });
-                        }
-                        Err(e) => {
-                            log::warn!("Failed to parse repost event {}: {}", event.id, e);
-                        }
-                    }
-                } else if event.kind == Kind::TextNote {
-                    feed_items.push(FeedItem::OriginalPost(event));
-                }
-            }
-
-            // Sort by timestamp (repost time for reposts, created_at for originals)
-            feed_items.sort_by(|a, b| b.sort_timestamp().cmp(&a.sort_timestamp()));
-
-            Ok(feed_items)
+            Ok(events_to_feed_items(events, true))
         }
         Err(e) => {
             log::error!("Failed to fetch events: {}", e);
@@ -1773,6 +1678,15 @@ async fn load_global_feed(until: Option<u64>) -> Result<Vec<FeedItem>, String> {
     }
 }
 
+// Helper function to load the feed of a single relay
+async fn load_relay_feed(relay: &str, until: Option<u64>) -> Result<Vec<FeedItem>, String> {
+    log::info!("Loading relay feed from {} (until: {:?})...", relay, until);
+
+    let events = home_feed::fetch_relay_events(relay, home_feed::global_filter(until), Duration::from_secs(10)).await?;
+    log::info!("Loaded {} events from {}", events.len(), relay);
+    Ok(events_to_feed_items(events, true))
+}
+
 /// Batch prefetch author metadata for all feed items
 /// This checks the database first and only fetches missing metadata
 /// For reposts, it fetches both the original author AND the reposter
@@ -1790,6 +1704,9 @@ async fn prefetch_author_metadata(feed_items: &[FeedItem]) {
                 pubkeys.push(original.pubkey); // Original author
                 pubkeys.push(*reposted_by);     // Reposter
             }
+            FeedItem::QuotedNote { note, .. } => {
+                pubkeys.push(note.pubkey);
+            }
         }
     }
 
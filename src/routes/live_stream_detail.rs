@@ -2,6 +2,7 @@ use dioxus::prelude::*;
 use nostr_sdk::{Filter, Kind, PublicKey, FromBech32};
 use crate::components::{LiveStreamPlayer, LiveChat, StreamStatus, ZapModal, LiveStreamShareModal};
 use crate::components::live_stream_card::{parse_live_stream_event, LiveStreamMeta};
+use crate::components::live_stream_utils::media_session_info;
 use crate::components::icons::{ArrowLeftIcon, ZapIcon, ShareIcon};
 use crate::routes::Route;
 use crate::stores::nostr_client::{fetch_events_aggregated, CLIENT_INITIALIZED, HAS_SIGNER};
@@ -22,6 +23,8 @@ pub fn LiveStreamDetail(note_id: String) -> Element {
     // Modal state
     let mut show_zap_modal = use_signal(|| false);
     let mut show_share_modal = use_signal(|| false);
+    // Keep only the audio playing (e.g. on mobile with the screen off)
+    let mut audio_only = use_signal(|| false);
     // Use memo for reactive has_signer that updates on login/logout
     let has_signer = use_memo(move || *HAS_SIGNER.read());
 
@@ -38,6 +41,15 @@ pub fn LiveStreamDetail(note_id: String) -> Element {
         profiles::get_profile(&pubkey_str)
     });
 
+    // Lock screen metadata for the player
+    let media_session = use_memo(move || {
+        let event = stream_event.read();
+        let metadata = author_metadata.read();
+        let host_name = metadata.as_ref()
+            .and_then(|m| m.display_name.clone().or_else(|| m.name.clone()));
+        event.as_ref().and_then(|event| media_session_info(event, host_name.as_deref()))
+    });
+
     // Fetch stream event
     use_effect(use_reactive(
         (&*CLIENT_INITIALIZED.read(), &parsed_naddr),
@@ -277,7 +289,17 @@ pub fn LiveStreamDetail(note_id: String) -> Element {
                                         LiveStreamPlayer {
                                             stream_url: stream_url.clone(),
                                             poster: meta.image.clone(),
-                                            autoplay: true
+                                            autoplay: true,
+                                            audio_only: *audio_only.read(),
+                                            media_session: media_session.read().clone()
+                                        }
+                                        div {
+                                            class: "flex justify-end",
+                                            button {
+                                                class: "px-3 py-1.5 text-sm rounded-lg hover:bg-accent transition-colors",
+                                                onclick: move |_| audio_only.toggle(),
+                                                if *audio_only.read() { "🎬 Show video" } else { "🎧 Audio only" }
+                                            }
                                         }
                                     } else {
                                         {render_no_stream_placeholder(&meta.status)}
@@ -423,10 +445,16 @@ pub fn LiveStreamDetail(note_id: String) -> Element {
                             if let Some(_event) = stream_event.read().as_ref() {
                                 {
                                     let (author_pk, dtag) = parsed_naddr.peek().clone();
+                                    let ended = stream_meta
+                                        .read()
+                                        .as_ref()
+                                        .is_some_and(|meta| meta.status == StreamStatus::Ended);
                                     rsx! {
                                         LiveChat {
+                                            key: "{author_pk}:{dtag}",
                                             stream_author_pubkey: author_pk,
-                                            stream_d_tag: dtag
+                                            stream_d_tag: dtag,
+                                            ended
                                         }
                                     }
                                 }
@@ -68,7 +68,7 @@ use live_stream_detail::LiveStreamDetail;
 use live_stream_new::LiveStreamNew;
 use articles::Articles;
 use article_detail::ArticleDetail;
-use music::{MusicHome, MusicRadio, MusicLeaderboard, MusicArtist, MusicAlbum, MusicSearch, MusicTrackNew, MusicPlaylistNew, MusicPlaylistDetail};
+use music::{MusicHome, MusicRadio, MusicLeaderboard, MusicArtist, MusicAlbum, MusicSearch, MusicTrackNew, MusicPlaylistNew, MusicPlaylistEdit, MusicPlaylistDetail};
 use photos::Photos;
 use photo_detail::PhotoDetail;
 use voicemessages::VoiceMessages;
@@ -80,7 +80,7 @@ use poll_view::PollView;
 use poll_new::PollNew;
 use cashu_wallet::CashuWallet;
 use note_new::NoteNew;
-use article_new::ArticleNew;
+use article_new::{ArticleNew, ArticleEdit};
 use photo_new::PhotoNew;
 use video_new_landscape::VideoNewLandscape;
 use video_new_portrait::VideoNewPortrait;
@@ -157,6 +157,9 @@ pub enum Route {
         #[route("/music/playlist/new")]
         MusicPlaylistNew {},
 
+        #[route("/music/playlist/edit/:naddr")]
+        MusicPlaylistEdit { naddr: String },
+
         #[route("/music/playlist/:naddr")]
         MusicPlaylistDetail { naddr: String },
 
@@ -205,6 +208,9 @@ pub enum Route {
         #[route("/articles/new")]
         ArticleNew {},
 
+        #[route("/articles/edit/:naddr")]
+        ArticleEdit { naddr: String },
+
         #[route("/photos/new")]
         PhotoNew {},
 
@@ -256,10 +262,11 @@ pub enum Route {
 
 #[component]
 fn Layout() -> Element {
-    use crate::stores::{auth_store, notifications as notif_store};
+    use crate::stores::{auth_store, dms, notifications as notif_store};
 
     let auth = auth_store::AUTH_STATE.read();
     let notif_count = use_memo(move || notif_store::get_unread_count());
+    let dm_unread_count = use_memo(move || dms::total_unread_count());
     let mut sidebar_open = use_signal(|| false);
     let mut more_menu_open = use_signal(|| false);
     let mut radial_menu_open = use_signal(|| false);
@@ -270,13 +277,14 @@ fn Layout() -> Element {
     let is_dms_page = matches!(current_route, Route::DMs {});
     let is_videos_page = matches!(current_route, Route::Videos {} | Route::VideoDetail { .. } | Route::VideosLive {} | Route::VideosLiveTag { .. } | Route::LiveStreamDetail { .. });
     let is_wallet_page = matches!(current_route, Route::CashuWallet {});
-    let is_music_page = matches!(current_route, Route::MusicHome {} | Route::MusicRadio {} | Route::MusicLeaderboard {} | Route::MusicSearch { .. } | Route::MusicArtist { .. } | Route::MusicAlbum { .. } | Route::MusicTrackNew {} | Route::MusicPlaylistNew {} | Route::MusicPlaylistDetail { .. });
+    let is_music_page = matches!(current_route, Route::MusicHome {} | Route::MusicRadio {} | Route::MusicLeaderboard {} | Route::MusicSearch { .. } | Route::MusicArtist { .. } | Route::MusicAlbum { .. } | Route::MusicTrackNew {} | Route::MusicPlaylistNew {} | Route::MusicPlaylistEdit { .. } | Route::MusicPlaylistDetail { .. });
 
     // Check if we're on any creation pages (hide right sidebar for better editor space)
     let is_creation_page = matches!(
         current_route,
         Route::NoteNew { .. }
         | Route::ArticleNew {}
+        | Route::ArticleEdit { .. }
         | Route::PhotoNew {}
         | Route::VideoNewLandscape {}
         | Route::VideoNewPortrait {}
@@ -297,6 +305,12 @@ fn Layout() -> Element {
                 }
             },
 
+            // j/k, Enter, n and / shortcuts
+            crate::components::KeyboardShortcuts {}
+
+            // Announces automatic Cashu dust sweeps
+            crate::components::DustSweepToast {}
+
             // 3-Column Layout Container
             div {
                 class: "flex justify-center max-w-[1600px] mx-auto",
@@ -423,7 +437,8 @@ fn Layout() -> Element {
                                 NavLink {
                                     to: Route::DMs {},
                                     icon: rsx! { crate::components::icons::MailIcon { class: "w-7 h-7" } },
-                                    label: "Messages"
+                                    label: "Messages",
+                                    badge: Some(*dm_unread_count.read())
                                 }
                                 NavLink {
                                     to: Route::Bookmarks {},
@@ -766,7 +781,8 @@ fn Layout() -> Element {
                                             NavLink {
                                                 to: Route::DMs {},
                                                 icon: rsx! { crate::components::icons::MailIcon { class: "w-7 h-7" } },
-                                                label: "Messages"
+                                                label: "Messages",
+                                                badge: Some(*dm_unread_count.read())
                                             }
                                         }
                                         div {
@@ -15,5 +15,5 @@ pub use artist::MusicArtist;
 pub use album::MusicAlbum;
 pub use music_search::MusicSearch;
 pub use track_new::MusicTrackNew;
-pub use playlist_new::MusicPlaylistNew;
+pub use playlist_new::{MusicPlaylistNew, MusicPlaylistEdit};
 pub use playlist_detail::MusicPlaylistDetail;
@@ -3,7 +3,7 @@
 
 use dioxus::prelude::*;
 use crate::routes::Route;
-use crate::stores::{nostr_music, music_player, profiles};
+use crate::stores::{auth_store, nostr_music, music_player, profiles};
 use crate::components::{UnifiedTrackCard, UnifiedTrackCardSkeleton};
 
 #[component]
@@ -83,6 +83,20 @@ pub fn MusicPlaylistDetail(naddr: String) -> Element {
         music_player::play_track(track, Some(playlist_tracks), Some(index));
     };
 
+    // Only the author can edit; the editor takes a bech32 naddr
+    let edit_naddr = playlist.read().as_ref().and_then(|pl| {
+        if auth_store::get_pubkey().as_deref() != Some(pl.pubkey.as_str()) {
+            return None;
+        }
+        let pubkey = nostr_sdk::PublicKey::from_hex(&pl.pubkey).ok()?;
+        crate::utils::nip19::encode_naddr(
+            nostr_sdk::Kind::from(nostr_music::KIND_PLAYLIST),
+            pubkey,
+            &pl.d_tag,
+            &[],
+        ).ok()
+    });
+
     rsx! {
         div {
             class: "max-w-4xl mx-auto p-4",
@@ -281,6 +295,13 @@ pub fn MusicPlaylistDetail(naddr: String) -> Element {
                                     }
                                     "Play"
                                 }
+                                if let Some(edit_naddr) = edit_naddr.clone() {
+                                    Link {
+                                        to: Route::MusicPlaylistEdit { naddr: edit_naddr },
+                                        class: "px-6 py-3 border border-border rounded-full font-medium hover:bg-muted transition",
+                                        "Edit"
+                                    }
+                                }
                             }
                         }
                     }
@@ -1,13 +1,24 @@
 // Playlist Creation Page
-// Allows users to create Kind 34139 playlists on nostr
+// Allows users to create Kind 34139 playlists on nostr, or edit their own
 
 use dioxus::prelude::*;
 use crate::routes::Route;
-use crate::stores::{auth_store, nostr_music};
-use crate::utils::slugify;
+use crate::stores::{auth_store, nostr_client, nostr_music};
+use crate::utils::addressable::{self, EditTarget};
 
 #[component]
 pub fn MusicPlaylistNew() -> Element {
+    rsx! { PlaylistEditor { naddr: None } }
+}
+
+/// Edit a published playlist, keeping its d-tag and tracks
+#[component]
+pub fn MusicPlaylistEdit(naddr: String) -> Element {
+    rsx! { PlaylistEditor { naddr: Some(naddr) } }
+}
+
+#[component]
+fn PlaylistEditor(naddr: Option<String>) -> Element {
     let navigator = navigator();
     let is_authenticated = auth_store::is_authenticated();
 
@@ -21,6 +32,51 @@ pub fn MusicPlaylistNew() -> Element {
     let mut category_input = use_signal(String::new);
     let mut is_publishing = use_signal(|| false);
     let mut error_msg = use_signal(|| None::<String>);
+    let mut target = use_signal(EditTarget::default);
+    // Tracks of the playlist being edited, republished unchanged
+    let mut track_refs = use_signal(Vec::<String>::new);
+    let mut loading_existing = use_signal(|| naddr.is_some());
+
+    // Load the playlist being edited
+    let naddr_for_load = naddr.clone();
+    use_effect(move || {
+        let Some(naddr) = naddr_for_load.clone() else {
+            return;
+        };
+        if !*nostr_client::CLIENT_INITIALIZED.read() {
+            return;
+        }
+        let my_pubkey = auth_store::get_pubkey().unwrap_or_default();
+
+        spawn(async move {
+            let result = async {
+                let address = addressable::decode_edit_naddr(&naddr, nostr_music::KIND_PLAYLIST, &my_pubkey)?;
+                nostr_music::fetch_playlist_by_coordinate(&address.pubkey, &address.identifier)
+                    .await?
+                    .ok_or_else(|| "Playlist not found".to_string())
+            }
+            .await;
+
+            match result {
+                Ok(playlist) => {
+                    title.set(playlist.title);
+                    description.set(playlist.description.unwrap_or_default());
+                    image_url.set(playlist.image.unwrap_or_default());
+                    is_public.set(playlist.is_public);
+                    is_collaborative.set(playlist.is_collaborative);
+                    categories.set(playlist.categories);
+                    track_refs.set(playlist.track_refs);
+                    target.set(EditTarget::Existing { identifier: playlist.d_tag });
+                }
+                Err(e) => error_msg.set(Some(format!("Can't edit this playlist: {}", e))),
+            }
+            loading_existing.set(false);
+        });
+    });
+
+    let is_edit = target.read().is_edit();
+    // An edit whose playlist failed to load must not publish under a new d-tag
+    let can_publish = !*is_publishing.read() && !*loading_existing.read() && (naddr.is_none() || is_edit);
 
     // Redirect if not authenticated
     if !is_authenticated {
@@ -35,7 +91,7 @@ pub fn MusicPlaylistNew() -> Element {
                     }
                     p {
                         class: "text-muted-foreground mb-6",
-                        "You need to sign in to create playlists."
+                        "You need to sign in to create or edit playlists."
                     }
                     Link {
                         to: Route::Home {},
@@ -66,6 +122,8 @@ pub fn MusicPlaylistNew() -> Element {
         let is_public_val = *is_public.read();
         let is_collaborative_val = *is_collaborative.read();
         let categories_val = categories.read().clone();
+        let target_val = target.read().clone();
+        let track_refs_val = track_refs.read().clone();
 
         // Validation
         if title_val.is_empty() {
@@ -73,6 +131,17 @@ pub fn MusicPlaylistNew() -> Element {
             return;
         }
 
+        // New playlists get a d-tag from the title and timestamp; edits keep theirs
+        let d_tag = match target_val.identifier_for_publish(|| {
+            addressable::fresh_unique_identifier(&title_val, chrono::Utc::now().timestamp())
+        }) {
+            Ok(d_tag) => d_tag,
+            Err(e) => {
+                error_msg.set(Some(e));
+                return;
+            }
+        };
+
         is_publishing.set(true);
         error_msg.set(None);
 
@@ -80,27 +149,26 @@ pub fn MusicPlaylistNew() -> Element {
             let description = if description_val.is_empty() { None } else { Some(description_val) };
             let image = if image_url_val.is_empty() { None } else { Some(image_url_val) };
 
-            // Generate a unique d-tag based on title and timestamp
-            let d_tag = format!("{}-{}",
-                slugify(&title_val),
-                chrono::Utc::now().timestamp()
-            );
-
             match nostr_music::publish_playlist(
                 d_tag,
                 title_val,
                 description,
                 image,
-                Vec::new(), // Empty track list initially - user can add tracks later
+                track_refs_val, // Empty for new playlists - user can add tracks later
                 categories_val,
                 is_public_val,
                 is_collaborative_val,
             ).await {
                 Ok(_event_id) => {
-                    navigator.push(crate::routes::Route::MusicHome {});
+                    if target_val.is_edit() {
+                        navigator.go_back();
+                    } else {
+                        navigator.push(crate::routes::Route::MusicHome {});
+                    }
                 }
                 Err(e) => {
-                    error_msg.set(Some(format!("Failed to create playlist: {}", e)));
+                    let action = if target_val.is_edit() { "update" } else { "create" };
+                    error_msg.set(Some(format!("Failed to {} playlist: {}", action, e)));
                     is_publishing.set(false);
                 }
             }
@@ -133,7 +201,7 @@ pub fn MusicPlaylistNew() -> Element {
                 }
                 h1 {
                     class: "text-2xl font-bold",
-                    "Create Playlist"
+                    if is_edit { "Edit Playlist" } else { "Create Playlist" }
                 }
             }
 
@@ -290,8 +358,14 @@ pub fn MusicPlaylistNew() -> Element {
                 // Info box
                 div {
                     class: "p-4 bg-muted rounded-lg text-sm text-muted-foreground",
-                    p {
-                        "You can add tracks to your playlist after creating it. Visit the playlist page to add tracks from your library or discover new ones."
+                    if is_edit {
+                        p {
+                            "Saving replaces the published playlist. Its {track_refs.read().len()} tracks are kept."
+                        }
+                    } else {
+                        p {
+                            "You can add tracks to your playlist after creating it. Visit the playlist page to add tracks from your library or discover new ones."
+                        }
                     }
                 }
 
@@ -301,10 +375,14 @@ pub fn MusicPlaylistNew() -> Element {
                     button {
                         r#type: "button",
                         class: "w-full px-6 py-3 bg-primary text-primary-foreground rounded-lg font-medium hover:bg-primary/90 transition disabled:opacity-50 disabled:cursor-not-allowed",
-                        disabled: *is_publishing.read(),
+                        disabled: !can_publish,
                         onclick: handle_publish,
-                        if *is_publishing.read() {
-                            "Creating..."
+                        if *loading_existing.read() {
+                            "Loading..."
+                        } else if *is_publishing.read() {
+                            if is_edit { "Saving..." } else { "Creating..." }
+                        } else if is_edit {
+                            "Save Playlist"
                         } else {
                             "Create Playlist"
                         }
@@ -1,6 +1,10 @@
 use dioxus::prelude::*;
 use crate::routes::Route;
-use nostr_sdk::{PublicKey, EventId, FromBech32};
+use crate::stores::nostr_client;
+use crate::utils::nip19::{decode_nostr_uri, encode_naddr, relay_hints};
+use nostr_sdk::nips::nip19::Nip19;
+use nostr_sdk::{Event, Filter, Kind, PublicKey, EventId, FromBech32};
+use std::time::Duration;
 
 #[component]
 pub fn Nip19Handler(identifier: String) -> Element {
@@ -111,24 +115,118 @@ async fn decode_and_redirect(identifier: &str) -> Result<Route, String> {
             Err(e) => Err(format!("Invalid note ID: {}", e))
         }
     } else if identifier.starts_with("nprofile") {
-        // Profile with relay hints - not yet supported but we can extract the pubkey
-        Err("nprofile decoding not yet supported. Please use npub instead.".to_string())
+        // Profile with relay hints; fetch the metadata there so the profile page has it
+        let entity = decode_nostr_uri(identifier).ok_or("Invalid nprofile")?;
+        let Nip19::Profile(nprofile) = &entity else {
+            return Err("Invalid nprofile".to_string());
+        };
+        let pubkey = nprofile.public_key;
+        log::info!("Decoded nprofile: {} ({} relay hints)", pubkey, nprofile.relays.len());
+
+        let filter = Filter::new().author(pubkey).kind(Kind::Metadata).limit(1);
+        fetch_with_relay_hints(&relay_hints(&entity), filter).await;
+
+        Ok(Route::Profile {
+            pubkey: pubkey.to_hex()
+        })
     } else if identifier.starts_with("nevent") {
-        // Event with relay hints - not yet supported but we can extract the event ID
-        Err("nevent decoding not yet supported. Please use note instead.".to_string())
+        // Event with relay hints; fetch it there to find out where it belongs
+        let entity = decode_nostr_uri(identifier).ok_or("Invalid nevent")?;
+        let Nip19::Event(nevent) = &entity else {
+            return Err("Invalid nevent".to_string());
+        };
+        let hints = relay_hints(&entity);
+        log::info!("Decoded nevent: {} ({} relay hints)", nevent.event_id, hints.len());
+
+        let filter = Filter::new().id(nevent.event_id).limit(1);
+        let event = fetch_with_relay_hints(&hints, filter).await.into_iter().next();
+        Ok(event_route(nevent.event_id, nevent.kind, event.as_ref(), &hints))
     } else if identifier.starts_with("nsec") {
         // Secret key - security warning
         Err("🔒 This is a private key (nsec)! Never share your private key with anyone or paste it into websites. Keep it safe!".to_string())
     } else if identifier.starts_with("naddr") {
-        // Addressable event - not yet supported
-        Err("Addressable events (naddr) are not yet supported. Coming soon!".to_string())
+        // Addressable event with relay hints
+        let entity = decode_nostr_uri(identifier).ok_or("Invalid naddr")?;
+        let Nip19::Coordinate(naddr) = &entity else {
+            return Err("Invalid naddr".to_string());
+        };
+        let coordinate = &naddr.coordinate;
+        log::info!("Decoded naddr: {}:{}:{}", coordinate.kind, coordinate.public_key, coordinate.identifier);
+
+        let route = match coordinate.kind {
+            Kind::LongFormTextNote => Route::ArticleDetail { naddr: identifier.to_string() },
+            Kind::LiveEvent => Route::LiveStreamDetail { note_id: identifier.to_string() },
+            kind => return Err(format!("Addressable events of kind {} are not supported yet", kind)),
+        };
+
+        let filter = Filter::new()
+            .kind(coordinate.kind)
+            .author(coordinate.public_key)
+            .identifier(coordinate.identifier.clone())
+            .limit(1);
+        fetch_with_relay_hints(&relay_hints(&entity), filter).await;
+
+        Ok(route)
     } else if identifier.starts_with("nrelay") {
         // Relay URL
         Err("Relay URLs (nrelay) are not yet supported. Relay management coming soon.".to_string())
     } else {
         Err(format!(
-            "Unrecognized identifier type. Supported types: npub, note, nprofile, nevent. Got: {}",
+            "Unrecognized identifier type. Supported types: npub, note, nprofile, nevent, naddr. Got: {}",
             identifier.chars().take(6).collect::<String>()
         ))
     }
 }
+
+/// The page for an event, by its kind (from the event, or else the nevent)
+fn event_route(event_id: EventId, kind: Option<Kind>, event: Option<&Event>, hints: &[String]) -> Route {
+    let id = event_id.to_hex();
+    match event.map(|e| e.kind).or(kind) {
+        Some(Kind::Custom(20)) => Route::PhotoDetail { photo_id: id },
+        Some(Kind::Custom(21)) | Some(Kind::Custom(22)) => Route::VideoDetail { video_id: id },
+        Some(Kind::Poll) => Route::PollView { noteid: id },
+        Some(Kind::LongFormTextNote) => {
+            // Articles are addressed by naddr, which needs the event's d tag
+            event
+                .and_then(|e| {
+                    let d = e.tags.identifier()?;
+                    encode_naddr(e.kind, e.pubkey, d, hints).ok()
+                })
+                .map(|naddr| Route::ArticleDetail { naddr })
+                .unwrap_or(Route::Note { note_id: id, from_voice: None })
+        }
+        _ => Route::Note { note_id: id, from_voice: None },
+    }
+}
+
+/// Fetch events with an entity's relay hints added to the pool
+///
+/// Hint relays we aren't connected to are borrowed just for this fetch.
+/// Without hints, or when the hinted relays don't have it, the usual gossip
+/// routing is used. Errors only mean the destination page fetches it again,
+/// so they're logged and an empty list is returned.
+async fn fetch_with_relay_hints(hints: &[String], filter: Filter) -> Vec<Event> {
+    let Some(client) = nostr_client::get_client() else {
+        return Vec::new();
+    };
+
+    let temp_relays = nostr_client::add_temp_relays(&client, hints).await;
+
+    let mut events: Vec<Event> = Vec::new();
+    if !hints.is_empty() {
+        match client.fetch_events_from(hints.to_vec(), filter.clone(), Duration::from_secs(5)).await {
+            Ok(found) => events.extend(found),
+            Err(e) => log::debug!("Relay hint fetch failed: {}", e),
+        }
+    }
+    if events.is_empty() {
+        match client.fetch_events(filter, Duration::from_secs(10)).await {
+            Ok(found) => events.extend(found),
+            Err(e) => log::warn!("Failed to fetch NIP-19 entity: {}", e),
+        }
+    }
+
+    temp_relays.release().await;
+
+    events
+}
@@ -1,13 +1,18 @@
 use dioxus::prelude::*;
-use crate::stores::nostr_client;
+use crate::stores::{auth_store, contacts, nostr_client, zap_goals};
 use crate::routes::Route;
-use crate::components::{NoteCard, ThreadedComment, ClientInitializing, VoiceMessageCard};
-use crate::utils::{build_thread_tree, merge_pending_into_tree, event::is_voice_message};
+use crate::components::{NoteCard, ThreadedComment, ThreadSortSelect, ClientInitializing, VoiceMessageCard, OpenInApp, LinkedZapGoal};
+use crate::utils::{build_thread_tree, merge_pending_into_tree, apply_thread_scores, sort_thread, filter_thread_nodes, SortMode, event::is_voice_message};
+use crate::services::aggregation::fetch_interaction_counts_batch;
+use std::collections::{HashMap, HashSet};
 use crate::stores::pending_comments::get_pending_comments;
 use nostr_sdk::prelude::*;
 use nostr_sdk::Event as NostrEvent;
 use std::time::Duration;
 
+/// Reply levels shown expanded on the note page; deeper chains start collapsed
+const THREAD_COLLAPSE_DEPTH: usize = 3;
+
 // Helper functions for parallel loading
 
 async fn fetch_main_note(event_id: EventId) -> std::result::Result<NostrEvent, String> {
@@ -102,6 +107,11 @@ pub fn Note(note_id: String, from_voice: Option<String>) -> Element {
     let mut loading_parents = use_signal(|| false);
     let mut loading_replies = use_signal(|| false);
     let mut error = use_signal(|| None::<String>);
+    let sort_mode = use_signal(SortMode::default);
+    // Reactions + zaps per reply (event ID hex), loaded when sorting by top
+    let mut reply_scores = use_signal(|| None::<HashMap<String, u64>>);
+    // Only show replies from people we follow (and our own)
+    let mut following_only = use_signal(|| false);
 
     // PARALLEL LOADING - Fetch all data at once (10s instead of 30s)
     use_effect(use_reactive!(|note_id| {
@@ -119,6 +129,7 @@ pub fn Note(note_id: String, from_voice: Option<String>) -> Element {
             loading_parents.set(true);
             loading_replies.set(true);
             error.set(None);
+            reply_scores.set(None);
 
             // Clear profile cache to prevent stale author metadata when navigating between notes
             crate::stores::profiles::PROFILE_CACHE.write().clear();
@@ -196,6 +207,36 @@ pub fn Note(note_id: String, from_voice: Option<String>) -> Element {
         });
     }));
 
+    // The filter needs the contact list
+    use_effect(move || {
+        if *following_only.read() {
+            contacts::ensure_contact_list();
+        }
+    });
+
+    // Top sorting needs interaction counts for every reply
+    use_effect(move || {
+        if *sort_mode.read() != SortMode::Top || *loading_replies.read() || reply_scores.peek().is_some() {
+            return;
+        }
+        let reply_ids: Vec<EventId> = replies.peek().iter().map(|reply| reply.id).collect();
+        spawn(async move {
+            match fetch_interaction_counts_batch(reply_ids, Duration::from_secs(5)).await {
+                Ok(counts) => {
+                    let scores = counts
+                        .into_iter()
+                        .map(|(id, counts)| (id, (counts.likes + counts.zaps) as u64))
+                        .collect();
+                    reply_scores.set(Some(scores));
+                }
+                Err(e) => {
+                    log::warn!("Failed to load reply counts for sorting: {}", e);
+                    reply_scores.set(Some(HashMap::new()));
+                }
+            }
+        });
+    });
+
     rsx! {
         div {
             class: "min-h-screen",
@@ -293,6 +334,19 @@ pub fn Note(note_id: String, from_voice: Option<String>) -> Element {
                     }
                 }
 
+                // The NIP-75 zap goal this is (or points at), with progress
+                if let Some(goal_id) = zap_goals::linked_goal_id(event) {
+                    div {
+                        class: "px-4 pb-3",
+                        LinkedZapGoal { key: "{goal_id}", goal_id }
+                    }
+                }
+
+                // Other clients for kinds shown poorly here (NIP-89)
+                OpenInApp {
+                    event: event.clone()
+                }
+
                 div {
                     class: "border-b border-border"
                 }
@@ -323,13 +377,31 @@ pub fn Note(note_id: String, from_voice: Option<String>) -> Element {
                     // Only build thread tree after loading completes to avoid caching empty results
                     {
                         let reply_vec = replies.read().clone();
-                        let confirmed_tree = build_thread_tree(reply_vec, &event.id);
+                        let confirmed_tree = build_thread_tree(reply_vec, &event.id, Some(THREAD_COLLAPSE_DEPTH));
                         // Merge pending comments for optimistic display
                         let pending = get_pending_comments(&event.id);
-                        let thread_tree = merge_pending_into_tree(confirmed_tree, pending, &event.id);
+                        let mut thread_tree = merge_pending_into_tree(confirmed_tree, pending, &event.id);
+                        let mode = *sort_mode.read();
+                        if let Some(scores) = reply_scores.read().as_ref() {
+                            apply_thread_scores(&mut thread_tree, scores);
+                        }
+                        sort_thread(&mut thread_tree, mode);
+
+                        let total_replies = thread_tree.len();
+                        if *following_only.read() {
+                            let mut authors: HashSet<PublicKey> = contacts::CONTACT_LIST
+                                .read()
+                                .as_ref()
+                                .map(|list| list.contacts.iter().map(|contact| contact.pubkey).collect())
+                                .unwrap_or_default();
+                            if let Some(me) = auth_store::get_pubkey().and_then(|pk| PublicKey::from_hex(&pk).ok()) {
+                                authors.insert(me);
+                            }
+                            thread_tree = filter_thread_nodes(&thread_tree, &authors);
+                        }
 
                         rsx! {
-                            if thread_tree.is_empty() {
+                            if total_replies == 0 {
                                 div {
                                     class: "flex flex-col items-center justify-center py-10 px-4 text-center text-muted-foreground",
                                     p { "No replies yet" }
@@ -339,10 +411,34 @@ pub fn Note(note_id: String, from_voice: Option<String>) -> Element {
                                     }
                                 }
                             } else {
+                                div {
+                                    class: "flex items-center justify-between pl-4",
+                                    if auth_store::is_authenticated() {
+                                        label {
+                                            class: "flex items-center gap-2 text-sm text-muted-foreground cursor-pointer",
+                                            input {
+                                                r#type: "checkbox",
+                                                checked: *following_only.read(),
+                                                onchange: move |evt| following_only.set(evt.checked())
+                                            }
+                                            "Only people I follow"
+                                        }
+                                    } else {
+                                        div {}
+                                    }
+                                    ThreadSortSelect { mode: sort_mode }
+                                }
+                                if thread_tree.is_empty() {
+                                    div {
+                                        class: "py-10 px-4 text-center text-muted-foreground",
+                                        "No replies from people you follow"
+                                    }
+                                }
                                 div {
                                     class: "divide-y divide-border",
                                     for node in thread_tree {
                                         ThreadedComment {
+                                            key: "{node.event.id}",
                                             node: node.clone(),
                                             depth: 0
                                         }
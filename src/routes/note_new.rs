@@ -1,6 +1,12 @@
 use dioxus::prelude::*;
-use crate::stores::{nostr_client::publish_note, auth_store};
-use crate::components::{MediaUploader, EmojiPicker, GifPicker};
+use crate::stores::{nostr_client::{publish_note, publish_thread}, auth_store, scheduled_notes};
+use crate::components::{MediaUploader, EmojiPicker, GifPicker, LongNoteOptions};
+use crate::utils::note_split::{split_into_thread, DEFAULT_PART_LENGTH};
+use crate::utils::blockquote_seed;
+use crate::utils::nip19::decode_nostr_uri;
+use nostr_sdk::nips::nip19::Nip19;
+use nostr_sdk::Filter;
+use std::time::Duration;
 
 const MAX_LENGTH: usize = 5000;
 
@@ -16,6 +22,11 @@ pub fn NoteNew(quote: Option<String>) -> Element {
     let mut content = use_signal(move || initial_content);
     let mut is_publishing = use_signal(|| false);
     let mut show_image_uploader = use_signal(|| false);
+    let thread_mode = use_signal(|| false);
+    let mut show_schedule = use_signal(|| false);
+    let mut schedule_input = use_signal(String::new);
+    let mut schedule_error = use_signal(|| None::<String>);
+    let mut quoting_text = use_signal(|| false);
 
     // Check if user is authenticated
     let is_authenticated = use_memo(move || auth_store::AUTH_STATE.read().is_authenticated);
@@ -23,9 +34,12 @@ pub fn NoteNew(quote: Option<String>) -> Element {
     // Character count logic
     let char_count = content.read().chars().count();
     let remaining = MAX_LENGTH.saturating_sub(char_count);
-    let is_over_limit = char_count > MAX_LENGTH;
-    let show_warning = remaining < 100 && !is_over_limit;
+    // Thread mode splits the note into parts, so the single-note limit doesn't apply
+    let is_over_limit = char_count > MAX_LENGTH && !*thread_mode.read();
+    let show_warning = remaining < 100 && !is_over_limit && !*thread_mode.read();
     let can_publish = char_count > 0 && !is_over_limit && !*is_publishing.read();
+    // Threads need each part's ID before the next is signed, so only single notes can be scheduled
+    let is_scheduling = *show_schedule.read() && !*thread_mode.read();
 
     let counter_color = if is_over_limit {
         "text-red-500"
@@ -35,6 +49,35 @@ pub fn NoteNew(quote: Option<String>) -> Element {
         "text-muted-foreground"
     };
 
+    // Replace the bare quote reference with the quoted note's text as a blockquote
+    let quote_for_seed = quote.clone();
+    let handle_quote_text = move |_| {
+        let Some(quote) = quote_for_seed.clone() else {
+            return;
+        };
+        let event_id = match decode_nostr_uri(&quote) {
+            Some(Nip19::Event(nevent)) => nevent.event_id,
+            Some(Nip19::EventId(id)) => id,
+            _ => return,
+        };
+        quoting_text.set(true);
+        spawn(async move {
+            let filter = Filter::new().id(event_id).limit(1);
+            match crate::stores::nostr_client::fetch_events_aggregated(filter, Duration::from_secs(5)).await {
+                Ok(events) => match events.first().map(blockquote_seed) {
+                    Some(Ok(seed)) => {
+                        let rest = content.read().replace(&format!("nostr:{}", quote), "");
+                        content.set(format!("{}{}", seed, rest.trim()));
+                    }
+                    Some(Err(e)) => log::warn!("Failed to quote note: {}", e),
+                    None => log::warn!("Quoted note not found"),
+                },
+                Err(e) => log::warn!("Failed to fetch quoted note: {}", e),
+            }
+            quoting_text.set(false);
+        });
+    };
+
     // Handle publishing the note
     let handle_publish = move |_| {
         let content_value = content.read().clone();
@@ -43,10 +86,34 @@ pub fn NoteNew(quote: Option<String>) -> Element {
             return;
         }
 
+        let as_thread = *thread_mode.read();
+        let publish_at = if is_scheduling {
+            match scheduled_notes::parse_schedule_input(&schedule_input.read()).and_then(|at| {
+                scheduled_notes::validate_publish_at(at, chrono::Utc::now().timestamp() as u64).map(|_| at)
+            }) {
+                Ok(at) => Some(at),
+                Err(e) => {
+                    schedule_error.set(Some(e));
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+        schedule_error.set(None);
         is_publishing.set(true);
 
         spawn(async move {
-            match publish_note(content_value, Vec::new()).await {
+            let result = if let Some(publish_at) = publish_at {
+                scheduled_notes::schedule_note(content_value, publish_at).await
+            } else if as_thread {
+                let parts = split_into_thread(&content_value, DEFAULT_PART_LENGTH);
+                publish_thread(parts, Vec::new(), None).await.map(|ids| ids.join(", "))
+            } else {
+                publish_note(content_value, Vec::new()).await
+            };
+
+            match result {
                 Ok(event_id) => {
                     log::info!("Note published successfully: {}", event_id);
                     is_publishing.set(false);
@@ -54,6 +121,9 @@ pub fn NoteNew(quote: Option<String>) -> Element {
                 }
                 Err(e) => {
                     log::error!("Failed to publish note: {}", e);
+                    if publish_at.is_some() {
+                        schedule_error.set(Some(e));
+                    }
                     is_publishing.set(false);
                 }
             }
@@ -159,10 +229,57 @@ pub fn NoteNew(quote: Option<String>) -> Element {
                         autofocus: true,
                     }
 
+                    if quote.is_some() {
+                        button {
+                            class: "mt-2 px-2 py-1 text-xs rounded-full hover:bg-accent transition disabled:opacity-50",
+                            title: "Quote the note's text as well as linking it",
+                            disabled: *quoting_text.read() || *is_publishing.read(),
+                            onclick: handle_quote_text,
+                            if *quoting_text.read() { "Loading note..." } else { "❝ Include quoted text" }
+                        }
+                    }
+
                     // Character counter
                     div {
                         class: "mt-2 text-sm {counter_color} text-right",
-                        "{remaining} / {MAX_LENGTH}"
+                        if *thread_mode.read() {
+                            "{char_count} characters (thread)"
+                        } else {
+                            "{remaining} / {MAX_LENGTH}"
+                        }
+                    }
+
+                    // Long note warning with thread/article alternatives
+                    LongNoteOptions {
+                        content: content.read().clone(),
+                        thread_mode: thread_mode,
+                        disabled: *is_publishing.read()
+                    }
+
+                    // Schedule picker
+                    if is_scheduling {
+                        div {
+                            class: "mt-4 flex flex-col gap-1",
+                            label {
+                                class: "text-sm font-medium",
+                                "Publish at"
+                            }
+                            input {
+                                r#type: "datetime-local",
+                                class: "px-3 py-2 bg-background border border-border rounded-lg focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                value: "{schedule_input}",
+                                oninput: move |e| {
+                                    schedule_input.set(e.value());
+                                    schedule_error.set(None);
+                                },
+                            }
+                            if let Some(err) = schedule_error.read().as_ref() {
+                                p {
+                                    class: "text-sm text-red-500",
+                                    "{err}"
+                                }
+                            }
+                        }
                     }
 
                     // Media uploader
@@ -206,6 +323,24 @@ pub fn NoteNew(quote: Option<String>) -> Element {
                             on_gif_selected: handle_gif_selected,
                             icon_only: true
                         }
+
+                        // Schedule toggle
+                        if !*thread_mode.read() {
+                            button {
+                                class: if is_scheduling {
+                                    "p-2 rounded-full bg-accent text-blue-500 transition"
+                                } else {
+                                    "p-2 rounded-full hover:bg-accent transition"
+                                },
+                                title: "Schedule",
+                                onclick: move |_| {
+                                    let current = *show_schedule.read();
+                                    show_schedule.set(!current);
+                                    schedule_error.set(None);
+                                },
+                                "🕒"
+                            }
+                        }
                     }
 
                     // Publish button
@@ -219,7 +354,9 @@ pub fn NoteNew(quote: Option<String>) -> Element {
                         onclick: handle_publish,
 
                         if *is_publishing.read() {
-                            "Publishing..."
+                            if is_scheduling { "Scheduling..." } else { "Publishing..." }
+                        } else if is_scheduling {
+                            "Schedule"
                         } else {
                             "Post"
                         }
@@ -1,55 +1,14 @@
 use dioxus::prelude::*;
-use crate::stores::{auth_store, nostr_client, notifications as notif_store, profiles};
+use crate::stores::{auth_store, home_feed, nostr_client, notifications as notif_store, profiles};
+use crate::stores::notifications::{NotificationFilter, NotificationType};
 use crate::components::{NoteCard, ClientInitializing};
 use crate::hooks::use_infinite_scroll;
 use crate::routes::Route;
-use nostr_sdk::{Event as NostrEvent, Filter, Kind, Timestamp};
+use futures::future::join_all;
+use nostr_sdk::{Event as NostrEvent, EventId, Filter, Kind, PublicKey, Timestamp};
+use std::collections::HashSet;
 use std::time::Duration;
 
-#[derive(Clone, Debug, PartialEq)]
-#[allow(dead_code)]
-enum NotificationType {
-    Mention(NostrEvent),
-    Reply(NostrEvent),
-    Reaction(NostrEvent),
-    Repost(NostrEvent),
-    Zap(NostrEvent),
-}
-
-#[derive(Clone, Copy, PartialEq)]
-enum NotificationFilter {
-    All,
-    Replies,
-    Mentions,
-    Reactions,
-    Reposts,
-    Zaps,
-}
-
-impl NotificationFilter {
-    fn label(&self) -> &'static str {
-        match self {
-            Self::All => "All",
-            Self::Replies => "Replies",
-            Self::Mentions => "Mentions",
-            Self::Reactions => "Reactions",
-            Self::Reposts => "Reposts",
-            Self::Zaps => "Zaps",
-        }
-    }
-
-    fn matches(&self, notification: &NotificationType) -> bool {
-        match self {
-            Self::All => true,
-            Self::Replies => matches!(notification, NotificationType::Reply(_)),
-            Self::Mentions => matches!(notification, NotificationType::Mention(_)),
-            Self::Reactions => matches!(notification, NotificationType::Reaction(_)),
-            Self::Reposts => matches!(notification, NotificationType::Repost(_)),
-            Self::Zaps => matches!(notification, NotificationType::Zap(_)),
-        }
-    }
-}
-
 #[component]
 pub fn Notifications() -> Element {
     let mut notifications = use_signal(|| Vec::<NotificationType>::new());
@@ -59,6 +18,9 @@ pub fn Notifications() -> Element {
     let mut active_filter = use_signal(|| NotificationFilter::All);
     let mut has_more = use_signal(|| true);
     let mut oldest_timestamp = use_signal(|| None::<u64>);
+    // Last checked time from before this visit: what arrived since stays
+    // marked unread on the page until "Mark all read"
+    let mut seen_until = use_signal(notif_store::get_checked_at);
 
     // Load initial notifications (limit: 100 for historical data)
     use_effect(move || {
@@ -70,29 +32,25 @@ pub fn Notifications() -> Element {
             return;
         }
 
-        // Mark notifications as checked at current time (updates localStorage and clears badge)
-        let now = Timestamp::now().as_secs() as i64;
-        notif_store::set_checked_at(now);
+        // Visiting marks notifications as checked (updates localStorage and clears badge)
+        notif_store::mark_all_read();
 
         loading.set(true);
         error.set(None);
 
         spawn(async move {
             match load_notifications(None).await {
-                Ok(notifs) => {
+                Ok(NotificationPage { notifications: notifs, next_until }) => {
+                    oldest_timestamp.set(next_until);
+                    has_more.set(next_until.is_some());
                     if !notifs.is_empty() {
-                        let oldest = notifs.iter().map(|n| get_timestamp(n)).min();
-                        oldest_timestamp.set(oldest);
-                        let len = notifs.len();
+                        notif_store::set_unread_count(notif_store::count_unread(&notifs, notif_store::get_checked_at()));
                         notifications.set(notifs.clone());
-                        has_more.set(len >= 100);
 
                         // Spawn non-blocking background prefetch for notification authors
                         spawn(async move {
                             prefetch_notification_authors(&notifs).await;
                         });
-                    } else {
-                        has_more.set(false);
                     }
                 }
                 Err(e) => {
@@ -112,15 +70,15 @@ pub fn Notifications() -> Element {
         }
 
         refreshing.set(true);
+        notif_store::LIVE_NOTIFICATIONS.write().clear();
         spawn(async move {
             match load_notifications(None).await {
-                Ok(notifs) => {
+                Ok(NotificationPage { notifications: notifs, next_until }) => {
+                    oldest_timestamp.set(next_until);
+                    has_more.set(next_until.is_some());
                     if !notifs.is_empty() {
-                        let oldest = notifs.iter().map(|n| get_timestamp(n)).min();
-                        oldest_timestamp.set(oldest);
-                        let len = notifs.len();
+                        notif_store::set_unread_count(notif_store::count_unread(&notifs, notif_store::get_checked_at()));
                         notifications.set(notifs.clone());
-                        has_more.set(len >= 100);
 
                         // Spawn non-blocking background prefetch for notification authors
                         spawn(async move {
@@ -147,23 +105,17 @@ pub fn Notifications() -> Element {
 
         spawn(async move {
             match load_notifications(until).await {
-                Ok(new_notifs) => {
+                Ok(NotificationPage { notifications: new_notifs, next_until }) => {
+                    oldest_timestamp.set(next_until);
+                    has_more.set(next_until.is_some());
                     if !new_notifs.is_empty() {
-                        let oldest = new_notifs.iter().map(|n| get_timestamp(n)).min();
-                        oldest_timestamp.set(oldest);
-
-                        let mut current = notifications.read().clone();
-                        current.extend(new_notifs.clone());
-                        notifications.set(current);
-
-                        has_more.set(new_notifs.len() >= 100);
+                        // Relays ignore `until` differently, so pages can overlap
+                        notif_store::merge_into(&mut notifications.write(), new_notifs.clone());
 
                         // Spawn non-blocking background prefetch for notification authors
                         spawn(async move {
                             prefetch_notification_authors(&new_notifs).await;
                         });
-                    } else {
-                        has_more.set(false);
                     }
                 }
                 Err(_) => {
@@ -177,14 +129,50 @@ pub fn Notifications() -> Element {
     // Setup infinite scroll (callback, has_more, loading)
     let sentinel_id = use_infinite_scroll(load_more, has_more, loading);
 
+    // Notifications that arrived live and aren't in the list yet. They wait
+    // behind a button so the list doesn't shift under the reader.
+    let pending_notifications = use_memo(move || {
+        let list = notifications.read();
+        let oldest_shown = list.last().map(|n| n.timestamp()).unwrap_or(0);
+        let shown: HashSet<EventId> = list.iter().map(|n| n.event().id).collect();
+        notif_store::LIVE_NOTIFICATIONS.read()
+            .iter()
+            .filter(|n| !shown.contains(&n.event().id) && n.timestamp() >= oldest_shown)
+            .cloned()
+            .collect::<Vec<_>>()
+    });
+
+    // Merge pending notifications into the list and jump to the top
+    let show_new_notifications = move |_| {
+        let pending = pending_notifications.read().clone();
+        notif_store::merge_into(&mut notifications.write(), pending.clone());
+        notif_store::LIVE_NOTIFICATIONS.write().clear();
+
+        spawn(async move {
+            prefetch_notification_authors(&pending).await;
+        });
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            if let Some(window) = web_sys::window() {
+                window.scroll_to_with_x_and_y(0.0, 0.0);
+            }
+        }
+    };
+
     let auth = auth_store::AUTH_STATE.read();
 
     // Filter notifications based on active filter
-    let filtered_notifications: Vec<NotificationType> = notifications.read()
-        .iter()
-        .filter(|n| active_filter.read().matches(n))
-        .cloned()
-        .collect();
+    let filtered_notifications = notif_store::filter_by_kind(&notifications.read(), *active_filter.read());
+    let checked_at = *seen_until.read();
+    let has_unread = notif_store::get_unread_count() > 0
+        || notifications.read().iter().any(|n| n.is_unread(checked_at));
+
+    // Mark everything read; the badge clears and unread markers go away
+    let handle_mark_all_read = move |_| {
+        notif_store::mark_all_read();
+        seen_until.set(notif_store::get_checked_at());
+    };
 
     rsx! {
         div {
@@ -200,13 +188,23 @@ pub fn Notifications() -> Element {
                         "🔔 Notifications"
                     }
                     if auth.is_authenticated {
-                        button {
-                            class: "p-2 hover:bg-accent rounded-lg transition",
-                            onclick: handle_refresh,
-                            disabled: *refreshing.read(),
-                            span {
-                                class: if *refreshing.read() { "inline-block animate-spin" } else { "" },
-                                "🔄"
+                        div {
+                            class: "flex items-center gap-2",
+                            if has_unread {
+                                button {
+                                    class: "px-3 py-1.5 text-sm hover:bg-accent rounded-lg transition",
+                                    onclick: handle_mark_all_read,
+                                    "Mark all read"
+                                }
+                            }
+                            button {
+                                class: "p-2 hover:bg-accent rounded-lg transition",
+                                onclick: handle_refresh,
+                                disabled: *refreshing.read(),
+                                span {
+                                    class: if *refreshing.read() { "inline-block animate-spin" } else { "" },
+                                    "🔄"
+                                }
                             }
                         }
                     }
@@ -218,16 +216,12 @@ pub fn Notifications() -> Element {
                         class: "px-4 pb-2 overflow-x-auto",
                         div {
                             class: "flex gap-2 min-w-max",
-                            for filter in [
-                                NotificationFilter::All,
-                                NotificationFilter::Replies,
-                                NotificationFilter::Mentions,
-                                NotificationFilter::Reactions,
-                                NotificationFilter::Reposts,
-                                NotificationFilter::Zaps
-                            ] {
+                            for filter in NotificationFilter::ALL {
                                 {
                                     let is_active = *active_filter.read() == filter;
+                                    let tab_unread = notifications.read().iter()
+                                        .filter(|n| filter.matches(n) && n.is_unread(checked_at))
+                                        .count();
                                     rsx! {
                                         button {
                                             key: "{filter.label()}",
@@ -241,6 +235,12 @@ pub fn Notifications() -> Element {
                                                 active_filter.set(filter);
                                             },
                                             span { "{filter.label()}" }
+                                            if tab_unread > 0 {
+                                                span {
+                                                    class: "ml-1.5 px-1.5 py-0.5 text-xs rounded-full bg-primary text-primary-foreground",
+                                                    "{tab_unread}"
+                                                }
+                                            }
                                             if is_active {
                                                 div {
                                                     class: "absolute bottom-0 left-0 right-0 h-0.5 bg-primary rounded-full"
@@ -319,10 +319,31 @@ pub fn Notifications() -> Element {
                             }
                         }
                     } else {
+                        // New notifications indicator (sticky below the header)
+                        if !pending_notifications.read().is_empty() {
+                            {
+                                let count = pending_notifications.read().len();
+                                let noun = if count == 1 { "notification" } else { "notifications" };
+                                rsx! {
+                                    div {
+                                        class: "sticky top-[105px] z-10 border-b border-border bg-blue-500 hover:bg-blue-600 transition-colors cursor-pointer",
+                                        onclick: show_new_notifications,
+                                        div {
+                                            class: "px-4 py-3 text-center",
+                                            span {
+                                                class: "text-white font-medium",
+                                                "Show {count} new {noun}"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
                         div {
                             class: "divide-y divide-border",
                             for notification in filtered_notifications.iter() {
-                                {render_notification(notification)}
+                                {render_notification(notification, notification.is_unread(checked_at))}
                             }
 
                             // Infinite scroll sentinel
@@ -351,12 +372,12 @@ pub fn Notifications() -> Element {
     }
 }
 
-fn render_notification(notification: &NotificationType) -> Element {
-    match notification {
+fn render_notification(notification: &NotificationType, unread: bool) -> Element {
+    let event = notification.event();
+    let body = match notification {
         NotificationType::Mention(event) | NotificationType::Reply(event) => {
             rsx! {
                 div {
-                    key: "{event.id}",
                     class: "p-4 hover:bg-accent/50 transition",
                     div {
                         class: "flex items-center gap-2 mb-2 text-sm text-muted-foreground",
@@ -378,7 +399,6 @@ fn render_notification(notification: &NotificationType) -> Element {
         NotificationType::Reaction(event) => {
             rsx! {
                 ReactionNotification {
-                    key: "{event.id}",
                     event: event.clone()
                 }
             }
@@ -386,7 +406,6 @@ fn render_notification(notification: &NotificationType) -> Element {
         NotificationType::Repost(event) => {
             rsx! {
                 RepostNotification {
-                    key: "{event.id}",
                     event: event.clone()
                 }
             }
@@ -394,11 +413,25 @@ fn render_notification(notification: &NotificationType) -> Element {
         NotificationType::Zap(event) => {
             rsx! {
                 ZapNotification {
-                    key: "{event.id}",
                     event: event.clone()
                 }
             }
         }
+        NotificationType::Follow(event) => {
+            rsx! {
+                FollowNotification {
+                    event: event.clone()
+                }
+            }
+        }
+    };
+
+    rsx! {
+        div {
+            key: "{event.id}",
+            class: if unread { "border-l-2 border-primary bg-primary/5" } else { "" },
+            {body}
+        }
     }
 }
 
@@ -812,6 +845,67 @@ fn ZapNotification(event: NostrEvent) -> Element {
     }
 }
 
+#[component]
+fn FollowNotification(event: NostrEvent) -> Element {
+    let mut profile = use_signal(|| None::<profiles::Profile>);
+
+    let follower_pubkey = event.pubkey.to_string();
+    let follower_pubkey_for_effect = follower_pubkey.clone();
+
+    // Fetch follower's profile
+    use_effect(move || {
+        let pubkey = follower_pubkey_for_effect.clone();
+        spawn(async move {
+            if let Ok(p) = profiles::fetch_profile(pubkey).await {
+                profile.set(Some(p));
+            }
+        });
+    });
+
+    let display_name = profile.read().as_ref()
+        .map(|p| p.get_display_name())
+        .unwrap_or_else(|| format!("{}...", &follower_pubkey[..16]));
+
+    let avatar_url = profile.read().as_ref()
+        .map(|p| p.get_avatar_url())
+        .unwrap_or_else(|| format!("https://api.dicebear.com/7.x/identicon/svg?seed={}", follower_pubkey));
+
+    rsx! {
+        div {
+            class: "p-4 hover:bg-accent/50 transition",
+            div {
+                class: "flex items-center gap-3",
+                Link {
+                    to: Route::Profile { pubkey: follower_pubkey.clone() },
+                    onclick: move |e: MouseEvent| e.stop_propagation(),
+                    img {
+                        src: "{avatar_url}",
+                        alt: "{display_name}",
+                        class: "w-10 h-10 rounded-full object-cover flex-shrink-0",
+                    }
+                }
+                div {
+                    class: "flex items-center gap-2 text-sm",
+                    span {
+                        class: "text-2xl",
+                        "👤"
+                    }
+                    Link {
+                        to: Route::Profile { pubkey: follower_pubkey.clone() },
+                        onclick: move |e: MouseEvent| e.stop_propagation(),
+                        class: "font-semibold hover:underline",
+                        "{display_name}"
+                    }
+                    span {
+                        class: "text-muted-foreground",
+                        "followed you"
+                    }
+                }
+            }
+        }
+    }
+}
+
 /// Helper to extract the actual zapper's pubkey from a zap receipt event (kind 9735)
 /// The event.pubkey is the Lightning node's pubkey, the actual zapper is in the description
 fn extract_zapper_pubkey(event: &NostrEvent) -> Option<String> {
@@ -910,98 +1004,131 @@ fn parse_bolt11_amount(bolt11: &str) -> Option<u64> {
     Some(sats)
 }
 
-/// Helper to get timestamp from notification
-fn get_timestamp(notification: &NotificationType) -> u64 {
-    match notification {
-        NotificationType::Mention(e) | NotificationType::Reply(e) |
-        NotificationType::Reaction(e) | NotificationType::Repost(e) |
-        NotificationType::Zap(e) => e.created_at.as_secs(),
-    }
+/// Most notifications asked of each relay per page
+const PAGE_LIMIT: usize = 100;
+
+/// A page of notifications and where the next one starts
+struct NotificationPage {
+    notifications: Vec<NotificationType>,
+    /// `until` of the next page; None once every relay ran out
+    next_until: Option<u64>,
 }
 
-async fn load_notifications(until: Option<u64>) -> Result<Vec<NotificationType>, String> {
+async fn load_notifications(until: Option<u64>) -> Result<NotificationPage, String> {
     let client = nostr_client::NOSTR_CLIENT.read().as_ref()
         .ok_or("Client not initialized")?.clone();
 
     let pubkey_str = auth_store::get_pubkey()
         .ok_or("Not authenticated")?;
+    let my_pubkey = PublicKey::parse(&pubkey_str)
+        .map_err(|e| format!("Invalid pubkey: {}", e))?;
 
     log::info!("Loading notifications for {} (until: {:?})", pubkey_str, until);
 
-    let mut all_notifications = Vec::new();
-
     // Build unified filter for all notification types using #p tag
     // This is the correct way - fetch events that tag our pubkey
     // Use limit: 100 for historical/initial load
     let mut filter = Filter::new()
-        .kinds(vec![
-            Kind::TextNote,      // 1 - for mentions and replies
-            Kind::Repost,        // 6
-            Kind::Reaction,      // 7
-            Kind::ZapReceipt,    // 9735
-        ])
+        .kinds(notif_store::NOTIFICATION_KINDS)
         .custom_tag(
             nostr_sdk::SingleLetterTag::lowercase(nostr_sdk::Alphabet::P),
             pubkey_str.clone()
         )
-        .limit(100);
+        .limit(PAGE_LIMIT);
 
     // Add until timestamp for pagination
     if let Some(until_ts) = until {
         filter = filter.until(Timestamp::from(until_ts));
     }
 
-    // Use gossip for automatic relay routing
-    match client.fetch_events(filter, Duration::from_secs(10)).await
-        .map(|e| e.into_iter().collect::<Vec<_>>()) {
-        Ok(events) => {
-            for event in events {
-                // Skip our own events
-                if event.pubkey.to_hex() == pubkey_str {
-                    continue;
-                }
-
-                match event.kind {
-                    Kind::TextNote => {
-                        // Check if it's a reply (has 'e' tag) or just a mention
-                        let is_reply = event.tags.iter().any(|tag| {
-                            tag.kind() == nostr_sdk::TagKind::SingleLetter(
-                                nostr_sdk::SingleLetterTag::lowercase(nostr_sdk::Alphabet::E)
-                            )
-                        });
-
-                        if is_reply {
-                            all_notifications.push(NotificationType::Reply(event));
-                        } else {
-                            all_notifications.push(NotificationType::Mention(event));
-                        }
-                    }
-                    Kind::Reaction => {
-                        all_notifications.push(NotificationType::Reaction(event));
-                    }
-                    Kind::Repost => {
-                        all_notifications.push(NotificationType::Repost(event));
-                    }
-                    Kind::ZapReceipt => {
-                        all_notifications.push(NotificationType::Zap(event));
-                    }
-                    _ => {}
-                }
-            }
-        }
-        Err(e) => {
+    // Ask the gossip-routed relays and each of our relays separately, so one
+    // relay's limit doesn't hide what the others have
+    let relay_urls: Vec<String> = client.relays().await.keys().map(|url| url.to_string()).collect();
+    let gossip = client.fetch_events(filter.clone(), Duration::from_secs(10));
+    let per_relay = join_all(relay_urls.iter().map(|url| {
+        client.fetch_events_from([url.as_str()], filter.clone(), Duration::from_secs(10))
+    }));
+    let (gossip_result, relay_results) = futures::join!(gossip, per_relay);
+
+    let mut batches: Vec<Vec<NostrEvent>> = relay_results
+        .into_iter()
+        .filter_map(|result| result.ok())
+        .map(|events| events.into_iter().collect())
+        .collect();
+    match gossip_result {
+        Ok(events) => batches.push(events.into_iter().collect()),
+        Err(e) if batches.is_empty() => {
             log::error!("Failed to fetch notifications: {}", e);
             return Err(format!("Failed to fetch notifications: {}", e));
         }
+        Err(e) => log::warn!("Gossip notification fetch failed, using relay results: {}", e),
     }
 
-    // Sort by timestamp (newest first)
-    all_notifications.sort_by(|a, b| {
-        get_timestamp(b).cmp(&get_timestamp(a))
-    });
+    // A relay that filled its page has older notifications left, so the page
+    // ends where the fullest such relay stopped and the next one starts there
+    let more_left = batches.iter().any(|batch| batch.len() >= PAGE_LIMIT);
+    let events = home_feed::merge_source_pages(
+        batches.into_iter().map(|batch| (batch, Some(PAGE_LIMIT))).collect(),
+    );
+    let next_until = more_left
+        .then(|| events.iter().map(|event| event.created_at.as_secs()).min())
+        .flatten();
+    let batches = vec![events];
+
+    let deleted = deleted_reaction_targets(&client, &my_pubkey, &batches).await;
+    let mut all_notifications = notif_store::merge_relay_batches(batches, &my_pubkey, &deleted);
+
+    // Leave out replies in threads we muted (refreshes the cached mute list)
+    if let Err(e) = nostr_client::get_muted_posts().await {
+        log::warn!("Failed to load muted threads: {}", e);
+    }
+    let muted_threads = nostr_client::MUTED_THREADS.read().clone();
+    all_notifications.retain(|n| !notif_store::is_in_muted_thread(n.event(), &muted_threads));
 
     log::info!("Loaded {} notifications", all_notifications.len());
-    Ok(all_notifications)
+    Ok(NotificationPage { notifications: all_notifications, next_until })
+}
+
+/// Notes we deleted that reactions in `batches` point at
+///
+/// Deletions made in this session are known already; older ones come from
+/// our kind 5 requests on relays.
+async fn deleted_reaction_targets(
+    client: &nostr_sdk::Client,
+    my_pubkey: &PublicKey,
+    batches: &[Vec<NostrEvent>],
+) -> HashSet<EventId> {
+    let targets: HashSet<EventId> = batches
+        .iter()
+        .flatten()
+        .filter(|event| event.kind == Kind::Reaction)
+        .filter_map(notif_store::reacted_event_id)
+        .collect();
+    if targets.is_empty() {
+        return HashSet::new();
+    }
+
+    let mut deleted: HashSet<EventId> = {
+        let session_deleted = nostr_client::DELETED_EVENTS.read();
+        targets.iter().filter(|id| session_deleted.contains(&id.to_hex())).copied().collect()
+    };
+
+    let filter = Filter::new()
+        .kind(Kind::EventDeletion)
+        .author(*my_pubkey)
+        .events(targets.iter().copied());
+    match client.fetch_events(filter, Duration::from_secs(5)).await {
+        Ok(deletions) => {
+            deleted.extend(
+                deletions
+                    .iter()
+                    .flat_map(|deletion| deletion.tags.event_ids().copied().collect::<Vec<_>>())
+                    .filter(|id| targets.contains(id)),
+            );
+        }
+        Err(e) => log::warn!("Failed to fetch deletions for reacted notes: {}", e),
+    }
+    deleted
 }
 
 /// Batch prefetch author metadata for notification authors
@@ -1013,15 +1140,7 @@ async fn prefetch_notification_authors(notifications: &[NotificationType]) {
     }
 
     // Extract pubkeys directly without string conversion
-    let pubkeys = profile_prefetch::extract_pubkeys(notifications, |notif| {
-        match notif {
-            NotificationType::Mention(e) => e.pubkey,
-            NotificationType::Reply(e) => e.pubkey,
-            NotificationType::Reaction(e) => e.pubkey,
-            NotificationType::Repost(e) => e.pubkey,
-            NotificationType::Zap(e) => e.pubkey,
-        }
-    });
+    let pubkeys = profile_prefetch::extract_pubkeys(notifications, |notif| notif.event().pubkey);
 
     // Use optimized prefetch utility - no string conversions, direct database queries
     profile_prefetch::prefetch_pubkeys(pubkeys).await;
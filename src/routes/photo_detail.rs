@@ -1,7 +1,7 @@
 use dioxus::prelude::*;
 use crate::stores::nostr_client;
 use crate::components::{PhotoCard, ThreadedComment, CommentComposer, ClientInitializing};
-use crate::utils::{build_thread_tree, merge_pending_into_tree};
+use crate::utils::{build_thread_tree, merge_pending_into_tree, nip22};
 use crate::stores::pending_comments::get_pending_comments;
 use nostr_sdk::{Event, Filter, Kind, EventId};
 use std::time::Duration;
@@ -53,51 +53,10 @@ pub fn PhotoDetail(photo_id: String) -> Element {
             loading_comments.set(true);
 
             spawn(async move {
-                let event_id = event.id;
-                let event_id_hex = event_id.to_hex();
+                log::info!("Loading comments for photo {}", event.id.to_hex());
 
-                log::info!("Loading comments for photo {}", event_id_hex);
-
-                // Create filter for uppercase E tags (NIP-22 comments)
-                let upper_e_tag = nostr_sdk::SingleLetterTag::uppercase(nostr_sdk::Alphabet::E);
-                let filter_upper = Filter::new()
-                    .kind(Kind::Comment)
-                    .custom_tag(upper_e_tag, event_id_hex.clone())
-                    .limit(500);
-
-                // Create filter for lowercase e tags (standard replies)
-                let filter_lower = Filter::new()
-                    .kinds(vec![Kind::TextNote, Kind::Comment])
-                    .event(event_id)
-                    .limit(500);
-
-                log::info!("Fetching comments with uppercase E and lowercase e tag filters");
-
-                // Fetch both filters and combine results
-                let mut all_comments = Vec::new();
-
-                if let Ok(upper_comments) = nostr_client::fetch_events_aggregated(filter_upper, Duration::from_secs(10)).await {
-                    log::info!("Loaded {} comments with uppercase E tags", upper_comments.len());
-                    all_comments.extend(upper_comments.into_iter());
-                } else {
-                    log::warn!("Failed to fetch comments with uppercase E tags");
-                }
-
-                if let Ok(lower_comments) = nostr_client::fetch_events_aggregated(filter_lower, Duration::from_secs(10)).await {
-                    log::info!("Loaded {} comments with lowercase e tags", lower_comments.len());
-                    all_comments.extend(lower_comments.into_iter());
-                } else {
-                    log::warn!("Failed to fetch comments with lowercase e tags");
-                }
-
-                // Deduplicate by event ID
-                let mut seen_ids = std::collections::HashSet::new();
-                let unique_comments: Vec<Event> = all_comments.into_iter()
-                    .filter(|event| seen_ids.insert(event.id))
-                    .collect();
-
-                let mut sorted_comments = unique_comments;
-                sorted_comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+                // NIP-22 comments scoped to this photo, plus kind 1 replies
+                let sorted_comments = nip22::fetch_comments(&event).await;
                 log::info!("Total unique comments: {}", sorted_comments.len());
                 comments.set(sorted_comments);
 
@@ -198,7 +157,7 @@ pub fn PhotoDetail(photo_id: String) -> Element {
                                 // Build thread tree and render
                                 {
                                     let comment_vec = comments.read().clone();
-                                    let confirmed_tree = build_thread_tree(comment_vec, &event.id);
+                                    let confirmed_tree = build_thread_tree(comment_vec, &event.id, None);
                                     // Merge pending comments for optimistic display
                                     let pending = get_pending_comments(&event.id);
                                     let thread_tree = merge_pending_into_tree(confirmed_tree, pending, &event.id);
@@ -210,7 +169,8 @@ pub fn PhotoDetail(photo_id: String) -> Element {
                                                 ThreadedComment {
                                                     key: "{node.event.id}",
                                                     node: node.clone(),
-                                                    depth: 0
+                                                    depth: 0,
+                                                    comment_root: Some(event.clone())
                                                 }
                                             }
                                         }
@@ -232,38 +192,7 @@ pub fn PhotoDetail(photo_id: String) -> Element {
                                 let event_clone = event.clone();
                                 spawn(async move {
                                     loading_comments.set(true);
-                                    let event_id = event_clone.id;
-                                    let event_id_hex = event_id.to_hex();
-
-                                    let upper_e_tag = nostr_sdk::SingleLetterTag::uppercase(nostr_sdk::Alphabet::E);
-                                    let filter_upper = Filter::new()
-                                        .kind(Kind::Comment)
-                                        .custom_tag(upper_e_tag, event_id_hex.clone())
-                                        .limit(500);
-
-                                    let filter_lower = Filter::new()
-                                        .kinds(vec![Kind::TextNote, Kind::Comment])
-                                        .event(event_id)
-                                        .limit(500);
-
-                                    let mut all_comments = Vec::new();
-
-                                    if let Ok(upper_comments) = nostr_client::fetch_events_aggregated(filter_upper, Duration::from_secs(10)).await {
-                                        all_comments.extend(upper_comments.into_iter());
-                                    }
-
-                                    if let Ok(lower_comments) = nostr_client::fetch_events_aggregated(filter_lower, Duration::from_secs(10)).await {
-                                        all_comments.extend(lower_comments.into_iter());
-                                    }
-
-                                    let mut seen_ids = std::collections::HashSet::new();
-                                    let unique_comments: Vec<Event> = all_comments.into_iter()
-                                        .filter(|event| seen_ids.insert(event.id))
-                                        .collect();
-
-                                    let mut sorted_comments = unique_comments;
-                                    sorted_comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-                                    comments.set(sorted_comments);
+                                    comments.set(nip22::fetch_comments(&event_clone).await);
                                     loading_comments.set(false);
                                 });
                             }
@@ -1,25 +1,61 @@
 use dioxus::prelude::*;
-use crate::stores::auth_store;
-use crate::components::MediaUploader;
+use std::collections::HashMap;
+use crate::stores::{auth_store, blossom_store};
+use crate::stores::photo_draft::{self, ComposerImage, ImageUploadStatus, PhotoDraft};
+
+/// Compression quality for picture uploads
+const UPLOAD_QUALITY: u8 = 80;
+
+const FILE_INPUT_ID: &str = "photo-new-files";
 
 #[component]
 pub fn PhotoNew() -> Element {
     let navigator = navigator();
-    let mut title = use_signal(|| String::new());
-    let mut caption = use_signal(|| String::new());
-    let mut image_urls = use_signal(|| Vec::<String>::new());
-    let mut hashtags = use_signal(|| String::new());
-    let mut location = use_signal(|| String::new());
+    // Unsent post from last time, restored into the fields below
+    let restored = use_hook(photo_draft::load_draft);
+    let mut show_restored_notice = use_signal(|| restored.is_some());
+
+    let draft = restored.clone().unwrap_or_default();
+    let mut title = use_signal(|| draft.title.clone());
+    let mut caption = use_signal(|| draft.caption.clone());
+    let mut hashtags = use_signal(|| draft.hashtags.clone());
+    let mut location = use_signal(|| draft.location.clone());
+    let mut images = use_signal(|| {
+        draft.images.iter().cloned().map(|image| image.into_composer()).collect::<Vec<ComposerImage>>()
+    });
+    // Bytes and MIME type of images not uploaded yet, by image ID
+    let mut pending_files = use_signal(HashMap::<String, (Vec<u8>, String)>::new);
+    let mut uploading = use_signal(|| false);
     let mut is_publishing = use_signal(|| false);
-    let mut show_image_uploader = use_signal(|| true);
+    let mut confirm_partial = use_signal(|| false);
     let mut error_message = use_signal(|| Option::<String>::None);
+    let upload_progress = *blossom_store::UPLOAD_PROGRESS.read();
 
     // Check if user is authenticated
     let is_authenticated = use_memo(move || auth_store::AUTH_STATE.read().is_authenticated);
 
+    // Autosave text, uploaded images and alt text
+    use_effect(move || {
+        let draft = PhotoDraft::new(
+            &title.read(),
+            &caption.read(),
+            &location.read(),
+            &hashtags.read(),
+            &images.read(),
+        );
+        if !*is_publishing.peek() {
+            photo_draft::save_draft(&draft);
+        }
+    });
+
+    let uploaded_count = images.read().iter().filter(|image| image.uploaded().is_some()).count();
+    let failed_count = images.read().iter().filter(|image| image.is_failed()).count();
+    let pending_count = images.read().iter().filter(|image| image.is_pending()).count();
+
     // Validation
     let can_publish = title.read().chars().count() > 0
-        && image_urls.read().len() > 0
+        && uploaded_count > 0
+        && pending_count == 0
         && !*is_publishing.read();
 
     // Handle close
@@ -27,20 +63,81 @@ pub fn PhotoNew() -> Element {
         navigator.go_back();
     };
 
-    // Handle image upload
-    let handle_image_uploaded = move |url: String| {
-        let mut urls = image_urls.write();
-        urls.push(url.clone());
-        log::info!("Image added: {}", url);
-        // Keep uploader open for more images
+    // Upload queued images one at a time
+    let mut process_queue = move || {
+        if *uploading.peek() {
+            return;
+        }
+        uploading.set(true);
+
+        spawn(async move {
+            loop {
+                let next = images
+                    .peek()
+                    .iter()
+                    .find(|image| image.status == ImageUploadStatus::Queued)
+                    .map(|image| image.id.clone());
+                let Some(id) = next else {
+                    break;
+                };
+
+                let file = pending_files.peek().get(&id).cloned();
+                let status = match file {
+                    Some((data, mime_type)) => {
+                        set_status(images, &id, ImageUploadStatus::Uploading);
+                        match blossom_store::upload_media(data, mime_type, UPLOAD_QUALITY).await {
+                            Ok(media) => {
+                                log::info!("Image uploaded: {}", media.url);
+                                pending_files.write().remove(&id);
+                                ImageUploadStatus::Uploaded(media)
+                            }
+                            Err(e) => {
+                                log::error!("Image upload failed: {}", e);
+                                ImageUploadStatus::Failed(e)
+                            }
+                        }
+                    }
+                    None => ImageUploadStatus::Failed("File is no longer available".to_string()),
+                };
+                set_status(images, &id, status);
+            }
+            uploading.set(false);
+        });
     };
 
-    // Handle remove image
-    let mut handle_remove_image = move |index: usize| {
-        let mut urls = image_urls.write();
-        if index < urls.len() {
-            urls.remove(index);
-        }
+    // Read every picked file and queue it
+    let handle_files_selected = move |_| {
+        spawn(async move {
+            match read_selected_files(FILE_INPUT_ID).await {
+                Ok(files) => {
+                    for (file_name, data, mime_type) in files {
+                        let id = uuid::Uuid::new_v4().to_string();
+                        pending_files.write().insert(id.clone(), (data, mime_type));
+                        images.write().push(ComposerImage {
+                            id,
+                            file_name,
+                            status: ImageUploadStatus::Queued,
+                            alt: String::new(),
+                        });
+                    }
+                    confirm_partial.set(false);
+                    process_queue();
+                }
+                Err(e) => error_message.set(Some(format!("Failed to read files: {}", e))),
+            }
+            clear_file_input(FILE_INPUT_ID);
+        });
+    };
+
+    let mut handle_remove_image = move |id: String| {
+        images.write().retain(|image| image.id != id);
+        pending_files.write().remove(&id);
+    };
+
+    let mut handle_retry = move |id: String| {
+        set_status(images, &id, ImageUploadStatus::Queued);
+        confirm_partial.set(false);
+        process_queue();
     };
 
     // Handle publishing
@@ -49,13 +146,20 @@ pub fn PhotoNew() -> Element {
             return;
         }
 
+        // Failed uploads are left out, but only once the user agrees
+        if failed_count > 0 && !*confirm_partial.read() {
+            confirm_partial.set(true);
+            return;
+        }
+
         let title_val = title.read().clone();
         let caption_val = caption.read().clone();
-        let image_urls_val = image_urls.read().clone();
+        let imeta_tags = photo_draft::picture_imeta_tags(&images.read());
         let hashtags_val = hashtags.read().clone();
         let location_val = location.read().clone();
 
         is_publishing.set(true);
+        confirm_partial.set(false);
         error_message.set(None);
 
         spawn(async move {
@@ -69,13 +173,13 @@ pub fn PhotoNew() -> Element {
             match crate::stores::nostr_client::publish_picture(
                 title_val,
                 caption_val,
-                image_urls_val,
+                imeta_tags,
                 tags_vec,
                 location_val,
             ).await {
                 Ok(event_id) => {
                     log::info!("Picture post published successfully: {}", event_id);
-                    is_publishing.set(false);
+                    photo_draft::clear_draft();
                     navigator.push(crate::routes::Route::Photos {});
                 }
                 Err(e) => {
@@ -87,6 +191,17 @@ pub fn PhotoNew() -> Element {
         });
     };
 
+    let handle_discard_draft = move |_| {
+        photo_draft::clear_draft();
+        title.set(String::new());
+        caption.set(String::new());
+        hashtags.set(String::new());
+        location.set(String::new());
+        images.set(Vec::new());
+        pending_files.write().clear();
+        show_restored_notice.set(false);
+    };
+
     // Redirect if not authenticated
     use_effect(move || {
         if !*is_authenticated.read() {
@@ -102,6 +217,8 @@ pub fn PhotoNew() -> Element {
         };
     }
 
+    let image_count = images.read().len();
+
     rsx! {
         div {
             class: "min-h-screen bg-background",
@@ -136,6 +253,8 @@ pub fn PhotoNew() -> Element {
 
                         if *is_publishing.read() {
                             "Publishing..."
+                        } else if pending_count > 0 {
+                            "Uploading..."
                         } else {
                             "Post"
                         }
@@ -147,6 +266,27 @@ pub fn PhotoNew() -> Element {
             div {
                 class: "max-w-4xl mx-auto px-4 py-8",
 
+                // Restored draft
+                if *show_restored_notice.read() {
+                    div {
+                        class: "mb-4 p-3 bg-blue-50 dark:bg-blue-900/20 border border-blue-200 dark:border-blue-800 rounded-lg flex items-center justify-between gap-3 text-sm",
+                        span { "Restored your unsent post." }
+                        div {
+                            class: "flex gap-2",
+                            button {
+                                class: "px-3 py-1 rounded hover:bg-accent transition",
+                                onclick: handle_discard_draft,
+                                "Discard"
+                            }
+                            button {
+                                class: "px-3 py-1 rounded hover:bg-accent transition",
+                                onclick: move |_| show_restored_notice.set(false),
+                                "Keep"
+                            }
+                        }
+                    }
+                }
+
                 // Error message
                 if let Some(err) = error_message.read().as_ref() {
                     div {
@@ -155,62 +295,168 @@ pub fn PhotoNew() -> Element {
                     }
                 }
 
+                // Confirm publishing without the failed images
+                if *confirm_partial.read() {
+                    div {
+                        class: "mb-4 p-4 bg-yellow-50 dark:bg-yellow-900/20 border border-yellow-300 dark:border-yellow-800 rounded-lg space-y-3",
+                        p {
+                            class: "text-sm",
+                            "{failed_count} image(s) failed to upload. Publish the other {uploaded_count} without them?"
+                        }
+                        div {
+                            class: "flex gap-2",
+                            button {
+                                class: "px-4 py-2 bg-blue-500 hover:bg-blue-600 text-white rounded-lg transition text-sm",
+                                onclick: handle_publish,
+                                "Publish anyway"
+                            }
+                            button {
+                                class: "px-4 py-2 bg-accent hover:bg-accent/80 rounded-lg transition text-sm",
+                                onclick: move |_| confirm_partial.set(false),
+                                "Cancel"
+                            }
+                        }
+                    }
+                }
+
                 div {
                     class: "space-y-6",
 
-                    // Image uploader
+                    // Images
                     div {
                         label {
                             class: "block text-sm font-medium mb-2",
                             "Images * (upload one or more)"
                         }
 
-                        // Display uploaded images
-                        if image_urls.read().len() > 0 {
+                        if image_count > 0 {
                             div {
                                 class: "grid grid-cols-2 md:grid-cols-3 gap-4 mb-4",
-                                for (index , url) in image_urls.read().iter().enumerate() {
+                                for (index, image) in images.read().iter().cloned().enumerate() {
                                     div {
-                                        key: "{url}",
-                                        class: "relative aspect-square group",
-                                        img {
-                                            src: "{url}",
-                                            class: "w-full h-full object-cover rounded-lg border border-border",
-                                        }
-                                        button {
-                                            class: "absolute top-2 right-2 bg-red-500 hover:bg-red-600 text-white rounded-full p-2 opacity-0 group-hover:opacity-100 transition",
-                                            onclick: move |_| handle_remove_image(index),
-                                            svg {
-                                                xmlns: "http://www.w3.org/2000/svg",
-                                                class: "w-4 h-4",
-                                                fill: "none",
-                                                view_box: "0 0 24 24",
-                                                stroke: "currentColor",
-                                                stroke_width: "2",
-                                                path {
-                                                    stroke_linecap: "round",
-                                                    stroke_linejoin: "round",
-                                                    d: "M6 18L18 6M6 6l12 12"
+                                        key: "{image.id}",
+                                        class: "space-y-2",
+                                        div {
+                                            class: "relative aspect-square rounded-lg border border-border overflow-hidden bg-accent/30 group",
+                                            match &image.status {
+                                                ImageUploadStatus::Uploaded(media) => rsx! {
+                                                    img {
+                                                        src: "{media.url}",
+                                                        alt: "{image.alt}",
+                                                        class: "w-full h-full object-cover",
+                                                    }
+                                                },
+                                                ImageUploadStatus::Uploading => rsx! {
+                                                    div {
+                                                        class: "w-full h-full flex flex-col items-center justify-center gap-2 p-3 text-xs text-muted-foreground",
+                                                        span { class: "truncate max-w-full", "{image.file_name}" }
+                                                        div {
+                                                            class: "w-full h-1.5 bg-border rounded-full overflow-hidden",
+                                                            div {
+                                                                class: "h-full bg-blue-500 transition-all",
+                                                                style: "width: {upload_progress.unwrap_or(0.0)}%",
+                                                            }
+                                                        }
+                                                        span { "Uploading {upload_progress.unwrap_or(0.0):.0}%" }
+                                                    }
+                                                },
+                                                ImageUploadStatus::Queued => rsx! {
+                                                    div {
+                                                        class: "w-full h-full flex flex-col items-center justify-center gap-1 p-3 text-xs text-muted-foreground",
+                                                        span { class: "truncate max-w-full", "{image.file_name}" }
+                                                        span { "Waiting to upload" }
+                                                    }
+                                                },
+                                                ImageUploadStatus::Failed(reason) => {
+                                                    let id = image.id.clone();
+                                                    rsx! {
+                                                        div {
+                                                            class: "w-full h-full flex flex-col items-center justify-center gap-2 p-3 text-xs text-red-600 dark:text-red-400 text-center",
+                                                            span { class: "truncate max-w-full", "{image.file_name}" }
+                                                            span { class: "line-clamp-2", "Upload failed: {reason}" }
+                                                            button {
+                                                                class: "px-3 py-1 rounded bg-background border border-border text-foreground hover:bg-accent transition",
+                                                                onclick: move |_| handle_retry(id.clone()),
+                                                                "Retry"
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+
+                                            // Reorder and remove
+                                            div {
+                                                class: "absolute top-2 inset-x-2 flex justify-between opacity-0 group-hover:opacity-100 transition",
+                                                div {
+                                                    class: "flex gap-1",
+                                                    button {
+                                                        class: "bg-black/60 hover:bg-black/80 text-white rounded-full w-7 h-7 disabled:opacity-30",
+                                                        title: "Move earlier",
+                                                        disabled: index == 0,
+                                                        onclick: move |_| photo_draft::move_image(&mut images.write(), index, index.saturating_sub(1)),
+                                                        "←"
+                                                    }
+                                                    button {
+                                                        class: "bg-black/60 hover:bg-black/80 text-white rounded-full w-7 h-7 disabled:opacity-30",
+                                                        title: "Move later",
+                                                        disabled: index + 1 >= image_count,
+                                                        onclick: move |_| photo_draft::move_image(&mut images.write(), index, index + 1),
+                                                        "→"
+                                                    }
+                                                }
+                                                button {
+                                                    class: "bg-red-500 hover:bg-red-600 text-white rounded-full w-7 h-7",
+                                                    title: "Remove",
+                                                    onclick: {
+                                                        let id = image.id.clone();
+                                                        move |_| handle_remove_image(id.clone())
+                                                    },
+                                                    "✕"
                                                 }
                                             }
                                         }
+
+                                        // Alt text
+                                        input {
+                                            r#type: "text",
+                                            class: "w-full px-2 py-1 text-sm bg-background border border-border rounded focus:outline-none focus:ring-2 focus:ring-blue-500",
+                                            placeholder: "Alt text",
+                                            value: "{image.alt}",
+                                            oninput: {
+                                                let id = image.id.clone();
+                                                move |e: FormEvent| {
+                                                    if let Some(image) = images.write().iter_mut().find(|image| image.id == id) {
+                                                        image.alt = e.value();
+                                                    }
+                                                }
+                                            },
+                                        }
                                     }
                                 }
                             }
                         }
 
-                        // Image uploader
-                        if *show_image_uploader.read() {
-                            MediaUploader {
-                                on_upload: handle_image_uploaded,
+                        // Picker
+                        label {
+                            class: "flex flex-col items-center justify-center w-full h-32 border-2 border-gray-300 border-dashed rounded-lg cursor-pointer bg-gray-50 dark:bg-gray-700 hover:bg-gray-100 dark:hover:bg-gray-600 dark:border-gray-600",
+                            span {
+                                class: "text-4xl mb-2",
+                                "📷"
                             }
-                        }
-
-                        if !*show_image_uploader.read() {
-                            button {
-                                class: "px-4 py-2 bg-accent hover:bg-accent/80 text-foreground rounded-lg transition",
-                                onclick: move |_| show_image_uploader.set(true),
-                                "+ Add more images"
+                            p {
+                                class: "text-sm text-gray-500 dark:text-gray-400",
+                                span {
+                                    class: "font-semibold",
+                                    if image_count > 0 { "Add more images" } else { "Choose images" }
+                                }
+                            }
+                            input {
+                                id: FILE_INPUT_ID,
+                                r#type: "file",
+                                class: "hidden",
+                                accept: "image/*",
+                                multiple: true,
+                                onchange: handle_files_selected,
                             }
                         }
                     }
@@ -279,3 +525,52 @@ pub fn PhotoNew() -> Element {
         }
     }
 }
+
+fn set_status(mut images: Signal<Vec<ComposerImage>>, id: &str, status: ImageUploadStatus) {
+    if let Some(image) = images.write().iter_mut().find(|image| image.id == id) {
+        image.status = status;
+    }
+}
+
+/// Name, bytes and MIME type of every file picked in the input
+async fn read_selected_files(input_id: &str) -> Result<Vec<(String, Vec<u8>, String)>, String> {
+    use js_sys::{ArrayBuffer, Uint8Array};
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::HtmlInputElement;
+
+    let input = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(input_id))
+        .ok_or("Input not found")?
+        .dyn_into::<HtmlInputElement>()
+        .map_err(|_| "Not an input element")?;
+    let Some(file_list) = input.files() else {
+        return Ok(Vec::new());
+    };
+
+    let mut files = Vec::new();
+    for index in 0..file_list.length() {
+        let Some(file) = file_list.get(index) else {
+            continue;
+        };
+        let buffer = JsFuture::from(file.array_buffer())
+            .await
+            .map_err(|_| format!("Failed to read {}", file.name()))?;
+        let buffer: ArrayBuffer = buffer.dyn_into().map_err(|_| "Not an ArrayBuffer")?;
+        files.push((file.name(), Uint8Array::new(&buffer).to_vec(), file.type_()));
+    }
+    Ok(files)
+}
+
+fn clear_file_input(input_id: &str) {
+    use wasm_bindgen::JsCast;
+
+    if let Some(input) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(input_id))
+        .and_then(|element| element.dyn_into::<web_sys::HtmlInputElement>().ok())
+    {
+        input.set_value("");
+    }
+}
@@ -1,6 +1,7 @@
 use dioxus::prelude::*;
+use dioxus_core::{spawn_forever, use_drop};
 use nostr_sdk::{Event as NostrEvent, EventId, Filter, Kind};
-use crate::stores::nostr_client;
+use crate::stores::{nostr_client, polls};
 use crate::components::{PollCard, ClientInitializing};
 use std::time::Duration;
 
@@ -128,7 +129,8 @@ pub fn PollView(noteid: String) -> Element {
                     // Poll display
                     div {
                         class: "border-b border-border",
-                        PollCard {
+                        LivePollCard {
+                            key: "{event.id}",
                             event: event.clone()
                         }
                     }
@@ -162,6 +164,26 @@ pub fn PollView(noteid: String) -> Element {
     }
 }
 
+/// PollCard whose results update as new votes arrive
+#[component]
+fn LivePollCard(event: NostrEvent) -> Element {
+    let poll_id = event.id;
+    let live_results = use_hook(move || polls::subscribe_poll_results(poll_id));
+
+    use_drop(move || {
+        spawn_forever(async move {
+            polls::unsubscribe_poll_results(poll_id).await;
+        });
+    });
+
+    rsx! {
+        PollCard {
+            event: event,
+            live_results: Some(live_results)
+        }
+    }
+}
+
 /// Decode event ID from bech32 (note1...) or hex format
 fn decode_event_id(noteid: &str) -> Result<EventId, String> {
     // Try bech32 first (note1...)
@@ -1,6 +1,6 @@
 use dioxus::prelude::*;
-use crate::stores::{nostr_client, auth_store, dms};
-use crate::components::{NoteCard, ClientInitializing, ProfileEditorModal, PhotoCard, VideoCard, ArticleCard};
+use crate::stores::{nostr_client, auth_store, contacts, dms};
+use crate::components::{NoteCard, ClientInitializing, ProfileEditorModal, PhotoCard, VideoCard, ArticleCard, ProfileGoals, ProfileBadges, UserStatusLine, UserStatusEditor};
 use crate::components::icons::{InfoIcon, MailIcon};
 use crate::components::dialog::{DialogRoot, DialogTitle, DialogDescription};
 use crate::hooks::use_infinite_scroll;
@@ -86,8 +86,8 @@ pub fn Profile(pubkey: String) -> Element {
     let mut loading_events = use_signal(|| false);
     let mut current_tab_has_more = use_signal(|| true);
 
-    // Follow state
-    let mut is_following = use_signal(|| false);
+    // Follow state, from the logged-in account's contact list
+    let is_following = contacts::is_following(&pubkey);
     let mut follow_loading = use_signal(|| false);
     let mut follows_you = use_signal(|| false);
 
@@ -136,7 +136,6 @@ pub fn Profile(pubkey: String) -> Element {
         tab_data.set(default_tab_data_map());
         loading_events.set(false);
         current_tab_has_more.set(true);
-        is_following.set(false);
         follows_you.set(false);
         following_count.set(0);
         followers_count.set(0);
@@ -367,34 +366,14 @@ pub fn Profile(pubkey: String) -> Element {
         });
     }));
 
-    // Check if following this user
-    use_effect(use_reactive(&pubkey, move |pubkey_str| {
+    // Load our contact list for the follow button
+    use_effect(move || {
         let client_initialized = *nostr_client::CLIENT_INITIALIZED.read();
 
-        if !client_initialized || !auth_store::is_authenticated() {
-            return;
+        if client_initialized && auth_store::is_authenticated() {
+            contacts::ensure_contact_list();
         }
-
-        spawn(async move {
-            // Convert pubkey to hex format for comparison
-            let hex_pubkey = if let Ok(pk) = PublicKey::from_bech32(&pubkey_str) {
-                pk.to_hex()
-            } else if let Ok(pk) = PublicKey::from_hex(&pubkey_str) {
-                pk.to_hex()
-            } else {
-                return;
-            };
-
-            match nostr_client::is_following(hex_pubkey).await {
-                Ok(following) => {
-                    is_following.set(following);
-                }
-                Err(e) => {
-                    log::error!("Failed to check following status: {}", e);
-                }
-            }
-        });
-    }));
+    });
 
     // OPTIMIZATION: Combined "follows you" check + stats fetch
     // This eliminates a duplicate fetch_contacts() call and runs both in parallel
@@ -670,7 +649,7 @@ pub fn Profile(pubkey: String) -> Element {
                         }
                     } else if auth.is_authenticated {
                         button {
-                                class: if *is_following.read() {
+                                class: if is_following {
                                     "px-6 py-2 border border-border rounded-full font-semibold hover:bg-accent transition"
                                 } else {
                                     "px-6 py-2 bg-foreground text-background rounded-full font-semibold hover:opacity-90 transition"
@@ -681,30 +660,14 @@ pub fn Profile(pubkey: String) -> Element {
                                     follow_loading.set(true);
 
                                     spawn(async move {
-                                        // Convert to hex
-                                        let hex_pubkey = if let Ok(pk) = PublicKey::from_bech32(&pubkey_clone) {
-                                            pk.to_hex()
-                                        } else if let Ok(pk) = PublicKey::from_hex(&pubkey_clone) {
-                                            pk.to_hex()
-                                        } else {
-                                            follow_loading.set(false);
-                                            return;
-                                        };
-
-                                        let result = if *is_following.read() {
-                                            nostr_client::unfollow_user(hex_pubkey).await
+                                        let result = if is_following {
+                                            contacts::unfollow(&pubkey_clone).await
                                         } else {
-                                            nostr_client::follow_user(hex_pubkey).await
+                                            contacts::follow(&pubkey_clone, None, None).await
                                         };
 
-                                        match result {
-                                            Ok(_) => {
-                                                let current = *is_following.read();
-                                                is_following.set(!current);
-                                            }
-                                            Err(e) => {
-                                                log::error!("Failed to follow/unfollow: {}", e);
-                                            }
+                                        if let Err(e) = result {
+                                            log::error!("Failed to follow/unfollow: {}", e);
                                         }
 
                                         follow_loading.set(false);
@@ -712,7 +675,7 @@ pub fn Profile(pubkey: String) -> Element {
                                 },
                                 if *follow_loading.read() {
                                     "..."
-                                } else if *is_following.read() {
+                                } else if is_following {
                                     "Following"
                                 } else {
                                     "Follow"
@@ -740,6 +703,18 @@ pub fn Profile(pubkey: String) -> Element {
                         "@{get_username(metadata, &pubkey_for_display)}"
                     }
 
+                    // NIP-38 statuses
+                    div {
+                        class: "mt-1",
+                        UserStatusLine {
+                            key: "{pubkey_for_display}",
+                            pubkey: pubkey_for_display.clone()
+                        }
+                    }
+                    if is_own_profile {
+                        UserStatusEditor {}
+                    }
+
                     // Bio
                     if let Some(about) = &metadata.about {
                         if !about.is_empty() {
@@ -750,6 +725,12 @@ pub fn Profile(pubkey: String) -> Element {
                         }
                     }
 
+                    // NIP-58 badges
+                    ProfileBadges {
+                        key: "{pubkey_for_display}",
+                        pubkey: pubkey_for_display.clone()
+                    }
+
                     // Website and joined date
                     div {
                         class: "flex flex-wrap gap-4 mt-3 text-sm text-muted-foreground",
@@ -863,6 +844,12 @@ pub fn Profile(pubkey: String) -> Element {
                         }
                     }
                 }
+
+                // Open NIP-75 zap goals
+                ProfileGoals {
+                    key: "{pubkey_for_display}",
+                    pubkey: pubkey_for_display.clone()
+                }
             }
 
             // Content tabs
@@ -5,6 +5,7 @@ use crate::services::content_search::{
     search_text_notes, search_articles, search_photos, search_videos, get_contact_pubkeys,
     ContentSearchResult,
 };
+use crate::utils::search_query::parse_search_query;
 use crate::components::{NoteCard, NoteCardSkeleton, PhotoCard, VideoCard};
 
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -28,6 +29,8 @@ impl SearchTab {
 
 #[derive(Clone, Copy, PartialEq, Debug)]
 enum SortOrder {
+    /// As ranked by `rank_search_results`
+    Relevance,
     Newest,
     Oldest,
     FollowingFirst,
@@ -36,6 +39,7 @@ enum SortOrder {
 impl SortOrder {
     fn label(&self) -> &'static str {
         match self {
+            SortOrder::Relevance => "Best match",
             SortOrder::Newest => "Newest",
             SortOrder::Oldest => "Oldest",
             SortOrder::FollowingFirst => "Following first",
@@ -49,10 +53,11 @@ pub fn Search(q: String) -> Element {
     let mut results = use_signal(|| Vec::<ContentSearchResult>::new());
     let mut loading = use_signal(|| false);
     let mut error = use_signal(|| None::<String>);
+    let mut query_hints = use_signal(|| Vec::<String>::new());
     let mut contact_pubkeys = use_signal(|| Vec::<PublicKey>::new());
     let mut query = use_signal(|| q.clone());
     let mut search_version = use_signal(|| 0u64);
-    let mut sort_order = use_signal(|| SortOrder::FollowingFirst);
+    let mut sort_order = use_signal(|| SortOrder::Relevance);
     let mut show_sort_dropdown = use_signal(|| false);
 
     // Update query signal when prop changes (e.g., new search from search bar)
@@ -73,8 +78,10 @@ pub fn Search(q: String) -> Element {
         let q = query.read().clone();
         let tab = *active_tab.read();
         let contacts = contact_pubkeys.read().clone();
+        let parsed = parse_search_query(&q);
+        query_hints.set(parsed.hints.clone());
 
-        if q.is_empty() {
+        if parsed.is_empty() {
             // Increment version to invalidate any in-flight searches (without subscribing)
             search_version.with_mut(|v| {
                 *v += 1;
@@ -95,10 +102,10 @@ pub fn Search(q: String) -> Element {
 
         spawn(async move {
             let search_result = match tab {
-                SearchTab::TextNotes => search_text_notes(&q, 50, &contacts).await,
-                SearchTab::Articles => search_articles(&q, 50, &contacts).await,
-                SearchTab::Photos => search_photos(&q, 50, &contacts).await,
-                SearchTab::Videos => search_videos(&q, 50, &contacts).await,
+                SearchTab::TextNotes => search_text_notes(&parsed, 50, &contacts).await,
+                SearchTab::Articles => search_articles(&parsed, 50, &contacts).await,
+                SearchTab::Photos => search_photos(&parsed, 50, &contacts).await,
+                SearchTab::Videos => search_videos(&parsed, 50, &contacts).await,
             };
 
             // Only update state if this is still the most recent search
@@ -130,6 +137,7 @@ pub fn Search(q: String) -> Element {
         let order = *sort_order.read();
 
         match order {
+            SortOrder::Relevance => {}
             SortOrder::Newest => {
                 sorted.sort_by(|a, b| b.event.created_at.cmp(&a.event.created_at));
             }
@@ -169,6 +177,14 @@ pub fn Search(q: String) -> Element {
                         class: "text-sm text-muted-foreground mt-1",
                         "Searching for: \"{query.read()}\""
                     }
+                    // Operators that couldn't be used (non-fatal)
+                    for hint in query_hints.read().iter() {
+                        p {
+                            key: "{hint}",
+                            class: "text-xs text-amber-600 dark:text-amber-400 mt-1",
+                            "{hint}"
+                        }
+                    }
                 }
 
                 // Tabs
@@ -297,7 +313,7 @@ pub fn Search(q: String) -> Element {
                                 }
                                 div {
                                     class: "absolute right-0 top-full mt-1 w-40 bg-background border border-border rounded-lg shadow-lg z-50 overflow-hidden",
-                                    for option in [SortOrder::Newest, SortOrder::Oldest, SortOrder::FollowingFirst] {
+                                    for option in [SortOrder::Relevance, SortOrder::Newest, SortOrder::Oldest, SortOrder::FollowingFirst] {
                                         {
                                             let is_selected = *sort_order.read() == option;
                                             rsx! {
@@ -418,7 +434,7 @@ pub fn Search(q: String) -> Element {
                     }
                     p {
                         class: "text-sm text-muted-foreground text-center max-w-md",
-                        "Use the search bar above to find posts, articles, photos, and videos on Nostr"
+                        "Use the search bar above to find posts, articles, photos, and videos on Nostr. Narrow results with from:npub…, since:YYYY-MM-DD, until:YYYY-MM-DD or kind:N"
                     }
                 }
             }
@@ -1,16 +1,31 @@
 use dioxus::prelude::*;
-use crate::stores::{auth_store, theme_store, nostr_client, settings_store, blossom_store, relay_metadata, nwc_store, reactions_store};
-use crate::stores::nostr_client::RelayPoolStoreStoreExt;
+use crate::stores::{auth_store, theme_store, nostr_client, settings_store, blossom_store, relay_metadata, relay_auth, nwc_store, reactions_store, music_player, dms, dvm_store};
 use crate::stores::blossom_store::BlossomServersStoreStoreExt;
 use crate::components::{NwcSetupModal, ReactionDefaultsModal};
 use crate::routes::Route;
-use nostr_sdk::ToBech32;
+use crate::services::{btc_price, trending};
+use nostr_sdk::{Timestamp, ToBech32};
+use nostr::nips::nip47::TransactionType;
+use crate::utils::time::format_relative_time_ex;
 use gloo_storage::Storage;
 
+/// How often the relay connection list is refreshed
+const RELAY_HEALTH_REFRESH_MS: u32 = 5_000;
+
 #[component]
 pub fn Settings() -> Element {
     let theme = theme_store::THEME.read();
-    let relays = nostr_client::RELAY_POOL.read();
+    // Live relay health, polled while the page is open
+    let mut relay_health = use_signal(Vec::<nostr_client::RelayHealth>::new);
+    use_hook(move || {
+        spawn(async move {
+            loop {
+                relay_health.set(nostr_client::get_relay_health().await);
+                gloo_timers::future::TimeoutFuture::new(RELAY_HEALTH_REFRESH_MS).await;
+            }
+        });
+    });
+    let connected_relays = relay_health.read().iter().filter(|relay| relay.is_connected()).count();
     let blossom_servers = blossom_store::BLOSSOM_SERVERS.read();
 
     // Relay management state - initialize from USER_RELAY_METADATA using peek()
@@ -42,6 +57,16 @@ pub fn Settings() -> Element {
     let mut new_relay_url = use_signal(|| String::new());
     let mut new_dm_relay_url = use_signal(|| String::new());
     let mut relay_error = use_signal(|| None::<String>);
+    let fetch_timeout = *nostr_client::DEFAULT_FETCH_TIMEOUT.read();
+    let crossfade_secs = music_player::get_crossfade_secs();
+    let translation_language = settings_store::SETTINGS.read().translation_language.clone();
+    let trending_config = settings_store::SETTINGS.read().trending.clone();
+    let trending_window = trending_config.window_hours();
+    let trending_weights = [
+        ("Reactions", trending_config.reaction_weight),
+        ("Reposts", trending_config.repost_weight),
+        ("Zaps", trending_config.zap_weight),
+    ];
     let mut dm_relay_error = use_signal(|| None::<String>);
     let mut save_status = use_signal(|| None::<String>);
 
@@ -52,6 +77,8 @@ pub fn Settings() -> Element {
     let mut show_nwc_modal = use_signal(|| false);
     let nwc_status = nwc_store::NWC_STATUS.read().clone();
     let nwc_balance = nwc_store::NWC_BALANCE.read().clone();
+    let nwc_budget = nwc_store::NWC_REMAINING_BUDGET.read().clone();
+    let nwc_transactions = nwc_store::NWC_TRANSACTIONS.read().clone();
 
     // Reactions modal state
     let mut show_reactions_modal = use_signal(|| false);
@@ -213,7 +240,7 @@ pub fn Settings() -> Element {
             }
 
             // Publish kind 10050 (DM relays)
-            match relay_metadata::publish_dm_relay_list(dm.clone(), client.clone()).await {
+            match dms::publish_dm_relays(dm.clone()).await {
                 Ok(_) => {
                     log::info!("DM relay list published");
                 }
@@ -546,6 +573,234 @@ pub fn Settings() -> Element {
                 }
             }
 
+            // Relay Timeout section
+            div {
+                class: "bg-white dark:bg-gray-800 rounded-lg shadow-lg p-6",
+                h3 {
+                    class: "text-xl font-semibold text-gray-900 dark:text-white mb-4",
+                    "⏱️ Relay Timeout"
+                }
+                p {
+                    class: "text-sm text-gray-600 dark:text-gray-400 mb-4",
+                    "How long to wait for relays when loading data such as your wallet. Increase this on slow or mobile connections."
+                }
+                div {
+                    class: "space-y-2",
+                    label {
+                        class: "block text-sm font-medium text-gray-700 dark:text-gray-300",
+                        "Timeout: {fetch_timeout} seconds"
+                    }
+                    input {
+                        class: "w-full h-2 bg-gray-200 rounded-lg appearance-none cursor-pointer dark:bg-gray-600",
+                        r#type: "range",
+                        min: "{nostr_client::MIN_FETCH_TIMEOUT_SECS}",
+                        max: "{nostr_client::MAX_FETCH_TIMEOUT_SECS}",
+                        step: "1",
+                        value: "{fetch_timeout}",
+                        // Preview while dragging, save once released
+                        oninput: move |evt| {
+                            if let Ok(secs) = evt.value().parse::<u64>() {
+                                nostr_client::set_default_fetch_timeout(secs);
+                            }
+                        },
+                        onchange: move |evt| {
+                            if let Ok(secs) = evt.value().parse::<u64>() {
+                                spawn(async move {
+                                    settings_store::update_fetch_timeout(secs).await;
+                                });
+                            }
+                        }
+                    }
+                    div {
+                        class: "flex justify-between text-xs text-gray-500 dark:text-gray-400",
+                        span { "{nostr_client::MIN_FETCH_TIMEOUT_SECS}s" }
+                        span { "{nostr_client::MAX_FETCH_TIMEOUT_SECS}s" }
+                    }
+                }
+            }
+
+            // Trending section
+            div {
+                class: "bg-white dark:bg-gray-800 rounded-lg shadow-lg p-6",
+                h3 {
+                    class: "text-xl font-semibold text-gray-900 dark:text-white mb-4",
+                    "📈 Trending"
+                }
+                p {
+                    class: "text-sm text-gray-600 dark:text-gray-400 mb-4",
+                    "How far back trending looks, and how much each kind of interaction counts when ranking notes."
+                }
+                div {
+                    class: "space-y-4",
+                    div {
+                        class: "space-y-2",
+                        label {
+                            class: "block text-sm font-medium text-gray-700 dark:text-gray-300",
+                            "Window: {trending_window} hours"
+                        }
+                        input {
+                            class: "w-full h-2 bg-gray-200 rounded-lg appearance-none cursor-pointer dark:bg-gray-600",
+                            r#type: "range",
+                            min: "{trending::MIN_WINDOW_HOURS}",
+                            max: "{trending::MAX_WINDOW_HOURS}",
+                            step: "1",
+                            value: "{trending_window}",
+                            onchange: move |evt| {
+                                if let Ok(hours) = evt.value().parse::<u32>() {
+                                    let mut config = settings_store::SETTINGS.read().trending.clone();
+                                    config.window_hours = hours;
+                                    spawn(async move {
+                                        settings_store::update_trending_config(config).await;
+                                    });
+                                }
+                            }
+                        }
+                        div {
+                            class: "flex justify-between text-xs text-gray-500 dark:text-gray-400",
+                            span { "{trending::MIN_WINDOW_HOURS}h" }
+                            span { "{trending::MAX_WINDOW_HOURS}h" }
+                        }
+                    }
+                    div {
+                        class: "grid grid-cols-3 gap-3",
+                        for (index, (name, weight)) in trending_weights.into_iter().enumerate() {
+                            label {
+                                key: "{name}",
+                                class: "block text-sm font-medium text-gray-700 dark:text-gray-300",
+                                "{name} weight"
+                                input {
+                                    class: "mt-1 w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg bg-white dark:bg-gray-700 text-gray-900 dark:text-white",
+                                    r#type: "number",
+                                    min: "0",
+                                    step: "0.5",
+                                    value: "{weight}",
+                                    onchange: move |evt| {
+                                        let Ok(weight) = evt.value().parse::<f64>() else { return };
+                                        if !weight.is_finite() || weight < 0.0 {
+                                            return;
+                                        }
+                                        let mut config = settings_store::SETTINGS.read().trending.clone();
+                                        match index {
+                                            0 => config.reaction_weight = weight,
+                                            1 => config.repost_weight = weight,
+                                            _ => config.zap_weight = weight,
+                                        }
+                                        spawn(async move {
+                                            settings_store::update_trending_config(config).await;
+                                        });
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // Music section
+            div {
+                class: "bg-white dark:bg-gray-800 rounded-lg shadow-lg p-6",
+                h3 {
+                    class: "text-xl font-semibold text-gray-900 dark:text-white mb-4",
+                    "🎵 Music"
+                }
+                p {
+                    class: "text-sm text-gray-600 dark:text-gray-400 mb-4",
+                    "Fade the next track in while the current one ends. Tracks shorter than the crossfade are played back to back."
+                }
+                div {
+                    class: "space-y-2",
+                    label {
+                        class: "block text-sm font-medium text-gray-700 dark:text-gray-300",
+                        if crossfade_secs == 0 {
+                            "Crossfade: off"
+                        } else {
+                            "Crossfade: {crossfade_secs} seconds"
+                        }
+                    }
+                    input {
+                        class: "w-full h-2 bg-gray-200 rounded-lg appearance-none cursor-pointer dark:bg-gray-600",
+                        r#type: "range",
+                        min: "0",
+                        max: "{music_player::MAX_CROSSFADE_SECS}",
+                        step: "1",
+                        value: "{crossfade_secs}",
+                        oninput: move |evt| {
+                            if let Ok(secs) = evt.value().parse::<u8>() {
+                                music_player::set_crossfade_secs(secs);
+                            }
+                        }
+                    }
+                    div {
+                        class: "flex justify-between text-xs text-gray-500 dark:text-gray-400",
+                        span { "Off" }
+                        span { "{music_player::MAX_CROSSFADE_SECS}s" }
+                    }
+                }
+
+                // NIP-38 "now playing" status
+                div {
+                    class: "flex items-center justify-between gap-4 pt-4 mt-4 border-t border-gray-200 dark:border-gray-700",
+                    div {
+                        div {
+                            class: "text-sm font-medium text-gray-900 dark:text-white",
+                            "Share what I'm listening to"
+                        }
+                        p {
+                            class: "text-xs text-gray-600 dark:text-gray-400",
+                            "Publishes the playing track as your music status, shown under your name in other clients."
+                        }
+                    }
+                    label {
+                        class: "relative inline-flex items-center cursor-pointer flex-shrink-0",
+                        input {
+                            r#type: "checkbox",
+                            class: "sr-only peer",
+                            checked: settings_store::SETTINGS.read().share_music_status,
+                            onchange: move |evt| {
+                                let enabled = evt.checked();
+                                spawn(async move {
+                                    settings_store::update_share_music_status(enabled).await;
+                                });
+                            }
+                        }
+                        div {
+                            class: "w-11 h-6 bg-gray-300 dark:bg-gray-700 peer-focus:outline-none peer-focus:ring-4 peer-focus:ring-blue-300 dark:peer-focus:ring-blue-800 rounded-full peer peer-checked:after:translate-x-full peer-checked:after:border-white after:content-[''] after:absolute after:top-[2px] after:left-[2px] after:bg-white after:border-gray-300 after:border after:rounded-full after:h-5 after:w-5 after:transition-all dark:border-gray-600 peer-checked:bg-blue-600"
+                        }
+                    }
+                }
+            }
+
+            // Translation section
+            div {
+                class: "bg-white dark:bg-gray-800 rounded-lg shadow-lg p-6",
+                h3 {
+                    class: "text-xl font-semibold text-gray-900 dark:text-white mb-4",
+                    "🌐 Translation"
+                }
+                p {
+                    class: "text-sm text-gray-600 dark:text-gray-400 mb-4",
+                    "Notes are translated by a NIP-90 translation service into this language."
+                }
+                select {
+                    class: "px-3 py-2 text-sm bg-white dark:bg-gray-700 border border-gray-300 dark:border-gray-600 rounded-lg text-gray-900 dark:text-white",
+                    value: "{translation_language}",
+                    onchange: move |e| {
+                        let language = e.value();
+                        spawn(async move {
+                            settings_store::update_translation_language(language).await;
+                        });
+                    },
+                    for (code, name) in dvm_store::TRANSLATION_LANGUAGES {
+                        option {
+                            key: "{code}",
+                            value: "{code}",
+                            selected: translation_language == *code,
+                            "{name}"
+                        }
+                    }
+                }
+            }
+
             // NWC Section
             div {
                 class: "bg-white dark:bg-gray-800 rounded-lg shadow-lg p-6",
@@ -599,6 +854,80 @@ pub fn Settings() -> Element {
                                             }
                                         }
                                     }
+
+                                    div {
+                                        class: "flex items-center justify-between",
+                                        span {
+                                            class: "text-xs text-gray-600 dark:text-gray-400",
+                                            "Remaining budget:"
+                                        }
+                                        span {
+                                            class: "text-sm font-mono text-gray-900 dark:text-white",
+                                            {match nwc_budget {
+                                                nwc_store::SpendBudget::Remaining(msats) => format!("{} sats", msats / 1000),
+                                                nwc_store::SpendBudget::Unknown => "unknown".to_string(),
+                                            }}
+                                        }
+                                    }
+                                }
+
+                                // Transaction history, if the wallet supports it
+                                if let Some(transactions) = nwc_transactions.as_ref() {
+                                    div {
+                                        class: "border border-gray-200 dark:border-gray-700 rounded-lg",
+                                        h4 {
+                                            class: "px-4 py-2 text-sm font-medium text-gray-900 dark:text-white border-b border-gray-200 dark:border-gray-700",
+                                            "Recent Transactions"
+                                        }
+                                        if transactions.is_empty() {
+                                            p {
+                                                class: "px-4 py-3 text-xs text-gray-500 dark:text-gray-400",
+                                                "No transactions yet"
+                                            }
+                                        }
+                                        div {
+                                            class: "max-h-64 overflow-y-auto divide-y divide-gray-200 dark:divide-gray-700",
+                                            for tx in transactions.iter() {
+                                                {
+                                                    let incoming = tx.direction == Some(TransactionType::Incoming);
+                                                    let sign = match tx.direction {
+                                                        Some(TransactionType::Incoming) => "+",
+                                                        Some(TransactionType::Outgoing) => "-",
+                                                        None => "",
+                                                    };
+                                                    let amount_class = if incoming {
+                                                        "text-sm font-mono text-green-600 dark:text-green-400"
+                                                    } else {
+                                                        "text-sm font-mono text-gray-900 dark:text-white"
+                                                    };
+                                                    let when = format_relative_time_ex(tx.created_at, true, false);
+                                                    rsx! {
+                                                        div {
+                                                            key: "{tx.payment_hash}",
+                                                            class: "px-4 py-2 flex items-center justify-between gap-3",
+                                                            div {
+                                                                class: "min-w-0",
+                                                                p {
+                                                                    class: "text-sm text-gray-900 dark:text-white truncate",
+                                                                    {tx.description.clone().unwrap_or_else(|| if incoming { "Received".to_string() } else { "Sent".to_string() })}
+                                                                }
+                                                                p {
+                                                                    class: "text-xs text-gray-500 dark:text-gray-400",
+                                                                    "{when}"
+                                                                    if tx.settled_at.is_none() { " · pending" }
+                                                                    if tx.fees_msats > 0 { " · fee {tx.fees_msats / 1000} sats" }
+                                                                }
+                                                            }
+                                                            span {
+                                                                class: amount_class,
+                                                                "{sign}{tx.amount_msats / 1000} sats"
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
                                 }
 
                                 // Action buttons
@@ -610,10 +939,13 @@ pub fn Settings() -> Element {
                                                 hover:bg-gray-200 dark:hover:bg-gray-600 transition-colors",
                                         onclick: move |_| {
                                             spawn(async move {
-                                                let _ = nwc_store::refresh_balance().await;
+                                                let _ = nwc_store::nwc_get_balance().await;
+                                                if nwc_store::NWC_TRANSACTIONS.peek().is_some() {
+                                                    let _ = nwc_store::nwc_list_transactions(nwc_store::TRANSACTION_LIST_LIMIT).await;
+                                                }
                                             });
                                         },
-                                        "Refresh Balance"
+                                        "Refresh"
                                     }
                                     button {
                                         class: "px-4 py-2 text-sm bg-red-100 dark:bg-red-900/30
@@ -798,6 +1130,47 @@ pub fn Settings() -> Element {
                         }
                     }
                 }
+
+                ZapAmountSettings {}
+            }
+
+            // Cashu wallet section
+            div {
+                class: "bg-white dark:bg-gray-800 rounded-lg shadow-lg p-6",
+                h3 {
+                    class: "text-xl font-semibold text-gray-900 dark:text-white mb-4",
+                    "🥜 Cashu Wallet"
+                }
+                div {
+                    class: "flex items-center justify-between gap-4",
+                    div {
+                        div {
+                            class: "text-sm font-medium text-gray-900 dark:text-white",
+                            "Sweep dust automatically"
+                        }
+                        p {
+                            class: "text-xs text-gray-600 dark:text-gray-400",
+                            "After receiving or minting, swap piles of tiny proofs at that mint into larger ones. The mint's swap fee applies."
+                        }
+                    }
+                    label {
+                        class: "relative inline-flex items-center cursor-pointer flex-shrink-0",
+                        input {
+                            r#type: "checkbox",
+                            class: "sr-only peer",
+                            checked: settings_store::SETTINGS.read().auto_sweep_dust,
+                            onchange: move |evt| {
+                                let enabled = evt.checked();
+                                spawn(async move {
+                                    settings_store::update_auto_sweep_dust(enabled).await;
+                                });
+                            }
+                        }
+                        div {
+                            class: "w-11 h-6 bg-gray-300 dark:bg-gray-700 peer-focus:outline-none peer-focus:ring-4 peer-focus:ring-blue-300 dark:peer-focus:ring-blue-800 rounded-full peer peer-checked:after:translate-x-full peer-checked:after:border-white after:content-[''] after:absolute after:top-[2px] after:left-[2px] after:bg-white after:border-gray-300 after:border after:rounded-full after:h-5 after:w-5 after:transition-all dark:border-gray-600 peer-checked:bg-blue-600"
+                        }
+                    }
+                }
             }
 
             // Content Moderation section
@@ -1093,7 +1466,7 @@ pub fn Settings() -> Element {
                     }
                 }
 
-                // Current relay connections
+                // Current relay connections, refreshed live
                 div {
                     class: "bg-white dark:bg-gray-800 rounded-lg shadow-lg p-6",
                     h3 {
@@ -1102,24 +1475,28 @@ pub fn Settings() -> Element {
                     }
                     p {
                         class: "text-sm text-gray-600 dark:text-gray-400 mb-4",
-                        "Connected to {relays.data().read().len()} relay(s)"
+                        "Connected to {connected_relays} of {relay_health.read().len()} relay(s)"
                     }
 
                     // Relay list
                     div {
                         class: "space-y-2",
-                        if relays.data().read().is_empty() {
+                        if relay_health.read().is_empty() {
                             div {
                                 class: "text-center p-8 text-gray-500 dark:text-gray-400",
                                 "No relays connected"
                             }
                         } else {
-                            for relay in relays.data().read().iter() {
+                            for relay in relay_health.read().iter() {
                                 div {
                                     key: "{relay.url}",
-                                    class: "flex items-center justify-between p-3 bg-gray-50 dark:bg-gray-700 rounded-lg",
+                                    class: if relay.is_connected() {
+                                        "flex items-center justify-between p-3 bg-gray-50 dark:bg-gray-700 rounded-lg"
+                                    } else {
+                                        "flex items-center justify-between p-3 bg-red-50 dark:bg-red-900/20 border border-red-300 dark:border-red-800 rounded-lg"
+                                    },
                                     div {
-                                        class: "flex items-center gap-3 flex-1",
+                                        class: "flex items-center gap-3 flex-1 min-w-0",
                                         span {
                                             class: match relay.status {
                                                 nostr_client::RelayStatus::Connected => "w-3 h-3 rounded-full bg-green-500",
@@ -1129,9 +1506,9 @@ pub fn Settings() -> Element {
                                             }
                                         }
                                         div {
-                                            class: "flex-1",
+                                            class: "flex-1 min-w-0",
                                             p {
-                                                class: "font-mono text-sm text-gray-900 dark:text-white",
+                                                class: "font-mono text-sm text-gray-900 dark:text-white truncate",
                                                 "{relay.url}"
                                             }
                                             p {
@@ -1142,8 +1519,60 @@ pub fn Settings() -> Element {
                                                     nostr_client::RelayStatus::Disconnected => "Disconnected",
                                                     nostr_client::RelayStatus::Error(e) => &e,
                                                 }
+                                                if let Some(last) = relay.last_message_at {
+                                                    {format!(" • last message {}", format_relative_time_ex(Timestamp::from(last), true, false))}
+                                                }
+                                                if let Some(next) = relay.next_reconnect_at {
+                                                    {format!(
+                                                        " • retrying in {}s (attempt {})",
+                                                        next.saturating_sub(Timestamp::now().as_secs()),
+                                                        relay.reconnect_attempts + 1
+                                                    )}
+                                                }
+                                            }
+                                            match relay_auth::RELAY_AUTH_STATE.read().get(relay.url.trim_end_matches('/')) {
+                                                Some(relay_auth::RelayAuthState::AwaitingApproval) => rsx! {
+                                                    p {
+                                                        class: "text-xs text-amber-600 dark:text-amber-400",
+                                                        "🔐 Requests authentication — allow AUTH to use it"
+                                                    }
+                                                },
+                                                Some(relay_auth::RelayAuthState::Authenticated) => rsx! {
+                                                    p {
+                                                        class: "text-xs text-green-600 dark:text-green-400",
+                                                        "🔐 Authenticated"
+                                                    }
+                                                },
+                                                Some(relay_auth::RelayAuthState::Failed(e)) => rsx! {
+                                                    p {
+                                                        class: "text-xs text-red-600 dark:text-red-400",
+                                                        "🔐 {e}"
+                                                    }
+                                                },
+                                                None => rsx! {},
+                                            }
+                                        }
+                                    }
+                                    label {
+                                        class: "flex items-center gap-1 text-xs text-gray-500 dark:text-gray-400 ml-3 cursor-pointer",
+                                        title: "Let this relay see your pubkey when it asks for NIP-42 authentication",
+                                        input {
+                                            r#type: "checkbox",
+                                            checked: relay_auth::is_auth_allowed(&relay.url),
+                                            onchange: {
+                                                let url = relay.url.clone();
+                                                move |e: FormEvent| relay_auth::set_auth_allowed(&url, e.checked())
                                             }
                                         }
+                                        "AUTH"
+                                    }
+                                    span {
+                                        class: "text-xs font-mono text-gray-500 dark:text-gray-400 ml-3",
+                                        if let Some(avg) = relay.avg_rtt_ms {
+                                            "{avg:.0} ms"
+                                        } else {
+                                            "– ms"
+                                        }
                                     }
                                 }
                             }
@@ -1243,6 +1672,38 @@ pub fn Settings() -> Element {
                     }
                 }
 
+                // Metadata stripping (applies to NIP-96 uploads too)
+                div {
+                    class: "flex items-center justify-between gap-4 pt-4 mt-4 border-t border-gray-200 dark:border-gray-700",
+                    div {
+                        div {
+                            class: "text-sm font-medium text-gray-900 dark:text-white",
+                            "Remove photo metadata"
+                        }
+                        p {
+                            class: "text-xs text-gray-600 dark:text-gray-400",
+                            "Strips location (GPS), camera and other EXIF data from JPEG, PNG and WebP images before upload."
+                        }
+                    }
+                    label {
+                        class: "relative inline-flex items-center cursor-pointer flex-shrink-0",
+                        input {
+                            r#type: "checkbox",
+                            class: "sr-only peer",
+                            checked: settings_store::SETTINGS.read().strip_image_metadata,
+                            onchange: move |evt| {
+                                let enabled = evt.checked();
+                                spawn(async move {
+                                    settings_store::update_strip_image_metadata(enabled).await;
+                                });
+                            }
+                        }
+                        div {
+                            class: "w-11 h-6 bg-gray-300 dark:bg-gray-700 peer-focus:outline-none peer-focus:ring-4 peer-focus:ring-blue-300 dark:peer-focus:ring-blue-800 rounded-full peer peer-checked:after:translate-x-full peer-checked:after:border-white after:content-[''] after:absolute after:top-[2px] after:left-[2px] after:bg-white after:border-gray-300 after:border after:rounded-full after:h-5 after:w-5 after:transition-all dark:border-gray-600 peer-checked:bg-blue-600"
+                        }
+                    }
+                }
+
                 // Publish button (only when authenticated)
                 if auth.is_authenticated {
                     div {
@@ -1306,6 +1767,153 @@ pub fn Settings() -> Element {
     }
 }
 
+/// Zap dialog preset amounts and the currency for fiat equivalents
+#[component]
+fn ZapAmountSettings() -> Element {
+    let mut presets_input = use_signal(|| {
+        settings_store::SETTINGS.read().zap_presets.iter().map(|amount| amount.to_string()).collect::<Vec<_>>().join(", ")
+    });
+    let mut presets_error = use_signal(|| None::<String>);
+    let mut presets_saved = use_signal(|| false);
+    let fiat_currency = settings_store::SETTINGS.read().fiat_currency.clone();
+    let mut comment_input = use_signal(|| settings_store::SETTINGS.read().default_zap_comment.clone());
+    let anonymous_zaps = settings_store::SETTINGS.read().anonymous_zaps;
+
+    rsx! {
+        div {
+            class: "mt-6 pt-6 border-t border-gray-200 dark:border-gray-700 space-y-4",
+            h4 {
+                class: "text-sm font-medium text-gray-900 dark:text-white",
+                "Zap Defaults"
+            }
+
+            // Preset amounts
+            div {
+                p {
+                    class: "text-xs text-gray-600 dark:text-gray-400 mb-2",
+                    "Amount buttons in the zap dialog, in sats (up to {settings_store::MAX_ZAP_PRESETS})"
+                }
+                div {
+                    class: "flex gap-2",
+                    input {
+                        r#type: "text",
+                        class: "flex-1 px-3 py-2 text-sm bg-white dark:bg-gray-700 border border-gray-300 dark:border-gray-600 rounded-lg text-gray-900 dark:text-white",
+                        placeholder: "21, 100, 1000",
+                        value: "{presets_input}",
+                        oninput: move |e| {
+                            presets_input.set(e.value());
+                            presets_error.set(None);
+                            presets_saved.set(false);
+                        }
+                    }
+                    button {
+                        class: "px-4 py-2 text-sm bg-blue-600 hover:bg-blue-700 text-white rounded-lg transition",
+                        onclick: move |_| {
+                            match settings_store::parse_zap_presets(&presets_input.read()) {
+                                Ok(presets) => {
+                                    presets_input.set(presets.iter().map(|amount| amount.to_string()).collect::<Vec<_>>().join(", "));
+                                    presets_saved.set(true);
+                                    spawn(async move {
+                                        settings_store::update_zap_presets(presets).await;
+                                    });
+                                }
+                                Err(e) => presets_error.set(Some(e)),
+                            }
+                        },
+                        "Save"
+                    }
+                }
+                if let Some(error) = presets_error.read().as_ref() {
+                    p {
+                        class: "text-xs text-red-600 dark:text-red-400 mt-1",
+                        "{error}"
+                    }
+                } else if *presets_saved.read() {
+                    p {
+                        class: "text-xs text-green-600 dark:text-green-400 mt-1",
+                        "Saved"
+                    }
+                }
+            }
+
+            // Fiat currency
+            div {
+                p {
+                    class: "text-xs text-gray-600 dark:text-gray-400 mb-2",
+                    "Show zap amounts in a local currency too"
+                }
+                select {
+                    class: "px-3 py-2 text-sm bg-white dark:bg-gray-700 border border-gray-300 dark:border-gray-600 rounded-lg text-gray-900 dark:text-white",
+                    value: "{fiat_currency}",
+                    onchange: move |e| {
+                        let currency = e.value();
+                        spawn(async move {
+                            settings_store::update_fiat_currency(currency).await;
+                        });
+                    },
+                    option { value: "", selected: fiat_currency.is_empty(), "None" }
+                    for (code, _symbol) in btc_price::SUPPORTED_CURRENCIES {
+                        option {
+                            key: "{code}",
+                            value: "{code}",
+                            selected: fiat_currency == code,
+                            "{code.to_uppercase()}"
+                        }
+                    }
+                }
+            }
+
+            // Default comment, saved when the field loses focus
+            div {
+                p {
+                    class: "text-xs text-gray-600 dark:text-gray-400 mb-2",
+                    "Default zap comment"
+                }
+                input {
+                    r#type: "text",
+                    class: "w-full px-3 py-2 text-sm bg-white dark:bg-gray-700 border border-gray-300 dark:border-gray-600 rounded-lg text-gray-900 dark:text-white",
+                    placeholder: "e.g. Great post! ⚡",
+                    value: "{comment_input}",
+                    oninput: move |e| comment_input.set(e.value()),
+                    onchange: move |_| {
+                        let comment = comment_input.read().clone();
+                        spawn(async move {
+                            settings_store::update_zap_defaults(comment, anonymous_zaps).await;
+                        });
+                    }
+                }
+            }
+
+            // Anonymous by default
+            label {
+                class: "flex items-start gap-3 cursor-pointer",
+                input {
+                    r#type: "checkbox",
+                    class: "mt-1",
+                    checked: anonymous_zaps,
+                    onchange: move |e| {
+                        let comment = comment_input.read().clone();
+                        let anonymous = e.checked();
+                        spawn(async move {
+                            settings_store::update_zap_defaults(comment, anonymous).await;
+                        });
+                    }
+                }
+                div {
+                    div {
+                        class: "text-sm font-medium text-gray-900 dark:text-white",
+                        "Zap anonymously by default"
+                    }
+                    p {
+                        class: "text-xs text-gray-600 dark:text-gray-400",
+                        "Zap requests are signed with a throwaway key so recipients can't see who zapped"
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 fn render_account_info() -> Element {
     let auth = auth_store::AUTH_STATE.read();
@@ -1313,6 +1921,8 @@ fn render_account_info() -> Element {
     let _show_npub_export = use_signal(|| false);
     #[cfg_attr(not(target_arch = "wasm32"), allow(unused_mut))]
     let mut copy_status = use_signal(|| None::<String>);
+    let mut wipe_on_logout = use_signal(|| false);
+    let mut logout_error = use_signal(|| None::<String>);
 
     let copy_to_clipboard = move |_text: String, _label: &str| {
         #[cfg(target_arch = "wasm32")]
@@ -1527,17 +2137,48 @@ fn render_account_info() -> Element {
                 }
             }
 
-            // Logout button
+            // Logout
+            label {
+                class: "flex items-start gap-3 p-3 rounded-lg border border-gray-200 dark:border-gray-700 cursor-pointer",
+                input {
+                    r#type: "checkbox",
+                    class: "mt-1",
+                    checked: *wipe_on_logout.read(),
+                    onchange: move |evt| wipe_on_logout.set(evt.checked()),
+                }
+                div {
+                    p {
+                        class: "text-sm font-medium text-gray-900 dark:text-white",
+                        "Wipe local data on this device"
+                    }
+                    p {
+                        class: "text-xs text-gray-600 dark:text-gray-400",
+                        "Also clears cached wallet data, cached notes and profiles, and DM read state. Your wallet and posts stay on your relays. Not possible while wallet updates are still publishing or Lightning payments are in flight."
+                    }
+                }
+            }
+            if let Some(error) = logout_error.read().as_ref() {
+                div {
+                    class: "p-3 bg-red-100 dark:bg-red-900 text-red-800 dark:text-red-200 rounded-lg text-sm",
+                    "{error}"
+                }
+            }
             button {
                 class: "w-full px-4 py-3 bg-red-600 hover:bg-red-700 text-white rounded-lg font-medium transition",
                 onclick: move |_| {
                     let nav = navigator();
+                    let wipe = *wipe_on_logout.read();
+                    logout_error.set(None);
                     spawn(async move {
-                        auth_store::logout().await;
-                        nav.push(Route::Home {});
+                        match auth_store::logout(wipe).await {
+                            Ok(()) => {
+                                nav.push(Route::Home {});
+                            }
+                            Err(e) => logout_error.set(Some(e)),
+                        }
                     });
                 },
-                "🚪 Logout"
+                if *wipe_on_logout.read() { "🚪 Logout and wipe local data" } else { "🚪 Logout" }
             }
         }
     }
@@ -1,5 +1,5 @@
 use dioxus::prelude::*;
-use crate::stores::nostr_client;
+use crate::stores::{nostr_client, settings_store};
 use crate::routes::Route;
 
 #[component]
@@ -25,6 +25,28 @@ pub fn SettingsMuted() -> Element {
         });
     });
 
+    let mut new_word = use_signal(|| String::new());
+    let mut new_word_whole = use_signal(|| true);
+    let mut word_error = use_signal(|| None::<String>);
+    let muted_words = settings_store::SETTINGS.read().muted_words.clone();
+
+    let handle_add_word = move |_| {
+        let word = new_word.read().clone();
+        let whole_word = *new_word_whole.read();
+        spawn(async move {
+            match settings_store::add_muted_word(word, whole_word).await {
+                Ok(_) => {
+                    new_word.set(String::new());
+                    word_error.set(None);
+                }
+                Err(e) => {
+                    log::error!("Failed to mute word: {}", e);
+                    word_error.set(Some(e));
+                }
+            }
+        });
+    };
+
     let handle_unmute = move |event_id: String| {
         let event_id_clone = event_id.clone();
         spawn(async move {
@@ -65,6 +87,84 @@ pub fn SettingsMuted() -> Element {
                 }
             }
 
+            // Muted words
+            div {
+                class: "bg-background border border-border rounded-lg shadow-sm mb-6 p-4",
+                h2 {
+                    class: "text-lg font-semibold",
+                    "Muted Words"
+                }
+                p {
+                    class: "text-sm text-muted-foreground mb-4",
+                    "Notes containing these words are hidden from your feeds. Start with # to mute a hashtag."
+                }
+
+                div {
+                    class: "flex flex-wrap items-center gap-2",
+                    input {
+                        r#type: "text",
+                        class: "flex-1 min-w-[10rem] px-3 py-2 text-sm bg-background border border-border rounded-lg focus:outline-none focus:ring-2 focus:ring-primary",
+                        placeholder: "Word or #hashtag",
+                        value: "{new_word}",
+                        oninput: move |e| new_word.set(e.value())
+                    }
+                    label {
+                        class: "flex items-center gap-2 text-sm text-muted-foreground",
+                        input {
+                            r#type: "checkbox",
+                            checked: *new_word_whole.read(),
+                            onchange: move |e| new_word_whole.set(e.checked())
+                        }
+                        "Whole word only"
+                    }
+                    button {
+                        class: "px-4 py-2 text-sm bg-primary hover:bg-primary/90 text-primary-foreground rounded-lg transition disabled:opacity-50",
+                        disabled: new_word.read().trim().is_empty(),
+                        onclick: handle_add_word,
+                        "Mute"
+                    }
+                }
+
+                if let Some(err) = word_error.read().as_ref() {
+                    p {
+                        class: "mt-2 text-sm text-red-600",
+                        "{err}"
+                    }
+                }
+
+                if !muted_words.is_empty() {
+                    div {
+                        class: "mt-4 flex flex-wrap gap-2",
+                        for muted in muted_words {
+                            span {
+                                key: "{muted.word}",
+                                class: "inline-flex items-center gap-1 px-3 py-1 text-sm bg-accent rounded-full",
+                                "{muted.word}"
+                                if !muted.whole_word {
+                                    span { class: "text-xs text-muted-foreground", "(partial)" }
+                                }
+                                button {
+                                    class: "ml-1 text-muted-foreground hover:text-foreground",
+                                    title: "Unmute",
+                                    onclick: {
+                                        let word = muted.word.clone();
+                                        move |_| {
+                                            let word = word.clone();
+                                            spawn(async move {
+                                                if let Err(e) = settings_store::remove_muted_word(word).await {
+                                                    log::error!("Failed to unmute word: {}", e);
+                                                }
+                                            });
+                                        }
+                                    },
+                                    "✕"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
             // Content
             div {
                 class: "bg-background border border-border rounded-lg shadow-sm",
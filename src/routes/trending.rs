@@ -1,6 +1,7 @@
 use dioxus::prelude::*;
 use crate::services::trending::{get_trending_notes, TrendingNote};
 use crate::components::{NoteCard, NoteCardSkeleton};
+use crate::stores::settings_store;
 use nostr_sdk::{Event as NostrEvent, EventId, PublicKey, Timestamp, Kind, Tag};
 use nostr::secp256k1::schnorr::Signature;
 
@@ -21,7 +22,8 @@ pub fn Trending() -> Element {
         error.set(None);
 
         spawn(async move {
-            match get_trending_notes(Some(100)).await {
+            let config = settings_store::SETTINGS.read().trending.clone();
+            match get_trending_notes(Some(100), &config).await {
                 Ok(notes) => {
                     // Convert TrendingNote to nostr_sdk::Event
                     let mut converted_events = Vec::new();
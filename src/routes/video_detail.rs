@@ -2,14 +2,18 @@ use dioxus::prelude::*;
 use crate::stores::{auth_store, nostr_client};
 use crate::stores::signer::SIGNER_INFO;
 use crate::components::{ThreadedComment, CommentComposer, ClientInitializing, ShareModal, icons::MessageCircleIcon};
-use crate::utils::{build_thread_tree, merge_pending_into_tree};
+use crate::utils::{build_thread_tree, merge_pending_into_tree, nip22};
 use crate::stores::pending_comments::get_pending_comments;
 use crate::utils::format_sats_compact;
+use crate::utils::video_variants::{self, VideoVariant};
 use nostr_sdk::{Event, Filter, Kind, EventId, Timestamp, PublicKey};
 use std::time::Duration;
 use wasm_bindgen::JsCast;
 use web_sys::HtmlVideoElement;
 
+/// Quality picked in the player ("720p", "auto"), kept for the session
+pub static PREFERRED_VIDEO_QUALITY: GlobalSignal<Option<String>> = Signal::global(|| None);
+
 #[derive(Clone, Copy, PartialEq, Debug)]
 enum FeedType {
     Following,
@@ -130,50 +134,63 @@ fn LandscapePlayer(event: Event) -> Element {
     let mut comments = use_signal(|| Vec::<Event>::new());
     let mut loading_comments = use_signal(|| false);
     let mut show_comment_composer = use_signal(|| false);
-    let event_id = event.id;
+    let root = event.clone();
 
     // Fetch NIP-22 comments for the video
     use_effect(move || {
+        let root = root.clone();
         spawn(async move {
             loading_comments.set(true);
 
-            // Fetch Kind 1111 (NIP-22 Comment) events and Kind 1 replies
-            let upper_e_tag = nostr_sdk::SingleLetterTag::uppercase(nostr_sdk::Alphabet::E);
-            let filter_upper = Filter::new()
-                .kind(Kind::Comment)
-                .custom_tag(upper_e_tag, event_id.to_hex())
-                .limit(500);
-
-            let filter_lower = Filter::new()
-                .kinds(vec![Kind::TextNote, Kind::Comment])
-                .event(event_id)
-                .limit(500);
-
-            let mut all_comments = Vec::new();
-
-            if let Ok(upper_comments) = nostr_client::fetch_events_aggregated(filter_upper, Duration::from_secs(10)).await {
-                all_comments.extend(upper_comments.into_iter());
-            }
-
-            if let Ok(lower_comments) = nostr_client::fetch_events_aggregated(filter_lower, Duration::from_secs(10)).await {
-                all_comments.extend(lower_comments.into_iter());
-            }
-
-            // Deduplicate
-            let mut seen_ids = std::collections::HashSet::new();
-            let unique_comments: Vec<Event> = all_comments.into_iter()
-                .filter(|event| seen_ids.insert(event.id))
-                .collect();
-
-            let mut sorted_comments = unique_comments;
-            sorted_comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-            comments.set(sorted_comments);
+            // NIP-22 comments scoped to the video, plus kind 1 replies
+            comments.set(nip22::fetch_comments(&root).await);
 
             loading_comments.set(false);
         });
     });
 
     let video_meta = parse_video_meta(&event);
+    let player_id = format!("video-player-{}", &event.id.to_hex()[..8]);
+    let mut resume_at = use_signal(|| None::<f64>);
+    // Variant picked in the quality menu, by URL: labels can repeat
+    let mut chosen_url = use_signal(|| None::<String>);
+
+    let preferred = PREFERRED_VIDEO_QUALITY.read().clone();
+    let selected = chosen_url.read().as_ref()
+        .and_then(|url| video_meta.variants.iter().find(|variant| variant.url == *url))
+        .or_else(|| video_variants::pick_variant(&video_meta.variants, preferred.as_deref()))
+        .cloned();
+    let sources = selected.as_ref()
+        .map(|variant| video_variants::playback_sources(&video_meta.variants, variant))
+        .unwrap_or_default();
+    let selected_url = selected.as_ref().map(|v| v.url.clone());
+
+    // Reload the player when the source list changes (quality switch)
+    let first_source = sources.first().map(|(url, _)| url.clone());
+    let player_id_for_effect = player_id.clone();
+    use_effect(use_reactive!(|first_source| {
+        if first_source.is_some() && resume_at.peek().is_some() {
+            if let Some(video) = find_video_element(&player_id_for_effect) {
+                video.load();
+            }
+        }
+    }));
+
+    let player_id_for_switch = player_id.clone();
+    let variants_for_switch = video_meta.variants.clone();
+    let mut switch_quality = move |url: String| {
+        let Some(variant) = variants_for_switch.iter().find(|variant| variant.url == url) else {
+            return;
+        };
+        // Carry on from the same spot after the switch
+        if let Some(video) = find_video_element(&player_id_for_switch) {
+            resume_at.set(Some(video.current_time()));
+        }
+        *PREFERRED_VIDEO_QUALITY.write() = Some(variant.label());
+        chosen_url.set(Some(url));
+    };
+
+    let player_id_for_resume = player_id.clone();
 
     rsx! {
         div {
@@ -205,15 +222,53 @@ fn LandscapePlayer(event: Event) -> Element {
                     class: "relative w-full bg-black rounded-lg overflow-hidden mb-4",
                     style: "max-height: 80vh;",
 
-                    if let Some(url) = &video_meta.url {
+                    if !sources.is_empty() {
                         video {
+                            id: "{player_id}",
                             class: "w-full h-full object-contain",
-                            src: "{url}",
                             poster: "{video_meta.thumbnail.clone().unwrap_or_default()}",
                             controls: true,
                             muted: *is_muted.read(),
                             autoplay: true,
                             playsinline: true,
+                            onloadedmetadata: move |_| {
+                                let position = resume_at.write().take();
+                                if let (Some(position), Some(video)) = (position, find_video_element(&player_id_for_resume)) {
+                                    video.set_current_time(position);
+                                    let _ = video.play();
+                                }
+                            },
+                            for (url, source_type) in sources.iter() {
+                                source {
+                                    key: "{url}",
+                                    src: "{url}",
+                                    r#type: source_type.clone(),
+                                }
+                            }
+                        }
+
+                        // Quality menu, when there is a choice
+                        if video_meta.variants.len() > 1 {
+                            div {
+                                class: "absolute top-3 right-3 z-10",
+                                select {
+                                    class: "px-2 py-1 text-xs bg-black/70 text-white border border-white/20 rounded",
+                                    title: "Quality",
+                                    value: selected_url.clone().unwrap_or_default(),
+                                    onchange: move |e| switch_quality(e.value()),
+                                    for variant in video_meta.variants.iter() {
+                                        option {
+                                            key: "{variant.url}",
+                                            value: "{variant.url}",
+                                            if variant.is_hls() {
+                                                "auto (HLS)"
+                                            } else {
+                                                "{variant.label()}"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
                         }
                     } else {
                         div {
@@ -337,10 +392,10 @@ fn LandscapePlayer(event: Event) -> Element {
                         // Only build thread tree after loading completes to avoid caching empty results
                         {
                             let comment_vec = comments.read().clone();
-                            let confirmed_tree = build_thread_tree(comment_vec, &event_id);
+                            let confirmed_tree = build_thread_tree(comment_vec, &event.id, None);
                             // Merge pending comments for optimistic display
-                            let pending = get_pending_comments(&event_id);
-                            let thread_tree = merge_pending_into_tree(confirmed_tree, pending, &event_id);
+                            let pending = get_pending_comments(&event.id);
+                            let thread_tree = merge_pending_into_tree(confirmed_tree, pending, &event.id);
 
                             rsx! {
                                 if thread_tree.is_empty() {
@@ -357,8 +412,10 @@ fn LandscapePlayer(event: Event) -> Element {
                                         class: "divide-y divide-border",
                                         for node in thread_tree {
                                             ThreadedComment {
+                                                key: "{node.event.id}",
                                                 node: node.clone(),
-                                                depth: 0
+                                                depth: 0,
+                                                comment_root: Some(event.clone())
                                             }
                                         }
                                     }
@@ -377,38 +434,10 @@ fn LandscapePlayer(event: Event) -> Element {
                         on_success: move |_| {
                             show_comment_composer.set(false);
                             // Refresh comments
+                            let root = event.clone();
                             spawn(async move {
                                 loading_comments.set(true);
-
-                                let upper_e_tag = nostr_sdk::SingleLetterTag::uppercase(nostr_sdk::Alphabet::E);
-                                let filter_upper = Filter::new()
-                                    .kind(Kind::Comment)
-                                    .custom_tag(upper_e_tag, event_id.to_hex())
-                                    .limit(500);
-
-                                let filter_lower = Filter::new()
-                                    .kinds(vec![Kind::TextNote, Kind::Comment])
-                                    .event(event_id)
-                                    .limit(500);
-
-                                let mut all_comments = Vec::new();
-
-                                if let Ok(upper_comments) = nostr_client::fetch_events_aggregated(filter_upper, Duration::from_secs(10)).await {
-                                    all_comments.extend(upper_comments.into_iter());
-                                }
-
-                                if let Ok(lower_comments) = nostr_client::fetch_events_aggregated(filter_lower, Duration::from_secs(10)).await {
-                                    all_comments.extend(lower_comments.into_iter());
-                                }
-
-                                let mut seen_ids = std::collections::HashSet::new();
-                                let unique_comments: Vec<Event> = all_comments.into_iter()
-                                    .filter(|event| seen_ids.insert(event.id))
-                                    .collect();
-
-                                let mut sorted_comments = unique_comments;
-                                sorted_comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-                                comments.set(sorted_comments);
+                                comments.set(nip22::fetch_comments(&root).await);
 
                                 loading_comments.set(false);
                             });
@@ -659,6 +688,10 @@ fn VerticalVideoPlayer(
     let video_id = format!("video-{}", event.id.to_hex()[..8].to_string());
     let video_id_for_effect = video_id.clone();
     let video_meta = parse_video_meta(&event);
+    let preferred = PREFERRED_VIDEO_QUALITY.read().clone();
+    let sources = video_variants::pick_variant(&video_meta.variants, preferred.as_deref())
+        .map(|variant| video_variants::playback_sources(&video_meta.variants, variant))
+        .unwrap_or_default();
 
     // Reactively update muted state
     use_effect(use_reactive(&is_muted, move |muted| {
@@ -681,17 +714,23 @@ fn VerticalVideoPlayer(
         div {
             class: "relative w-full h-full flex items-center justify-center bg-black",
 
-            if let Some(url) = video_meta.url.clone() {
+            if !sources.is_empty() {
                 video {
                     id: "{video_id}",
                     class: "max-w-full max-h-full object-contain",
-                    src: "{url}",
                     poster: "{video_meta.thumbnail.clone().unwrap_or_default()}",
                     loop: true,
                     muted: is_muted,
                     autoplay: is_active,
                     playsinline: true,
                     controls: true,
+                    for (url, source_type) in sources.iter() {
+                        source {
+                            key: "{url}",
+                            src: "{url}",
+                            r#type: source_type.clone(),
+                        }
+                    }
                 }
             } else {
                 div {
@@ -730,7 +769,6 @@ fn VideoInfo(
     let author_pubkey_for_fetch = author_pubkey.clone();
     let event_id = event.id.to_string();
     let event_id_counts = event_id.clone();
-    let event_id_for_comments = event_id.clone();
     let event_id_parsed = event.id;
 
     let mut author_metadata = use_signal(|| None::<nostr_sdk::Metadata>);
@@ -850,54 +888,11 @@ fn VideoInfo(
     }));
 
     // Fetch comments on mount (same pattern as photo_detail)
-    use_effect(use_reactive(&event_id_for_comments, move |event_id_str| {
-        let event_id_clone = event_id_str.to_string();
-
+    use_effect(use_reactive(&event, move |root| {
         spawn(async move {
             loading_comments.set(true);
 
-            let event_id_parsed = match nostr_sdk::EventId::from_hex(&event_id_clone) {
-                Ok(id) => id,
-                Err(_) => {
-                    loading_comments.set(false);
-                    return;
-                }
-            };
-
-            let event_id_hex = event_id_parsed.to_hex();
-
-            // Create filter for uppercase E tags (NIP-22 comments)
-            let upper_e_tag = nostr_sdk::SingleLetterTag::uppercase(nostr_sdk::Alphabet::E);
-            let filter_upper = Filter::new()
-                .kind(Kind::Comment)
-                .custom_tag(upper_e_tag, event_id_hex.clone())
-                .limit(500);
-
-            // Create filter for lowercase e tags (standard replies)
-            let filter_lower = Filter::new()
-                .kinds(vec![Kind::TextNote, Kind::Comment])
-                .event(event_id_parsed)
-                .limit(500);
-
-            // Fetch both filters and combine results
-            let mut all_comments = Vec::new();
-
-            if let Ok(upper_comments) = nostr_client::fetch_events_aggregated(filter_upper, Duration::from_secs(10)).await {
-                all_comments.extend(upper_comments.into_iter());
-            }
-
-            if let Ok(lower_comments) = nostr_client::fetch_events_aggregated(filter_lower, Duration::from_secs(10)).await {
-                all_comments.extend(lower_comments.into_iter());
-            }
-
-            // Deduplicate by event ID
-            let mut seen_ids = std::collections::HashSet::new();
-            let unique_comments: Vec<Event> = all_comments.into_iter()
-                .filter(|event| seen_ids.insert(event.id))
-                .collect();
-
-            let mut sorted_comments = unique_comments;
-            sorted_comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+            let sorted_comments = nip22::fetch_comments(&root).await;
             log::info!("VideoInfo loaded {} comments", sorted_comments.len());
             comments.set(sorted_comments);
 
@@ -1149,7 +1144,7 @@ fn VideoInfo(
                                 // Only build thread tree after loading completes to avoid caching empty results
                                 {
                                     let comment_vec = comments.read().clone();
-                                    let confirmed_tree = build_thread_tree(comment_vec, &event_id_parsed);
+                                    let confirmed_tree = build_thread_tree(comment_vec, &event_id_parsed, None);
                                     // Merge pending comments for optimistic display
                                     let pending = get_pending_comments(&event_id_parsed);
                                     let thread_tree = merge_pending_into_tree(confirmed_tree, pending, &event_id_parsed);
@@ -1169,8 +1164,10 @@ fn VideoInfo(
                                                 class: "divide-y divide-border",
                                                 for node in thread_tree {
                                                     ThreadedComment {
+                                                        key: "{node.event.id}",
                                                         node: node.clone(),
-                                                        depth: 0
+                                                        depth: 0,
+                                                        comment_root: Some(event.clone())
                                                     }
                                                 }
                                             }
@@ -1205,40 +1202,10 @@ fn VideoInfo(
                     on_success: move |_| {
                         show_comment_composer.set(false);
                         // Reload comments (same pattern as photo_detail)
-                        let event_id = event_id_parsed;
+                        let root = event.clone();
                         spawn(async move {
                             loading_comments.set(true);
-                            let event_id_hex = event_id.to_hex();
-
-                            let upper_e_tag = nostr_sdk::SingleLetterTag::uppercase(nostr_sdk::Alphabet::E);
-                            let filter_upper = Filter::new()
-                                .kind(Kind::Comment)
-                                .custom_tag(upper_e_tag, event_id_hex.clone())
-                                .limit(500);
-
-                            let filter_lower = Filter::new()
-                                .kinds(vec![Kind::TextNote, Kind::Comment])
-                                .event(event_id)
-                                .limit(500);
-
-                            let mut all_comments = Vec::new();
-
-                            if let Ok(upper_comments) = nostr_client::fetch_events_aggregated(filter_upper, Duration::from_secs(10)).await {
-                                all_comments.extend(upper_comments.into_iter());
-                            }
-
-                            if let Ok(lower_comments) = nostr_client::fetch_events_aggregated(filter_lower, Duration::from_secs(10)).await {
-                                all_comments.extend(lower_comments.into_iter());
-                            }
-
-                            let mut seen_ids = std::collections::HashSet::new();
-                            let unique_comments: Vec<Event> = all_comments.into_iter()
-                                .filter(|event| seen_ids.insert(event.id))
-                                .collect();
-
-                            let mut sorted_comments = unique_comments;
-                            sorted_comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-                            comments.set(sorted_comments);
+                            comments.set(nip22::fetch_comments(&root).await);
                             loading_comments.set(false);
                         });
                         show_comments_modal.set(true);
@@ -1511,7 +1478,8 @@ fn AuthorInfo(pubkey: String) -> Element {
 // Video metadata structure
 #[derive(Clone, Debug, PartialEq)]
 struct VideoMeta {
-    url: Option<String>,
+    /// Every rendition, sorted for the quality menu
+    variants: Vec<VideoVariant>,
     thumbnail: Option<String>,
     title: Option<String>,
     duration: Option<String>,
@@ -1521,7 +1489,7 @@ struct VideoMeta {
 // Parse NIP-71 video metadata from imeta tags
 fn parse_video_meta(event: &Event) -> VideoMeta {
     let mut meta = VideoMeta {
-        url: None,
+        variants: video_variants::parse_video_variants(event),
         thumbnail: None,
         title: None,
         duration: None,
@@ -1544,7 +1512,6 @@ fn parse_video_meta(event: &Event) -> VideoMeta {
             for field in tag_vec.iter().skip(1) {
                 if let Some((key, value)) = field.split_once(' ') {
                     match key {
-                        "url" => meta.url = Some(value.to_string()),
                         "image" => meta.thumbnail = Some(value.to_string()),
                         "duration" => meta.duration = Some(value.to_string()),
                         "dim" => meta.dimensions = Some(value.to_string()),
@@ -1558,6 +1525,15 @@ fn parse_video_meta(event: &Event) -> VideoMeta {
     meta
 }
 
+// Look up a video element by id
+fn find_video_element(id: &str) -> Option<HtmlVideoElement> {
+    web_sys::window()?
+        .document()?
+        .get_element_by_id(id)?
+        .dyn_into::<HtmlVideoElement>()
+        .ok()
+}
+
 // Format timestamp as "X ago"
 fn format_time_ago(timestamp: u64) -> String {
     let now = (js_sys::Date::now() / 1000.0) as u64;
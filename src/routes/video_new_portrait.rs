@@ -1,6 +1,7 @@
 use dioxus::prelude::*;
 use crate::stores::auth_store;
 use crate::components::MediaUploader;
+use crate::utils::file_metadata::UploadedMedia;
 
 #[component]
 pub fn VideoNewPortrait() -> Element {
@@ -8,6 +9,8 @@ pub fn VideoNewPortrait() -> Element {
     let mut title = use_signal(|| String::new());
     let mut description = use_signal(|| String::new());
     let mut video_url = use_signal(|| Option::<String>::None);
+    // NIP-94 metadata of the uploaded video
+    let mut video_media = use_signal(|| Option::<UploadedMedia>::None);
     let mut thumbnail_url = use_signal(|| String::new());
     let mut hashtags = use_signal(|| String::new());
     let mut is_publishing = use_signal(|| false);
@@ -35,6 +38,10 @@ pub fn VideoNewPortrait() -> Element {
         log::info!("Video uploaded: {}", url);
     };
 
+    let handle_video_media = move |media: UploadedMedia| {
+        video_media.set(Some(media));
+    };
+
     // Handle thumbnail upload
     let handle_thumbnail_uploaded = move |url: String| {
         thumbnail_url.set(url.clone());
@@ -51,6 +58,11 @@ pub fn VideoNewPortrait() -> Element {
         let title_val = title.read().clone();
         let description_val = description.read().clone();
         let video_url_val = video_url.read().clone().unwrap_or_default();
+        let video_val = video_media
+            .read()
+            .clone()
+            .filter(|media| media.url == video_url_val)
+            .unwrap_or_else(|| UploadedMedia::from_url(video_url_val));
         let thumbnail_url_val = thumbnail_url.read().clone();
         let hashtags_val = hashtags.read().clone();
 
@@ -68,7 +80,7 @@ pub fn VideoNewPortrait() -> Element {
             match crate::stores::nostr_client::publish_video(
                 title_val,
                 description_val,
-                video_url_val,
+                video_val,
                 thumbnail_url_val,
                 tags_vec,
                 true, // portrait/vertical video
@@ -185,6 +197,7 @@ pub fn VideoNewPortrait() -> Element {
                         } else if *show_video_uploader.read() {
                             MediaUploader {
                                 on_upload: handle_video_uploaded,
+                                on_upload_media: handle_video_media,
                             }
                         }
 
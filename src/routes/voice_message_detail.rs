@@ -197,7 +197,7 @@ pub fn VoiceMessageDetail(voice_id: String) -> Element {
                                 // Build thread tree and render - handle both voice and text replies
                                 {
                                     let reply_vec = replies.read().clone();
-                                    let confirmed_tree = build_thread_tree(reply_vec.clone(), &event.id);
+                                    let confirmed_tree = build_thread_tree(reply_vec.clone(), &event.id, None);
                                     // Merge pending comments for optimistic display
                                     let pending = get_pending_comments(&event.id);
                                     let thread_tree = merge_pending_into_tree(confirmed_tree, pending, &event.id);
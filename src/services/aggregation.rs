@@ -18,8 +18,9 @@
 
 use dioxus::prelude::ReadableExt;
 use lru::LruCache;
-use nostr_sdk::{Event, EventId, Filter, Kind, Timestamp, TagStandard};
+use nostr_sdk::{Event, EventId, Filter, JsonUtil, Kind, PublicKey, Timestamp, TagStandard};
 use nostr_relay_pool::{SyncOptions, SyncDirection};
+use crate::services::lnurl::{self, LnUrlError};
 use crate::stores::nostr_client::get_client;
 use crate::stores::signer::SIGNER_INFO;
 use std::collections::HashMap;
@@ -530,6 +531,8 @@ pub async fn fetch_interaction_counts_batch(
     // we use "first seen wins" - the first reaction we see is the most recent one.
     let mut user_reactions_seen: std::collections::HashSet<String> = std::collections::HashSet::new();
 
+    let zap_amounts = verified_zap_amounts(&events).await;
+
     // Count interactions
     for event in events {
         // Get the event this interaction is referencing, only if it's one we requested
@@ -596,10 +599,9 @@ pub async fn fetch_interaction_counts_batch(
             },
             Kind::Repost => counts.reposts += 1,
             Kind::ZapReceipt => {
-                counts.zaps += 1;
-                // Extract zap amount from bolt11 tag
-                if let Some(amount) = extract_zap_amount(&event) {
-                    counts.zap_amount_sats += amount;
+                if let Some(msats) = zap_amounts.get(&event.id) {
+                    counts.zaps += 1;
+                    counts.zap_amount_sats += msats / 1000;
                 }
             }
             _ => {}
@@ -714,6 +716,8 @@ pub async fn sync_interaction_counts(
                 .map(|id| id.to_hex())
                 .collect();
 
+            let zap_amounts = verified_zap_amounts(&new_events).await;
+
             // Process new events and update counts
             for event in new_events {
                 let referenced_event_id = match extract_referenced_event(&event, &requested_ids) {
@@ -767,9 +771,9 @@ pub async fn sync_interaction_counts(
                     }
                     Kind::Repost => counts.reposts += 1,
                     Kind::ZapReceipt => {
-                        counts.zaps += 1;
-                        if let Some(amount) = extract_zap_amount(&event) {
-                            counts.zap_amount_sats += amount;
+                        if let Some(msats) = zap_amounts.get(&event.id) {
+                            counts.zaps += 1;
+                            counts.zap_amount_sats += msats / 1000;
                         }
                     }
                     _ => {}
@@ -845,74 +849,248 @@ fn extract_referenced_event(event: &Event, requested_ids: &std::collections::Has
     None
 }
 
-/// Extract zap amount in satoshis from a zap event (kind 9735)
-fn extract_zap_amount(event: &Event) -> Option<u64> {
-    // Look for 'bolt11' tag first (use as_slice for zero-copy access)
-    if let Some(bolt11_tag) = event.tags.iter().find(|tag| {
-        tag.as_slice().first().map(|k| k.as_str() == "bolt11").unwrap_or(false)
-    }) {
-        if let Some(bolt11) = bolt11_tag.as_slice().get(1) {
-            // Parse bolt11 invoice to extract amount
-            // For now, try to extract from description tag as fallback
-            // Full bolt11 parsing would require additional dependency
-            if let Some(amount) = parse_bolt11_amount(bolt11.as_str()) {
-                return Some(amount);
+/// Why a zap receipt (kind 9735) was rejected
+#[derive(Debug, Clone, PartialEq)]
+pub enum ZapError {
+    MissingBolt11,
+    InvalidBolt11,
+    MissingDescription,
+    InvalidZapRequest(String),
+    InvalidSignature,
+    AmountMismatch { invoice_msats: u64, requested_msats: u64 },
+    RecipientMismatch,
+    /// Not signed by the recipient's LNURL server
+    ZapperMismatch,
+    EventMismatch,
+}
+
+impl std::fmt::Display for ZapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ZapError::MissingBolt11 => write!(f, "Zap receipt has no bolt11 invoice"),
+            ZapError::InvalidBolt11 => write!(f, "Zap receipt invoice has no valid amount"),
+            ZapError::MissingDescription => write!(f, "Zap receipt has no zap request"),
+            ZapError::InvalidZapRequest(e) => write!(f, "Invalid zap request: {}", e),
+            ZapError::InvalidSignature => write!(f, "Zap request signature is invalid"),
+            ZapError::AmountMismatch { invoice_msats, requested_msats } => write!(
+                f,
+                "Invoice amount {} msats doesn't match requested {} msats",
+                invoice_msats, requested_msats
+            ),
+            ZapError::RecipientMismatch => write!(f, "Zap recipient doesn't match"),
+            ZapError::ZapperMismatch => {
+                write!(f, "Zap receipt isn't signed by the recipient's LNURL server")
             }
+            ZapError::EventMismatch => write!(f, "Zapped event doesn't match the zap request"),
         }
     }
+}
 
-    // Fallback: check description tag for amount (use as_slice for zero-copy access)
-    if let Some(description_tag) = event.tags.iter().find(|tag| {
-        tag.as_slice().first().map(|k| k.as_str() == "description").unwrap_or(false)
-    }) {
-        if let Some(desc) = description_tag.as_slice().get(1) {
-            // Description contains the zap request which has amount
-            return parse_amount_from_description(desc.as_str());
+impl std::error::Error for ZapError {}
+
+/// First value of the tag named `name`
+fn tag_value<'a>(event: &'a Event, name: &str) -> Option<&'a str> {
+    event.tags.iter().find_map(|tag| {
+        let parts = tag.as_slice();
+        if parts.len() >= 2 && parts[0] == name {
+            Some(parts[1].as_str())
+        } else {
+            None
+        }
+    })
+}
+
+/// Verify a zap receipt (kind 9735) per NIP-57 and return its amount in msats
+///
+/// The embedded zap request (`description` tag) must be a validly signed kind
+/// 9734 event, the bolt11 amount must match the request's `amount` tag when it
+/// has one, and the receipt's `p` and `e` tags must match the request's.
+/// Anyone can publish a 9735, so unverified receipts must not be counted.
+pub fn verify_zap_receipt(event: &Event) -> Result<u64, ZapError> {
+    let bolt11 = tag_value(event, "bolt11").ok_or(ZapError::MissingBolt11)?;
+    let invoice_msats = parse_bolt11_amount(bolt11).ok_or(ZapError::InvalidBolt11)?;
+
+    let description = tag_value(event, "description").ok_or(ZapError::MissingDescription)?;
+    let zap_request = Event::from_json(description)
+        .map_err(|e| ZapError::InvalidZapRequest(e.to_string()))?;
+    if zap_request.kind != Kind::ZapRequest {
+        return Err(ZapError::InvalidZapRequest(format!("unexpected kind {}", zap_request.kind)));
+    }
+    zap_request.verify().map_err(|_| ZapError::InvalidSignature)?;
+
+    if let Some(requested) = tag_value(&zap_request, "amount") {
+        let requested_msats = requested
+            .parse::<u64>()
+            .map_err(|_| ZapError::InvalidZapRequest("invalid amount tag".to_string()))?;
+        if requested_msats != invoice_msats {
+            return Err(ZapError::AmountMismatch { invoice_msats, requested_msats });
         }
     }
 
-    None
+    match (tag_value(event, "p"), tag_value(&zap_request, "p")) {
+        (Some(receipt_p), Some(request_p)) if receipt_p == request_p => {}
+        _ => return Err(ZapError::RecipientMismatch),
+    }
+
+    if let Some(request_e) = tag_value(&zap_request, "e") {
+        if tag_value(event, "e") != Some(request_e) {
+            return Err(ZapError::EventMismatch);
+        }
+    }
+
+    Ok(invoice_msats)
 }
 
-/// Parse amount from bolt11 invoice string
-/// This is a simplified parser - a full implementation would use a bolt11 crate
-fn parse_bolt11_amount(_bolt11: &str) -> Option<u64> {
-    // TODO: Implement proper bolt11 parsing
-    // For now, return None and rely on description parsing
-    None
+/// Verify a zap receipt for a note by `author` and return its amount in msats
+///
+/// Like `verify_zap_receipt`, additionally requiring the zap to be addressed to
+/// the note's author and the receipt to be signed by `zapper`, the
+/// `nostrPubkey` of the author's LNURL server (see `zapper_pubkey`).
+pub fn verify_zap_receipt_for(event: &Event, author: &PublicKey, zapper: &PublicKey) -> Result<u64, ZapError> {
+    let msats = verify_zap_receipt(event)?;
+    if tag_value(event, "p") != Some(author.to_hex().as_str()) {
+        return Err(ZapError::RecipientMismatch);
+    }
+    if event.pubkey != *zapper {
+        return Err(ZapError::ZapperMismatch);
+    }
+    Ok(msats)
 }
 
-/// Parse amount from zap request description
-fn parse_amount_from_description(description: &str) -> Option<u64> {
-    // Try to parse the description as JSON (zap request) to extract amount
-    if let Ok(json) = serde_json::from_str::<serde_json::Value>(description) {
-        // NIP-57: Amount is in the tags array as ["amount", "millisats"]
-        if let Some(tags) = json.get("tags").and_then(|t| t.as_array()) {
-            for tag in tags {
-                if let Some(tag_vals) = tag.as_array() {
-                    if tag_vals.first().and_then(|v| v.as_str()) == Some("amount") {
-                        if let Some(amount_str) = tag_vals.get(1).and_then(|v| v.as_str()) {
-                            // Amount is in millisats, convert to sats
-                            if let Ok(millisats) = amount_str.parse::<u64>() {
-                                return Some(millisats / 1000);
-                            }
-                        }
-                    }
-                }
+/// Zapper pubkeys per recipient, None when they can't receive zaps
+static ZAPPER_PUBKEYS: OnceLock<Mutex<HashMap<PublicKey, Option<PublicKey>>>> = OnceLock::new();
+
+/// The pubkey `recipient`'s LNURL server signs zap receipts with (`nostrPubkey`)
+///
+/// Looked up from the recipient's lightning address once per session. None
+/// when they have no lightning address or their server doesn't do zaps;
+/// failed lookups aren't remembered.
+pub async fn zapper_pubkey(recipient: &PublicKey) -> Option<PublicKey> {
+    let cache = ZAPPER_PUBKEYS.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Some(zapper) = cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).get(recipient) {
+        return *zapper;
+    }
+
+    let profile = crate::stores::profiles::fetch_profile(recipient.to_hex()).await.ok()?;
+    let zapper = match profile.lud16.as_deref().filter(|lud16| !lud16.trim().is_empty()) {
+        Some(lud16) => match lnurl::get_lnurl_pay_info(Some(lud16), None).await {
+            Ok(info) => info.nostr_pubkey.and_then(|pk| PublicKey::from_hex(&pk).ok()),
+            Err(LnUrlError::FetchError(e)) => {
+                log::debug!("LNURL lookup for {} failed: {}", recipient, e);
+                return None;
             }
+            Err(_) => None,
+        },
+        None => None,
+    };
+
+    cache
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(*recipient, zapper);
+    zapper
+}
+
+/// Authors of the given notes, from the local database or else relays
+async fn note_authors(ids: Vec<EventId>) -> HashMap<EventId, PublicKey> {
+    let Some(client) = get_client() else {
+        return HashMap::new();
+    };
+
+    let mut authors: HashMap<EventId, PublicKey> = match client.database().query(Filter::new().ids(ids.clone())).await {
+        Ok(events) => events.into_iter().map(|event| (event.id, event.pubkey)).collect(),
+        Err(_) => HashMap::new(),
+    };
+
+    let missing: Vec<EventId> = ids.into_iter().filter(|id| !authors.contains_key(id)).collect();
+    if !missing.is_empty() {
+        if let Ok(events) = client.fetch_events(Filter::new().ids(missing), Duration::from_secs(5)).await {
+            authors.extend(events.into_iter().map(|event| (event.id, event.pubkey)));
         }
-        // Fallback: check for amount at root level (some implementations)
-        if let Some(amount) = json.get("amount") {
-            if let Some(amount_str) = amount.as_str() {
-                if let Ok(millisats) = amount_str.parse::<u64>() {
-                    return Some(millisats / 1000);
-                }
-            } else if let Some(amount_num) = amount.as_u64() {
-                return Some(amount_num / 1000);
+    }
+    authors
+}
+
+/// Amounts in msats of the zap receipts among `events` that verify with
+/// `verify_zap_receipt_for`, by receipt ID
+///
+/// Receipts for notes whose author can't be found are skipped.
+async fn verified_zap_amounts(events: &[Event]) -> HashMap<EventId, u64> {
+    let receipts: Vec<(&Event, EventId)> = events
+        .iter()
+        .filter(|event| event.kind == Kind::ZapReceipt)
+        .filter_map(|receipt| {
+            let note = EventId::from_hex(tag_value(receipt, "e")?).ok()?;
+            Some((receipt, note))
+        })
+        .collect();
+    if receipts.is_empty() {
+        return HashMap::new();
+    }
+
+    let mut note_ids: Vec<EventId> = receipts.iter().map(|(_, note)| *note).collect();
+    note_ids.sort();
+    note_ids.dedup();
+    let authors = note_authors(note_ids).await;
+
+    let mut recipients: Vec<PublicKey> = authors.values().copied().collect();
+    recipients.sort();
+    recipients.dedup();
+    let zappers: HashMap<PublicKey, PublicKey> = futures::future::join_all(
+        recipients
+            .into_iter()
+            .map(|recipient| async move { zapper_pubkey(&recipient).await.map(|zapper| (recipient, zapper)) }),
+    )
+    .await
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let mut amounts = HashMap::new();
+    for (receipt, note) in receipts {
+        let Some(author) = authors.get(&note) else {
+            continue;
+        };
+        let Some(zapper) = zappers.get(author) else {
+            continue;
+        };
+        // Spoofed receipts are discarded rather than counted
+        match verify_zap_receipt_for(receipt, author, zapper) {
+            Ok(msats) => {
+                amounts.insert(receipt.id, msats);
             }
+            Err(e) => log::debug!("Ignoring zap receipt {}: {}", receipt.id, e),
         }
     }
-    None
+    amounts
+}
+
+/// Parse the amount in msats from a bolt11 invoice's human-readable part
+///
+/// Format: `ln<currency><amount><multiplier>1<data>`, e.g. `lnbc210n1...` is
+/// 210 nano-BTC. Invoices without an amount return None.
+fn parse_bolt11_amount(bolt11: &str) -> Option<u64> {
+    let lower = bolt11.to_lowercase();
+    // The bech32 separator is the last '1'; the data part never contains one
+    let hrp = &lower[..lower.rfind('1')?];
+    let rest = hrp.strip_prefix("ln")?;
+
+    // Skip the currency prefix (bc, tb, bcrt, ...)
+    let amount_part = rest.trim_start_matches(|c: char| c.is_ascii_alphabetic());
+    let digits_end = amount_part
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(amount_part.len());
+    let amount: u64 = amount_part[..digits_end].parse().ok()?;
+
+    match &amount_part[digits_end..] {
+        "" => amount.checked_mul(100_000_000_000),
+        "m" => amount.checked_mul(100_000_000),
+        "u" => amount.checked_mul(100_000),
+        "n" => amount.checked_mul(100),
+        // A pico-BTC is a tenth of a msat; amounts that aren't whole msats are invalid
+        "p" if amount % 10 == 0 => Some(amount / 10),
+        _ => None,
+    }
 }
 
 /// Fetch interaction counts for a time range (useful for trending/popular feeds)
@@ -947,6 +1125,9 @@ pub async fn fetch_trending_interactions(
     // Use empty set to accept all event IDs (trending mode)
     let empty_filter = std::collections::HashSet::new();
 
+    let events: Vec<Event> = events.into_iter().collect();
+    let zap_amounts = verified_zap_amounts(&events).await;
+
     for event in events {
         let referenced_event_id = match extract_referenced_event(&event, &empty_filter) {
             Some(id) => id,
@@ -966,9 +1147,9 @@ pub async fn fetch_trending_interactions(
             },
             Kind::Repost => counts.reposts += 1,
             Kind::ZapReceipt => {
-                counts.zaps += 1;
-                if let Some(amount) = extract_zap_amount(&event) {
-                    counts.zap_amount_sats += amount;
+                if let Some(msats) = zap_amounts.get(&event.id) {
+                    counts.zaps += 1;
+                    counts.zap_amount_sats += msats / 1000;
                 }
             }
             _ => {}
@@ -981,6 +1162,7 @@ pub async fn fetch_trending_interactions(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use nostr_sdk::{EventBuilder, Keys, Tag};
 
     #[test]
     fn test_interaction_counts_default() {
@@ -992,10 +1174,99 @@ mod tests {
         assert_eq!(counts.zap_amount_sats, 0);
     }
 
+    fn zap_request(zapper: &Keys, recipient: &PublicKey, note: EventId, amount_msats: u64) -> Event {
+        EventBuilder::new(Kind::ZapRequest, "Great post!")
+            .tags([
+                Tag::public_key(*recipient),
+                Tag::event(note),
+                Tag::parse(["amount", amount_msats.to_string().as_str()]).unwrap(),
+                Tag::parse(["relays", "wss://relay.example"]).unwrap(),
+            ])
+            .sign_with_keys(zapper)
+            .unwrap()
+    }
+
+    fn zap_receipt(request: &Event, bolt11: &str) -> Event {
+        let recipient = tag_value(request, "p").unwrap();
+        let note = tag_value(request, "e").unwrap();
+        EventBuilder::new(Kind::ZapReceipt, "")
+            .tags([
+                Tag::parse(["p", recipient]).unwrap(),
+                Tag::parse(["e", note]).unwrap(),
+                Tag::parse(["bolt11", bolt11]).unwrap(),
+                Tag::parse(["description", request.as_json().as_str()]).unwrap(),
+            ])
+            .sign_with_keys(&Keys::generate())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_bolt11_amount() {
+        assert_eq!(parse_bolt11_amount("lnbc210n1pjtestdata"), Some(21_000));
+        assert_eq!(parse_bolt11_amount("lnbc2500u1pvjluez"), Some(250_000_000));
+        assert_eq!(parse_bolt11_amount("LNBC1M1PTEST"), Some(100_000_000));
+        assert_eq!(parse_bolt11_amount("lntb20p1test"), Some(2));
+        assert_eq!(parse_bolt11_amount("lnbcrt5n1test"), Some(500));
+        // No amount, sub-msat amount, not an invoice
+        assert_eq!(parse_bolt11_amount("lnbc1pvjluez"), None);
+        assert_eq!(parse_bolt11_amount("lnbc15p1test"), None);
+        assert_eq!(parse_bolt11_amount("hello"), None);
+    }
+
+    #[test]
+    fn test_verify_valid_zap_receipt() {
+        let author = Keys::generate();
+        let zapper = Keys::generate();
+        let note = EventBuilder::text_note("gm").sign_with_keys(&author).unwrap();
+        let request = zap_request(&zapper, &author.public_key(), note.id, 21_000);
+        let receipt = zap_receipt(&request, "lnbc210n1pjtestdata");
+
+        assert_eq!(verify_zap_receipt(&receipt), Ok(21_000));
+        assert_eq!(
+            verify_zap_receipt_for(&receipt, &author.public_key(), &receipt.pubkey),
+            Ok(21_000)
+        );
+        // Not addressed to this note's author
+        assert_eq!(
+            verify_zap_receipt_for(&receipt, &zapper.public_key(), &receipt.pubkey),
+            Err(ZapError::RecipientMismatch)
+        );
+        // Not signed by the author's LNURL server
+        assert_eq!(
+            verify_zap_receipt_for(&receipt, &author.public_key(), &zapper.public_key()),
+            Err(ZapError::ZapperMismatch)
+        );
+    }
+
+    #[test]
+    fn test_reject_zap_receipt_with_mismatched_amount() {
+        let author = Keys::generate();
+        let note = EventBuilder::text_note("gm").sign_with_keys(&author).unwrap();
+        let request = zap_request(&Keys::generate(), &author.public_key(), note.id, 21_000);
+        // The invoice is for 2100 sats while the request asked for 21
+        let receipt = zap_receipt(&request, "lnbc21u1pjtestdata");
+
+        assert_eq!(
+            verify_zap_receipt(&receipt),
+            Err(ZapError::AmountMismatch { invoice_msats: 2_100_000, requested_msats: 21_000 })
+        );
+    }
+
     #[test]
-    fn test_parse_zap_description() {
-        let desc = r#"{"amount":"5000","content":"Great post!"}"#;
-        let amount = parse_amount_from_description(desc);
-        assert_eq!(amount, Some(5)); // 5000 millisats = 5 sats
+    fn test_reject_zap_receipt_with_forged_request() {
+        let author = Keys::generate();
+        let note = EventBuilder::text_note("gm").sign_with_keys(&author).unwrap();
+        let request = zap_request(&Keys::generate(), &author.public_key(), note.id, 21_000);
+
+        // Tamper with the signed request's content
+        let mut json: serde_json::Value = serde_json::from_str(&request.as_json()).unwrap();
+        json["content"] = serde_json::Value::String("forged".to_string());
+        let forged = Event::from_json(json.to_string()).unwrap();
+        let receipt = zap_receipt(&forged, "lnbc210n1pjtestdata");
+
+        assert!(matches!(
+            verify_zap_receipt(&receipt),
+            Err(ZapError::InvalidSignature) | Err(ZapError::InvalidZapRequest(_))
+        ));
     }
 }
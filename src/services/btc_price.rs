@@ -0,0 +1,128 @@
+//! Bitcoin price for showing fiat equivalents of sat amounts
+//!
+//! Prices come from the CoinGecko simple price API and are cached per
+//! currency for a few minutes. Callers treat a failed fetch as "no fiat
+//! line" rather than an error the user has to deal with.
+
+use gloo_net::http::Request;
+use nostr_sdk::Timestamp;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+const PRICE_API_URL: &str = "https://api.coingecko.com/api/v3/simple/price";
+
+/// How long a fetched price is reused
+const PRICE_TTL_SECS: u64 = 300;
+
+const SATS_PER_BTC: f64 = 100_000_000.0;
+
+/// Currencies offered in settings: (code, symbol)
+pub const SUPPORTED_CURRENCIES: [(&str, &str); 7] = [
+    ("usd", "$"),
+    ("eur", "€"),
+    ("gbp", "£"),
+    ("jpy", "¥"),
+    ("cad", "CA$"),
+    ("aud", "A$"),
+    ("chf", "CHF "),
+];
+
+#[derive(Deserialize)]
+struct PriceResponse {
+    bitcoin: HashMap<String, f64>,
+}
+
+/// Cached prices by currency code: (price, fetched at)
+static PRICE_CACHE: OnceLock<Mutex<HashMap<String, (f64, u64)>>> = OnceLock::new();
+
+fn price_cache() -> &'static Mutex<HashMap<String, (f64, u64)>> {
+    PRICE_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Price of one bitcoin in `currency`, from cache when fresh
+pub async fn get_btc_price(currency: &str) -> Result<f64, String> {
+    let currency = currency.to_lowercase();
+    let now = Timestamp::now().as_secs();
+
+    if let Some((price, fetched_at)) = price_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&currency)
+    {
+        if now.saturating_sub(*fetched_at) < PRICE_TTL_SECS {
+            return Ok(*price);
+        }
+    }
+
+    let url = format!("{}?ids=bitcoin&vs_currencies={}", PRICE_API_URL, currency);
+    let response = Request::get(&url)
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .map_err(|e| format!("Price request failed: {}", e))?;
+
+    if !response.ok() {
+        return Err(format!("Price request failed: {}", response.status()));
+    }
+
+    let body: PriceResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse price: {}", e))?;
+    let price = body
+        .bitcoin
+        .get(&currency)
+        .copied()
+        .filter(|price| *price > 0.0)
+        .ok_or_else(|| format!("No bitcoin price for {}", currency))?;
+
+    price_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(currency, (price, now));
+    Ok(price)
+}
+
+/// Value of `sats` at `btc_price` per bitcoin
+pub fn sats_to_fiat(sats: u64, btc_price: f64) -> f64 {
+    sats as f64 / SATS_PER_BTC * btc_price
+}
+
+/// "≈ $0.63" style display of a fiat amount
+///
+/// Yen has no decimals; amounts that round to zero show as "< $0.01".
+pub fn format_fiat(amount: f64, currency: &str) -> String {
+    let currency = currency.to_lowercase();
+    let symbol = SUPPORTED_CURRENCIES
+        .iter()
+        .find(|(code, _)| *code == currency)
+        .map(|(_, symbol)| symbol.to_string())
+        .unwrap_or_else(|| format!("{} ", currency.to_uppercase()));
+    let decimals = if currency == "jpy" { 0 } else { 2 };
+    let smallest = 10f64.powi(-decimals);
+
+    if amount > 0.0 && amount < smallest {
+        format!("< {}{:.*}", symbol, decimals as usize, smallest)
+    } else {
+        format!("≈ {}{:.*}", symbol, decimals as usize, amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sats_to_fiat() {
+        assert_eq!(sats_to_fiat(100_000_000, 60_000.0), 60_000.0);
+        assert!((sats_to_fiat(1_000, 60_000.0) - 0.6).abs() < 1e-9);
+        assert_eq!(sats_to_fiat(0, 60_000.0), 0.0);
+
+        assert_eq!(format_fiat(sats_to_fiat(1_000, 60_000.0), "USD"), "≈ $0.60");
+        assert_eq!(format_fiat(sats_to_fiat(21, 60_000.0), "eur"), "≈ €0.01");
+        assert_eq!(format_fiat(sats_to_fiat(1, 60_000.0), "usd"), "< $0.01");
+        assert_eq!(format_fiat(sats_to_fiat(21_000, 9_000_000.0), "jpy"), "≈ ¥1890");
+        assert_eq!(format_fiat(12.5, "sek"), "≈ SEK 12.50");
+    }
+}
@@ -1,8 +1,12 @@
 use nostr_sdk::prelude::*;
 use dioxus::prelude::ReadableExt;
+use futures::future::join_all;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
 use std::time::Duration;
 
-use crate::stores::nostr_client::NOSTR_CLIENT;
+use crate::stores::nostr_client::{self, NOSTR_CLIENT};
+use crate::utils::search_query::SearchQuery;
 
 /// Result type for content search
 #[derive(Clone, Debug)]
@@ -25,174 +29,52 @@ impl Eq for ContentSearchResult {}
 
 /// Search for text notes (Kind 1) using NIP-50
 pub async fn search_text_notes(
-    query: &str,
+    query: &SearchQuery,
     limit: usize,
     contact_pubkeys: &[PublicKey],
 ) -> std::result::Result<Vec<ContentSearchResult>, String> {
-    if query.is_empty() {
-        return Ok(Vec::new());
-    }
-
-    let client_opt = (*NOSTR_CLIENT.read()).clone();
-    let client = match client_opt {
-        Some(c) => c,
-        None => return Err("Nostr client not initialized".to_string()),
-    };
-
-    log::debug!("Searching for text notes matching: {}", query);
-
-    // NIP-50 search for text notes
-    let filter = Filter::new()
-        .kind(Kind::TextNote)
-        .search(query)
-        .limit(limit);
-
-    match client.fetch_events(filter, Duration::from_secs(5)).await {
-        Ok(events) => {
-            log::debug!("Found {} text notes from relays", events.len());
-
-            let mut results: Vec<ContentSearchResult> = events
-                .into_iter()
-                .map(|event| {
-                    let is_from_contact = contact_pubkeys.contains(&event.pubkey);
-                    let relevance = calculate_relevance(&event, query, is_from_contact);
-
-                    ContentSearchResult {
-                        event,
-                        is_from_contact,
-                        relevance,
-                    }
-                })
-                .collect();
-
-            // Sort by relevance (descending), with contacts prioritized
-            results.sort_by(|a, b| b.relevance.cmp(&a.relevance));
-
-            log::debug!("Text note search for '{}' returned {} results", query, results.len());
-            Ok(results)
-        }
-        Err(e) => {
-            log::error!("Failed to search text notes: {}", e);
-            Err(format!("Failed to search text notes: {}", e))
-        }
-    }
+    run_search(query, &[Kind::TextNote], limit, contact_pubkeys, "text note").await
 }
 
 /// Search for long-form articles (Kind 30023) using NIP-50
 pub async fn search_articles(
-    query: &str,
+    query: &SearchQuery,
     limit: usize,
     contact_pubkeys: &[PublicKey],
 ) -> std::result::Result<Vec<ContentSearchResult>, String> {
-    if query.is_empty() {
-        return Ok(Vec::new());
-    }
-
-    let client_opt = (*NOSTR_CLIENT.read()).clone();
-    let client = match client_opt {
-        Some(c) => c,
-        None => return Err("Nostr client not initialized".to_string()),
-    };
-
-    log::debug!("Searching for articles matching: {}", query);
-
-    // NIP-50 search for long-form content (kind 30023)
-    let filter = Filter::new()
-        .kind(Kind::from(30023))
-        .search(query)
-        .limit(limit);
-
-    match client.fetch_events(filter, Duration::from_secs(5)).await {
-        Ok(events) => {
-            log::debug!("Found {} articles from relays", events.len());
-
-            let mut results: Vec<ContentSearchResult> = events
-                .into_iter()
-                .map(|event| {
-                    let is_from_contact = contact_pubkeys.contains(&event.pubkey);
-                    let relevance = calculate_relevance(&event, query, is_from_contact);
-
-                    ContentSearchResult {
-                        event,
-                        is_from_contact,
-                        relevance,
-                    }
-                })
-                .collect();
-
-            // Sort by relevance (descending)
-            results.sort_by(|a, b| b.relevance.cmp(&a.relevance));
-
-            log::debug!("Article search for '{}' returned {} results", query, results.len());
-            Ok(results)
-        }
-        Err(e) => {
-            log::error!("Failed to search articles: {}", e);
-            Err(format!("Failed to search articles: {}", e))
-        }
-    }
+    run_search(query, &[Kind::from(30023)], limit, contact_pubkeys, "article").await
 }
 
 /// Search for photos (Kind 20 - NIP-68) using NIP-50
 pub async fn search_photos(
-    query: &str,
+    query: &SearchQuery,
     limit: usize,
     contact_pubkeys: &[PublicKey],
 ) -> std::result::Result<Vec<ContentSearchResult>, String> {
-    if query.is_empty() {
-        return Ok(Vec::new());
-    }
-
-    let client_opt = (*NOSTR_CLIENT.read()).clone();
-    let client = match client_opt {
-        Some(c) => c,
-        None => return Err("Nostr client not initialized".to_string()),
-    };
-
-    log::debug!("Searching for photos matching: {}", query);
-
-    // NIP-50 search for kind 20 photo events (NIP-68)
-    let filter = Filter::new()
-        .kind(Kind::Custom(20))
-        .search(query)
-        .limit(limit);
-
-    match client.fetch_events(filter, Duration::from_secs(5)).await {
-        Ok(events) => {
-            log::debug!("Found {} photo events from relays", events.len());
-
-            let mut results: Vec<ContentSearchResult> = events
-                .into_iter()
-                .map(|event| {
-                    let is_from_contact = contact_pubkeys.contains(&event.pubkey);
-                    let relevance = calculate_relevance(&event, query, is_from_contact);
-
-                    ContentSearchResult {
-                        event,
-                        is_from_contact,
-                        relevance,
-                    }
-                })
-                .collect();
-
-            // Sort by relevance (descending)
-            results.sort_by(|a, b| b.relevance.cmp(&a.relevance));
-
-            log::debug!("Photo search for '{}' returned {} results", query, results.len());
-            Ok(results)
-        }
-        Err(e) => {
-            log::error!("Failed to search photos: {}", e);
-            Err(format!("Failed to search photos: {}", e))
-        }
-    }
+    run_search(query, &[Kind::Custom(20)], limit, contact_pubkeys, "photo").await
 }
 
 /// Search for videos (Kind 21 & 22 - NIP-71) using NIP-50
 pub async fn search_videos(
-    query: &str,
+    query: &SearchQuery,
+    limit: usize,
+    contact_pubkeys: &[PublicKey],
+) -> std::result::Result<Vec<ContentSearchResult>, String> {
+    // Kind 21 (landscape) and 22 (portrait) video events
+    run_search(query, &[Kind::Custom(21), Kind::Custom(22)], limit, contact_pubkeys, "video").await
+}
+
+/// Fetch `kinds` matching the query and rank them with `rank_search_results`
+///
+/// Free-text queries only go to relays that advertise NIP-50; others would
+/// ignore the `search` field and answer with their latest events. Without
+/// any, the query goes to [`DEFAULT_SEARCH_RELAY`].
+async fn run_search(
+    query: &SearchQuery,
+    kinds: &[Kind],
     limit: usize,
     contact_pubkeys: &[PublicKey],
+    what: &str,
 ) -> std::result::Result<Vec<ContentSearchResult>, String> {
     if query.is_empty() {
         return Ok(Vec::new());
@@ -204,43 +86,49 @@ pub async fn search_videos(
         None => return Err("Nostr client not initialized".to_string()),
     };
 
-    log::debug!("Searching for videos matching: {}", query);
-
-    // NIP-50 search for kind 21 (landscape) and 22 (portrait) video events (NIP-71)
-    let filter = Filter::new()
-        .kinds([Kind::Custom(21), Kind::Custom(22)])
-        .search(query)
-        .limit(limit);
-
-    match client.fetch_events(filter, Duration::from_secs(5)).await {
-        Ok(events) => {
-            log::debug!("Found {} video events from relays", events.len());
-
-            let mut results: Vec<ContentSearchResult> = events
-                .into_iter()
-                .map(|event| {
-                    let is_from_contact = contact_pubkeys.contains(&event.pubkey);
-                    let relevance = calculate_relevance(&event, query, is_from_contact);
-
-                    ContentSearchResult {
-                        event,
-                        is_from_contact,
-                        relevance,
-                    }
-                })
-                .collect();
-
-            // Sort by relevance (descending)
-            results.sort_by(|a, b| b.relevance.cmp(&a.relevance));
-
-            log::debug!("Video search for '{}' returned {} results", query, results.len());
-            Ok(results)
+    log::debug!("Searching for {}s matching: {:?}", what, query);
+
+    let filter = query.to_filter(kinds, limit);
+    let fetched = if query.text.is_empty() {
+        client.fetch_events(filter, Duration::from_secs(5)).await
+    } else {
+        let relays = search_relays(&client).await;
+        if relays.is_empty() {
+            log::debug!("No NIP-50 relays connected, searching {}", DEFAULT_SEARCH_RELAY);
+            let default_relay = vec![DEFAULT_SEARCH_RELAY.to_string()];
+            let temp = nostr_client::add_temp_relays(&client, &default_relay).await;
+            let fetched = client.fetch_events_from(default_relay, filter, Duration::from_secs(5)).await;
+            temp.release().await;
+            fetched
+        } else {
+            log::debug!("Searching {} NIP-50 relays: {:?}", relays.len(), relays);
+            client.fetch_events_from(relays, filter, Duration::from_secs(5)).await
         }
+    };
+
+    let events: Vec<Event> = match fetched {
+        Ok(events) => events.into_iter().collect(),
         Err(e) => {
-            log::error!("Failed to search videos: {}", e);
-            Err(format!("Failed to search videos: {}", e))
+            log::error!("Failed to search {}s: {}", what, e);
+            return Err(format!("Failed to search {}s: {}", what, e));
         }
-    }
+    };
+    log::debug!("Found {} {} events from relays", events.len(), what);
+
+    let following: HashSet<PublicKey> = contact_pubkeys.iter().copied().collect();
+    let terms = search_terms(&query.text);
+    let now = Timestamp::now();
+    let results: Vec<ContentSearchResult> = rank_search_results_at(&query.text, events, &following, now)
+        .into_iter()
+        .map(|event| ContentSearchResult {
+            is_from_contact: following.contains(&event.pubkey),
+            relevance: search_score(&event, &terms, &following, now),
+            event,
+        })
+        .collect();
+
+    log::debug!("{} search for '{}' returned {} results", what, query.text, results.len());
+    Ok(results)
 }
 
 /// Get user's contact list public keys
@@ -266,43 +154,314 @@ pub async fn get_contact_pubkeys() -> Vec<PublicKey> {
     }
 }
 
-/// Calculate relevance score for a content search result
-fn calculate_relevance(event: &Event, query: &str, is_from_contact: bool) -> u32 {
-    let query_lower = query.to_lowercase();
-    let content_lower = event.content.to_lowercase();
+/// Connected relays that advertise NIP-50 search in their NIP-11 document
+async fn search_relays(client: &Client) -> Vec<String> {
+    let urls: Vec<String> = client.relays().await.keys().map(|url| url.to_string()).collect();
+    let supported = join_all(urls.iter().map(|url| relay_supports_search(url))).await;
+    urls.into_iter()
+        .zip(supported)
+        .filter_map(|(url, supports)| supports.then_some(url))
+        .collect()
+}
+
+/// Searched when none of the user's relays support NIP-50
+const DEFAULT_SEARCH_RELAY: &str = "wss://relay.nostr.band";
+
+/// How long to wait for a relay's NIP-11 document
+const RELAY_INFO_TIMEOUT_MS: u32 = 3000;
+
+/// How long a relay whose NIP-11 document couldn't be fetched is skipped
+const RELAY_INFO_RETRY_SECS: u64 = 600;
+
+/// What a relay's NIP-11 document said about NIP-50
+#[derive(Clone, Copy, Debug)]
+enum SearchSupport {
+    Known(bool),
+    /// The document couldn't be fetched at this time (Unix seconds)
+    Unreachable(u64),
+}
+
+/// NIP-50 support by relay URL
+static SEARCH_SUPPORT_CACHE: OnceLock<Mutex<HashMap<String, SearchSupport>>> = OnceLock::new();
+
+fn search_support_cache() -> &'static Mutex<HashMap<String, SearchSupport>> {
+    SEARCH_SUPPORT_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+async fn relay_supports_search(relay_url: &str) -> bool {
+    let now = Timestamp::now().as_secs();
+    let cached = search_support_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(relay_url)
+        .copied();
+    match cached {
+        Some(SearchSupport::Known(supports)) => return supports,
+        Some(SearchSupport::Unreachable(at)) if now < at + RELAY_INFO_RETRY_SECS => return false,
+        _ => {}
+    }
+
+    let (support, supports) = match fetch_relay_info(relay_url).await {
+        Ok(info) => {
+            let supports = supports_nip(&info, 50);
+            (SearchSupport::Known(supports), supports)
+        }
+        Err(e) => {
+            // Retried after a while: the relay may answer then
+            log::debug!("No NIP-11 document for {}: {}", relay_url, e);
+            (SearchSupport::Unreachable(now), false)
+        }
+    };
+    search_support_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(relay_url.to_string(), support);
+    supports
+}
 
-    let mut relevance = 0u32;
+async fn fetch_relay_info(relay_url: &str) -> std::result::Result<serde_json::Value, String> {
+    use futures::future::{select, Either};
+    use futures::pin_mut;
+    use gloo_timers::future::TimeoutFuture;
+
+    let http_url = relay_info_url(relay_url).ok_or("Not a websocket relay URL")?;
+    let request = async {
+        let response = gloo_net::http::Request::get(&http_url)
+            .header("Accept", "application/nostr+json")
+            .send()
+            .await
+            .map_err(|e| format!("Failed to fetch relay info: {}", e))?;
+        if !response.ok() {
+            return Err(format!("Failed to fetch relay info: HTTP {}", response.status()));
+        }
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Invalid relay info: {}", e))
+    };
+    let timeout = TimeoutFuture::new(RELAY_INFO_TIMEOUT_MS);
+    pin_mut!(request);
+    pin_mut!(timeout);
 
-    // Boost content from contacts significantly
-    if is_from_contact {
-        relevance += 10000;
+    match select(request, timeout).await {
+        Either::Left((result, _)) => result,
+        Either::Right(_) => Err("Timed out fetching relay info".to_string()),
     }
+}
 
-    // Exact match in content
-    if content_lower.contains(&query_lower) {
-        relevance += 500;
+/// The HTTP(S) URL a relay serves its NIP-11 document on
+fn relay_info_url(relay_url: &str) -> Option<String> {
+    if let Some(rest) = relay_url.strip_prefix("wss://") {
+        Some(format!("https://{}", rest))
+    } else {
+        relay_url.strip_prefix("ws://").map(|rest| format!("http://{}", rest))
     }
+}
 
-    // Check if query appears at the start of content (higher relevance)
-    if content_lower.starts_with(&query_lower) {
-        relevance += 300;
+/// Whether a NIP-11 document lists `nip` in `supported_nips`
+///
+/// Some relays list NIPs as strings rather than numbers; both count.
+fn supports_nip(info: &serde_json::Value, nip: u64) -> bool {
+    info.get("supported_nips")
+        .and_then(|nips| nips.as_array())
+        .is_some_and(|nips| {
+            nips.iter().any(|n| {
+                n.as_u64() == Some(nip) || n.as_str().and_then(|s| s.trim().parse().ok()) == Some(nip)
+            })
+        })
+}
+
+/// Most times one term is counted per event, so repeating a word doesn't win
+const MAX_TERM_HITS: u32 = 5;
+
+/// Lowercase words of a search query, without duplicates
+fn search_terms(query: &str) -> Vec<String> {
+    let mut terms: Vec<String> = Vec::new();
+    for word in query.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+        let word = word.to_lowercase();
+        if !terms.contains(&word) {
+            terms.push(word);
+        }
     }
+    terms
+}
 
-    // Boost recent content (decay over time)
-    let now = Timestamp::now();
-    let age_seconds = now.as_secs().saturating_sub(event.created_at.as_secs());
-    let age_days = age_seconds / 86400;
-
-    if age_days < 1 {
-        relevance += 200;
-    } else if age_days < 7 {
-        relevance += 100;
-    } else if age_days < 30 {
-        relevance += 50;
+/// Relevance of an event for `terms`: term frequency, recency and following
+fn search_score(
+    event: &Event,
+    terms: &[String],
+    following: &HashSet<PublicKey>,
+    now: Timestamp,
+) -> u32 {
+    let content = event.content.to_lowercase();
+    let words: Vec<&str> = content
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let mut score = 0u32;
+
+    // Term frequency, capped per term
+    let mut matched_terms = 0;
+    for term in terms {
+        let hits = words.iter().filter(|word| **word == term.as_str()).count() as u32;
+        if hits > 0 {
+            matched_terms += 1;
+            score += 100 * hits.min(MAX_TERM_HITS);
+        }
     }
+    // Events matching every term beat ones matching a single term repeatedly
+    if terms.len() > 1 && matched_terms == terms.len() {
+        score += 300;
+    }
+    // The query words appearing together, in order
+    if terms.len() > 1 && content.contains(&terms.join(" ")) {
+        score += 200;
+    }
+
+    // Recency: 300 points when new, halving every 30 days
+    let age_days = now.as_secs().saturating_sub(event.created_at.as_secs()) / 86400;
+    score += (300 * 30 / (30 + age_days)) as u32;
+
+    // Authors the user follows
+    if following.contains(&event.pubkey) {
+        score += 500;
+    }
+
+    score
+}
+
+/// Order search results by relevance, best first
+///
+/// Relays return NIP-50 matches in no particular order, so results are scored
+/// client-side by how often the query terms appear in the content, how
+/// recent the event is and whether the author is followed. Ties go to the
+/// newer event.
+pub fn rank_search_results(
+    query: &str,
+    events: Vec<Event>,
+    following_set: &HashSet<PublicKey>,
+) -> Vec<Event> {
+    rank_search_results_at(query, events, following_set, Timestamp::now())
+}
+
+fn rank_search_results_at(
+    query: &str,
+    events: Vec<Event>,
+    following_set: &HashSet<PublicKey>,
+    now: Timestamp,
+) -> Vec<Event> {
+    let terms = search_terms(query);
+    let mut seen = HashSet::new();
+    let mut scored: Vec<(u32, Event)> = events
+        .into_iter()
+        .filter(|event| seen.insert(event.id))
+        .map(|event| (search_score(&event, &terms, following_set, now), event))
+        .collect();
+    scored.sort_by(|(score_a, a), (score_b, b)| {
+        score_b
+            .cmp(score_a)
+            .then_with(|| b.created_at.cmp(&a.created_at))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    scored.into_iter().map(|(_, event)| event).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Boost content with more reactions (if we can detect them)
-    // This is a simplification - in a real implementation, we'd fetch reaction counts
+    const DAY: u64 = 86400;
+    const NOW: u64 = 1_700_000_000;
 
-    relevance
+    fn note(keys: &Keys, content: &str, age_days: u64) -> Event {
+        EventBuilder::text_note(content)
+            .custom_created_at(Timestamp::from(NOW - age_days * DAY))
+            .sign_with_keys(keys)
+            .unwrap()
+    }
+
+    fn contents(events: &[Event]) -> Vec<&str> {
+        events.iter().map(|e| e.content.as_str()).collect()
+    }
+
+    #[test]
+    fn test_rank_by_term_frequency() {
+        let keys = Keys::generate();
+        let corpus = vec![
+            note(&keys, "Lightning is fast", 2),
+            note(&keys, "Lightning nodes: running a lightning node on lightning", 2),
+            note(&keys, "Had coffee this morning", 2),
+            note(&keys, "lightning and lightning", 2),
+        ];
+
+        let ranked = rank_search_results_at("lightning", corpus, &HashSet::new(), Timestamp::from(NOW));
+        assert_eq!(
+            contents(&ranked),
+            vec![
+                "Lightning nodes: running a lightning node on lightning",
+                "lightning and lightning",
+                "Lightning is fast",
+                "Had coffee this morning",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rank_prefers_all_terms_and_recent() {
+        let keys = Keys::generate();
+        let corpus = vec![
+            note(&keys, "nostr nostr nostr", 1),
+            note(&keys, "old nostr relay notes", 400),
+            note(&keys, "a new nostr relay", 1),
+        ];
+
+        let ranked = rank_search_results_at("nostr relay", corpus, &HashSet::new(), Timestamp::from(NOW));
+        assert_eq!(
+            contents(&ranked),
+            vec!["a new nostr relay", "old nostr relay notes", "nostr nostr nostr"]
+        );
+    }
+
+    #[test]
+    fn test_rank_boosts_followed_authors() {
+        let stranger = Keys::generate();
+        let friend = Keys::generate();
+        let corpus = vec![
+            note(&stranger, "bitcoin meetup tonight", 0),
+            note(&friend, "bitcoin meetup tonight", 0),
+            note(&stranger, "bitcoin bitcoin bitcoin meetup", 0),
+        ];
+        let following: HashSet<PublicKey> = [friend.public_key()].into_iter().collect();
+
+        let ranked = rank_search_results_at("bitcoin", corpus, &following, Timestamp::from(NOW));
+        assert_eq!(ranked[0].pubkey, friend.public_key());
+        assert_eq!(ranked[1].content, "bitcoin bitcoin bitcoin meetup");
+    }
+
+    #[test]
+    fn test_rank_dedupes_and_breaks_ties_by_date() {
+        let keys = Keys::generate();
+        let older = note(&keys, "gm", 3);
+        let newer = note(&keys, "gm", 2);
+
+        let ranked = rank_search_results_at("gm", vec![older.clone(), newer.clone(), older.clone()], &HashSet::new(), Timestamp::from(NOW));
+        assert_eq!(ranked, vec![newer, older]);
+    }
+
+    #[test]
+    fn test_supports_nip() {
+        let info: serde_json::Value =
+            serde_json::from_str(r#"{"name":"search","supported_nips":[1,11,50]}"#).unwrap();
+        assert!(supports_nip(&info, 50));
+        let info: serde_json::Value = serde_json::from_str(r#"{"supported_nips":["1","50"]}"#).unwrap();
+        assert!(supports_nip(&info, 50));
+        let info: serde_json::Value = serde_json::from_str(r#"{"supported_nips":[1,2,9,11]}"#).unwrap();
+        assert!(!supports_nip(&info, 50));
+        assert!(!supports_nip(&serde_json::json!({"name": "no nips"}), 50));
+
+        assert_eq!(relay_info_url("wss://relay.nostr.band/"), Some("https://relay.nostr.band/".to_string()));
+        assert_eq!(relay_info_url("ws://localhost:7777"), Some("http://localhost:7777".to_string()));
+        assert_eq!(relay_info_url("https://example.com"), None);
+    }
 }
@@ -1,4 +1,4 @@
-use nostr_sdk::{Event, EventBuilder, EventId, PublicKey, RelayUrl, JsonUtil};
+use nostr_sdk::{Event, EventBuilder, EventId, Keys, PublicKey, RelayUrl, JsonUtil, Tag, TagKind};
 use nostr_sdk::nips::nip01::Coordinate;
 use nostr_sdk::nips::nip57::ZapRequestData;
 use serde::{Deserialize, Serialize};
@@ -152,6 +152,17 @@ pub fn create_zap_request_unsigned(
     EventBuilder::public_zap_request(data)
 }
 
+/// Sign a zap request anonymously (NIP-57)
+///
+/// A throwaway key signs it and an empty `anon` tag marks it, so nothing in
+/// the request or the resulting zap receipt links back to the sender.
+pub fn sign_anonymous_zap_request(builder: EventBuilder) -> Result<Event, String> {
+    builder
+        .tag(Tag::custom(TagKind::Custom("anon".into()), Vec::<String>::new()))
+        .sign_with_keys(&Keys::generate())
+        .map_err(|e| format!("Failed to sign anonymous zap request: {}", e))
+}
+
 /// Request a zap invoice from LNURL callback
 pub async fn request_zap_invoice(
     callback_url: &str,
@@ -204,3 +215,50 @@ pub async fn prepare_zap(
 
     Ok((pay_info, amount_msats))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::Kind;
+
+    fn zap_request_builder(recipient: PublicKey, event_id: EventId) -> EventBuilder {
+        create_zap_request_unsigned(
+            recipient,
+            vec![RelayUrl::parse("wss://relay.example.com").unwrap()],
+            21_000,
+            Some("Great post".to_string()),
+            Some(event_id),
+            None,
+        )
+    }
+
+    fn has_tag(event: &Event, name: &str) -> bool {
+        event.tags.iter().any(|tag| tag.as_slice().first().map(String::as_str) == Some(name))
+    }
+
+    #[test]
+    fn test_anonymous_zap_request_hides_sender() {
+        let sender = Keys::generate();
+        let recipient = Keys::generate().public_key();
+        let event_id = EventId::all_zeros();
+
+        let standard = zap_request_builder(recipient, event_id).sign_with_keys(&sender).unwrap();
+        assert_eq!(standard.kind, Kind::ZapRequest);
+        assert_eq!(standard.pubkey, sender.public_key());
+        assert!(!has_tag(&standard, "anon"));
+
+        let anonymous = sign_anonymous_zap_request(zap_request_builder(recipient, event_id)).unwrap();
+        assert_eq!(anonymous.kind, Kind::ZapRequest);
+        assert_ne!(anonymous.pubkey, sender.public_key());
+        assert!(anonymous.verify().is_ok());
+        let anon_tag = anonymous.tags.iter().find(|tag| tag.as_slice()[0] == "anon").unwrap();
+        assert_eq!(anon_tag.as_slice().len(), 1);
+
+        // Still zaps the same recipient and note, for the same amount
+        for name in ["p", "e", "amount", "relays"] {
+            assert!(has_tag(&anonymous, name), "missing {} tag", name);
+        }
+        assert_eq!(anonymous.content, standard.content);
+        assert!(!anonymous.tags.iter().any(|tag| tag.as_slice().contains(&sender.public_key().to_hex())));
+    }
+}
@@ -1,4 +1,7 @@
-use nostr_sdk::{EventId, Filter, Kind};
+use std::time::Duration;
+
+use nostr_sdk::{Event, EventId, Filter, Kind, PublicKey, Timestamp};
+use std::collections::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
@@ -49,6 +52,55 @@ pub struct TrendingStats {
     pub zaps: Option<u32>,
 }
 
+/// Shortest and longest trending window offered in settings
+pub const MIN_WINDOW_HOURS: u32 = 1;
+pub const MAX_WINDOW_HOURS: u32 = 168;
+
+/// How trending notes are looked up and ranked
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrendingConfig {
+    /// How far back to look for trending notes
+    pub window_hours: u32,
+    pub reaction_weight: f64,
+    pub repost_weight: f64,
+    pub zap_weight: f64,
+}
+
+impl Default for TrendingConfig {
+    fn default() -> Self {
+        Self {
+            window_hours: 24,
+            reaction_weight: 1.0,
+            repost_weight: 2.0,
+            zap_weight: 3.0,
+        }
+    }
+}
+
+impl TrendingConfig {
+    /// Window in hours, clamped to the supported range
+    pub fn window_hours(&self) -> u32 {
+        self.window_hours.clamp(MIN_WINDOW_HOURS, MAX_WINDOW_HOURS)
+    }
+}
+
+/// Trending score of a note: its reactions, reposts and zaps, weighted
+pub fn score_note(interactions: &TrendingStats, config: &TrendingConfig) -> f64 {
+    let count = |value: Option<u32>| f64::from(value.unwrap_or(0));
+    count(interactions.reactions) * config.reaction_weight
+        + count(interactions.reposts) * config.repost_weight
+        + count(interactions.zaps) * config.zap_weight
+}
+
+/// Sort notes by score, highest first; ties keep their original order
+pub fn rank_trending_notes(notes: &mut [TrendingNote], config: &TrendingConfig) {
+    let score = |note: &TrendingNote| {
+        note.stats.as_ref().map(|stats| score_note(stats, config)).unwrap_or(0.0)
+    };
+    notes.sort_by(|a, b| score(b).total_cmp(&score(a)));
+}
+
 /// Response item from nostr.wine trending API
 #[derive(Debug, Clone, Deserialize)]
 struct NostrWineTrendingItem {
@@ -62,20 +114,24 @@ struct NostrWineTrendingItem {
 }
 
 /// Fetch trending notes from nostr.wine API
-/// Returns the top trending posts ordered by reactions
+/// Returns the top trending posts ranked by `score_note`
 ///
 /// Parameters:
 /// - limit: Number of events to return (1-200, default 10)
+/// - config: Lookback window and ranking weights
 ///
-/// Note: Hours is fixed at 24 and order is fixed to "reactions" for best engagement signal.
-pub async fn get_trending_notes(limit: Option<usize>) -> Result<Vec<TrendingNote>, String> {
+/// Note: nostr.wine is asked to order by reactions; the weights re-rank what it returns.
+pub async fn get_trending_notes(
+    limit: Option<usize>,
+    config: &TrendingConfig,
+) -> Result<Vec<TrendingNote>, String> {
     let limit = limit.unwrap_or(10).clamp(1, 200);
+    let window_hours = config.window_hours();
 
     // Fetch trending event IDs from nostr.wine
-    // Use 24 hours lookback and order by reactions for best engagement signal
     let url = format!(
-        "{}/trending?limit={}&hours=24&order=reactions",
-        NOSTR_WINE_API, limit
+        "{}/trending?limit={}&hours={}&order=reactions",
+        NOSTR_WINE_API, limit, window_hours
     );
 
     log::info!("Fetching trending from nostr.wine: {}", url);
@@ -145,12 +201,14 @@ pub async fn get_trending_notes(limit: Option<usize>) -> Result<Vec<TrendingNote
         .filter_map(|item| EventId::from_hex(&item.event_id).ok())
         .collect();
 
+    let since = Timestamp::now() - Duration::from_secs(u64::from(window_hours) * 3600);
     let filter = Filter::new()
         .ids(event_ids.clone())
-        .kind(Kind::TextNote);
+        .kind(Kind::TextNote)
+        .since(since);
 
     let events = client
-        .fetch_events(filter, std::time::Duration::from_secs(10))
+        .fetch_events(filter, Duration::from_secs(10))
         .await
         .map_err(|e| format!("Failed to fetch events: {}", e))?;
 
@@ -211,6 +269,8 @@ pub async fn get_trending_notes(limit: Option<usize>) -> Result<Vec<TrendingNote
         );
     }
 
+    rank_trending_notes(&mut trending_notes, config);
+
     log::info!("Built {} trending notes from nostr.wine", trending_notes.len());
     Ok(trending_notes)
 }
@@ -247,3 +307,187 @@ pub fn truncate_content(content: &str, max_length: usize) -> String {
         format!("{}...", content[..truncate_at].trim())
     }
 }
+
+/// How many recent notes are sampled for trending hashtags
+const HASHTAG_SAMPLE_SIZE: usize = 500;
+
+/// Fewest authors a hashtag needs before it can trend
+pub const MIN_HASHTAG_AUTHORS: usize = 3;
+
+/// How many trending hashtags are shown
+pub const TRENDING_HASHTAG_LIMIT: usize = 12;
+
+/// Lowercase a `t` tag value and drop a leading `#`; None when nothing is left
+pub fn normalize_hashtag(tag: &str) -> Option<String> {
+    let tag = tag.trim().trim_start_matches('#').trim().to_lowercase();
+    (!tag.is_empty()).then_some(tag)
+}
+
+/// Count `t` tags across notes, most used first
+///
+/// Case variants are one tag. Each author counts once per tag, so one
+/// account posting the same tag repeatedly can't make it trend, and tags
+/// from fewer than `min_authors` authors are dropped.
+pub fn count_hashtags(events: &[Event], min_authors: usize, limit: usize) -> Vec<(String, usize)> {
+    let mut authors: HashMap<String, HashSet<PublicKey>> = HashMap::new();
+    for event in events {
+        for tag in event.tags.hashtags() {
+            if let Some(tag) = normalize_hashtag(tag) {
+                authors.entry(tag).or_default().insert(event.pubkey);
+            }
+        }
+    }
+
+    let mut counts: Vec<(String, usize)> = authors
+        .into_iter()
+        .map(|(tag, authors)| (tag, authors.len()))
+        .filter(|(_, count)| *count >= min_authors.max(1))
+        .collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.truncate(limit);
+    counts
+}
+
+/// Hashtags trending in notes from the last `window_hours`, with how many
+/// authors used each
+///
+/// Samples recent notes from the connected relays; empty if none could be
+/// fetched.
+pub async fn get_trending_hashtags(window_hours: u32) -> Vec<(String, usize)> {
+    let Some(client) = get_client() else {
+        return Vec::new();
+    };
+    let hours = window_hours.clamp(MIN_WINDOW_HOURS, MAX_WINDOW_HOURS) as u64;
+    let filter = Filter::new()
+        .kind(Kind::TextNote)
+        .since(Timestamp::now() - Duration::from_secs(hours * 3600))
+        .limit(HASHTAG_SAMPLE_SIZE);
+
+    match client.fetch_events(filter, Duration::from_secs(8)).await {
+        Ok(events) => {
+            let events: Vec<Event> = events.into_iter().collect();
+            count_hashtags(&events, MIN_HASHTAG_AUTHORS, TRENDING_HASHTAG_LIMIT)
+        }
+        Err(e) => {
+            log::warn!("Failed to sample notes for trending hashtags: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn note(id: &str, reactions: u32, reposts: u32, zaps: u32) -> TrendingNote {
+        TrendingNote {
+            event: TrendingEvent {
+                id: id.to_string(),
+                pubkey: String::new(),
+                created_at: 0,
+                kind: 1,
+                tags: Vec::new(),
+                content: String::new(),
+                sig: String::new(),
+            },
+            author: TrendingAuthor { pubkey: String::new() },
+            profile: None,
+            stats: Some(TrendingStats {
+                replies: Some(0),
+                reactions: Some(reactions),
+                reposts: Some(reposts),
+                zaps: Some(zaps),
+            }),
+        }
+    }
+
+    fn ranked_ids(mut notes: Vec<TrendingNote>, config: &TrendingConfig) -> Vec<String> {
+        rank_trending_notes(&mut notes, config);
+        notes.into_iter().map(|note| note.event.id).collect()
+    }
+
+    fn sample_notes() -> Vec<TrendingNote> {
+        vec![
+            note("liked", 30, 0, 0),
+            note("reposted", 5, 10, 0),
+            note("zapped", 2, 1, 8),
+        ]
+    }
+
+    #[test]
+    fn test_score_note() {
+        let config = TrendingConfig::default();
+        let stats = TrendingStats { replies: Some(100), reactions: Some(4), reposts: Some(2), zaps: None };
+        // Replies and missing counts don't contribute
+        assert_eq!(score_note(&stats, &config), 4.0 * 1.0 + 2.0 * 2.0);
+    }
+
+    #[test]
+    fn test_weights_reorder_notes() {
+        let reactions_only = TrendingConfig {
+            reaction_weight: 1.0,
+            repost_weight: 0.0,
+            zap_weight: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(ranked_ids(sample_notes(), &reactions_only), ["liked", "reposted", "zapped"]);
+
+        let reposts_heavy = TrendingConfig { repost_weight: 5.0, ..reactions_only.clone() };
+        assert_eq!(ranked_ids(sample_notes(), &reposts_heavy), ["reposted", "liked", "zapped"]);
+
+        let zaps_heavy = TrendingConfig { zap_weight: 10.0, ..reactions_only };
+        assert_eq!(ranked_ids(sample_notes(), &zaps_heavy), ["zapped", "liked", "reposted"]);
+    }
+
+    #[test]
+    fn test_ties_keep_original_order() {
+        let notes = vec![note("first", 1, 1, 0), note("second", 3, 0, 0), note("third", 0, 0, 1)];
+        let config = TrendingConfig::default();
+        assert_eq!(ranked_ids(notes, &config), ["first", "second", "third"]);
+    }
+
+    #[test]
+    fn test_window_hours_clamped() {
+        let config = TrendingConfig { window_hours: 0, ..Default::default() };
+        assert_eq!(config.window_hours(), MIN_WINDOW_HOURS);
+        let config = TrendingConfig { window_hours: 10_000, ..Default::default() };
+        assert_eq!(config.window_hours(), MAX_WINDOW_HOURS);
+    }
+
+    #[test]
+    fn test_count_hashtags_normalizes_and_filters_noise() {
+        use nostr_sdk::{EventBuilder, Keys, Tag};
+
+        let note = |keys: &Keys, tags: &[&str]| {
+            EventBuilder::text_note("gm")
+                .tags(tags.iter().map(|t| Tag::parse(["t", *t]).unwrap()))
+                .sign_with_keys(keys)
+                .unwrap()
+        };
+        let (alice, bob, carol) = (Keys::generate(), Keys::generate(), Keys::generate());
+        let events = vec![
+            note(&alice, &["Bitcoin", "nostr"]),
+            note(&bob, &["bitcoin", "#Nostr", "art"]),
+            note(&carol, &["BITCOIN", "nostr"]),
+            // Repeats from one author count once
+            note(&alice, &["spam", "bitcoin"]),
+            note(&alice, &["spam"]),
+            note(&alice, &["spam"]),
+            note(&bob, &["  ", "#"]),
+        ];
+
+        assert_eq!(
+            count_hashtags(&events, 2, 10),
+            vec![("bitcoin".to_string(), 3), ("nostr".to_string(), 3)]
+        );
+        assert_eq!(count_hashtags(&events, 3, 1), vec![("bitcoin".to_string(), 3)]);
+
+        let all = count_hashtags(&events, 1, 10);
+        assert!(all.contains(&("spam".to_string(), 1)));
+        assert!(all.contains(&("art".to_string(), 1)));
+        assert_eq!(all.len(), 4);
+
+        assert_eq!(normalize_hashtag(" #Zaps "), Some("zaps".to_string()));
+        assert_eq!(normalize_hashtag("#"), None);
+    }
+}
@@ -0,0 +1,191 @@
+//! Activity Digest Store - "While you were away" summary
+//!
+//! Tracks when the user was last active and summarizes what happened since then
+//! from events already in the local database (filled by the notifications
+//! subscription and feed loads) and the loaded DM conversations. Nothing is
+//! fetched from relays to build the digest.
+
+use dioxus::prelude::*;
+use dioxus_core::spawn_forever;
+use gloo_storage::{LocalStorage, Storage};
+use nostr_sdk::{EventId, Filter, Kind, PublicKey, Timestamp};
+
+use crate::stores::{auth_store, dms, nostr_client};
+use crate::utils::activity_digest::{
+    in_window, rank_top_posts, summarize_notifications, ActivityDigest, TOP_POSTS_LIMIT,
+};
+use crate::utils::time::now_secs;
+
+const LAST_SEEN_KEY: &str = "activity_last_seen_at";
+
+/// How often the last-seen timestamp is refreshed while the app is open
+const LAST_SEEN_HEARTBEAT_MS: u32 = 60_000;
+
+/// Absences shorter than this don't get a digest (brief reloads, tab switches)
+const MIN_ABSENCE_SECONDS: u64 = 30 * 60;
+
+/// Window summarized for this session, captured once at startup
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DigestWindow {
+    /// No previous visit recorded on this device
+    FirstVisit,
+    /// Last visit was too recent to be worth summarizing
+    Recent,
+    /// Summarize activity in `(since, until]`
+    Since { since: u64, until: u64 },
+}
+
+/// Window for the current session and the account it was captured for
+/// (None until `start_session` runs)
+pub static DIGEST_WINDOW: GlobalSignal<Option<(String, DigestWindow)>> = Signal::global(|| None);
+
+/// Whether the digest card was dismissed in this session
+pub static DIGEST_DISMISSED: GlobalSignal<bool> = Signal::global(|| false);
+
+/// Whether the last-seen heartbeat task is running
+static HEARTBEAT_STARTED: GlobalSignal<bool> = Signal::global(|| false);
+
+/// Last-seen is tracked per account so switching accounts doesn't mix windows
+fn last_seen_key(pubkey: &str) -> String {
+    format!("{}:{}", LAST_SEEN_KEY, pubkey)
+}
+
+fn save_last_seen(pubkey: &str, timestamp: u64) {
+    if let Err(e) = LocalStorage::set(last_seen_key(pubkey), timestamp) {
+        log::error!("Failed to save last seen timestamp to localStorage: {}", e);
+    }
+}
+
+/// Capture the digest window for this session and start tracking activity
+///
+/// The previous last-seen value is read before it is overwritten, so the
+/// window ends exactly where this session starts. Safe to call repeatedly;
+/// only the first call per account has an effect.
+pub fn start_session(pubkey: &str) {
+    if matches!(&*DIGEST_WINDOW.read(), Some((current, _)) if current == pubkey) {
+        return;
+    }
+
+    let now = now_secs();
+    let window = match LocalStorage::get::<u64>(last_seen_key(pubkey)) {
+        Ok(last_seen) if now.saturating_sub(last_seen) >= MIN_ABSENCE_SECONDS => {
+            DigestWindow::Since { since: last_seen, until: now }
+        }
+        Ok(_) => DigestWindow::Recent,
+        Err(_) => DigestWindow::FirstVisit,
+    };
+    log::debug!("Activity digest window: {:?}", window);
+
+    *DIGEST_WINDOW.write() = Some((pubkey.to_string(), window));
+    *DIGEST_DISMISSED.write() = false;
+    save_last_seen(pubkey, now);
+
+    if *HEARTBEAT_STARTED.read() {
+        return;
+    }
+    *HEARTBEAT_STARTED.write() = true;
+
+    // Keep last-seen current while the app is open so a long session isn't
+    // summarized again on the next visit
+    spawn_forever(async move {
+        loop {
+            gloo_timers::future::TimeoutFuture::new(LAST_SEEN_HEARTBEAT_MS).await;
+            if let Some(pubkey) = auth_store::get_pubkey() {
+                save_last_seen(&pubkey, now_secs());
+            }
+        }
+    });
+}
+
+/// Get the digest window for the current session, if captured
+pub fn get_digest_window() -> Option<DigestWindow> {
+    DIGEST_WINDOW.read().as_ref().map(|(_, window)| *window)
+}
+
+/// Hide the digest card for the rest of the session
+pub fn dismiss_digest() {
+    *DIGEST_DISMISSED.write() = true;
+}
+
+/// Build the digest for `(since, until]` from locally available data
+pub async fn compute_digest(since: u64, until: u64) -> Result<ActivityDigest, String> {
+    let client = nostr_client::get_client().ok_or("Client not initialized")?;
+    let pubkey_str = auth_store::get_pubkey().ok_or("Not logged in")?;
+    let me = PublicKey::parse(&pubkey_str).map_err(|e| format!("Invalid pubkey: {}", e))?;
+
+    let since_ts = Timestamp::from(since);
+    let until_ts = Timestamp::from(until);
+
+    // Mentions, replies, reactions and zaps addressed to us
+    let notification_filter = Filter::new()
+        .pubkey(me)
+        .kinds(vec![Kind::TextNote, Kind::Reaction, Kind::ZapReceipt])
+        .since(since_ts)
+        .until(until_ts);
+
+    let notifications: Vec<_> = client
+        .database()
+        .query(notification_filter)
+        .await
+        .map_err(|e| format!("Failed to query notifications: {}", e))?
+        .into_iter()
+        .collect();
+
+    let zapper = crate::services::aggregation::zapper_pubkey(&me).await;
+    let mut digest = summarize_notifications(&notifications, &me, zapper.as_ref(), since, until);
+
+    // DMs use the message's own timestamp (the rumor time for NIP-17), since
+    // gift wrap timestamps are deliberately randomized
+    digest.direct_messages = dms::CONVERSATIONS
+        .read()
+        .data()
+        .read()
+        .values()
+        .flat_map(|conversation| conversation.messages.iter())
+        .filter(|message| message.sender() != me)
+        .filter(|message| in_window(message.created_at().as_secs(), since, until))
+        .count();
+
+    // Top posts from follows, ranked by interactions we already have locally
+    let follows: Vec<PublicKey> = nostr_client::fetch_contacts(pubkey_str)
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|pk| PublicKey::parse(pk).ok())
+        .collect();
+
+    if !follows.is_empty() {
+        let posts_filter = Filter::new()
+            .authors(follows)
+            .kind(Kind::TextNote)
+            .since(since_ts)
+            .until(until_ts);
+
+        let posts: Vec<_> = client
+            .database()
+            .query(posts_filter)
+            .await
+            .map_err(|e| format!("Failed to query follow posts: {}", e))?
+            .into_iter()
+            .collect();
+
+        if !posts.is_empty() {
+            let post_ids: Vec<EventId> = posts.iter().map(|post| post.id).collect();
+            let interactions_filter = Filter::new()
+                .events(post_ids)
+                .kinds(vec![Kind::Reaction, Kind::Repost, Kind::ZapReceipt]);
+
+            let interactions: Vec<_> = client
+                .database()
+                .query(interactions_filter)
+                .await
+                .map_err(|e| format!("Failed to query interactions: {}", e))?
+                .into_iter()
+                .collect();
+
+            digest.top_posts = rank_top_posts(&posts, &interactions, TOP_POSTS_LIMIT);
+        }
+    }
+
+    Ok(digest)
+}
@@ -0,0 +1,307 @@
+//! NIP-89 application handlers - "Open in <client>"
+//!
+//! Clients announce the kinds they can display with kind 31990 events. Each
+//! `web` tag is a URL template where `<bech32>` stands for the entity to
+//! open, optionally limited to one entity type (`nevent`, `naddr`, ...). For
+//! kinds this app doesn't render well, the note page links to those clients.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use nostr_sdk::{Alphabet, Event, Filter, Kind, PublicKey, SingleLetterTag};
+
+use crate::stores::nostr_client;
+use crate::utils::nip19::{encode_naddr, encode_nevent};
+
+/// NIP-89 handler information
+pub const KIND_APP_HANDLER: u16 = 31990;
+
+/// Placeholder for the entity in a `web` URL template
+const BECH32_PLACEHOLDER: &str = "<bech32>";
+
+/// Most handlers listed for one event
+const MAX_HANDLERS: usize = 5;
+
+/// Kinds the app renders itself, where no handler links are offered
+const NATIVE_KINDS: &[u16] = &[
+    0,     // Profile
+    1,     // Text note
+    6,     // Repost
+    7,     // Reaction
+    16,    // Generic repost
+    20,    // Picture
+    21,    // Video
+    22,    // Short video
+    1068,  // Poll
+    1111,  // Comment
+    1222,  // Voice message
+    1244,  // Voice message reply
+    9041,  // Zap goal
+    9735,  // Zap receipt
+    30023, // Article
+    30311, // Live stream
+    34139, // Playlist
+    36787, // Music track
+];
+
+/// Handlers per kind, fetched once per session
+///
+/// Kinds with no handlers found aren't cached: the relays may just not have
+/// answered in time, so they're asked again next time.
+static HANDLER_CACHE: OnceLock<Mutex<HashMap<u16, Vec<AppHandler>>>> = OnceLock::new();
+
+fn handler_cache() -> &'static Mutex<HashMap<u16, Vec<AppHandler>>> {
+    HANDLER_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// A `web` handler URL template
+#[derive(Clone, Debug, PartialEq)]
+pub struct WebTemplate {
+    pub url: String,
+    /// Entity type this template opens; None opens any
+    pub entity: Option<String>,
+}
+
+/// A client that can display some kinds (parsed from kind 31990)
+#[derive(Clone, Debug, PartialEq)]
+pub struct AppHandler {
+    pub pubkey: PublicKey,
+    pub identifier: String,
+    pub name: String,
+    pub picture: Option<String>,
+    pub web: Vec<WebTemplate>,
+    pub created_at: u64,
+}
+
+impl AppHandler {
+    /// Template for `entity`, preferring one made for that entity type
+    pub fn web_template(&self, entity: &str) -> Option<&str> {
+        self.web
+            .iter()
+            .find(|template| template.entity.as_deref() == Some(entity))
+            .or_else(|| self.web.iter().find(|template| template.entity.is_none()))
+            .map(|template| template.url.as_str())
+    }
+}
+
+/// Parse a kind 31990 event; None if it has no usable `web` template
+pub fn parse_app_handler(event: &Event) -> Option<AppHandler> {
+    if event.kind.as_u16() != KIND_APP_HANDLER {
+        return None;
+    }
+
+    let mut identifier = String::new();
+    let mut web = Vec::new();
+    for tag in event.tags.iter() {
+        let parts = tag.as_slice();
+        match (parts.first().map(|s| s.as_str()), parts.get(1)) {
+            (Some("d"), Some(d)) => identifier = d.clone(),
+            (Some("web"), Some(url)) if is_web_template(url) => web.push(WebTemplate {
+                url: url.clone(),
+                entity: parts.get(2).filter(|entity| !entity.is_empty()).cloned(),
+            }),
+            _ => {}
+        }
+    }
+    if web.is_empty() {
+        return None;
+    }
+
+    // Content is optional kind 0 style metadata
+    let metadata: serde_json::Value = serde_json::from_str(&event.content).unwrap_or_default();
+    let text = |key: &str| {
+        metadata
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+    };
+    let name = text("display_name")
+        .or_else(|| text("name"))
+        .or_else(|| url::Url::parse(&web[0].url).ok()?.host_str().map(String::from))
+        .unwrap_or_else(|| "Unknown app".to_string());
+
+    Some(AppHandler {
+        pubkey: event.pubkey,
+        identifier,
+        name,
+        picture: text("picture").filter(|url| url.starts_with("https://")),
+        web,
+        created_at: event.created_at.as_u64(),
+    })
+}
+
+fn is_web_template(url: &str) -> bool {
+    (url.starts_with("https://") || url.starts_with("http://")) && url.contains(BECH32_PLACEHOLDER)
+}
+
+/// Substitute `bech32` into a `web` handler URL template
+///
+/// None if the template has no `<bech32>` placeholder.
+pub fn fill_handler_template(template: &str, bech32: &str) -> Option<String> {
+    if !template.contains(BECH32_PLACEHOLDER) {
+        return None;
+    }
+    Some(template.replace(BECH32_PLACEHOLDER, bech32))
+}
+
+/// Whether the app displays this kind itself
+pub fn is_natively_supported(kind: Kind) -> bool {
+    NATIVE_KINDS.contains(&kind.as_u16())
+}
+
+/// The entity type and bech32 string handlers should open an event with
+///
+/// Addressable events get an `naddr` so the link follows later versions.
+pub fn event_entity(event: &Event) -> Result<(&'static str, String), String> {
+    if event.kind.is_addressable() {
+        let identifier = event.tags.identifier().unwrap_or_default();
+        Ok(("naddr", encode_naddr(event.kind, event.pubkey, identifier, &[])?))
+    } else {
+        Ok(("nevent", encode_nevent(event.id, &[], Some(event.pubkey))?))
+    }
+}
+
+/// An "Open in <client>" link
+#[derive(Clone, Debug, PartialEq)]
+pub struct HandlerLink {
+    pub name: String,
+    pub picture: Option<String>,
+    pub url: String,
+}
+
+/// Links opening `bech32` in each handler that supports `entity`
+pub fn handler_links(handlers: &[AppHandler], entity: &str, bech32: &str) -> Vec<HandlerLink> {
+    let mut links: Vec<HandlerLink> = Vec::new();
+    for handler in handlers {
+        let Some(url) = handler
+            .web_template(entity)
+            .and_then(|template| fill_handler_template(template, bech32))
+        else {
+            continue;
+        };
+        if links.iter().any(|link| link.url == url) {
+            continue;
+        }
+        links.push(HandlerLink {
+            name: handler.name.clone(),
+            picture: handler.picture.clone(),
+            url,
+        });
+        if links.len() == MAX_HANDLERS {
+            break;
+        }
+    }
+    links
+}
+
+/// Fetch the handlers announcing support for `kind`, newest first
+pub async fn fetch_handlers(kind: Kind) -> Result<Vec<AppHandler>, String> {
+    let cached = handler_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&kind.as_u16())
+        .cloned();
+    if let Some(cached) = cached {
+        return Ok(cached);
+    }
+
+    let filter = Filter::new()
+        .kind(Kind::from(KIND_APP_HANDLER))
+        .custom_tag(SingleLetterTag::lowercase(Alphabet::K), kind.as_u16().to_string())
+        .limit(50);
+    let events = nostr_client::fetch_events_aggregated(filter, Duration::from_secs(5)).await?;
+
+    let mut handlers: Vec<AppHandler> = events.iter().filter_map(parse_app_handler).collect();
+
+    // A handler is addressable: keep the latest version of each
+    handlers.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    let mut seen = std::collections::HashSet::new();
+    handlers.retain(|handler| seen.insert((handler.pubkey, handler.identifier.clone())));
+
+    if !handlers.is_empty() {
+        handler_cache()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(kind.as_u16(), handlers.clone());
+    }
+    Ok(handlers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::{EventBuilder, Keys, Tag};
+
+    fn handler_event(content: &str, tags: Vec<Vec<&str>>) -> Event {
+        let tags: Vec<Tag> = tags.into_iter().map(|tag| Tag::parse(tag).unwrap()).collect();
+        EventBuilder::new(Kind::from(KIND_APP_HANDLER), content)
+            .tags(tags)
+            .sign_with_keys(&Keys::generate())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_fill_handler_template() {
+        assert_eq!(
+            fill_handler_template("https://calendar.example/e/<bech32>", "naddr1abc").as_deref(),
+            Some("https://calendar.example/e/naddr1abc")
+        );
+        assert_eq!(
+            fill_handler_template("https://app.example/?open=<bech32>&ref=nostr", "nevent1xyz").as_deref(),
+            Some("https://app.example/?open=nevent1xyz&ref=nostr")
+        );
+        assert_eq!(fill_handler_template("https://app.example/", "nevent1xyz"), None);
+    }
+
+    #[test]
+    fn test_parse_handler_and_pick_template() {
+        let event = handler_event(
+            r#"{"name":"Calendar","picture":"https://calendar.example/icon.png"}"#,
+            vec![
+                vec!["d", "calendar"],
+                vec!["k", "31923"],
+                vec!["web", "https://calendar.example/a/<bech32>", "naddr"],
+                vec!["web", "https://calendar.example/e/<bech32>"],
+                vec!["web", "https://calendar.example/no-placeholder", "nevent"],
+            ],
+        );
+        let handler = parse_app_handler(&event).unwrap();
+        assert_eq!(handler.name, "Calendar");
+        assert_eq!(handler.identifier, "calendar");
+        assert_eq!(handler.web.len(), 2);
+        assert_eq!(handler.web_template("naddr"), Some("https://calendar.example/a/<bech32>"));
+        // No nevent template of its own, so the untyped one is used
+        assert_eq!(handler.web_template("nevent"), Some("https://calendar.example/e/<bech32>"));
+
+        // Without metadata the host names the app; without web tags it's unusable
+        let bare = handler_event("", vec![vec!["web", "https://bare.example/<bech32>", "nevent"]]);
+        assert_eq!(parse_app_handler(&bare).unwrap().name, "bare.example");
+        assert!(parse_app_handler(&handler_event("", vec![vec!["k", "31923"]])).is_none());
+    }
+
+    #[test]
+    fn test_handler_links_lists_each_handler() {
+        let handlers: Vec<AppHandler> = [
+            vec!["web", "https://one.example/<bech32>", "nevent"],
+            vec!["web", "https://two.example/a/<bech32>", "naddr"],
+            vec!["web", "https://three.example/<bech32>"],
+        ]
+        .into_iter()
+        .filter_map(|web| parse_app_handler(&handler_event("", vec![web])))
+        .collect();
+
+        let links = handler_links(&handlers, "nevent", "nevent1xyz");
+        let urls: Vec<&str> = links.iter().map(|link| link.url.as_str()).collect();
+        assert_eq!(urls, vec!["https://one.example/nevent1xyz", "https://three.example/nevent1xyz"]);
+    }
+
+    #[test]
+    fn test_native_kinds() {
+        assert!(is_natively_supported(Kind::TextNote));
+        assert!(is_natively_supported(Kind::LongFormTextNote));
+        assert!(!is_natively_supported(Kind::from(31923)));
+    }
+}
@@ -0,0 +1,218 @@
+//! Article drafts - autosave of the long-form editor
+//!
+//! The in-progress article is written to localStorage every few seconds under
+//! a stable draft ID, offered for restore when the editor is reopened and
+//! cleared once the article is published.
+
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+/// Draft ID used by the new-article editor
+pub const NEW_ARTICLE_DRAFT_ID: &str = "new";
+
+/// How often the editor autosaves
+pub const AUTOSAVE_INTERVAL_MS: u32 = 3_000;
+
+const DRAFT_KEY_PREFIX: &str = "nostr_blue_article_draft";
+
+/// Snapshot of the article editor
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArticleDraft {
+    pub draft_id: String,
+    pub title: String,
+    pub summary: String,
+    /// Markdown body
+    pub content: String,
+    /// d-tag
+    pub identifier: String,
+    #[serde(default)]
+    pub cover_image: String,
+    /// Comma separated hashtags, as typed
+    #[serde(default)]
+    pub hashtags: String,
+    /// Unix seconds of the last save
+    #[serde(default)]
+    pub saved_at: u64,
+}
+
+impl ArticleDraft {
+    /// True when there's nothing worth saving
+    pub fn is_empty(&self) -> bool {
+        self.title.trim().is_empty()
+            && self.summary.trim().is_empty()
+            && self.content.trim().is_empty()
+            && self.cover_image.trim().is_empty()
+            && self.hashtags.trim().is_empty()
+    }
+
+    /// True when both drafts hold the same article, ignoring when they were saved
+    pub fn same_content(&self, other: &ArticleDraft) -> bool {
+        ArticleDraft { saved_at: 0, ..self.clone() } == ArticleDraft { saved_at: 0, ..other.clone() }
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(self).map_err(|e| format!("Failed to serialize draft: {}", e))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|e| format!("Failed to parse draft: {}", e))
+    }
+}
+
+/// Key-value storage the drafts are kept in
+pub trait DraftStorage {
+    fn get_item(&self, key: &str) -> Option<String>;
+    fn set_item(&self, key: &str, value: &str) -> Result<(), String>;
+    fn remove_item(&self, key: &str);
+}
+
+/// The browser's localStorage
+pub struct BrowserDraftStorage;
+
+impl DraftStorage for BrowserDraftStorage {
+    fn get_item(&self, key: &str) -> Option<String> {
+        LocalStorage::raw().get_item(key).ok().flatten()
+    }
+
+    fn set_item(&self, key: &str, value: &str) -> Result<(), String> {
+        LocalStorage::raw()
+            .set_item(key, value)
+            .map_err(|e| format!("Failed to save draft: {:?}", e))
+    }
+
+    fn remove_item(&self, key: &str) {
+        let _ = LocalStorage::raw().remove_item(key);
+    }
+}
+
+fn draft_key(draft_id: &str) -> String {
+    format!("{}:{}", DRAFT_KEY_PREFIX, draft_id)
+}
+
+/// Load a saved draft, dropping it if it can't be parsed
+pub fn load_draft_in(storage: &impl DraftStorage, draft_id: &str) -> Option<ArticleDraft> {
+    let key = draft_key(draft_id);
+    let json = storage.get_item(&key)?;
+    match ArticleDraft::from_json(&json) {
+        Ok(draft) if !draft.is_empty() => Some(draft),
+        Ok(_) => None,
+        Err(e) => {
+            log::warn!("{}, discarding it", e);
+            storage.remove_item(&key);
+            None
+        }
+    }
+}
+
+/// Save a draft; empty drafts remove the stored one instead
+pub fn save_draft_in(storage: &impl DraftStorage, draft: &ArticleDraft) -> Result<(), String> {
+    let key = draft_key(&draft.draft_id);
+    if draft.is_empty() {
+        storage.remove_item(&key);
+        return Ok(());
+    }
+    storage.set_item(&key, &draft.to_json()?)
+}
+
+pub fn clear_draft_in(storage: &impl DraftStorage, draft_id: &str) {
+    storage.remove_item(&draft_key(draft_id));
+}
+
+/// Load the saved draft from localStorage
+pub fn load_draft(draft_id: &str) -> Option<ArticleDraft> {
+    load_draft_in(&BrowserDraftStorage, draft_id)
+}
+
+/// Save the draft to localStorage, stamping the save time
+pub fn save_draft(mut draft: ArticleDraft) -> Result<(), String> {
+    draft.saved_at = chrono::Utc::now().timestamp() as u64;
+    save_draft_in(&BrowserDraftStorage, &draft)
+}
+
+/// Remove the draft from localStorage (after publishing or discarding it)
+pub fn clear_draft(draft_id: &str) {
+    clear_draft_in(&BrowserDraftStorage, draft_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+
+    #[derive(Default)]
+    struct MemoryStorage(RefCell<HashMap<String, String>>);
+
+    impl DraftStorage for MemoryStorage {
+        fn get_item(&self, key: &str) -> Option<String> {
+            self.0.borrow().get(key).cloned()
+        }
+
+        fn set_item(&self, key: &str, value: &str) -> Result<(), String> {
+            self.0.borrow_mut().insert(key.to_string(), value.to_string());
+            Ok(())
+        }
+
+        fn remove_item(&self, key: &str) {
+            self.0.borrow_mut().remove(key);
+        }
+    }
+
+    fn sample_draft() -> ArticleDraft {
+        ArticleDraft {
+            draft_id: NEW_ARTICLE_DRAFT_ID.to_string(),
+            title: "Hello Nostr".to_string(),
+            summary: "A first article".to_string(),
+            content: "# Heading\n\nSome *markdown*.".to_string(),
+            identifier: crate::utils::slugify("Hello Nostr"),
+            cover_image: String::new(),
+            hashtags: "nostr, writing".to_string(),
+            saved_at: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn test_draft_json_roundtrip() {
+        let draft = sample_draft();
+        let json = draft.to_json().unwrap();
+        assert_eq!(ArticleDraft::from_json(&json).unwrap(), draft);
+        assert_eq!(draft.identifier, "hello-nostr");
+
+        // Drafts saved before optional fields existed still load
+        let old = r#"{"draft_id":"new","title":"T","summary":"","content":"body","identifier":"t"}"#;
+        let parsed = ArticleDraft::from_json(old).unwrap();
+        assert_eq!(parsed.title, "T");
+        assert_eq!(parsed.saved_at, 0);
+
+        assert!(ArticleDraft::from_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_draft_cleared_on_publish() {
+        let storage = MemoryStorage::default();
+        let draft = sample_draft();
+
+        save_draft_in(&storage, &draft).unwrap();
+        assert_eq!(load_draft_in(&storage, NEW_ARTICLE_DRAFT_ID), Some(draft));
+
+        // Publishing succeeded
+        clear_draft_in(&storage, NEW_ARTICLE_DRAFT_ID);
+        assert_eq!(load_draft_in(&storage, NEW_ARTICLE_DRAFT_ID), None);
+        assert!(storage.0.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_empty_and_corrupt_drafts_not_restored() {
+        let storage = MemoryStorage::default();
+        let empty = ArticleDraft {
+            draft_id: NEW_ARTICLE_DRAFT_ID.to_string(),
+            ..Default::default()
+        };
+        save_draft_in(&storage, &empty).unwrap();
+        assert_eq!(load_draft_in(&storage, NEW_ARTICLE_DRAFT_ID), None);
+
+        storage.set_item(&draft_key(NEW_ARTICLE_DRAFT_ID), "{broken").unwrap();
+        assert_eq!(load_draft_in(&storage, NEW_ARTICLE_DRAFT_ID), None);
+        assert!(storage.0.borrow().is_empty());
+    }
+}
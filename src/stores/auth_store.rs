@@ -369,6 +369,11 @@ async fn run_post_login_init() {
     // Load notification checked_at timestamp from localStorage
     crate::stores::notifications::load_checked_at();
 
+    // Capture the "while you were away" window before last-seen is refreshed
+    if let Some(pubkey) = get_pubkey() {
+        crate::stores::activity_digest::start_session(&pubkey);
+    }
+
     // Fetch and merge notification checked_at from NIP-78 (if sync enabled)
     crate::stores::notifications::fetch_and_merge_from_nip78().await;
 
@@ -383,6 +388,13 @@ async fn run_post_login_init() {
     // Fetch custom emojis
     crate::stores::emoji_store::init_emoji_fetch();
 
+    // Load DM read state and keep conversations fresh for the unread badge
+    crate::stores::dms::load_read_state();
+    crate::stores::dms::start_dm_refresh();
+
+    // Publish notes scheduled in a previous session once they are due
+    crate::stores::scheduled_notes::start_scheduled_notes_checker();
+
     // Batch prefetch metadata for all contacts (runs in background)
     // This populates IndexedDB so avatars are ready when feed loads
     spawn(async move {
@@ -490,15 +502,60 @@ pub fn get_login_method() -> Option<LoginMethod> {
 }
 
 /// Logout and clear credentials
-pub async fn logout() {
-    log::info!("Logging out...");
+///
+/// Every logout stops the notification subscription, drops the wallet's
+/// in-memory handle and contact list, removes the signer and deletes the
+/// stored login (nsec, npub, method, bunker URI, app keys). Everything else stays on the
+/// device for the next login.
+///
+/// With `wipe_local_data` it also clears:
+/// - the wallet IndexedDB (proofs, quotes, transactions, pending events)
+///   and the wallet, pending event and payment request signals
+/// - the cached event database
+/// - the app cache IndexedDB (profiles, mint reliability, home feeds)
+/// - the in-memory profile cache
+/// - DM conversations and this account's stored DM read state
+/// - the notifications checked-at time and unread count
+///
+/// A wipe is refused, without logging out, while wallet events are waiting
+/// to be published or Lightning payments are in flight: those live only in
+/// the wallet database.
+pub async fn logout(wipe_local_data: bool) -> Result<(), String> {
+    log::info!("Logging out (wipe local data: {})...", wipe_local_data);
+    let pubkey = get_pubkey();
+
+    if wipe_local_data {
+        crate::stores::cashu::check_wallet_wipe().await?;
+    }
 
     // Stop real-time notification subscription
     crate::stores::notifications::stop_realtime_subscription().await;
 
-    // Clear Cashu wallet state
+    // Stop the background DM refresh; the next login starts its own
+    crate::stores::dms::stop_dm_refresh();
+
+    // Clear Cashu wallet state, closing the proof state subscriptions first
+    crate::stores::cashu::stop_proof_state_watch();
     crate::stores::cashu_cdk_bridge::clear_multi_wallet();
 
+    if wipe_local_data {
+        // Databases first, while the wallet's localstore handle still exists
+        if let Err(e) = crate::stores::cashu::wipe_wallet_database().await {
+            log::error!("{}", e);
+        }
+        if let Err(e) = nostr_client::wipe_database().await {
+            log::error!("{}", e);
+        }
+        if let Err(e) = crate::stores::cache_database::wipe_cache_database().await {
+            log::error!("{}", e);
+        }
+        if let Some(pubkey) = &pubkey {
+            crate::stores::dms::delete_read_state(pubkey);
+        }
+        crate::stores::notifications::delete_checked_at();
+        reset_local_data();
+    }
+
     // Unset signer from client
     let _ = nostr_client::set_read_only().await;
 
@@ -510,12 +567,24 @@ pub async fn logout() {
     LocalStorage::delete(STORAGE_KEY_METHOD);
     LocalStorage::delete(STORAGE_KEY_BUNKER_URI);
     LocalStorage::delete(STORAGE_KEY_APP_KEYS);
+
+    Ok(())
+}
+
+/// Reset the in-memory state a wiping logout clears
+fn reset_local_data() {
+    crate::stores::cashu::reset_wallet_state();
+    crate::stores::profiles::PROFILE_CACHE.write().clear();
+    crate::stores::dms::reset_dm_state();
+    crate::stores::notifications::reset_notification_state();
 }
 
 /// Clear authentication state
 fn clear_auth() {
     *AUTH_STATE.write() = AuthState::default();
     *KEYS.write() = None;
+    *crate::stores::contacts::CONTACT_LIST.write() = None;
+    nostr_client::MUTED_THREADS.write().clear();
 }
 
 /// Sign a message with current keys
@@ -539,3 +608,62 @@ pub fn export_npub() -> Result<String, String> {
     let pubkey = get_pubkey().ok_or("Not logged in")?;
     Ok(pubkey)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stores::{cashu, dms, notifications, profiles};
+
+    #[test]
+    fn test_wipe_resets_local_data() {
+        let mut dom = VirtualDom::new(|| rsx! {});
+        dom.rebuild_in_place();
+
+        dom.in_runtime(|| {
+            let pubkey = Keys::generate().public_key().to_hex();
+            *cashu::WALLET_BALANCE.write() = 2100;
+            *cashu::WALLET_STATUS.write() = cashu::WalletStatus::Ready;
+            cashu::PENDING_PAYMENT_REQUESTS.write().insert(
+                "request".to_string(),
+                cashu::NostrPaymentWaitInfo {
+                    request_id: "request".to_string(),
+                    secret_key: Keys::generate().secret_key().clone(),
+                    relays: vec!["wss://relay.example.com".to_string()],
+                    pubkey: Keys::generate().public_key(),
+                    amount: Some(21),
+                },
+            );
+            profiles::PROFILE_CACHE.write().put(
+                pubkey.clone(),
+                profiles::Profile {
+                    pubkey: pubkey.clone(),
+                    name: Some("alice".to_string()),
+                    display_name: None,
+                    about: None,
+                    picture: None,
+                    banner: None,
+                    nip05: None,
+                    lud16: None,
+                    website: None,
+                    fetched_at: chrono::Utc::now(),
+                },
+            );
+            dms::DM_READ_STATE.write().conversations.insert(pubkey.clone(), 1_700_000_000);
+            *notifications::NOTIFICATIONS_CHECKED_AT.write() = 1_700_000_000;
+            *notifications::UNREAD_COUNT.write() = 5;
+
+            reset_local_data();
+
+            assert_eq!(*cashu::WALLET_BALANCE.read(), 0);
+            assert_eq!(*cashu::WALLET_STATUS.read(), cashu::WalletStatus::Uninitialized);
+            assert!(cashu::PENDING_PAYMENT_REQUESTS.read().is_empty());
+            assert!(cashu::PENDING_NOSTR_EVENTS.read().is_empty());
+            assert!(cashu::SHARED_LOCALSTORE.read().is_none());
+            assert!(profiles::PROFILE_CACHE.read().is_empty());
+            assert_eq!(*dms::DM_READ_STATE.read(), dms::DmReadState::default());
+            assert!(dms::CONVERSATIONS.read().data().read().is_empty());
+            assert_eq!(*notifications::NOTIFICATIONS_CHECKED_AT.read(), 0);
+            assert_eq!(*notifications::UNREAD_COUNT.read(), 0);
+        });
+    }
+}
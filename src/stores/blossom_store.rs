@@ -6,7 +6,9 @@ use sha2::{Sha256, Digest};
 use image::ImageFormat;
 use std::io::Cursor;
 use std::time::Duration;
-use crate::stores::{nostr_client, auth_store};
+use crate::stores::{nostr_client, auth_store, settings_store};
+use crate::utils::image_metadata;
+use crate::utils::file_metadata::UploadedMedia;
 
 /// Default Blossom server
 pub const DEFAULT_SERVER: &str = "https://blossom.primal.net";
@@ -71,6 +73,19 @@ pub async fn upload_image(
     content_type: String,
     quality: u8,
 ) -> Result<String, String> {
+    upload_media(data, content_type, quality).await.map(|media| media.url)
+}
+
+/// Upload media like [`upload_image`], also describing the uploaded file
+///
+/// # Returns
+/// URL plus the NIP-94 metadata (hash, type, size, dimensions, blurhash) of
+/// the bytes actually uploaded, after stripping and compression
+pub async fn upload_media(
+    data: Vec<u8>,
+    content_type: String,
+    quality: u8,
+) -> Result<UploadedMedia, String> {
     let is_video = content_type.starts_with("video/");
     let media_type = if is_video { "video" } else { "image" };
 
@@ -85,6 +100,13 @@ pub async fn upload_image(
         return Err("Not authenticated. Please sign in to upload media.".to_string());
     }
 
+    // Strip EXIF/GPS metadata from images before anything leaves the device
+    let data = if !is_video && settings_store::SETTINGS.read().strip_image_metadata {
+        image_metadata::strip_for_upload(data, &content_type)
+    } else {
+        data
+    };
+
     // Compress image if quality < 100 and not a video
     let final_data = if !is_video && quality < 100 {
         log::info!("Compressing image to {}% quality", quality);
@@ -101,12 +123,16 @@ pub async fn upload_image(
     log::info!("Final {} size: {} bytes", media_type, final_data.len());
     UPLOAD_PROGRESS.write().replace(50.0);
 
-    upload_blob_with_auth(
+    let media = UploadedMedia::describe(String::new(), &final_data, &content_type);
+
+    let url = upload_blob_with_auth(
         final_data,
         content_type,
         format!("Upload {} via nostr.blue", media_type),
         50.0,
-    ).await
+    ).await?;
+
+    Ok(UploadedMedia { url, ..media })
 }
 
 /// Compress an image to the specified quality level
@@ -159,81 +185,274 @@ async fn compress_image(
     Ok(compressed_data)
 }
 
-/// Internal helper to upload a blob with authentication
+/// Outcome of uploading (or mirroring) a blob to one server
+#[derive(Clone, Debug, PartialEq)]
+pub struct ServerUploadResult {
+    pub server: String,
+    /// URL of the blob on this server, or why it failed
+    pub result: Result<String, String>,
+}
+
+/// Outcome of uploading a blob to several servers
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MultiUploadResult {
+    pub results: Vec<ServerUploadResult>,
+}
+
+impl MultiUploadResult {
+    /// URLs of every server that holds the blob, in server order
+    pub fn urls(&self) -> Vec<String> {
+        self.results
+            .iter()
+            .filter_map(|r| r.result.as_ref().ok().cloned())
+            .collect()
+    }
+
+    /// URL from the first server that succeeded
+    pub fn primary_url(&self) -> Option<String> {
+        self.urls().into_iter().next()
+    }
+
+    /// Servers that failed, with their error
+    pub fn failures(&self) -> Vec<(String, String)> {
+        self.results
+            .iter()
+            .filter_map(|r| r.result.as_ref().err().map(|e| (r.server.clone(), e.clone())))
+            .collect()
+    }
+}
+
+/// Per-server operations of a multi-server upload
+trait BlobTarget {
+    /// Upload the blob to `server`, returning its URL there
+    async fn upload(&self, server: &str) -> Result<String, String>;
+    /// Ask `server` to copy the blob from `source_url` (BUD-04), returning its URL there
+    async fn mirror(&self, server: &str, source_url: &str) -> Result<String, String>;
+}
+
+/// Put a blob on every server, in order
+///
+/// Servers are tried one after another until an upload succeeds; the servers
+/// after it mirror that copy, falling back to a direct upload if mirroring
+/// fails. A failing server never stops the others.
+async fn upload_to_servers(target: &impl BlobTarget, servers: &[String]) -> MultiUploadResult {
+    let mut outcome = MultiUploadResult::default();
+    let mut source_url: Option<String> = None;
+
+    for server in servers {
+        let result = match &source_url {
+            None => target.upload(server).await,
+            Some(source) => match target.mirror(server, source).await {
+                Ok(url) => Ok(url),
+                Err(mirror_err) => {
+                    log::warn!("Mirror to {} failed ({}), uploading instead", server, mirror_err);
+                    target.upload(server).await
+                }
+            },
+        };
+
+        match &result {
+            Ok(url) => {
+                log::info!("Blob stored on {}: {}", server, url);
+                source_url.get_or_insert_with(|| url.clone());
+            }
+            Err(e) => log::warn!("Blob upload to {} failed: {}", server, e),
+        }
+
+        outcome.results.push(ServerUploadResult {
+            server: server.clone(),
+            result,
+        });
+    }
+
+    outcome
+}
+
+/// Uploads through the Blossom HTTP API with the current signer
+struct BlossomTarget {
+    data: Vec<u8>,
+    content_type: String,
+    auth_content: String,
+    sha256: String,
+    signer: crate::stores::signer::SignerType,
+}
+
+impl BlobTarget for BlossomTarget {
+    async fn upload(&self, server: &str) -> Result<String, String> {
+        let url = Url::parse(server).map_err(|e| format!("Invalid server URL: {}", e))?;
+        let client = BlossomClient::new(url);
+
+        // The client signs a kind 24242 authorization for this server
+        let auth_options = Some(BlossomAuthorizationOptions {
+            content: Some(self.auth_content.clone()),
+            expiration: None, // No expiration
+            action: None, // Default action (upload)
+            scope: None, // No specific scope restriction
+        });
+        let data = self.data.clone();
+        let content_type = Some(self.content_type.clone());
+
+        let descriptor = match &self.signer {
+            crate::stores::signer::SignerType::Keys(keys) => {
+                client.upload_blob(data, content_type, auth_options, Some(keys)).await
+            }
+            #[cfg(target_family = "wasm")]
+            crate::stores::signer::SignerType::BrowserExtension(browser_signer) => {
+                client.upload_blob(data, content_type, auth_options, Some(browser_signer.as_ref())).await
+            }
+            crate::stores::signer::SignerType::NostrConnect(nostr_connect) => {
+                client.upload_blob(data, content_type, auth_options, Some(nostr_connect.as_ref())).await
+            }
+        }
+        .map_err(|e| format!("Upload failed: {}", e))?;
+
+        Ok(descriptor.url.to_string())
+    }
+
+    async fn mirror(&self, server: &str, source_url: &str) -> Result<String, String> {
+        let endpoint = format!("{}/mirror", server.trim_end_matches('/'));
+        let authorization = self.mirror_authorization(server).await?;
+
+        let response = gloo_net::http::Request::put(&endpoint)
+            .header("Authorization", &authorization)
+            .header("Content-Type", "application/json")
+            .body(serde_json::json!({ "url": source_url }).to_string())
+            .map_err(|e| format!("Failed to build request: {}", e))?
+            .send()
+            .await
+            .map_err(|e| format!("HTTP request failed: {}", e))?;
+
+        if !response.ok() {
+            return Err(format!("HTTP error: {}", response.status()));
+        }
+
+        let descriptor: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        descriptor
+            .get("url")
+            .and_then(|v| v.as_str())
+            .map(|url| url.to_string())
+            .ok_or_else(|| "Invalid response format: missing 'url'".to_string())
+    }
+}
+
+impl BlossomTarget {
+    /// `Authorization` header for a BUD-04 mirror: a kind 24242 upload
+    /// authorization for this blob, scoped to `server`
+    async fn mirror_authorization(&self, server: &str) -> Result<String, String> {
+        use base64::Engine;
+
+        let expiration = nostr_sdk::Timestamp::now() + Duration::from_secs(300);
+        let mut tags = vec![
+            Tag::custom(TagKind::t(), vec!["upload".to_string()]),
+            Tag::custom(TagKind::custom("x"), vec![self.sha256.clone()]),
+            Tag::expiration(expiration),
+        ];
+        if let Ok(url) = url::Url::parse(server) {
+            if let Some(host) = url.host_str() {
+                tags.push(Tag::custom(TagKind::custom("server"), vec![host.to_string()]));
+            }
+        }
+
+        let builder = nostr_sdk::EventBuilder::new(Kind::from(24242), self.auth_content.clone())
+            .tags(tags);
+
+        let event = match &self.signer {
+            crate::stores::signer::SignerType::Keys(keys) => builder.sign(keys).await,
+            #[cfg(target_family = "wasm")]
+            crate::stores::signer::SignerType::BrowserExtension(browser_signer) => {
+                builder.sign(browser_signer.as_ref()).await
+            }
+            crate::stores::signer::SignerType::NostrConnect(nostr_connect) => {
+                builder.sign(nostr_connect.as_ref()).await
+            }
+        }
+        .map_err(|e| format!("Failed to sign authorization: {}", e))?;
+
+        let json = serde_json::to_string(&event)
+            .map_err(|e| format!("Failed to serialize authorization: {}", e))?;
+        Ok(format!(
+            "Nostr {}",
+            base64::engine::general_purpose::STANDARD.encode(json.as_bytes())
+        ))
+    }
+}
+
+/// Upload a blob to each of `servers`, mirroring from the first successful upload
 ///
 /// # Arguments
 /// * `data` - Raw blob bytes
 /// * `content_type` - MIME type
 /// * `auth_content` - Authorization message content
-/// * `start_progress` - Progress value to set at upload start (after any pre-processing)
+/// * `servers` - Blossom servers, primary first
 ///
 /// # Returns
-/// URL of the uploaded blob
-async fn upload_blob_with_auth(
+/// Per-server results; check `primary_url()` for whether any server succeeded
+pub async fn upload_blob(
     data: Vec<u8>,
     content_type: String,
     auth_content: String,
-    start_progress: f32,
-) -> Result<String, String> {
-    // Get signer for authentication
+    servers: &[String],
+) -> Result<MultiUploadResult, String> {
     let signer = nostr_client::get_signer()
         .ok_or("Not authenticated. Please sign in to upload.")?;
 
-    UPLOAD_PROGRESS.write().replace(start_progress);
+    let target = BlossomTarget {
+        sha256: calculate_sha256(&data),
+        data,
+        content_type,
+        auth_content,
+        signer,
+    };
 
-    // Get primary server
-    let server_url = get_primary_server();
-    let url = Url::parse(&server_url).map_err(|e| format!("Invalid server URL: {}", e))?;
+    Ok(upload_to_servers(&target, servers).await)
+}
 
-    // Create Blossom client
-    let client = BlossomClient::new(url);
+/// Internal helper to upload a blob with authentication to all configured servers
+///
+/// # Arguments
+/// * `data` - Raw blob bytes
+/// * `content_type` - MIME type
+/// * `auth_content` - Authorization message content
+/// * `start_progress` - Progress value to set at upload start (after any pre-processing)
+///
+/// # Returns
+/// URL of the uploaded blob on the first server that accepted it
+async fn upload_blob_with_auth(
+    data: Vec<u8>,
+    content_type: String,
+    auth_content: String,
+    start_progress: f32,
+) -> Result<String, String> {
+    UPLOAD_PROGRESS.write().replace(start_progress);
 
-    log::info!("Uploading to {} with authentication", server_url);
+    let servers = BLOSSOM_SERVERS.read().data().read().clone();
+    log::info!("Uploading to {} Blossom servers with authentication", servers.len());
     UPLOAD_PROGRESS.write().replace(start_progress + 25.0);
 
-    // Create authorization options for the upload
-    let auth_options = Some(BlossomAuthorizationOptions {
-        content: Some(auth_content),
-        expiration: None, // No expiration
-        action: None, // Default action (upload)
-        scope: None, // No specific scope restriction
-    });
+    let outcome = upload_blob(data, content_type, auth_content, &servers).await?;
 
-    // Upload with proper authentication based on signer type
-    let descriptor = match signer {
-        crate::stores::signer::SignerType::Keys(keys) => {
-            client
-                .upload_blob(data, Some(content_type), auth_options, Some(&keys))
-                .await
-                .map_err(|e| {
-                    UPLOAD_PROGRESS.write().replace(0.0);
-                    format!("Upload failed: {}", e)
-                })?
-        }
-        #[cfg(target_family = "wasm")]
-        crate::stores::signer::SignerType::BrowserExtension(browser_signer) => {
-            client
-                .upload_blob(data, Some(content_type), auth_options, Some(browser_signer.as_ref()))
-                .await
-                .map_err(|e| {
-                    UPLOAD_PROGRESS.write().replace(0.0);
-                    format!("Upload failed: {}", e)
-                })?
-        }
-        crate::stores::signer::SignerType::NostrConnect(nostr_connect) => {
-            client
-                .upload_blob(data, Some(content_type), auth_options, Some(nostr_connect.as_ref()))
-                .await
-                .map_err(|e| {
-                    UPLOAD_PROGRESS.write().replace(0.0);
-                    format!("Upload failed: {}", e)
-                })?
-        }
+    let Some(url) = outcome.primary_url() else {
+        UPLOAD_PROGRESS.write().replace(0.0);
+        let errors: Vec<String> = outcome
+            .failures()
+            .into_iter()
+            .map(|(server, e)| format!("{}: {}", server, e))
+            .collect();
+        return Err(format!("Upload failed: {}", errors.join("; ")));
     };
 
     UPLOAD_PROGRESS.write().replace(100.0);
 
-    log::info!("Upload successful: {}", descriptor.url);
+    log::info!(
+        "Upload successful: {} ({} of {} servers)",
+        url,
+        outcome.urls().len(),
+        outcome.results.len()
+    );
 
     // Clear progress after a short delay
     spawn(async move {
@@ -241,7 +460,7 @@ async fn upload_blob_with_auth(
         *UPLOAD_PROGRESS.write() = None;
     });
 
-    Ok(descriptor.url.to_string())
+    Ok(url)
 }
 
 /// Upload audio to Blossom (no compression)
@@ -270,7 +489,6 @@ pub async fn upload_audio(
 }
 
 /// Calculate SHA-256 hash of data
-#[allow(dead_code)]
 pub fn calculate_sha256(data: &[u8]) -> String {
     let mut hasher = Sha256::new();
     hasher.update(data);
@@ -435,3 +653,75 @@ pub async fn publish_user_servers() -> Result<String, String> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use crate::utils::testing::block_on;
+
+    /// Servers listed in `down` fail every request
+    struct FakeTarget {
+        down: Vec<&'static str>,
+        calls: RefCell<Vec<String>>,
+    }
+
+    impl BlobTarget for FakeTarget {
+        async fn upload(&self, server: &str) -> Result<String, String> {
+            self.calls.borrow_mut().push(format!("upload {}", server));
+            if self.down.contains(&server) {
+                return Err("503 Service Unavailable".to_string());
+            }
+            Ok(format!("{}/abc123", server))
+        }
+
+        async fn mirror(&self, server: &str, _source_url: &str) -> Result<String, String> {
+            self.calls.borrow_mut().push(format!("mirror {}", server));
+            if self.down.contains(&server) {
+                return Err("503 Service Unavailable".to_string());
+            }
+            Ok(format!("{}/abc123", server))
+        }
+    }
+
+    #[test]
+    fn test_failing_primary_falls_through_to_mirror() {
+        let target = FakeTarget {
+            down: vec!["https://a.example"],
+            calls: RefCell::new(Vec::new()),
+        };
+        let servers = vec![
+            "https://a.example".to_string(),
+            "https://b.example".to_string(),
+            "https://c.example".to_string(),
+        ];
+
+        let outcome = block_on(upload_to_servers(&target, &servers));
+
+        assert_eq!(outcome.primary_url().as_deref(), Some("https://b.example/abc123"));
+        assert_eq!(
+            outcome.urls(),
+            vec!["https://b.example/abc123".to_string(), "https://c.example/abc123".to_string()]
+        );
+        assert_eq!(outcome.failures().len(), 1);
+        assert_eq!(outcome.failures()[0].0, "https://a.example");
+        assert_eq!(
+            *target.calls.borrow(),
+            vec!["upload https://a.example", "upload https://b.example", "mirror https://c.example"]
+        );
+    }
+
+    #[test]
+    fn test_all_servers_failing() {
+        let target = FakeTarget {
+            down: vec!["https://a.example", "https://b.example"],
+            calls: RefCell::new(Vec::new()),
+        };
+        let servers = vec!["https://a.example".to_string(), "https://b.example".to_string()];
+
+        let outcome = block_on(upload_to_servers(&target, &servers));
+
+        assert_eq!(outcome.primary_url(), None);
+        assert_eq!(outcome.failures().len(), 2);
+    }
+}
@@ -1,7 +1,7 @@
 use dioxus::prelude::*;
 use dioxus::signals::ReadableExt;
 use dioxus_stores::Store;
-use nostr_sdk::{Event, Filter, Kind, EventBuilder, PublicKey};
+use nostr_sdk::{Event, Filter, Kind, EventBuilder, PublicKey, Tag};
 use crate::stores::{auth_store, nostr_client};
 use std::time::Duration;
 
@@ -398,3 +398,343 @@ pub async fn fetch_bookmarked_events_paginated(skip: usize, limit: Option<usize>
 pub fn get_bookmarks_count() -> usize {
     BOOKMARKED_EVENTS.read().data().read().len()
 }
+
+// ============================================================================
+// Bookmark sets (NIP-51 kind 30003): named folders of saved notes and articles
+// ============================================================================
+
+/// d-tag of the main bookmark list published by `publish_bookmarks`, not a set
+const MAIN_BOOKMARKS_IDENTIFIER: &str = "bookmark";
+
+/// Something saved in a bookmark set
+#[derive(Clone, Debug, PartialEq)]
+pub enum BookmarkItem {
+    /// A note or other event, by hex ID (`e` tag)
+    Event(String),
+    /// An addressable event such as an article, as `kind:pubkey:d` (`a` tag)
+    Address(String),
+}
+
+impl BookmarkItem {
+    fn from_tag(tag: &Tag) -> Option<Self> {
+        let parts = tag.as_slice();
+        match (parts.first().map(|s| s.as_str()), parts.get(1)) {
+            (Some("e"), Some(id)) if nostr_sdk::EventId::from_hex(id).is_ok() => Some(Self::Event(id.clone())),
+            (Some("a"), Some(address)) => Some(Self::Address(address.clone())),
+            _ => None,
+        }
+    }
+
+    fn to_tag(&self) -> Result<Tag, String> {
+        let (name, value) = match self {
+            Self::Event(id) => ("e", id),
+            Self::Address(address) => ("a", address),
+        };
+        Tag::parse([name, value.as_str()]).map_err(|e| format!("Invalid bookmark: {}", e))
+    }
+}
+
+/// A named bookmark set
+#[derive(Clone, Debug, PartialEq)]
+pub struct BookmarkSet {
+    /// d-tag
+    pub identifier: String,
+    pub title: String,
+    pub items: Vec<BookmarkItem>,
+    /// Tags this client doesn't edit (image, description, hashtags, ...),
+    /// published back unchanged
+    pub other_tags: Vec<Tag>,
+    /// Encrypted private items, published back unchanged
+    pub content: String,
+}
+
+impl BookmarkSet {
+    pub fn new(identifier: String, title: String) -> Self {
+        Self { identifier, title, items: Vec::new(), other_tags: Vec::new(), content: String::new() }
+    }
+
+    pub fn contains(&self, item: &BookmarkItem) -> bool {
+        self.items.contains(item)
+    }
+
+    /// Add an item; returns false (and changes nothing) if it's already in the set
+    pub fn add(&mut self, item: BookmarkItem) -> bool {
+        if self.contains(&item) {
+            return false;
+        }
+        self.items.push(item);
+        true
+    }
+
+    /// Remove an item; returns false if it wasn't in the set
+    pub fn remove(&mut self, item: &BookmarkItem) -> bool {
+        let before = self.items.len();
+        self.items.retain(|existing| existing != item);
+        self.items.len() != before
+    }
+
+    /// Tags of the kind 30003 event for this set
+    pub fn to_tags(&self) -> Result<Vec<Tag>, String> {
+        let mut tags = vec![
+            Tag::identifier(self.identifier.clone()),
+            Tag::parse(["title", self.title.as_str()]).map_err(|e| e.to_string())?,
+        ];
+        for item in &self.items {
+            tags.push(item.to_tag()?);
+        }
+        tags.extend(self.other_tags.iter().cloned());
+        Ok(tags)
+    }
+}
+
+/// Parse a kind 30003 event into a bookmark set
+pub fn parse_bookmark_set(event: &Event) -> Option<BookmarkSet> {
+    if event.kind != Kind::BookmarkSet {
+        return None;
+    }
+    let identifier = event.tags.identifier()?.to_string();
+    let title = event
+        .tags
+        .iter()
+        .find_map(|tag| {
+            let parts = tag.as_slice();
+            (parts.len() >= 2 && parts[0] == "title" && !parts[1].trim().is_empty()).then(|| parts[1].clone())
+        })
+        .unwrap_or_else(|| identifier.clone());
+    let items = event.tags.iter().filter_map(BookmarkItem::from_tag).collect();
+    let other_tags = event
+        .tags
+        .iter()
+        .filter(|tag| {
+            let name = tag.as_slice().first().map(|s| s.as_str());
+            !matches!(name, Some("d") | Some("title")) && BookmarkItem::from_tag(tag).is_none()
+        })
+        .cloned()
+        .collect();
+
+    Some(BookmarkSet { identifier, title, items, other_tags, content: event.content.clone() })
+}
+
+/// A d-tag for a new set named `name` that doesn't clash with `existing`
+pub fn unique_set_identifier(name: &str, existing: &[BookmarkSet]) -> String {
+    let base = match crate::utils::slugify(name) {
+        slug if slug.is_empty() => "bookmarks".to_string(),
+        slug => slug,
+    };
+    let taken = |candidate: &str| {
+        candidate == MAIN_BOOKMARKS_IDENTIFIER || existing.iter().any(|set| set.identifier == candidate)
+    };
+
+    let mut candidate = base.clone();
+    let mut suffix = 2;
+    while taken(&candidate) {
+        candidate = format!("{}-{}", base, suffix);
+        suffix += 1;
+    }
+    candidate
+}
+
+/// The user's bookmark sets, sorted by title
+pub static BOOKMARK_SETS: GlobalSignal<Vec<BookmarkSet>> = Signal::global(Vec::new);
+
+/// Fetch the user's bookmark sets (latest event per d-tag)
+pub async fn list_bookmark_sets() -> Result<Vec<BookmarkSet>, String> {
+    let pubkey_str = auth_store::get_pubkey().ok_or("Not authenticated")?;
+    let pubkey = PublicKey::parse(&pubkey_str).map_err(|e| format!("Invalid pubkey: {}", e))?;
+
+    let filter = Filter::new().author(pubkey).kind(Kind::BookmarkSet);
+    let events = nostr_client::fetch_events_aggregated(filter, Duration::from_secs(10)).await?;
+
+    let mut latest: std::collections::HashMap<String, Event> = std::collections::HashMap::new();
+    for event in events {
+        let Some(identifier) = event.tags.identifier().map(|d| d.to_string()) else {
+            continue;
+        };
+        match latest.get(&identifier) {
+            Some(existing) if existing.created_at >= event.created_at => {}
+            _ => {
+                latest.insert(identifier, event);
+            }
+        }
+    }
+
+    let mut sets: Vec<BookmarkSet> = latest
+        .values()
+        .filter_map(parse_bookmark_set)
+        .filter(|set| set.identifier != MAIN_BOOKMARKS_IDENTIFIER)
+        .collect();
+    sets.sort_by_key(|set| set.title.to_lowercase());
+
+    log::info!("Loaded {} bookmark sets", sets.len());
+    *BOOKMARK_SETS.write() = sets.clone();
+    Ok(sets)
+}
+
+async fn publish_bookmark_set(set: &BookmarkSet) -> Result<(), String> {
+    let client = nostr_client::get_client().ok_or("Client not initialized")?;
+    if !*nostr_client::HAS_SIGNER.read() {
+        return Err("No signer attached".to_string());
+    }
+
+    let builder = EventBuilder::new(Kind::BookmarkSet, &set.content).tags(set.to_tags()?);
+    client
+        .send_event_builder(builder)
+        .await
+        .map_err(|e| format!("Failed to publish bookmark set: {}", e))?;
+    Ok(())
+}
+
+/// Replace (or insert) a set in `BOOKMARK_SETS`, keeping the title order
+fn store_bookmark_set(set: BookmarkSet) {
+    let mut sets = BOOKMARK_SETS.write();
+    sets.retain(|existing| existing.identifier != set.identifier);
+    sets.push(set);
+    sets.sort_by_key(|set| set.title.to_lowercase());
+}
+
+/// Create and publish an empty bookmark set named `name`
+pub async fn create_bookmark_set(name: String) -> Result<BookmarkSet, String> {
+    let title = name.trim().to_string();
+    if title.is_empty() {
+        return Err("Bookmark set name can't be empty".to_string());
+    }
+
+    let identifier = unique_set_identifier(&title, &BOOKMARK_SETS.read());
+    let set = BookmarkSet::new(identifier, title);
+    publish_bookmark_set(&set).await?;
+
+    log::info!("Created bookmark set {}", set.identifier);
+    store_bookmark_set(set.clone());
+    Ok(set)
+}
+
+/// Add an item to the set with d-tag `set_d` and publish it
+///
+/// Adding an item that's already in the set is a no-op.
+pub async fn add_to_set(set_d: &str, item: BookmarkItem) -> Result<(), String> {
+    let mut set = BOOKMARK_SETS
+        .read()
+        .iter()
+        .find(|set| set.identifier == set_d)
+        .cloned()
+        .ok_or_else(|| format!("Bookmark set '{}' not found", set_d))?;
+
+    if !set.add(item) {
+        return Ok(());
+    }
+
+    publish_bookmark_set(&set).await?;
+    store_bookmark_set(set);
+    Ok(())
+}
+
+/// Fetch the events saved in a bookmark set, newest first
+pub async fn fetch_bookmark_set_events(set: &BookmarkSet) -> Result<Vec<Event>, String> {
+    let mut filters = Vec::new();
+
+    let event_ids: Vec<nostr_sdk::EventId> = set
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            BookmarkItem::Event(id) => nostr_sdk::EventId::from_hex(id).ok(),
+            BookmarkItem::Address(_) => None,
+        })
+        .collect();
+    if !event_ids.is_empty() {
+        filters.push(Filter::new().ids(event_ids));
+    }
+
+    for item in &set.items {
+        if let BookmarkItem::Address(address) = item {
+            if let Ok(coordinate) = nostr_sdk::nips::nip01::Coordinate::parse(address) {
+                filters.push(Filter::new().coordinate(&coordinate));
+            }
+        }
+    }
+
+    let mut events = Vec::new();
+    for filter in filters {
+        events.extend(nostr_client::fetch_events_aggregated(filter, Duration::from_secs(10)).await?);
+    }
+    events.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    events.dedup_by_key(|event| event.id);
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::Keys;
+
+    fn note_id(n: u8) -> String {
+        format!("{:064x}", n)
+    }
+
+    #[test]
+    fn test_add_and_remove_set_items() {
+        let mut set = BookmarkSet::new("reading".to_string(), "Reading".to_string());
+
+        assert!(set.add(BookmarkItem::Event(note_id(1))));
+        assert!(set.add(BookmarkItem::Address("30023:abc:my-article".to_string())));
+        // Already in the set: no-op
+        assert!(!set.add(BookmarkItem::Event(note_id(1))));
+        assert_eq!(set.items.len(), 2);
+
+        assert!(set.remove(&BookmarkItem::Event(note_id(1))));
+        assert!(!set.remove(&BookmarkItem::Event(note_id(1))));
+        assert_eq!(set.items, vec![BookmarkItem::Address("30023:abc:my-article".to_string())]);
+    }
+
+    #[test]
+    fn test_bookmark_set_event_roundtrip() {
+        let mut set = BookmarkSet::new("recipes".to_string(), "Recipes 🍲".to_string());
+        set.add(BookmarkItem::Event(note_id(7)));
+        set.add(BookmarkItem::Address("30023:abc:soup".to_string()));
+
+        let event = EventBuilder::new(Kind::BookmarkSet, "")
+            .tags(set.to_tags().unwrap())
+            .sign_with_keys(&Keys::generate())
+            .unwrap();
+        assert_eq!(parse_bookmark_set(&event), Some(set));
+    }
+
+    #[test]
+    fn test_republishing_keeps_unknown_tags_and_private_items() {
+        let keys = Keys::generate();
+        let event = EventBuilder::new(Kind::BookmarkSet, "encrypted-private-items")
+            .tags([
+                Tag::identifier("recipes"),
+                Tag::parse(["title", "Recipes"]).unwrap(),
+                Tag::parse(["image", "https://example.com/soup.jpg"]).unwrap(),
+                Tag::parse(["e", note_id(1).as_str()]).unwrap(),
+                Tag::hashtag("food"),
+            ])
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        let mut set = parse_bookmark_set(&event).unwrap();
+        set.add(BookmarkItem::Event(note_id(2)));
+        let republished = EventBuilder::new(Kind::BookmarkSet, &set.content)
+            .tags(set.to_tags().unwrap())
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        assert_eq!(republished.content, "encrypted-private-items");
+        for tag in event.tags.iter() {
+            assert!(republished.tags.iter().any(|t| t == tag), "{:?} dropped", tag.as_slice());
+        }
+        assert_eq!(parse_bookmark_set(&republished).unwrap().items.len(), 2);
+    }
+
+    #[test]
+    fn test_unique_set_identifier() {
+        let existing = vec![
+            BookmarkSet::new("to-read".to_string(), "To read".to_string()),
+            BookmarkSet::new("to-read-2".to_string(), "To read".to_string()),
+        ];
+        assert_eq!(unique_set_identifier("Cooking", &existing), "cooking");
+        assert_eq!(unique_set_identifier("To Read", &existing), "to-read-3");
+        // The main bookmark list's d-tag is never reused for a set
+        assert_eq!(unique_set_identifier("Bookmark", &[]), "bookmark-2");
+    }
+}
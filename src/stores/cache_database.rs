@@ -0,0 +1,233 @@
+//! # IndexedDB App Cache
+//!
+//! Caches that are not wallet data live in their own database
+//! (`nostr_blue_cache`), apart from the Cashu wallet database, so they can be
+//! dropped or versioned without touching proofs:
+//! - `profiles` - Kind 0 profile metadata
+//! - `mint_reliability` - Per-mint operation success/failure and latency
+//! - `feed_cache` - Recent home feed notes for offline reading
+//!
+//! Values are stored as JSON strings, like in the wallet database.
+
+use dioxus::prelude::*;
+use indexed_db_futures::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::future::IntoFuture;
+use std::sync::Arc;
+use wasm_bindgen::JsValue;
+use web_sys::IdbTransactionMode;
+
+const DB_NAME: &str = "nostr_blue_cache";
+const DB_VERSION: u32 = 1;
+
+const STORE_PROFILES: &str = "profiles";
+const STORE_MINT_RELIABILITY: &str = "mint_reliability";
+const STORE_FEED_CACHE: &str = "feed_cache";
+
+const ALL_STORES: [&str; 3] = [STORE_PROFILES, STORE_MINT_RELIABILITY, STORE_FEED_CACHE];
+
+/// Shared handle, opened on first use
+static CACHE_DB: GlobalSignal<Option<Arc<CacheDatabase>>> = Signal::global(|| None);
+
+/// IndexedDB database for non-wallet caches
+#[derive(Clone, Debug)]
+pub struct CacheDatabase {
+    db: Arc<IdbDatabase>,
+}
+
+// SAFETY: In WASM, there's only one thread
+unsafe impl Send for CacheDatabase {}
+unsafe impl Sync for CacheDatabase {}
+
+/// Get or open the shared cache database
+pub async fn get_cache_db() -> Result<Arc<CacheDatabase>, String> {
+    if let Some(db) = CACHE_DB.peek().clone() {
+        return Ok(db);
+    }
+
+    let db = Arc::new(CacheDatabase::open().await?);
+    // Another task may have opened it meanwhile; keep the first handle
+    let mut shared = CACHE_DB.write();
+    Ok(shared.get_or_insert(db).clone())
+}
+
+/// Delete every cached record, leaving the empty database in place
+pub async fn wipe_cache_database() -> Result<(), String> {
+    get_cache_db().await?.clear_all().await
+}
+
+impl CacheDatabase {
+    async fn open() -> Result<Self, String> {
+        let mut db_req: OpenDbRequest = IdbDatabase::open_u32(DB_NAME, DB_VERSION)
+            .map_err(|e| format!("Failed to open cache database: {:?}", e))?;
+
+        db_req.set_on_upgrade_needed(Some(|evt: &IdbVersionChangeEvent| {
+            let db = evt.db();
+            for store_name in ALL_STORES {
+                if !db.object_store_names().any(|n| n == store_name) {
+                    db.create_object_store(store_name)?;
+                }
+            }
+            Ok(())
+        }));
+
+        let db: IdbDatabase = db_req
+            .into_future()
+            .await
+            .map_err(|e| format!("Failed to open cache database: {:?}", e))?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    async fn get_value<T>(&self, store_name: &str, key: &str) -> Result<Option<T>, String>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let tx = self
+            .db
+            .transaction_on_one_with_mode(store_name, IdbTransactionMode::Readonly)
+            .map_err(|e| format!("Transaction error: {:?}", e))?;
+        let store = tx
+            .object_store(store_name)
+            .map_err(|e| format!("Store error: {:?}", e))?;
+
+        let value = store
+            .get(&JsValue::from_str(key))
+            .map_err(|e| format!("Get error: {:?}", e))?
+            .await
+            .map_err(|e| format!("Get await error: {:?}", e))?;
+
+        let Some(json_str) = value.and_then(|value| value.as_string()) else {
+            return Ok(None);
+        };
+        serde_json::from_str(&json_str)
+            .map(Some)
+            .map_err(|e| format!("JSON deserialization error: {}", e))
+    }
+
+    async fn put_value<T>(&self, store_name: &str, key: &str, value: &T) -> Result<(), String>
+    where
+        T: Serialize,
+    {
+        let tx = self
+            .db
+            .transaction_on_one_with_mode(store_name, IdbTransactionMode::Readwrite)
+            .map_err(|e| format!("Transaction error: {:?}", e))?;
+        let store = tx
+            .object_store(store_name)
+            .map_err(|e| format!("Store error: {:?}", e))?;
+
+        let json_str = serde_json::to_string(value)
+            .map_err(|e| format!("JSON serialization error: {}", e))?;
+        store
+            .put_key_val(&JsValue::from_str(key), &JsValue::from_str(&json_str))
+            .map_err(|e| format!("Put error: {:?}", e))?;
+
+        tx.await
+            .into_result()
+            .map_err(|e| format!("Transaction commit error: {:?}", e))
+    }
+
+    async fn get_all_values<T>(&self, store_name: &str) -> Result<Vec<T>, String>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let tx = self
+            .db
+            .transaction_on_one_with_mode(store_name, IdbTransactionMode::Readonly)
+            .map_err(|e| format!("Transaction error: {:?}", e))?;
+        let store = tx
+            .object_store(store_name)
+            .map_err(|e| format!("Store error: {:?}", e))?;
+
+        let values = store
+            .get_all()
+            .map_err(|e| format!("Get all error: {:?}", e))?
+            .await
+            .map_err(|e| format!("Get all await error: {:?}", e))?;
+
+        values
+            .into_iter()
+            .filter_map(|value| value.as_string())
+            .map(|json_str| {
+                serde_json::from_str(&json_str)
+                    .map_err(|e| format!("JSON deserialization error: {}", e))
+            })
+            .collect()
+    }
+
+    async fn clear_all(&self) -> Result<(), String> {
+        let tx = self
+            .db
+            .transaction_on_multi_with_mode(&ALL_STORES, IdbTransactionMode::Readwrite)
+            .map_err(|e| format!("Transaction error: {:?}", e))?;
+
+        for store_name in ALL_STORES {
+            tx.object_store(store_name)
+                .map_err(|e| format!("Store error: {:?}", e))?
+                .clear()
+                .map_err(|e| format!("Clear error: {:?}", e))?;
+        }
+
+        tx.await
+            .into_result()
+            .map_err(|e| format!("Transaction commit error: {:?}", e))
+    }
+
+    // =========================================================================
+    // Profile Cache (Kind 0 Metadata)
+    // =========================================================================
+
+    /// Save profile metadata fetched from relays
+    pub async fn save_profile(&self, profile: &crate::stores::profiles::StoredProfile) -> Result<(), String> {
+        self.put_value(STORE_PROFILES, &profile.pubkey, profile).await
+    }
+
+    /// Load cached profile metadata by hex pubkey
+    pub async fn get_profile(
+        &self,
+        pubkey: &str,
+    ) -> Result<Option<crate::stores::profiles::StoredProfile>, String> {
+        self.get_value(STORE_PROFILES, pubkey).await
+    }
+
+    // =========================================================================
+    // Mint Reliability
+    // =========================================================================
+
+    /// Save the recorded operation outcomes for a mint
+    pub async fn save_mint_reliability(
+        &self,
+        stats: &crate::stores::cashu::types::MintReliabilityStats,
+    ) -> Result<(), String> {
+        self.put_value(STORE_MINT_RELIABILITY, &stats.mint_url, stats).await
+    }
+
+    /// Load the recorded operation outcomes for all mints
+    pub async fn get_all_mint_reliability(
+        &self,
+    ) -> Result<Vec<crate::stores::cashu::types::MintReliabilityStats>, String> {
+        self.get_all_values(STORE_MINT_RELIABILITY).await
+    }
+
+    // =========================================================================
+    // Feed Cache (Offline Home Feed)
+    // =========================================================================
+
+    /// Save the cached items of a home feed
+    pub async fn save_feed_cache(
+        &self,
+        key: &str,
+        items: &[crate::stores::feed_cache::CachedFeedItem],
+    ) -> Result<(), String> {
+        self.put_value(STORE_FEED_CACHE, key, &items).await
+    }
+
+    /// Load the cached items of a home feed
+    pub async fn get_feed_cache(
+        &self,
+        key: &str,
+    ) -> Result<Option<Vec<crate::stores::feed_cache::CachedFeedItem>>, String> {
+        self.get_value(STORE_FEED_CACHE, key).await
+    }
+}
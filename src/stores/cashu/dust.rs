@@ -8,9 +8,9 @@
 
 use std::collections::HashMap;
 
-use dioxus::prelude::ReadableExt;
+use dioxus::prelude::*;
 
-use crate::stores::cashu_cdk_bridge;
+use crate::stores::{cashu_cdk_bridge, settings_store};
 use super::internal::get_or_create_wallet;
 use super::proofs::proof_data_to_cdk_proof;
 use super::signals::{try_acquire_mint_lock, WALLET_TOKENS};
@@ -65,9 +65,12 @@ pub fn find_dust_proofs(mint_url: &str, threshold: u64) -> Vec<ProofData> {
         .collect()
 }
 
-/// Get dust statistics for a mint
-pub fn get_dust_stats(mint_url: &str, threshold: u64) -> DustStats {
-    let dust = find_dust_proofs(mint_url, threshold);
+/// Dust statistics for a set of proof amounts
+///
+/// Amounts above `threshold` are ignored, so this can be given every proof
+/// held at a mint.
+pub fn dust_stats_for_amounts(amounts: impl IntoIterator<Item = u64>, threshold: u64) -> DustStats {
+    let dust: Vec<u64> = amounts.into_iter().filter(|amount| *amount <= threshold).collect();
 
     if dust.is_empty() {
         return DustStats::default();
@@ -76,8 +79,7 @@ pub fn get_dust_stats(mint_url: &str, threshold: u64) -> DustStats {
     let count = dust.len();
     let total_value: u64 = dust
         .iter()
-        .map(|p| p.amount)
-        .fold(0u64, |acc, amt| acc.saturating_add(amt));
+        .fold(0u64, |acc, amt| acc.saturating_add(*amt));
     let avg_value = total_value / count as u64;
 
     // Estimate fee: 1 sat per proof (typical)
@@ -96,6 +98,14 @@ pub fn get_dust_stats(mint_url: &str, threshold: u64) -> DustStats {
     }
 }
 
+/// Get dust statistics for a mint
+pub fn get_dust_stats(mint_url: &str, threshold: u64) -> DustStats {
+    dust_stats_for_amounts(
+        find_dust_proofs(mint_url, threshold).iter().map(|p| p.amount),
+        threshold,
+    )
+}
+
 /// Get dust statistics for all mints
 pub fn get_all_dust_stats(threshold: u64) -> HashMap<String, DustStats> {
     let store = WALLET_TOKENS();
@@ -263,6 +273,36 @@ pub fn should_consolidate_dust() -> Vec<(String, DustStats)> {
         .collect()
 }
 
+/// Last automatic sweep, for the wallet UI to announce
+pub static DUST_SWEEP_RESULT: GlobalSignal<Option<DustConsolidationResult>> =
+    Signal::global(|| None);
+
+/// Sweep a mint's dust in the background after a receive or mint
+///
+/// Does nothing unless auto-sweep is on in settings and the mint's dust
+/// calls for consolidation. Call it once the receive has released the mint
+/// lock; if a send grabs the lock first, the sweep is skipped and the dust
+/// is picked up after the next receive.
+pub fn schedule_dust_sweep(mint_url: String) {
+    if !settings_store::SETTINGS.peek().auto_sweep_dust {
+        return;
+    }
+
+    dioxus_core::spawn_forever(async move {
+        if !get_dust_stats(&mint_url, DEFAULT_DUST_THRESHOLD).should_consolidate {
+            return;
+        }
+
+        match consolidate_dust(&mint_url, DEFAULT_DUST_THRESHOLD).await {
+            Ok(result) if result.proofs_consolidated > 0 => {
+                *DUST_SWEEP_RESULT.write() = Some(result);
+            }
+            Ok(_) => {}
+            Err(e) => log::info!("Skipped dust sweep for {}: {}", mint_url, e),
+        }
+    });
+}
+
 /// Get total dust across all mints
 pub fn get_total_dust_stats() -> DustStats {
     let all_stats = get_all_dust_stats(DEFAULT_DUST_THRESHOLD);
@@ -301,4 +341,26 @@ mod tests {
         assert_eq!(stats.total_value, 0);
         assert!(!stats.should_consolidate);
     }
+
+    #[test]
+    fn test_dust_heavy_mint_triggers_sweep() {
+        // Twelve 1-2 sat proofs next to a few regular ones
+        let mut amounts = vec![1, 2, 1, 1, 2, 2, 1, 2, 1, 2, 2, 1];
+        amounts.extend([64, 128, 512]);
+
+        let stats = dust_stats_for_amounts(amounts.clone(), DEFAULT_DUST_THRESHOLD);
+        assert_eq!(stats.count, 12);
+        assert_eq!(stats.total_value, 18);
+        assert_eq!(stats.estimated_fee, 12);
+        assert!(stats.should_consolidate);
+
+        // Too few dust proofs to be worth a swap
+        let stats = dust_stats_for_amounts(amounts[6..].to_vec(), DEFAULT_DUST_THRESHOLD);
+        assert_eq!(stats.count, 6);
+        assert!(!stats.should_consolidate);
+
+        // Dust that would all go to fees isn't swept
+        let stats = dust_stats_for_amounts(vec![1; 12], DEFAULT_DUST_THRESHOLD);
+        assert!(!stats.should_consolidate);
+    }
 }
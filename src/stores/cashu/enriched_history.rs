@@ -5,14 +5,22 @@
 //! - Swap details (source/destination keysets)
 //! - Error details for failed transactions
 //! - Transaction descriptions from quotes
+//! - A unified activity feed of history, open quotes and queued events
 
 // Allow dead_code for planned features not yet wired to UI
 #![allow(dead_code)]
 
+use dioxus::prelude::*;
 use nostr_sdk::nips::nip60::TransactionDirection;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
-use super::types::HistoryItem;
+use super::events::{retry_decision, RetryDecision};
+use super::signals::{PENDING_MELT_QUOTES, PENDING_MINT_QUOTES, PENDING_NOSTR_EVENTS, WALLET_HISTORY};
+use super::types::{
+    HistoryItem, MeltQuoteInfo, MintQuoteInfo, PendingEventType, PendingNostrEvent,
+    PendingMeltQuotesStoreStoreExt, PendingMintQuotesStoreStoreExt, WalletHistoryStoreStoreExt,
+};
 
 // =============================================================================
 // Direction Wrapper (for serialization)
@@ -433,6 +441,191 @@ pub fn create_swap_history(
     }
 }
 
+// =============================================================================
+// Activity Feed
+// =============================================================================
+
+/// Status badge of a wallet activity item
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActivityStatus {
+    /// Done and recorded in the wallet history
+    Confirmed,
+    /// Waiting on a payment, the mint or a relay
+    Pending,
+    /// Expired, or out of automatic retries
+    Failed,
+}
+
+impl ActivityStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Confirmed => "Confirmed",
+            Self::Pending => "Pending",
+            Self::Failed => "Failed",
+        }
+    }
+}
+
+/// Where an activity item comes from
+#[derive(Clone, Debug, PartialEq)]
+pub enum ActivitySource {
+    History(HistoryItem),
+    /// Lightning invoice waiting to be paid to us
+    MintQuote { quote_id: String, mint_url: String },
+    /// Lightning payment the mint is still settling
+    MeltQuote { quote_id: String, mint_url: String },
+    /// Wallet event not yet published to relays
+    QueuedEvent { id: String, event_type: PendingEventType },
+}
+
+/// One entry of the wallet activity feed
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActivityItem {
+    /// Unique key of the underlying operation
+    pub key: String,
+    pub source: ActivitySource,
+    pub direction: Direction,
+    /// None when the amount isn't known locally (e.g. an unpublished nutzap)
+    pub amount: Option<u64>,
+    pub created_at: u64,
+    pub status: ActivityStatus,
+    pub description: String,
+}
+
+/// Id of the signed event held by a queued event, if it parses
+fn queued_event_id(event: &PendingNostrEvent) -> Option<String> {
+    serde_json::from_str::<nostr_sdk::Event>(&event.builder_json)
+        .ok()
+        .map(|event| event.id.to_hex())
+}
+
+/// Merge wallet history, open quotes and queued events into one feed, newest first
+///
+/// A history event still waiting to be published shows once, as the history
+/// item with a pending (or failed) status. Quotes seen twice show once.
+/// Only in-flight melt quotes are included; a melt quote that was never paid
+/// isn't wallet activity.
+pub fn merge_activity(
+    history: &[HistoryItem],
+    mint_quotes: &[MintQuoteInfo],
+    melt_quotes: &[MeltQuoteInfo],
+    pending_events: &[PendingNostrEvent],
+    now: u64,
+) -> Vec<ActivityItem> {
+    let mut items = Vec::new();
+    let mut seen = HashSet::new();
+
+    // Status of queued events by the id of the event they carry
+    let queued: Vec<(Option<String>, &PendingNostrEvent, ActivityStatus)> = pending_events
+        .iter()
+        .filter(|event| matches!(event.event_type, PendingEventType::HistoryEvent | PendingEventType::Nutzap))
+        .map(|event| {
+            let status = match retry_decision(event, now, false) {
+                RetryDecision::NeedsAttention => ActivityStatus::Failed,
+                _ => ActivityStatus::Pending,
+            };
+            (queued_event_id(event), event, status)
+        })
+        .collect();
+
+    for item in history {
+        if !seen.insert(format!("event:{}", item.event_id)) {
+            continue;
+        }
+        let status = queued
+            .iter()
+            .find(|(event_id, _, _)| event_id.as_deref() == Some(item.event_id.as_str()))
+            .map_or(ActivityStatus::Confirmed, |(_, _, status)| *status);
+        let direction = Direction::from(item.direction);
+        items.push(ActivityItem {
+            key: format!("event:{}", item.event_id),
+            description: match direction {
+                Direction::In => "Received".to_string(),
+                Direction::Out => "Sent".to_string(),
+            },
+            source: ActivitySource::History(item.clone()),
+            direction,
+            amount: Some(item.amount),
+            created_at: item.created_at,
+            status,
+        });
+    }
+
+    for (event_id, event, status) in &queued {
+        if event_id.as_ref().is_some_and(|id| seen.contains(&format!("event:{}", id))) {
+            continue;
+        }
+        if !seen.insert(format!("queued:{}", event.id)) {
+            continue;
+        }
+        if let Some(id) = event_id {
+            seen.insert(format!("event:{}", id));
+        }
+        items.push(ActivityItem {
+            key: format!("queued:{}", event.id),
+            source: ActivitySource::QueuedEvent { id: event.id.clone(), event_type: event.event_type.clone() },
+            direction: Direction::Out,
+            amount: None,
+            created_at: event.created_at,
+            status: *status,
+            description: format!("Publishing {}", event.event_type.label()),
+        });
+    }
+
+    for quote in mint_quotes {
+        if !seen.insert(format!("quote:{}", quote.quote_id)) {
+            continue;
+        }
+        let expired = quote.expiry.is_some_and(|expiry| expiry <= now);
+        items.push(ActivityItem {
+            key: format!("quote:{}", quote.quote_id),
+            source: ActivitySource::MintQuote { quote_id: quote.quote_id.clone(), mint_url: quote.mint_url.clone() },
+            direction: Direction::In,
+            amount: Some(quote.amount),
+            created_at: quote.created_at.unwrap_or(now),
+            status: if expired { ActivityStatus::Failed } else { ActivityStatus::Pending },
+            description: if expired { "Invoice expired".to_string() } else { "Waiting for lightning payment".to_string() },
+        });
+    }
+
+    for quote in melt_quotes {
+        let Some(pending_since) = quote.pending_since else {
+            continue;
+        };
+        if !seen.insert(format!("quote:{}", quote.quote_id)) {
+            continue;
+        }
+        items.push(ActivityItem {
+            key: format!("quote:{}", quote.quote_id),
+            source: ActivitySource::MeltQuote { quote_id: quote.quote_id.clone(), mint_url: quote.mint_url.clone() },
+            direction: Direction::Out,
+            amount: Some(quote.amount),
+            created_at: pending_since,
+            status: ActivityStatus::Pending,
+            description: "Lightning payment settling".to_string(),
+        });
+    }
+
+    // Newest first; ties keep pending items above confirmed ones
+    items.sort_by(|a, b| {
+        b.created_at
+            .cmp(&a.created_at)
+            .then_with(|| (a.status == ActivityStatus::Confirmed).cmp(&(b.status == ActivityStatus::Confirmed)))
+    });
+    items
+}
+
+/// The wallet's activity feed from the current wallet state
+pub fn build_activity_feed() -> Vec<ActivityItem> {
+    let now = chrono::Utc::now().timestamp() as u64;
+    let history = WALLET_HISTORY.read().data().read().clone();
+    let mint_quotes = PENDING_MINT_QUOTES.read().data().read().clone();
+    let melt_quotes = PENDING_MELT_QUOTES.read().data().read().clone();
+    let pending_events = PENDING_NOSTR_EVENTS.read().clone();
+
+    merge_activity(&history, &mint_quotes, &melt_quotes, &pending_events, now)
+}
+
 // =============================================================================
 // Tests
 // =============================================================================
@@ -447,6 +640,88 @@ mod tests {
         assert_eq!(TransactionType::P2pkReceive.display_name(), "P2PK Receive");
     }
 
+    #[test]
+    fn test_activity_feed_merges_and_sorts_sources() {
+        use nostr_sdk::{EventBuilder, JsonUtil, Keys, Kind};
+
+        let history_item = |event_id: &str, direction, amount, created_at| HistoryItem {
+            event_id: event_id.to_string(),
+            direction,
+            amount,
+            unit: "sat".to_string(),
+            created_at,
+            created_tokens: Vec::new(),
+            destroyed_tokens: Vec::new(),
+            redeemed_events: Vec::new(),
+        };
+        let queued = |id: &str, event_type, json: String, created_at, retry_count| PendingNostrEvent {
+            id: id.to_string(),
+            builder_json: json,
+            event_type,
+            created_at,
+            retry_count,
+            last_retry_at: None,
+            publish_at: None,
+            relays: Vec::new(),
+        };
+
+        // A history event that hasn't reached relays yet
+        let unsynced = EventBuilder::new(Kind::from(7376), "encrypted")
+            .sign_with_keys(&Keys::generate())
+            .unwrap();
+        let unsynced_id = unsynced.id.to_hex();
+
+        let history = vec![
+            history_item("old", TransactionDirection::In, 500, 100),
+            history_item(&unsynced_id, TransactionDirection::Out, 21, 400),
+            history_item("old", TransactionDirection::In, 500, 100),
+        ];
+        let pending_events = vec![
+            queued("q-history", PendingEventType::HistoryEvent, unsynced.as_json(), 400, 0),
+            queued("q-nutzap", PendingEventType::Nutzap, String::new(), 250, 5),
+            queued("q-token", PendingEventType::TokenEvent, String::new(), 260, 0),
+        ];
+        let mint_quote = |quote_id: &str, expiry, created_at| MintQuoteInfo {
+            quote_id: quote_id.to_string(),
+            invoice: "lnbc...".to_string(),
+            amount: 1000,
+            expiry: Some(expiry),
+            mint_url: "https://mint.example.com".to_string(),
+            created_at,
+        };
+        let mint_quotes = vec![
+            mint_quote("mq-open", 10_000, Some(300)),
+            mint_quote("mq-expired", 150, Some(50)),
+            mint_quote("mq-open", 10_000, Some(300)),
+        ];
+        let melt_quote = |quote_id: &str, pending_since| MeltQuoteInfo {
+            quote_id: quote_id.to_string(),
+            invoice: "lnbc...".to_string(),
+            amount: 2000,
+            fee_reserve: 10,
+            mint_url: "https://mint.example.com".to_string(),
+            expiry: None,
+            pending_since,
+            input_secrets: Vec::new(),
+            change_outputs: None,
+        };
+        let melt_quotes = vec![melt_quote("melt-inflight", Some(350)), melt_quote("melt-unpaid", None)];
+
+        let feed = merge_activity(&history, &mint_quotes, &melt_quotes, &pending_events, 1_000);
+        let summary: Vec<(&str, ActivityStatus)> = feed.iter().map(|item| (item.key.as_str(), item.status)).collect();
+        let unsynced_key = format!("event:{}", unsynced_id);
+        assert_eq!(summary, vec![
+            (unsynced_key.as_str(), ActivityStatus::Pending),
+            ("quote:melt-inflight", ActivityStatus::Pending),
+            ("quote:mq-open", ActivityStatus::Pending),
+            ("queued:q-nutzap", ActivityStatus::Failed),
+            ("event:old", ActivityStatus::Confirmed),
+            ("quote:mq-expired", ActivityStatus::Failed),
+        ]);
+        assert_eq!(feed[0].amount, Some(21));
+        assert_eq!(feed[3].amount, None);
+    }
+
     #[test]
     fn test_swap_reason() {
         let reason = SwapReason::Consolidation;
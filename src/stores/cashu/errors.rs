@@ -429,6 +429,25 @@ pub fn is_insufficient_funds_error_string(error_msg: &str) -> bool {
     error_msg.to_lowercase().contains("insufficient")
 }
 
+/// Helper function to check if a CDK error says the mint couldn't be reached
+/// or answered with an HTTP error instead of a protocol response
+pub fn is_mint_unavailable_error(error: &CdkError) -> bool {
+    match error {
+        CdkError::HttpError(_, _) => true,
+        _ => is_mint_unavailable_error_string(&error.to_string()),
+    }
+}
+
+/// Helper function to check if an error message indicates a transport failure
+pub fn is_mint_unavailable_error_string(error_msg: &str) -> bool {
+    let msg = error_msg.to_lowercase();
+    msg.contains("failed to fetch")
+        || msg.contains("network")
+        || msg.contains("connection")
+        || msg.contains("timed out")
+        || msg.contains("timeout")
+}
+
 /// Helper function to check if a CDK error indicates DLEQ proof is missing (NUT-12)
 pub fn is_dleq_missing_error(error: &CdkError) -> bool {
     matches!(error, CdkError::DleqProofNotProvided)
@@ -10,7 +10,6 @@
 use dioxus::prelude::*;
 use nostr_sdk::{Kind, Filter, PublicKey, EventId, Timestamp};
 use nostr::nips::nip60::{SpendingHistory, TransactionDirection};
-use std::time::Duration;
 
 use super::signals::{
     PENDING_NOSTR_EVENTS, WALLET_TOKENS, WALLET_BALANCE, SHARED_LOCALSTORE, SYNC_STATE,
@@ -31,6 +30,24 @@ use crate::stores::{auth_store, nostr_client};
 pub async fn queue_nostr_event(
     event_json: String,
     event_type: PendingEventType,
+) -> Result<String, String> {
+    queue_event(event_json, event_type, None, Vec::new()).await
+}
+
+/// Queue a signed event to be published once `publish_at` has passed
+pub async fn queue_scheduled_event(
+    event_json: String,
+    event_type: PendingEventType,
+    publish_at: u64,
+) -> Result<String, String> {
+    queue_event(event_json, event_type, Some(publish_at), Vec::new()).await
+}
+
+async fn queue_event(
+    event_json: String,
+    event_type: PendingEventType,
+    publish_at: Option<u64>,
+    relays: Vec<String>,
 ) -> Result<String, String> {
     use uuid::Uuid;
 
@@ -44,6 +61,8 @@ pub async fn queue_nostr_event(
         created_at,
         retry_count: 0,
         last_retry_at: None,
+        publish_at,
+        relays,
     };
 
     // Save to in-memory queue
@@ -56,14 +75,7 @@ pub async fn queue_nostr_event(
         }
     }
 
-    log::debug!("Queued {} event: {}",
-        match event_type {
-            PendingEventType::TokenEvent => "token",
-            PendingEventType::DeletionEvent => "deletion",
-            PendingEventType::HistoryEvent => "history",
-            PendingEventType::QuoteEvent => "quote",
-        },
-        event_id);
+    log::debug!("Queued {} event: {}", event_type.label(), event_id);
 
     Ok(event_id)
 }
@@ -103,6 +115,28 @@ pub async fn queue_signed_event_for_retry(event: nostr_sdk::Event, event_type: P
     }
 }
 
+/// Queue a signed event for retry to specific relays, e.g. a nutzap to the
+/// recipient's relays
+pub async fn queue_signed_event_for_relays(
+    event: nostr_sdk::Event,
+    event_type: PendingEventType,
+    relays: Vec<String>,
+) {
+    match serde_json::to_string(&event) {
+        Ok(event_json) => match queue_event(event_json, event_type, None, relays).await {
+            Ok(queue_id) => {
+                log::info!("Queued signed event {} for retry: {}", event.id.to_hex(), queue_id);
+            }
+            Err(queue_err) => {
+                log::error!("Failed to queue event for retry: {}", queue_err);
+            }
+        },
+        Err(json_err) => {
+            log::error!("Failed to serialize event for queueing: {}", json_err);
+        }
+    }
+}
+
 /// Queue an EventBuilder for retry when initial publication fails
 pub async fn queue_event_for_retry(builder: nostr_sdk::EventBuilder, event_type: PendingEventType) {
     let signer = match crate::stores::signer::get_signer() {
@@ -317,7 +351,7 @@ pub async fn fetch_tokens() -> Result<(), String> {
     // Note: If we had previous sync state, deleted events from prior syncs are handled
     // through the del field in token events (deleted_via_del_field) or kind-5 deletions
 
-    if let Ok(deletion_events) = client.fetch_events(deletion_filter, Duration::from_secs(10)).await {
+    if let Ok(deletion_events) = client.fetch_events(deletion_filter, nostr_client::default_fetch_timeout()).await {
         for del_event in deletion_events {
             for tag in del_event.tags.iter() {
                 if let Some(nostr::TagStandard::Event { event_id, .. }) = tag.as_standardized() {
@@ -342,7 +376,7 @@ pub async fn fetch_tokens() -> Result<(), String> {
             .kind(Kind::from(7375))
     };
 
-    match client.fetch_events(filter, Duration::from_secs(10)).await {
+    match nostr_client::fetch_events_with_timeout(filter, nostr_client::default_fetch_timeout()).await {
         Ok(events) => {
             let signer = crate::stores::signer::get_signer()
                 .ok_or("No signer available")?
@@ -358,7 +392,7 @@ pub async fn fetch_tokens() -> Result<(), String> {
                     continue;
                 }
 
-                if let Ok(decrypted) = signer.nip44_decrypt(&event.pubkey, &event.content).await {
+                if let Ok(decrypted) = crate::stores::signer::decrypt_with_fallback(&*signer, &event.pubkey, &event.content).await {
                     if let Ok(token_event) = serde_json::from_str::<TokenEventData>(&decrypted) {
                         for del_event_id in &token_event.del {
                             deleted_via_del_field.insert(del_event_id.clone());
@@ -388,7 +422,7 @@ pub async fn fetch_tokens() -> Result<(), String> {
                     continue;
                 }
 
-                match signer.nip44_decrypt(&event.pubkey, &event.content).await {
+                match crate::stores::signer::decrypt_with_fallback(&*signer, &event.pubkey, &event.content).await {
                     Ok(decrypted) => {
                         match serde_json::from_str::<TokenEventData>(&decrypted) {
                             Ok(token_event) => {
@@ -651,6 +685,11 @@ pub async fn create_history_event_full(
 // Pending Events Processing
 // =============================================================================
 
+/// Pending events that may be published at `now` (scheduled ones only once due)
+pub fn due_events(events: &[PendingNostrEvent], now: u64) -> Vec<PendingNostrEvent> {
+    events.iter().filter(|event| event.is_due(now)).cloned().collect()
+}
+
 /// Publish a single pending event
 async fn publish_pending_event(event: &PendingNostrEvent) -> Result<(), String> {
     let client = nostr_client::NOSTR_CLIENT.read().as_ref()
@@ -660,16 +699,139 @@ async fn publish_pending_event(event: &PendingNostrEvent) -> Result<(), String>
     let evt: nostr_sdk::Event = serde_json::from_str(&event.builder_json)
         .map_err(|e| format!("Failed to deserialize event: {}", e))?;
 
-    client.send_event(&evt).await
-        .map_err(|e| format!("Failed to publish event: {}", e))?;
+    if event.relays.is_empty() {
+        client.send_event(&evt).await
+            .map_err(|e| format!("Failed to publish event: {}", e))?;
+        return Ok(());
+    }
+
+    let temp_relays = nostr_client::add_temp_relays(&client, &event.relays).await;
+    let result = client.send_event_to(event.relays.iter().map(String::as_str), &evt).await;
+    temp_relays.release().await;
 
+    let output = result.map_err(|e| format!("Failed to publish event: {}", e))?;
+    if output.success.is_empty() {
+        return Err(format!("None of the {} relays accepted the event", event.relays.len()));
+    }
     Ok(())
 }
 
+/// Failed attempts after which an event waits for the user instead of retrying
+pub const MAX_RETRIES: u32 = 5;
+const BASE_RETRY_DELAY_SECS: u64 = 10;
+const MAX_RETRY_DELAY_SECS: u64 = 60;
+
+/// What a processing pass does with a due pending event
+#[derive(Clone, Debug, PartialEq)]
+pub enum RetryDecision {
+    Publish,
+    /// Still backing off from the last failure
+    Wait { remaining_secs: u64 },
+    /// Out of automatic retries; kept for the user to retry or delete
+    NeedsAttention,
+}
+
+/// Decide whether to attempt `event` at `now`
+///
+/// `force` (the user pressed "Retry now") skips the backoff timer and the
+/// retry limit.
+pub fn retry_decision(event: &PendingNostrEvent, now: u64, force: bool) -> RetryDecision {
+    if force {
+        return RetryDecision::Publish;
+    }
+    if event.retry_count >= MAX_RETRIES {
+        return RetryDecision::NeedsAttention;
+    }
+
+    // Scheduled events go out as soon as they are due; backoff only applies to retries
+    if event.publish_at.is_some() && event.last_retry_at.is_none() {
+        return RetryDecision::Publish;
+    }
+
+    // Use last_retry_at if available, otherwise created_at (for first attempt)
+    let last_attempt = event.last_retry_at.unwrap_or(event.created_at);
+    let elapsed = now.saturating_sub(last_attempt);
+
+    // Adaptive backoff: BASE_DELAY * (1 + retry_count/2), capped at MAX_DELAY
+    // This gives delays of: 10s, 15s, 20s, 25s, 30s (capped)
+    let multiplier = 1 + (event.retry_count / 2);
+    let adaptive_delay = BASE_RETRY_DELAY_SECS * (multiplier as u64);
+    let retry_delay = adaptive_delay.min(MAX_RETRY_DELAY_SECS);
+
+    if elapsed < retry_delay {
+        RetryDecision::Wait { remaining_secs: retry_delay - elapsed }
+    } else {
+        RetryDecision::Publish
+    }
+}
+
+/// A queued event as shown in the wallet's pending events list
+#[derive(Clone, Debug, PartialEq)]
+pub struct PendingEventSummary {
+    pub id: String,
+    /// Event type label ("token", "nutzap", ...)
+    pub kind_label: &'static str,
+    pub age_secs: u64,
+    pub retry_count: u32,
+    /// Seconds until the next automatic attempt; None when it's due now or
+    /// retries are exhausted
+    pub next_retry_in_secs: Option<u64>,
+    /// Out of automatic retries
+    pub needs_attention: bool,
+}
+
+/// Summaries of the due pending events at `now`, oldest first
+pub fn summarize_pending_events(events: &[PendingNostrEvent], now: u64) -> Vec<PendingEventSummary> {
+    let mut summaries: Vec<PendingEventSummary> = due_events(events, now)
+        .iter()
+        .map(|event| {
+            let decision = retry_decision(event, now, false);
+            PendingEventSummary {
+                id: event.id.clone(),
+                kind_label: event.event_type.label(),
+                age_secs: now.saturating_sub(event.created_at),
+                retry_count: event.retry_count,
+                next_retry_in_secs: match decision {
+                    RetryDecision::Wait { remaining_secs } => Some(remaining_secs),
+                    _ => None,
+                },
+                needs_attention: decision == RetryDecision::NeedsAttention,
+            }
+        })
+        .collect();
+    summaries.sort_by(|a, b| b.age_secs.cmp(&a.age_secs));
+    summaries
+}
+
+/// Pending events waiting to be published (scheduled notes only once due)
+pub fn get_pending_events_detailed() -> Vec<PendingEventSummary> {
+    let now = chrono::Utc::now().timestamp() as u64;
+    summarize_pending_events(&PENDING_NOSTR_EVENTS.read(), now)
+}
+
 /// Process pending events with adaptive backoff retry logic
 ///
 /// Uses nostr-sdk pattern: adaptive multiplier with jitter to prevent synchronized retries.
+/// Events out of retries are kept for the user (see `get_pending_events_detailed`).
 pub async fn process_pending_events() -> Result<usize, String> {
+    process_pending_events_forcing(None).await
+}
+
+/// Publish one pending event now, ignoring its backoff and retry limit
+pub async fn retry_pending_event_now(event_id: &str) -> Result<(), String> {
+    if !PENDING_NOSTR_EVENTS.read().iter().any(|event| event.id == event_id) {
+        return Err("Pending event not found".to_string());
+    }
+
+    process_pending_events_forcing(Some(event_id)).await?;
+
+    if PENDING_NOSTR_EVENTS.read().iter().any(|event| event.id == event_id) {
+        return Err("Publishing failed again, check your relay connections".to_string());
+    }
+    Ok(())
+}
+
+async fn process_pending_events_forcing(force_id: Option<&str>) -> Result<usize, String> {
     // Early check for client initialization (pattern from Nostr SDK)
     // Avoids per-event failures and retry count increments when client isn't ready
     if nostr_client::NOSTR_CLIENT.read().as_ref().is_none() {
@@ -677,36 +839,27 @@ pub async fn process_pending_events() -> Result<usize, String> {
         return Ok(0);
     }
 
-    const MAX_RETRIES: u32 = 5;
-    const BASE_RETRY_DELAY_SECS: u64 = 10;
-    const MAX_RETRY_DELAY_SECS: u64 = 60;
-
-    let pending_events = PENDING_NOSTR_EVENTS.read().clone();
+    let now = chrono::Utc::now().timestamp() as u64;
+    let pending_events: Vec<PendingNostrEvent> = due_events(&PENDING_NOSTR_EVENTS.read(), now)
+        .into_iter()
+        .filter(|event| force_id.is_none_or(|id| event.id == id))
+        .collect();
     let mut processed_count = 0;
 
     log::info!("Processing {} pending events", pending_events.len());
 
     for event in pending_events {
-        if event.retry_count >= MAX_RETRIES {
-            log::warn!("Event {} exceeded max retries, removing", event.id);
-            let _ = remove_pending_event(&event.id).await;
-            continue;
-        }
-
         let now = chrono::Utc::now().timestamp() as u64;
-        // Use last_retry_at if available, otherwise created_at (for first attempt)
-        let last_attempt = event.last_retry_at.unwrap_or(event.created_at);
-        let elapsed = now.saturating_sub(last_attempt);
-
-        // Adaptive backoff: BASE_DELAY * (1 + retry_count/2), capped at MAX_DELAY
-        // This gives delays of: 10s, 15s, 20s, 25s, 30s (capped)
-        let multiplier = 1 + (event.retry_count / 2);
-        let adaptive_delay = BASE_RETRY_DELAY_SECS * (multiplier as u64);
-        let retry_delay = adaptive_delay.min(MAX_RETRY_DELAY_SECS);
-
-        if elapsed < retry_delay {
-            log::debug!("Event {} not ready for retry yet ({}s < {}s)", event.id, elapsed, retry_delay);
-            continue;
+        match retry_decision(&event, now, force_id.is_some()) {
+            RetryDecision::Publish => {}
+            RetryDecision::Wait { remaining_secs } => {
+                log::debug!("Event {} not ready for retry yet ({}s left)", event.id, remaining_secs);
+                continue;
+            }
+            RetryDecision::NeedsAttention => {
+                log::debug!("Event {} exceeded max retries, waiting for the user", event.id);
+                continue;
+            }
         }
 
         match publish_pending_event(&event).await {
@@ -787,3 +940,78 @@ pub fn start_pending_events_processor() {
 pub fn start_pending_events_processor() {
     log::debug!("Pending events processor only runs in WASM");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending(id: &str, publish_at: Option<u64>) -> PendingNostrEvent {
+        PendingNostrEvent {
+            id: id.to_string(),
+            builder_json: String::new(),
+            event_type: if publish_at.is_some() {
+                PendingEventType::ScheduledNote
+            } else {
+                PendingEventType::TokenEvent
+            },
+            created_at: 100,
+            retry_count: 0,
+            last_retry_at: None,
+            publish_at,
+            relays: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_due_events_selects_only_due() {
+        let events = vec![
+            pending("retry", None),
+            pending("past", Some(500)),
+            pending("now", Some(1_000)),
+            pending("future", Some(1_001)),
+        ];
+
+        let due: Vec<String> = due_events(&events, 1_000).into_iter().map(|e| e.id).collect();
+        assert_eq!(due, vec!["retry", "past", "now"]);
+    }
+
+    fn failed(id: &str, retry_count: u32, last_retry_at: u64) -> PendingNostrEvent {
+        PendingNostrEvent {
+            retry_count,
+            last_retry_at: Some(last_retry_at),
+            ..pending(id, None)
+        }
+    }
+
+    #[test]
+    fn test_force_retry_bypasses_backoff() {
+        // Failed 5s ago; the backoff asks for 10s
+        let event = failed("backing-off", 1, 995);
+        assert_eq!(retry_decision(&event, 1_000, false), RetryDecision::Wait { remaining_secs: 5 });
+        assert_eq!(retry_decision(&event, 1_000, true), RetryDecision::Publish);
+        assert_eq!(retry_decision(&event, 1_005, false), RetryDecision::Publish);
+
+        // Out of retries: kept for the user rather than dropped, who may still force it
+        let exhausted = failed("exhausted", MAX_RETRIES, 0);
+        assert_eq!(retry_decision(&exhausted, 1_000, false), RetryDecision::NeedsAttention);
+        assert_eq!(retry_decision(&exhausted, 1_000, true), RetryDecision::Publish);
+    }
+
+    #[test]
+    fn test_summarize_pending_events() {
+        let events = vec![
+            failed("backing-off", 1, 995),
+            failed("exhausted", MAX_RETRIES, 500),
+            pending("future", Some(2_000)),
+        ];
+
+        let summaries = summarize_pending_events(&events, 1_000);
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].age_secs, 900);
+        assert_eq!(summaries[0].next_retry_in_secs, Some(5));
+        assert!(!summaries[0].needs_attention);
+        assert!(summaries[1].needs_attention);
+        assert_eq!(summaries[1].next_retry_in_secs, None);
+        assert_eq!(summaries[1].kind_label, "token");
+    }
+}
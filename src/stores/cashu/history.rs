@@ -4,7 +4,6 @@
 //! Supports incremental sync to avoid re-fetching all events.
 
 use std::collections::HashSet;
-use std::time::Duration;
 
 use dioxus::prelude::*;
 use nostr_sdk::nips::nip60::TransactionDirection;
@@ -78,7 +77,7 @@ pub async fn fetch_history() -> Result<(), String> {
         Filter::new().author(pubkey).kind(Kind::from(7376))
     };
 
-    match client.fetch_events(filter, Duration::from_secs(10)).await {
+    match nostr_client::fetch_events_with_timeout(filter, nostr_client::default_fetch_timeout()).await {
         Ok(events) => {
             let signer = crate::stores::signer::get_signer()
                 .ok_or("No signer available")?
@@ -88,7 +87,7 @@ pub async fn fetch_history() -> Result<(), String> {
 
             for event in events {
                 // Decrypt history event using signer
-                match signer.nip44_decrypt(&event.pubkey, &event.content).await {
+                match crate::stores::signer::decrypt_with_fallback(&*signer, &event.pubkey, &event.content).await {
                     Ok(decrypted) => {
                         // Parse JSON array: [["direction", "in"], ["amount", "100"], ["e", "id", "", "created"], ...]
                         match serde_json::from_str::<Vec<Vec<String>>>(&decrypted) {
@@ -12,22 +12,23 @@ use nostr_sdk::{Event, EventBuilder, Filter, Kind, PublicKey, SecretKey, Tag, Ur
 use super::events::{fetch_tokens, start_pending_events_processor};
 use super::history::fetch_history;
 use super::internal::{init_multi_mint_wallet, inject_nip60_proofs_to_cdk};
+use super::mint_mgmt::load_mint_reliability;
+use super::payment_request::sweep_stored_payment_requests;
+use super::pending_melt::resume_pending_melts;
+use super::proof_watch::start_proof_state_watch;
 use super::recovery::{recover_pending_operations, sync_state_with_all_mints};
 use super::signals::{TERMS_ACCEPTED, TERMS_D_TAG, WALLET_STATE, WALLET_STATUS};
 use super::types::{WalletState, WalletStatus};
-use super::utils::normalize_mint_url;
+use super::utils::dedup_mint_urls;
 use crate::stores::{auth_store, cashu_cdk_bridge, nostr_client};
 
 // NIP-60 Wallet event structure
 struct WalletEvent {
     privkey: String,
-    mints: Vec<Url>,
-}
-
-impl WalletEvent {
-    fn new(privkey: String, mints: Vec<Url>) -> Self {
-        Self { privkey, mints }
-    }
+    /// Normalized and deduplicated
+    mints: Vec<String>,
+    /// The event lists mints that aren't normalized, or the same mint twice
+    needs_migration: bool,
 }
 
 // =============================================================================
@@ -157,7 +158,7 @@ pub async fn init_wallet() -> Result<(), String> {
     // Ensure relays are ready before fetching
     nostr_client::ensure_relays_ready(&client).await;
 
-    match client.fetch_events(filter, Duration::from_secs(10)).await {
+    match nostr_client::fetch_events_with_timeout(filter, nostr_client::default_fetch_timeout()).await {
         Ok(events) => {
             if let Some(wallet_event) = events.into_iter().next() {
                 // Decrypt and parse wallet event
@@ -167,13 +168,27 @@ pub async fn init_wallet() -> Result<(), String> {
 
                         *WALLET_STATE.write() = Some(WalletState {
                             privkey: Some(wallet_data.privkey.clone()),
-                            // Normalize mint URLs on load to ensure consistent lookups
-                            mints: wallet_data.mints.iter().map(|u| normalize_mint_url(&u.to_string())).collect(),
+                            mints: wallet_data.mints.clone(),
                             initialized: true,
                         });
 
+                        // Rewrite an event from before mint URLs were normalized
+                        if wallet_data.needs_migration {
+                            let privkey = wallet_data.privkey.clone();
+                            let mints = wallet_data.mints.clone();
+                            spawn(async move {
+                                match send_wallet_event(&privkey, &mints).await {
+                                    Ok(()) => log::info!("Migrated wallet event to normalized mint URLs"),
+                                    Err(e) => log::warn!("Failed to migrate wallet event mint URLs: {}", e),
+                                }
+                            });
+                        }
+
                         // Initialize MultiMintWallet with all mints
-                        if let Err(e) = init_multi_mint_wallet(&wallet_data.mints).await {
+                        let mint_urls: Vec<Url> = wallet_data.mints.iter()
+                            .filter_map(|m| Url::parse(m).ok())
+                            .collect();
+                        if let Err(e) = init_multi_mint_wallet(&mint_urls).await {
                             log::error!("Failed to initialize MultiMintWallet: {}", e);
                             // Continue without MultiMintWallet - fallback to legacy mode
                         }
@@ -207,6 +222,9 @@ pub async fn init_wallet() -> Result<(), String> {
                         // Start background processor for pending events
                         start_pending_events_processor();
 
+                        // Load recorded mint reliability for the mint list and send picker
+                        load_mint_reliability().await;
+
                         // Set status to Recovering while background sync runs
                         *WALLET_STATUS.write() = WalletStatus::Recovering;
 
@@ -229,6 +247,15 @@ pub async fn init_wallet() -> Result<(), String> {
                                 log::warn!("Pending operation recovery failed: {}", e);
                             }
 
+                            // Resume polling lightning payments left pending by a previous session
+                            let resumed = resume_pending_melts().await;
+                            if resumed > 0 {
+                                log::info!("Resumed {} pending lightning payments", resumed);
+                            }
+
+                            // Payments that arrived after their request stopped being watched
+                            sweep_stored_payment_requests().await;
+
                             // Phase 3: Check for paid mint quotes using CDK
                             // This uses CDK's built-in check_all_mint_quotes()
                             if let Some(multi_wallet) = cashu_cdk_bridge::MULTI_WALLET.read().as_ref() {
@@ -248,6 +275,9 @@ pub async fn init_wallet() -> Result<(), String> {
 
                             log::info!("Wallet recovery complete");
                             *WALLET_STATUS.write() = WalletStatus::Ready;
+
+                            // Keep balances live: proofs spent from another device are cleaned up
+                            start_proof_state_watch();
                         });
 
                         Ok(())
@@ -286,71 +316,72 @@ pub async fn create_wallet(mints: Vec<String>) -> Result<(), String> {
         return Err("Wallet already exists. Cannot overwrite existing wallet.".to_string());
     }
 
-    if !*nostr_client::HAS_SIGNER.read() {
-        return Err("No signer attached".to_string());
-    }
-
-    let client = nostr_client::NOSTR_CLIENT
-        .read()
-        .as_ref()
-        .ok_or("Client not initialized")?
-        .clone();
-
-    let signer = crate::stores::signer::get_signer()
-        .ok_or("No signer available")?
-        .as_nostr_signer();
-
-    let pubkey_str = auth_store::get_pubkey().ok_or("Not authenticated")?;
-    let pubkey = PublicKey::parse(&pubkey_str).map_err(|e| format!("Invalid pubkey: {}", e))?;
-
     // Generate new private key for P2PK ecash (separate from Nostr key)
     let wallet_secret = SecretKey::generate();
     let wallet_privkey = wallet_secret.to_secret_hex();
 
     log::info!("Creating new wallet with {} mints", mints.len());
 
-    // Validate and parse mint URLs - fail on any invalid URL
-    let mut mint_urls = Vec::new();
-    let mut invalid_mints = Vec::new();
-    for m in &mints {
-        match Url::parse(m) {
-            Ok(url) => mint_urls.push(url),
-            Err(e) => invalid_mints.push(format!("{}: {}", m, e)),
-        }
-    }
-    if !invalid_mints.is_empty() {
-        return Err(format!("Invalid mint URLs: {}", invalid_mints.join(", ")));
+    publish_wallet(wallet_privkey, mints).await
+}
+
+/// Create a new wallet around an existing P2PK private key
+///
+/// For users coming from another NIP-60 client: tokens locked to the old
+/// wallet key stay spendable. `privkey_hex` is the 64 character hex secret.
+pub async fn create_wallet_with_privkey(privkey_hex: &str, mints: Vec<String>) -> Result<(), String> {
+    // Guard against overwriting existing wallet
+    if is_wallet_initialized() {
+        return Err("Wallet already exists. Cannot overwrite existing wallet.".to_string());
     }
 
-    // Build wallet data following rust-nostr's internal format
-    let wallet_event = WalletEvent::new(wallet_privkey.clone(), mint_urls);
+    let wallet_privkey = parse_wallet_privkey(privkey_hex)?;
 
-    let mut content_array: Vec<Vec<&str>> = vec![vec!["privkey", &wallet_event.privkey]];
-    for mint in wallet_event.mints.iter() {
-        content_array.push(vec!["mint", mint.as_str()]);
+    log::info!("Creating wallet from imported key with {} mints", mints.len());
+
+    publish_wallet(wallet_privkey, mints).await
+}
+
+/// Validate a wallet private key given as hex, returning it in lowercase
+fn parse_wallet_privkey(privkey_hex: &str) -> Result<String, String> {
+    let privkey_hex = privkey_hex.trim();
+    if privkey_hex.len() != 64 || !privkey_hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("Wallet private key must be 64 hex characters".to_string());
     }
 
-    let json_content = serde_json::to_string(&content_array)
-        .map_err(|e| format!("Failed to serialize wallet data: {}", e))?;
+    let secret = SecretKey::from_hex(privkey_hex)
+        .map_err(|e| format!("Invalid wallet private key: {}", e))?;
+    Ok(secret.to_secret_hex())
+}
 
-    // Encrypt content using signer
-    let encrypted_content = signer
-        .nip44_encrypt(&pubkey, &json_content)
-        .await
-        .map_err(|e| format!("Failed to encrypt wallet data: {}", e))?;
+/// Publish the wallet event (kind 17375) for `wallet_privkey` and `mints`
+/// and make it the current wallet
+///
+/// Also used to restore a wallet from a backup, keeping its P2PK key.
+pub(super) async fn publish_wallet(wallet_privkey: String, mints: Vec<String>) -> Result<(), String> {
+    if !*nostr_client::HAS_SIGNER.read() {
+        return Err("No signer attached".to_string());
+    }
 
-    // Build event using rust-nostr kind constant
-    let builder = nostr_sdk::EventBuilder::new(Kind::CashuWallet, encrypted_content);
+    // Validate mint URLs - fail on any invalid URL
+    let invalid_mints: Vec<String> = mints
+        .iter()
+        .filter_map(|m| Url::parse(m).err().map(|e| format!("{}: {}", m, e)))
+        .collect();
+    if !invalid_mints.is_empty() {
+        return Err(format!("Invalid mint URLs: {}", invalid_mints.join(", ")));
+    }
+    let mints = dedup_mint_urls(&mints);
 
     // Publish wallet event
-    match client.send_event_builder(builder).await {
-        Ok(_) => {
+    match send_wallet_event(&wallet_privkey, &mints).await {
+        Ok(()) => {
             log::info!("Wallet created successfully");
 
             // Update local state
             *WALLET_STATE.write() = Some(WalletState {
                 privkey: Some(wallet_privkey),
-                mints: mints.clone(),
+                mints,
                 initialized: true,
             });
 
@@ -365,6 +396,52 @@ pub async fn create_wallet(mints: Vec<String>) -> Result<(), String> {
     }
 }
 
+/// NIP-60 wallet event content: `[["privkey", "hex"], ["mint", "url"], ...]`
+///
+/// Mint URLs are normalized and written once each.
+pub(super) fn wallet_event_content(privkey: &str, mints: &[String]) -> Result<String, String> {
+    let mut content_array: Vec<Vec<String>> = vec![vec!["privkey".to_string(), privkey.to_string()]];
+    for mint in dedup_mint_urls(mints) {
+        content_array.push(vec!["mint".to_string(), mint]);
+    }
+
+    serde_json::to_string(&content_array)
+        .map_err(|e| format!("Failed to serialize wallet data: {}", e))
+}
+
+/// Encrypt and publish the wallet event (kind 17375)
+pub(super) async fn send_wallet_event(privkey: &str, mints: &[String]) -> Result<(), String> {
+    let client = nostr_client::NOSTR_CLIENT
+        .read()
+        .as_ref()
+        .ok_or("Client not initialized")?
+        .clone();
+
+    let signer = crate::stores::signer::get_signer()
+        .ok_or("No signer available")?
+        .as_nostr_signer();
+
+    let pubkey_str = auth_store::get_pubkey().ok_or("Not authenticated")?;
+    let pubkey = PublicKey::parse(&pubkey_str).map_err(|e| format!("Invalid pubkey: {}", e))?;
+
+    let json_content = wallet_event_content(privkey, mints)?;
+
+    // Encrypt content using signer
+    let encrypted_content = signer
+        .nip44_encrypt(&pubkey, &json_content)
+        .await
+        .map_err(|e| format!("Failed to encrypt wallet data: {}", e))?;
+
+    // Build event using rust-nostr kind constant
+    let builder = nostr_sdk::EventBuilder::new(Kind::CashuWallet, encrypted_content);
+
+    client
+        .send_event_builder(builder)
+        .await
+        .map_err(|e| format!("Failed to publish wallet event: {}", e))?;
+    Ok(())
+}
+
 /// Check if wallet is initialized
 pub fn is_wallet_initialized() -> bool {
     WALLET_STATE
@@ -374,6 +451,47 @@ pub fn is_wallet_initialized() -> bool {
         .unwrap_or(false)
 }
 
+/// Fail while the wallet database holds work that exists nowhere else
+///
+/// Wallet events that haven't reached relays yet and Lightning payments
+/// still in flight are only tracked on this device; wiping them would lose
+/// the funds they account for.
+pub async fn check_wallet_wipe() -> Result<(), String> {
+    let localstore = super::internal::get_shared_localstore().await?;
+    let events = localstore
+        .get_all_pending_events()
+        .await
+        .map_err(|e| format!("Failed to read pending events: {}", e))?
+        .len();
+    let melts = localstore
+        .get_all_pending_melts()
+        .await
+        .map_err(|e| format!("Failed to read pending payments: {}", e))?
+        .len();
+
+    if events > 0 || melts > 0 {
+        return Err(format!(
+            "{} wallet updates haven't reached your relays and {} Lightning payments are still in flight. Wait for them to finish before wiping local data.",
+            events, melts
+        ));
+    }
+    Ok(())
+}
+
+/// Delete everything the wallet keeps in IndexedDB on this device
+///
+/// Proofs, quotes, transactions and pending events. The
+/// NIP-60 events on relays are untouched, so the wallet can be restored by
+/// logging in again. Check [`check_wallet_wipe`] first. In-memory state is
+/// reset separately with `reset_wallet_state`.
+pub async fn wipe_wallet_database() -> Result<(), String> {
+    let localstore = super::internal::get_shared_localstore().await?;
+    localstore
+        .clear_all()
+        .await
+        .map_err(|e| format!("Failed to clear wallet database: {}", e))
+}
+
 // =============================================================================
 // Internal Helpers
 // =============================================================================
@@ -386,9 +504,8 @@ async fn decrypt_wallet_event(event: &Event) -> Result<WalletEvent, String> {
         .ok_or("No signer available")?
         .as_nostr_signer();
 
-    // Decrypt the content using signer's NIP-44 method
-    let decrypted = signer
-        .nip44_decrypt(&event.pubkey, &event.content)
+    // NIP-44, or NIP-04 for wallets written by older clients
+    let decrypted = crate::stores::signer::decrypt_with_fallback(&*signer, &event.pubkey, &event.content)
         .await
         .map_err(|e| format!("Failed to decrypt wallet event: {}", e))?;
 
@@ -417,7 +534,7 @@ async fn decrypt_wallet_event(event: &Event) -> Result<WalletEvent, String> {
                 }
             }
             "mint" => match Url::parse(&pair[1]) {
-                Ok(mint_url) => mints.push(mint_url),
+                Ok(_) => mints.push(pair[1].clone()),
                 Err(e) => {
                     log::warn!("Skipping invalid mint URL '{}': {}", pair[1], e);
                 }
@@ -439,7 +556,12 @@ async fn decrypt_wallet_event(event: &Event) -> Result<WalletEvent, String> {
         return Err("Missing required field: mint (at least one mint URL required)".to_string());
     }
 
-    Ok(WalletEvent::new(privkey, mints))
+    let normalized = dedup_mint_urls(&mints);
+    Ok(WalletEvent {
+        privkey,
+        needs_migration: normalized != mints,
+        mints: normalized,
+    })
 }
 
 /// Load pending events from IndexedDB on startup
@@ -479,3 +601,57 @@ async fn load_pending_events() -> Result<(), String> {
     log::info!("Pending events loaded and ready for retry processing");
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::send::get_wallet_pubkey;
+
+    #[test]
+    fn test_imported_privkey_gives_expected_pubkey() {
+        let mut dom = VirtualDom::new(|| rsx! {});
+        dom.rebuild_in_place();
+
+        dom.in_runtime(|| {
+            let privkey = parse_wallet_privkey(
+                "  0000000000000000000000000000000000000000000000000000000000000001 ",
+            )
+            .unwrap();
+            *WALLET_STATE.write() = Some(WalletState {
+                privkey: Some(privkey),
+                mints: vec![],
+                initialized: true,
+            });
+
+            // Secret key 1 is the curve generator
+            assert_eq!(
+                get_wallet_pubkey().unwrap(),
+                "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+            );
+        });
+
+        assert!(parse_wallet_privkey("nsec1abc").is_err());
+        assert!(parse_wallet_privkey(&"0".repeat(64)).is_err());
+        assert!(parse_wallet_privkey(&"f".repeat(63)).is_err());
+        assert_eq!(
+            parse_wallet_privkey(&"AB".repeat(32)).unwrap(),
+            "ab".repeat(32)
+        );
+    }
+
+    #[test]
+    fn test_wallet_event_content_writes_each_mint_once() {
+        let mints = vec![
+            "https://mint.example.com/".to_string(),
+            "https://Mint.Example.com".to_string(),
+            "other.mint.com".to_string(),
+        ];
+        let content = wallet_event_content("ab", &mints).unwrap();
+        let pairs: Vec<Vec<String>> = serde_json::from_str(&content).unwrap();
+        assert_eq!(pairs, vec![
+            vec!["privkey".to_string(), "ab".to_string()],
+            vec!["mint".to_string(), "https://mint.example.com".to_string()],
+            vec!["mint".to_string(), "https://other.mint.com".to_string()],
+        ]);
+    }
+}
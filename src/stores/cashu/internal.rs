@@ -359,11 +359,108 @@ pub(crate) async fn validate_proofs_with_mint(
     Ok(proofs)
 }
 
+/// Default number of proofs sent per NUT-07 state check during cleanup
+///
+/// Large wallets checked in a single request frequently time out on slower mints.
+pub(crate) const PROOF_STATE_CHUNK_SIZE: usize = 100;
+
+/// Merge per-chunk NUT-07 results back into one state per proof, in order
+///
+/// `chunk_lens` gives the number of proofs sent in each chunk. Proofs from a
+/// chunk that failed (or returned the wrong number of states) get `None`.
+/// Returns the merged states and one error message per failed chunk.
+pub(crate) fn merge_chunked_states(
+    chunk_results: Vec<Result<Vec<State>, String>>,
+    chunk_lens: &[usize],
+) -> (Vec<Option<State>>, Vec<String>) {
+    let mut states = Vec::with_capacity(chunk_lens.iter().sum());
+    let mut errors = Vec::new();
+
+    for (idx, (result, &len)) in chunk_results.into_iter().zip(chunk_lens.iter()).enumerate() {
+        match result {
+            Ok(chunk_states) if chunk_states.len() == len => {
+                states.extend(chunk_states.into_iter().map(Some));
+            }
+            Ok(chunk_states) => {
+                errors.push(format!(
+                    "Chunk {}: mint returned {} states for {} proofs",
+                    idx,
+                    chunk_states.len(),
+                    len
+                ));
+                states.extend(std::iter::repeat(None).take(len));
+            }
+            Err(e) => {
+                errors.push(format!("Chunk {}: {}", idx, e));
+                states.extend(std::iter::repeat(None).take(len));
+            }
+        }
+    }
+
+    (states, errors)
+}
+
+/// Find proofs that are no longer available given their mint states
+///
+/// Spent and pending proofs are always unavailable. Reserved proofs are only
+/// unavailable when they have no active transaction (orphaned reserves).
+/// Proofs without a known state are kept. Returns (secrets, total_amount).
+pub(crate) fn classify_unavailable_proofs(
+    states: &[Option<State>],
+    proofs: &[ProofData],
+) -> (std::collections::HashSet<String>, u64) {
+    let mut unavailable_secrets = std::collections::HashSet::new();
+    let mut unavailable_amount = 0u64;
+
+    for (state, proof) in states.iter().zip(proofs.iter()) {
+        match state {
+            Some(State::Spent) | Some(State::Pending) => {
+                // Always remove spent and pending proofs
+                unavailable_secrets.insert(proof.secret.clone());
+                unavailable_amount += proof.amount;
+            }
+            Some(State::Reserved) => {
+                // Only remove Reserved proofs if they have no active transaction
+                // (orphaned reserves). Proofs with transaction_id are part of
+                // active operations and should not be deleted.
+                if proof.transaction_id.is_none() {
+                    // Log amount instead of secret (CDK pattern: never log secrets)
+                    log::debug!(
+                        "Removing orphaned reserved proof (no transaction_id): {} sats",
+                        proof.amount
+                    );
+                    unavailable_secrets.insert(proof.secret.clone());
+                    unavailable_amount += proof.amount;
+                } else {
+                    log::debug!(
+                        "Preserving reserved proof with active transaction: {} sats",
+                        proof.amount
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+
+    (unavailable_secrets, unavailable_amount)
+}
+
 /// Internal cleanup function for spent proofs
 ///
 /// Checks proof states with mint and removes spent/reserved/pending proofs.
 /// Returns (count_cleaned, sats_cleaned).
 pub(crate) async fn cleanup_spent_proofs_internal(mint_url: &str) -> Result<(usize, u64), String> {
+    cleanup_spent_proofs_chunked(mint_url, PROOF_STATE_CHUNK_SIZE).await
+}
+
+/// Cleanup spent proofs, checking states with the mint in concurrent chunks
+///
+/// A chunk that fails to check is skipped (its proofs are kept) rather than
+/// aborting the whole cleanup; only a failure of every chunk is an error.
+pub(crate) async fn cleanup_spent_proofs_chunked(
+    mint_url: &str,
+    chunk_size: usize,
+) -> Result<(usize, u64), String> {
     use nostr_sdk::signer::NostrSigner;
     use nostr_sdk::{EventId, Kind, PublicKey};
 
@@ -398,50 +495,39 @@ pub(crate) async fn cleanup_spent_proofs_internal(mint_url: &str) -> Result<(usi
     // Create ephemeral wallet
     let wallet = create_ephemeral_wallet(mint_url, vec![]).await?;
 
-    // Check states at mint
-    let states = wallet
-        .check_proofs_spent(cdk_proofs.clone())
-        .await
-        .map_err(|e| format!("Failed to check proof states: {}", e))?;
+    // Check states at mint in concurrent chunks (NUT-07)
+    let chunk_size = chunk_size.max(1);
+    let chunk_lens: Vec<usize> = cdk_proofs.chunks(chunk_size).map(|c| c.len()).collect();
+    let chunk_futures = cdk_proofs.chunks(chunk_size).map(|chunk| {
+        let wallet = &wallet;
+        async move {
+            wallet
+                .check_proofs_spent(chunk.to_vec())
+                .await
+                .map(|states| states.into_iter().map(|s| s.state).collect::<Vec<_>>())
+                .map_err(|e| e.to_string())
+        }
+    });
+    let chunk_results = futures::future::join_all(chunk_futures).await;
 
-    // Find unavailable proofs (spent, pending, or orphaned reserves)
-    let mut unavailable_secrets = std::collections::HashSet::new();
-    let mut unavailable_amount = 0u64;
+    let (states, errors) = merge_chunked_states(chunk_results, &chunk_lens);
 
-    for ((state, proof), local_proof) in states
-        .iter()
-        .zip(cdk_proofs.iter())
-        .zip(all_mint_proofs.iter())
-    {
-        match state.state {
-            State::Spent | State::Pending => {
-                // Always remove spent and pending proofs
-                unavailable_secrets.insert(proof.secret.to_string());
-                unavailable_amount += u64::from(proof.amount);
-            }
-            State::Reserved => {
-                // Only remove Reserved proofs if they have no active transaction
-                // (orphaned reserves). Proofs with transaction_id are part of
-                // active operations and should not be deleted.
-                if local_proof.transaction_id.is_none() {
-                    // Log amount instead of secret (CDK pattern: never log secrets)
-                    log::debug!(
-                        "Removing orphaned reserved proof (no transaction_id): {} sats",
-                        u64::from(proof.amount)
-                    );
-                    unavailable_secrets.insert(proof.secret.to_string());
-                    unavailable_amount += u64::from(proof.amount);
-                } else {
-                    log::debug!(
-                        "Preserving reserved proof with active transaction: {} sats",
-                        u64::from(proof.amount)
-                    );
-                }
-            }
-            _ => {}
+    if !errors.is_empty() {
+        if errors.len() == chunk_lens.len() {
+            return Err(format!("Failed to check proof states: {}", errors.join("; ")));
         }
+        log::warn!(
+            "{} of {} proof state checks failed, keeping those proofs for now: {}",
+            errors.len(),
+            chunk_lens.len(),
+            errors.join("; ")
+        );
     }
 
+    // Find unavailable proofs (spent, pending, or orphaned reserves)
+    let (unavailable_secrets, unavailable_amount) =
+        classify_unavailable_proofs(&states, &all_mint_proofs);
+
     if unavailable_secrets.is_empty() {
         log::info!("No spent/reserved/pending proofs found");
         return Ok((0, 0));
@@ -952,3 +1038,87 @@ pub(crate) async fn try_swap_or_recover(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_proof(i: usize, transaction_id: Option<u64>) -> ProofData {
+        ProofData {
+            transaction_id,
+            ..ProofData::for_test(1 << (i % 8), format!("secret-{}", i))
+        }
+    }
+
+    fn mixed_states(count: usize) -> Vec<State> {
+        (0..count)
+            .map(|i| match i % 4 {
+                0 => State::Unspent,
+                1 => State::Spent,
+                2 => State::Pending,
+                _ => State::Reserved,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_chunked_states_match_single_call() {
+        let proofs: Vec<ProofData> = (0..250)
+            .map(|i| make_proof(i, if i % 8 == 3 { Some(42) } else { None }))
+            .collect();
+        let mint_states = mixed_states(proofs.len());
+
+        // Baseline: one request for all proofs
+        let (single, single_errors) =
+            merge_chunked_states(vec![Ok(mint_states.clone())], &[proofs.len()]);
+        assert!(single_errors.is_empty());
+        let baseline = classify_unavailable_proofs(&single, &proofs);
+
+        // Chunked: 100 + 100 + 50
+        let chunk_lens: Vec<usize> = proofs.chunks(PROOF_STATE_CHUNK_SIZE).map(|c| c.len()).collect();
+        assert_eq!(chunk_lens, vec![100, 100, 50]);
+        let chunk_results = mint_states
+            .chunks(PROOF_STATE_CHUNK_SIZE)
+            .map(|c| Ok(c.to_vec()))
+            .collect();
+        let (merged, errors) = merge_chunked_states(chunk_results, &chunk_lens);
+
+        assert!(errors.is_empty());
+        assert_eq!(merged, single);
+        assert_eq!(classify_unavailable_proofs(&merged, &proofs), baseline);
+    }
+
+    #[test]
+    fn test_failed_chunk_keeps_its_proofs() {
+        let proofs: Vec<ProofData> = (0..250).map(|i| make_proof(i, None)).collect();
+        let mint_states = mixed_states(proofs.len());
+        let chunk_lens = vec![100, 100, 50];
+
+        let chunk_results = vec![
+            Ok(mint_states[..100].to_vec()),
+            Err("timeout".to_string()),
+            Ok(mint_states[200..].to_vec()),
+        ];
+        let (merged, errors) = merge_chunked_states(chunk_results, &chunk_lens);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(merged.len(), 250);
+        assert!(merged[100..200].iter().all(|s| s.is_none()));
+
+        let (secrets, _) = classify_unavailable_proofs(&merged, &proofs);
+        assert!(proofs[100..200].iter().all(|p| !secrets.contains(&p.secret)));
+        assert!(secrets.contains(&proofs[1].secret));
+        assert!(secrets.contains(&proofs[201].secret));
+    }
+
+    #[test]
+    fn test_reserved_with_transaction_is_kept() {
+        let proofs = vec![make_proof(0, Some(7)), make_proof(1, None)];
+        let states = vec![Some(State::Reserved), Some(State::Reserved)];
+
+        let (secrets, amount) = classify_unavailable_proofs(&states, &proofs);
+        assert_eq!(secrets.len(), 1);
+        assert!(secrets.contains(&proofs[1].secret));
+        assert_eq!(amount, proofs[1].amount);
+    }
+}
@@ -10,7 +10,7 @@ use std::collections::HashMap;
 use std::str::FromStr;
 
 use cdk::nuts::Id;
-use dioxus::prelude::ReadableExt;
+use dioxus::prelude::*;
 
 use super::cache::{CachedKeyset, MINT_CACHE};
 use super::signals::WALLET_TOKENS;
@@ -274,19 +274,37 @@ pub async fn refresh_keysets(mint_url: &str) -> Result<KeysetRefreshResult, Stri
 // Keyset Migration
 // =============================================================================
 
+/// Proofs swapped per request while migrating
+pub const MIGRATION_BATCH_SIZE: usize = 20;
+
+/// Progress of the running migration: (proofs migrated, proofs to migrate)
+pub static KEYSET_MIGRATION_PROGRESS: GlobalSignal<Option<(usize, usize)>> =
+    Signal::global(|| None);
+
 /// Migrate proofs from inactive keysets to the active keyset
 ///
-/// This swaps all proofs from rotated/inactive keysets to the current active keyset.
+/// This swaps all proofs from rotated/inactive keysets to the current active keyset,
+/// [`MIGRATION_BATCH_SIZE`] at a time, reporting progress in
+/// [`KEYSET_MIGRATION_PROGRESS`]. If a batch fails, the batches already swapped
+/// stay migrated and the remaining proofs are left untouched on their old
+/// keyset, so running the migration again picks up where it stopped.
 /// Should be called after detecting keyset rotation to prevent loss of funds.
 pub async fn migrate_inactive_proofs(mint_url: &str) -> Result<KeysetMigrationResult, String> {
     use super::proofs::proof_data_to_cdk_proof;
     use super::internal::get_or_create_wallet;
+    use super::signals::try_acquire_mint_lock;
+    use super::utils::normalize_mint_url;
+    use crate::stores::cashu_cdk_bridge;
     use cdk::amount::SplitTarget;
 
-    log::info!("Migrating proofs from inactive keysets for mint: {}", mint_url);
+    let normalized = normalize_mint_url(mint_url);
+    log::info!("Migrating proofs from inactive keysets for mint: {}", normalized);
+
+    let _lock = try_acquire_mint_lock(&normalized)
+        .ok_or_else(|| format!("Another operation in progress for {}", normalized))?;
 
     // Get proofs to migrate
-    let proofs_to_migrate = get_proofs_to_migrate(mint_url);
+    let proofs_to_migrate = get_proofs_to_migrate(&normalized);
 
     if proofs_to_migrate.is_empty() {
         log::info!("No proofs to migrate");
@@ -310,55 +328,100 @@ pub async fn migrate_inactive_proofs(mint_url: &str) -> Result<KeysetMigrationRe
         total_value
     );
 
-    // Convert to CDK proofs
-    let cdk_proofs: Vec<cdk::nuts::Proof> = proofs_to_migrate.iter()
-        .filter_map(|p| proof_data_to_cdk_proof(p).ok())
-        .collect();
-
-    if cdk_proofs.len() != proof_count {
-        log::warn!(
-            "Some proofs failed to convert: {} of {}",
-            proof_count - cdk_proofs.len(),
-            proof_count
-        );
-    }
-
     // Get wallet and swap proofs
-    let wallet = get_or_create_wallet(mint_url).await?;
+    let wallet = get_or_create_wallet(&normalized).await?;
 
     // Get active keyset
     let active_keyset = wallet.get_active_keyset().await
         .map_err(|e| format!("Failed to get active keyset: {}", e))?;
 
-    // Swap all proofs to active keyset
-    // This uses CDK's internal swap which outputs to the active keyset
-    let swap_result = wallet.swap(
-        None, // No specific amount, swap all
-        SplitTarget::default(),
-        cdk_proofs.clone().into(),
-        None, // No spending conditions
-        true, // Include fee
-    ).await
-    .map_err(|e| format!("Swap failed: {}", e))?;
+    let mut proofs_migrated = 0usize;
+    let mut input_migrated = 0u64;
+    let mut value_migrated = 0u64;
+    let mut failure = None;
+    *KEYSET_MIGRATION_PROGRESS.write() = Some((0, proof_count));
+
+    for batch in proofs_to_migrate.chunks(MIGRATION_BATCH_SIZE) {
+        // Convert to CDK proofs - a batch that can't be converted is not swapped at all
+        let cdk_proofs = match batch
+            .iter()
+            .map(proof_data_to_cdk_proof)
+            .collect::<Result<Vec<cdk::nuts::Proof>, _>>()
+        {
+            Ok(proofs) => proofs,
+            Err(e) => {
+                failure = Some(format!("Failed to convert proof: {}", e));
+                break;
+            }
+        };
+
+        // Swap the batch to the active keyset
+        // This uses CDK's internal swap which outputs to the active keyset
+        let swap_result = wallet.swap(
+            None, // No specific amount, swap all
+            SplitTarget::default(),
+            cdk_proofs.into(),
+            None, // No spending conditions
+            true, // Include fee
+        ).await;
+
+        match swap_result {
+            Ok(output) => {
+                proofs_migrated += batch.len();
+                input_migrated = batch
+                    .iter()
+                    .map(|p| p.amount)
+                    .fold(input_migrated, |acc, amt| acc.saturating_add(amt));
+                value_migrated = output
+                    .iter()
+                    .flatten()
+                    .map(|p| u64::from(p.amount))
+                    .fold(value_migrated, |acc, amt| acc.saturating_add(amt));
+                *KEYSET_MIGRATION_PROGRESS.write() = Some((proofs_migrated, proof_count));
+            }
+            Err(e) => {
+                failure = Some(format!("Swap failed: {}", e));
+                break;
+            }
+        }
+    }
 
-    // Calculate fee paid
-    let output_value: u64 = swap_result.as_ref()
-        .map(|proofs| proofs.iter().map(|p| u64::from(p.amount)).fold(0u64, |acc, amt| acc.saturating_add(amt)))
-        .unwrap_or(0);
+    *KEYSET_MIGRATION_PROGRESS.write() = None;
 
-    let fee_paid = total_value.saturating_sub(output_value);
+    // Sync wallet state so the UI shows the batches that went through
+    if proofs_migrated > 0 {
+        if let Err(e) = cashu_cdk_bridge::sync_wallet_state().await {
+            log::warn!("Failed to sync wallet state after keyset migration: {}", e);
+        }
+    }
+
+    if let Some(e) = failure {
+        log::error!(
+            "Keyset migration stopped after {} of {} proofs: {}",
+            proofs_migrated,
+            proof_count,
+            e
+        );
+        return Err(format!(
+            "{} ({} of {} proofs migrated, the rest are unchanged)",
+            e, proofs_migrated, proof_count
+        ));
+    }
+
+    // Calculate fee paid
+    let fee_paid = input_migrated.saturating_sub(value_migrated);
 
     log::info!(
         "Migration complete: {} proofs ({} sats) migrated to keyset {}, fee: {} sats",
-        proof_count,
-        output_value,
+        proofs_migrated,
+        value_migrated,
         active_keyset.id,
         fee_paid
     );
 
     Ok(KeysetMigrationResult {
-        proofs_migrated: proof_count,
-        value_migrated: output_value,
+        proofs_migrated,
+        value_migrated,
         fee_paid,
         target_keyset: active_keyset.id,
     })
@@ -383,6 +446,36 @@ pub fn get_migration_recommendation(mint_url: &str) -> Option<(usize, u64)> {
     }
 }
 
+/// What the wallet shows about proofs left on rotated keysets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationBanner {
+    /// Offer to move the proofs to the active keyset
+    Migrate { proofs: usize, value: u64, fee: u64 },
+    /// The proofs are worth no more than the swap fee; say so, don't offer
+    NotWorthMigrating { proofs: usize, value: u64 },
+}
+
+/// Map a migration recommendation and its estimated fee to a banner
+pub fn migration_banner(recommendation: Option<(usize, u64)>, estimated_fee: u64) -> Option<MigrationBanner> {
+    let (proofs, value) = recommendation?;
+    if proofs == 0 {
+        None
+    } else if value <= estimated_fee {
+        Some(MigrationBanner::NotWorthMigrating { proofs, value })
+    } else {
+        Some(MigrationBanner::Migrate { proofs, value, fee: estimated_fee })
+    }
+}
+
+/// Banner for a mint, after refreshing its keysets from the mint
+pub async fn check_keyset_rotation(mint_url: &str) -> Option<MigrationBanner> {
+    if let Err(e) = refresh_keysets(mint_url).await {
+        log::warn!("Keyset check failed for {}: {}", mint_url, e);
+    }
+    let fee = calculate_proofs_fee(mint_url, &get_proofs_to_migrate(mint_url));
+    migration_banner(get_migration_recommendation(mint_url), fee)
+}
+
 // =============================================================================
 // Keyset Fee Helpers
 // =============================================================================
@@ -425,4 +518,19 @@ mod tests {
         assert_eq!(active, 0);
         assert_eq!(inactive, 0);
     }
+
+    #[test]
+    fn test_migration_banner_decision() {
+        assert_eq!(migration_banner(None, 0), None);
+        assert_eq!(migration_banner(Some((0, 0)), 0), None);
+        assert_eq!(
+            migration_banner(Some((3, 100)), 1),
+            Some(MigrationBanner::Migrate { proofs: 3, value: 100, fee: 1 })
+        );
+        // Worth exactly the fee: nothing would be left after the swap
+        assert_eq!(
+            migration_banner(Some((2, 2)), 2),
+            Some(MigrationBanner::NotWorthMigrating { proofs: 2, value: 2 })
+        );
+    }
 }
@@ -6,7 +6,12 @@ use dioxus::prelude::*;
 use nostr_sdk::signer::NostrSigner;
 use nostr_sdk::{EventId, Kind, PublicKey};
 
+use super::dust::schedule_dust_sweep;
 use super::events::{publish_quote_event, queue_event_for_retry};
+use super::pending_melt::{
+    change_outputs_between, expire_pending_melt, find_pending_melt, finalize_paid_melt,
+    melt_counter, melt_input_secrets, restore_failed_melt, track_pending_melt,
+};
 use super::recovery::is_quote_about_to_expire;
 use super::internal::{
     cleanup_spent_proofs_internal, create_ephemeral_wallet, is_token_spent_error_string,
@@ -17,12 +22,13 @@ use super::signals::{
     try_acquire_mint_lock, MELT_PROGRESS, PENDING_MELT_QUOTES, PENDING_MINT_QUOTES, WALLET_BALANCE,
     WALLET_TOKENS,
 };
+use super::mint_mgmt::with_mint_reliability;
 use super::types::{
-    ExtendedCashuProof, ExtendedTokenEvent, MeltProgress, MeltQuoteInfo, MintQuoteInfo,
+    ExtendedCashuProof, ExtendedTokenEvent, MeltProgress, MeltQuoteInfo, MintOperation, MintQuoteInfo,
     MintQuoteState, MeltQuoteState, ProofData, TokenData,
     PendingMintQuotesStoreStoreExt, PendingMeltQuotesStoreStoreExt, WalletTokensStoreStoreExt,
 };
-use super::utils::{mint_matches, normalize_mint_url};
+use super::utils::{mint_matches, normalize_mint_url, now_secs};
 use crate::stores::{auth_store, cashu_cdk_bridge, nostr_client};
 use super::types::PendingEventType;
 
@@ -143,9 +149,12 @@ pub async fn mint_tokens_from_quote(mint_url: String, quote_id: String) -> Resul
     log::info!("Quote is paid, proceeding to mint tokens");
 
     // Mint tokens
-    let proofs = match wallet
-        .mint(&quote_id, cdk::amount::SplitTarget::default(), None)
-        .await
+    let proofs = match with_mint_reliability(
+        &mint_url,
+        MintOperation::Mint,
+        wallet.mint(&quote_id, cdk::amount::SplitTarget::default(), None),
+    )
+    .await
     {
         Ok(proofs) => {
             log::info!("Mint succeeded, received {} proofs", proofs.len());
@@ -286,6 +295,8 @@ pub async fn mint_tokens_from_quote(mint_url: String, quote_id: String) -> Resul
 
     log::info!("Mint complete: {} sats", amount_minted);
 
+    schedule_dust_sweep(mint_url);
+
     Ok(amount_minted)
 }
 
@@ -339,7 +350,6 @@ pub async fn create_melt_quote(mint_url: String, invoice: String) -> Result<Melt
 
 /// Check melt quote status
 /// Returns CDK's MeltQuoteState directly for better type safety
-#[allow(dead_code)]
 pub async fn check_melt_quote_status(
     mint_url: String,
     quote_id: String,
@@ -425,15 +435,40 @@ pub async fn melt_tokens(
 
     *MELT_PROGRESS.write() = Some(MeltProgress::PayingInvoice);
 
+    // Counter ahead of the melt, to find its change outputs if it goes pending
+    let counter_before = melt_counter(&mint_url).await;
+
     // Execute melt with auto-retry
     let (melted, keep_proofs) =
         execute_melt_with_retry(&mint_url, &quote_id, all_proofs, amount_needed).await?;
 
-    let paid = melted.state == cdk::nuts::MeltQuoteState::Paid;
+    #[allow(unreachable_patterns)] // Forward compatibility for future CDK states
+    match melted.state {
+        MeltQuoteState::Paid => {}
+        // The mint rejected the payment and kept the inputs unspent
+        MeltQuoteState::Unpaid | MeltQuoteState::Failed => {
+            let error = "Lightning payment failed. Your funds were not spent.".to_string();
+            *MELT_PROGRESS.write() = Some(MeltProgress::Failed {
+                error: error.clone(),
+            });
+            return Err(error);
+        }
+        // The payment hasn't settled yet: keep the quote, lock the inputs and
+        // let the background poller finalize or restore them. History is only
+        // recorded once the payment settles.
+        _ => {
+            let input_secrets = melt_input_secrets(&mint_url, &keep_proofs);
+            let mut quote_info = quote_info;
+            quote_info.change_outputs = change_outputs_between(counter_before, melt_counter(&mint_url).await);
+            track_pending_melt(quote_info, input_secrets).await;
+            return Ok((false, None, 0));
+        }
+    }
+
     let preimage = melted.preimage;
     let fee_paid = u64::from(melted.fee_paid);
 
-    log::info!("Melt result: paid={}, fee_paid={}", paid, fee_paid);
+    log::info!("Melt paid, fee_paid={}", fee_paid);
 
     // Validate fee didn't exceed reserve (warn but don't fail - transaction already completed)
     if fee_paid > quote_info.fee_reserve {
@@ -445,15 +480,11 @@ pub async fn melt_tokens(
         );
     }
 
-    if paid {
-        *MELT_PROGRESS.write() = Some(MeltProgress::Completed {
-            total_paid: quote_info.amount.saturating_add(fee_paid),
-            fee_paid,
-            preimage: preimage.clone(),
-        });
-    } else {
-        *MELT_PROGRESS.write() = Some(MeltProgress::WaitingForConfirmation);
-    }
+    *MELT_PROGRESS.write() = Some(MeltProgress::Completed {
+        total_paid: quote_info.amount.saturating_add(fee_paid),
+        fee_paid,
+        preimage: preimage.clone(),
+    });
 
     // Publish events and update state
     let new_event_id =
@@ -500,13 +531,133 @@ pub async fn melt_tokens(
     }
 
     log::info!(
-        "Melt complete: paid={}, amount={}, fee={}",
-        paid,
+        "Melt complete: amount={}, fee={}",
         quote_info.amount,
         fee_paid
     );
 
-    Ok((paid, preimage, fee_paid))
+    Ok((true, preimage, fee_paid))
+}
+
+// =============================================================================
+// Melt Quote Polling
+// =============================================================================
+
+/// Delay before the first melt quote status check
+const MELT_POLL_INITIAL_DELAY_MS: u32 = 3_000;
+
+/// Upper bound for the delay between melt quote status checks
+const MELT_POLL_MAX_DELAY_MS: u32 = 60_000;
+
+/// What the poller should do after observing a melt quote state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MeltPollAction {
+    /// Payment not resolved yet
+    KeepPolling,
+    /// Payment settled
+    Paid,
+    /// Payment failed (or never started before the quote expired)
+    Failed,
+    /// Quote expired while the payment was still in flight
+    Expired,
+}
+
+/// Tracks a melt quote across polls: backoff and state transitions
+#[derive(Debug, Default)]
+pub(crate) struct MeltQuotePoll {
+    attempts: u32,
+    seen_pending: bool,
+}
+
+impl MeltQuotePoll {
+    /// Delay before the next check: 3s growing by 1.5x per attempt, capped at 60s
+    pub(crate) fn next_delay_ms(&self) -> u32 {
+        let delay = MELT_POLL_INITIAL_DELAY_MS as f64 * 1.5f64.powi(self.attempts.min(16) as i32);
+        delay.min(MELT_POLL_MAX_DELAY_MS as f64) as u32
+    }
+
+    /// Poll for a melt the mint already reported as pending
+    ///
+    /// Unpaid from here on means the payment failed, so the inputs are
+    /// released instead of waiting for the quote to expire.
+    pub(crate) fn after_pending() -> Self {
+        Self { attempts: 0, seen_pending: true }
+    }
+
+    /// Count a failed status check towards the backoff
+    pub(crate) fn record_error(&mut self) {
+        self.attempts = self.attempts.saturating_add(1);
+    }
+
+    /// Decide what to do with the state reported by the mint
+    ///
+    /// Unpaid is only final once the payment was seen in flight (Pending →
+    /// Unpaid means the route failed) or the quote expired without the
+    /// payment starting.
+    pub(crate) fn observe(&mut self, state: MeltQuoteState, now: u64, expiry: Option<u64>) -> MeltPollAction {
+        self.attempts = self.attempts.saturating_add(1);
+        let expired = expiry.map(|e| now >= e).unwrap_or(false);
+
+        #[allow(unreachable_patterns)] // Forward compatibility for future CDK states
+        match state {
+            MeltQuoteState::Paid => MeltPollAction::Paid,
+            MeltQuoteState::Failed => MeltPollAction::Failed,
+            MeltQuoteState::Pending => {
+                self.seen_pending = true;
+                if expired { MeltPollAction::Expired } else { MeltPollAction::KeepPolling }
+            }
+            MeltQuoteState::Unpaid if self.seen_pending || expired => MeltPollAction::Failed,
+            _ if expired => MeltPollAction::Expired,
+            _ => MeltPollAction::KeepPolling,
+        }
+    }
+}
+
+/// Poll a tracked melt quote in the background until the payment resolves
+///
+/// Updates `MELT_PROGRESS` to `Completed`/`Failed` when the mint settles the
+/// quote, and stops once the quote expires. The quote must already be tracked
+/// in `PENDING_MELT_QUOTES` (see `track_pending_melt`).
+pub fn start_melt_quote_poller(quote_id: String, mint_url: String) {
+    spawn(async move {
+        // Only melts the mint reported as pending are tracked
+        let mut poll = MeltQuotePoll::after_pending();
+
+        loop {
+            gloo_timers::future::TimeoutFuture::new(poll.next_delay_ms()).await;
+
+            let Some(quote) = find_pending_melt(&quote_id, &mint_url) else {
+                log::debug!("Melt quote {} no longer tracked, stopping poll", quote_id);
+                break;
+            };
+
+            let state = match check_melt_quote_status(mint_url.clone(), quote_id.clone()).await {
+                Ok(state) => state,
+                Err(e) => {
+                    log::warn!("Melt quote {} status check failed: {}", quote_id, e);
+                    poll.record_error();
+                    continue;
+                }
+            };
+
+            match poll.observe(state, now_secs(), quote.expiry) {
+                MeltPollAction::KeepPolling => {}
+                MeltPollAction::Paid => match finalize_paid_melt(&quote).await {
+                    Ok(()) => break,
+                    // Typically the mint lock is held; retry on the next poll
+                    Err(e) => log::warn!("Deferring settlement of melt {}: {}", quote_id, e),
+                },
+                MeltPollAction::Failed => {
+                    restore_failed_melt(&quote).await;
+                    break;
+                }
+                MeltPollAction::Expired => {
+                    expire_pending_melt(&quote);
+                    break;
+                }
+            }
+        }
+    });
 }
 
 // =============================================================================
@@ -514,7 +665,7 @@ pub async fn melt_tokens(
 // =============================================================================
 
 /// Get proofs and event IDs for a specific mint
-fn get_proofs_and_events_for_mint(
+pub(super) fn get_proofs_and_events_for_mint(
     mint_url: &str,
 ) -> Result<(Vec<cdk::nuts::Proof>, Vec<String>), String> {
     let store = WALLET_TOKENS.read();
@@ -549,7 +700,9 @@ async fn execute_melt_with_retry(
     let result = async {
         let wallet = create_ephemeral_wallet(mint_url, all_proofs.clone()).await?;
 
-        let melted = wallet.melt(quote_id).await.map_err(|e| e.to_string())?;
+        let melted = with_mint_reliability(mint_url, MintOperation::Melt, wallet.melt(quote_id))
+            .await
+            .map_err(|e| e.to_string())?;
         let keep_proofs = wallet.get_unspent_proofs().await.map_err(|e| e.to_string())?;
 
         Ok::<(cdk::types::Melted, Vec<cdk::nuts::Proof>), String>((melted, keep_proofs))
@@ -624,7 +777,7 @@ async fn execute_melt_with_retry(
 }
 
 /// Publish token and deletion events after melt
-async fn publish_melt_events(
+pub(super) async fn publish_melt_events(
     mint_url: &str,
     keep_proofs: &[cdk::nuts::Proof],
     event_ids_to_delete: &[String],
@@ -722,7 +875,7 @@ async fn publish_melt_events(
 }
 
 /// Update local state after melt
-fn update_local_state_after_melt(
+pub(super) fn update_local_state_after_melt(
     mint_url: &str,
     keep_proofs: &[cdk::nuts::Proof],
     event_ids_to_delete: &[String],
@@ -842,3 +995,60 @@ pub async fn create_history_event_with_type(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poll_unpaid_pending_paid() {
+        let mut poll = MeltQuotePoll::default();
+        let expiry = Some(1_000);
+
+        assert_eq!(poll.observe(MeltQuoteState::Unpaid, 100, expiry), MeltPollAction::KeepPolling);
+        assert_eq!(poll.observe(MeltQuoteState::Pending, 110, expiry), MeltPollAction::KeepPolling);
+        assert_eq!(poll.observe(MeltQuoteState::Paid, 120, expiry), MeltPollAction::Paid);
+    }
+
+    #[test]
+    fn test_poll_pending_then_unpaid_fails() {
+        let mut poll = MeltQuotePoll::default();
+
+        assert_eq!(poll.observe(MeltQuoteState::Pending, 100, None), MeltPollAction::KeepPolling);
+        assert_eq!(poll.observe(MeltQuoteState::Unpaid, 110, None), MeltPollAction::Failed);
+    }
+
+    #[test]
+    fn test_poll_tracked_melt_unpaid_releases_inputs() {
+        let mut poll = MeltQuotePoll::after_pending();
+        assert_eq!(poll.observe(MeltQuoteState::Unpaid, 100, None), MeltPollAction::Failed);
+
+        let mut poll = MeltQuotePoll::after_pending();
+        assert_eq!(poll.observe(MeltQuoteState::Pending, 100, Some(1_000)), MeltPollAction::KeepPolling);
+        assert_eq!(poll.observe(MeltQuoteState::Unpaid, 2_000, Some(1_000)), MeltPollAction::Failed);
+    }
+
+    #[test]
+    fn test_poll_stops_after_expiry() {
+        let mut poll = MeltQuotePoll::default();
+        assert_eq!(poll.observe(MeltQuoteState::Pending, 1_000, Some(1_000)), MeltPollAction::Expired);
+
+        // Never started before expiry: nothing left in flight, restore the proofs
+        let mut poll = MeltQuotePoll::default();
+        assert_eq!(poll.observe(MeltQuoteState::Unpaid, 1_000, Some(1_000)), MeltPollAction::Failed);
+    }
+
+    #[test]
+    fn test_poll_backoff_is_capped() {
+        let mut poll = MeltQuotePoll::default();
+        assert_eq!(poll.next_delay_ms(), MELT_POLL_INITIAL_DELAY_MS);
+
+        poll.record_error();
+        assert_eq!(poll.next_delay_ms(), 4_500);
+
+        for _ in 0..50 {
+            poll.record_error();
+        }
+        assert_eq!(poll.next_delay_ms(), MELT_POLL_MAX_DELAY_MS);
+    }
+}
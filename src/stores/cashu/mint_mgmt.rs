@@ -11,15 +11,17 @@ use std::collections::HashMap;
 use std::time::Duration;
 use dioxus::prelude::*;
 use nostr_sdk::{Kind, PublicKey, Filter};
-use super::errors::CashuResult;
+use super::errors::{is_mint_unavailable_error, CashuResult};
+use super::init::send_wallet_event;
 use super::internal::create_ephemeral_wallet;
 use super::proofs::{proof_data_to_cdk_proof, cdk_proof_to_proof_data};
 use super::signals::{
-    COUNTER_BACKUPS, SHARED_LOCALSTORE, WALLET_STATE, WALLET_TOKENS, WALLET_BALANCE,
+    COUNTER_BACKUPS, MINT_RELIABILITY, SHARED_LOCALSTORE, WALLET_STATE, WALLET_TOKENS, WALLET_BALANCE,
     try_acquire_mint_lock,
 };
 use super::types::{
-    CounterBackup, MintInfoDisplay, DiscoveredMint, MintRecommendation, ConsolidationResult,
+    default_unit, CounterBackup, MintInfoDisplay, DiscoveredMint, MintRecommendation, ConsolidationResult,
+    MintOperation, MintReliability, MintReliabilityStats,
     ProofData, TokenData, ExtendedCashuProof, ExtendedTokenEvent, WalletTokensStoreStoreExt,
 };
 use super::utils::{mint_matches, normalize_mint_url};
@@ -182,6 +184,43 @@ pub fn get_mint_balance(mint_url: &str) -> u64 {
         .fold(0u64, |acc, amt| acc.saturating_add(amt))
 }
 
+/// Sum proof amounts grouped by currency unit
+///
+/// Token events written before the unit field existed have an empty unit and
+/// are counted as the default unit ("sat").
+pub fn balance_per_unit(tokens: &[TokenData]) -> HashMap<String, u64> {
+    let mut balances: HashMap<String, u64> = HashMap::new();
+
+    for token in tokens {
+        let unit = if token.unit.trim().is_empty() {
+            default_unit()
+        } else {
+            token.unit.trim().to_lowercase()
+        };
+        let amount = token.proofs
+            .iter()
+            .map(|p| p.amount)
+            .fold(0u64, |acc, amt| acc.saturating_add(amt));
+
+        let entry = balances.entry(unit).or_insert(0);
+        *entry = entry.saturating_add(amount);
+    }
+
+    balances
+}
+
+/// Get wallet balance per currency unit (e.g. "sat", "usd")
+///
+/// `WALLET_BALANCE` sums every proof regardless of unit; use this when the
+/// wallet may hold tokens from non-sat keysets.
+pub fn get_balance_per_unit() -> HashMap<String, u64> {
+    let store = WALLET_TOKENS.read();
+    let data = store.data();
+    let tokens = data.read();
+
+    balance_per_unit(&tokens)
+}
+
 /// Get proof count for a specific mint
 pub fn get_mint_proof_count(mint_url: &str) -> usize {
     let store = WALLET_TOKENS.read();
@@ -253,6 +292,175 @@ pub async fn get_mint_info(mint_url: &str) -> Result<MintInfoDisplay, String> {
     })
 }
 
+// =============================================================================
+// Mint Reliability
+// =============================================================================
+
+/// Fewer recorded operations than this show as "insufficient data"
+pub const MIN_RELIABILITY_SAMPLES: u32 = 5;
+
+/// Once a mint has this many recorded outcomes, the counts are halved so
+/// recent behaviour outweighs old history
+const RELIABILITY_WINDOW: u32 = 100;
+
+/// Weight of the newest latency sample in the rolling average
+const LATENCY_SMOOTHING: f64 = 0.2;
+
+impl MintReliabilityStats {
+    /// Add one operation outcome; latency only counts for successes
+    pub fn record(&mut self, success: bool, latency_ms: u64, now: u64) {
+        if success {
+            let latency_ms = latency_ms as f64;
+            self.avg_latency_ms = if self.successes == 0 {
+                latency_ms
+            } else {
+                self.avg_latency_ms + LATENCY_SMOOTHING * (latency_ms - self.avg_latency_ms)
+            };
+            self.successes = self.successes.saturating_add(1);
+        } else {
+            self.failures = self.failures.saturating_add(1);
+        }
+
+        if self.successes.saturating_add(self.failures) > RELIABILITY_WINDOW {
+            self.successes /= 2;
+            self.failures /= 2;
+        }
+        self.updated_at = now;
+    }
+
+    pub fn reliability(&self) -> MintReliability {
+        let sample_count = self.successes.saturating_add(self.failures);
+        MintReliability {
+            success_rate: if sample_count == 0 {
+                0.0
+            } else {
+                self.successes as f64 / sample_count as f64
+            },
+            avg_latency: Duration::from_millis(self.avg_latency_ms.round() as u64),
+            sample_count,
+        }
+    }
+}
+
+impl MintReliability {
+    /// Whether enough operations were recorded to judge the mint
+    pub fn has_enough_data(&self) -> bool {
+        self.sample_count >= MIN_RELIABILITY_SAMPLES
+    }
+}
+
+/// Recorded reliability of a mint, None if nothing was recorded yet
+pub fn get_mint_reliability(mint_url: &str) -> Option<MintReliability> {
+    MINT_RELIABILITY
+        .read()
+        .get(&normalize_mint_url(mint_url))
+        .map(|stats| stats.reliability())
+}
+
+/// Run a mint, melt or swap request against `mint_url` and record the outcome
+///
+/// Only transport and HTTP failures count against the mint. Protocol errors
+/// (spent inputs, unpaid quote, ...) are about the request, not the mint.
+pub async fn with_mint_reliability<T>(
+    mint_url: &str,
+    operation: MintOperation,
+    request: impl std::future::Future<Output = Result<T, cdk::Error>>,
+) -> Result<T, cdk::Error> {
+    let started_at = js_sys::Date::now();
+    let result = request.await;
+    match &result {
+        Ok(_) => record_mint_operation(mint_url, operation, true, started_at).await,
+        Err(e) if is_mint_unavailable_error(e) => {
+            record_mint_operation(mint_url, operation, false, started_at).await
+        }
+        Err(_) => {}
+    }
+    result
+}
+
+/// Record the outcome of a mint, melt or swap against `mint_url`
+///
+/// `started_at_ms` is `js_sys::Date::now()` taken before the request.
+async fn record_mint_operation(mint_url: &str, operation: MintOperation, success: bool, started_at_ms: f64) {
+    let latency_ms = (js_sys::Date::now() - started_at_ms).max(0.0) as u64;
+    let key = normalize_mint_url(mint_url);
+
+    let stats = {
+        let mut all = MINT_RELIABILITY.write();
+        let stats = all.entry(key.clone()).or_insert_with(|| MintReliabilityStats {
+            mint_url: key.clone(),
+            ..Default::default()
+        });
+        stats.record(success, latency_ms, js_sys::Date::now() as u64 / 1000);
+        stats.clone()
+    };
+
+    log::debug!(
+        "{:?} at {} {} in {}ms",
+        operation,
+        key,
+        if success { "succeeded" } else { "failed" },
+        latency_ms
+    );
+
+    match crate::stores::cache_database::get_cache_db().await {
+        Ok(cache_db) => {
+            if let Err(e) = cache_db.save_mint_reliability(&stats).await {
+                log::warn!("Failed to persist reliability for {}: {}", key, e);
+            }
+        }
+        Err(e) => log::warn!("Cannot persist mint reliability: {}", e),
+    }
+}
+
+/// Load recorded mint reliability from IndexedDB
+pub async fn load_mint_reliability() {
+    let cache_db = match crate::stores::cache_database::get_cache_db().await {
+        Ok(db) => db,
+        Err(e) => {
+            log::warn!("Cannot load mint reliability: {}", e);
+            return;
+        }
+    };
+
+    match cache_db.get_all_mint_reliability().await {
+        Ok(stats) => {
+            *MINT_RELIABILITY.write() = stats
+                .into_iter()
+                .map(|s| (normalize_mint_url(&s.mint_url), s))
+                .collect();
+        }
+        Err(e) => log::warn!("Failed to load mint reliability: {}", e),
+    }
+}
+
+/// Order two mints by reliability: mints with enough data first, higher
+/// success rate first, then lower latency
+pub fn compare_reliability(a: Option<&MintReliability>, b: Option<&MintReliability>) -> std::cmp::Ordering {
+    let a = a.filter(|r| r.has_enough_data());
+    let b = b.filter(|r| r.has_enough_data());
+    match (a, b) {
+        (Some(a), Some(b)) => b
+            .success_rate
+            .total_cmp(&a.success_rate)
+            .then(a.avg_latency.cmp(&b.avg_latency)),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    }
+}
+
+/// Wallet mints, most reliable first; mints without enough data keep their order
+pub fn get_mints_by_reliability() -> Vec<String> {
+    let mut mints = get_mints();
+    let reliability: HashMap<String, Option<MintReliability>> = mints
+        .iter()
+        .map(|m| (m.clone(), get_mint_reliability(m)))
+        .collect();
+    mints.sort_by(|a, b| compare_reliability(reliability[a].as_ref(), reliability[b].as_ref()));
+    mints
+}
+
 // =============================================================================
 // Counter Backup/Restore
 // =============================================================================
@@ -461,7 +669,6 @@ pub fn get_counter_backup(mint_url: &str) -> Option<CounterBackup> {
 /// 4. Restores counters if we previously had this mint
 /// 5. Runs background proof restoration
 pub async fn add_mint(mint_url: &str) -> Result<(), String> {
-    use nostr_sdk::signer::NostrSigner;
     use url::Url;
 
     // Normalize the URL to prevent duplicates
@@ -551,35 +758,9 @@ pub async fn add_mint(mint_url: &str) -> Result<(), String> {
     let privkey = wallet_state.privkey.as_ref()
         .ok_or("Wallet private key not available")?;
 
-    let signer = crate::stores::signer::get_signer()
-        .ok_or("No signer available")?
-        .as_nostr_signer();
-
-    let pubkey_str = auth_store::get_pubkey()
-        .ok_or("Not authenticated")?;
-    let pubkey = PublicKey::parse(&pubkey_str)
-        .map_err(|e| format!("Invalid pubkey: {}", e))?;
-
-    let client = nostr_client::NOSTR_CLIENT.read().as_ref()
-        .ok_or("Client not initialized")?.clone();
-
-    // Build wallet event content array following NIP-60 format
-    let mut content_array: Vec<Vec<&str>> = vec![vec!["privkey", privkey]];
-    for mint in wallet_state.mints.iter() {
-        content_array.push(vec!["mint", mint.as_str()]);
-    }
-
-    let json_content = serde_json::to_string(&content_array)
-        .map_err(|e| format!("Failed to serialize wallet data: {}", e))?;
-
-    let encrypted = signer.nip44_encrypt(&pubkey, &json_content).await
-        .map_err(|e| format!("Failed to encrypt: {}", e))?;
-
-    let builder = nostr_sdk::EventBuilder::new(Kind::CashuWallet, encrypted);
-
     // Publish - rollback local state on failure (nostr SDK pattern: transactional safety)
-    match client.send_event_builder(builder).await {
-        Ok(_) => log::info!("Published updated wallet event with new mint"),
+    match send_wallet_event(privkey, &wallet_state.mints).await {
+        Ok(()) => log::info!("Published updated wallet event with new mint"),
         Err(e) => {
             log::error!("Failed to publish wallet event: {}", e);
             // Rollback: remove the mint we just added to local state
@@ -589,7 +770,7 @@ pub async fn add_mint(mint_url: &str) -> Result<(), String> {
                     wallet_state.mints.retain(|m| normalize_mint_url(m) != mint_url);
                 }
             }
-            return Err(format!("Failed to publish wallet event: {}", e));
+            return Err(e);
         }
     }
 
@@ -666,8 +847,6 @@ pub async fn restore_proofs_from_mint(mint_url: &str) -> CashuResult<u64> {
 /// 4. Updates wallet state
 /// Returns (event_count, total_amount) on success.
 pub async fn remove_mint(mint_url: &str) -> Result<(usize, u64), String> {
-    use nostr_sdk::signer::NostrSigner;
-
     // Backup counters before removing
     if let Err(e) = backup_mint_counters(mint_url).await {
         log::warn!("Failed to backup counters for {}: {}", mint_url, e);
@@ -758,34 +937,8 @@ pub async fn remove_mint(mint_url: &str) -> Result<(usize, u64), String> {
                 return Ok((token_count, total_amount));
             };
 
-            let signer = crate::stores::signer::get_signer()
-                .ok_or("No signer available")?
-                .as_nostr_signer();
-
-            let pubkey_str = auth_store::get_pubkey()
-                .ok_or("Not authenticated")?;
-            let pubkey = PublicKey::parse(&pubkey_str)
-                .map_err(|e| format!("Invalid pubkey: {}", e))?;
-
-            let client = nostr_client::NOSTR_CLIENT.read().as_ref()
-                .ok_or("Client not initialized")?.clone();
-
-            // Build wallet event content array following NIP-60 format
-            let mut content_array: Vec<Vec<&str>> = vec![vec!["privkey", privkey]];
-            for mint in state.mints.iter() {
-                content_array.push(vec!["mint", mint.as_str()]);
-            }
-
-            let json_content = serde_json::to_string(&content_array)
-                .map_err(|e| format!("Failed to serialize wallet data: {}", e))?;
-
-            let encrypted = signer.nip44_encrypt(&pubkey, &json_content).await
-                .map_err(|e| format!("Failed to encrypt: {}", e))?;
-
-            let builder = nostr_sdk::EventBuilder::new(Kind::CashuWallet, encrypted);
-
-            match client.send_event_builder(builder).await {
-                Ok(_) => log::info!("Published updated wallet event after mint removal"),
+            match send_wallet_event(privkey, &state.mints).await {
+                Ok(()) => log::info!("Published updated wallet event after mint removal"),
                 Err(e) => log::warn!("Failed to publish wallet event: {}", e),
             }
         }
@@ -1100,8 +1253,8 @@ pub async fn discover_mints() -> Result<Vec<DiscoveredMint>, String> {
 
     // Fetch both in parallel
     let (mint_events, recommendation_events) = futures::join!(
-        client.fetch_events(mint_filter, Duration::from_secs(10)),
-        client.fetch_events(recommendation_filter, Duration::from_secs(10))
+        client.fetch_events(mint_filter, nostr_client::default_fetch_timeout()),
+        client.fetch_events(recommendation_filter, nostr_client::default_fetch_timeout())
     );
 
     let mint_events = mint_events.map_err(|e| format!("Failed to fetch mint events: {}", e))?;
@@ -1241,3 +1394,110 @@ pub async fn discover_mints() -> Result<Vec<DiscoveredMint>, String> {
 
     Ok(mints)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_token(unit: &str, amounts: &[u64]) -> TokenData {
+        TokenData {
+            event_id: format!("event-{}-{}", unit, amounts.len()),
+            mint: "https://mint.example.com".to_string(),
+            unit: unit.to_string(),
+            proofs: amounts
+                .iter()
+                .enumerate()
+                .map(|(i, &amount)| ProofData::for_test(amount, format!("{}-{}", unit, i)))
+                .collect(),
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_balance_per_unit_sat_and_usd() {
+        let tokens = vec![
+            make_token("sat", &[1, 2, 4]),
+            make_token("usd", &[100, 50]),
+            make_token("sat", &[8]),
+        ];
+
+        let balances = balance_per_unit(&tokens);
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances.get("sat"), Some(&15));
+        assert_eq!(balances.get("usd"), Some(&150));
+    }
+
+    #[test]
+    fn test_reliability_success_rate_and_rolling_latency() {
+        let mut stats = MintReliabilityStats::default();
+        stats.record(true, 200, 1);
+        assert_eq!(stats.avg_latency_ms, 200.0);
+
+        // Each new latency moves the average a fifth of the way
+        stats.record(true, 700, 2);
+        assert!((stats.avg_latency_ms - 300.0).abs() < 1e-9);
+
+        // Failures don't touch latency
+        stats.record(false, 30_000, 3);
+        assert!((stats.avg_latency_ms - 300.0).abs() < 1e-9);
+        assert_eq!(stats.updated_at, 3);
+
+        let reliability = stats.reliability();
+        assert_eq!(reliability.sample_count, 3);
+        assert!((reliability.success_rate - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(reliability.avg_latency, Duration::from_millis(300));
+        assert!(!reliability.has_enough_data());
+
+        stats.record(true, 300, 4);
+        stats.record(true, 300, 5);
+        assert!(stats.reliability().has_enough_data());
+        assert_eq!(stats.reliability().success_rate, 0.8);
+    }
+
+    #[test]
+    fn test_reliability_window_keeps_rate() {
+        let mut stats = MintReliabilityStats::default();
+        for i in 0..RELIABILITY_WINDOW {
+            stats.record(i % 4 != 0, 100, 0);
+        }
+        stats.record(true, 100, 0);
+
+        let reliability = stats.reliability();
+        assert!(reliability.sample_count <= RELIABILITY_WINDOW);
+        assert!((reliability.success_rate - 0.75).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_compare_reliability() {
+        let rel = |success_rate: f64, latency_ms: u64, sample_count: u32| MintReliability {
+            success_rate,
+            avg_latency: Duration::from_millis(latency_ms),
+            sample_count,
+        };
+        let reliable = rel(0.99, 800, 50);
+        let fast = rel(0.99, 200, 50);
+        let flaky = rel(0.5, 100, 50);
+        let new_mint = rel(1.0, 100, 2);
+
+        let mut mints = vec![None, Some(&new_mint), Some(&flaky), Some(&reliable), Some(&fast)];
+        mints.sort_by(|a, b| compare_reliability(*a, *b));
+        assert_eq!(mints, vec![Some(&fast), Some(&reliable), Some(&flaky), None, Some(&new_mint)]);
+    }
+
+    #[test]
+    fn test_only_unavailable_mints_count_as_failures() {
+        assert!(is_mint_unavailable_error(&cdk::Error::HttpError(Some(502), "Bad Gateway".to_string())));
+        assert!(is_mint_unavailable_error(&cdk::Error::Custom("Failed to fetch".to_string())));
+        assert!(!is_mint_unavailable_error(&cdk::Error::TokenAlreadySpent));
+        assert!(!is_mint_unavailable_error(&cdk::Error::InsufficientFunds));
+    }
+
+    #[test]
+    fn test_balance_per_unit_empty_unit_defaults_to_sat() {
+        let tokens = vec![make_token("", &[16]), make_token("sat", &[4])];
+
+        let balances = balance_per_unit(&tokens);
+        assert_eq!(balances.len(), 1);
+        assert_eq!(balances.get("sat"), Some(&20));
+    }
+}
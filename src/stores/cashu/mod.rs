@@ -13,6 +13,7 @@
 //! - Adaptive proof pagination
 //! - Fee estimation including P2PK overhead
 //! - Dust consolidation
+//! - NIP-61 nutzaps
 
 pub mod types;
 pub mod errors;
@@ -23,6 +24,7 @@ pub mod init;
 pub mod send;
 pub mod receive;
 pub mod lightning;
+pub mod pending_melt;
 pub mod mpp;
 pub mod mint_mgmt;
 pub mod history;
@@ -46,6 +48,8 @@ pub mod pagination;
 pub mod dust;
 pub mod enriched_history;
 pub mod ws;
+pub mod proof_watch;
+pub mod nutzap;
 
 // Re-export commonly used types
 pub use types::*;
@@ -58,8 +62,11 @@ pub use signals::*;
 pub use init::{
     init_wallet,
     create_wallet,
+    create_wallet_with_privkey,
     check_terms_accepted,
     accept_terms,
+    check_wallet_wipe,
+    wipe_wallet_database,
 };
 pub use send::{
     send_tokens, send_tokens_p2pk, get_wallet_pubkey, estimate_send_fee,
@@ -73,7 +80,11 @@ pub use lightning::{
     mint_tokens_from_quote,
     create_melt_quote,
     melt_tokens,
+    start_melt_quote_poller,
 };
+pub use pending_melt::resume_pending_melts;
+pub use proof_watch::{start_proof_state_watch, stop_proof_state_watch};
+pub use nutzap::{send_nutzap, NutzapError};
 pub use mpp::{
     get_balances_per_mint,
     calculate_mpp_split,
@@ -87,11 +98,14 @@ pub use mint_mgmt::{
     remove_mint,
     get_mints,
     get_mint_balance,
+    get_balance_per_unit,
     get_mint_info,
     get_mint_proof_count,
     get_total_proof_count,
     discover_mints,
     consolidate_all_mints,
+    get_mint_reliability,
+    get_mints_by_reliability,
 };
 #[allow(unused_imports)]
 pub use mint_mgmt::{check_keyset_collision, KeysetCollision};
@@ -159,6 +173,7 @@ pub use auth::{
 pub use keyset::{
     refresh_keysets, migrate_inactive_proofs, should_migrate,
     get_migration_recommendation, get_active_keyset_ids,
+    migration_banner, check_keyset_rotation, MigrationBanner, KEYSET_MIGRATION_PROGRESS,
     KeysetRefreshResult, KeysetMigrationResult,
 };
 // Spending conditions with SIG_ALL (NUT-10/11)
@@ -230,7 +245,8 @@ pub use dust::{
     find_dust_proofs, get_dust_stats, get_all_dust_stats,
     consolidate_dust, consolidate_all_dust,
     should_consolidate_dust, get_total_dust_stats,
-    DEFAULT_DUST_THRESHOLD,
+    dust_stats_for_amounts, schedule_dust_sweep,
+    DEFAULT_DUST_THRESHOLD, DUST_SWEEP_RESULT,
 };
 // Enriched history
 #[allow(unused_imports)]
@@ -240,6 +256,7 @@ pub use enriched_history::{
     enrich_history_item,
     create_lightning_receive_history, create_lightning_send_history,
     create_p2pk_send_history, create_swap_history,
+    ActivityItem, ActivitySource, ActivityStatus, build_activity_feed,
 };
 // Auth token caching
 #[allow(unused_imports)]
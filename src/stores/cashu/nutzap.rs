@@ -0,0 +1,278 @@
+//! Nutzaps (NIP-61)
+//!
+//! A nutzap is ecash P2PK-locked to the key the recipient advertises in their
+//! kind 10019 event, sent from one of the mints they accept and published as a
+//! kind 9321 event carrying the proofs.
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use cdk::nuts::Token;
+use nostr_sdk::{EventBuilder, EventId, Filter, Kind, PublicKey, Tag};
+
+use super::events::{queue_event_for_retry, queue_signed_event_for_relays};
+use super::internal::create_ephemeral_wallet;
+use super::mpp::{get_balances_per_mint, MintBalance};
+use super::send::send_tokens_p2pk;
+use super::types::PendingEventType;
+use super::utils::{mint_matches, normalize_mint_url};
+use crate::stores::nostr_client;
+
+/// Kind 10019 - Nutzap informational event
+pub const KIND_NUTZAP_INFO: u16 = 10019;
+
+/// Kind 9321 - Nutzap
+pub const KIND_NUTZAP: u16 = 9321;
+
+/// Where and how a user accepts nutzaps (from their kind 10019 event)
+#[derive(Clone, Debug, PartialEq)]
+pub struct NutzapInfo {
+    /// Key to lock the ecash to, as a Nostr (x-only hex) pubkey
+    pub p2pk_pubkey: String,
+    /// Mints accepting sats the recipient trusts
+    pub mints: Vec<String>,
+    /// Relays the recipient reads nutzaps from
+    pub relays: Vec<String>,
+}
+
+/// Parse a kind 10019 event; None if it names no usable P2PK key or mint
+pub fn parse_nutzap_info(event: &nostr_sdk::Event) -> Option<NutzapInfo> {
+    if event.kind != Kind::from(KIND_NUTZAP_INFO) {
+        return None;
+    }
+
+    let mut p2pk_pubkey = None;
+    let mut mints = Vec::new();
+    let mut relays = Vec::new();
+
+    for tag in event.tags.iter() {
+        let parts = tag.as_slice();
+        if parts.len() < 2 {
+            continue;
+        }
+        match parts[0].as_str() {
+            "pubkey" if p2pk_pubkey.is_none() => p2pk_pubkey = p2pk_to_nostr_pubkey(&parts[1]),
+            // Units are optional; without any the mint is assumed to take sats
+            "mint" if parts.len() == 2 || parts[2..].iter().any(|unit| unit == "sat") => {
+                mints.push(parts[1].clone());
+            }
+            "relay" => relays.push(parts[1].clone()),
+            _ => {}
+        }
+    }
+
+    let p2pk_pubkey = p2pk_pubkey?;
+    if mints.is_empty() {
+        return None;
+    }
+
+    Some(NutzapInfo { p2pk_pubkey, mints, relays })
+}
+
+/// Accept both x-only and even-parity compressed keys (`02` prefix)
+///
+/// Odd-parity keys can't be expressed as a Nostr pubkey, which the P2PK send
+/// path expects, so they are rejected.
+fn p2pk_to_nostr_pubkey(key: &str) -> Option<String> {
+    let x_only = match key.len() {
+        64 => key,
+        66 if key.starts_with("02") => &key[2..],
+        _ => return None,
+    };
+    PublicKey::from_hex(x_only).ok().map(|pk| pk.to_hex())
+}
+
+/// Pick the mint to send a nutzap from: the accepted mint holding the most
+/// funds, provided it covers `amount`
+pub fn pick_nutzap_mint(accepted: &[String], balances: &[MintBalance], amount: u64) -> Option<String> {
+    balances
+        .iter()
+        .filter(|b| b.balance >= amount)
+        .filter(|b| {
+            accepted
+                .iter()
+                .any(|mint| mint_matches(&b.mint_url, &normalize_mint_url(mint)))
+        })
+        .max_by_key(|b| b.balance)
+        .map(|b| b.mint_url.clone())
+}
+
+/// Fetch a user's nutzap info (kind 10019), if they published one
+pub async fn fetch_nutzap_info(recipient: PublicKey) -> Result<Option<NutzapInfo>, String> {
+    let filter = Filter::new()
+        .author(recipient)
+        .kind(Kind::from(KIND_NUTZAP_INFO))
+        .limit(1);
+
+    let events = nostr_client::fetch_events_aggregated(filter, Duration::from_secs(5)).await?;
+    Ok(events
+        .into_iter()
+        .max_by_key(|event| event.created_at)
+        .and_then(|event| parse_nutzap_info(&event)))
+}
+
+/// Why a nutzap didn't go out
+#[derive(Clone, Debug, PartialEq)]
+pub enum NutzapError {
+    /// Nothing was spent, so the zap can still be paid another way
+    NotSent(String),
+    /// The ecash is already locked to the recipient; paying again would pay twice
+    Locked(String),
+}
+
+impl std::fmt::Display for NutzapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NutzapError::NotSent(e) | NutzapError::Locked(e) => f.write_str(e),
+        }
+    }
+}
+
+/// Send a nutzap of `amount` sats to `recipient`, optionally for `event_id`
+///
+/// Fails with [`NutzapError::NotSent`] if the recipient doesn't accept
+/// nutzaps or no shared mint holds enough funds. Once the ecash is locked the
+/// nutzap is never dropped: a failed publish is queued for retry to the
+/// recipient's relays. Returns the nutzap event ID.
+pub async fn send_nutzap(
+    recipient: PublicKey,
+    amount: u64,
+    comment: String,
+    event_id: Option<EventId>,
+) -> Result<String, NutzapError> {
+    use NutzapError::{Locked, NotSent};
+
+    let client = nostr_client::get_client().ok_or_else(|| NotSent("Client not initialized".to_string()))?;
+
+    let info = fetch_nutzap_info(recipient)
+        .await
+        .map_err(NotSent)?
+        .ok_or_else(|| NotSent("Recipient doesn't accept nutzaps".to_string()))?;
+
+    let balances = get_balances_per_mint().await.map_err(NotSent)?;
+    let mint_url = pick_nutzap_mint(&info.mints, &balances, amount)
+        .ok_or_else(|| NotSent("None of the recipient's mints has enough funds in your wallet".to_string()))?;
+
+    // Everything that can fail without spending happens before the send
+    let wallet = create_ephemeral_wallet(&mint_url, Vec::new()).await.map_err(NotSent)?;
+    let keysets = wallet
+        .get_mint_keysets()
+        .await
+        .map_err(|e| NotSent(format!("Failed to get keysets: {}", e)))?;
+
+    log::info!("Sending {} sat nutzap to {} via {}", amount, recipient.to_hex(), mint_url);
+
+    let token_string = send_tokens_p2pk(mint_url.clone(), amount, info.p2pk_pubkey.clone())
+        .await
+        .map_err(NotSent)?;
+
+    // The proofs go into the event itself rather than as an encoded token
+    let token = Token::from_str(&token_string)
+        .map_err(|e| Locked(format!("Invalid token {}: {}", token_string, e)))?;
+    let proofs = token
+        .proofs(&keysets)
+        .map_err(|e| Locked(format!("Failed to extract proofs from token {}: {}", token_string, e)))?;
+
+    let mut tags = Vec::with_capacity(proofs.len() + 3);
+    for proof in &proofs {
+        let proof_json = serde_json::to_string(proof)
+            .map_err(|e| Locked(format!("Failed to serialize proof: {}", e)))?;
+        tags.push(Tag::parse(["proof", proof_json.as_str()]).map_err(|e| Locked(e.to_string()))?);
+    }
+    tags.push(Tag::parse(["u", mint_url.as_str()]).map_err(|e| Locked(e.to_string()))?);
+    if let Some(event_id) = event_id {
+        tags.push(Tag::event(event_id));
+    }
+    tags.push(Tag::public_key(recipient));
+
+    let builder = EventBuilder::new(Kind::from(KIND_NUTZAP), comment).tags(tags);
+    let event = match client.sign_event_builder(builder.clone()).await {
+        Ok(event) => event,
+        Err(e) => {
+            log::warn!("Failed to sign nutzap, queuing for retry: {}", e);
+            queue_event_for_retry(builder, PendingEventType::Nutzap).await;
+            return Err(Locked(format!("Failed to sign nutzap, it will be retried: {}", e)));
+        }
+    };
+    let nutzap_id = event.id.to_hex();
+
+    // The ecash is already locked to the recipient, so never drop the event
+    if let Err(e) = publish_nutzap(&client, &event, &info.relays).await {
+        log::warn!("Failed to publish nutzap, queuing for retry: {}", e);
+        queue_signed_event_for_relays(event, PendingEventType::Nutzap, info.relays).await;
+    }
+
+    Ok(nutzap_id)
+}
+
+/// Publish a nutzap to the relays the recipient reads them from (kind 10019),
+/// or through our relays if they listed none
+async fn publish_nutzap(
+    client: &std::sync::Arc<nostr_sdk::Client>,
+    event: &nostr_sdk::Event,
+    relays: &[String],
+) -> Result<(), String> {
+    if relays.is_empty() {
+        client.send_event(event).await.map_err(|e| e.to_string())?;
+        return Ok(());
+    }
+
+    let temp_relays = nostr_client::add_temp_relays(client, relays).await;
+    let result = client.send_event_to(relays.iter().map(String::as_str), event).await;
+    temp_relays.release().await;
+
+    let output = result.map_err(|e| e.to_string())?;
+    if output.success.is_empty() {
+        return Err(format!("None of the recipient's {} relays accepted the nutzap", relays.len()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::Keys;
+
+    fn info_event(tags: Vec<Vec<&str>>) -> nostr_sdk::Event {
+        let tags: Vec<Tag> = tags.into_iter().map(|tag| Tag::parse(tag).unwrap()).collect();
+        EventBuilder::new(Kind::from(KIND_NUTZAP_INFO), "")
+            .tags(tags)
+            .sign_with_keys(&Keys::generate())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_nutzap_info() {
+        let p2pk = Keys::generate().public_key().to_hex();
+        let compressed = format!("02{}", p2pk);
+        let event = info_event(vec![
+            vec!["relay", "wss://relay.example"],
+            vec!["mint", "https://sats.example", "usd", "sat"],
+            vec!["mint", "https://usd.example", "usd"],
+            vec!["mint", "https://plain.example"],
+            vec!["pubkey", &compressed],
+        ]);
+
+        let info = parse_nutzap_info(&event).unwrap();
+        assert_eq!(info.p2pk_pubkey, p2pk);
+        assert_eq!(info.mints, vec!["https://sats.example", "https://plain.example"]);
+        assert_eq!(info.relays, vec!["wss://relay.example"]);
+
+        // No P2PK key means no nutzaps
+        assert!(parse_nutzap_info(&info_event(vec![vec!["mint", "https://sats.example"]])).is_none());
+    }
+
+    #[test]
+    fn test_pick_nutzap_mint() {
+        let balances = vec![
+            MintBalance { mint_url: "https://a.example".to_string(), balance: 50 },
+            MintBalance { mint_url: "https://b.example".to_string(), balance: 500 },
+            MintBalance { mint_url: "https://c.example".to_string(), balance: 900 },
+        ];
+        let accepted = vec!["https://a.example".to_string(), "https://b.example/".to_string()];
+
+        assert_eq!(pick_nutzap_mint(&accepted, &balances, 21).as_deref(), Some("https://b.example"));
+        // Only a.example is shared but it can't cover the amount
+        assert_eq!(pick_nutzap_mint(&accepted[..1], &balances, 100), None);
+    }
+}
@@ -11,7 +11,9 @@
 use std::str::FromStr;
 
 use dioxus::prelude::*;
+use gloo_storage::{LocalStorage, Storage};
 use nostr_sdk::{EventId, Kind, PublicKey};
+use serde::{Deserialize, Serialize};
 
 // CDK NUT-18 types
 use cdk::nuts::{
@@ -32,7 +34,7 @@ use super::signals::{
     WALLET_TOKENS,
 };
 use super::types::{
-    ExtendedCashuProof, ExtendedTokenEvent, NostrPaymentWaitInfo,
+    AmountMismatchPolicy, ExtendedCashuProof, ExtendedTokenEvent, NostrPaymentWaitInfo,
     PaymentRequestProgress, ProofData, TokenData, WalletTokensStoreStoreExt,
 };
 use super::utils::{mint_matches, normalize_mint_url};
@@ -49,8 +51,6 @@ pub async fn create_payment_request(
     description: Option<String>,
     use_nostr_transport: bool,
 ) -> Result<(String, Option<NostrPaymentWaitInfo>), String> {
-    use nostr_sdk::ToBech32;
-
     log::info!(
         "Creating payment request: amount={:?}, nostr={}",
         amount,
@@ -64,6 +64,9 @@ pub async fn create_payment_request(
 
     let request_id = uuid::Uuid::new_v4().to_string()[..8].to_string();
 
+    // A new request starts with a clean status
+    *PAYMENT_REQUEST_PROGRESS.write() = None;
+
     // Build transport using CDK's Transport builder
     let (transports, nostr_info): (Vec<Transport>, Option<NostrPaymentWaitInfo>) =
         if use_nostr_transport {
@@ -76,32 +79,14 @@ pub async fn create_payment_request(
                 return Err("No relays configured for Nostr transport".to_string());
             }
 
-            // Create nprofile with relays
-            let relay_urls: Vec<nostr_sdk::RelayUrl> = relays
-                .iter()
-                .filter_map(|r| nostr_sdk::RelayUrl::parse(r).ok())
-                .collect();
-
-            let nprofile =
-                nostr_sdk::nips::nip19::Nip19Profile::new(keys.public_key(), relay_urls);
-
-            let nprofile_str = nprofile
-                .to_bech32()
-                .map_err(|e| format!("Failed to encode nprofile: {}", e))?;
-
-            // Use CDK's Transport builder
-            let transport = Transport::builder()
-                .transport_type(TransportType::Nostr)
-                .target(nprofile_str)
-                .tags(vec![vec!["n".to_string(), "17".to_string()]]) // NIP-17 gift wrap
-                .build()
-                .map_err(|e| format!("Failed to build transport: {}", e))?;
+            let transport = nostr_transport(&keys.public_key(), &relays)?;
 
             let wait_info = NostrPaymentWaitInfo {
                 request_id: request_id.clone(),
                 secret_key: keys.secret_key().clone(),
                 relays,
                 pubkey: keys.public_key(),
+                amount,
             };
 
             (vec![transport], Some(wait_info))
@@ -109,15 +94,65 @@ pub async fn create_payment_request(
             (vec![], None)
         };
 
-    // Convert mint strings to MintUrl
+    let request = build_payment_request(&request_id, amount, description, &mints, transports);
+
+    // CDK's PaymentRequest implements Display for encoding (creqA...)
+    let request_string = request.to_string();
+
+    // Store wait info for later if Nostr transport is enabled
+    if let Some(ref info) = nostr_info {
+        remember_request_key(info);
+        PENDING_PAYMENT_REQUESTS
+            .write()
+            .insert(request_id, info.clone());
+    }
+
+    log::info!(
+        "Created payment request: {}",
+        &request_string[..50.min(request_string.len())]
+    );
+
+    Ok((request_string, nostr_info))
+}
+
+/// NUT-18 Nostr transport: an nprofile for `pubkey` on `relays`, NIP-17 gift wrapped
+pub fn nostr_transport(pubkey: &PublicKey, relays: &[String]) -> Result<Transport, String> {
+    use nostr_sdk::ToBech32;
+
+    let relay_urls: Vec<nostr_sdk::RelayUrl> = relays
+        .iter()
+        .filter_map(|r| nostr_sdk::RelayUrl::parse(r).ok())
+        .collect();
+
+    let nprofile_str = nostr_sdk::nips::nip19::Nip19Profile::new(*pubkey, relay_urls)
+        .to_bech32()
+        .map_err(|e| format!("Failed to encode nprofile: {}", e))?;
+
+    Transport::builder()
+        .transport_type(TransportType::Nostr)
+        .target(nprofile_str)
+        .tags(vec![vec!["n".to_string(), "17".to_string()]]) // NIP-17 gift wrap
+        .build()
+        .map_err(|e| format!("Failed to build transport: {}", e))
+}
+
+/// Single-use sat request payable at any of `mints`
+///
+/// Mint URLs that don't parse are left out.
+pub fn build_payment_request(
+    request_id: &str,
+    amount: Option<u64>,
+    description: Option<String>,
+    mints: &[String],
+    transports: Vec<Transport>,
+) -> PaymentRequest {
     let mint_urls: Vec<MintUrl> = mints
         .iter()
         .filter_map(|m| MintUrl::from_str(m).ok())
         .collect();
 
-    // Build request using CDK's PaymentRequest builder
     let mut builder = PaymentRequest::builder()
-        .payment_id(&request_id)
+        .payment_id(request_id)
         .unit(CurrencyUnit::Sat)
         .single_use(true)
         .mints(mint_urls);
@@ -134,24 +169,28 @@ pub async fn create_payment_request(
         builder = builder.transports(transports);
     }
 
-    let request = builder.build();
+    builder.build()
+}
 
-    // CDK's PaymentRequest implements Display for encoding (creqA...)
-    let request_string = request.to_string();
+/// How an incoming payment's amount compares to what was asked
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AmountCheck {
+    /// At least the requested amount (payers may add the swap fee on top)
+    Ok,
+    /// Short, but the policy is to take it anyway
+    Short { expected: u64 },
+    /// Short, and the request stays open for the full amount
+    Reject { expected: u64 },
+}
 
-    // Store wait info for later if Nostr transport is enabled
-    if let Some(ref info) = nostr_info {
-        PENDING_PAYMENT_REQUESTS
-            .write()
-            .insert(request_id, info.clone());
+fn check_payment_amount(received: u64, expected: Option<u64>, policy: AmountMismatchPolicy) -> AmountCheck {
+    match expected {
+        Some(expected) if received < expected => match policy {
+            AmountMismatchPolicy::Accept => AmountCheck::Short { expected },
+            AmountMismatchPolicy::Reject => AmountCheck::Reject { expected },
+        },
+        _ => AmountCheck::Ok,
     }
-
-    log::info!(
-        "Created payment request: {}",
-        &request_string[..50.min(request_string.len())]
-    );
-
-    Ok((request_string, nostr_info))
 }
 
 /// Parse a payment request string (creqA...)
@@ -524,8 +563,15 @@ pub async fn pay_payment_request(
 /// Wait for a Nostr payment for a created request
 ///
 /// This listens for gift-wrapped events on the relays and processes
-/// incoming payments.
-pub async fn wait_for_nostr_payment(request_id: String, timeout_secs: u64) -> Result<u64, String> {
+/// incoming payments. The request expires after `timeout_secs`. A payment
+/// for less than the requested amount is always received; `on_mismatch`
+/// decides whether it settles the request. Payments arriving after the wait
+/// ends are picked up by [`sweep_stored_payment_requests`].
+pub async fn wait_for_nostr_payment(
+    request_id: String,
+    timeout_secs: u64,
+    on_mismatch: AmountMismatchPolicy,
+) -> Result<u64, String> {
     use nostr_sdk::prelude::*;
 
     log::info!("Waiting for Nostr payment for request: {}", request_id);
@@ -552,8 +598,8 @@ pub async fn wait_for_nostr_payment(request_id: String, timeout_secs: u64) -> Re
 
     client.connect().await;
 
-    // Subscribe to events for our pubkey
-    let filter = Filter::new().pubkey(wait_info.pubkey);
+    // Subscribe to gift wraps for our pubkey
+    let filter = Filter::new().kind(Kind::GiftWrap).pubkey(wait_info.pubkey);
     client
         .subscribe(filter, None)
         .await
@@ -572,9 +618,9 @@ pub async fn wait_for_nostr_payment(request_id: String, timeout_secs: u64) -> Re
         // Check timeout
         let elapsed = chrono::Utc::now().timestamp() as u64 - start;
         if elapsed > timeout_secs {
-            *PAYMENT_REQUEST_PROGRESS.write() = Some(PaymentRequestProgress::Cancelled);
+            *PAYMENT_REQUEST_PROGRESS.write() = Some(PaymentRequestProgress::Expired);
             PENDING_PAYMENT_REQUESTS.write().remove(&request_id);
-            return Err("Timeout waiting for payment".to_string());
+            return Err("Payment request expired".to_string());
         }
 
         // Wait for next notification with timeout
@@ -612,54 +658,37 @@ pub async fn wait_for_nostr_payment(request_id: String, timeout_secs: u64) -> Re
         };
 
         if let Some(RelayPoolNotification::Event { event, .. }) = notification {
-            // Try to unwrap gift wrap
-            match client.unwrap_gift_wrap(&event).await {
-                Ok(unwrapped) => {
-                    let rumor = unwrapped.rumor;
-
-                    // Try to parse payload (using CDK's PaymentRequestPayload type)
-                    match serde_json::from_str::<CdkPaymentRequestPayload>(&rumor.content) {
-                        Ok(payload) => {
-                            log::info!("Received payment payload: {} proofs", payload.proofs.len());
-
-                            // Calculate amount with overflow protection
-                            // CDK's Proof.amount is Amount type, convert via u64::from()
-                            let amount: u64 = payload.proofs
-                                .iter()
-                                .map(|p| u64::from(p.amount))
-                                .try_fold(0u64, |acc, amt| acc.checked_add(amt))
-                                .unwrap_or(u64::MAX); // Cap at max if overflow
-
-                            // Convert CDK proofs to ProofData
-                            let proof_data: Vec<ProofData> = payload.proofs
-                                .iter()
-                                .map(cdk_proof_to_proof_data)
-                                .collect();
-
-                            // Receive the tokens (mint is MintUrl, convert to string)
-                            let mint_str = payload.mint.to_string();
-                            match receive_payment_proofs(&mint_str, proof_data).await {
-                                Ok(_) => {
-                                    *PAYMENT_REQUEST_PROGRESS.write() =
-                                        Some(PaymentRequestProgress::Received { amount });
-                                    PENDING_PAYMENT_REQUESTS.write().remove(&request_id);
-                                    return Ok(amount);
-                                }
-                                Err(e) => {
-                                    log::error!("Failed to receive payment proofs: {}", e);
-                                    // Continue listening - might be a different payment
-                                }
-                            }
+            let Some((amount, mint_str, proof_data)) =
+                unwrap_payment(&client, &event, &request_id).await
+            else {
+                continue;
+            };
+
+            // Short payments are received too: turning them away would strand the tokens
+            let check = check_payment_amount(amount, wait_info.amount, on_mismatch);
+            match receive_payment_proofs(&mint_str, proof_data).await {
+                Ok(_) => {
+                    let progress = match check {
+                        AmountCheck::Reject { expected } => {
+                            log::warn!("Received short payment of {} sats (expected {}), still waiting", amount, expected);
+                            *PAYMENT_REQUEST_PROGRESS.write() =
+                                Some(PaymentRequestProgress::Rejected { amount, expected });
+                            continue;
                         }
-                        Err(e) => {
-                            log::debug!("Failed to parse payment payload: {}", e);
-                            // Continue listening
+                        AmountCheck::Short { expected } => {
+                            log::warn!("Accepted short payment of {} sats (expected {})", amount, expected);
+                            PaymentRequestProgress::ReceivedWrongAmount { amount, expected }
                         }
-                    }
+                        AmountCheck::Ok => PaymentRequestProgress::Received { amount },
+                    };
+                    *PAYMENT_REQUEST_PROGRESS.write() = Some(progress);
+                    PENDING_PAYMENT_REQUESTS.write().remove(&request_id);
+                    forget_request_key(&request_id);
+                    return Ok(amount);
                 }
                 Err(e) => {
-                    log::debug!("Failed to unwrap gift wrap: {}", e);
-                    // Continue listening
+                    log::error!("Failed to receive payment proofs: {}", e);
+                    // Continue listening - might be a different payment
                 }
             }
         }
@@ -677,6 +706,182 @@ pub async fn wait_for_nostr_payment(request_id: String, timeout_secs: u64) -> Re
     Err("Connection closed while waiting for payment".to_string())
 }
 
+/// Sat payment for `request_id` carried by a gift wrap to the request's key
+///
+/// Returns the amount, mint and proofs, or `None` for anything else.
+async fn unwrap_payment(
+    client: &nostr_sdk::Client,
+    event: &nostr_sdk::Event,
+    request_id: &str,
+) -> Option<(u64, String, Vec<ProofData>)> {
+    let unwrapped = match client.unwrap_gift_wrap(event).await {
+        Ok(unwrapped) => unwrapped,
+        Err(e) => {
+            log::debug!("Failed to unwrap gift wrap: {}", e);
+            return None;
+        }
+    };
+
+    // Using CDK's PaymentRequestPayload type
+    let payload = match serde_json::from_str::<CdkPaymentRequestPayload>(&unwrapped.rumor.content) {
+        Ok(payload) => payload,
+        Err(e) => {
+            log::debug!("Failed to parse payment payload: {}", e);
+            return None;
+        }
+    };
+    log::info!("Received payment payload: {} proofs", payload.proofs.len());
+
+    // Only sat payments for this request
+    if payload.unit != CurrencyUnit::Sat {
+        log::warn!("Ignoring payment in unsupported unit: {}", payload.unit);
+        return None;
+    }
+    if payload.id.as_deref().is_some_and(|id| id != request_id) {
+        log::debug!("Ignoring payment for another request: {:?}", payload.id);
+        return None;
+    }
+
+    // Calculate amount with overflow protection
+    // CDK's Proof.amount is Amount type, convert via u64::from()
+    let amount: u64 = payload.proofs
+        .iter()
+        .map(|p| u64::from(p.amount))
+        .try_fold(0u64, |acc, amt| acc.checked_add(amt))
+        .unwrap_or(u64::MAX); // Cap at max if overflow
+
+    let proofs = payload.proofs.iter().map(cdk_proof_to_proof_data).collect();
+    Some((amount, payload.mint.to_string(), proofs))
+}
+
+/// localStorage key prefix for the ephemeral keys of Nostr payment requests
+const REQUEST_KEYS_STORAGE_PREFIX: &str = "nostr_blue_payment_request_keys";
+
+/// How long the key of an unpaid request is kept for late payments
+const REQUEST_KEY_RETENTION_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Ephemeral key of a Nostr payment request, kept until a payment is swept
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+struct StoredRequestKey {
+    request_id: String,
+    /// Hex secret key
+    secret_key: String,
+    relays: Vec<String>,
+    /// Unix seconds
+    created_at: u64,
+}
+
+/// Request keys are stored per account
+fn request_keys_storage_key() -> Option<String> {
+    auth_store::get_pubkey().map(|pubkey| format!("{}_{}", REQUEST_KEYS_STORAGE_PREFIX, pubkey))
+}
+
+fn load_request_keys() -> Vec<StoredRequestKey> {
+    request_keys_storage_key()
+        .and_then(|key| LocalStorage::get(&key).ok())
+        .unwrap_or_default()
+}
+
+fn save_request_keys(keys: &[StoredRequestKey]) {
+    let Some(storage_key) = request_keys_storage_key() else {
+        return;
+    };
+    if keys.is_empty() {
+        LocalStorage::delete(&storage_key);
+    } else if let Err(e) = LocalStorage::set(&storage_key, keys) {
+        log::warn!("Failed to save payment request keys: {}", e);
+    }
+}
+
+/// Keep a request's key so payments arriving after the wait ends can be swept
+fn remember_request_key(info: &NostrPaymentWaitInfo) {
+    let mut keys = load_request_keys();
+    keys.retain(|k| k.request_id != info.request_id);
+    keys.push(StoredRequestKey {
+        request_id: info.request_id.clone(),
+        secret_key: info.secret_key.to_secret_hex(),
+        relays: info.relays.clone(),
+        created_at: chrono::Utc::now().timestamp() as u64,
+    });
+    save_request_keys(&keys);
+}
+
+fn forget_request_key(request_id: &str) {
+    let mut keys = load_request_keys();
+    keys.retain(|k| k.request_id != request_id);
+    save_request_keys(&keys);
+}
+
+/// Receive payments that reached requests nobody was waiting on anymore
+///
+/// Covers payers who paid after a request expired or was cancelled, and
+/// pages closed mid-wait. A key is dropped once a payment to it is
+/// received, or after [`REQUEST_KEY_RETENTION_SECS`]. Returns the sats
+/// received.
+pub async fn sweep_stored_payment_requests() -> u64 {
+    use nostr_sdk::prelude::*;
+
+    let now = chrono::Utc::now().timestamp() as u64;
+    let mut total = 0u64;
+
+    for stored in load_request_keys() {
+        // Requests still being waited on are received by their wait
+        if PENDING_PAYMENT_REQUESTS.peek().contains_key(&stored.request_id) {
+            continue;
+        }
+        let Ok(secret_key) = SecretKey::parse(&stored.secret_key) else {
+            forget_request_key(&stored.request_id);
+            continue;
+        };
+
+        let keys = Keys::new(secret_key);
+        let filter = Filter::new().kind(Kind::GiftWrap).pubkey(keys.public_key());
+        let client = Client::new(keys);
+        for relay in &stored.relays {
+            if let Err(e) = client.add_read_relay(relay.clone()).await {
+                log::warn!("Failed to add relay {}: {}", relay, e);
+            }
+        }
+        client.connect().await;
+
+        let events = match client.fetch_events(filter, nostr_client::default_fetch_timeout()).await {
+            Ok(events) => events,
+            Err(e) => {
+                log::warn!("Failed to check payment request {}: {}", stored.request_id, e);
+                client.disconnect().await;
+                continue;
+            }
+        };
+
+        let mut received = false;
+        for event in events.iter() {
+            let Some((_, mint_str, proof_data)) =
+                unwrap_payment(&client, event, &stored.request_id).await
+            else {
+                continue;
+            };
+            // Payments already received during the wait fail here as spent
+            match receive_payment_proofs(&mint_str, proof_data).await {
+                Ok(amount) => {
+                    total = total.saturating_add(amount);
+                    received = true;
+                }
+                Err(e) => log::debug!("Payment to request {} not received: {}", stored.request_id, e),
+            }
+        }
+        client.disconnect().await;
+
+        if received || now.saturating_sub(stored.created_at) > REQUEST_KEY_RETENTION_SECS {
+            forget_request_key(&stored.request_id);
+        }
+    }
+
+    if total > 0 {
+        log::info!("Swept {} sats paid to earlier payment requests", total);
+    }
+    total
+}
+
 /// Receive proofs from a payment request payload
 async fn receive_payment_proofs(mint_url: &str, proofs: Vec<ProofData>) -> Result<u64, String> {
     use nostr_sdk::signer::NostrSigner;
@@ -819,3 +1024,63 @@ pub fn cancel_payment_request(request_id: &str) {
 pub fn cancel_payment_request_wait(request_id: &str) {
     cancel_payment_request(request_id);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::nips::nip19::Nip19Profile;
+    use nostr_sdk::FromBech32;
+
+    #[test]
+    fn test_nostr_payment_request_round_trip() {
+        let pubkey = nostr_sdk::Keys::generate().public_key();
+        let relays = vec!["wss://relay.damus.io".to_string(), "not a relay".to_string()];
+        let transport = nostr_transport(&pubkey, &relays).unwrap();
+        let mints = vec!["https://mint.example.com".to_string()];
+
+        let request = build_payment_request(
+            "abcd1234",
+            Some(21),
+            Some("coffee".to_string()),
+            &mints,
+            vec![transport],
+        );
+        let encoded = request.to_string();
+        assert!(encoded.starts_with("creqA"));
+
+        let parsed = parse_payment_request(&encoded).unwrap();
+        assert_eq!(parsed.payment_id.as_deref(), Some("abcd1234"));
+        assert_eq!(parsed.amount, Some(Amount::from(21)));
+        assert_eq!(parsed.unit, Some(CurrencyUnit::Sat));
+        assert_eq!(parsed.description.as_deref(), Some("coffee"));
+        assert_eq!(parsed.mints.as_ref().map(|m| m.len()), Some(1));
+
+        assert_eq!(parsed.transports.len(), 1);
+        let transport = &parsed.transports[0];
+        assert_eq!(transport._type, TransportType::Nostr);
+        assert_eq!(
+            transport.tags.as_ref(),
+            Some(&vec![vec!["n".to_string(), "17".to_string()]])
+        );
+
+        // The target names the ephemeral key and only the relays that parsed
+        let nprofile = Nip19Profile::from_bech32(&transport.target).unwrap();
+        assert_eq!(nprofile.public_key, pubkey);
+        assert_eq!(nprofile.relays.len(), 1);
+        assert_eq!(nprofile.relays[0], nostr_sdk::RelayUrl::parse("wss://relay.damus.io").unwrap());
+    }
+
+    #[test]
+    fn test_check_payment_amount() {
+        use AmountMismatchPolicy::*;
+
+        assert_eq!(check_payment_amount(21, Some(21), Reject), AmountCheck::Ok);
+        // Paying the fee on top is fine
+        assert_eq!(check_payment_amount(23, Some(21), Reject), AmountCheck::Ok);
+        // Open amount requests take anything
+        assert_eq!(check_payment_amount(1, None, Reject), AmountCheck::Ok);
+
+        assert_eq!(check_payment_amount(10, Some(21), Accept), AmountCheck::Short { expected: 21 });
+        assert_eq!(check_payment_amount(10, Some(21), Reject), AmountCheck::Reject { expected: 21 });
+    }
+}
@@ -0,0 +1,461 @@
+//! Pending melt handling
+//!
+//! Lightning payments can sit in `MeltQuoteState::Pending` while the mint waits
+//! for the route to settle. Instead of treating the melt as finished, the quote
+//! stays tracked, the input proofs stay locked as PendingSpent, and
+//! `lightning::start_melt_quote_poller` polls `melt_quote_status` until the
+//! payment resolves:
+//! - Paid: remaining proofs and change are published, history is recorded
+//! - Unpaid/Failed: the locked proofs are restored as spendable
+//! - Quote expired while in flight: polling stops, proofs stay locked
+//!
+//! Pending quotes are persisted in IndexedDB so polling resumes after a restart.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use cdk::nuts::{Id, PreMintSecrets};
+use cdk::wallet::Wallet;
+use dioxus::prelude::*;
+
+use super::internal::{
+    create_ephemeral_wallet, derive_wallet_seed, get_or_create_wallet, get_shared_localstore,
+    remove_melt_quote_from_db,
+};
+use super::lightning::{
+    create_history_event_with_type, get_proofs_and_events_for_mint, publish_melt_events,
+    start_melt_quote_poller, update_local_state_after_melt,
+};
+use super::proof_recovery::recalculate_balance;
+use super::proofs::{
+    create_transaction, get_all_proofs_for_mint, move_proofs_to_pending_spent,
+    register_proofs_pending_at_mint, remove_from_pending_at_mint, revert_proofs_to_spendable,
+    update_transaction_status,
+};
+use super::signals::{
+    try_acquire_mint_lock, ACTIVE_TRANSACTIONS, MELT_PROGRESS, PENDING_MELT_QUOTES,
+};
+use super::types::{
+    MeltChangeOutputs, MeltProgress, MeltQuoteInfo, PendingMeltQuotesStoreStoreExt,
+    TransactionStatus, TransactionType,
+};
+use super::utils::now_secs;
+use crate::stores::cashu_cdk_bridge;
+
+// =============================================================================
+// Tracking
+// =============================================================================
+
+/// Track a melt the mint reported as pending and start polling it
+///
+/// `input_secrets` are the proofs handed to the mint for this payment; they stay
+/// locked until the quote resolves.
+pub(crate) async fn track_pending_melt(mut quote: MeltQuoteInfo, input_secrets: Vec<String>) {
+    quote.pending_since = Some(now_secs());
+    quote.input_secrets = input_secrets;
+
+    lock_pending_melt_inputs(&quote);
+
+    if let Ok(localstore) = get_shared_localstore().await {
+        if let Err(e) = localstore.save_pending_melt(&quote).await {
+            log::warn!("Failed to persist pending melt {}: {}", quote.quote_id, e);
+        }
+    }
+
+    *MELT_PROGRESS.write() = Some(MeltProgress::PaymentPending {
+        quote_id: quote.quote_id.clone(),
+        amount: quote.amount,
+    });
+
+    log::info!(
+        "Melt {} is pending at the mint, {} proofs locked until it resolves",
+        quote.quote_id,
+        quote.input_secrets.len()
+    );
+
+    start_melt_quote_poller(quote.quote_id.clone(), quote.mint_url.clone());
+}
+
+/// Active keyset of a mint and its deterministic counter
+///
+/// Read before and after a melt to locate the blank outputs it sent for change.
+pub(super) async fn melt_counter(mint_url: &str) -> Option<(Id, u32)> {
+    let wallet = get_or_create_wallet(mint_url).await.ok()?;
+    let keyset = wallet.get_active_keyset().await.ok()?;
+    let counter = get_shared_localstore()
+        .await
+        .ok()?
+        .get_keyset_counter(&keyset.id)
+        .await
+        .ok()?;
+    Some((keyset.id, counter))
+}
+
+/// Blank outputs of a melt, from the counters read around it
+pub(super) fn change_outputs_between(
+    before: Option<(Id, u32)>,
+    after: Option<(Id, u32)>,
+) -> Option<MeltChangeOutputs> {
+    match (before, after) {
+        (Some((keyset_id, start)), Some((after_id, end))) if keyset_id == after_id && end > start => {
+            Some(MeltChangeOutputs {
+                keyset_id: keyset_id.to_string(),
+                counter_start: start,
+                counter_end: end,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Number of blank outputs a wallet sends to receive `overpaid` sats of change
+fn blank_output_count(overpaid: u64) -> u32 {
+    if overpaid == 0 {
+        return 0;
+    }
+    ((overpaid as f64).log2().ceil() as u32).max(1)
+}
+
+/// Inclusive counter range of the blank outputs of the melt that went through
+///
+/// A melt retried after spent inputs advanced the counter twice; the
+/// outputs of the last attempt are the last `count` counters.
+fn change_counter_range(outputs: &MeltChangeOutputs, count: u32) -> Option<(u32, u32)> {
+    if count == 0 || outputs.counter_end <= outputs.counter_start {
+        return None;
+    }
+    let start = outputs.counter_end.saturating_sub(count).max(outputs.counter_start);
+    Some((start, outputs.counter_end - 1))
+}
+
+/// Unblind the change the mint returned for a melt that settled after being pending
+///
+/// The mint signs the blank outputs in the order they were sent, so the
+/// change lines up with the first derived secrets of the range.
+async fn claim_melt_change(
+    wallet: &Wallet,
+    quote: &MeltQuoteInfo,
+    outputs: &MeltChangeOutputs,
+    input_total: u64,
+) -> Result<Vec<cdk::nuts::Proof>, String> {
+    let response = wallet
+        .melt_quote_status(&quote.quote_id)
+        .await
+        .map_err(|e| format!("Failed to fetch melt change: {}", e))?;
+    let change = response.change.unwrap_or_default();
+    if change.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let count = blank_output_count(input_total.saturating_sub(quote.amount));
+    let (start, end) = change_counter_range(outputs, count)
+        .ok_or("Mint returned change for a melt without blank outputs")?;
+    let keyset_id = Id::from_str(&outputs.keyset_id)
+        .map_err(|e| format!("Invalid keyset ID {}: {}", outputs.keyset_id, e))?;
+
+    let seed = derive_wallet_seed().await?;
+    let premint = PreMintSecrets::restore_batch(keyset_id, &seed, start, end)
+        .map_err(|e| format!("Failed to derive change outputs: {}", e))?;
+    let keys = wallet
+        .load_keyset_keys(keyset_id)
+        .await
+        .map_err(|e| format!("Failed to load keyset keys: {}", e))?;
+
+    let signed = change.len().min(premint.len());
+    cdk::dhke::construct_proofs(
+        change.into_iter().take(signed).collect(),
+        premint.rs().into_iter().take(signed).collect(),
+        premint.secrets().into_iter().take(signed).collect(),
+        &keys,
+    )
+    .map_err(|e| format!("Failed to unblind melt change: {}", e))
+}
+
+/// Resume polling for melts left pending by a previous session
+///
+/// Returns the number of pending melts resumed.
+pub async fn resume_pending_melts() -> usize {
+    let localstore = match get_shared_localstore().await {
+        Ok(store) => store,
+        Err(e) => {
+            log::warn!("Cannot resume pending melts: {}", e);
+            return 0;
+        }
+    };
+
+    let quotes = match localstore.get_all_pending_melts().await {
+        Ok(quotes) => quotes,
+        Err(e) => {
+            log::warn!("Failed to load pending melts: {}", e);
+            return 0;
+        }
+    };
+
+    for quote in &quotes {
+        log::info!("Resuming pending melt {} at {}", quote.quote_id, quote.mint_url);
+        lock_pending_melt_inputs(quote);
+        start_melt_quote_poller(quote.quote_id.clone(), quote.mint_url.clone());
+    }
+
+    quotes.len()
+}
+
+/// Register the quote in PENDING_MELT_QUOTES and lock its input proofs
+fn lock_pending_melt_inputs(quote: &MeltQuoteInfo) {
+    {
+        let store = PENDING_MELT_QUOTES.read();
+        let mut data = store.data();
+        let mut quotes = data.write();
+        quotes.retain(|q| q.quote_id != quote.quote_id);
+        quotes.push(quote.clone());
+    }
+
+    let tx_id = create_transaction(
+        TransactionType::Transfer,
+        quote.amount,
+        &quote.mint_url,
+        quote.input_secrets.clone(),
+        Some(quote.quote_id.clone()),
+        None,
+    );
+    update_transaction_status(
+        tx_id,
+        TransactionStatus::Pending,
+        Some("Lightning payment pending at mint".to_string()),
+        None,
+    );
+
+    move_proofs_to_pending_spent(&quote.input_secrets, tx_id);
+    register_proofs_pending_at_mint(&quote.input_secrets);
+    recalculate_balance();
+}
+
+/// Find a tracked melt that is still waiting on the mint
+pub(super) fn find_pending_melt(quote_id: &str, mint_url: &str) -> Option<MeltQuoteInfo> {
+    PENDING_MELT_QUOTES
+        .read()
+        .data()
+        .read()
+        .iter()
+        .find(|q| q.quote_id == quote_id && q.mint_url == mint_url && q.is_payment_pending())
+        .cloned()
+}
+
+// =============================================================================
+// Resolution
+// =============================================================================
+
+/// Finalize a melt that settled after being pending: publish the remaining
+/// proofs (including change), record history and drop the quote
+pub(super) async fn finalize_paid_melt(quote: &MeltQuoteInfo) -> Result<(), String> {
+    // Another operation on this mint will finish first; retry on the next poll
+    let _lock_guard = try_acquire_mint_lock(&quote.mint_url)
+        .ok_or_else(|| format!("Mint {} busy, deferring melt finalization", quote.mint_url))?;
+
+    let input_secrets: HashSet<&String> = quote.input_secrets.iter().collect();
+    let (all_proofs, event_ids_to_delete) = get_proofs_and_events_for_mint(&quote.mint_url)?;
+    let total_before: u64 = all_proofs
+        .iter()
+        .map(|p| u64::from(p.amount))
+        .fold(0u64, |acc, amt| acc.saturating_add(amt));
+    let input_total: u64 = all_proofs
+        .iter()
+        .filter(|p| input_secrets.contains(&p.secret.to_string()))
+        .map(|p| u64::from(p.amount))
+        .fold(0u64, |acc, amt| acc.saturating_add(amt));
+
+    // The cached wallet has processed the change and knows which inputs were spent
+    let unspent_local: Vec<cdk::nuts::Proof> = all_proofs
+        .into_iter()
+        .filter(|p| !input_secrets.contains(&p.secret.to_string()))
+        .collect();
+    let wallet = create_ephemeral_wallet(&quote.mint_url, unspent_local).await?;
+    let mut keep_proofs = wallet
+        .get_unspent_proofs()
+        .await
+        .map_err(|e| format!("Failed to get remaining proofs: {}", e))?;
+
+    // The mint only hands out change for a settled melt in the quote status
+    if let Some(outputs) = &quote.change_outputs {
+        match claim_melt_change(&wallet, quote, outputs, input_total).await {
+            Ok(change) if !change.is_empty() => {
+                let change_total: u64 = change
+                    .iter()
+                    .map(|p| u64::from(p.amount))
+                    .fold(0u64, |acc, amt| acc.saturating_add(amt));
+                log::info!("Claimed {} sats of change for melt {}", change_total, quote.quote_id);
+                create_ephemeral_wallet(&quote.mint_url, change.clone()).await?;
+                keep_proofs.extend(change);
+            }
+            Ok(_) => {}
+            Err(e) => log::warn!("Could not claim change of melt {}: {}", quote.quote_id, e),
+        }
+    }
+
+    let total_after: u64 = keep_proofs
+        .iter()
+        .map(|p| u64::from(p.amount))
+        .fold(0u64, |acc, amt| acc.saturating_add(amt));
+    let total_paid = total_before.saturating_sub(total_after);
+    let fee_paid = total_paid.saturating_sub(quote.amount);
+
+    let new_event_id =
+        publish_melt_events(&quote.mint_url, &keep_proofs, &event_ids_to_delete).await?;
+    update_local_state_after_melt(&quote.mint_url, &keep_proofs, &event_ids_to_delete, &new_event_id)?;
+
+    remove_from_pending_at_mint(&quote.input_secrets);
+    settle_transaction(&quote.quote_id, TransactionStatus::Completed, "Lightning payment settled", Some(fee_paid));
+
+    let valid_destroyed: Vec<String> = event_ids_to_delete
+        .iter()
+        .filter(|id| nostr_sdk::EventId::from_hex(id).is_ok())
+        .cloned()
+        .collect();
+    if let Err(e) = create_history_event_with_type(
+        "out",
+        total_paid,
+        new_event_id.iter().cloned().collect(),
+        valid_destroyed,
+        Some("lightning_melt"),
+        Some(&quote.invoice),
+    )
+    .await
+    {
+        log::warn!("Failed to create history event for settled melt: {}", e);
+    }
+
+    forget_pending_melt(&quote.quote_id).await;
+
+    if matches!(&*MELT_PROGRESS.read(), Some(MeltProgress::PaymentPending { quote_id, .. }) if *quote_id == quote.quote_id) {
+        *MELT_PROGRESS.write() = Some(MeltProgress::Completed {
+            total_paid,
+            fee_paid,
+            preimage: None,
+        });
+    }
+
+    if let Err(e) = cashu_cdk_bridge::sync_wallet_state().await {
+        log::warn!("Failed to sync MultiMintWallet state after pending melt: {}", e);
+    }
+
+    log::info!(
+        "Pending melt {} settled: paid {} sats (fee {})",
+        quote.quote_id,
+        total_paid,
+        fee_paid
+    );
+    Ok(())
+}
+
+/// Restore the locked proofs of a melt that failed after being pending
+pub(super) async fn restore_failed_melt(quote: &MeltQuoteInfo) {
+    revert_proofs_to_spendable(&quote.input_secrets);
+    remove_from_pending_at_mint(&quote.input_secrets);
+    recalculate_balance();
+
+    settle_transaction(&quote.quote_id, TransactionStatus::Reverted, "Lightning payment failed, proofs restored", None);
+    forget_pending_melt(&quote.quote_id).await;
+
+    if matches!(&*MELT_PROGRESS.read(), Some(MeltProgress::PaymentPending { quote_id, .. }) if *quote_id == quote.quote_id) {
+        *MELT_PROGRESS.write() = Some(MeltProgress::Failed {
+            error: "Lightning payment failed. Your funds have been restored.".to_string(),
+        });
+    }
+
+    log::info!(
+        "Pending melt {} failed, restored {} proofs",
+        quote.quote_id,
+        quote.input_secrets.len()
+    );
+}
+
+/// Stop tracking a melt whose quote expired while the payment was in flight
+///
+/// The proofs stay locked and the persisted record is kept, so the next
+/// session checks the quote once more on startup.
+pub(super) fn expire_pending_melt(quote: &MeltQuoteInfo) {
+    PENDING_MELT_QUOTES
+        .read()
+        .data()
+        .write()
+        .retain(|q| q.quote_id != quote.quote_id);
+
+    if matches!(&*MELT_PROGRESS.read(), Some(MeltProgress::PaymentPending { quote_id, .. }) if *quote_id == quote.quote_id) {
+        *MELT_PROGRESS.write() = Some(MeltProgress::Failed {
+            error: "The payment is still unconfirmed after the quote expired. Your funds stay locked until the mint resolves it.".to_string(),
+        });
+    }
+
+    log::warn!(
+        "Melt quote {} expired while still pending, stopped polling",
+        quote.quote_id
+    );
+}
+
+fn settle_transaction(quote_id: &str, status: TransactionStatus, message: &str, fee_paid: Option<u64>) {
+    let tx_ids: Vec<u64> = ACTIVE_TRANSACTIONS
+        .read()
+        .iter()
+        .filter(|tx| tx.quote_id.as_deref() == Some(quote_id))
+        .map(|tx| tx.id)
+        .collect();
+
+    for tx_id in tx_ids {
+        update_transaction_status(tx_id, status.clone(), Some(message.to_string()), fee_paid);
+    }
+}
+
+/// Drop a resolved melt quote from memory and both IndexedDB stores
+async fn forget_pending_melt(quote_id: &str) {
+    PENDING_MELT_QUOTES
+        .read()
+        .data()
+        .write()
+        .retain(|q| q.quote_id != quote_id);
+
+    if let Ok(localstore) = get_shared_localstore().await {
+        if let Err(e) = localstore.remove_pending_melt(quote_id).await {
+            log::warn!("Failed to remove pending melt {}: {}", quote_id, e);
+        }
+    }
+
+    if let Err(e) = remove_melt_quote_from_db(quote_id).await {
+        log::warn!("Failed to remove melt quote from database: {}", e);
+    }
+}
+
+/// Secrets of all local proofs for a mint not kept by a melt (i.e. its inputs)
+pub(crate) fn melt_input_secrets(mint_url: &str, keep_proofs: &[cdk::nuts::Proof]) -> Vec<String> {
+    let keep: HashSet<String> = keep_proofs.iter().map(|p| p.secret.to_string()).collect();
+    get_all_proofs_for_mint(mint_url)
+        .into_iter()
+        .map(|p| p.secret)
+        .filter(|secret| !keep.contains(secret))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_change_counter_range() {
+        assert_eq!(blank_output_count(0), 0);
+        assert_eq!(blank_output_count(1), 1);
+        assert_eq!(blank_output_count(3), 2);
+        assert_eq!(blank_output_count(1000), 10);
+
+        let outputs = MeltChangeOutputs {
+            keyset_id: "009a1f293253e41e".to_string(),
+            counter_start: 40,
+            counter_end: 50,
+        };
+        // A single attempt used the whole range
+        assert_eq!(change_counter_range(&outputs, 10), Some((40, 49)));
+        // A retried melt: the last attempt's outputs are at the end
+        assert_eq!(change_counter_range(&outputs, 4), Some((46, 49)));
+        // Never before the counter read ahead of the melt
+        assert_eq!(change_counter_range(&outputs, 12), Some((40, 49)));
+        assert_eq!(change_counter_range(&outputs, 0), None);
+    }
+}
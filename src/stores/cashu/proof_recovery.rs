@@ -397,7 +397,7 @@ pub fn get_recovery_stats() -> (usize, u64, usize, u64) {
 // =============================================================================
 
 /// Recalculate and update wallet balance
-fn recalculate_balance() {
+pub(super) fn recalculate_balance() {
     let store = WALLET_TOKENS();
     let data = store.data();
     let tokens = data.read();
@@ -0,0 +1,189 @@
+//! Live proof state watch (NUT-17) so the balance follows spends made elsewhere
+//!
+//! Once the wallet has loaded, every mint that supports NUT-17 gets a proof
+//! state subscription for the proofs we hold there; mints without WebSocket
+//! support are polled over NUT-07 instead. A held proof turning SPENT (for
+//! example a token redeemed from another device) runs the spent proof
+//! cleanup for that mint, and the watch restarts with the proofs we hold
+//! afterwards. Subscriptions are also renewed every few minutes to pick up
+//! proofs received in the meantime; a dropped connection falls back to one
+//! polling round before resubscribing. Everything stops on logout.
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use cdk::dhke::hash_to_curve;
+use dioxus::prelude::*;
+use dioxus_core::spawn_forever;
+use futures::future::{select, Either};
+use gloo_timers::future::TimeoutFuture;
+
+use super::mint_mgmt::get_mints;
+use super::recovery::cleanup_spent_proofs;
+use super::signals::WALLET_TOKENS;
+use super::utils::normalize_mint_url;
+use super::ws::{self as cashu_ws, ProofState, ProofStateNotification};
+
+/// Bumped to stop the running watch; each mint loop exits when it changes
+static WATCH_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// How often mints without WebSocket support are polled
+const POLL_INTERVAL_MS: u32 = 60_000;
+
+/// How long a subscription lives before it's renewed with the current proofs
+const RESUBSCRIBE_INTERVAL_MS: u32 = 300_000;
+
+/// Proofs being watched at one mint, by Y value
+#[derive(Debug, Default)]
+pub struct ProofWatch {
+    watched: HashSet<String>,
+}
+
+impl ProofWatch {
+    pub fn new(y_values: impl IntoIterator<Item = String>) -> Self {
+        Self { watched: y_values.into_iter().collect() }
+    }
+
+    /// Whether a notification calls for a cleanup at this mint
+    ///
+    /// Only a watched proof turning SPENT counts, and each proof only once so a
+    /// burst of notifications for the same proof runs one cleanup.
+    pub fn needs_cleanup(&mut self, notification: &ProofStateNotification) -> bool {
+        notification.state == ProofState::Spent && self.watched.remove(&notification.y)
+    }
+}
+
+/// Y values (hash_to_curve of the secret) of the proofs we hold at a mint
+fn held_y_values(mint_url: &str) -> Vec<String> {
+    let mint_url = normalize_mint_url(mint_url);
+    let tokens = WALLET_TOKENS.peek().data().peek().clone();
+    tokens
+        .iter()
+        .filter(|token| normalize_mint_url(&token.mint) == mint_url)
+        .flat_map(|token| token.proofs.iter())
+        .filter_map(|proof| hash_to_curve(proof.secret.as_bytes()).ok())
+        .map(|y| y.to_string())
+        .collect()
+}
+
+fn closures_key(mint_url: &str) -> String {
+    format!("{}:proof_watch", mint_url)
+}
+
+/// Start watching proof states at every wallet mint, replacing any running watch
+pub fn start_proof_state_watch() {
+    let generation = WATCH_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+    for mint_url in get_mints() {
+        cashu_ws::close_proof_state_subscription(&closures_key(&mint_url));
+        spawn_forever(watch_mint(mint_url, generation));
+    }
+}
+
+/// Stop the proof state watch and close its subscriptions (on logout)
+pub fn stop_proof_state_watch() {
+    WATCH_GENERATION.fetch_add(1, Ordering::SeqCst);
+    for mint_url in get_mints() {
+        cashu_ws::close_proof_state_subscription(&closures_key(&mint_url));
+    }
+}
+
+fn is_current(generation: u64) -> bool {
+    WATCH_GENERATION.load(Ordering::SeqCst) == generation
+}
+
+async fn run_cleanup(mint_url: &str) {
+    match cleanup_spent_proofs(mint_url.to_string()).await {
+        Ok((count, sats)) if count > 0 => {
+            log::info!("Removed {} proofs ({} sats) spent elsewhere at {}", count, sats, mint_url);
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("Spent proof cleanup failed for {}: {}", mint_url, e),
+    }
+}
+
+async fn watch_mint(mint_url: String, generation: u64) {
+    let use_websocket = cashu_ws::mint_supports_websocket(&mint_url).await;
+    log::info!(
+        "Watching proof states at {} via {}",
+        mint_url,
+        if use_websocket { "WebSocket" } else { "polling" }
+    );
+
+    while is_current(generation) {
+        let y_values = held_y_values(&mint_url);
+        if y_values.is_empty() {
+            TimeoutFuture::new(POLL_INTERVAL_MS).await;
+            continue;
+        }
+        let mut watch = ProofWatch::new(y_values.clone());
+
+        if use_websocket {
+            let key = closures_key(&mint_url);
+            match cashu_ws::open_proof_state_subscription(mint_url.clone(), y_values.clone(), key.clone()) {
+                Ok(mut rx) => {
+                    let mut renew = TimeoutFuture::new(RESUBSCRIBE_INTERVAL_MS);
+                    // None when the connection dropped (or stop_proof_state_watch closed it)
+                    let spent = loop {
+                        match select(Box::pin(rx.recv()), &mut renew).await {
+                            Either::Left((Some(notification), _)) => {
+                                if watch.needs_cleanup(&notification) {
+                                    break Some(true);
+                                }
+                            }
+                            Either::Left((None, _)) => break None,
+                            Either::Right(_) => break Some(false),
+                        }
+                    };
+                    cashu_ws::close_proof_state_subscription(&key);
+                    if !is_current(generation) {
+                        break;
+                    }
+                    match spent {
+                        Some(spent) => {
+                            if spent {
+                                run_cleanup(&mint_url).await;
+                            }
+                            continue;
+                        }
+                        None => log::debug!("Proof state WebSocket for {} closed, polling this round", mint_url),
+                    }
+                }
+                Err(e) => log::warn!("Proof state subscription failed for {}: {}, polling", mint_url, e),
+            }
+        }
+
+        match cashu_ws::poll_proof_states(&mint_url, y_values).await {
+            Ok(states) => {
+                if states.iter().any(|notification| watch.needs_cleanup(notification)) && is_current(generation) {
+                    run_cleanup(&mint_url).await;
+                }
+            }
+            Err(e) => log::debug!("Proof state poll failed for {}: {}", mint_url, e),
+        }
+        TimeoutFuture::new(POLL_INTERVAL_MS).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(y: &str, state: &str) -> ProofStateNotification {
+        serde_json::from_value(serde_json::json!({ "Y": y, "state": state, "witness": null })).unwrap()
+    }
+
+    #[test]
+    fn test_spent_notification_dispatches_one_cleanup() {
+        let mut watch = ProofWatch::new(vec!["02aa".to_string(), "02bb".to_string()]);
+
+        // Pending and unknown proofs don't trigger anything
+        assert!(!watch.needs_cleanup(&notification("02aa", "PENDING")));
+        assert!(!watch.needs_cleanup(&notification("02cc", "SPENT")));
+        assert!(!watch.needs_cleanup(&notification("02bb", "UNSPENT")));
+
+        assert!(watch.needs_cleanup(&notification("02aa", "SPENT")));
+        // A repeated notification for the same proof is ignored
+        assert!(!watch.needs_cleanup(&notification("02aa", "SPENT")));
+        assert!(watch.needs_cleanup(&notification("02bb", "SPENT")));
+    }
+}
@@ -11,28 +11,7 @@ use std::sync::atomic::Ordering;
 
 use super::signals::*;
 use super::types::*;
-use super::utils::mint_matches;
-
-// =============================================================================
-// Cross-Platform Time Helper
-// =============================================================================
-
-/// Cross-platform timestamp helper (aligned with nostr SDK pattern)
-/// Returns current time in seconds since Unix epoch
-pub fn now_secs() -> u64 {
-    #[cfg(target_arch = "wasm32")]
-    {
-        (js_sys::Date::now() / 1000.0) as u64
-    }
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        use std::time::{SystemTime, UNIX_EPOCH};
-        SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map(|d| d.as_secs())
-            .unwrap_or(0)
-    }
-}
+use super::utils::{mint_matches, now_secs};
 
 // =============================================================================
 // Proof-to-Event Mapping
@@ -113,6 +92,30 @@ pub fn move_proofs_to_reserved(proof_secrets: &[String], tx_id: u64) {
     );
 }
 
+/// Move proofs to PendingSpent state (submitted to the mint, awaiting settlement)
+pub fn move_proofs_to_pending_spent(proof_secrets: &[String], tx_id: u64) {
+    let store = WALLET_TOKENS.read();
+    let mut data = store.data();
+    let mut tokens = data.write();
+    let now = now_secs();
+
+    for token in tokens.iter_mut() {
+        for proof in &mut token.proofs {
+            if proof_secrets.contains(&proof.secret) {
+                proof.state = ProofState::PendingSpent;
+                proof.transaction_id = Some(tx_id);
+                proof.state_set_at = Some(now);
+            }
+        }
+    }
+
+    log::debug!(
+        "Marked {} proofs as PendingSpent for tx {}",
+        proof_secrets.len(),
+        tx_id
+    );
+}
+
 /// Move proofs to Spent state
 pub fn move_proofs_to_spent(proof_secrets: &[String]) {
     let store = WALLET_TOKENS.read();
@@ -195,7 +195,7 @@ pub fn cleanup_expired_melt_quotes() -> usize {
         .data()
         .read()
         .iter()
-        .filter(|q| is_quote_expired(q.expiry))
+        .filter(|q| is_quote_expired(q.expiry) && !q.is_payment_pending())
         .map(|q| q.quote_id.clone())
         .collect();
 
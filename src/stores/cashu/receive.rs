@@ -6,13 +6,17 @@ use dioxus::prelude::*;
 use nostr_sdk::signer::NostrSigner;
 use nostr_sdk::{Kind, PublicKey};
 
+use super::dust::schedule_dust_sweep;
 use super::internal::{
     cleanup_spent_proofs_internal, collect_p2pk_signing_keys, create_ephemeral_wallet,
     is_token_already_spent_error,
 };
+use super::mint_mgmt::with_mint_reliability;
 use super::proofs::{cdk_proof_to_proof_data, register_proofs_in_event_map};
 use super::signals::{try_acquire_mint_lock, WALLET_BALANCE, WALLET_TOKENS};
-use super::types::{ExtendedCashuProof, ExtendedTokenEvent, ProofData, TokenData, WalletTokensStoreStoreExt};
+use super::types::{
+    ExtendedCashuProof, ExtendedTokenEvent, MintOperation, ProofData, TokenData, WalletTokensStoreStoreExt,
+};
 use super::utils::normalize_mint_url;
 use crate::stores::{auth_store, cashu_cdk_bridge, nostr_client};
 
@@ -63,13 +67,8 @@ pub async fn receive_tokens_with_options(
         token_string.chars().take(10).collect::<String>()
     );
 
-    // Validate token format
-    if !token_string.starts_with("cashuA") && !token_string.starts_with("cashuB") {
-        return Err(format!(
-            "Invalid token format. Cashu tokens must start with 'cashuA' or 'cashuB'. Your token starts with: '{}'",
-            token_string.chars().take(10).collect::<String>()
-        ));
-    }
+    // Validate token format, unwrapping `cashu:` URIs and bare TokenV4 payloads
+    let token_string = super::token::normalize_token_input(&token_string)?;
 
     // Check for control characters that might indicate encoding issues
     if token_string.chars().any(|c| c.is_control()) {
@@ -80,11 +79,7 @@ pub async fn receive_tokens_with_options(
     }
 
     // Extract and validate the base64 portion
-    let base64_part = if token_string.starts_with("cashuA") || token_string.starts_with("cashuB") {
-        &token_string[6..]
-    } else {
-        ""
-    };
+    let base64_part = &token_string[6..];
 
     log::info!(
         "Base64 portion length: {}, last 20 chars: {}",
@@ -246,7 +241,13 @@ pub async fn receive_tokens_with_options(
     };
 
     // Receive token (contacts mint to swap proofs)
-    let amount_received = match wallet.receive(&token_to_parse, receive_opts).await {
+    let amount_received = match with_mint_reliability(
+        &mint_url,
+        MintOperation::Swap,
+        wallet.receive(&token_to_parse, receive_opts),
+    )
+    .await
+    {
         Ok(amount) => amount,
         Err(e) => {
             if is_token_already_spent_error(&e) {
@@ -382,5 +383,9 @@ pub async fn receive_tokens_with_options(
         log::warn!("Failed to sync MultiMintWallet state after receive: {}", e);
     }
 
+    // Release the mint before the sweep tries to take it
+    drop(_lock_guard);
+    schedule_dust_sweep(mint_url);
+
     Ok(amount)
 }
@@ -1211,6 +1211,11 @@ pub async fn process_pending_melt_quotes() -> Result<(usize, usize, usize), Stri
     for quote in quotes {
         checked += 1;
 
+        // In-flight payments outlive their quote expiry; the pending melt poller owns them
+        if quote.is_payment_pending() {
+            continue;
+        }
+
         // Check expiry
         if let Some(expiry) = quote.expiry {
             if now >= expiry {
@@ -1440,3 +1445,504 @@ pub fn start_quote_processor() {
         }
     });
 }
+
+// =============================================================================
+// Encrypted Wallet Backup
+// =============================================================================
+
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use serde::{Deserialize, Serialize};
+
+use super::signals::{WALLET_STATE, WALLET_TOKENS};
+
+/// Backup file format version
+const BACKUP_VERSION: u8 = 1;
+
+/// scrypt cost (log2 N) for new backups, as recommended by NIP-49
+const BACKUP_SCRYPT_LOG_N: u8 = 16;
+
+/// Highest scrypt cost accepted from a backup file: what the exporter writes
+const MAX_BACKUP_SCRYPT_LOG_N: u8 = BACKUP_SCRYPT_LOG_N;
+
+/// Everything needed to restore a wallet without relays
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalletBackup {
+    pub version: u8,
+    pub created_at: u64,
+    /// Wallet P2PK private key (hex)
+    pub privkey: Option<String>,
+    pub mints: Vec<String>,
+    pub tokens: Vec<BackupToken>,
+}
+
+/// Proofs from one token event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackupToken {
+    pub mint: String,
+    pub unit: String,
+    pub proofs: Vec<ProofData>,
+}
+
+/// The encrypted file: scrypt-derived key, XChaCha20-Poly1305 ciphertext
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupEnvelope {
+    version: u8,
+    kdf: String,
+    log_n: u8,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Result of importing a backup
+#[derive(Debug, Clone, Default)]
+pub struct BackupImportSummary {
+    pub tokens_published: usize,
+    pub proofs_imported: usize,
+    pub sats_imported: u64,
+    /// Proofs skipped because the wallet already holds them
+    pub duplicate_proofs: usize,
+    pub duplicate_sats: u64,
+    /// Proofs skipped because the mint reports them spent
+    pub spent_proofs: usize,
+    pub spent_sats: u64,
+}
+
+fn backup_key(password: &str, salt: &[u8], log_n: u8) -> Result<[u8; 32], String> {
+    let params = scrypt::Params::new(log_n, 8, 1, 32)
+        .map_err(|e| format!("Invalid scrypt parameters: {}", e))?;
+    let mut key = [0u8; 32];
+    scrypt::scrypt(password.as_bytes(), salt, &params, &mut key)
+        .map_err(|e| format!("Failed to derive backup key: {}", e))?;
+    Ok(key)
+}
+
+/// Encrypt `plaintext` with a key derived from `password` at scrypt cost `log_n`
+fn encrypt_backup_with(plaintext: &str, password: &str, log_n: u8) -> Result<String, String> {
+    use rand::RngCore;
+
+    if password.is_empty() {
+        return Err("Backup password cannot be empty".to_string());
+    }
+
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 24];
+    rand::thread_rng().fill_bytes(&mut salt);
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let key = backup_key(password, &salt, log_n)?;
+    let cipher = XChaCha20Poly1305::new(&key.into());
+    let ciphertext = cipher
+        .encrypt(XNonce::from_slice(&nonce), plaintext.as_bytes())
+        .map_err(|_| "Failed to encrypt backup".to_string())?;
+
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let envelope = BackupEnvelope {
+        version: BACKUP_VERSION,
+        kdf: "scrypt".to_string(),
+        log_n,
+        salt: b64.encode(salt),
+        nonce: b64.encode(nonce),
+        ciphertext: b64.encode(ciphertext),
+    };
+    serde_json::to_string(&envelope).map_err(|e| format!("Failed to serialize backup: {}", e))
+}
+
+/// Decrypt a backup file; a wrong password fails authentication
+fn decrypt_backup(data: &str, password: &str) -> Result<String, String> {
+    let envelope: BackupEnvelope = serde_json::from_str(data.trim())
+        .map_err(|_| "Not a wallet backup file".to_string())?;
+    if envelope.version != BACKUP_VERSION || envelope.kdf != "scrypt" {
+        return Err(format!(
+            "Unsupported backup format (version {}, {})",
+            envelope.version, envelope.kdf
+        ));
+    }
+    if envelope.log_n == 0 || envelope.log_n > MAX_BACKUP_SCRYPT_LOG_N {
+        return Err(format!("Unsupported scrypt cost: {}", envelope.log_n));
+    }
+
+    let b64 = base64::engine::general_purpose::STANDARD;
+    let decode = |field: &str, value: &str| {
+        b64.decode(value).map_err(|e| format!("Corrupt backup {}: {}", field, e))
+    };
+    let salt = decode("salt", &envelope.salt)?;
+    let nonce = decode("nonce", &envelope.nonce)?;
+    let ciphertext = decode("ciphertext", &envelope.ciphertext)?;
+    if nonce.len() != 24 {
+        return Err("Corrupt backup nonce".to_string());
+    }
+
+    let key = backup_key(password, &salt, envelope.log_n)?;
+    let cipher = XChaCha20Poly1305::new(&key.into());
+    let plaintext = cipher
+        .decrypt(XNonce::from_slice(&nonce), ciphertext.as_ref())
+        .map_err(|_| "Wrong password or corrupted backup".to_string())?;
+
+    String::from_utf8(plaintext).map_err(|_| "Corrupt backup contents".to_string())
+}
+
+/// Split backup tokens into proofs the wallet doesn't hold yet
+///
+/// Proofs are matched by secret against `existing_secrets` and each other.
+/// Returns the tokens left with new proofs and the duplicates skipped.
+fn new_backup_proofs(
+    tokens: Vec<BackupToken>,
+    existing_secrets: &std::collections::HashSet<String>,
+) -> (Vec<BackupToken>, Vec<ProofData>) {
+    let mut seen = existing_secrets.clone();
+    let mut duplicates = Vec::new();
+    let fresh = tokens
+        .into_iter()
+        .filter_map(|mut token| {
+            let (new, dup): (Vec<_>, Vec<_>) = token
+                .proofs
+                .into_iter()
+                .partition(|p| seen.insert(p.secret.clone()));
+            duplicates.extend(dup);
+            token.proofs = new;
+            (!token.proofs.is_empty()).then_some(token)
+        })
+        .collect();
+    (fresh, duplicates)
+}
+
+/// Drop backup proofs their mint reports as spent (NUT-07)
+///
+/// A mint that can't be asked fails the import: storing proofs that may be
+/// spent would show funds the wallet doesn't have.
+async fn unspent_backup_tokens(
+    tokens: Vec<BackupToken>,
+    summary: &mut BackupImportSummary,
+) -> Result<Vec<BackupToken>, String> {
+    use std::collections::HashSet;
+
+    let mut unspent = Vec::with_capacity(tokens.len());
+    for mut token in tokens {
+        let wallet = super::internal::get_or_create_wallet(&token.mint)
+            .await
+            .map_err(|e| format!("Couldn't reach {} to check backup proofs: {}", token.mint, e))?;
+        let cdk_proofs = token
+            .proofs
+            .iter()
+            .map(proof_data_to_cdk_proof)
+            .collect::<Result<Vec<_>, _>>()?;
+        let checked = super::utils::validate_proofs_batched(&wallet, cdk_proofs).await?;
+
+        let valid: HashSet<String> = checked.valid_proofs.iter().map(|p| p.secret.to_string()).collect();
+        let (keep, spent): (Vec<_>, Vec<_>) =
+            token.proofs.into_iter().partition(|p| valid.contains(&p.secret));
+        summary.spent_proofs += spent.len();
+        summary.spent_sats = spent.iter().map(|p| p.amount).fold(summary.spent_sats, |a, b| a.saturating_add(b));
+
+        token.proofs = keep;
+        if !token.proofs.is_empty() {
+            unspent.push(token);
+        }
+    }
+
+    if summary.spent_proofs > 0 {
+        log::warn!(
+            "Backup contains {} proofs ({} sats) already spent at their mint; they were not imported",
+            summary.spent_proofs,
+            summary.spent_sats
+        );
+    }
+    Ok(unspent)
+}
+
+/// Export the wallet key, mints and all proofs as a password-encrypted file
+///
+/// The file is independent of relays: anyone with it and the password can
+/// spend the proofs, so it must be kept as safe as the funds themselves.
+pub fn export_wallet_backup(password: &str) -> Result<String, String> {
+    if !is_wallet_initialized() {
+        return Err("Wallet not initialized".to_string());
+    }
+
+    let (privkey, mints) = WALLET_STATE
+        .read()
+        .as_ref()
+        .map(|w| (w.privkey.clone(), w.mints.clone()))
+        .unwrap_or_default();
+
+    let tokens: Vec<BackupToken> = {
+        let store = WALLET_TOKENS.read();
+        let data = store.data();
+        let tokens = data.read();
+        tokens
+            .iter()
+            .filter(|t| !t.proofs.is_empty())
+            .map(|t| BackupToken {
+                mint: t.mint.clone(),
+                unit: t.unit.clone(),
+                proofs: t.proofs.clone(),
+            })
+            .collect()
+    };
+
+    let backup = WalletBackup {
+        version: BACKUP_VERSION,
+        created_at: super::utils::now_secs(),
+        privkey,
+        mints,
+        tokens,
+    };
+    let json = serde_json::to_string(&backup)
+        .map_err(|e| format!("Failed to serialize backup: {}", e))?;
+
+    log::info!(
+        "Exporting wallet backup with {} proofs",
+        backup.tokens.iter().map(|t| t.proofs.len()).sum::<usize>()
+    );
+    encrypt_backup_with(&json, password, BACKUP_SCRYPT_LOG_N)
+}
+
+/// Restore a wallet from an encrypted backup and republish its proofs
+///
+/// Creates the wallet from the backup when none exists. Proofs the wallet
+/// already holds are skipped with a warning, since publishing them again
+/// would double-count them, as are proofs the mint reports spent; the
+/// summary reports how many were skipped.
+pub async fn import_wallet_backup(data: &str, password: &str) -> Result<BackupImportSummary, String> {
+    use std::collections::HashSet;
+
+    let backup: WalletBackup = serde_json::from_str(&decrypt_backup(data, password)?)
+        .map_err(|e| format!("Invalid backup contents: {}", e))?;
+
+    // Restore or extend the wallet itself
+    if !is_wallet_initialized() {
+        let privkey = backup.privkey.clone().ok_or("Backup has no wallet key")?;
+        super::init::publish_wallet(privkey, backup.mints.clone()).await?;
+        log::info!("Restored wallet from backup");
+    } else {
+        let current_privkey = WALLET_STATE.read().as_ref().and_then(|w| w.privkey.clone());
+        if backup.privkey.is_some() && backup.privkey != current_privkey {
+            log::warn!(
+                "Backup was made with a different wallet key; keeping the current key. \
+                P2PK-locked proofs from the backup may not be spendable."
+            );
+        }
+        let current_mints: HashSet<String> =
+            get_mints().iter().map(|m| super::utils::normalize_mint_url(m)).collect();
+        for mint in &backup.mints {
+            if !current_mints.contains(&super::utils::normalize_mint_url(mint)) {
+                if let Err(e) = super::mint_mgmt::add_mint(mint).await {
+                    log::warn!("Failed to add mint {} from backup: {}", mint, e);
+                }
+            }
+        }
+    }
+
+    let existing_secrets: HashSet<String> = {
+        let store = WALLET_TOKENS.read();
+        let data = store.data();
+        let tokens = data.read();
+        tokens.iter().flat_map(|t| t.proofs.iter().map(|p| p.secret.clone())).collect()
+    };
+    let (tokens, duplicates) = new_backup_proofs(backup.tokens, &existing_secrets);
+
+    let mut summary = BackupImportSummary {
+        duplicate_proofs: duplicates.len(),
+        duplicate_sats: duplicates.iter().map(|p| p.amount).fold(0u64, |a, b| a.saturating_add(b)),
+        ..Default::default()
+    };
+    if summary.duplicate_proofs > 0 {
+        log::warn!(
+            "WARNING: backup contains {} proofs ({} sats) already in this wallet. \
+            They were NOT imported again to avoid duplicate proofs.",
+            summary.duplicate_proofs,
+            summary.duplicate_sats
+        );
+    }
+    let tokens = unspent_backup_tokens(tokens, &mut summary).await?;
+
+    let mut created_events = Vec::new();
+    for token in tokens {
+        let amount = token.proofs.iter().map(|p| p.amount).fold(0u64, |a, b| a.saturating_add(b));
+        let count = token.proofs.len();
+        let event_id = publish_backup_token(token).await?;
+        created_events.push(event_id);
+        summary.tokens_published += 1;
+        summary.proofs_imported += count;
+        summary.sats_imported = summary.sats_imported.saturating_add(amount);
+    }
+
+    if created_events.is_empty() {
+        log::info!("Backup import found no new proofs");
+        return Ok(summary);
+    }
+
+    super::proof_recovery::recalculate_balance();
+
+    if let Err(e) = inject_nip60_proofs_to_cdk().await {
+        log::warn!("Failed to inject imported proofs to CDK: {}", e);
+    }
+
+    if let Err(e) = super::lightning::create_history_event_with_type(
+        "in",
+        summary.sats_imported,
+        created_events,
+        vec![],
+        Some("backup_import"),
+        None,
+    )
+    .await
+    {
+        log::warn!("Failed to record backup import in history: {}", e);
+    }
+
+    log::info!(
+        "Imported {} proofs ({} sats) from backup",
+        summary.proofs_imported,
+        summary.sats_imported
+    );
+    Ok(summary)
+}
+
+/// Publish one backup token as a token event (kind 7375) and track it locally
+async fn publish_backup_token(token: BackupToken) -> Result<String, String> {
+    use nostr_sdk::signer::NostrSigner;
+    use nostr_sdk::{Kind, PublicKey};
+    use crate::stores::{auth_store, nostr_client};
+
+    let signer = crate::stores::signer::get_signer()
+        .ok_or("No signer available")?
+        .as_nostr_signer();
+
+    let pubkey_str = auth_store::get_pubkey().ok_or("Not authenticated")?;
+    let pubkey = PublicKey::parse(&pubkey_str).map_err(|e| format!("Invalid pubkey: {}", e))?;
+
+    let token_event_data = ExtendedTokenEvent {
+        mint: token.mint.clone(),
+        unit: token.unit.clone(),
+        proofs: token.proofs.iter().cloned().map(ExtendedCashuProof::from).collect(),
+        del: vec![],
+    };
+
+    let json_content = serde_json::to_string(&token_event_data)
+        .map_err(|e| format!("Failed to serialize token event: {}", e))?;
+
+    let encrypted = signer
+        .nip44_encrypt(&pubkey, &json_content)
+        .await
+        .map_err(|e| format!("Failed to encrypt token event: {}", e))?;
+
+    let builder = nostr_sdk::EventBuilder::new(Kind::CashuWalletUnspentProof, encrypted);
+
+    let client = nostr_client::NOSTR_CLIENT
+        .read()
+        .as_ref()
+        .ok_or("Client not initialized")?
+        .clone();
+
+    let event_id = client
+        .send_event_builder(builder)
+        .await
+        .map_err(|e| format!("Failed to publish token event: {}", e))?
+        .id()
+        .to_hex();
+
+    log::info!("Published restored token event: {}", event_id);
+
+    {
+        let store = WALLET_TOKENS.read();
+        let mut data = store.data();
+        let mut tokens = data.write();
+
+        tokens.push(TokenData {
+            event_id: event_id.clone(),
+            mint: token.mint,
+            unit: token.unit,
+            proofs: token.proofs.clone(),
+            created_at: super::utils::now_secs(),
+        });
+
+        super::proofs::register_proofs_in_event_map(&event_id, &token.proofs);
+    }
+
+    Ok(event_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    // Cheap scrypt cost so the tests stay fast
+    const TEST_LOG_N: u8 = 4;
+
+    fn proof(secret: &str, amount: u64) -> ProofData {
+        serde_json::from_value(serde_json::json!({
+            "id": "009a1f293253e41e",
+            "amount": amount,
+            "secret": secret,
+            "C": "02698c4e2b5f9534cd0687d87513c759790cf829aa5739184a3e3735471fbda904",
+        }))
+        .unwrap()
+    }
+
+    fn sample_backup() -> WalletBackup {
+        WalletBackup {
+            version: BACKUP_VERSION,
+            created_at: 1_700_000_000,
+            privkey: Some("11".repeat(32)),
+            mints: vec!["https://mint.example.com".to_string()],
+            tokens: vec![BackupToken {
+                mint: "https://mint.example.com".to_string(),
+                unit: "sat".to_string(),
+                proofs: vec![proof("secret-a", 8), proof("secret-b", 2)],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_backup_round_trip() {
+        let backup = sample_backup();
+        let json = serde_json::to_string(&backup).unwrap();
+
+        let encrypted = encrypt_backup_with(&json, "correct horse battery staple", TEST_LOG_N).unwrap();
+        assert!(!encrypted.contains("secret-a"));
+        assert!(!encrypted.contains(&"11".repeat(32)));
+
+        let decrypted = decrypt_backup(&encrypted, "correct horse battery staple").unwrap();
+        let restored: WalletBackup = serde_json::from_str(&decrypted).unwrap();
+        assert_eq!(restored, backup);
+
+        // Fresh salt and nonce every time
+        assert_ne!(encrypt_backup_with(&json, "pw", TEST_LOG_N).unwrap(), encrypt_backup_with(&json, "pw", TEST_LOG_N).unwrap());
+    }
+
+    #[test]
+    fn test_backup_wrong_password() {
+        let json = serde_json::to_string(&sample_backup()).unwrap();
+        let encrypted = encrypt_backup_with(&json, "hunter2", TEST_LOG_N).unwrap();
+
+        assert_eq!(
+            decrypt_backup(&encrypted, "hunter3"),
+            Err("Wrong password or corrupted backup".to_string())
+        );
+        assert!(decrypt_backup("not a backup", "hunter2").is_err());
+        assert!(encrypt_backup_with(&json, "", TEST_LOG_N).is_err());
+    }
+
+    #[test]
+    fn test_new_backup_proofs_skips_duplicates() {
+        let mut backup = sample_backup();
+        backup.tokens.push(BackupToken {
+            mint: "https://mint.example.com".to_string(),
+            unit: "sat".to_string(),
+            // Already held, and repeated within the backup itself
+            proofs: vec![proof("secret-held", 4), proof("secret-a", 8)],
+        });
+        let existing: HashSet<String> = ["secret-held".to_string()].into_iter().collect();
+
+        let (fresh, duplicates) = new_backup_proofs(backup.tokens, &existing);
+        assert_eq!(fresh.len(), 1);
+        assert_eq!(fresh[0].proofs.len(), 2);
+        let skipped: Vec<&str> = duplicates.iter().map(|p| p.secret.as_str()).collect();
+        assert_eq!(skipped, vec!["secret-held", "secret-a"]);
+    }
+}
@@ -7,6 +7,7 @@ use nostr_sdk::signer::NostrSigner;
 use nostr_sdk::{EventId, Kind, PublicKey};
 
 use super::events::{queue_event_for_retry, queue_signed_event_for_retry};
+use super::fees::{calculate_proof_fee, get_mint_fee_ppk};
 use super::internal::{
     cleanup_spent_proofs_internal, create_ephemeral_wallet, is_insufficient_funds_error_string,
     is_token_spent_error_string, nostr_pubkey_to_cdk_pubkey, validate_proofs_with_mint,
@@ -120,15 +121,32 @@ pub async fn send_tokens(mint_url: String, amount: u64) -> Result<String, String
         ));
     }
 
+    // Only hand the mint a subset covering amount + fee to limit change and fees
+    let fee_ppk = get_mint_fee_ppk(&mint_url).await.unwrap_or_else(|e| {
+        log::warn!("Could not get fee rate for {}, assuming none: {}", mint_url, e);
+        0
+    });
+    let (send_proofs, untouched_proofs) = split_send_proofs(all_proofs, amount, fee_ppk);
+
     // Prepare and confirm send with auto-retry on spent proofs
-    let (token_string, keep_proofs) = execute_send_with_retry(
+    let (token_string, mut keep_proofs) = execute_send_with_retry(
         &mint_url,
         amount,
-        all_proofs,
+        send_proofs,
         None, // No P2PK conditions
     )
     .await?;
 
+    // Proofs left out of the send stay in the wallet (the retry path may
+    // already have used every proof, so skip ones it returned)
+    let kept: std::collections::HashSet<String> =
+        keep_proofs.iter().map(|p| p.secret.to_string()).collect();
+    keep_proofs.extend(
+        untouched_proofs
+            .into_iter()
+            .filter(|p| !kept.contains(&p.secret.to_string())),
+    );
+
     // Publish events and update state
     let new_event_id =
         publish_send_events(&mint_url, &keep_proofs, &event_ids_to_delete).await?;
@@ -158,6 +176,75 @@ pub async fn send_tokens(mint_url: String, amount: u64) -> Result<String, String
     Ok(token_string)
 }
 
+/// Pick a subset of proofs covering `amount` plus fees, preferring few, large proofs
+///
+/// The target includes the input fee of the selected proofs and the fee the
+/// recipient pays to redeem the sent outputs (`include_fee`). Each step takes
+/// the smallest proof that covers what's left, or else the largest remaining
+/// one. If the proofs can't cover the target, all of them are returned and
+/// the send fails with the usual insufficient funds error.
+pub fn select_proofs_for_amount(proofs: Vec<ProofData>, amount: u64, fee_ppk: u64) -> Vec<ProofData> {
+    let output_fee = calculate_proof_fee(amount.count_ones() as usize, fee_ppk);
+    let target = |count: usize| {
+        amount
+            .saturating_add(output_fee)
+            .saturating_add(calculate_proof_fee(count, fee_ppk))
+    };
+
+    // Largest first
+    let mut remaining = proofs;
+    remaining.sort_by(|a, b| b.amount.cmp(&a.amount));
+
+    let mut selected: Vec<ProofData> = Vec::new();
+    let mut total: u64 = 0;
+
+    while total < target(selected.len()) {
+        if remaining.is_empty() {
+            // Not enough: let the send report insufficient funds
+            selected.append(&mut remaining);
+            return selected;
+        }
+
+        let missing = target(selected.len() + 1).saturating_sub(total);
+        // Smallest proof covering the rest (exact match if there is one)
+        let index = remaining
+            .iter()
+            .rposition(|p| p.amount >= missing)
+            .unwrap_or(0);
+
+        let proof = remaining.remove(index);
+        total = total.saturating_add(proof.amount);
+        selected.push(proof);
+    }
+
+    selected
+}
+
+/// Split cdk proofs into the ones to send from and the ones left untouched
+fn split_send_proofs(
+    proofs: Vec<cdk::nuts::Proof>,
+    amount: u64,
+    fee_ppk: u64,
+) -> (Vec<cdk::nuts::Proof>, Vec<cdk::nuts::Proof>) {
+    let proof_data: Vec<ProofData> = proofs.iter().map(cdk_proof_to_proof_data).collect();
+    let selected: std::collections::HashSet<String> = select_proofs_for_amount(proof_data, amount, fee_ppk)
+        .into_iter()
+        .map(|p| p.secret)
+        .collect();
+
+    let (send, untouched): (Vec<_>, Vec<_>) = proofs
+        .into_iter()
+        .partition(|p| selected.contains(&p.secret.to_string()));
+
+    log::info!(
+        "Selected {} of {} proofs for sending {} sats",
+        send.len(),
+        send.len() + untouched.len(),
+        amount
+    );
+    (send, untouched)
+}
+
 /// Send ecash tokens locked to a recipient's public key (P2PK / NUT-11)
 ///
 /// This creates tokens that can only be spent by the holder of the corresponding
@@ -913,3 +1000,62 @@ pub fn extract_y_values_from_token(token_str: &str) -> Result<Vec<String>, Strin
 
     y_values
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proofs(amounts: &[u64]) -> Vec<ProofData> {
+        amounts
+            .iter()
+            .enumerate()
+            .map(|(i, &amount)| ProofData::for_test(amount, format!("secret-{}", i)))
+            .collect()
+    }
+
+    fn total(proofs: &[ProofData]) -> u64 {
+        proofs.iter().map(|p| p.amount).sum()
+    }
+
+    #[test]
+    fn test_selection_prefers_exact_and_large_proofs() {
+        let wallet = proofs(&[1, 2, 4, 8, 16, 32, 64, 128, 1, 1, 2]);
+        let fee_ppk = 100;
+
+        let selected = select_proofs_for_amount(wallet.clone(), 64, fee_ppk);
+        let target = 64 + calculate_proof_fee(1, fee_ppk) + calculate_proof_fee(selected.len(), fee_ppk);
+        assert!(total(&selected) >= target);
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].amount, 128);
+
+        // Fewer inputs means a lower fee than spending everything
+        let selected_fee = calculate_proof_fee(selected.len(), fee_ppk);
+        let all_fee = calculate_proof_fee(wallet.len(), fee_ppk);
+        assert!(selected_fee < all_fee);
+
+        // Without fees an exact proof is used as is
+        let exact = select_proofs_for_amount(wallet, 32, 0);
+        assert_eq!(exact.iter().map(|p| p.amount).collect::<Vec<_>>(), vec![32]);
+    }
+
+    #[test]
+    fn test_selection_with_many_small_proofs_covers_fees() {
+        let wallet = proofs(&[1; 40]);
+        let fee_ppk = 100;
+
+        let selected = select_proofs_for_amount(wallet.clone(), 10, fee_ppk);
+        let output_fee = calculate_proof_fee(10u64.count_ones() as usize, fee_ppk);
+        let input_fee = calculate_proof_fee(selected.len(), fee_ppk);
+        assert!(total(&selected) >= 10 + output_fee + input_fee);
+        assert!(selected.len() < wallet.len());
+        assert!(input_fee < calculate_proof_fee(wallet.len(), fee_ppk));
+    }
+
+    #[test]
+    fn test_selection_insufficient_returns_everything() {
+        let wallet = proofs(&[1, 2, 4]);
+        let selected = select_proofs_for_amount(wallet, 50, 0);
+        assert_eq!(total(&selected), 7);
+        assert_eq!(selected.len(), 3);
+    }
+}
@@ -88,6 +88,10 @@ pub static SHARED_LOCALSTORE: GlobalSignal<
     Option<Arc<crate::stores::indexeddb_database::IndexedDbDatabase>>,
 > = Signal::global(|| None);
 
+/// Recorded operation outcomes per normalized mint URL, loaded from IndexedDB
+pub static MINT_RELIABILITY: GlobalSignal<HashMap<String, MintReliabilityStats>> =
+    Signal::global(|| HashMap::new());
+
 // =============================================================================
 // Pending Events & Quotes Signals
 // =============================================================================
@@ -223,6 +227,7 @@ pub fn reset_wallet_state() {
     ACTIVE_TRANSACTIONS.write().clear();
     PENDING_BY_MINT_SECRETS.write().clear();
     *SYNC_STATE.write() = None;
+    MINT_RELIABILITY.write().clear();
 
     clear_shared_localstore();
 
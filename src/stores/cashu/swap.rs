@@ -14,9 +14,10 @@ use dioxus::prelude::ReadableExt;
 
 use super::denomination::DenominationStrategy;
 use super::internal::get_or_create_wallet;
+use super::mint_mgmt::with_mint_reliability;
 use super::proofs::{cdk_proof_to_proof_data, proof_data_to_cdk_proof};
 use super::signals::{try_acquire_mint_lock, WALLET_TOKENS};
-use super::types::{ProofData, WalletTokensStoreStoreExt};
+use super::types::{MintOperation, ProofData, WalletTokensStoreStoreExt};
 use super::utils::mint_matches;
 
 // =============================================================================
@@ -128,16 +129,19 @@ pub async fn execute_swap(
     let split_target = options.denomination.to_split_target();
 
     // Execute swap
-    let output_proofs = wallet
-        .swap(
+    let output_proofs = with_mint_reliability(
+        mint_url,
+        MintOperation::Swap,
+        wallet.swap(
             amount,
             split_target,
             cdk_proofs.into(),
             options.conditions,
             options.include_fee,
-        )
-        .await
-        .map_err(|e| format!("Swap failed: {}", e))?;
+        ),
+    )
+    .await
+    .map_err(|e| format!("Swap failed: {}", e))?;
 
     // Handle result
     let output_proofs = output_proofs.ok_or("Swap returned no proofs")?;
@@ -201,6 +201,61 @@ pub fn validate_token(token_str: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// URI scheme some wallets wrap tokens in (e.g. "cashu:cashuB...")
+const CASHU_URI_SCHEME: &str = "cashu:";
+
+/// Strip a leading `cashu:` URI scheme (case-insensitive), if present
+pub fn strip_cashu_uri(token_str: &str) -> &str {
+    let has_scheme = token_str
+        .get(..CASHU_URI_SCHEME.len())
+        .map(|prefix| prefix.eq_ignore_ascii_case(CASHU_URI_SCHEME))
+        .unwrap_or(false);
+
+    if has_scheme {
+        token_str[CASHU_URI_SCHEME.len()..].trim_start_matches("//")
+    } else {
+        token_str
+    }
+}
+
+/// Pad a base64 string with '=' when it is 2 or 3 characters short of a
+/// multiple of 4 (a common copy/paste truncation)
+pub fn pad_base64(base64_str: &str) -> String {
+    match base64_str.len() % 4 {
+        remainder @ (2 | 3) => format!("{}{}", base64_str, "=".repeat(4 - remainder)),
+        _ => base64_str.to_string(),
+    }
+}
+
+/// Normalize pasted token input into a `cashuA`/`cashuB` token string
+///
+/// Accepts regular tokens, tokens wrapped in a `cashu:` URI and bare TokenV4
+/// CBOR payloads without the `cashuB` prefix. Callers are expected to strip
+/// whitespace first.
+pub fn normalize_token_input(token_str: &str) -> Result<String, String> {
+    use cdk::nuts::TokenV4;
+
+    let stripped = strip_cashu_uri(token_str);
+
+    if stripped.starts_with("cashuA") || stripped.starts_with("cashuB") {
+        return Ok(stripped.to_string());
+    }
+
+    // Some wallets hand out the raw base64url CBOR without the prefix
+    if !stripped.is_empty() {
+        let candidate = format!("cashuB{}", pad_base64(stripped));
+        if TokenV4::from_str(&candidate).is_ok() {
+            log::info!("Detected prefix-less TokenV4 payload");
+            return Ok(candidate);
+        }
+    }
+
+    Err(format!(
+        "Invalid token format. Cashu tokens must start with 'cashuA' or 'cashuB'. Your token starts with: '{}'",
+        token_str.chars().take(10).collect::<String>()
+    ))
+}
+
 /// Check if a string looks like a valid Cashu token
 pub fn is_token(s: &str) -> bool {
     let trimmed = s.trim();
@@ -231,4 +286,65 @@ mod tests {
         assert!(!is_token("cashuA")); // Too short
         assert!(!is_token("invalid"));
     }
+
+    /// Build a small valid V4 token (the C value is the secp256k1 generator point)
+    fn sample_v4_token() -> String {
+        use cdk::mint_url::MintUrl;
+        use cdk::nuts::{CurrencyUnit, Id, Proof, PublicKey, Token};
+        use cdk::secret::Secret;
+        use cdk::Amount;
+
+        let proof = Proof::new(
+            Amount::from(8),
+            Id::from_str("00ad268c4d1f5826").unwrap(),
+            Secret::new("407915bc212be61a77e3e6d2aeb4c727980bda51cd06a6afc29e2861768a7837"),
+            PublicKey::from_hex(
+                "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            )
+            .unwrap(),
+        );
+
+        Token::new(
+            MintUrl::from_str("https://mint.example.com").unwrap(),
+            vec![proof].into(),
+            None,
+            CurrencyUnit::Sat,
+        )
+        .to_string()
+    }
+
+    #[test]
+    fn test_normalize_cashu_uri() {
+        let token = sample_v4_token();
+        assert_eq!(normalize_token_input(&format!("cashu:{}", token)).unwrap(), token);
+        assert_eq!(normalize_token_input(&format!("CASHU:{}", token)).unwrap(), token);
+        assert_eq!(normalize_token_input(&format!("cashu://{}", token)).unwrap(), token);
+    }
+
+    #[test]
+    fn test_normalize_bare_cbor_payload() {
+        let token = sample_v4_token();
+        let bare = token.strip_prefix("cashuB").unwrap();
+
+        let normalized = normalize_token_input(bare).unwrap();
+        assert!(normalized.starts_with("cashuB"));
+        assert!(validate_token(&normalized).is_ok());
+    }
+
+    #[test]
+    fn test_normalize_malformed_still_errors() {
+        let err = normalize_token_input("notatoken!!").unwrap_err();
+        assert!(err.contains("must start with 'cashuA' or 'cashuB'"));
+        assert!(err.contains("notatoken!"));
+
+        assert!(normalize_token_input("cashu:garbage").is_err());
+    }
+
+    #[test]
+    fn test_pad_base64() {
+        assert_eq!(pad_base64("abcd"), "abcd");
+        assert_eq!(pad_base64("abcdef"), "abcdef==");
+        assert_eq!(pad_base64("abcdefg"), "abcdefg=");
+        assert_eq!(pad_base64("abcde"), "abcde");
+    }
 }
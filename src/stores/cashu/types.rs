@@ -278,6 +278,24 @@ pub struct ExtendedCashuProof {
     pub dleq: Option<DleqData>,
 }
 
+#[cfg(test)]
+impl ProofData {
+    /// Unspent proof on a fixed keyset, for tests
+    pub fn for_test(amount: u64, secret: impl Into<String>) -> Self {
+        ProofData {
+            id: "00ad268c4d1f5826".to_string(),
+            amount,
+            secret: secret.into(),
+            c: String::new(),
+            witness: None,
+            dleq: None,
+            state: ProofState::default(),
+            transaction_id: None,
+            state_set_at: None,
+        }
+    }
+}
+
 impl From<ProofData> for ExtendedCashuProof {
     fn from(p: ProofData) -> Self {
         Self {
@@ -504,6 +522,9 @@ pub struct MintQuoteInfo {
     pub amount: u64,
     pub expiry: Option<u64>,
     pub mint_url: String,
+    /// When the quote was requested (unknown for quotes saved by older versions)
+    #[serde(default)]
+    pub created_at: Option<u64>,
 }
 
 impl MintQuoteInfo {
@@ -515,6 +536,7 @@ impl MintQuoteInfo {
             amount: quote.amount.map(u64::from).unwrap_or(0),
             expiry: Some(quote.expiry),
             mint_url,
+            created_at: Some(chrono::Utc::now().timestamp() as u64),
         }
     }
 }
@@ -529,6 +551,27 @@ pub struct MeltQuoteInfo {
     pub fee_reserve: u64,
     pub mint_url: String,
     pub expiry: Option<u64>,
+    /// Set when the mint reported the payment as in-flight (MeltQuoteState::Pending)
+    #[serde(default)]
+    pub pending_since: Option<u64>,
+    /// Secrets of the proofs locked into the in-flight payment
+    #[serde(default)]
+    pub input_secrets: Vec<String>,
+    /// Blank outputs sent for the change of the in-flight payment
+    #[serde(default)]
+    pub change_outputs: Option<MeltChangeOutputs>,
+}
+
+/// Where the blank outputs of a melt were derived, so its change can be
+/// unblinded once a pending payment settles
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MeltChangeOutputs {
+    /// Keyset the outputs were derived on
+    pub keyset_id: String,
+    /// Deterministic counter of the keyset before the melt
+    pub counter_start: u32,
+    /// Deterministic counter of the keyset after the melt
+    pub counter_end: u32,
 }
 
 impl MeltQuoteInfo {
@@ -541,8 +584,16 @@ impl MeltQuoteInfo {
             fee_reserve: u64::from(quote.fee_reserve),
             mint_url,
             expiry: Some(quote.expiry),
+            pending_since: None,
+            input_secrets: Vec::new(),
+            change_outputs: None,
         }
     }
+
+    /// Returns true if the mint is still settling this payment
+    pub fn is_payment_pending(&self) -> bool {
+        self.pending_since.is_some()
+    }
 }
 
 // =============================================================================
@@ -561,6 +612,12 @@ pub enum MeltProgress {
     PreparingPayment,
     PayingInvoice,
     WaitingForConfirmation,
+    /// Mint accepted the payment but the lightning route hasn't settled yet.
+    /// Proofs stay locked until the background poll resolves the quote.
+    PaymentPending {
+        quote_id: String,
+        amount: u64,
+    },
     Completed {
         total_paid: u64,
         fee_paid: u64,
@@ -644,6 +701,34 @@ pub struct MintInfoDisplay {
     pub version: Option<String>,
 }
 
+/// Recorded outcomes of operations against one mint (persisted in IndexedDB)
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MintReliabilityStats {
+    pub mint_url: String,
+    pub successes: u32,
+    pub failures: u32,
+    /// Rolling average latency of successful operations in milliseconds
+    pub avg_latency_ms: f64,
+    pub updated_at: u64,
+}
+
+/// Mint reliability summary for display and sorting
+#[derive(Clone, Debug, PartialEq)]
+pub struct MintReliability {
+    /// Share of recorded operations that succeeded (0.0 - 1.0)
+    pub success_rate: f64,
+    pub avg_latency: std::time::Duration,
+    pub sample_count: u32,
+}
+
+/// Operation whose outcome counts towards a mint's reliability
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MintOperation {
+    Mint,
+    Melt,
+    Swap,
+}
+
 /// NIP-87: Discovered Cashu mint from kind:38172 events
 #[derive(Clone, Debug, PartialEq)]
 pub struct DiscoveredMint {
@@ -739,6 +824,18 @@ pub struct NostrPaymentWaitInfo {
     pub relays: Vec<String>,
     /// Public key to receive on
     pub pubkey: nostr_sdk::PublicKey,
+    /// Amount asked for, if the request named one
+    pub amount: Option<u64>,
+}
+
+/// What to do when a payment for a request carries less than it asked for
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AmountMismatchPolicy {
+    /// Receive the tokens and warn
+    #[default]
+    Accept,
+    /// Receive the tokens but keep waiting for the full amount
+    Reject,
 }
 
 /// Payment request progress
@@ -748,7 +845,13 @@ pub enum PaymentRequestProgress {
     WaitingForPayment,
     /// Payment received
     Received { amount: u64 },
-    /// Timeout or cancelled
+    /// Payment received, but for less than the request asked
+    ReceivedWrongAmount { amount: u64, expected: u64 },
+    /// A payment for less than the request asked was received but doesn't settle it; still waiting
+    Rejected { amount: u64, expected: u64 },
+    /// No payment arrived before the request expired
+    Expired,
+    /// Cancelled by the user
     Cancelled,
     /// Error
     Error { message: String },
@@ -812,6 +915,23 @@ pub enum PendingEventType {
     DeletionEvent,
     HistoryEvent,
     QuoteEvent,
+    /// User note signed ahead of time, published at `publish_at`
+    ScheduledNote,
+    /// NIP-61 nutzap carrying ecash locked to the recipient
+    Nutzap,
+}
+
+impl PendingEventType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::TokenEvent => "token",
+            Self::DeletionEvent => "deletion",
+            Self::HistoryEvent => "history",
+            Self::QuoteEvent => "quote",
+            Self::ScheduledNote => "scheduled note",
+            Self::Nutzap => "nutzap",
+        }
+    }
 }
 
 /// Pending Nostr event awaiting publication
@@ -825,6 +945,19 @@ pub struct PendingNostrEvent {
     /// Timestamp of last retry attempt (for proper backoff calculation)
     #[serde(default)]
     pub last_retry_at: Option<u64>,
+    /// Don't publish before this time (scheduled notes); None publishes ASAP
+    #[serde(default)]
+    pub publish_at: Option<u64>,
+    /// Relays to publish to instead of ours (nutzaps go to the recipient's)
+    #[serde(default)]
+    pub relays: Vec<String>,
+}
+
+impl PendingNostrEvent {
+    /// Whether the event may be published at `now`
+    pub fn is_due(&self, now: u64) -> bool {
+        self.publish_at.is_none_or(|publish_at| publish_at <= now)
+    }
 }
 
 // =============================================================================
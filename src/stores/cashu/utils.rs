@@ -5,32 +5,44 @@
 
 use nostr_sdk::types::url::Url;
 
+pub use crate::utils::time::now_secs;
+
 /// Normalize a mint URL to prevent duplicates like "mint.coinos.io" vs "mint.coinos.io/"
 /// This should be called when storing or comparing mint URLs.
 pub fn normalize_mint_url(url: &str) -> String {
     let mut normalized = url.trim().to_string();
 
-    // Remove trailing slashes
-    while normalized.ends_with('/') {
-        normalized.pop();
-    }
-
     // Ensure https:// prefix if no scheme
-    if !normalized.starts_with("http://") && !normalized.starts_with("https://") {
+    let lowercase = normalized.to_lowercase();
+    if !lowercase.starts_with("http://") && !lowercase.starts_with("https://") {
         normalized = format!("https://{}", normalized);
     }
 
-    // Lowercase the host portion for consistency
+    // Parsing lowercases the scheme and host; the path keeps its case
     if let Ok(parsed) = Url::parse(&normalized) {
-        if let Some(host) = parsed.host_str() {
-            let lowercase_host = host.to_lowercase();
-            normalized = normalized.replacen(host, &lowercase_host, 1);
-        }
+        normalized = parsed.to_string();
+    }
+
+    // Remove trailing slashes
+    while normalized.ends_with('/') {
+        normalized.pop();
     }
 
     normalized
 }
 
+/// Normalize mint URLs and drop the ones that normalize to an earlier entry
+pub fn dedup_mint_urls<S: AsRef<str>>(mints: impl IntoIterator<Item = S>) -> Vec<String> {
+    let mut deduped: Vec<String> = Vec::new();
+    for mint in mints {
+        let mint = normalize_mint_url(mint.as_ref());
+        if !deduped.contains(&mint) {
+            deduped.push(mint);
+        }
+    }
+    deduped
+}
+
 /// Check if a mint URL matches a normalized mint URL
 /// Used for filtering tokens where stored URLs might not be normalized
 #[inline]
@@ -38,11 +50,6 @@ pub fn mint_matches(stored_mint: &str, normalized_mint: &str) -> bool {
     normalize_mint_url(stored_mint) == normalized_mint
 }
 
-/// Get current timestamp in seconds
-pub fn now_secs() -> u64 {
-    js_sys::Date::now() as u64 / 1000
-}
-
 /// Get current timestamp using chrono (for non-WASM contexts)
 pub fn chrono_now_secs() -> u64 {
     chrono::Utc::now().timestamp() as u64
@@ -195,6 +202,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_dedup_mint_urls() {
+        let mints = dedup_mint_urls([
+            "https://mint.example.com/",
+            "https://MINT.example.com",
+            "HTTPS://mint.example.com//",
+            "mint.example.com",
+            "https://mint.example.com/Cashu/",
+            "https://other.mint.com",
+        ]);
+        assert_eq!(mints, vec![
+            "https://mint.example.com".to_string(),
+            "https://mint.example.com/Cashu".to_string(),
+            "https://other.mint.com".to_string(),
+        ]);
+    }
+
     #[test]
     fn test_mint_matches() {
         assert!(mint_matches("https://mint.example.com/", "https://mint.example.com"));
@@ -38,6 +38,8 @@ thread_local! {
 
 /// Stored closures for a WebSocket connection
 struct WsClosures {
+    /// The socket the closures are attached to
+    ws: WebSocket,
     #[allow(dead_code)]
     onopen: Closure<dyn FnMut(web_sys::Event)>,
     #[allow(dead_code)]
@@ -414,6 +416,7 @@ pub async fn subscribe_to_quote(
     // This allows proper cleanup when close_connection is called
     WS_CLOSURES.with(|closures| {
         closures.borrow_mut().insert(mint_url.clone(), WsClosures {
+            ws: ws.clone(),
             onopen: onopen_callback,
             onmessage: onmessage_callback,
             onerror: onerror_callback,
@@ -543,6 +546,20 @@ pub async fn poll_quote_status(
 pub async fn subscribe_to_proof_states(
     mint_url: String,
     y_values: Vec<String>,
+) -> Result<mpsc::Receiver<ProofStateNotification>, String> {
+    let closures_key = format!("{}:proof_states", mint_url);
+    open_proof_state_subscription(mint_url, y_values, closures_key)
+}
+
+/// Open a proof state subscription whose closures are kept under `closures_key`
+///
+/// Separate keys let the background proof watch and a sent token watch on
+/// the same mint run side by side; [`close_proof_state_subscription`] ends one.
+/// The receiver also ends when the socket errors or closes.
+pub(crate) fn open_proof_state_subscription(
+    mint_url: String,
+    y_values: Vec<String>,
+    closures_key: String,
 ) -> Result<mpsc::Receiver<ProofStateNotification>, String> {
     if y_values.is_empty() {
         return Err("No Y values provided".to_string());
@@ -607,6 +624,8 @@ pub async fn subscribe_to_proof_states(
     ws.set_onopen(Some(onopen_callback.as_ref().unchecked_ref()));
 
     let sub_id_for_msg = sub_id.clone();
+    // Shared so a socket error or close can drop the sender and end the receiver
+    let tx = std::rc::Rc::new(RefCell::new(Some(tx)));
     let tx_for_msg = tx.clone();
 
     // Set up onmessage handler for proof state notifications
@@ -627,8 +646,10 @@ pub async fn subscribe_to_proof_states(
                         if sub_id_match {
                             if let Some(payload) = params.get("payload") {
                                 if let Ok(notification) = serde_json::from_value::<ProofStateNotification>(payload.clone()) {
-                                    if let Err(e) = tx_for_msg.try_send(notification) {
-                                        log::warn!("Channel full, dropping proof state update: {:?}", e);
+                                    if let Some(tx) = tx_for_msg.borrow().as_ref() {
+                                        if let Err(e) = tx.try_send(notification) {
+                                            log::warn!("Channel full, dropping proof state update: {:?}", e);
+                                        }
                                     }
                                 }
                             }
@@ -643,8 +664,10 @@ pub async fn subscribe_to_proof_states(
 
     // Set up onerror handler
     let mint_url_for_error = mint_url.clone();
+    let tx_for_error = tx.clone();
     let onerror_callback = Closure::wrap(Box::new(move |e: ErrorEvent| {
         log::error!("WebSocket error for {}: {:?}", mint_url_for_error, e.message());
+        tx_for_error.borrow_mut().take();
     }) as Box<dyn FnMut(ErrorEvent)>);
 
     ws.set_onerror(Some(onerror_callback.as_ref().unchecked_ref()));
@@ -652,8 +675,10 @@ pub async fn subscribe_to_proof_states(
     // Set up onclose handler
     let mint_url_for_close = mint_url.clone();
     let sub_id_for_close = sub_id.clone();
+    let tx_for_close = tx;
     let onclose_callback = Closure::wrap(Box::new(move |e: CloseEvent| {
         log::info!("WebSocket closed for {}: code={}, reason={}", mint_url_for_close, e.code(), e.reason());
+        tx_for_close.borrow_mut().take();
 
         let mut connections = WS_CONNECTIONS.write();
         if let Some(state) = connections.get_mut(&mint_url_for_close) {
@@ -668,7 +693,8 @@ pub async fn subscribe_to_proof_states(
 
     // Store closures
     WS_CLOSURES.with(|closures| {
-        closures.borrow_mut().insert(format!("{}:proof_states", mint_url), WsClosures {
+        closures.borrow_mut().insert(closures_key, WsClosures {
+            ws: ws.clone(),
             onopen: onopen_callback,
             onmessage: onmessage_callback,
             onerror: onerror_callback,
@@ -690,6 +716,23 @@ pub async fn subscribe_to_proof_states(
     Ok(rx)
 }
 
+/// Close a proof state subscription opened with [`open_proof_state_subscription`]
+///
+/// Detaches the handlers before closing so the dropped closures are never
+/// called; dropping them also ends the subscription's receiver.
+pub(crate) fn close_proof_state_subscription(closures_key: &str) {
+    let removed = WS_CLOSURES.with(|closures| closures.borrow_mut().remove(closures_key));
+    if let Some(entry) = removed {
+        entry.ws.set_onopen(None);
+        entry.ws.set_onmessage(None);
+        entry.ws.set_onerror(None);
+        entry.ws.set_onclose(None);
+        if let Err(e) = entry.ws.close() {
+            log::error!("Failed to close proof state WebSocket {}: {:?}", closures_key, e);
+        }
+    }
+}
+
 /// Poll proof states via HTTP (fallback when WebSocket not available)
 ///
 /// Uses the /v1/checkstate endpoint to query proof states.
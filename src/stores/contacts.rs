@@ -0,0 +1,315 @@
+//! NIP-02 contact list (kind 3) of the logged-in account
+//!
+//! Following someone republishes the whole list, so every change starts
+//! from the newest list we can find (relays, local database and the copy in
+//! memory) and keeps every existing `p` tag with its relay hint and
+//! petname, any other tags and the event content.
+
+use dioxus::prelude::*;
+use nostr_sdk::{Event, EventBuilder, Filter, Kind, PublicKey, Tag, Timestamp};
+use std::time::Duration;
+
+use crate::stores::{auth_store, nostr_client};
+use crate::utils::nip19::normalize_pubkey;
+
+/// One followed account
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContactEntry {
+    pub pubkey: PublicKey,
+    pub relay_hint: Option<String>,
+    pub petname: Option<String>,
+}
+
+/// A kind 3 contact list
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ContactList {
+    pub contacts: Vec<ContactEntry>,
+    /// Non-`p` tags, republished unchanged
+    pub other_tags: Vec<Tag>,
+    /// Event content (some clients keep a relay list here)
+    pub content: String,
+    /// When the list was published; None for a list never published
+    pub created_at: Option<Timestamp>,
+}
+
+fn non_empty(value: Option<&String>) -> Option<String> {
+    value.map(|v| v.trim()).filter(|v| !v.is_empty()).map(String::from)
+}
+
+impl ContactList {
+    /// Parse a kind 3 event; repeated `p` tags keep the first entry
+    pub fn from_event(event: &Event) -> Self {
+        let mut list = ContactList {
+            content: event.content.clone(),
+            created_at: Some(event.created_at),
+            ..Default::default()
+        };
+
+        for tag in event.tags.iter() {
+            let parts = tag.as_slice();
+            let pubkey = (parts.first().map(|s| s.as_str()) == Some("p"))
+                .then(|| parts.get(1).and_then(|pk| PublicKey::from_hex(pk).ok()))
+                .flatten();
+            match pubkey {
+                Some(pubkey) => {
+                    if !list.contains(&pubkey) {
+                        list.contacts.push(ContactEntry {
+                            pubkey,
+                            relay_hint: non_empty(parts.get(2)),
+                            petname: non_empty(parts.get(3)),
+                        });
+                    }
+                }
+                None => list.other_tags.push(tag.clone()),
+            }
+        }
+
+        list
+    }
+
+    /// Whether this list was found on relays or published by us
+    ///
+    /// A list that was never found may only mean the relays didn't answer;
+    /// publishing it would replace the account's real follows.
+    pub fn is_published(&self) -> bool {
+        self.created_at.is_some()
+    }
+
+    pub fn contains(&self, pubkey: &PublicKey) -> bool {
+        self.contacts.iter().any(|c| &c.pubkey == pubkey)
+    }
+
+    /// Add a contact, or update the relay hint and petname of an existing
+    /// one (only the fields given). Returns whether anything changed.
+    pub fn follow(&mut self, entry: ContactEntry) -> bool {
+        match self.contacts.iter_mut().find(|c| c.pubkey == entry.pubkey) {
+            Some(existing) => {
+                let mut changed = false;
+                if entry.relay_hint.is_some() && existing.relay_hint != entry.relay_hint {
+                    existing.relay_hint = entry.relay_hint;
+                    changed = true;
+                }
+                if entry.petname.is_some() && existing.petname != entry.petname {
+                    existing.petname = entry.petname;
+                    changed = true;
+                }
+                changed
+            }
+            None => {
+                self.contacts.push(entry);
+                true
+            }
+        }
+    }
+
+    /// Remove a contact. Returns whether it was in the list.
+    pub fn unfollow(&mut self, pubkey: &PublicKey) -> bool {
+        let before = self.contacts.len();
+        self.contacts.retain(|c| &c.pubkey != pubkey);
+        self.contacts.len() != before
+    }
+
+    /// Tags for publishing: `["p", pubkey, relay, petname]` per contact,
+    /// trailing empty fields dropped, then the other tags
+    pub fn to_tags(&self) -> Vec<Tag> {
+        let mut tags: Vec<Tag> = self
+            .contacts
+            .iter()
+            .filter_map(|contact| {
+                let mut parts = vec!["p".to_string(), contact.pubkey.to_hex()];
+                match (&contact.relay_hint, &contact.petname) {
+                    (relay, Some(petname)) => {
+                        parts.push(relay.clone().unwrap_or_default());
+                        parts.push(petname.clone());
+                    }
+                    (Some(relay), None) => parts.push(relay.clone()),
+                    (None, None) => {}
+                }
+                Tag::parse(parts).ok()
+            })
+            .collect();
+        tags.extend(self.other_tags.iter().cloned());
+        tags
+    }
+}
+
+/// The logged-in account's contact list, once loaded
+pub static CONTACT_LIST: GlobalSignal<Option<ContactList>> = Signal::global(|| None);
+
+/// Whether a background load of [`CONTACT_LIST`] is running
+static CONTACT_LIST_LOADING: GlobalSignal<bool> = Signal::global(|| false);
+
+/// Load [`CONTACT_LIST`] in the background unless it is loaded or loading
+///
+/// Components that only need to read the list call this instead of
+/// [`load_contact_list`], so the list is fetched once, not once per mount.
+pub fn ensure_contact_list() {
+    if CONTACT_LIST.peek().is_some() || *CONTACT_LIST_LOADING.peek() || nostr_client::get_client().is_none() {
+        return;
+    }
+    *CONTACT_LIST_LOADING.write() = true;
+    dioxus_core::spawn_forever(async move {
+        if let Err(e) = load_contact_list().await {
+            log::warn!("Failed to load contact list: {}", e);
+        }
+        *CONTACT_LIST_LOADING.write() = false;
+    });
+}
+
+/// Whether the logged-in account follows `pubkey` (hex or npub)
+///
+/// False until the list has been loaded with [`ensure_contact_list`].
+pub fn is_following(pubkey: &str) -> bool {
+    let Ok(pubkey) = normalize_pubkey(pubkey).and_then(|hex| PublicKey::from_hex(&hex).map_err(|e| e.to_string())) else {
+        return false;
+    };
+    CONTACT_LIST.read().as_ref().is_some_and(|list| list.contains(&pubkey))
+}
+
+/// Fetch the newest contact list from relays and the local database
+///
+/// The copy already in memory wins if it is newer, so a slow relay can't
+/// roll back a change made in this session.
+pub async fn load_contact_list() -> Result<ContactList, String> {
+    let client = nostr_client::get_client().ok_or("Client not initialized")?;
+    let my_pubkey = auth_store::get_pubkey().ok_or("Not logged in")?;
+    let author = PublicKey::parse(&my_pubkey).map_err(|e| format!("Invalid pubkey: {}", e))?;
+    let filter = Filter::new().author(author).kind(Kind::ContactList).limit(1);
+
+    let mut events: Vec<Event> = client
+        .database()
+        .query(filter.clone())
+        .await
+        .map(|events| events.into_iter().collect())
+        .unwrap_or_default();
+    let relay_events = client
+        .fetch_events(filter, Duration::from_secs(10))
+        .await
+        .map_err(|e| format!("Failed to fetch contact list: {}", e))?;
+    events.extend(relay_events);
+
+    let fetched = events
+        .iter()
+        .max_by_key(|event| event.created_at)
+        .map(ContactList::from_event);
+    let known = CONTACT_LIST.peek().clone();
+
+    let list = match (fetched, known) {
+        (Some(fetched), Some(known)) if known.created_at > fetched.created_at => known,
+        (Some(fetched), _) => fetched,
+        (None, Some(known)) => known,
+        // Nothing found: keep it unpublished so it is never sent out
+        (None, None) => ContactList::default(),
+    };
+    *CONTACT_LIST.write() = Some(list.clone());
+    Ok(list)
+}
+
+async fn publish_contact_list(mut list: ContactList) -> Result<(), String> {
+    let client = nostr_client::get_client().ok_or("Client not initialized")?;
+    if !*nostr_client::HAS_SIGNER.read() {
+        return Err("No signer attached. Cannot publish events.".to_string());
+    }
+
+    // Never go backwards in time, or relays would keep the old list
+    let created_at = match list.created_at {
+        Some(previous) if previous >= Timestamp::now() => previous + 1,
+        _ => Timestamp::now(),
+    };
+    let builder = EventBuilder::new(Kind::ContactList, list.content.clone())
+        .tags(list.to_tags())
+        .custom_created_at(created_at);
+
+    client
+        .send_event_builder(builder)
+        .await
+        .map_err(|e| format!("Failed to publish contact list: {}", e))?;
+
+    log::info!("Published contact list with {} contacts", list.contacts.len());
+    list.created_at = Some(created_at);
+    *CONTACT_LIST.write() = Some(list);
+    nostr_client::invalidate_contacts_cache();
+    Ok(())
+}
+
+const NO_CONTACT_LIST: &str =
+    "Couldn't find your follow list on your relays. Not publishing a new one, as it would replace your follows.";
+
+/// Follow `pubkey`, optionally with a relay hint and petname
+pub async fn follow(pubkey: &str, relay_hint: Option<String>, petname: Option<String>) -> Result<(), String> {
+    let pubkey = PublicKey::from_hex(&normalize_pubkey(pubkey)?).map_err(|e| format!("Invalid pubkey: {}", e))?;
+    let mut list = load_contact_list().await?;
+    if !list.is_published() {
+        return Err(NO_CONTACT_LIST.to_string());
+    }
+
+    if list.follow(ContactEntry { pubkey, relay_hint, petname }) {
+        publish_contact_list(list).await
+    } else {
+        log::info!("Already following: {}", pubkey);
+        Ok(())
+    }
+}
+
+/// Stop following `pubkey`
+pub async fn unfollow(pubkey: &str) -> Result<(), String> {
+    let pubkey = PublicKey::from_hex(&normalize_pubkey(pubkey)?).map_err(|e| format!("Invalid pubkey: {}", e))?;
+    let mut list = load_contact_list().await?;
+    if !list.is_published() {
+        return Err(NO_CONTACT_LIST.to_string());
+    }
+
+    if list.unfollow(&pubkey) {
+        publish_contact_list(list).await
+    } else {
+        log::info!("Not following: {}", pubkey);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::Keys;
+
+    fn tag(parts: &[&str]) -> Tag {
+        Tag::parse(parts.iter().map(|p| p.to_string()).collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn test_follow_preserves_entries_and_unfollow_removes_only_target() {
+        let alice = Keys::generate().public_key();
+        let bob = Keys::generate().public_key();
+        let carol = Keys::generate().public_key();
+        let event = EventBuilder::new(Kind::ContactList, r#"{"wss://relay.example.com":{"read":true,"write":true}}"#)
+            .tags(vec![
+                tag(&["p", &alice.to_hex(), "wss://alice.example.com", "alice"]),
+                tag(&["p", &bob.to_hex()]),
+                tag(&["p", "not-a-pubkey"]),
+                tag(&["t", "nostr"]),
+            ])
+            .sign_with_keys(&Keys::generate())
+            .unwrap();
+
+        let mut list = ContactList::from_event(&event);
+        assert_eq!(list.contacts.len(), 2);
+
+        assert!(list.follow(ContactEntry { pubkey: carol, relay_hint: None, petname: Some("carol".to_string()) }));
+        // Following again with nothing new changes nothing
+        assert!(!list.follow(ContactEntry { pubkey: alice, relay_hint: None, petname: None }));
+
+        let tags: Vec<Vec<String>> = list.to_tags().iter().map(|t| t.as_slice().to_vec()).collect();
+        assert_eq!(tags[0], vec!["p".to_string(), alice.to_hex(), "wss://alice.example.com".to_string(), "alice".to_string()]);
+        assert_eq!(tags[1], vec!["p".to_string(), bob.to_hex()]);
+        assert_eq!(tags[2], vec!["p".to_string(), carol.to_hex(), String::new(), "carol".to_string()]);
+        // Unparseable and non-p tags survive
+        assert_eq!(tags.len(), 5);
+        assert_eq!(list.content, event.content);
+
+        assert!(list.unfollow(&bob));
+        assert!(!list.unfollow(&bob));
+        assert!(list.contains(&alice) && list.contains(&carol) && !list.contains(&bob));
+        assert_eq!(list.contacts[0].petname.as_deref(), Some("alice"));
+        assert_eq!(list.to_tags().len(), 4);
+    }
+}
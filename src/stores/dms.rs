@@ -1,10 +1,19 @@
 use dioxus::prelude::*;
 use dioxus::signals::ReadableExt;
 use dioxus_stores::Store;
-use nostr_sdk::{Event, EventId, Filter, Kind, PublicKey, Timestamp, UnsignedEvent};
-use crate::stores::{auth_store, nostr_client};
+use gloo_storage::{LocalStorage, Storage};
+use nostr_sdk::{Event, EventId, Filter, Kind, NostrSigner, PublicKey, Timestamp, UnsignedEvent};
+use serde::{Deserialize, Serialize};
+use crate::stores::{auth_store, nostr_client, relay_metadata};
 use std::time::Duration;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// LocalStorage key prefix for read state; the account pubkey is appended
+const DM_READ_STATE_KEY_PREFIX: &str = "dm_read_state_";
+
+/// How often DMs are refreshed in the background for the unread badge
+const DM_REFRESH_INTERVAL_MS: u32 = 5 * 60 * 1000;
 
 /// Represents a message in a conversation, handling both NIP-04 and NIP-17
 #[derive(Clone, Debug, PartialEq)]
@@ -52,7 +61,23 @@ impl ConversationMessage {
 pub struct Conversation {
     pub pubkey: String,
     pub messages: Vec<ConversationMessage>,
-    pub unread_count: usize,
+}
+
+/// When each conversation was last read, in Unix seconds
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DmReadState {
+    /// Read time for conversations never opened: when read tracking began,
+    /// so existing history doesn't all show up as unread
+    pub baseline: u64,
+    /// Last read time by conversation partner pubkey
+    #[serde(default)]
+    pub conversations: HashMap<String, u64>,
+}
+
+impl DmReadState {
+    pub fn last_read(&self, pubkey: &str) -> Timestamp {
+        Timestamp::from(self.conversations.get(pubkey).copied().unwrap_or(self.baseline))
+    }
 }
 
 /// Store for DM conversations with fine-grained reactivity
@@ -65,6 +90,15 @@ pub struct ConversationsStore {
 pub static CONVERSATIONS: GlobalSignal<Store<ConversationsStore>> =
     Signal::global(|| Store::new(ConversationsStore::default()));
 
+/// Read state of the logged-in account's conversations
+pub static DM_READ_STATE: GlobalSignal<DmReadState> = Signal::global(DmReadState::default);
+
+/// Whether the background DM refresh has been started
+static REFRESH_STARTED: GlobalSignal<bool> = Signal::global(|| false);
+
+/// Bumped by [`stop_dm_refresh`] so a running refresh loop exits
+static REFRESH_GENERATION: GlobalSignal<u64> = Signal::global(|| 0);
+
 /// Initialize DMs by fetching conversations from relays
 pub async fn init_dms() -> Result<(), String> {
     let pubkey_str = auth_store::get_pubkey()
@@ -78,23 +112,102 @@ pub async fn init_dms() -> Result<(), String> {
 
     log::info!("Loading DMs for {}", pubkey_str);
 
+    let all_messages = fetch_dm_events(pubkey, None, None).await;
+    log::info!("Loaded {} total DM events", all_messages.len());
+
+    let mut conversations: HashMap<String, Conversation> = HashMap::new();
+    add_dm_events(&client, &pubkey_str, all_messages, &mut conversations).await;
+
+    log::info!("Organized into {} conversations", conversations.len());
+    *CONVERSATIONS.read().data().write() = conversations;
+
+    Ok(())
+}
+
+/// Fetch only the DMs newer than the loaded conversations and add them
+///
+/// Messages already loaded aren't fetched or decrypted again. Gift wraps
+/// are asked for from two days before the newest one, as their timestamps
+/// are moved back by up to that much. Does a full [`init_dms`] when nothing
+/// is loaded yet.
+pub async fn refresh_dms() -> Result<(), String> {
+    let mut conversations = CONVERSATIONS.read().data().read().clone();
+    if conversations.is_empty() {
+        return init_dms().await;
+    }
+
+    let pubkey_str = auth_store::get_pubkey()
+        .ok_or("Not authenticated")?;
+    let client = nostr_client::NOSTR_CLIENT.read().as_ref()
+        .ok_or("Client not initialized")?.clone();
+    let pubkey = PublicKey::parse(&pubkey_str)
+        .map_err(|e| format!("Invalid pubkey: {}", e))?;
+
+    let messages = conversations.values().flat_map(|conversation| conversation.messages.iter());
+    let mut nip04_since = None;
+    let mut wrap_since = None;
+    for message in messages {
+        match message {
+            ConversationMessage::Nip04 { event } => {
+                nip04_since = nip04_since.max(Some(event.created_at));
+            }
+            ConversationMessage::Nip17 { gift_wrap, .. } => {
+                wrap_since = wrap_since.max(Some(gift_wrap.created_at));
+            }
+        }
+    }
+    let wrap_since = wrap_since.map(|newest: Timestamp| {
+        Timestamp::from(newest.as_secs().saturating_sub(WRAP_TIMESTAMP_WINDOW_SECS))
+    });
+
+    let events = fetch_dm_events(pubkey, nip04_since, wrap_since).await;
+    let added = add_dm_events(&client, &pubkey_str, events, &mut conversations).await;
+    if added > 0 {
+        log::info!("Added {} new DMs", added);
+        *CONVERSATIONS.read().data().write() = conversations;
+    }
+
+    Ok(())
+}
+
+/// Fetch NIP-04 DMs (sent and received) and NIP-17 gift wraps addressed to
+/// `pubkey`, optionally only those from `nip04_since` / `wrap_since` on
+async fn fetch_dm_events(
+    pubkey: PublicKey,
+    nip04_since: Option<Timestamp>,
+    wrap_since: Option<Timestamp>,
+) -> Vec<Event> {
+    let since = |filter: Filter, since: Option<Timestamp>| match since {
+        Some(since) => filter.since(since),
+        None => filter,
+    };
+
     // Create filters for all DM types
-    let received_nip04 = Filter::new()
-        .kind(Kind::EncryptedDirectMessage)
-        .pubkey(pubkey)
-        .limit(200);
+    let received_nip04 = since(
+        Filter::new()
+            .kind(Kind::EncryptedDirectMessage)
+            .pubkey(pubkey)
+            .limit(200),
+        nip04_since,
+    );
 
-    let sent_nip04 = Filter::new()
-        .kind(Kind::EncryptedDirectMessage)
-        .author(pubkey)
-        .limit(200);
+    let sent_nip04 = since(
+        Filter::new()
+            .kind(Kind::EncryptedDirectMessage)
+            .author(pubkey)
+            .limit(200),
+        nip04_since,
+    );
 
     // NIP-17: Query gift wraps with our pubkey in p-tag
     // This gets BOTH received messages AND sent message copies (per NIP-17 spec)
-    let nip17_all = Filter::new()
-        .kind(Kind::GiftWrap)
-        .pubkey(pubkey)
-        .limit(300);
+    let nip17_all = since(
+        Filter::new()
+            .kind(Kind::GiftWrap)
+            .pubkey(pubkey)
+            .limit(300),
+        wrap_since,
+    );
 
     // PARALLEL FETCHES - All three at once!
     let (received_nip04_result, sent_nip04_result, nip17_all_result) = tokio::join!(
@@ -127,12 +240,38 @@ pub async fn init_dms() -> Result<(), String> {
         log::error!("Failed to fetch NIP-17 DMs: {}", e);
     }
 
-    log::info!("Loaded {} total DM events", all_messages.len());
+    all_messages
+}
 
-    // Group messages by conversation partner
-    let mut conversations: HashMap<String, Conversation> = HashMap::new();
+/// Group DM events into `conversations` by conversation partner
+///
+/// Events already in a conversation are skipped without unwrapping. Returns
+/// how many messages were added.
+async fn add_dm_events(
+    client: &nostr_sdk::Client,
+    pubkey_str: &str,
+    all_messages: Vec<Event>,
+    conversations: &mut HashMap<String, Conversation>,
+) -> usize {
+    let mut known_events: HashSet<EventId> = HashSet::new();
+    // The same rumor can arrive in several gift wraps (re-sends, multiple
+    // copies addressed to us), so NIP-17 messages are deduped by rumor id
+    let mut seen_rumors: HashSet<EventId> = HashSet::new();
+    for message in conversations.values().flat_map(|conversation| conversation.messages.iter()) {
+        known_events.insert(message.id());
+        if let ConversationMessage::Nip17 { rumor, .. } = message {
+            seen_rumors.insert(rumor_id(rumor));
+        }
+    }
+
+    let mut added = 0;
+    let mut touched: HashSet<String> = HashSet::new();
 
     for msg in all_messages {
+        if !known_events.insert(msg.id) {
+            continue;
+        }
+
         // Handle NIP-17 (GiftWrap) vs NIP-04 (EncryptedDirectMessage)
         if msg.kind == Kind::GiftWrap {
             // NIP-17: Unwrap the gift wrap to get the actual sender and receiver
@@ -140,10 +279,15 @@ pub async fn init_dms() -> Result<(), String> {
                 Ok(unwrapped) => {
                     // The rumor contains the actual message (Kind 14)
                     if unwrapped.rumor.kind == Kind::PrivateDirectMessage {
+                        if !seen_rumors.insert(rumor_id(&unwrapped.rumor)) {
+                            log::debug!("Skipping duplicate NIP-17 message");
+                            continue;
+                        }
+
                         let sender_pubkey = unwrapped.sender.to_string();
 
                         // Determine the other party (conversation partner)
-                        let other_pubkey = if sender_pubkey == pubkey_str {
+                        let other_pubkey = if sender_pubkey == *pubkey_str {
                             // WE sent this message - get receiver from rumor's p-tag
                             unwrapped.rumor.tags.iter()
                                 .find(|tag| tag.kind() == nostr_sdk::TagKind::p())
@@ -171,9 +315,10 @@ pub async fn init_dms() -> Result<(), String> {
                             .or_insert_with(|| Conversation {
                                 pubkey: other_pubkey.clone(),
                                 messages: Vec::new(),
-                                unread_count: 0,
                             })
                             .messages.push(conversation_msg);
+                        touched.insert(other_pubkey);
+                        added += 1;
                     }
                 }
                 Err(e) => {
@@ -206,27 +351,125 @@ pub async fn init_dms() -> Result<(), String> {
                 .or_insert_with(|| Conversation {
                     pubkey: other_pubkey.clone(),
                     messages: Vec::new(),
-                    unread_count: 0,
                 })
                 .messages.push(conversation_msg);
+            touched.insert(other_pubkey);
+            added += 1;
         }
     }
 
     // Sort messages in each conversation by timestamp (uses actual rumor timestamp for NIP-17)
-    for conversation in conversations.values_mut() {
-        conversation.messages.sort_by(|a, b| a.created_at().cmp(&b.created_at()));
+    for pubkey in touched {
+        if let Some(conversation) = conversations.get_mut(&pubkey) {
+            conversation.messages.sort_by(|a, b| a.created_at().cmp(&b.created_at()));
+        }
     }
 
-    log::info!("Organized into {} conversations", conversations.len());
-    *CONVERSATIONS.read().data().write() = conversations;
+    added
+}
+
+/// DM inbox relays per user (hex pubkey), kept for the session
+static DM_RELAYS_CACHE: OnceLock<Mutex<HashMap<String, Vec<String>>>> = OnceLock::new();
+
+fn dm_relays_cache() -> &'static Mutex<HashMap<String, Vec<String>>> {
+    DM_RELAYS_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Relays a user receives DMs on
+///
+/// Their kind 10050 DM relay list; without one, the read relays from their
+/// kind 10002 list. Empty when they've published neither, in which case
+/// gift wraps go out through gossip routing.
+pub async fn fetch_dm_relays(pubkey: PublicKey) -> Result<Vec<String>, String> {
+    let key = pubkey.to_hex();
+    if let Some(cached) = dm_relays_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&key)
+    {
+        return Ok(cached.clone());
+    }
+
+    let client = nostr_client::get_client().ok_or("Client not initialized")?;
+    let relays = match relay_metadata::fetch_relay_list(pubkey, client).await {
+        Ok(metadata) if !metadata.dm_relays.is_empty() => metadata.dm_relays,
+        Ok(metadata) => {
+            log::info!("No DM relay list for {}, using their read relays", key);
+            metadata.relays.into_iter().filter(|r| r.read).map(|r| r.url).collect()
+        }
+        Err(e) => {
+            log::info!("No relay lists for {}: {}", key, e);
+            Vec::new()
+        }
+    };
+
+    dm_relays_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(key, relays.clone());
+    Ok(relays)
+}
 
+/// Publish our DM relay list (kind 10050) so others can reach us
+pub async fn publish_dm_relays(relays: Vec<String>) -> Result<String, String> {
+    let client = nostr_client::get_client().ok_or("Client not initialized")?;
+    let event_id = relay_metadata::publish_dm_relay_list(relays.clone(), client).await?;
+
+    if let Some(pubkey) = auth_store::get_pubkey().and_then(|pk| PublicKey::parse(&pk).ok()) {
+        dm_relays_cache()
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(pubkey.to_hex(), relays.clone());
+    }
+    if let Some(metadata) = relay_metadata::USER_RELAY_METADATA.write().as_mut() {
+        metadata.dm_relays = relays;
+    }
+    Ok(event_id)
+}
+
+/// Our own DM relays, for the copy of each message we send ourselves
+async fn own_dm_relays(pubkey: PublicKey) -> Vec<String> {
+    let local = relay_metadata::USER_RELAY_METADATA
+        .read()
+        .as_ref()
+        .map(|metadata| metadata.dm_relays.clone())
+        .unwrap_or_default();
+    if !local.is_empty() {
+        return local;
+    }
+    fetch_dm_relays(pubkey).await.unwrap_or_default()
+}
+
+/// Send a gift wrap to `relays`, or through gossip routing if there are none
+///
+/// Relays not yet in the pool are borrowed for the send.
+async fn send_gift_wrap(client: &Arc<nostr_sdk::Client>, relays: &[String], gift_wrap: &Event) -> Result<(), String> {
+    if relays.is_empty() {
+        let output = client.send_event(gift_wrap).await.map_err(|e| e.to_string())?;
+        log::debug!("Sent gift wrap via gossip: {:?}", output.val);
+        return Ok(());
+    }
+
+    let temp_relays = nostr_client::add_temp_relays(client, relays).await;
+    let result = client.send_event_to(relays.iter().map(String::as_str), gift_wrap).await;
+    temp_relays.release().await;
+
+    let output = result.map_err(|e| e.to_string())?;
+    if output.success.is_empty() {
+        return Err(format!("No DM relay accepted the message ({} failed)", output.failed.len()));
+    }
+    log::debug!("Sent gift wrap to {} DM relays", output.success.len());
     Ok(())
 }
 
 /// Send an encrypted DM to a recipient (NIP-17 compliant with sender copy)
 pub async fn send_dm(recipient_pubkey: String, content: String) -> Result<(), String> {
-    use nostr_sdk::EventBuilder;
+    send_private_message(recipient_pubkey, content).await
+}
 
+/// Send a NIP-17 private message: a kind 14 rumor, sealed (kind 13) and gift
+/// wrapped (kind 1059) once for the recipient and once for our own copy
+pub async fn send_private_message(recipient_pubkey: String, text: String) -> Result<(), String> {
     let client = nostr_client::NOSTR_CLIENT.read().as_ref()
         .ok_or("Client not initialized")?.clone();
 
@@ -240,47 +483,110 @@ pub async fn send_dm(recipient_pubkey: String, content: String) -> Result<(), St
     let signer = client.signer().await
         .map_err(|e| format!("Failed to get signer: {}", e))?;
 
+    log::info!("Sending DM to {}", recipient_pubkey);
+
     let sender_pk = signer.get_public_key().await
         .map_err(|e| format!("Failed to get sender pubkey: {}", e))?;
 
-    log::info!("Sending DM from {} to {}", sender_pk.to_hex(), recipient_pubkey);
+    let (receiver_gift_wrap, sender_gift_wrap) =
+        build_private_message_wraps(&signer, recipient_pk, text).await?;
+
+    // Send gift wrap to receiver's DM inbox relays (NIP-17)
+    let receiver_relays = fetch_dm_relays(recipient_pk).await.unwrap_or_default();
+    send_gift_wrap(&client, &receiver_relays, &receiver_gift_wrap).await
+        .map_err(|e| format!("Failed to send to receiver: {}", e))?;
+
+    log::info!("Sent gift wrap to receiver via {} DM relays", receiver_relays.len());
+
+    // Send gift wrap to our own DM relays for our copy
+    let sender_relays = own_dm_relays(sender_pk).await;
+    send_gift_wrap(&client, &sender_relays, &sender_gift_wrap).await
+        .map_err(|e| format!("Failed to send sender copy: {}", e))?;
+
+    log::info!("Sent gift wrap to sender (copy) via {} DM relays", sender_relays.len());
+
+    // Refresh conversations to include new message
+    if let Err(e) = refresh_dms().await {
+        log::error!("Failed to refresh DM conversations after sending message: {}", e);
+        // Continue despite refresh failure - message was sent successfully
+    }
+
+    Ok(())
+}
+
+/// How far in the past a seal's or gift wrap's `created_at` may be moved
+/// (NIP-59), so relays can't line messages up by time
+const WRAP_TIMESTAMP_WINDOW_SECS: u64 = 2 * 24 * 60 * 60;
+
+/// A `created_at` up to two days before `now`, never after it
+fn randomized_past_timestamp(now: Timestamp, random: u64) -> Timestamp {
+    Timestamp::from(now.as_secs().saturating_sub(random % (WRAP_TIMESTAMP_WINDOW_SECS + 1)))
+}
+
+/// Seal `rumor` for `receiver` and gift wrap it
+///
+/// The seal gets a randomized past timestamp; nostr-sdk gives the wrap its
+/// own within the same window. The rumor inside keeps the real time it was
+/// written.
+async fn seal_and_wrap<T>(signer: &T, receiver: &PublicKey, rumor: UnsignedEvent) -> Result<Event, String>
+where
+    T: ?Sized + NostrSigner,
+{
+    use nostr_sdk::EventBuilder;
+
+    let seal = EventBuilder::seal(signer, receiver, rumor)
+        .await
+        .map_err(|e| format!("Failed to seal message: {}", e))?
+        .custom_created_at(randomized_past_timestamp(Timestamp::now(), rand::random()))
+        .sign(signer)
+        .await
+        .map_err(|e| format!("Failed to sign seal: {}", e))?;
+
+    // Signed by a throwaway key
+    EventBuilder::gift_wrap_from_seal(receiver, &seal, [])
+        .map_err(|e| format!("Failed to wrap seal: {}", e))
+}
+
+/// Build the recipient's and the sender's gift wraps for one private message
+///
+/// Both wraps contain the same rumor, so they share a rumor id. The signer's
+/// NIP-44 methods encrypt the seal; each wrap is signed by a fresh random key.
+/// Returns (receiver_wrap, sender_wrap).
+async fn build_private_message_wraps<T>(
+    signer: &T,
+    recipient_pk: PublicKey,
+    text: String,
+) -> Result<(Event, Event), String>
+where
+    T: ?Sized + NostrSigner,
+{
+    use nostr_sdk::EventBuilder;
+
+    let sender_pk = signer.get_public_key().await
+        .map_err(|e| format!("Failed to get sender pubkey: {}", e))?;
 
     // Build the rumor (kind 14 unsigned message)
-    let rumor = EventBuilder::private_msg_rumor(recipient_pk, content.clone())
+    let rumor = EventBuilder::private_msg_rumor(recipient_pk, text)
         .build(sender_pk);
 
     // Create gift wrap for RECEIVER (with receiver's p-tag)
-    let receiver_gift_wrap = EventBuilder::gift_wrap(&signer, &recipient_pk, rumor.clone(), [])
+    let receiver_gift_wrap = seal_and_wrap(signer, &recipient_pk, rumor.clone())
         .await
         .map_err(|e| format!("Failed to create receiver gift wrap: {}", e))?;
 
     // Create gift wrap for SENDER (with sender's p-tag) - NIP-17 requirement!
-    let sender_gift_wrap = EventBuilder::gift_wrap(&signer, &sender_pk, rumor, [])
+    let sender_gift_wrap = seal_and_wrap(signer, &sender_pk, rumor)
         .await
         .map_err(|e| format!("Failed to create sender gift wrap: {}", e))?;
 
-    // With gossip, the client automatically routes to appropriate DM inbox relays (NIP-17)
-    log::debug!("Sending DM gift wraps using gossip routing");
-
-    // Send gift wrap to receiver's inbox relays (NIP-17 compliant)
-    let receiver_result = client.send_event(&receiver_gift_wrap).await
-        .map_err(|e| format!("Failed to send to receiver: {}", e))?;
-
-    log::info!("Sent gift wrap to receiver: {:?}", receiver_result.val);
-
-    // Send gift wrap to sender's inbox relays for their own copy
-    let sender_result = client.send_event(&sender_gift_wrap).await
-    .map_err(|e| format!("Failed to send sender copy: {}", e))?;
-
-    log::info!("Sent gift wrap to sender (copy): {:?}", sender_result.val);
-
-    // Refresh conversations to include new message
-    if let Err(e) = init_dms().await {
-        log::error!("Failed to refresh DM conversations after sending message: {}", e);
-        // Continue despite refresh failure - message was sent successfully
-    }
+    Ok((receiver_gift_wrap, sender_gift_wrap))
+}
 
-    Ok(())
+/// Id of a rumor, computing it when the unsigned event doesn't carry one
+fn rumor_id(rumor: &UnsignedEvent) -> EventId {
+    rumor.id.unwrap_or_else(|| {
+        EventId::new(&rumor.pubkey, &rumor.created_at, &rumor.kind, &rumor.tags, &rumor.content)
+    })
 }
 
 /// Decrypt a DM message (supports both NIP-04 and NIP-17)
@@ -296,7 +602,7 @@ pub async fn decrypt_dm(msg: &ConversationMessage) -> Result<String, String> {
         return Err("Invalid message type".to_string());
     };
 
-    let _client = nostr_client::NOSTR_CLIENT.read().as_ref()
+    let client = nostr_client::NOSTR_CLIENT.read().as_ref()
         .ok_or("Client not initialized")?.clone();
 
     // NIP-04: For Kind 4 encrypted direct messages
@@ -305,50 +611,38 @@ pub async fn decrypt_dm(msg: &ConversationMessage) -> Result<String, String> {
         // when fetching events. If the content is still encrypted, we need to
         // manually decrypt it.
 
-        // Check if content looks encrypted (base64-like)
-        if event.content.contains("?iv=") {
-            // This is an encrypted NIP-04 message, need to decrypt
-            let my_pubkey = auth_store::get_pubkey()
-                .ok_or("Not authenticated")?;
-
-            // Determine the other party's pubkey
-            let other_pubkey = if event.pubkey.to_string() == my_pubkey {
-                // We sent it, decrypt with recipient's pubkey
-                event.tags.iter()
-                    .find(|tag| tag.kind() == nostr_sdk::TagKind::p())
-                    .and_then(|tag| tag.content())
-                    .ok_or("No recipient found in sent message")?
-                    .to_string()
-            } else {
-                // We received it, decrypt with sender's pubkey
-                event.pubkey.to_string()
-            };
-
-            let other_pk = PublicKey::parse(&other_pubkey)
-                .map_err(|e| format!("Invalid pubkey: {}", e))?;
-
-            // Use signer to decrypt (NIP-04)
-            let signer_result = nostr_client::NOSTR_CLIENT.read().as_ref()
-                .ok_or("Client not initialized")?
-                .signer().await;
-
-            if let Ok(signer) = signer_result {
-                match signer.nip04_decrypt(&other_pk, &event.content).await {
-                    Ok(decrypted) => {
-                        log::debug!("Successfully decrypted NIP-04 message");
-                        return Ok(decrypted);
-                    }
-                    Err(e) => {
-                        log::error!("Failed to decrypt NIP-04 message: {}", e);
-                        return Err(format!("Failed to decrypt NIP-04 message: {}", e));
-                    }
-                }
-            } else {
-                return Err("No signer available for decryption".to_string());
-            }
+        let my_pubkey = auth_store::get_pubkey()
+            .ok_or("Not authenticated")?;
+
+        // Determine the other party's pubkey
+        let other_pubkey = if event.pubkey.to_string() == my_pubkey {
+            // We sent it, decrypt with recipient's pubkey
+            event.tags.iter()
+                .find(|tag| tag.kind() == nostr_sdk::TagKind::p())
+                .and_then(|tag| tag.content())
+                .ok_or("No recipient found in sent message")?
+                .to_string()
         } else {
-            // Content is already decrypted or plain text
-            return Ok(event.content.clone());
+            // We received it, decrypt with sender's pubkey
+            event.pubkey.to_string()
+        };
+
+        let other_pk = PublicKey::parse(&other_pubkey)
+            .map_err(|e| format!("Invalid pubkey: {}", e))?;
+
+        let signer = client
+            .signer().await
+            .map_err(|_| "No signer available for decryption".to_string())?;
+
+        // Some clients put NIP-44 payloads in kind 4; most are NIP-04
+        match crate::stores::signer::decrypt_with_fallback(&*signer, &other_pk, &event.content).await {
+            Ok(decrypted) => return Ok(decrypted),
+            // NIP-04 payloads always carry an IV; without one it's plain text
+            Err(_) if !event.content.contains("?iv=") => return Ok(event.content.clone()),
+            Err(e) => {
+                log::error!("Failed to decrypt direct message: {}", e);
+                return Err(format!("Failed to decrypt direct message: {}", e));
+            }
         }
     }
 
@@ -374,3 +668,241 @@ pub fn get_conversations_sorted() -> Vec<Conversation> {
 
     convos
 }
+
+/// Messages received after `last_read`; our own messages never count
+pub fn count_unread(messages: &[ConversationMessage], my_pubkey: &PublicKey, last_read: Timestamp) -> usize {
+    messages
+        .iter()
+        .filter(|msg| msg.sender() != *my_pubkey && msg.created_at() > last_read)
+        .count()
+}
+
+/// Unread messages in the conversation with `pubkey`
+pub fn unread_count(pubkey: &str) -> usize {
+    let Some(my_pubkey) = auth_store::get_pubkey().and_then(|pk| PublicKey::parse(&pk).ok()) else {
+        return 0;
+    };
+    let last_read = DM_READ_STATE.read().last_read(pubkey);
+    CONVERSATIONS
+        .read()
+        .data()
+        .read()
+        .get(pubkey)
+        .map(|conversation| count_unread(&conversation.messages, &my_pubkey, last_read))
+        .unwrap_or(0)
+}
+
+/// Unread messages across all conversations, for the Messages badge
+pub fn total_unread_count() -> usize {
+    let Some(my_pubkey) = auth_store::get_pubkey().and_then(|pk| PublicKey::parse(&pk).ok()) else {
+        return 0;
+    };
+    let read_state = DM_READ_STATE.read();
+    CONVERSATIONS
+        .read()
+        .data()
+        .read()
+        .values()
+        .map(|conversation| {
+            count_unread(&conversation.messages, &my_pubkey, read_state.last_read(&conversation.pubkey))
+        })
+        .sum()
+}
+
+fn read_state_key(my_pubkey: &str) -> String {
+    format!("{}{}", DM_READ_STATE_KEY_PREFIX, my_pubkey)
+}
+
+/// Load the account's read state from localStorage
+///
+/// The first time, the baseline is set to now so only messages arriving from
+/// here on count as unread.
+pub fn load_read_state() {
+    let Some(my_pubkey) = auth_store::get_pubkey() else {
+        return;
+    };
+    let key = read_state_key(&my_pubkey);
+    let state = LocalStorage::get::<DmReadState>(&key).unwrap_or_else(|_| {
+        let state = DmReadState {
+            baseline: Timestamp::now().as_secs(),
+            conversations: HashMap::new(),
+        };
+        if let Err(e) = LocalStorage::set(&key, &state) {
+            log::error!("Failed to save DM read state: {}", e);
+        }
+        state
+    });
+    *DM_READ_STATE.write() = state;
+}
+
+/// Mark the conversation with `pubkey` read up to its latest message
+pub fn mark_conversation_read(pubkey: &str) {
+    let Some(my_pubkey) = auth_store::get_pubkey() else {
+        return;
+    };
+    let latest = CONVERSATIONS
+        .peek()
+        .data()
+        .peek()
+        .get(pubkey)
+        .and_then(|conversation| conversation.messages.last().map(|m| m.created_at().as_secs()))
+        .unwrap_or(0);
+    // A sender's clock may run ahead; never mark read before that message
+    let read_at = latest.max(Timestamp::now().as_secs());
+
+    if DM_READ_STATE.peek().conversations.get(pubkey).is_some_and(|at| *at >= read_at) {
+        return;
+    }
+    let mut state = DM_READ_STATE.write();
+    state.conversations.insert(pubkey.to_string(), read_at);
+    if let Err(e) = LocalStorage::set(read_state_key(&my_pubkey), &*state) {
+        log::error!("Failed to save DM read state: {}", e);
+    }
+}
+
+/// Forget loaded conversations, read state and cached DM relays
+///
+/// Only in memory; see [`delete_read_state`] for the stored read state.
+pub fn reset_dm_state() {
+    CONVERSATIONS.read().data().write().clear();
+    *DM_READ_STATE.write() = DmReadState::default();
+    dm_relays_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clear();
+}
+
+/// Delete the stored read state of `my_pubkey`'s conversations
+pub fn delete_read_state(my_pubkey: &str) {
+    LocalStorage::delete(read_state_key(my_pubkey));
+}
+
+/// Keep conversations loaded in the background so the unread badge updates
+///
+/// Safe to call repeatedly; only the first call starts the task.
+pub fn start_dm_refresh() {
+    if *REFRESH_STARTED.peek() {
+        return;
+    }
+    *REFRESH_STARTED.write() = true;
+    let generation = *REFRESH_GENERATION.peek();
+
+    dioxus_core::spawn_forever(async move {
+        loop {
+            if *REFRESH_GENERATION.peek() != generation {
+                break;
+            }
+            if auth_store::is_authenticated() {
+                if let Err(e) = refresh_dms().await {
+                    log::warn!("Background DM refresh failed: {}", e);
+                }
+            }
+            gloo_timers::future::TimeoutFuture::new(DM_REFRESH_INTERVAL_MS).await;
+        }
+    });
+}
+
+/// Stop the background refresh and drop the loaded conversations
+///
+/// Called on logout, so the next login starts a fresh refresh for its own
+/// account.
+pub fn stop_dm_refresh() {
+    *REFRESH_GENERATION.write() += 1;
+    *REFRESH_STARTED.write() = false;
+    CONVERSATIONS.read().data().write().clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::{EventBuilder, Keys, Tag, UnwrappedGift};
+    use crate::utils::testing::block_on;
+
+    #[test]
+    fn test_private_message_round_trip() {
+        let alice = Keys::generate();
+        let bob = Keys::generate();
+
+        let (to_bob, to_alice) = block_on(build_private_message_wraps(
+            &alice,
+            bob.public_key(),
+            "hello bob".to_string(),
+        ))
+        .unwrap();
+
+        assert_eq!(to_bob.kind, Kind::GiftWrap);
+        // Wraps are signed by throwaway keys, never by the sender
+        assert_ne!(to_bob.pubkey, alice.public_key());
+
+        // Bob unwraps his copy
+        let received = block_on(UnwrappedGift::from_gift_wrap(&bob, &to_bob)).unwrap();
+        assert_eq!(received.sender, alice.public_key());
+        assert_eq!(received.rumor.kind, Kind::PrivateDirectMessage);
+        assert_eq!(received.rumor.content, "hello bob");
+
+        // Alice unwraps her own copy; it carries the same rumor
+        let sent = block_on(UnwrappedGift::from_gift_wrap(&alice, &to_alice)).unwrap();
+        assert_eq!(sent.rumor.content, "hello bob");
+        assert_eq!(rumor_id(&sent.rumor), rumor_id(&received.rumor));
+
+        // Bob can't open Alice's copy
+        assert!(block_on(UnwrappedGift::from_gift_wrap(&bob, &to_alice)).is_err());
+
+        // The rumor keeps the real time; the wraps are never in the future
+        let now = Timestamp::now();
+        assert!(now.as_secs() - received.rumor.created_at.as_secs() < 60);
+        assert!(to_bob.created_at <= now && to_alice.created_at <= now);
+    }
+
+    #[test]
+    fn test_randomized_timestamp_stays_in_past_window() {
+        let now = Timestamp::from(1_700_000_000);
+        let earliest = now.as_secs() - WRAP_TIMESTAMP_WINDOW_SECS;
+
+        for random in [0, 1, 12_345, WRAP_TIMESTAMP_WINDOW_SECS, u64::MAX, rand::random(), rand::random()] {
+            let created_at = randomized_past_timestamp(now, random).as_secs();
+            assert!(created_at >= earliest && created_at <= now.as_secs(), "{} out of window", created_at);
+        }
+        assert_eq!(randomized_past_timestamp(now, 0), now);
+        assert_eq!(randomized_past_timestamp(now, WRAP_TIMESTAMP_WINDOW_SECS).as_secs(), earliest);
+
+        // Close to the epoch it clamps instead of wrapping around
+        assert_eq!(randomized_past_timestamp(Timestamp::from(10), 500).as_secs(), 0);
+    }
+
+    #[test]
+    fn test_count_unread() {
+        let me = Keys::generate();
+        let alice = Keys::generate();
+        let message = |from: &Keys, to: &Keys, created_at: u64| ConversationMessage::Nip04 {
+            event: EventBuilder::new(Kind::EncryptedDirectMessage, "?iv=opaque")
+                .tag(Tag::public_key(to.public_key()))
+                .custom_created_at(Timestamp::from(created_at))
+                .sign_with_keys(from)
+                .unwrap(),
+        };
+
+        let messages = vec![
+            message(&alice, &me, 100),
+            message(&me, &alice, 150),
+            message(&alice, &me, 200),
+            message(&alice, &me, 300),
+            // Sent by me after my last read: still not unread
+            message(&me, &alice, 400),
+        ];
+        let my_pubkey = me.public_key();
+
+        assert_eq!(count_unread(&messages, &my_pubkey, Timestamp::from(0)), 3);
+        assert_eq!(count_unread(&messages, &my_pubkey, Timestamp::from(150)), 2);
+        // A message at exactly the last read time has been seen
+        assert_eq!(count_unread(&messages, &my_pubkey, Timestamp::from(200)), 1);
+        assert_eq!(count_unread(&messages, &my_pubkey, Timestamp::from(300)), 0);
+
+        let state = DmReadState {
+            baseline: 250,
+            conversations: HashMap::from([(alice.public_key().to_hex(), 150)]),
+        };
+        assert_eq!(state.last_read(&alice.public_key().to_hex()), Timestamp::from(150));
+        assert_eq!(state.last_read("someone else"), Timestamp::from(250));
+    }
+}
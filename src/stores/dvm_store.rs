@@ -4,6 +4,7 @@
 //! - DVM provider discovery (kind 31990 with #k=5300)
 //! - Content discovery requests (kind 5300)
 //! - Feed response parsing (kind 6300)
+//! - Note translation requests (kind 5002 → 6002)
 
 use dioxus::prelude::*;
 use nostr_sdk::{Event, EventId, Filter, Kind, PublicKey, Tag, Timestamp};
@@ -21,6 +22,44 @@ pub const KIND_CONTENT_DISCOVERY: u16 = 5300;
 /// Content discovery result kind (5300 + 1000)
 pub const KIND_CONTENT_DISCOVERY_RESULT: u16 = 6300;
 
+/// Translation job kind (NIP-90)
+pub const KIND_TRANSLATION: u16 = 5002;
+
+/// Translation result kind (5002 + 1000)
+pub const KIND_TRANSLATION_RESULT: u16 = 6002;
+
+/// Seconds to wait for a translation before giving up
+pub const TRANSLATION_TIMEOUT_SECS: u32 = 30;
+
+/// Target languages offered for translation (ISO 639-1 code, name)
+pub const TRANSLATION_LANGUAGES: &[(&str, &str)] = &[
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("pt", "Portuguese"),
+    ("fr", "French"),
+    ("de", "German"),
+    ("it", "Italian"),
+    ("nl", "Dutch"),
+    ("ru", "Russian"),
+    ("uk", "Ukrainian"),
+    ("pl", "Polish"),
+    ("tr", "Turkish"),
+    ("ar", "Arabic"),
+    ("hi", "Hindi"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("zh", "Chinese"),
+];
+
+/// Display name for a translation language code
+pub fn translation_language_name(code: &str) -> &str {
+    TRANSLATION_LANGUAGES
+        .iter()
+        .find(|(c, _)| *c == code)
+        .map(|(_, name)| *name)
+        .unwrap_or(code)
+}
+
 /// NIP-89 Handler information / DVM announcement
 pub const KIND_APP_HANDLER: u16 = 31990;
 
@@ -385,6 +424,66 @@ async fn parse_feed_response(response: &Event, client: &nostr_sdk::Client) -> Re
     Ok(event_vec)
 }
 
+/// Build a kind 5002 translation job for `content` into `language`
+///
+/// The text goes in as a `text` input and the target language as the
+/// `language` param. No `p` tag, so any translation DVM can pick it up.
+pub fn translation_request(content: &str, language: &str) -> nostr_sdk::EventBuilder {
+    let tags = vec![
+        Tag::parse(["i", content, "text"]).expect("valid i tag"),
+        Tag::parse(["param", "language", language]).expect("valid param tag"),
+        Tag::parse(["output", "text/plain"]).expect("valid output tag"),
+    ];
+    nostr_sdk::EventBuilder::new(Kind::from(KIND_TRANSLATION), "").tags(tags)
+}
+
+/// Translate a note's text with a DVM (kind 5002 → 6002)
+///
+/// Returns the first result any DVM publishes for our job, or an error once
+/// [`TRANSLATION_TIMEOUT_SECS`] pass without one.
+pub async fn request_translation(content: &str, language: &str) -> Result<String, String> {
+    let client = nostr_client::get_client()
+        .ok_or("Client not initialized")?;
+
+    if !*nostr_client::HAS_SIGNER.read() {
+        return Err("Sign in to translate notes".to_string());
+    }
+
+    let output = client.send_event_builder(translation_request(content, language)).await
+        .map_err(|e| format!("Failed to submit translation job: {}", e))?;
+    let request_id = *output.id();
+    log::info!("Translation request submitted: {} ({})", request_id.to_hex(), language);
+
+    let response_filter = Filter::new()
+        .kind(Kind::from(KIND_TRANSLATION_RESULT))
+        .event(request_id)
+        .limit(1);
+
+    for attempt in 1..=TRANSLATION_TIMEOUT_SECS {
+        // Brief delay between polls
+        #[cfg(target_arch = "wasm32")]
+        {
+            use gloo_timers::future::TimeoutFuture;
+            TimeoutFuture::new(1000).await;
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+
+        if let Ok(responses) = client.fetch_events(response_filter.clone(), Duration::from_secs(2)).await {
+            if let Some(response) = responses.into_iter().find(|r| !r.content.trim().is_empty()) {
+                log::info!("Received translation from DVM {}", response.pubkey.to_hex());
+                return Ok(response.content.trim().to_string());
+            }
+        }
+
+        log::debug!("Waiting for translation... attempt {}/{}", attempt, TRANSLATION_TIMEOUT_SECS);
+    }
+
+    Err("No translation service responded in time".to_string())
+}
+
 /// Set selected DVM provider
 pub fn set_selected_provider(pubkey: Option<PublicKey>) {
     *SELECTED_DVM_PROVIDER.write() = pubkey;
@@ -397,3 +496,24 @@ pub fn clear_feed() {
     *DVM_FEED_LOADING.write() = false;
     *DVM_LAST_REQUEST_ID.write() = None;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::Keys;
+
+    #[test]
+    fn test_translation_request_tags() {
+        let event = translation_request("Hola mundo", "en")
+            .sign_with_keys(&Keys::generate())
+            .unwrap();
+
+        assert_eq!(event.kind, Kind::from(KIND_TRANSLATION));
+        let tags: Vec<Vec<String>> = event.tags.iter().map(|t| t.as_slice().to_vec()).collect();
+        assert!(tags.contains(&vec!["i".to_string(), "Hola mundo".to_string(), "text".to_string()]));
+        assert!(tags.contains(&vec!["param".to_string(), "language".to_string(), "en".to_string()]));
+        // Open to any DVM
+        assert!(!tags.iter().any(|t| t[0] == "p"));
+        assert_eq!(translation_language_name("ja"), "Japanese");
+    }
+}
@@ -223,6 +223,19 @@ pub async fn fetch_custom_emojis(pubkey: String) {
     *EMOJI_FETCH_TIME.write() = Some(Timestamp::now());
 }
 
+/// User's custom emojis followed by those from their emoji sets, one per shortcode
+pub fn all_custom_emojis() -> Vec<CustomEmoji> {
+    let mut emojis: Vec<CustomEmoji> = CUSTOM_EMOJIS.read().data().read().clone();
+    for set in EMOJI_SETS.read().data().read().iter() {
+        for emoji in &set.emojis {
+            if !emojis.iter().any(|e| e.shortcode == emoji.shortcode) {
+                emojis.push(emoji.clone());
+            }
+        }
+    }
+    emojis
+}
+
 /// Initialize emoji fetching for the authenticated user
 pub fn init_emoji_fetch() {
     let auth_state = crate::stores::auth_store::AUTH_STATE.read();
@@ -0,0 +1,188 @@
+//! Offline copy of the home feed
+//!
+//! The newest notes of each home feed are kept per account in the app cache
+//! database (`feed_cache` store) so the timeline can be shown straight away
+//! on startup, before relays connect or with no network at all. Every loaded
+//! page refreshes the cache; once it holds more than [`FEED_CACHE_CAP`]
+//! notes, the ones gone longest without appearing in a loaded feed are
+//! dropped.
+
+use chrono::Utc;
+use nostr_sdk::{Event, JsonUtil, PublicKey, Timestamp};
+use serde::{Deserialize, Serialize};
+
+use crate::stores::home_feed::FeedType;
+use crate::utils::FeedItem;
+
+/// Most notes kept per feed
+pub const FEED_CACHE_CAP: usize = 200;
+
+/// A home feed item as cached in IndexedDB
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CachedFeedItem {
+    /// Hex ID of the note (the reposted note for reposts)
+    pub id: String,
+    /// The note as event JSON
+    pub event_json: String,
+    /// Hex pubkey of the reposter, for reposts
+    #[serde(default)]
+    pub reposted_by: Option<String>,
+    /// When the repost was made, for reposts
+    #[serde(default)]
+    pub repost_timestamp: Option<u64>,
+    /// Last time the item was in a loaded feed, Unix seconds
+    pub last_seen: u64,
+}
+
+impl CachedFeedItem {
+    pub fn from_feed_item(item: &FeedItem, seen_at: u64) -> Self {
+        let event = item.event();
+        let (reposted_by, repost_timestamp) = match item {
+            FeedItem::Repost { reposted_by, repost_timestamp, .. } => {
+                (Some(reposted_by.to_hex()), Some(repost_timestamp.as_u64()))
+            }
+            _ => (None, None),
+        };
+        CachedFeedItem {
+            id: event.id.to_hex(),
+            event_json: event.as_json(),
+            reposted_by,
+            repost_timestamp,
+            last_seen: seen_at,
+        }
+    }
+
+    pub fn to_feed_item(&self) -> Option<FeedItem> {
+        let event = Event::from_json(&self.event_json).ok()?;
+        match (&self.reposted_by, self.repost_timestamp) {
+            (Some(reposted_by), Some(repost_timestamp)) => Some(FeedItem::Repost {
+                original: event,
+                reposted_by: PublicKey::from_hex(reposted_by).ok()?,
+                repost_timestamp: Timestamp::from(repost_timestamp),
+            }),
+            _ => Some(FeedItem::from_note(event)),
+        }
+    }
+}
+
+/// Add freshly loaded items to the cache, keeping at most `cap`
+///
+/// An item already cached is replaced by its fresh copy. When over the cap,
+/// the least recently seen items go first.
+pub fn merge_feed_cache(
+    cached: Vec<CachedFeedItem>,
+    fresh: Vec<CachedFeedItem>,
+    cap: usize,
+) -> Vec<CachedFeedItem> {
+    let mut items = fresh;
+    for item in cached {
+        if !items.iter().any(|fresh| fresh.id == item.id) {
+            items.push(item);
+        }
+    }
+    // Stable, so items seen together keep the feed's order
+    items.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
+    items.truncate(cap);
+    items
+}
+
+/// Cache key of a feed for an account
+///
+/// Relay feeds aren't cached: their notes change with the relay picked.
+fn cache_key(pubkey: &str, feed_type: FeedType) -> Option<String> {
+    (feed_type != FeedType::Relay).then(|| format!("{}:{}", pubkey, feed_type.as_str()))
+}
+
+/// Cache key of a feed for the logged-in account, if any
+fn current_cache_key(feed_type: FeedType) -> Option<String> {
+    cache_key(&crate::stores::auth_store::get_pubkey()?, feed_type)
+}
+
+/// Remember the items of a loaded home feed page
+pub async fn save_feed_cache(feed_type: FeedType, items: &[FeedItem]) {
+    let Some(key) = current_cache_key(feed_type) else {
+        return;
+    };
+    let cache_db = match crate::stores::cache_database::get_cache_db().await {
+        Ok(cache_db) => cache_db,
+        Err(e) => {
+            log::debug!("Feed cache unavailable: {}", e);
+            return;
+        }
+    };
+
+    let now = Utc::now().timestamp() as u64;
+    let fresh = items.iter().map(|item| CachedFeedItem::from_feed_item(item, now)).collect();
+    let cached = cache_db.get_feed_cache(&key).await.ok().flatten().unwrap_or_default();
+    let merged = merge_feed_cache(cached, fresh, FEED_CACHE_CAP);
+
+    if let Err(e) = cache_db.save_feed_cache(&key, &merged).await {
+        log::debug!("Failed to cache {} feed: {}", key, e);
+    }
+}
+
+/// Cached items of a home feed, newest first
+pub async fn load_feed_cache(feed_type: FeedType) -> Vec<FeedItem> {
+    let Some(key) = current_cache_key(feed_type) else {
+        return Vec::new();
+    };
+    let Ok(cache_db) = crate::stores::cache_database::get_cache_db().await else {
+        return Vec::new();
+    };
+    let cached = match cache_db.get_feed_cache(&key).await {
+        Ok(cached) => cached.unwrap_or_default(),
+        Err(e) => {
+            log::debug!("Feed cache read failed for {}: {}", key, e);
+            return Vec::new();
+        }
+    };
+
+    let mut items: Vec<FeedItem> = cached.iter().filter_map(CachedFeedItem::to_feed_item).collect();
+    items.sort_by(|a, b| b.sort_timestamp().cmp(&a.sort_timestamp()));
+    items
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, last_seen: u64) -> CachedFeedItem {
+        CachedFeedItem {
+            id: id.to_string(),
+            event_json: String::new(),
+            reposted_by: None,
+            repost_timestamp: None,
+            last_seen,
+        }
+    }
+
+    fn ids(items: &[CachedFeedItem]) -> Vec<&str> {
+        items.iter().map(|item| item.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_merge_trims_least_recently_seen_at_cap() {
+        let cached = vec![item("a", 30), item("b", 20), item("c", 10)];
+
+        // Exactly at the cap nothing is dropped
+        let merged = merge_feed_cache(cached.clone(), vec![], 3);
+        assert_eq!(ids(&merged), vec!["a", "b", "c"]);
+
+        // One over: the item seen longest ago goes
+        let merged = merge_feed_cache(cached.clone(), vec![item("d", 40)], 3);
+        assert_eq!(ids(&merged), vec!["d", "a", "b"]);
+
+        // Seeing an old item again refreshes it instead of duplicating it
+        let merged = merge_feed_cache(cached, vec![item("c", 40), item("d", 40)], 3);
+        assert_eq!(ids(&merged), vec!["c", "d", "a"]);
+    }
+
+    #[test]
+    fn test_cache_key_is_per_account() {
+        assert_ne!(
+            cache_key("alice", FeedType::Following),
+            cache_key("bob", FeedType::Following)
+        );
+        assert_eq!(cache_key("alice", FeedType::Relay), None);
+    }
+}
@@ -1,8 +1,10 @@
 use dioxus::prelude::*;
 use dioxus::signals::ReadableExt;
 use dioxus_stores::Store;
-use nostr_sdk::{Filter, Kind, Timestamp, SingleLetterTag, Alphabet};
+use nostr_sdk::{EventId, Filter, Kind, Timestamp, SingleLetterTag, Alphabet};
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
+use std::sync::{Mutex, OnceLock};
 use std::time::Duration;
 
 /// GIF metadata from Nostr (NIP-94 format)
@@ -18,32 +20,207 @@ pub struct GifMetadata {
     pub created_at: Timestamp,
 }
 
-/// Store for GIF search results with fine-grained reactivity
-#[derive(Clone, Debug, Default, Store)]
-pub struct GifResultsStore {
-    pub data: Vec<GifMetadata>,
-}
-
 /// Store for recent GIFs with fine-grained reactivity
 #[derive(Clone, Debug, Default, Store)]
 pub struct RecentGifsStore {
     pub data: Vec<GifMetadata>,
 }
 
-/// Global state for GIF search results
-pub static GIF_RESULTS: GlobalSignal<Store<GifResultsStore>> =
-    Signal::global(|| Store::new(GifResultsStore::default()));
-pub static GIF_LOADING: GlobalSignal<bool> = Signal::global(|| false);
-pub static GIF_OLDEST_TIMESTAMP: GlobalSignal<Option<Timestamp>> = Signal::global(|| None);
 pub static RECENT_GIFS: GlobalSignal<Store<RecentGifsStore>> =
     Signal::global(|| Store::new(RecentGifsStore::default()));
-pub static CURRENT_SEARCH_QUERY: GlobalSignal<String> = Signal::global(String::new);
-pub static GIF_SEARCH_SEQ: GlobalSignal<u64> = Signal::global(|| 0);
 
 const MAX_RECENT_GIFS: usize = 20;
 
+/// GIFs requested per page of search results
+pub const GIF_PAGE_SIZE: usize = 50;
+
+/// GIF events ranked for the trending list
+const TRENDING_CANDIDATES: usize = 200;
+
+/// A page of GIF search results
+#[derive(Clone, Debug, PartialEq)]
+pub struct GifPage {
+    pub gifs: Vec<GifMetadata>,
+    /// Offset of the following page; None once the results are exhausted
+    pub next_offset: Option<usize>,
+}
+
+/// Search result pages fetched this session, keyed by query and offset
+///
+/// Relays paginate by time, not offset: the page at `offset` continues from
+/// the oldest GIF of the page before it, so pages are fetched in order.
+#[derive(Debug, Default)]
+pub struct GifPageCache {
+    pages: HashMap<(String, usize), GifPage>,
+}
+
+impl GifPageCache {
+    pub fn get(&self, query: &str, offset: usize) -> Option<GifPage> {
+        self.pages.get(&(query.to_string(), offset)).cloned()
+    }
+
+    /// `until` for fetching the page at `offset`
+    ///
+    /// Errors when the page before it hasn't been fetched yet.
+    pub fn cursor_for(&self, query: &str, offset: usize) -> Result<Option<Timestamp>, String> {
+        if offset == 0 {
+            return Ok(None);
+        }
+        if offset % GIF_PAGE_SIZE != 0 {
+            return Err(format!("Invalid GIF page offset {}", offset));
+        }
+        let previous = self
+            .pages
+            .get(&(query.to_string(), offset - GIF_PAGE_SIZE))
+            .ok_or_else(|| format!("GIF page {} requested before the page preceding it", offset))?;
+        Ok(previous.gifs.last().map(|gif| gif.created_at))
+    }
+
+    /// Record the `fetched` GIFs for the page at `offset`
+    ///
+    /// GIFs repeated from the previous page are dropped (`until` is
+    /// inclusive). A short page means there is nothing after it.
+    pub fn insert(&mut self, query: &str, offset: usize, fetched: Vec<GifMetadata>) -> GifPage {
+        let next_offset = (fetched.len() >= GIF_PAGE_SIZE).then_some(offset + GIF_PAGE_SIZE);
+
+        let previous = offset
+            .checked_sub(GIF_PAGE_SIZE)
+            .and_then(|previous| self.pages.get(&(query.to_string(), previous)));
+        let gifs = fetched
+            .into_iter()
+            .filter(|gif| !previous.is_some_and(|page| page.gifs.iter().any(|seen| seen.url == gif.url)))
+            .collect();
+
+        let page = GifPage { gifs, next_offset };
+        self.pages.insert((query.to_string(), offset), page.clone());
+        page
+    }
+}
+
+static GIF_PAGE_CACHE: OnceLock<Mutex<GifPageCache>> = OnceLock::new();
+
+fn gif_page_cache() -> &'static Mutex<GifPageCache> {
+    GIF_PAGE_CACHE.get_or_init(|| Mutex::new(GifPageCache::default()))
+}
+
+/// Trending GIFs, fetched once per session
+static TRENDING_CACHE: OnceLock<Mutex<Option<Vec<GifMetadata>>>> = OnceLock::new();
+
+fn trending_cache() -> &'static Mutex<Option<Vec<GifMetadata>>> {
+    TRENDING_CACHE.get_or_init(|| Mutex::new(None))
+}
+
+/// Whether a relay error is a rate limit (NIP-01 `rate-limited:` prefix)
+fn is_rate_limited(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("rate-limited") || error.contains("rate limit") || error.contains("too many requests")
+}
+
+fn friendly_fetch_error(error: String) -> String {
+    if is_rate_limited(&error) {
+        "GIF relays are rate limiting requests, try again in a moment".to_string()
+    } else {
+        error
+    }
+}
+
+/// Search GIFs, `GIF_PAGE_SIZE` at a time
+///
+/// An empty query lists the latest GIFs. Pages are cached for the session, so
+/// scrolling back or repeating a search doesn't hit the relays again.
+pub async fn search_gifs(query: String, offset: usize) -> Result<GifPage, String> {
+    let query = query.trim().to_string();
+
+    let until = {
+        let cache = gif_page_cache().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(page) = cache.get(&query, offset) {
+            log::debug!("GIF page '{}'@{} served from cache", query, offset);
+            return Ok(page);
+        }
+        cache.cursor_for(&query, offset)?
+    };
+
+    let search = if query.is_empty() { None } else { Some(query.clone()) };
+    let fetched = fetch_gifs(GIF_PAGE_SIZE, until, search)
+        .await
+        .map_err(friendly_fetch_error)?;
+
+    Ok(gif_page_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(&query, offset, fetched))
+}
+
+/// GIFs with the most reactions among the latest GIF uploads
+pub async fn trending_gifs() -> Result<Vec<GifMetadata>, String> {
+    if let Some(cached) = trending_cache().lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone() {
+        return Ok(cached);
+    }
+
+    let events = fetch_gif_events(TRENDING_CANDIDATES, None, None)
+        .await
+        .map_err(friendly_fetch_error)?;
+    if events.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let reactions_filter = Filter::new()
+        .kind(Kind::Reaction)
+        .events(events.iter().map(|event| event.id));
+    let reactions = crate::stores::nostr_client::fetch_events_aggregated(reactions_filter, Duration::from_secs(10))
+        .await
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to fetch GIF reactions, ranking by recency: {}", e);
+            Vec::new()
+        });
+
+    let mut counts: HashMap<EventId, usize> = HashMap::new();
+    for reaction in &reactions {
+        if let Some(target) = reaction.tags.event_ids().last() {
+            *counts.entry(*target).or_insert(0) += 1;
+        }
+    }
+
+    let mut ranked: Vec<(usize, GifMetadata)> = events
+        .iter()
+        .filter_map(|event| parse_gif_event(event).map(|gif| (counts.get(&event.id).copied().unwrap_or(0), gif)))
+        .collect();
+    ranked.sort_by(|(a_count, a), (b_count, b)| b_count.cmp(a_count).then(b.created_at.cmp(&a.created_at)));
+
+    let mut seen_urls = HashSet::new();
+    let trending: Vec<GifMetadata> = ranked
+        .into_iter()
+        .map(|(_, gif)| gif)
+        .filter(|gif| seen_urls.insert(gif.url.clone()))
+        .take(GIF_PAGE_SIZE)
+        .collect();
+
+    *trending_cache().lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(trending.clone());
+    Ok(trending)
+}
+
 /// Fetch GIFs from Nostr using NIP-94 (Kind 1063)
 pub async fn fetch_gifs(limit: usize, until: Option<Timestamp>, search_query: Option<String>) -> Result<Vec<GifMetadata>, String> {
+    let events = fetch_gif_events(limit, until, search_query).await?;
+
+    // Parse events into GifMetadata
+    let mut gifs = Vec::new();
+    for event in events {
+        if let Some(gif) = parse_gif_event(&event) {
+            gifs.push(gif);
+        }
+    }
+
+    // Sort by created_at (newest first)
+    gifs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    log::info!("Parsed {} valid GIF entries", gifs.len());
+
+    Ok(gifs)
+}
+
+/// Fetch GIF FileMetadata events, trying the gifbuddy relay before the user's relays
+async fn fetch_gif_events(limit: usize, until: Option<Timestamp>, search_query: Option<String>) -> Result<Vec<nostr::Event>, String> {
     log::info!("Fetching GIFs from Nostr (limit: {}, until: {:?}, search: {:?})", limit, until, search_query);
 
     let client = match crate::stores::nostr_client::get_client() {
@@ -111,20 +288,7 @@ pub async fn fetch_gifs(limit: usize, until: Option<Timestamp>, search_query: Op
 
     log::info!("Fetched {} GIF events total", events.len());
 
-    // Parse events into GifMetadata
-    let mut gifs = Vec::new();
-    for event in events {
-        if let Some(gif) = parse_gif_event(&event) {
-            gifs.push(gif);
-        }
-    }
-
-    // Sort by created_at (newest first)
-    gifs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
-
-    log::info!("Parsed {} valid GIF entries", gifs.len());
-
-    Ok(gifs)
+    Ok(events)
 }
 
 /// Parse a Nostr event into GifMetadata
@@ -216,138 +380,6 @@ fn parse_dimensions(dim_str: &str) -> Option<(u64, u64)> {
     }
 }
 
-/// Load initial GIFs (from cache and network)
-pub async fn load_initial_gifs() {
-    *GIF_LOADING.write() = true;
-
-    // Capture the current search query to detect if it changes during fetch
-    let captured_query = CURRENT_SEARCH_QUERY.read().clone();
-    let query = if captured_query.is_empty() { None } else { Some(captured_query.clone()) };
-
-    match fetch_gifs(100, None, query).await {
-        Ok(gifs) => {
-            // Bail if the search query changed while we were fetching (prevents stale results)
-            let current_query = CURRENT_SEARCH_QUERY.read().clone();
-            if captured_query != current_query {
-                log::debug!("Search query changed during initial load, discarding stale results");
-                *GIF_LOADING.write() = false;
-                return;
-            }
-
-            // Update oldest timestamp for pagination
-            if let Some(oldest) = gifs.last() {
-                *GIF_OLDEST_TIMESTAMP.write() = Some(oldest.created_at);
-            }
-
-            *GIF_RESULTS.read().data().write() = gifs;
-        }
-        Err(e) => {
-            log::error!("Failed to load initial GIFs: {}", e);
-        }
-    }
-
-    *GIF_LOADING.write() = false;
-}
-
-/// Search for GIFs with a specific query
-pub async fn search_gifs(query: String) {
-    // Increment sequence number to track this search request
-    let request_seq = {
-        let mut seq = GIF_SEARCH_SEQ.write();
-        *seq = seq.wrapping_add(1);
-        *seq
-    };
-
-    *GIF_LOADING.write() = true;
-    *CURRENT_SEARCH_QUERY.write() = query.clone();
-
-    let search_query = if query.is_empty() { None } else { Some(query) };
-
-    match fetch_gifs(100, None, search_query).await {
-        Ok(gifs) => {
-            // Only update state if this is still the latest search request
-            let current_seq = *GIF_SEARCH_SEQ.read();
-            if request_seq != current_seq {
-                log::debug!("Discarding stale search results (seq {} != {})", request_seq, current_seq);
-                return;
-            }
-
-            // Update oldest timestamp for pagination
-            if let Some(oldest) = gifs.last() {
-                *GIF_OLDEST_TIMESTAMP.write() = Some(oldest.created_at);
-            }
-
-            *GIF_RESULTS.read().data().write() = gifs;
-        }
-        Err(e) => {
-            log::error!("Failed to search GIFs: {}", e);
-        }
-    }
-
-    *GIF_LOADING.write() = false;
-}
-
-/// Load more GIFs (pagination)
-pub async fn load_more_gifs() {
-    let until = *GIF_OLDEST_TIMESTAMP.read();
-    if until.is_none() {
-        log::warn!("No oldest timestamp set, cannot paginate");
-        return;
-    }
-
-    *GIF_LOADING.write() = true;
-
-    // Capture the current search query to detect if it changes while we're loading
-    let captured_query = CURRENT_SEARCH_QUERY.read().clone();
-    let query = if captured_query.is_empty() { None } else { Some(captured_query.clone()) };
-
-    match fetch_gifs(100, until, query).await {
-        Ok(new_gifs) => {
-            // Bail if the search query changed while we were loading (prevents cross-contamination)
-            let current_query = CURRENT_SEARCH_QUERY.read().clone();
-            if captured_query != current_query {
-                log::debug!("Search query changed during pagination, discarding stale results");
-                *GIF_LOADING.write() = false;
-                return;
-            }
-
-            if new_gifs.is_empty() {
-                log::info!("No more GIFs to load");
-                *GIF_LOADING.write() = false;
-                return;
-            }
-
-            // Filter out duplicates (Filter::until is inclusive, so oldest event may be duplicated)
-            let oldest_timestamp = until;
-            let deduplicated_gifs: Vec<GifMetadata> = new_gifs.into_iter()
-                .filter(|gif| Some(gif.created_at) != oldest_timestamp)
-                .collect();
-
-            if deduplicated_gifs.is_empty() {
-                log::info!("No new GIFs after deduplication");
-                *GIF_LOADING.write() = false;
-                return;
-            }
-
-            // Update oldest timestamp
-            if let Some(oldest) = deduplicated_gifs.last() {
-                *GIF_OLDEST_TIMESTAMP.write() = Some(oldest.created_at);
-            }
-
-            // Append to existing results
-            let store = GIF_RESULTS.read();
-            let mut data = store.data();
-            let mut current = data.write();
-            current.extend(deduplicated_gifs);
-        }
-        Err(e) => {
-            log::error!("Failed to load more GIFs: {}", e);
-        }
-    }
-
-    *GIF_LOADING.write() = false;
-}
-
 /// Add a GIF to recent list
 pub fn add_recent_gif(gif: GifMetadata) {
     let store = RECENT_GIFS.read();
@@ -490,3 +522,57 @@ pub async fn publish_gif_event(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gif(n: u64) -> GifMetadata {
+        GifMetadata {
+            url: format!("https://gifs.example/{}.gif", n),
+            thumbnail: None,
+            dimensions: None,
+            size: None,
+            blurhash: None,
+            alt: None,
+            summary: None,
+            created_at: Timestamp::from(1_000_000 - n),
+        }
+    }
+
+    #[test]
+    fn test_gif_page_bookkeeping() {
+        let mut cache = GifPageCache::default();
+        assert_eq!(cache.cursor_for("cats", 0), Ok(None));
+        // Pages load in order
+        assert!(cache.cursor_for("cats", GIF_PAGE_SIZE).is_err());
+
+        let first: Vec<GifMetadata> = (0..GIF_PAGE_SIZE as u64).map(gif).collect();
+        let page = cache.insert("cats", 0, first);
+        assert_eq!(page.next_offset, Some(GIF_PAGE_SIZE));
+        assert_eq!(
+            cache.cursor_for("cats", GIF_PAGE_SIZE),
+            Ok(Some(gif(GIF_PAGE_SIZE as u64 - 1).created_at))
+        );
+
+        // The inclusive `until` repeats the oldest GIF; a short page is the last
+        let last = GIF_PAGE_SIZE as u64 - 1;
+        let second: Vec<GifMetadata> = (last..last + 10).map(gif).collect();
+        let page = cache.insert("cats", GIF_PAGE_SIZE, second);
+        assert_eq!(page.gifs.len(), 9);
+        assert_eq!(page.next_offset, None);
+
+        assert_eq!(cache.get("cats", GIF_PAGE_SIZE), Some(page));
+        assert_eq!(cache.get("dogs", 0), None);
+
+        // No results at all
+        assert_eq!(cache.insert("nothing", 0, Vec::new()).next_offset, None);
+    }
+
+    #[test]
+    fn test_rate_limit_errors() {
+        assert!(is_rate_limited("rate-limited: slow down"));
+        assert!(!is_rate_limited("connection refused"));
+        assert_eq!(friendly_fetch_error("timeout".to_string()), "timeout");
+    }
+}
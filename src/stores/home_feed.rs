@@ -0,0 +1,337 @@
+//! Home feed source - which notes the home timeline shows
+//!
+//! Following (authors from the kind 3 contact list, with or without
+//! replies, optionally joined by notes with a followed hashtag), global
+//! (everything the connected relays send) or a single relay. The last source
+//! and relay picked are remembered in localStorage.
+
+use gloo_storage::{LocalStorage, Storage};
+use nostr_sdk::{Event, Filter, Kind, PublicKey, RelayUrl, Timestamp};
+use std::time::Duration;
+
+use crate::stores::nostr_client;
+
+const STORAGE_KEY: &str = "nostr_blue_home_feed";
+const RELAY_STORAGE_KEY: &str = "nostr_blue_home_feed_relay";
+
+/// How far back the first page of the global and relay feeds reaches
+const RECENT_WINDOW_SECS: u64 = 86400;
+
+/// Shown when the following feed falls back to global
+pub const EMPTY_FOLLOWS_HINT: &str =
+    "You're not following anyone yet, so this is the global feed. Follow people to fill your timeline.";
+
+/// Where the home feed's notes come from
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum FeedType {
+    /// Top-level posts from followed accounts
+    #[default]
+    Following,
+    /// All posts from followed accounts, including replies
+    FollowingWithReplies,
+    /// Top-level posts from followed accounts and with followed hashtags
+    FollowingAndHashtags,
+    /// Posts from everyone on our relays
+    Global,
+    /// Posts from one chosen relay
+    Relay,
+}
+
+impl FeedType {
+    pub const ALL: [FeedType; 5] = [
+        FeedType::Following,
+        FeedType::FollowingWithReplies,
+        FeedType::FollowingAndHashtags,
+        FeedType::Global,
+        FeedType::Relay,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Following => "following",
+            Self::FollowingWithReplies => "following_replies",
+            Self::FollowingAndHashtags => "following_hashtags",
+            Self::Global => "global",
+            Self::Relay => "relay",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "following" => Some(Self::Following),
+            "following_replies" => Some(Self::FollowingWithReplies),
+            "following_hashtags" => Some(Self::FollowingAndHashtags),
+            "global" => Some(Self::Global),
+            "relay" => Some(Self::Relay),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Following => "Following",
+            Self::FollowingWithReplies => "Following + Replies",
+            Self::FollowingAndHashtags => "Following + Hashtags",
+            Self::Global => "Global",
+            Self::Relay => "Relay",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Following => "Top level posts only",
+            Self::FollowingWithReplies => "All posts including replies",
+            Self::FollowingAndHashtags => "Posts from people and hashtags you follow",
+            Self::Global => "Posts from everyone",
+            Self::Relay => "Posts from a single relay",
+        }
+    }
+
+    /// Whether the feed is built from the contact list
+    pub fn is_following(&self) -> bool {
+        matches!(self, Self::Following | Self::FollowingWithReplies | Self::FollowingAndHashtags)
+    }
+}
+
+/// Last feed type picked, Following if none
+pub fn load_feed_type() -> FeedType {
+    LocalStorage::get::<String>(STORAGE_KEY)
+        .ok()
+        .and_then(|s| FeedType::parse(&s))
+        .unwrap_or_default()
+}
+
+/// Last relay browsed in the relay feed
+pub fn load_feed_relay() -> Option<String> {
+    LocalStorage::get::<String>(RELAY_STORAGE_KEY).ok()
+}
+
+/// Remember the feed type (and, for the relay feed, the relay)
+pub fn save_feed(feed_type: FeedType, relay: Option<&str>) {
+    if let Err(e) = LocalStorage::set(STORAGE_KEY, feed_type.as_str()) {
+        log::warn!("Failed to save home feed type: {}", e);
+    }
+    if let Some(relay) = relay {
+        if let Err(e) = LocalStorage::set(RELAY_STORAGE_KEY, relay) {
+            log::warn!("Failed to save home feed relay: {}", e);
+        }
+    }
+}
+
+/// Followed pubkeys from a contact list; entries that don't parse are skipped
+pub fn contact_authors(contacts: &[String]) -> Vec<PublicKey> {
+    contacts.iter().filter_map(|contact| PublicKey::parse(contact).ok()).collect()
+}
+
+/// Filter for notes and reposts from followed accounts
+///
+/// The replies feed asks for more events (replies are filtered out of the
+/// other one afterwards) and starts from the last day. None when the
+/// contact list has no valid pubkeys.
+pub fn following_filter(contacts: &[String], include_replies: bool, until: Option<u64>) -> Option<Filter> {
+    let authors = contact_authors(contacts);
+    if authors.is_empty() {
+        return None;
+    }
+
+    let filter = Filter::new()
+        .kinds(vec![Kind::TextNote, Kind::Repost])
+        .authors(authors)
+        .limit(if include_replies { 150 } else { 100 });
+
+    Some(match until {
+        Some(until_ts) => filter.until(Timestamp::from(until_ts)),
+        // The top-level feed has no lower bound so it can reach older posts
+        None if include_replies => filter.since(Timestamp::now() - Duration::from_secs(RECENT_WINDOW_SECS)),
+        None => filter,
+    })
+}
+
+/// Filter for notes carrying any of the followed hashtags
+///
+/// None when no hashtags are followed.
+pub fn hashtag_filter(hashtags: &[String], until: Option<u64>) -> Option<Filter> {
+    if hashtags.is_empty() {
+        return None;
+    }
+
+    let filter = Filter::new()
+        .kind(Kind::TextNote)
+        .hashtags(hashtags.iter().cloned())
+        .limit(100);
+
+    Some(match until {
+        Some(until_ts) => filter.until(Timestamp::from(until_ts)),
+        None => filter,
+    })
+}
+
+/// Filters for the following + hashtags feed
+///
+/// Relays can't OR authors with `t` tags in one filter, so followed
+/// accounts and followed hashtags each get their own. Empty when there's
+/// nothing to follow.
+pub fn following_and_hashtags_filters(contacts: &[String], hashtags: &[String], until: Option<u64>) -> Vec<Filter> {
+    following_filter(contacts, false, until)
+        .into_iter()
+        .chain(hashtag_filter(hashtags, until))
+        .collect()
+}
+
+/// Merge the pages fetched with each filter of a combined feed
+///
+/// A source that filled its page may have older notes left, so the merged
+/// page ends at the newest "oldest note" among full pages. The next page
+/// then picks up every source from that point and none is skipped over.
+/// Duplicates across sources are dropped.
+pub fn merge_source_pages(pages: Vec<(Vec<Event>, Option<usize>)>) -> Vec<Event> {
+    let cutoff = pages
+        .iter()
+        .filter(|(events, limit)| limit.is_some_and(|limit| events.len() >= limit))
+        .filter_map(|(events, _)| events.iter().map(|event| event.created_at).min())
+        .max();
+
+    let mut seen = std::collections::HashSet::new();
+    pages
+        .into_iter()
+        .flat_map(|(events, _)| events)
+        .filter(|event| cutoff.is_none_or(|cutoff| event.created_at >= cutoff))
+        .filter(|event| seen.insert(event.id))
+        .collect()
+}
+
+/// Filter for recent notes and reposts from anyone (global and relay feeds)
+pub fn global_filter(until: Option<u64>) -> Filter {
+    let filter = Filter::new().kinds(vec![Kind::TextNote, Kind::Repost]).limit(50);
+    match until {
+        Some(until_ts) => filter.until(Timestamp::from(until_ts)),
+        None => filter.since(Timestamp::now() - Duration::from_secs(RECENT_WINDOW_SECS)),
+    }
+}
+
+/// Normalize a relay address typed by the user
+///
+/// `wss://` is assumed when no scheme is given. None for anything that isn't
+/// a websocket URL.
+pub fn normalize_relay_url(input: &str) -> Option<String> {
+    let input = input.trim().trim_end_matches('/');
+    if input.is_empty() {
+        return None;
+    }
+    let url = if input.contains("://") {
+        input.to_string()
+    } else {
+        format!("wss://{}", input)
+    };
+    if !url.starts_with("wss://") && !url.starts_with("ws://") {
+        return None;
+    }
+    RelayUrl::parse(&url).ok().map(|_| url)
+}
+
+/// Fetch events from a single relay
+///
+/// A relay not yet in the pool is borrowed for the fetch.
+pub async fn fetch_relay_events(relay_url: &str, filter: Filter, timeout: Duration) -> Result<Vec<Event>, String> {
+    let client = nostr_client::get_client().ok_or("Client not initialized")?;
+
+    let temp_relays = nostr_client::add_temp_relays(&client, &[relay_url.to_string()]).await;
+    let result = client
+        .fetch_events_from([relay_url], filter, timeout)
+        .await
+        .map(|events| events.into_iter().collect())
+        .map_err(|e| format!("Failed to load feed from {}: {}", relay_url, e));
+    temp_relays.release().await;
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::{Alphabet, Keys, SingleLetterTag, ToBech32};
+
+    #[test]
+    fn test_following_filter_from_contacts() {
+        let alice = Keys::generate().public_key();
+        let bob = Keys::generate().public_key();
+        let contacts = vec![alice.to_hex(), "not a pubkey".to_string(), bob.to_bech32().unwrap()];
+
+        let filter = following_filter(&contacts, false, Some(1_700_000_000)).unwrap();
+        let authors = filter.authors.clone().unwrap();
+        assert_eq!(authors.len(), 2);
+        assert!(authors.contains(&alice) && authors.contains(&bob));
+        let kinds = filter.kinds.clone().unwrap();
+        assert_eq!(kinds.len(), 2);
+        assert!(kinds.contains(&Kind::TextNote) && kinds.contains(&Kind::Repost));
+        assert_eq!(filter.limit, Some(100));
+        assert_eq!(filter.until, Some(Timestamp::from(1_700_000_000)));
+        assert_eq!(filter.since, None);
+
+        // The replies feed's first page only covers the last day
+        let filter = following_filter(&contacts, true, None).unwrap();
+        assert_eq!(filter.limit, Some(150));
+        assert!(filter.since.is_some());
+
+        // Nothing to follow
+        assert!(following_filter(&[], false, None).is_none());
+        assert!(following_filter(&["garbage".to_string()], true, None).is_none());
+    }
+
+    #[test]
+    fn test_following_and_hashtags_filters() {
+        let alice = Keys::generate().public_key();
+        let hashtags = vec!["nostr".to_string(), "bitcoin".to_string()];
+
+        let filters = following_and_hashtags_filters(&[alice.to_hex()], &hashtags, Some(1_700_000_000));
+        assert_eq!(filters.len(), 2);
+        assert_eq!(filters[0].authors.clone().unwrap().len(), 1);
+        let by_tag = &filters[1];
+        assert!(by_tag.authors.is_none());
+        let tags = by_tag.generic_tags.get(&SingleLetterTag::lowercase(Alphabet::T)).unwrap();
+        assert!(tags.contains("nostr") && tags.contains("bitcoin"));
+        assert_eq!(by_tag.kinds.clone().unwrap().len(), 1);
+        assert_eq!(by_tag.until, Some(Timestamp::from(1_700_000_000)));
+
+        // Either half can be missing
+        assert_eq!(following_and_hashtags_filters(&[], &hashtags, None).len(), 1);
+        assert_eq!(following_and_hashtags_filters(&[alice.to_hex()], &[], None).len(), 1);
+        assert!(following_and_hashtags_filters(&[], &[], None).is_empty());
+    }
+
+    #[test]
+    fn test_merge_source_pages_stops_where_a_full_page_ends() {
+        let keys = Keys::generate();
+        let note = |secs: u64| {
+            nostr_sdk::EventBuilder::text_note(format!("at {}", secs))
+                .custom_created_at(Timestamp::from(secs))
+                .sign_with_keys(&keys)
+                .unwrap()
+        };
+
+        // Follows fill their page of 2 down to 300; hashtags reach back to 100
+        let follows = vec![note(500), note(300)];
+        let hashtags = vec![note(400), note(200), note(100)];
+        let merged = merge_source_pages(vec![(follows.clone(), Some(2)), (hashtags, Some(10))]);
+        let mut times: Vec<u64> = merged.iter().map(|event| event.created_at.as_secs()).collect();
+        times.sort();
+        assert_eq!(times, vec![300, 400, 500]);
+
+        // No full page: everything is kept, duplicates once
+        let merged = merge_source_pages(vec![(follows.clone(), Some(5)), (follows, Some(5))]);
+        assert_eq!(merged.len(), 2);
+    }
+
+    #[test]
+    fn test_feed_type_round_trip_and_relay_urls() {
+        for feed_type in FeedType::ALL {
+            assert_eq!(FeedType::parse(feed_type.as_str()), Some(feed_type));
+        }
+        assert_eq!(FeedType::parse("trending"), None);
+
+        assert_eq!(normalize_relay_url(" relay.example.com/ ").as_deref(), Some("wss://relay.example.com"));
+        assert_eq!(normalize_relay_url("ws://localhost:7777").as_deref(), Some("ws://localhost:7777"));
+        assert_eq!(normalize_relay_url("https://relay.example.com"), None);
+        assert_eq!(normalize_relay_url(""), None);
+    }
+}
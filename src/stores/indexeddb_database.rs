@@ -20,6 +20,9 @@
 //! - `proofs` - Ecash proofs (tokens)
 //! - `transactions` - Transaction history
 //! - `keyset_counters` - Deterministic derivation counters
+//! - `pending_melts` - Lightning payments the mint reported as in-flight
+//!
+//! Caches that aren't wallet data live in `cache_database`.
 //!
 //! ## Thread Safety
 //!
@@ -55,7 +58,7 @@ use web_sys::IdbTransactionMode;
 
 // Database constants
 const DB_NAME: &str = "cashu_wallet_db";
-const DB_VERSION: u32 = 2;
+const DB_VERSION: u32 = 7;
 
 // Object store names
 const STORE_MINTS: &str = "mints";
@@ -69,6 +72,26 @@ const STORE_TRANSACTIONS: &str = "transactions";
 const STORE_KEYSET_COUNTERS: &str = "keyset_counters";
 const STORE_PENDING_EVENTS: &str = "pending_events";
 const STORE_SYNC_STATE: &str = "sync_state";
+const STORE_PENDING_MELTS: &str = "pending_melts";
+
+/// Cache stores that versions 4-6 kept here, moved to `cache_database`
+const MOVED_CACHE_STORES: [&str; 3] = ["profiles", "mint_reliability", "feed_cache"];
+
+/// Every object store, for wiping the database
+const ALL_STORES: [&str; 12] = [
+    STORE_MINTS,
+    STORE_KEYSETS,
+    STORE_KEYSET_BY_ID,
+    STORE_KEYS,
+    STORE_MINT_QUOTES,
+    STORE_MELT_QUOTES,
+    STORE_PROOFS,
+    STORE_TRANSACTIONS,
+    STORE_KEYSET_COUNTERS,
+    STORE_PENDING_EVENTS,
+    STORE_SYNC_STATE,
+    STORE_PENDING_MELTS,
+];
 
 /// IndexedDB-backed implementation of WalletDatabase
 #[derive(Clone, Debug)]
@@ -137,6 +160,16 @@ impl IndexedDbDatabase {
             if !db.object_store_names().any(|n| n == STORE_SYNC_STATE) {
                 db.create_object_store(STORE_SYNC_STATE)?;
             }
+            // V3: Add pending melt store for resuming in-flight lightning payments
+            if !db.object_store_names().any(|n| n == STORE_PENDING_MELTS) {
+                db.create_object_store(STORE_PENDING_MELTS)?;
+            }
+            // V7: Drop the non-wallet caches added in V4-V6 (now in cache_database)
+            for store_name in MOVED_CACHE_STORES {
+                if db.object_store_names().any(|n| n == store_name) {
+                    db.delete_object_store(store_name)?;
+                }
+            }
 
             Ok(())
         }));
@@ -396,6 +429,54 @@ impl IndexedDbDatabase {
     pub async fn clear_sync_state(&self) -> Result<(), database::Error> {
         self.delete_value(STORE_SYNC_STATE, "current").await
     }
+
+    /// Delete every record in every store (wallet and pending events),
+    /// leaving the empty database in place
+    pub async fn clear_all(&self) -> Result<(), database::Error> {
+        let tx = self
+            .db
+            .transaction_on_multi_with_mode(&ALL_STORES, IdbTransactionMode::Readwrite)
+            .map_err(|e| Self::make_error(format!("Transaction error: {:?}", e)))?;
+
+        for store_name in ALL_STORES {
+            let store = tx
+                .object_store(store_name)
+                .map_err(|e| Self::make_error(format!("Store error: {:?}", e)))?;
+            store
+                .clear()
+                .map_err(|e| Self::make_error(format!("Clear error: {:?}", e)))?;
+        }
+
+        tx.await
+            .into_result()
+            .map_err(|e| Self::make_error(format!("Transaction commit error: {:?}", e)))?;
+
+        Ok(())
+    }
+
+    // =========================================================================
+    // Pending Melts (In-flight Lightning Payments)
+    // =========================================================================
+
+    /// Save a melt quote that the mint reported as pending
+    pub async fn save_pending_melt(
+        &self,
+        quote: &crate::stores::cashu::types::MeltQuoteInfo,
+    ) -> Result<(), database::Error> {
+        self.put_value(STORE_PENDING_MELTS, &quote.quote_id, quote).await
+    }
+
+    /// Load all melt quotes still awaiting a final state
+    pub async fn get_all_pending_melts(
+        &self,
+    ) -> Result<Vec<crate::stores::cashu::types::MeltQuoteInfo>, database::Error> {
+        self.get_all_values(STORE_PENDING_MELTS).await
+    }
+
+    /// Remove a pending melt once it resolved to Paid or Failed
+    pub async fn remove_pending_melt(&self, quote_id: &str) -> Result<(), database::Error> {
+        self.delete_value(STORE_PENDING_MELTS, quote_id).await
+    }
 }
 
 // Implement WalletDatabase trait for IndexedDbDatabase
@@ -0,0 +1,250 @@
+//! NIP-51 interest list (kind 10015) of the logged-in account
+//!
+//! Followed hashtags are the list's `t` tags, stored lowercase so `#Nostr`
+//! and `#nostr` are one entry. Like the contact list, every change is made
+//! on the newest list we can find and keeps any other tags (interest sets)
+//! and the content (private items) untouched.
+
+use dioxus::prelude::*;
+use nostr_sdk::{Event, EventBuilder, Filter, Kind, PublicKey, Tag, Timestamp};
+use std::time::Duration;
+
+use crate::stores::{auth_store, nostr_client};
+
+/// A kind 10015 interest list
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct InterestList {
+    /// Followed hashtags, lowercase and without `#`
+    pub hashtags: Vec<String>,
+    /// Non-`t` tags, republished unchanged
+    pub other_tags: Vec<Tag>,
+    /// Event content (encrypted private items), republished unchanged
+    pub content: String,
+    /// When the list was published; None for a list never published
+    pub created_at: Option<Timestamp>,
+}
+
+/// Lowercase a hashtag and drop a leading `#`; None if nothing is left
+pub fn normalize_hashtag(tag: &str) -> Option<String> {
+    let tag = tag.trim().trim_start_matches('#').trim().to_lowercase();
+    (!tag.is_empty()).then_some(tag)
+}
+
+impl InterestList {
+    /// Parse a kind 10015 event; hashtags differing only in case are merged
+    pub fn from_event(event: &Event) -> Self {
+        let mut list = InterestList {
+            content: event.content.clone(),
+            created_at: Some(event.created_at),
+            ..Default::default()
+        };
+
+        for tag in event.tags.iter() {
+            let parts = tag.as_slice();
+            let hashtag = (parts.first().map(|s| s.as_str()) == Some("t"))
+                .then(|| parts.get(1).and_then(|t| normalize_hashtag(t)))
+                .flatten();
+            match hashtag {
+                Some(hashtag) => {
+                    if !list.hashtags.contains(&hashtag) {
+                        list.hashtags.push(hashtag);
+                    }
+                }
+                None => list.other_tags.push(tag.clone()),
+            }
+        }
+
+        list
+    }
+
+    /// Whether this list was found on relays or published by us
+    ///
+    /// A list that was never found may only mean the relays didn't answer;
+    /// publishing it would replace the account's real interests.
+    pub fn is_published(&self) -> bool {
+        self.created_at.is_some()
+    }
+
+    pub fn contains(&self, hashtag: &str) -> bool {
+        normalize_hashtag(hashtag).is_some_and(|tag| self.hashtags.contains(&tag))
+    }
+
+    /// Add a hashtag. Returns whether it was new.
+    pub fn follow(&mut self, hashtag: &str) -> bool {
+        match normalize_hashtag(hashtag) {
+            Some(tag) if !self.hashtags.contains(&tag) => {
+                self.hashtags.push(tag);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remove a hashtag. Returns whether it was in the list.
+    pub fn unfollow(&mut self, hashtag: &str) -> bool {
+        let Some(tag) = normalize_hashtag(hashtag) else {
+            return false;
+        };
+        let before = self.hashtags.len();
+        self.hashtags.retain(|t| t != &tag);
+        self.hashtags.len() != before
+    }
+
+    /// Tags for publishing: one `t` tag per hashtag, then the other tags
+    pub fn to_tags(&self) -> Vec<Tag> {
+        let mut tags: Vec<Tag> = self.hashtags.iter().map(Tag::hashtag).collect();
+        tags.extend(self.other_tags.iter().cloned());
+        tags
+    }
+}
+
+/// The logged-in account's interest list, once loaded
+pub static INTEREST_LIST: GlobalSignal<Option<InterestList>> = Signal::global(|| None);
+
+/// Whether the logged-in account follows `hashtag`
+///
+/// False until the list has been loaded with [`load_interest_list`].
+pub fn is_following_hashtag(hashtag: &str) -> bool {
+    INTEREST_LIST.read().as_ref().is_some_and(|list| list.contains(hashtag))
+}
+
+/// Fetch the newest interest list from relays and the local database
+///
+/// The copy already in memory wins if it is newer, so a slow relay can't
+/// roll back a change made in this session.
+pub async fn load_interest_list() -> Result<InterestList, String> {
+    let client = nostr_client::get_client().ok_or("Client not initialized")?;
+    let my_pubkey = auth_store::get_pubkey().ok_or("Not logged in")?;
+    let author = PublicKey::parse(&my_pubkey).map_err(|e| format!("Invalid pubkey: {}", e))?;
+    let filter = Filter::new().author(author).kind(Kind::Interests).limit(1);
+
+    let mut events: Vec<Event> = client
+        .database()
+        .query(filter.clone())
+        .await
+        .map(|events| events.into_iter().collect())
+        .unwrap_or_default();
+    let relay_events = client
+        .fetch_events(filter, Duration::from_secs(10))
+        .await
+        .map_err(|e| format!("Failed to fetch interest list: {}", e))?;
+    events.extend(relay_events);
+
+    let fetched = events
+        .iter()
+        .max_by_key(|event| event.created_at)
+        .map(InterestList::from_event);
+    let known = INTEREST_LIST.peek().clone();
+
+    let list = match (fetched, known) {
+        (Some(fetched), Some(known)) if known.created_at > fetched.created_at => known,
+        (Some(fetched), _) => fetched,
+        (None, Some(known)) => known,
+        // Nothing found: keep it unpublished so it is never sent out
+        (None, None) => InterestList::default(),
+    };
+    *INTEREST_LIST.write() = Some(list.clone());
+    Ok(list)
+}
+
+async fn publish_interest_list(mut list: InterestList) -> Result<(), String> {
+    let client = nostr_client::get_client().ok_or("Client not initialized")?;
+    if !*nostr_client::HAS_SIGNER.read() {
+        return Err("No signer attached. Cannot publish events.".to_string());
+    }
+
+    // Never go backwards in time, or relays would keep the old list
+    let created_at = match list.created_at {
+        Some(previous) if previous >= Timestamp::now() => previous + 1,
+        _ => Timestamp::now(),
+    };
+    let builder = EventBuilder::new(Kind::Interests, list.content.clone())
+        .tags(list.to_tags())
+        .custom_created_at(created_at);
+
+    client
+        .send_event_builder(builder)
+        .await
+        .map_err(|e| format!("Failed to publish interest list: {}", e))?;
+
+    log::info!("Published interest list with {} hashtags", list.hashtags.len());
+    list.created_at = Some(created_at);
+    *INTEREST_LIST.write() = Some(list);
+    Ok(())
+}
+
+const NO_INTEREST_LIST: &str =
+    "Couldn't find your followed hashtags on your relays. Not publishing a new list, as it would replace them.";
+
+/// Follow a hashtag
+pub async fn follow_hashtag(hashtag: &str) -> Result<(), String> {
+    let mut list = load_interest_list().await?;
+    if !list.is_published() {
+        return Err(NO_INTEREST_LIST.to_string());
+    }
+
+    if list.follow(hashtag) {
+        publish_interest_list(list).await
+    } else {
+        log::info!("Already following #{}", hashtag);
+        Ok(())
+    }
+}
+
+/// Stop following a hashtag
+pub async fn unfollow_hashtag(hashtag: &str) -> Result<(), String> {
+    let mut list = load_interest_list().await?;
+    if !list.is_published() {
+        return Err(NO_INTEREST_LIST.to_string());
+    }
+
+    if list.unfollow(hashtag) {
+        publish_interest_list(list).await
+    } else {
+        log::info!("Not following #{}", hashtag);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::Keys;
+
+    fn tag(parts: &[&str]) -> Tag {
+        Tag::parse(parts.iter().map(|p| p.to_string()).collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn test_follow_normalizes_and_keeps_other_tags() {
+        let event = EventBuilder::new(Kind::Interests, "encrypted-private-items")
+            .tags(vec![
+                tag(&["t", "Nostr"]),
+                tag(&["t", "nostr"]),
+                tag(&["t", "  "]),
+                tag(&["a", "30015:abc:photography"]),
+            ])
+            .sign_with_keys(&Keys::generate())
+            .unwrap();
+
+        let mut list = InterestList::from_event(&event);
+        assert_eq!(list.hashtags, vec!["nostr".to_string()]);
+
+        assert!(list.follow("#Bitcoin"));
+        // Same tag in another case is a dupe
+        assert!(!list.follow("BITCOIN"));
+        assert!(!list.follow("#"));
+        assert!(list.contains("#NOSTR"));
+
+        assert!(list.unfollow("NoStR"));
+        assert!(!list.unfollow("nostr"));
+
+        let tags: Vec<Vec<String>> = list.to_tags().iter().map(|t| t.as_slice().to_vec()).collect();
+        assert_eq!(tags[0], vec!["t".to_string(), "bitcoin".to_string()]);
+        // The empty `t` tag and the interest set reference survive untouched
+        assert_eq!(tags.len(), 3);
+        assert_eq!(list.content, event.content);
+        assert!(list.is_published());
+        assert!(!InterestList::default().is_published());
+    }
+}
@@ -0,0 +1,456 @@
+//! Labels Store - NIP-32 labels
+//!
+//! A label is a kind 1985 event: an `L` tag names the namespace, `l` tags
+//! carry the labels (`["l", <label>, <namespace>]`) and `e`, `p` or `r` tags
+//! name what is labeled. We use them to flag Cashu mints (by URL) and notes.
+//! Only labels from the user and the people they follow are fetched, so
+//! strangers can't flood a mint or note with labels. Like user statuses,
+//! labels are fetched in batches: components ask for a target and a
+//! short-delayed flush fetches everything asked for at once.
+
+use dioxus::prelude::*;
+use nostr_sdk::{Alphabet, Event, EventBuilder, EventId, Filter, Kind, PublicKey, SingleLetterTag, Tag, TagKind, Timestamp};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use crate::stores::{auth_store, nostr_client};
+use crate::utils::time::now_secs;
+
+/// NIP-32 label
+pub const KIND_LABEL: u16 = 1985;
+
+/// Namespace for Cashu mint labels
+pub const MINT_NAMESPACE: &str = "blue.nostr.mint";
+
+/// Namespace for note quality labels
+pub const CONTENT_NAMESPACE: &str = "blue.nostr.content";
+
+/// Labels offered for mints: (label, description)
+pub const MINT_LABELS: &[(&str, &str)] = &[
+    ("scam", "Scam"),
+    ("unreliable", "Unreliable"),
+    ("trusted", "Trusted"),
+];
+
+/// Labels offered for notes: (label, description)
+pub const CONTENT_LABELS: &[(&str, &str)] = &[
+    ("spam", "Spam"),
+    ("low-quality", "Low quality"),
+    ("misleading", "Misleading"),
+];
+
+/// Namespace of `l` tags that don't name one (NIP-32)
+const DEFAULT_NAMESPACE: &str = "ugc";
+
+/// How long fetched labels are reused before asking relays again
+const LABEL_TTL_SECS: u64 = 300;
+
+/// Delay before a batch of requested targets is fetched
+const BATCH_DELAY_MS: u32 = 300;
+
+/// Most label events fetched per target
+const LABELS_PER_TARGET: usize = 100;
+
+/// What a label is about
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LabelTarget {
+    Event(EventId),
+    Pubkey(PublicKey),
+    /// A URL, such as a mint's
+    Url(String),
+}
+
+impl LabelTarget {
+    /// Stable cache key
+    pub fn key(&self) -> String {
+        match self {
+            Self::Event(id) => format!("e:{}", id.to_hex()),
+            Self::Pubkey(pubkey) => format!("p:{}", pubkey.to_hex()),
+            Self::Url(url) => format!("r:{}", url.trim_end_matches('/')),
+        }
+    }
+
+    fn to_tag(&self) -> Tag {
+        match self {
+            Self::Event(id) => Tag::event(*id),
+            Self::Pubkey(pubkey) => Tag::public_key(*pubkey),
+            Self::Url(url) => Tag::custom(TagKind::custom("r"), [url.trim_end_matches('/').to_string()]),
+        }
+    }
+
+    /// Whether a raw tag (`["e", id, ...]`) points at this target
+    fn matches_tag(&self, parts: &[String]) -> bool {
+        match (self, parts) {
+            (Self::Event(id), [name, value, ..]) if name == "e" => *value == id.to_hex(),
+            (Self::Pubkey(pubkey), [name, value, ..]) if name == "p" => *value == pubkey.to_hex(),
+            (Self::Url(url), [name, value, ..]) if name == "r" => value.trim_end_matches('/') == url.trim_end_matches('/'),
+            _ => false,
+        }
+    }
+}
+
+/// One label applied to a target
+#[derive(Clone, Debug, PartialEq)]
+pub struct Label {
+    pub namespace: String,
+    pub value: String,
+    pub target: LabelTarget,
+    pub author: PublicKey,
+    /// Optional explanation (the event content)
+    pub comment: String,
+    pub created_at: Timestamp,
+}
+
+/// Build a label event for `target`
+pub fn build_label(namespace: &str, label: &str, target: &LabelTarget, comment: &str) -> EventBuilder {
+    let tags = vec![
+        Tag::custom(TagKind::SingleLetter(SingleLetterTag::uppercase(Alphabet::L)), [namespace.to_string()]),
+        Tag::custom(
+            TagKind::SingleLetter(SingleLetterTag::lowercase(Alphabet::L)),
+            [label.to_string(), namespace.to_string()],
+        ),
+        target.to_tag(),
+    ];
+    EventBuilder::new(Kind::from(KIND_LABEL), comment.trim()).tags(tags)
+}
+
+/// Labels a kind 1985 event applies to `target`
+///
+/// `l` tags whose namespace the event doesn't declare with an `L` tag are
+/// ignored, as NIP-32 asks; an `l` tag without a namespace is `ugc`.
+pub fn labels_from_event(event: &Event, target: &LabelTarget) -> Vec<Label> {
+    if event.kind != Kind::from(KIND_LABEL) {
+        return Vec::new();
+    }
+    let tags: Vec<&[String]> = event.tags.iter().map(|tag| tag.as_slice()).collect();
+    if !tags.iter().any(|parts| target.matches_tag(parts)) {
+        return Vec::new();
+    }
+
+    let namespaces: HashSet<&str> = tags
+        .iter()
+        .filter(|parts| parts.len() >= 2 && parts[0] == "L")
+        .map(|parts| parts[1].as_str())
+        .collect();
+
+    tags.iter()
+        .filter(|parts| parts.len() >= 2 && parts[0] == "l")
+        .filter_map(|parts| {
+            let namespace = parts.get(2).map(String::as_str).unwrap_or(DEFAULT_NAMESPACE);
+            (namespace == DEFAULT_NAMESPACE || namespaces.contains(namespace)).then(|| Label {
+                namespace: namespace.to_string(),
+                value: parts[1].clone(),
+                target: target.clone(),
+                author: event.pubkey,
+                comment: event.content.clone(),
+                created_at: event.created_at,
+            })
+        })
+        .collect()
+}
+
+/// A label value with how many people applied it
+#[derive(Clone, Debug, PartialEq)]
+pub struct LabelSummary {
+    pub namespace: String,
+    pub value: String,
+    /// Distinct authors
+    pub count: usize,
+    /// Of those, ourselves and people we follow
+    pub trusted_count: usize,
+}
+
+/// Count labels per value, one vote per author
+///
+/// Values with trusted labels come first, then the most applied.
+pub fn summarize_labels(labels: &[Label], trusted: &HashSet<PublicKey>) -> Vec<LabelSummary> {
+    let mut authors: HashMap<(String, String), HashSet<PublicKey>> = HashMap::new();
+    for label in labels {
+        authors
+            .entry((label.namespace.clone(), label.value.clone()))
+            .or_default()
+            .insert(label.author);
+    }
+
+    let mut summaries: Vec<LabelSummary> = authors
+        .into_iter()
+        .map(|((namespace, value), authors)| LabelSummary {
+            trusted_count: authors.iter().filter(|author| trusted.contains(author)).count(),
+            count: authors.len(),
+            namespace,
+            value,
+        })
+        .collect();
+    summaries.sort_by(|a, b| {
+        b.trusted_count
+            .cmp(&a.trusted_count)
+            .then(b.count.cmp(&a.count))
+            .then(a.value.cmp(&b.value))
+    });
+    summaries
+}
+
+/// Publish a label for `target`
+pub async fn publish_label(namespace: &str, label: &str, target: LabelTarget) -> Result<(), String> {
+    if !auth_store::is_authenticated() {
+        return Err("Not authenticated".to_string());
+    }
+    let client = nostr_client::get_client().ok_or("Client not initialized")?;
+    let event = client
+        .sign_event_builder(build_label(namespace, label, &target, ""))
+        .await
+        .map_err(|e| format!("Failed to sign label: {}", e))?;
+    client
+        .send_event(&event)
+        .await
+        .map_err(|e| format!("Failed to publish label: {}", e))?;
+
+    // Show our own label right away
+    if let Some(cached) = LABEL_CACHE.write().get_mut(&target.key()) {
+        cached.labels.extend(labels_from_event(&event, &target));
+    }
+    Ok(())
+}
+
+/// Filter for the labels `authors` put on `target`
+///
+/// URLs are asked for with and without a trailing slash, as labelers
+/// write mint URLs both ways.
+fn target_filter(target: &LabelTarget, authors: &HashSet<PublicKey>) -> Filter {
+    let base = Filter::new()
+        .kind(Kind::from(KIND_LABEL))
+        .authors(authors.iter().copied())
+        .limit(LABELS_PER_TARGET);
+    match target {
+        LabelTarget::Event(id) => base.event(*id),
+        LabelTarget::Pubkey(pubkey) => base.pubkey(*pubkey),
+        LabelTarget::Url(url) => {
+            let url = url.trim_end_matches('/');
+            base.custom_tags(SingleLetterTag::lowercase(Alphabet::R), [url.to_string(), format!("{}/", url)])
+        }
+    }
+}
+
+/// Fetch the labels `authors` put on `targets`, keyed by target
+async fn fetch_labels_for(
+    targets: &[LabelTarget],
+    authors: &HashSet<PublicKey>,
+) -> Result<HashMap<LabelTarget, Vec<Label>>, String> {
+    if authors.is_empty() {
+        return Ok(targets.iter().map(|target| (target.clone(), Vec::new())).collect());
+    }
+
+    let fetches = targets.iter().map(|target| async move {
+        let events = nostr_client::fetch_events_aggregated(target_filter(target, authors), Duration::from_secs(5)).await?;
+        let labels = events.iter().flat_map(|event| labels_from_event(event, target)).collect();
+        Ok::<_, String>((target.clone(), labels))
+    });
+    futures::future::join_all(fetches).await.into_iter().collect()
+}
+
+/// Fetch the labels the user and their follows put on `target`
+pub async fn fetch_labels(target: LabelTarget) -> Result<Vec<Label>, String> {
+    let authors = trusted_authors().await;
+    let mut by_target = fetch_labels_for(std::slice::from_ref(&target), &authors).await?;
+    Ok(by_target.remove(&target).unwrap_or_default())
+}
+
+/// Ourselves and the people we follow, whose labels we trust
+pub async fn trusted_authors() -> HashSet<PublicKey> {
+    let Some(pubkey) = auth_store::get_pubkey() else {
+        return HashSet::new();
+    };
+    let mut trusted: HashSet<PublicKey> = nostr_client::fetch_contacts(pubkey.clone())
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|contact| PublicKey::parse(contact).ok())
+        .collect();
+    if let Ok(me) = PublicKey::parse(&pubkey) {
+        trusted.insert(me);
+    }
+    trusted
+}
+
+// =============================================================================
+// Batched fetching
+// =============================================================================
+
+#[derive(Clone, Debug)]
+struct CachedLabels {
+    labels: Vec<Label>,
+    fetched_at: u64,
+}
+
+/// Labels per target key
+static LABEL_CACHE: GlobalSignal<HashMap<String, CachedLabels>> = Signal::global(HashMap::new);
+
+/// Targets waiting for the next batch fetch
+static PENDING: GlobalSignal<HashMap<String, LabelTarget>> = Signal::global(HashMap::new);
+
+/// Whether a batch fetch is scheduled
+static FLUSH_SCHEDULED: GlobalSignal<bool> = Signal::global(|| false);
+
+/// Ourselves and our follows, loaded once per account (keyed by hex pubkey)
+static TRUSTED: GlobalSignal<Option<(String, HashSet<PublicKey>)>> = Signal::global(|| None);
+
+/// Summarized labels on `target` in `namespace` (reactive); call
+/// `request_labels` to load them
+pub fn get_label_summary(target: &LabelTarget, namespace: &str) -> Vec<LabelSummary> {
+    let cache = LABEL_CACHE.read();
+    let Some(cached) = cache.get(&target.key()) else {
+        return Vec::new();
+    };
+    let labels: Vec<Label> = cached.labels.iter().filter(|label| label.namespace == namespace).cloned().collect();
+    let trusted = TRUSTED.read();
+    let trusted = trusted.as_ref().map(|(_, trusted)| trusted.clone()).unwrap_or_default();
+    summarize_labels(&labels, &trusted)
+}
+
+/// Queue a fetch of `target`'s labels unless they were fetched recently
+pub fn request_labels(target: LabelTarget) {
+    let key = target.key();
+    let fresh = LABEL_CACHE
+        .peek()
+        .get(&key)
+        .is_some_and(|cached| now_secs().saturating_sub(cached.fetched_at) < LABEL_TTL_SECS);
+    if fresh || PENDING.peek().contains_key(&key) {
+        return;
+    }
+    PENDING.write().insert(key, target);
+
+    if *FLUSH_SCHEDULED.peek() {
+        return;
+    }
+    *FLUSH_SCHEDULED.write() = true;
+
+    dioxus_core::spawn_forever(async move {
+        gloo_timers::future::TimeoutFuture::new(BATCH_DELAY_MS).await;
+        *FLUSH_SCHEDULED.write() = false;
+        let targets: Vec<LabelTarget> = PENDING.write().drain().map(|(_, target)| target).collect();
+
+        // Another account's follows (and the labels fetched for them) don't apply
+        let account = auth_store::get_pubkey().unwrap_or_default();
+        let trusted = match TRUSTED.peek().as_ref() {
+            Some((owner, trusted)) if *owner == account => Some(trusted.clone()),
+            _ => None,
+        };
+        let trusted = match trusted {
+            Some(trusted) => trusted,
+            None => {
+                let trusted = trusted_authors().await;
+                LABEL_CACHE.write().clear();
+                *TRUSTED.write() = Some((account, trusted.clone()));
+                trusted
+            }
+        };
+
+        match fetch_labels_for(&targets, &trusted).await {
+            Ok(by_target) => {
+                let fetched_at = now_secs();
+                let mut cache = LABEL_CACHE.write();
+                for (target, labels) in by_target {
+                    cache.insert(target.key(), CachedLabels { labels, fetched_at });
+                }
+            }
+            Err(e) => log::warn!("Failed to fetch labels: {}", e),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::Keys;
+
+    fn label_event(keys: &Keys, namespace: &str, label: &str, target: &LabelTarget) -> Event {
+        build_label(namespace, label, target, "").sign_with_keys(keys).unwrap()
+    }
+
+    #[test]
+    fn test_build_label_tags() {
+        let keys = Keys::generate();
+        let target = LabelTarget::Url("https://mint.example.com".to_string());
+        let event = build_label(MINT_NAMESPACE, "scam", &target, " took my sats ")
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        assert_eq!(event.kind, Kind::from(KIND_LABEL));
+        assert_eq!(event.content, "took my sats");
+        let tags: Vec<Vec<String>> = event.tags.iter().map(|tag| tag.as_slice().to_vec()).collect();
+        assert_eq!(
+            tags,
+            vec![
+                vec!["L".to_string(), MINT_NAMESPACE.to_string()],
+                vec!["l".to_string(), "scam".to_string(), MINT_NAMESPACE.to_string()],
+                vec!["r".to_string(), "https://mint.example.com".to_string()],
+            ]
+        );
+
+        let labels = labels_from_event(&event, &target);
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0].value, "scam");
+        assert_eq!(labels[0].namespace, MINT_NAMESPACE);
+        assert_eq!(labels[0].author, keys.public_key());
+
+        // Not about another mint
+        assert!(labels_from_event(&event, &LabelTarget::Url("https://other.example.com".to_string())).is_empty());
+    }
+
+    #[test]
+    fn test_undeclared_namespace_is_ignored() {
+        let keys = Keys::generate();
+        let note = EventId::all_zeros();
+        let target = LabelTarget::Event(note);
+        let event = EventBuilder::new(Kind::from(KIND_LABEL), "")
+            .tags([
+                Tag::parse(["L", CONTENT_NAMESPACE]).unwrap(),
+                Tag::parse(["l", "spam", CONTENT_NAMESPACE]).unwrap(),
+                Tag::parse(["l", "great", "other.namespace"]).unwrap(),
+                Tag::event(note),
+            ])
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        let values: Vec<String> = labels_from_event(&event, &target).into_iter().map(|l| l.value).collect();
+        assert_eq!(values, vec!["spam".to_string()]);
+    }
+
+    #[test]
+    fn test_summary_puts_trusted_labels_first() {
+        let target = LabelTarget::Url("https://mint.example.com".to_string());
+        let friend = Keys::generate();
+        let strangers: Vec<Keys> = (0..3).map(|_| Keys::generate()).collect();
+
+        let mut labels = Vec::new();
+        for stranger in &strangers {
+            labels.extend(labels_from_event(&label_event(stranger, MINT_NAMESPACE, "scam", &target), &target));
+        }
+        labels.extend(labels_from_event(&label_event(&friend, MINT_NAMESPACE, "trusted", &target), &target));
+        // A repeat label from the same author counts once
+        labels.extend(labels_from_event(&label_event(&friend, MINT_NAMESPACE, "trusted", &target), &target));
+
+        let trusted: HashSet<PublicKey> = [friend.public_key()].into_iter().collect();
+        let summary = summarize_labels(&labels, &trusted);
+        assert_eq!(summary.len(), 2);
+        assert_eq!((summary[0].value.as_str(), summary[0].count, summary[0].trusted_count), ("trusted", 1, 1));
+        assert_eq!((summary[1].value.as_str(), summary[1].count, summary[1].trusted_count), ("scam", 3, 0));
+
+        // Without a web of trust, the most applied label leads
+        let summary = summarize_labels(&labels, &HashSet::new());
+        assert_eq!(summary[0].value, "scam");
+    }
+
+    #[test]
+    fn test_target_filter_is_per_target_and_trusted_only() {
+        let friend = Keys::generate().public_key();
+        let authors: HashSet<PublicKey> = [friend].into_iter().collect();
+        let filter = target_filter(&LabelTarget::Url("https://mint.example.com/".to_string()), &authors);
+
+        assert_eq!(filter.limit, Some(LABELS_PER_TARGET));
+        assert_eq!(filter.authors.as_ref().map(|authors| authors.len()), Some(1));
+        let urls = filter.generic_tags.get(&SingleLetterTag::lowercase(Alphabet::R)).unwrap();
+        assert!(urls.contains("https://mint.example.com"));
+        assert!(urls.contains("https://mint.example.com/"));
+    }
+}
@@ -0,0 +1,215 @@
+//! Live Chat Store - NIP-53 live stream chat
+//!
+//! Chat messages are kind 1311 events whose `a` tag points at the kind 30311
+//! stream. The chat loads recent history, then follows new messages through a
+//! subscription. Only the latest `MAX_CHAT_MESSAGES` are kept.
+
+use dioxus::prelude::*;
+use nostr_sdk::{
+    Alphabet, Event, EventBuilder, Filter, Kind, RelayPoolNotification, SingleLetterTag,
+    SubscriptionId, Tag, TagKind, Timestamp,
+};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use crate::stores::nostr_client;
+
+/// NIP-53 live chat message
+pub const KIND_LIVE_CHAT_MESSAGE: u16 = 1311;
+
+/// NIP-53 live event (the stream itself)
+pub const KIND_LIVE_EVENT: u16 = 30311;
+
+/// Messages kept (and rendered) per chat; older ones are dropped
+pub const MAX_CHAT_MESSAGES: usize = 200;
+
+/// Chat subscriptions by stream coordinate, so they can be closed when leaving
+pub static CHAT_SUBSCRIPTIONS: GlobalSignal<HashMap<String, SubscriptionId>> =
+    Signal::global(HashMap::new);
+
+/// The `30311:<pubkey>:<d>` coordinate chat messages reference
+pub fn stream_coordinate(stream_author: &str, stream_d_tag: &str) -> String {
+    format!("{}:{}:{}", KIND_LIVE_EVENT, stream_author, stream_d_tag)
+}
+
+/// Build a chat message for the stream at `coordinate`
+pub fn build_chat_message(content: &str, coordinate: &str) -> EventBuilder {
+    EventBuilder::new(Kind::from(KIND_LIVE_CHAT_MESSAGE), content)
+        .tag(Tag::custom(TagKind::a(), vec![coordinate.to_string()]))
+}
+
+/// Add new messages, oldest first, keeping only the latest `MAX_CHAT_MESSAGES`
+///
+/// Messages already present (by event ID) are skipped. Returns whether
+/// anything was added.
+pub fn merge_chat_messages(messages: &mut Vec<Event>, incoming: impl IntoIterator<Item = Event>) -> bool {
+    let mut seen: HashSet<_> = messages.iter().map(|m| m.id).collect();
+    let before = messages.len();
+    messages.extend(incoming.into_iter().filter(|m| seen.insert(m.id)));
+    if messages.len() == before {
+        return false;
+    }
+
+    messages.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    if messages.len() > MAX_CHAT_MESSAGES {
+        messages.drain(..messages.len() - MAX_CHAT_MESSAGES);
+    }
+    true
+}
+
+fn chat_filter(coordinate: &str) -> Filter {
+    Filter::new()
+        .kind(Kind::from(KIND_LIVE_CHAT_MESSAGE))
+        .custom_tag(SingleLetterTag::lowercase(Alphabet::A), coordinate)
+}
+
+/// Chat messages for a stream, oldest first
+///
+/// Must be called from a component (e.g. inside `use_hook`): the signal and
+/// the listener task belong to the calling scope. It is None until recent
+/// history has loaded, then grows as messages arrive. Call `unsubscribe_chat`
+/// when the chat is no longer shown.
+pub fn subscribe_chat(coordinate: String) -> Signal<Option<Vec<Event>>> {
+    let messages = Signal::new(None);
+
+    spawn(async move {
+        if let Err(e) = run_chat_subscription(&coordinate, messages).await {
+            log::warn!("Live chat for {} stopped: {}", coordinate, e);
+        }
+    });
+
+    messages
+}
+
+async fn run_chat_subscription(
+    coordinate: &str,
+    mut messages: Signal<Option<Vec<Event>>>,
+) -> Result<(), String> {
+    let client = nostr_client::get_client().ok_or("Client not initialized")?;
+
+    // Listen before subscribing so no message slips in between
+    let mut notifications = client.notifications();
+    let sub_id = client
+        .subscribe(chat_filter(coordinate).since(Timestamp::now()), None)
+        .await
+        .map_err(|e| format!("Failed to subscribe to live chat: {}", e))?
+        .val;
+    CHAT_SUBSCRIPTIONS.write().insert(coordinate.to_string(), sub_id.clone());
+
+    let history = nostr_client::fetch_events_aggregated(
+        chat_filter(coordinate).limit(MAX_CHAT_MESSAGES),
+        Duration::from_secs(10),
+    )
+    .await
+    .unwrap_or_else(|e| {
+        log::error!("Failed to fetch chat messages: {}", e);
+        Vec::new()
+    });
+    {
+        let mut messages = messages.write();
+        let list = messages.get_or_insert_with(Vec::new);
+        merge_chat_messages(list, history);
+        log::info!("Loaded {} chat messages", list.len());
+    }
+
+    while let Ok(notification) = notifications.recv().await {
+        if let RelayPoolNotification::Event { subscription_id, event, .. } = notification {
+            if subscription_id != sub_id {
+                continue;
+            }
+            let mut messages = messages.write();
+            merge_chat_messages(messages.get_or_insert_with(Vec::new), [*event]);
+        }
+    }
+
+    Ok(())
+}
+
+/// Close the chat subscription for a stream
+pub async fn unsubscribe_chat(coordinate: &str) {
+    let sub_id = CHAT_SUBSCRIPTIONS.write().remove(coordinate);
+
+    if let (Some(id), Some(client)) = (sub_id, nostr_client::get_client()) {
+        client.unsubscribe(&id).await;
+        log::info!("Live chat subscription closed for {}", coordinate);
+    }
+}
+
+/// Sign and publish a chat message, returning the event so it can be shown
+/// before relays echo it back
+pub async fn send_chat_message(content: &str, coordinate: &str) -> Result<Event, String> {
+    let client = nostr_client::get_client().ok_or("Client not initialized")?;
+    let event = client
+        .sign_event_builder(build_chat_message(content, coordinate))
+        .await
+        .map_err(|e| format!("Failed to sign chat message: {}", e))?;
+    client
+        .send_event(&event)
+        .await
+        .map_err(|e| format!("Failed to send chat message: {}", e))?;
+    Ok(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::Keys;
+
+    fn message(keys: &Keys, content: &str, created_at: u64) -> Event {
+        build_chat_message(content, "30311:abc:stream")
+            .custom_created_at(Timestamp::from(created_at))
+            .sign_with_keys(keys)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_build_chat_message_references_stream() {
+        let keys = Keys::generate();
+        let host = Keys::generate().public_key().to_hex();
+        let coordinate = stream_coordinate(&host, "my-stream");
+        assert_eq!(coordinate, format!("30311:{}:my-stream", host));
+
+        let event = build_chat_message("gm chat", &coordinate).sign_with_keys(&keys).unwrap();
+        assert_eq!(event.kind, Kind::from(KIND_LIVE_CHAT_MESSAGE));
+        assert_eq!(event.content, "gm chat");
+        let a_tags: Vec<&[String]> = event
+            .tags
+            .iter()
+            .map(|tag| tag.as_slice())
+            .filter(|tag| tag[0] == "a")
+            .collect();
+        assert_eq!(a_tags, vec![&["a".to_string(), coordinate][..]]);
+    }
+
+    #[test]
+    fn test_merge_dedupes_and_sorts() {
+        let keys = Keys::generate();
+        let first = message(&keys, "first", 100);
+        let second = message(&keys, "second", 200);
+
+        let mut messages = vec![second.clone()];
+        assert!(merge_chat_messages(&mut messages, [first.clone(), second.clone()]));
+        assert_eq!(messages, vec![first.clone(), second.clone()]);
+
+        // The same events again (e.g. a relay echoing a sent message)
+        assert!(!merge_chat_messages(&mut messages, [first, second]));
+        assert_eq!(messages.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_keeps_latest_messages() {
+        let keys = Keys::generate();
+        let mut messages = Vec::new();
+        let incoming: Vec<Event> = (0..MAX_CHAT_MESSAGES as u64 + 10)
+            .map(|i| message(&keys, &format!("message {}", i), 1000 + i))
+            .collect();
+        merge_chat_messages(&mut messages, incoming);
+
+        assert_eq!(messages.len(), MAX_CHAT_MESSAGES);
+        assert_eq!(messages[0].content, "message 10");
+        assert_eq!(
+            messages.last().unwrap().content,
+            format!("message {}", MAX_CHAT_MESSAGES + 9)
+        );
+    }
+}
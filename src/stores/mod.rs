@@ -6,8 +6,11 @@ pub mod auth_store;
 pub mod theme_store;
 pub mod signer;
 pub mod bookmarks;
+pub mod contacts;  // NIP-02 follow list
+pub mod interests;  // NIP-51 followed hashtags
 pub mod dms;
 pub mod notifications;
+pub mod activity_digest;  // "While you were away" summary
 pub mod profiles;
 pub mod settings_store;
 pub mod blossom_store;
@@ -16,6 +19,7 @@ pub mod nostr_music;
 pub mod emoji_store;
 pub mod gif_store;
 pub mod relay_metadata;
+pub mod relay_auth;  // NIP-42 relay authentication
 pub mod voice_messages_store;
 pub mod webbookmarks;
 pub mod cashu_cdk_bridge;
@@ -24,6 +28,19 @@ pub mod nwc_store;
 pub mod indexeddb_database;
 pub mod reactions_store;  // NIP-78 preferred reactions
 pub mod dvm_store;  // NIP-90 Data Vending Machines
+pub mod app_handlers;  // NIP-89 "Open in" other clients
 pub mod nip96_store;  // NIP-96 HTTP File Storage
 pub mod pending_comments;  // Optimistic updates for comments
+pub mod polls;  // NIP-88 poll votes and live results
+pub mod live_chat;  // NIP-53 live stream chat
+pub mod zap_goals;  // NIP-75 zap goals
+pub mod user_status;  // NIP-38 user statuses
+pub mod labels;  // NIP-32 labels for mints and notes
+pub mod scheduled_notes;  // Notes queued for future publication
 
+pub mod article_draft;  // Autosaved long-form article drafts
+pub mod photo_draft;  // Picture composer images and autosaved draft
+pub mod zap_funding;  // Zap funding source (NWC or nutzap)
+pub mod home_feed;  // Home feed source (following, global or one relay)
+pub mod feed_cache;  // Home feed kept in IndexedDB for offline reading
+pub mod cache_database;  // IndexedDB for caches that aren't wallet data
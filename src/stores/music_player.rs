@@ -3,11 +3,10 @@ use dioxus::signals::ReadableExt;
 use gloo_storage::{LocalStorage, Storage};
 use serde::{Deserialize, Serialize};
 use crate::services::wavlake::WavlakeTrack;
-use crate::stores::{auth_store, nostr_client};
+use crate::stores::{auth_store, nostr_client, settings_store, user_status};
 use crate::stores::nostr_music::{TrackSource, NostrTrack, KIND_MUSIC_TRACK};
-use nostr_sdk::{EventBuilder, Timestamp, Kind, Tag, TagKind};
+use nostr_sdk::{EventBuilder, Kind, Tag, TagKind};
 use nostr_sdk::nips::nip01::Coordinate;
-use nostr_sdk::nips::nip38::{LiveStatus, StatusType};
 
 /// Kind number for Music Vote events (addressable, one per user)
 pub const KIND_MUSIC_VOTE: u16 = 33169;
@@ -87,6 +86,34 @@ impl From<NostrTrack> for MusicTrack {
     }
 }
 
+/// What happens when the current track ends
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RepeatMode {
+    /// Stop after the last track in the queue
+    Off,
+    /// Wrap around to the first track
+    #[default]
+    All,
+    /// Replay the current track
+    One,
+}
+
+/// Longest crossfade window offered in settings
+pub const MAX_CROSSFADE_SECS: u8 = 12;
+
+/// Progress of a crossfade into the next track
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CrossfadePhase {
+    /// No crossfade running; any leftover fade is cancelled by the player
+    #[default]
+    Idle,
+    /// The next track is fading in on the secondary audio element
+    Fading { next_index: usize },
+    /// The queue advanced to the faded-in track; the player hands playback
+    /// back to the main audio element without restarting it
+    Handoff,
+}
+
 /// Music player state
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct MusicPlayerState {
@@ -99,6 +126,15 @@ pub struct MusicPlayerState {
     pub is_muted: bool,
     pub current_time: f64,
     pub duration: f64,
+    #[serde(default)]
+    pub repeat: RepeatMode,
+    #[serde(default)]
+    pub shuffle: bool,
+    /// Crossfade length in seconds between tracks (0 disables)
+    #[serde(default)]
+    pub crossfade_secs: u8,
+    #[serde(skip)]
+    pub crossfade: CrossfadePhase,
     #[serde(skip)]
     pub show_zap_dialog: bool,
     #[serde(skip)]
@@ -117,6 +153,10 @@ impl Default for MusicPlayerState {
             is_muted: false,
             current_time: 0.0,
             duration: 0.0,
+            repeat: RepeatMode::default(),
+            shuffle: false,
+            crossfade_secs: 0,
+            crossfade: CrossfadePhase::Idle,
             show_zap_dialog: false,
             zap_track: None,
         }
@@ -129,6 +169,103 @@ pub static MUSIC_PLAYER: GlobalSignal<MusicPlayerState> =
 
 const STORAGE_KEY_VOLUME: &str = "music_player_volume";
 const STORAGE_KEY_MUTED: &str = "music_player_muted";
+const STORAGE_KEY_QUEUE: &str = "music_player_queue";
+const STORAGE_KEY_CROSSFADE: &str = "music_player_crossfade_secs";
+
+/// Queue snapshot kept in localStorage so the player survives a reload
+///
+/// Tracks are stored as raw JSON so an entry that no longer deserializes (e.g.
+/// after a `MusicTrack` schema change) can be skipped instead of dropping the
+/// whole queue.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct PersistedQueue {
+    tracks: Vec<serde_json::Value>,
+    current_track_id: Option<String>,
+    current_index: usize,
+    #[serde(default)]
+    repeat: RepeatMode,
+    #[serde(default)]
+    shuffle: bool,
+}
+
+impl PersistedQueue {
+    fn from_state(state: &MusicPlayerState) -> Self {
+        Self {
+            tracks: state
+                .playlist
+                .iter()
+                .filter_map(|track| serde_json::to_value(track).ok())
+                .collect(),
+            current_track_id: state.current_track.as_ref().map(|track| track.id.clone()),
+            current_index: state.current_index,
+            repeat: state.repeat,
+            shuffle: state.shuffle,
+        }
+    }
+
+    /// Restore the queue into `state`, skipping tracks that no longer resolve
+    ///
+    /// Returns false if no playable track survived. Playback stays paused since
+    /// browsers block autoplay on page load.
+    fn apply_to(self, state: &mut MusicPlayerState) -> bool {
+        let mut playlist = Vec::new();
+        let mut index_before_current = 0;
+        for (i, value) in self.tracks.into_iter().enumerate() {
+            match serde_json::from_value::<MusicTrack>(value) {
+                Ok(track) if !track.id.is_empty() && !track.media_url.is_empty() => {
+                    if i < self.current_index {
+                        index_before_current += 1;
+                    }
+                    playlist.push(track);
+                }
+                _ => log::warn!("Skipping persisted queue entry {} that no longer resolves", i),
+            }
+        }
+
+        if playlist.is_empty() {
+            return false;
+        }
+
+        // Prefer the saved track id; if that track was skipped, continue with
+        // the next surviving one
+        let current_index = self
+            .current_track_id
+            .and_then(|id| playlist.iter().position(|track| track.id == id))
+            .unwrap_or(index_before_current.min(playlist.len() - 1));
+
+        state.current_track = playlist.get(current_index).cloned();
+        state.playlist = playlist;
+        state.current_index = current_index;
+        state.repeat = self.repeat;
+        state.shuffle = self.shuffle;
+        state.is_playing = false;
+        state.is_visible = true;
+        state.current_time = 0.0;
+        true
+    }
+}
+
+/// Persist the current queue, repeat and shuffle state to localStorage
+pub fn save_queue_state(state: &MusicPlayerState) {
+    if state.playlist.is_empty() {
+        LocalStorage::delete(STORAGE_KEY_QUEUE);
+        return;
+    }
+
+    if let Err(e) = LocalStorage::set(STORAGE_KEY_QUEUE, PersistedQueue::from_state(state)) {
+        log::error!("Failed to save music queue to localStorage: {}", e);
+    }
+}
+
+/// Restore the persisted queue into `state`
+///
+/// Returns true if a queue was restored.
+pub fn load_queue_state(state: &mut MusicPlayerState) -> bool {
+    match LocalStorage::get::<PersistedQueue>(STORAGE_KEY_QUEUE) {
+        Ok(queue) => queue.apply_to(state),
+        Err(_) => false,
+    }
+}
 
 /// Initialize music player from localStorage
 pub fn init_player() {
@@ -144,93 +281,43 @@ pub fn init_player() {
         state.is_muted = is_muted;
     }
 
+    // Load crossfade setting
+    if let Ok(crossfade_secs) = LocalStorage::get::<u8>(STORAGE_KEY_CROSSFADE) {
+        state.crossfade_secs = crossfade_secs.min(MAX_CROSSFADE_SECS);
+    }
+
+    if load_queue_state(&mut state) {
+        log::info!("Restored music queue with {} tracks", state.playlist.len());
+    }
+
     *MUSIC_PLAYER.write() = state;
     log::info!("Music player initialized");
 }
 
-/// Publish NIP-38 music status (Kind 30315)
+/// Publish NIP-38 music status (Kind 30315) if sharing is enabled
 async fn publish_music_status(track: &MusicTrack) {
-    // Only publish if user is authenticated
-    if !auth_store::is_authenticated() {
+    if !auth_store::is_authenticated() || !settings_store::SETTINGS.read().share_music_status {
         return;
     }
 
-    let client = match nostr_client::get_client() {
-        Some(c) => c,
-        None => {
-            log::warn!("Nostr client not initialized, skipping music status");
-            return;
-        }
-    };
-
-    // Format content: "Track Title - Artist Name"
-    let content = format!("{} - {}", track.title, track.artist);
-
-    // Build track URL reference based on source
-    let track_reference = match &track.source {
-        TrackSource::Wavlake { .. } => {
-            // Link to Wavlake track on our site
-            format!("https://nostr.blue/music/track/{}", track.id)
-        }
-        TrackSource::Nostr { pubkey, d_tag, .. } => {
-            // Create naddr bech32 for nostr tracks (NIP-19)
-            // Format: nostr:naddr1... where naddr encodes kind:pubkey:d-tag
-            // For simplicity, we'll use the coordinate format which can be parsed
-            format!("https://nostr.blue/music/playlist/{}:{}:{}",
-                crate::stores::nostr_music::KIND_MUSIC_TRACK, pubkey, d_tag)
-        }
-    };
-
-    // Create LiveStatus with expiration based on track duration
-    let mut status = LiveStatus {
-        status_type: StatusType::Music,
-        expiration: None,
-        reference: Some(track_reference),
-    };
-
-    // Set expiration if track has duration
-    if let Some(duration) = track.duration {
-        if duration > 0 {
-            status.expiration = Some(Timestamp::now() + std::time::Duration::from_secs(duration as u64));
-        }
-    }
-
-    // Create Kind 30315 (User Status) event using NIP-38 helper
-    let builder = EventBuilder::live_status(status, content);
-
-    match client.send_event_builder(builder).await {
-        Ok(event_id) => {
-            log::info!("Music status published: {} (event: {})", track.title, event_id.to_hex());
-        }
-        Err(e) => {
-            log::error!("Failed to publish music status: {}", e);
-        }
+    match user_status::set_music_status(track).await {
+        Ok(()) => log::info!("Music status published: {}", track.title),
+        Err(e) => log::error!("Failed to publish music status: {}", e),
     }
 }
 
 /// Clear music status (publish empty status)
+///
+/// Cleared whether or not sharing is still enabled: it may have been turned
+/// off while a track's status was up.
 async fn clear_music_status() {
-    // Only publish if user is authenticated
     if !auth_store::is_authenticated() {
         return;
     }
 
-    let client = match nostr_client::get_client() {
-        Some(c) => c,
-        None => return,
-    };
-
-    // Create empty status to clear (per NIP-38: empty content clears the status)
-    let status = LiveStatus::new(StatusType::Music);
-    let builder = EventBuilder::live_status(status, "");
-
-    match client.send_event_builder(builder).await {
-        Ok(_) => {
-            log::info!("Music status cleared");
-        }
-        Err(e) => {
-            log::error!("Failed to clear music status: {}", e);
-        }
+    match user_status::clear_music_status().await {
+        Ok(()) => log::info!("Music status cleared"),
+        Err(e) => log::error!("Failed to clear music status: {}", e),
     }
 }
 
@@ -249,6 +336,8 @@ pub fn play_track(track: MusicTrack, playlist: Option<Vec<MusicTrack>>, index_ov
     state.is_playing = true;
     state.is_visible = true;
     state.current_time = 0.0;
+    state.crossfade = CrossfadePhase::Idle;
+    save_queue_state(&state);
 
     log::info!("Playing track: {}", track.title);
 
@@ -262,6 +351,8 @@ pub fn play_track(track: MusicTrack, playlist: Option<Vec<MusicTrack>>, index_ov
 pub fn toggle_play() {
     let mut state = MUSIC_PLAYER.write();
     state.is_playing = !state.is_playing;
+    // Pausing mid-fade cancels it; it restarts on resume if still due
+    state.crossfade = CrossfadePhase::Idle;
 
     // Clear status when pausing
     if !state.is_playing {
@@ -286,10 +377,13 @@ pub fn next_track() {
         return;
     }
 
-    state.current_index = (state.current_index + 1) % state.playlist.len();
+    state.current_index = upcoming_index(&state);
     state.current_track = state.playlist.get(state.current_index).cloned();
     state.is_playing = true;
     state.current_time = 0.0;
+    // A manual skip cancels any running crossfade
+    state.crossfade = CrossfadePhase::Idle;
+    save_queue_state(&state);
 
     if let Some(track) = state.current_track.clone() {
         log::info!("Next track: {}", track.title);
@@ -309,6 +403,8 @@ pub fn previous_track() {
         return;
     }
 
+    state.crossfade = CrossfadePhase::Idle;
+
     // If more than 3 seconds into the track, restart it
     if state.current_time > 3.0 {
         state.current_time = 0.0;
@@ -330,6 +426,7 @@ pub fn previous_track() {
     state.current_track = state.playlist.get(state.current_index).cloned();
     state.is_playing = true;
     state.current_time = 0.0;
+    save_queue_state(&state);
 
     if let Some(track) = state.current_track.clone() {
         log::info!("Previous track: {}", track.title);
@@ -341,6 +438,155 @@ pub fn previous_track() {
     }
 }
 
+/// Advance after the current track finished playing
+///
+/// Repeat-one is handled by looping the audio element, so this only has to
+/// decide whether to stop at the end of the queue. If a crossfade already
+/// started the next track, the queue moves to it without restarting playback.
+pub fn track_ended() {
+    if let CrossfadePhase::Fading { next_index } = MUSIC_PLAYER.read().crossfade {
+        finish_crossfade(next_index);
+        return;
+    }
+
+    let stop = {
+        let state = MUSIC_PLAYER.read();
+        state.repeat == RepeatMode::Off
+            && !state.shuffle
+            && state.current_index + 1 >= state.playlist.len()
+    };
+
+    if stop {
+        MUSIC_PLAYER.write().is_playing = false;
+        spawn(async move {
+            clear_music_status().await;
+        });
+    } else {
+        next_track();
+    }
+}
+
+/// Index of the track that plays after the current one
+fn upcoming_index(state: &MusicPlayerState) -> usize {
+    if state.shuffle {
+        random_other_index(state.current_index, state.playlist.len())
+    } else {
+        (state.current_index + 1) % state.playlist.len()
+    }
+}
+
+/// Pick a random queue index other than `current` (if there is one)
+fn random_other_index(current: usize, len: usize) -> usize {
+    use rand::Rng;
+    if len <= 1 {
+        return 0;
+    }
+    let offset = rand::thread_rng().gen_range(1..len);
+    (current + offset) % len
+}
+
+/// Length of the fade to start now, if the current track is in its crossfade window
+///
+/// Crossfade is disabled when `crossfade_secs` is 0 and for tracks not longer
+/// than the window. The fade is shortened to the time actually remaining.
+pub fn crossfade_window(crossfade_secs: u8, current_time: f64, duration: f64) -> Option<f64> {
+    let window = f64::from(crossfade_secs);
+    if crossfade_secs == 0 || !duration.is_finite() || duration <= window {
+        return None;
+    }
+
+    let remaining = duration - current_time;
+    (remaining > 0.0 && remaining <= window).then_some(remaining)
+}
+
+/// Start crossfading into the next track if the current one is about to end
+///
+/// Returns the next track's media URL and the fade length in seconds; the
+/// player runs the actual fade. Returns None if no fade should start (already
+/// fading, repeat-one, end of queue, or either track too short).
+pub fn begin_crossfade() -> Option<(String, f64)> {
+    let mut state = MUSIC_PLAYER.write();
+    if !state.is_playing
+        || state.crossfade != CrossfadePhase::Idle
+        || state.repeat == RepeatMode::One
+        || state.playlist.len() < 2
+    {
+        return None;
+    }
+
+    let fade_secs = crossfade_window(state.crossfade_secs, state.current_time, state.duration)?;
+
+    if state.repeat == RepeatMode::Off && !state.shuffle && state.current_index + 1 >= state.playlist.len() {
+        return None;
+    }
+
+    let next_index = upcoming_index(&state);
+    let next = state.playlist.get(next_index)?;
+    if next.duration.is_some_and(|d| d <= u32::from(state.crossfade_secs)) {
+        return None;
+    }
+
+    let media_url = next.media_url.clone();
+    state.crossfade = CrossfadePhase::Fading { next_index };
+    log::debug!("Crossfading into queue index {} over {:.1}s", next_index, fade_secs);
+    Some((media_url, fade_secs))
+}
+
+/// Move the queue to the track that was faded in
+fn finish_crossfade(next_index: usize) {
+    let mut state = MUSIC_PLAYER.write();
+    let Some(track) = state.playlist.get(next_index).cloned() else {
+        state.crossfade = CrossfadePhase::Idle;
+        return;
+    };
+
+    state.current_index = next_index;
+    state.current_track = Some(track.clone());
+    state.is_playing = true;
+    state.current_time = 0.0;
+    state.crossfade = CrossfadePhase::Handoff;
+    save_queue_state(&state);
+
+    log::info!("Crossfaded to: {}", track.title);
+    spawn(async move {
+        publish_music_status(&track).await;
+    });
+}
+
+/// Set crossfade length in seconds (0 disables)
+pub fn set_crossfade_secs(secs: u8) {
+    let clamped = secs.min(MAX_CROSSFADE_SECS);
+    let mut state = MUSIC_PLAYER.write();
+    state.crossfade_secs = clamped;
+    if clamped == 0 {
+        state.crossfade = CrossfadePhase::Idle;
+    }
+    LocalStorage::set(STORAGE_KEY_CROSSFADE, clamped).ok();
+}
+
+/// Get crossfade length in seconds
+pub fn get_crossfade_secs() -> u8 {
+    MUSIC_PLAYER.read().crossfade_secs
+}
+
+/// Cycle repeat mode: all -> one -> off -> all
+pub fn cycle_repeat_mode() {
+    let mut state = MUSIC_PLAYER.write();
+    state.repeat = match state.repeat {
+        RepeatMode::All => RepeatMode::One,
+        RepeatMode::One => RepeatMode::Off,
+        RepeatMode::Off => RepeatMode::All,
+    };
+    save_queue_state(&state);
+}
+
+/// Toggle shuffle
+pub fn toggle_shuffle() {
+    let mut state = MUSIC_PLAYER.write();
+    state.shuffle = !state.shuffle;
+    save_queue_state(&state);
+}
+
 /// Set volume (0.0 - 1.0)
 pub fn set_volume(volume: f64) {
     let clamped = volume.clamp(0.0, 1.0);
@@ -373,6 +619,7 @@ pub fn close_player() {
     let mut state = MUSIC_PLAYER.write();
     state.is_visible = false;
     state.is_playing = false;
+    state.crossfade = CrossfadePhase::Idle;
 
     // Clear NIP-38 music status
     spawn(async move {
@@ -400,6 +647,8 @@ pub fn clear_player() {
     state.is_visible = false;
     state.current_time = 0.0;
     state.duration = 0.0;
+    state.crossfade = CrossfadePhase::Idle;
+    save_queue_state(&state);
 
     // Clear NIP-38 music status
     spawn(async move {
@@ -527,3 +776,92 @@ pub async fn vote_for_music(track: &MusicTrack) -> Result<(), String> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(id: &str) -> MusicTrack {
+        MusicTrack {
+            id: id.to_string(),
+            title: format!("Track {}", id),
+            artist: "Artist".to_string(),
+            album: None,
+            media_url: format!("https://example.com/{}.mp3", id),
+            album_art_url: None,
+            artist_art_url: None,
+            duration: Some(180),
+            artist_id: None,
+            album_id: None,
+            artist_npub: None,
+            source: TrackSource::Wavlake {
+                artist_id: "artist".to_string(),
+                album_id: "album".to_string(),
+            },
+            msat_total: None,
+            created_at: None,
+        }
+    }
+
+    #[test]
+    fn test_queue_round_trip_with_repeat_one() {
+        let playlist = vec![track("a"), track("b"), track("c")];
+        let state = MusicPlayerState {
+            current_track: Some(playlist[1].clone()),
+            playlist: playlist.clone(),
+            current_index: 1,
+            is_playing: true,
+            repeat: RepeatMode::One,
+            shuffle: true,
+            ..Default::default()
+        };
+
+        let json = serde_json::to_string(&PersistedQueue::from_state(&state)).unwrap();
+        let queue: PersistedQueue = serde_json::from_str(&json).unwrap();
+
+        let mut restored = MusicPlayerState::default();
+        assert!(queue.apply_to(&mut restored));
+        assert_eq!(restored.playlist, playlist);
+        assert_eq!(restored.current_index, 1);
+        assert_eq!(restored.current_track, Some(playlist[1].clone()));
+        assert_eq!(restored.repeat, RepeatMode::One);
+        assert!(restored.shuffle);
+        assert!(!restored.is_playing);
+    }
+
+    #[test]
+    fn test_crossfade_window() {
+        // Disabled
+        assert_eq!(crossfade_window(0, 175.0, 180.0), None);
+        // Not yet in the window
+        assert_eq!(crossfade_window(5, 170.0, 180.0), None);
+        // In the window, fade over the remaining time
+        assert_eq!(crossfade_window(5, 176.0, 180.0), Some(4.0));
+        // Track shorter than the window
+        assert_eq!(crossfade_window(5, 1.0, 4.0), None);
+        // Duration not known yet
+        assert_eq!(crossfade_window(5, 0.0, f64::NAN), None);
+    }
+
+    #[test]
+    fn test_unresolvable_tracks_are_skipped() {
+        let playlist = vec![track("a"), track("b"), track("c")];
+        let state = MusicPlayerState {
+            current_track: Some(playlist[1].clone()),
+            playlist: playlist.clone(),
+            current_index: 1,
+            ..Default::default()
+        };
+
+        let mut queue = PersistedQueue::from_state(&state);
+        // The current track no longer deserializes
+        queue.tracks[1] = serde_json::json!({ "id": "b" });
+
+        let mut restored = MusicPlayerState::default();
+        assert!(queue.apply_to(&mut restored));
+        assert_eq!(restored.playlist, vec![track("a"), track("c")]);
+        // Continues with the next surviving track
+        assert_eq!(restored.current_track, Some(track("c")));
+        assert_eq!(restored.current_index, 1);
+    }
+}
@@ -5,6 +5,7 @@
 //!
 //! ## Overview
 //! - Upload files using NIP-96 protocol
+//! - Delete uploaded files
 //! - Authenticate requests using NIP-98 (HTTP Auth)
 //! - Parse response for NIP-94 file metadata
 //!
@@ -18,7 +19,10 @@ use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{FormData, Request, RequestInit, Response};
 
-use crate::stores::nostr_client;
+use crate::stores::{nostr_client, settings_store};
+use crate::utils::image_metadata;
+use crate::utils::nip98::build_nip98_auth;
+use nostr_sdk::nips::nip98::HttpMethod;
 
 // Disambiguate Result type
 type Result<T, E> = std::result::Result<T, E>;
@@ -144,17 +148,21 @@ pub async fn upload_to_nip96(
     // Reset progress
     *NIP96_UPLOAD_PROGRESS.write() = Some(0.0);
 
-    // Get signer for NIP-98 authentication
-    let signer = match nostr_client::get_signer() {
-        Some(s) => s,
-        None => {
-            *NIP96_UPLOAD_PROGRESS.write() = None;
-            return Err("Not authenticated. Please sign in to upload files.".to_string());
-        }
-    };
+    // NIP-98 authentication needs a signer
+    if nostr_client::get_signer().is_none() {
+        *NIP96_UPLOAD_PROGRESS.write() = None;
+        return Err("Not authenticated. Please sign in to upload files.".to_string());
+    }
 
     *NIP96_UPLOAD_PROGRESS.write() = Some(10.0);
 
+    // Strip EXIF/GPS metadata before hashing, so the hash matches what's uploaded
+    let file_data = if settings_store::SETTINGS.read().strip_image_metadata {
+        image_metadata::strip_for_upload(file_data, &mime_type)
+    } else {
+        file_data
+    };
+
     // Calculate SHA-256 hash of file
     use sha2::{Sha256, Digest};
     let mut hasher = Sha256::new();
@@ -166,7 +174,7 @@ pub async fn upload_to_nip96(
     *NIP96_UPLOAD_PROGRESS.write() = Some(20.0);
 
     // Create NIP-98 authorization header
-    let authorization = match create_nip98_auth(&signer, NOSTR_BUILD_API_URL, &file_hash_hex).await {
+    let authorization = match build_nip98_auth(HttpMethod::POST, NOSTR_BUILD_API_URL, None).await {
         Ok(auth) => auth,
         Err(e) => {
             *NIP96_UPLOAD_PROGRESS.write() = None;
@@ -212,38 +220,43 @@ pub async fn upload_to_nip96(
     Ok(metadata)
 }
 
-/// Create NIP-98 authorization header
-async fn create_nip98_auth(
-    signer: &crate::stores::signer::SignerType,
-    api_url: &str,
-    _file_hash: &str,
-) -> Result<String, String> {
-    use nostr_sdk::prelude::*;
-
-    let url = Url::parse(api_url).map_err(|e| format!("Invalid URL: {}", e))?;
-
-    // Create HTTP data for NIP-98
-    // Note: payload hash is optional per NIP-98 spec
-    let http_data = nip98::HttpData::new(url, nip98::HttpMethod::POST);
-
-    // Generate authorization header based on signer type
-    let authorization = match signer {
-        crate::stores::signer::SignerType::Keys(keys) => {
-            http_data.to_authorization(keys).await
-                .map_err(|e| format!("Failed to create NIP-98 auth: {}", e))?
-        }
-        #[cfg(target_family = "wasm")]
-        crate::stores::signer::SignerType::BrowserExtension(browser_signer) => {
-            http_data.to_authorization(browser_signer.as_ref()).await
-                .map_err(|e| format!("Failed to create NIP-98 auth: {}", e))?
-        }
-        crate::stores::signer::SignerType::NostrConnect(nostr_connect) => {
-            http_data.to_authorization(nostr_connect.as_ref()).await
-                .map_err(|e| format!("Failed to create NIP-98 auth: {}", e))?
-        }
-    };
+/// Delete a file from a NIP-96 server
+///
+/// # Arguments
+/// * `server` - The server's upload API URL (e.g. `NOSTR_BUILD_API_URL`)
+/// * `file_hash` - SHA-256 of the original file (the `ox` tag)
+///
+/// A file the server no longer has counts as deleted.
+pub async fn delete_file(server: &str, file_hash: &str) -> Result<(), String> {
+    if nostr_client::get_signer().is_none() {
+        return Err("Not authenticated. Please sign in to delete files.".to_string());
+    }
+
+    let delete_url = format!("{}/{}", server.trim_end_matches('/'), file_hash);
+    let authorization = build_nip98_auth(HttpMethod::DELETE, &delete_url, None).await?;
+
+    log::info!("Deleting NIP-96 file {}", delete_url);
 
-    Ok(authorization)
+    let response = gloo_net::http::Request::delete(&delete_url)
+        .header("Authorization", &authorization)
+        .send()
+        .await
+        .map_err(|e| format!("Delete request failed: {}", e))?;
+
+    delete_status_result(response.status())
+}
+
+/// Interpret the HTTP status of a NIP-96 delete
+fn delete_status_result(status: u16) -> Result<(), String> {
+    match status {
+        200..=299 => Ok(()),
+        404 => {
+            log::info!("File already gone from the server");
+            Ok(())
+        }
+        401 | 403 => Err("Not authorized to delete this file. Only the account that uploaded it can delete it.".to_string()),
+        _ => Err(format!("Delete failed: HTTP {}", status)),
+    }
 }
 
 /// Upload file using web_sys Fetch API with FormData (for WASM compatibility)
@@ -370,3 +383,17 @@ pub fn get_upload_servers() -> Vec<(String, String)> {
 
     servers
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_delete_status_result() {
+        assert!(delete_status_result(200).is_ok());
+        assert!(delete_status_result(204).is_ok());
+        // Already gone
+        assert!(delete_status_result(404).is_ok());
+        assert!(delete_status_result(403).unwrap_err().contains("Not authorized"));
+        assert!(delete_status_result(500).is_err());
+    }
+}
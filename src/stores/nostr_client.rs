@@ -4,6 +4,7 @@ use nostr_sdk::Client;
 use nostr_sdk::prelude::*;
 use nostr::Url;
 use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
 use std::sync::{OnceLock, Mutex};
 use std::time::Duration;
 
@@ -11,8 +12,9 @@ use std::time::Duration;
 use nostr_indexeddb::WebDatabase;
 
 use crate::stores::signer::SignerType;
-use crate::stores::relay_metadata;
+use crate::stores::{relay_auth, relay_metadata};
 use crate::utils::mention_extractor::{extract_mentioned_pubkeys, create_mention_tags};
+use crate::utils::file_metadata::UploadedMedia;
 
 #[cfg(target_arch = "wasm32")]
 use crate::services::admission_policy::NostrBlueAdmissionPolicy;
@@ -29,6 +31,20 @@ pub static HAS_SIGNER: GlobalSignal<bool> = Signal::global(|| false);
 /// The current signer type (if any)
 pub static CURRENT_SIGNER: GlobalSignal<Option<SignerType>> = Signal::global(|| None);
 
+/// Our own events we've asked relays to delete (NIP-09), hidden from feeds right away
+pub static DELETED_EVENTS: GlobalSignal<HashSet<String>> = Signal::global(HashSet::new);
+
+/// Muted threads and posts: the `e` tags of our mute list (NIP-51), as of the last fetch
+pub static MUTED_THREADS: GlobalSignal<HashSet<String>> = Signal::global(HashSet::new);
+
+/// Bounds and default for the user-configurable relay fetch timeout (seconds)
+pub const MIN_FETCH_TIMEOUT_SECS: u64 = 5;
+pub const MAX_FETCH_TIMEOUT_SECS: u64 = 30;
+pub const DEFAULT_FETCH_TIMEOUT_SECS: u64 = 10;
+
+/// Relay fetch timeout in seconds, configurable in Settings
+pub static DEFAULT_FETCH_TIMEOUT: GlobalSignal<u64> = Signal::global(|| DEFAULT_FETCH_TIMEOUT_SECS);
+
 /// Contacts cache for faster feed loading (5-minute TTL)
 struct CachedContacts {
     pubkey: String,
@@ -108,6 +124,355 @@ pub struct RelayPoolStore {
 
 pub static RELAY_POOL: GlobalSignal<Store<RelayPoolStore>> = Signal::global(|| Store::new(RelayPoolStore::default()));
 
+/// Weight of the newest sample in a relay's rolling round-trip average
+const RTT_SMOOTHING: f64 = 0.2;
+
+/// Rolling (exponentially weighted) average of a relay's fetch round-trips
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RelayLatency {
+    pub avg_ms: Option<f64>,
+    pub samples: u32,
+}
+
+impl RelayLatency {
+    /// Fold a new round-trip sample into the average
+    pub fn record(&mut self, sample_ms: f64) {
+        self.avg_ms = Some(match self.avg_ms {
+            Some(avg) => avg + RTT_SMOOTHING * (sample_ms - avg),
+            None => sample_ms,
+        });
+        self.samples += 1;
+    }
+}
+
+/// Delay before the first reconnect attempt
+const RECONNECT_BASE_DELAY_SECS: u64 = 2;
+
+/// Longest delay between reconnect attempts
+const RECONNECT_MAX_DELAY_SECS: u64 = 300;
+
+/// Random spread applied to each delay (±20%) so relays don't all retry at once
+const RECONNECT_JITTER: f64 = 0.2;
+
+/// How often dropped relays are checked for a due reconnect
+const RECONNECT_CHECK_INTERVAL_MS: u32 = 2_000;
+
+/// Timeout of a single reconnect attempt
+const RECONNECT_ATTEMPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delay before the next reconnect after `failed_attempts` failures in a row
+///
+/// Doubles from 2 seconds, capped at 5 minutes.
+pub fn reconnect_delay_secs(failed_attempts: u32) -> u64 {
+    2u64.checked_pow(failed_attempts)
+        .and_then(|factor| factor.checked_mul(RECONNECT_BASE_DELAY_SECS))
+        .map_or(RECONNECT_MAX_DELAY_SECS, |delay| delay.min(RECONNECT_MAX_DELAY_SECS))
+}
+
+/// Spread `delay_secs` by up to ±20%; `random` is in [0, 1)
+fn with_jitter(delay_secs: u64, random: f64) -> u64 {
+    let factor = 1.0 - RECONNECT_JITTER + 2.0 * RECONNECT_JITTER * random.clamp(0.0, 1.0);
+    ((delay_secs as f64 * factor).round() as u64).max(1)
+}
+
+/// Reconnect schedule of a relay that dropped
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReconnectBackoff {
+    /// Failed reconnect attempts in a row
+    pub failed_attempts: u32,
+    /// Unix seconds of the next attempt; None while connected
+    pub next_attempt_at: Option<u64>,
+}
+
+impl ReconnectBackoff {
+    /// Plan the next attempt from the current failure count
+    pub fn schedule(&mut self, now: u64, random: f64) {
+        self.next_attempt_at = Some(now + with_jitter(reconnect_delay_secs(self.failed_attempts), random));
+    }
+
+    /// An attempt failed: back off further
+    pub fn record_failure(&mut self, now: u64, random: f64) {
+        self.failed_attempts = self.failed_attempts.saturating_add(1);
+        self.schedule(now, random);
+    }
+
+    /// The relay is connected again
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn is_due(&self, now: u64) -> bool {
+        self.next_attempt_at.is_some_and(|at| now >= at)
+    }
+}
+
+/// What we've observed from a relay so far
+#[derive(Clone, Debug, Default)]
+struct RelayActivity {
+    /// Unix seconds of the last message received
+    last_message_at: Option<u64>,
+    latency: RelayLatency,
+    backoff: ReconnectBackoff,
+}
+
+static RELAY_ACTIVITY: OnceLock<Mutex<HashMap<String, RelayActivity>>> = OnceLock::new();
+
+fn with_relay_activity<R>(f: impl FnOnce(&mut HashMap<String, RelayActivity>) -> R) -> R {
+    let mut activity = RELAY_ACTIVITY
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&mut activity)
+}
+
+/// Connection state and responsiveness of one relay
+#[derive(Clone, Debug, PartialEq)]
+pub struct RelayHealth {
+    pub url: String,
+    pub status: RelayStatus,
+    /// Unix seconds of the last message received, if any
+    pub last_message_at: Option<u64>,
+    /// Rolling average fetch round-trip in milliseconds, if measured yet
+    pub avg_rtt_ms: Option<f64>,
+    /// Failed reconnect attempts since the relay dropped
+    pub reconnect_attempts: u32,
+    /// Unix seconds of the next reconnect attempt, while disconnected
+    pub next_reconnect_at: Option<u64>,
+}
+
+impl RelayHealth {
+    pub fn is_connected(&self) -> bool {
+        self.status == RelayStatus::Connected
+    }
+}
+
+fn relay_status_from_pool(status: nostr_relay_pool::RelayStatus) -> RelayStatus {
+    use nostr_relay_pool::RelayStatus as PoolRelayStatus;
+
+    match status {
+        PoolRelayStatus::Connected => RelayStatus::Connected,
+        PoolRelayStatus::Initialized | PoolRelayStatus::Pending | PoolRelayStatus::Connecting => {
+            RelayStatus::Connecting
+        }
+        PoolRelayStatus::Banned => RelayStatus::Error("Banned".to_string()),
+        _ => RelayStatus::Disconnected,
+    }
+}
+
+/// Health of every relay in the pool, disconnected relays first
+pub async fn get_relay_health() -> Vec<RelayHealth> {
+    let Some(client) = get_client() else {
+        return Vec::new();
+    };
+
+    let relays = client.relays().await;
+    let mut health: Vec<RelayHealth> = with_relay_activity(|activity| {
+        relays
+            .iter()
+            .map(|(url, relay)| {
+                let url = url.to_string();
+                let observed = activity.get(&url).cloned().unwrap_or_default();
+                RelayHealth {
+                    status: relay_status_from_pool(relay.status()),
+                    last_message_at: observed.last_message_at,
+                    avg_rtt_ms: observed.latency.avg_ms,
+                    reconnect_attempts: observed.backoff.failed_attempts,
+                    next_reconnect_at: observed.backoff.next_attempt_at,
+                    url,
+                }
+            })
+            .collect()
+    });
+
+    health.sort_by(|a, b| a.is_connected().cmp(&b.is_connected()).then_with(|| a.url.cmp(&b.url)));
+    health
+}
+
+/// Record the time of every relay message for `get_relay_health`
+///
+/// Also answers NIP-42 AUTH challenges (see `relay_auth`).
+fn start_relay_activity_monitor(client: Arc<Client>) {
+    dioxus_core::spawn_forever(async move {
+        let mut notifications = client.notifications();
+        loop {
+            match notifications.recv().await {
+                Ok(RelayPoolNotification::Message { relay_url, message }) => {
+                    let now = Timestamp::now().as_secs();
+                    with_relay_activity(|activity| {
+                        activity.entry(relay_url.to_string()).or_default().last_message_at = Some(now);
+                    });
+
+                    match message {
+                        RelayMessage::Auth { challenge } => {
+                            let client = client.clone();
+                            let challenge = challenge.to_string();
+                            spawn(async move {
+                                relay_auth::handle_auth_challenge(&client, relay_url, challenge).await;
+                            });
+                        }
+                        RelayMessage::Ok { status: false, message, .. } | RelayMessage::Closed { message, .. } => {
+                            relay_auth::note_auth_rejection(&relay_url, &message);
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(_) => {}
+                // Missed some messages; the next ones still update the timestamps
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {}
+                Err(_) => break,
+            }
+        }
+    });
+}
+
+/// Reconnect dropped relays with exponential backoff
+///
+/// The pool's own reconnect is disabled so a flaky relay isn't retried
+/// every few seconds. Each relay keeps its own schedule, which resets as
+/// soon as it is connected again.
+fn start_relay_reconnector(client: Arc<Client>) {
+    use nostr_relay_pool::RelayStatus as PoolRelayStatus;
+
+    dioxus_core::spawn_forever(async move {
+        loop {
+            gloo_timers::future::TimeoutFuture::new(RECONNECT_CHECK_INTERVAL_MS).await;
+
+            let now = Timestamp::now().as_secs();
+            for (url, relay) in client.relays().await {
+                let key = url.to_string();
+                let due = with_relay_activity(|activity| {
+                    let backoff = &mut activity.entry(key.clone()).or_default().backoff;
+                    match relay.status() {
+                        PoolRelayStatus::Connected => {
+                            if backoff.failed_attempts > 0 {
+                                log::info!("Relay {} recovered after {} failed attempts", key, backoff.failed_attempts);
+                            }
+                            backoff.reset();
+                            false
+                        }
+                        PoolRelayStatus::Disconnected | PoolRelayStatus::Terminated => {
+                            if backoff.next_attempt_at.is_none() {
+                                backoff.schedule(now, rand::random());
+                            }
+                            backoff.is_due(now)
+                        }
+                        // Connecting, banned or deliberately idle
+                        _ => false,
+                    }
+                });
+                if !due {
+                    continue;
+                }
+
+                let result = relay.try_connect(RECONNECT_ATTEMPT_TIMEOUT).await;
+                let now = Timestamp::now().as_secs();
+                with_relay_activity(|activity| {
+                    let backoff = &mut activity.entry(key.clone()).or_default().backoff;
+                    match &result {
+                        Ok(()) => backoff.reset(),
+                        Err(e) => {
+                            backoff.record_failure(now, rand::random());
+                            log::debug!(
+                                "Reconnect to {} failed ({}), attempt {}, next in {}s",
+                                key,
+                                e,
+                                backoff.failed_attempts,
+                                backoff.next_attempt_at.unwrap_or(now).saturating_sub(now)
+                            );
+                        }
+                    }
+                });
+            }
+        }
+    });
+}
+
+/// `client.fetch_events`, timing each relay's answer
+///
+/// The REQ goes out under a subscription ID of our own, so a relay's
+/// round-trip is the time until it ends the stored events of this fetch,
+/// not of another one running at the same time. Events are collected from
+/// the pool's message stream; if that stream lagged and may have dropped
+/// some, the fetch is repeated with `client.fetch_events`.
+async fn fetch_events_timed(
+    client: &Client,
+    filter: Filter,
+    timeout: Duration,
+) -> std::result::Result<Vec<nostr::Event>, String> {
+    use futures::future::{select, Either};
+    use tokio::sync::broadcast::error::RecvError;
+
+    let mut notifications = client.notifications();
+    let sub_id = SubscriptionId::generate();
+    let opts = SubscribeAutoCloseOptions::default()
+        .exit_policy(ReqExitPolicy::ExitOnEOSE)
+        .timeout(Some(timeout));
+    let started = instant::Instant::now();
+    let output = client
+        .subscribe_with_id(sub_id.clone(), filter.clone(), Some(opts))
+        .await
+        .map_err(|e| e.to_string())?;
+    if output.success.is_empty() && !output.failed.is_empty() {
+        return Err(format!("No relay accepted the request: {:?}", output.failed));
+    }
+
+    let mut waiting: HashSet<RelayUrl> = output.success;
+    let mut events: HashMap<EventId, nostr::Event> = HashMap::new();
+    let mut lagged = false;
+    let mut deadline = Box::pin(gloo_timers::future::TimeoutFuture::new(timeout.as_millis() as u32));
+
+    while !waiting.is_empty() {
+        let next = Box::pin(notifications.recv());
+        match select(next, deadline).await {
+            Either::Left((notification, pending)) => {
+                deadline = pending;
+                match notification {
+                    Ok(RelayPoolNotification::Message { relay_url, message }) => match message {
+                        RelayMessage::Event { subscription_id, event } if *subscription_id == sub_id => {
+                            events.entry(event.id).or_insert_with(|| event.into_owned());
+                        }
+                        RelayMessage::EndOfStoredEvents(subscription_id) if *subscription_id == sub_id => {
+                            if waiting.remove(&relay_url) {
+                                let elapsed_ms = started.elapsed().as_secs_f64() * 1000.0;
+                                with_relay_activity(|activity| {
+                                    activity.entry(relay_url.to_string()).or_default().latency.record(elapsed_ms);
+                                });
+                            }
+                        }
+                        RelayMessage::Closed { subscription_id, .. } if *subscription_id == sub_id => {
+                            waiting.remove(&relay_url);
+                        }
+                        _ => {}
+                    },
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(_)) => lagged = true,
+                    // No more notifications
+                    Err(_) => {
+                        lagged = true;
+                        break;
+                    }
+                }
+            }
+            // Relays that haven't answered by now are left out, as with fetch_events
+            Either::Right(_) => break,
+        }
+    }
+    client.unsubscribe(&sub_id).await;
+
+    if lagged {
+        log::debug!("Notification stream lagged during a timed fetch, fetching again");
+        return client
+            .fetch_events(filter, timeout)
+            .await
+            .map(|events| events.into_iter().collect())
+            .map_err(|e| e.to_string());
+    }
+
+    let mut events: Vec<nostr::Event> = events.into_values().collect();
+    events.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(events)
+}
+
 /// Default relays to connect to
 const DEFAULT_RELAYS: &[&str] = &[
     "wss://relay.damus.io",
@@ -129,12 +494,11 @@ pub async fn initialize_client() -> std::result::Result<Arc<Client>, String> {
         .verify_subscriptions(true)
         // Ban relays that send mismatched events
         .ban_relay_on_mismatch(true)
-        // Adjust retry interval based on success rate
-        .adjust_retry_interval(true)
-        // Initial retry interval: 10 seconds
-        .retry_interval(Duration::from_secs(10))
-        // Enable automatic reconnection
-        .reconnect(true);
+        // Reconnects are handled by `start_relay_reconnector`, with backoff
+        .reconnect(false);
+
+    // AUTH challenges are answered by `relay_auth`, only for relays the user allowed
+    let client_opts = ClientOptions::new().automatic_authentication(false);
 
     // Create client with database
     #[cfg(target_arch = "wasm32")]
@@ -156,11 +520,12 @@ pub async fn initialize_client() -> std::result::Result<Arc<Client>, String> {
             .database(database)
             .gossip(gossip)
             .admit_policy(NostrBlueAdmissionPolicy::default())
+            .opts(client_opts)
             .build()
     };
 
     #[cfg(not(target_arch = "wasm32"))]
-    let client = Client::builder().build();
+    let client = Client::builder().opts(client_opts).build();
 
     let client = Arc::new(client);
 
@@ -194,6 +559,9 @@ pub async fn initialize_client() -> std::result::Result<Arc<Client>, String> {
     *NOSTR_CLIENT.write() = Some(client.clone());
     *CLIENT_INITIALIZED.write() = true;
 
+    start_relay_activity_monitor(client.clone());
+    start_relay_reconnector(client.clone());
+
     // Connect to relays in background - spawn the future so it gets polled to completion
     // In WASM, simply dropping the Future won't reliably execute it
     log::debug!("Spawning background relay connections...");
@@ -272,7 +640,11 @@ async fn apply_relay_lists_to_client(client: Arc<Client>) -> std::result::Result
         .clone()
         .ok_or("No relay metadata available")?;
 
-    log::info!("Applying {} relays from kind 10002 to client", metadata.relays.len());
+    log::info!(
+        "Applying {} relays from kind 10002 to client ({} write relays)",
+        metadata.relays.len(),
+        relay_metadata::write_relays(&metadata.relays).len()
+    );
 
     // Add user's configured relays with read/write flags
     for relay in &metadata.relays {
@@ -336,6 +708,16 @@ pub async fn set_read_only() -> std::result::Result<(), String> {
     Ok(())
 }
 
+/// Delete every event cached in the local event database
+pub async fn wipe_database() -> std::result::Result<(), String> {
+    let client = get_client().ok_or("Client not initialized")?;
+    client
+        .database()
+        .wipe()
+        .await
+        .map_err(|e| format!("Failed to wipe event database: {}", e))
+}
+
 /// Add a custom relay
 #[allow(dead_code)]
 pub async fn add_relay(relay_url: &str) -> std::result::Result<(), String> {
@@ -377,6 +759,101 @@ pub async fn remove_relay(relay_url: &str) -> std::result::Result<(), String> {
     Ok(())
 }
 
+/// Holders of a relay borrowed into the pool for an operation
+struct TempRelayHold {
+    holders: usize,
+    /// Whether we added it; relays already in the pool are never removed
+    added: bool,
+}
+
+/// Relays borrowed into the pool by ongoing operations, keyed without a trailing slash
+static TEMP_RELAYS: OnceLock<Mutex<HashMap<String, TempRelayHold>>> = OnceLock::new();
+
+fn temp_relays() -> &'static Mutex<HashMap<String, TempRelayHold>> {
+    TEMP_RELAYS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn temp_relay_key(url: &str) -> String {
+    url.trim().trim_end_matches('/').to_string()
+}
+
+/// Relays added to the pool for the duration of one operation
+///
+/// Concurrent operations may borrow the same relay, so holders are counted:
+/// a relay is added only when it wasn't in the pool, and removed when the
+/// last holder calls [`TempRelays::release`].
+pub struct TempRelays {
+    client: Arc<Client>,
+    relays: Vec<String>,
+}
+
+/// Borrow `relays` into the pool, connecting any that weren't in it yet
+pub async fn add_temp_relays(client: &Arc<Client>, relays: &[String]) -> TempRelays {
+    let mut held = Vec::with_capacity(relays.len());
+    let mut connect = false;
+
+    for relay_url in relays {
+        let key = temp_relay_key(relay_url);
+        let first_holder = {
+            let mut holds = temp_relays().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            let hold = holds.entry(key.clone()).or_insert(TempRelayHold { holders: 0, added: false });
+            hold.holders += 1;
+            hold.holders == 1
+        };
+        held.push(key.clone());
+
+        // add_relay returns Ok(true) only when the relay wasn't in the pool yet
+        if first_holder && matches!(client.add_relay(relay_url.as_str()).await, Ok(true)) {
+            if let Some(hold) = temp_relays()
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .get_mut(&key)
+            {
+                hold.added = true;
+            }
+            connect = true;
+        }
+    }
+
+    if connect {
+        client.connect().await;
+    }
+    TempRelays { client: client.clone(), relays: held }
+}
+
+impl TempRelays {
+    /// Give the relays back, removing those no other operation holds
+    pub async fn release(self) {
+        for key in self.relays {
+            let remove = {
+                let mut holds = temp_relays().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                match holds.get_mut(&key) {
+                    Some(hold) if hold.holders > 1 => {
+                        hold.holders -= 1;
+                        false
+                    }
+                    Some(_) => holds.remove(&key).is_some_and(|hold| hold.added),
+                    None => false,
+                }
+            };
+            if remove {
+                if let Err(e) = self.client.remove_relay(key.as_str()).await {
+                    log::debug!("Could not remove temporary relay {}: {}", key, e);
+                }
+            }
+        }
+    }
+}
+
+/// Whether a relay is only in the pool while an operation borrows it
+pub fn is_temp_relay(url: &str) -> bool {
+    temp_relays()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&temp_relay_key(url))
+        .is_some_and(|hold| hold.added)
+}
+
 /// Disconnect from all relays
 #[allow(dead_code)]
 pub async fn disconnect() {
@@ -395,6 +872,33 @@ pub async fn reconnect() {
     }
 }
 
+/// Clamp a fetch timeout to the supported 5-30 second range
+pub fn clamp_fetch_timeout_secs(secs: u64) -> u64 {
+    secs.clamp(MIN_FETCH_TIMEOUT_SECS, MAX_FETCH_TIMEOUT_SECS)
+}
+
+/// Set the default relay fetch timeout (clamped to the supported range)
+pub fn set_default_fetch_timeout(secs: u64) {
+    *DEFAULT_FETCH_TIMEOUT.write() = clamp_fetch_timeout_secs(secs);
+}
+
+/// Get the user's default relay fetch timeout
+pub fn default_fetch_timeout() -> Duration {
+    Duration::from_secs(*DEFAULT_FETCH_TIMEOUT.read())
+}
+
+/// Fetch events from relays with an explicit timeout
+///
+/// Pass `default_fetch_timeout()` to honor the user's setting.
+pub async fn fetch_events_with_timeout(
+    filter: Filter,
+    timeout: Duration,
+) -> std::result::Result<Vec<nostr::Event>, String> {
+    let client = get_client().ok_or("Client not initialized")?;
+
+    fetch_events_timed(&client, filter, timeout).await
+}
+
 /// Fetch events using aggregated pattern: database first, then relays
 ///
 /// This function:
@@ -437,11 +941,7 @@ pub async fn fetch_events_aggregated(
     // Wait for at least one relay to be ready (non-blocking connect() may not have finished)
     ensure_relays_ready(&client).await;
 
-    client
-        .fetch_events(filter, timeout)
-        .await
-        .map(|events| events.into_iter().collect())
-        .map_err(|e| e.to_string())
+    fetch_events_timed(&client, filter, timeout).await
 }
 
 /// Fetch events using gossip (automatic relay routing)
@@ -482,10 +982,33 @@ pub async fn fetch_profile_events_db(
     }
 }
 
+/// Fetch a single author's events from their outbox relays merged with ours
+///
+/// Relays not yet in the pool are borrowed for the fetch.
+async fn fetch_author_events_outbox(
+    client: &Arc<Client>,
+    author: PublicKey,
+    filter: Filter,
+    timeout: Duration,
+) -> std::result::Result<Vec<nostr::Event>, String> {
+    let relays = relay_metadata::resolve_author_relays(author, client.clone()).await;
+
+    let temp_relays = add_temp_relays(client, &relays).await;
+    let result = client
+        .fetch_events_from(relays, filter, timeout)
+        .await
+        .map(|events| events.into_iter().collect())
+        .map_err(|e| e.to_string());
+    temp_relays.release().await;
+
+    result
+}
+
 /// Fetch events from relays only (for background refresh)
 ///
 /// This is Phase 2 of profile loading - fetches fresh data from relays.
-/// Uses gossip/outbox routing for efficient relay selection.
+/// Filters for a single author are sent to that author's NIP-65 write relays
+/// as well as ours; anything else uses gossip routing.
 pub async fn fetch_profile_events_from_relays(
     filter: Filter,
     timeout: Duration,
@@ -495,12 +1018,27 @@ pub async fn fetch_profile_events_from_relays(
     // Ensure relays are ready
     ensure_relays_ready(&client).await;
 
-    // Use gossip for automatic relay routing (NIP-65 outbox)
-    match client.fetch_events(filter, timeout).await {
+    let single_author = filter
+        .authors
+        .as_ref()
+        .filter(|authors| authors.len() == 1)
+        .and_then(|authors| authors.iter().next().copied());
+
+    let result = match single_author {
+        Some(author) => fetch_author_events_outbox(&client, author, filter, timeout).await,
+        // Use gossip for automatic relay routing
+        None => client
+            .fetch_events(filter, timeout)
+            .await
+            .map(|events| events.into_iter().collect())
+            .map_err(|e| e.to_string()),
+    };
+
+    match result {
         Ok(events) => {
             let count = events.len();
             log::info!("Profile relays: fetched {} events", count);
-            Ok(events.into_iter().collect())
+            Ok(events)
         }
         Err(e) => {
             log::warn!("Profile relay fetch failed: {}", e);
@@ -581,8 +1119,100 @@ pub async fn publish_note(content: String, tags: Vec<Vec<String>>) -> std::resul
 
     log::info!("Publishing note with {} characters", content.len());
 
+    let event = client.sign_event_builder(build_text_note(&content, tags)).await
+        .map_err(|e| format!("Failed to sign note: {}", e))?;
+
+    // Publish using gossip - automatic relay routing
+    let output = client.send_event(&event).await
+        .map_err(|e| format!("Failed to publish: {}", e))?;
+
+    if crate::utils::event::is_protected(&event) {
+        let auth_relays = relay_auth::auth_required_relays(&output.failed);
+        if !auth_relays.is_empty() {
+            relay_auth::retry_after_auth(&client, &event, auth_relays).await;
+        }
+    }
+
+    let event_id = event.id.to_hex();
+    log::info!("Note published successfully: {}", event_id);
+    Ok(event_id)
+}
+
+/// Publish a text note to the given relays only ("post to" in the composer)
+pub async fn publish_note_to(content: String, tags: Vec<Vec<String>>, relays: Vec<String>) -> std::result::Result<String, String> {
+    let client = get_client().ok_or("Client not initialized")?;
+
+    if !*HAS_SIGNER.read() {
+        return Err("No signer attached. Cannot publish events.".to_string());
+    }
+
+    let relays = relay_metadata::resolve_publish_relays(&relays);
+    if relays.is_empty() {
+        return Err("No relays selected".to_string());
+    }
+
+    log::info!("Publishing note with {} characters to {} relays", content.len(), relays.len());
+
+    let event = client.sign_event_builder(build_text_note(&content, tags)).await
+        .map_err(|e| format!("Failed to sign note: {}", e))?;
+
+    // Keep a local copy so the note shows in our own feeds even when only
+    // relays we don't read from accept it
+    if let Err(e) = client.database().save_event(&event).await {
+        log::warn!("Failed to save note locally: {}", e);
+    }
+
+    let temp_relays = add_temp_relays(&client, &relays).await;
+    let mut result = client.send_event_to(relays.clone(), &event).await;
+
+    // Protected (NIP-70) notes are refused until we AUTH; retry those relays
+    if let Ok(output) = result.as_mut() {
+        if crate::utils::event::is_protected(&event) {
+            let auth_relays = relay_auth::auth_required_relays(&output.failed);
+            if !auth_relays.is_empty() {
+                for relay_url in relay_auth::retry_after_auth(&client, &event, auth_relays).await {
+                    output.failed.remove(&relay_url);
+                    output.success.insert(relay_url);
+                }
+            }
+        }
+    }
+
+    temp_relays.release().await;
+
+    let output = result.map_err(|e| format!("Failed to publish: {}", e))?;
+    if output.success.is_empty() {
+        return Err(format!("None of the {} selected relays accepted the note", relays.len()));
+    }
+
+    let event_id = event.id.to_hex();
+    log::info!("Note published to {} of {} relays: {}", output.success.len(), relays.len(), event_id);
+    Ok(event_id)
+}
+
+/// Sign a text note dated `publish_at` without publishing it
+///
+/// Used for scheduled notes: the signed event is queued and sent once the
+/// time arrives, so it carries its publication time rather than the time it
+/// was written.
+pub async fn sign_scheduled_note(content: String, publish_at: u64) -> std::result::Result<nostr::Event, String> {
+    let client = get_client().ok_or("Client not initialized")?;
+
+    if !*HAS_SIGNER.read() {
+        return Err("No signer attached. Cannot sign events.".to_string());
+    }
+
+    let builder = build_text_note(&content, Vec::new())
+        .custom_created_at(Timestamp::from(publish_at));
+
+    client.sign_event_builder(builder).await
+        .map_err(|e| format!("Failed to sign scheduled note: {}", e))
+}
+
+/// Build a kind 1 note with mention, quote and extra tags
+fn build_text_note(content: &str, tags: Vec<Vec<String>>) -> nostr::EventBuilder {
     // Extract mentions from content and create p tags
-    let mentioned_pubkeys = extract_mentioned_pubkeys(&content);
+    let mentioned_pubkeys = extract_mentioned_pubkeys(content);
     let mut mention_tags = create_mention_tags(&mentioned_pubkeys);
     log::debug!("Extracted {} mentions from content", mentioned_pubkeys.len());
 
@@ -619,12 +1249,17 @@ pub async fn publish_note(content: String, tags: Vec<Vec<String>>) -> std::resul
                             None
                         };
 
+                        // Optional author pubkey (5th element)
+                        let public_key = tag_vec
+                            .get(4)
+                            .and_then(|pk| nostr::PublicKey::from_hex(pk).ok());
+
                         // Construct event tag with marker
                         let tag_standard = nostr::TagStandard::Event {
                             event_id,
                             relay_url,
                             marker: Some(m),
-                            public_key: None,
+                            public_key,
                             uppercase: false,
                         };
 
@@ -634,6 +1269,8 @@ pub async fn publish_note(content: String, tags: Vec<Vec<String>>) -> std::resul
                         Some(Tag::event(event_id))
                     }
                 },
+                // NIP-70: only the author may publish this note
+                "-" => Some(Tag::protected()),
                 "e" if tag_vec.len() >= 2 => {
                     // Simple e-tag without marker
                     Some(Tag::event(
@@ -644,7 +1281,17 @@ pub async fn publish_note(content: String, tags: Vec<Vec<String>>) -> std::resul
                     // Extract pubkey for Outbox routing (currently unused but prepared for future)
                     if let Ok(pubkey) = nostr::PublicKey::from_hex(&tag_vec[1]) {
                         _tagged_pubkeys.push(pubkey);
-                        Some(Tag::public_key(pubkey))
+                        // Keep the relay hint when there is one
+                        let relay_url = tag_vec
+                            .get(2)
+                            .filter(|url| !url.is_empty())
+                            .and_then(|url| nostr_sdk::RelayUrl::parse(url).ok());
+                        Some(Tag::from(nostr::TagStandard::PublicKey {
+                            public_key: pubkey,
+                            relay_url,
+                            alias: None,
+                            uppercase: false,
+                        }))
                     } else {
                         None
                     }
@@ -664,19 +1311,99 @@ pub async fn publish_note(content: String, tags: Vec<Vec<String>>) -> std::resul
     mention_tags.extend(nostr_tags);
 
     // Extract and add quote tags (NIP-18 compliance)
-    let quote_tags = extract_quote_tags(&content);
+    let quote_tags = extract_quote_tags(content);
     mention_tags.extend(quote_tags);
 
-    // Build the event
-    let builder = nostr::EventBuilder::text_note(&content).tags(mention_tags);
+    nostr::EventBuilder::text_note(content).tags(mention_tags)
+}
 
-    // Publish using gossip - automatic relay routing
-    let output = client.send_event_builder(builder).await
-        .map_err(|e| format!("Failed to publish: {}", e))?;
+/// Relays `event_id` was seen on, lowest URL first
+pub async fn event_seen_relays(event_id: &nostr::EventId) -> Vec<String> {
+    let Some(client) = get_client() else {
+        return Vec::new();
+    };
+    let mut relays: Vec<String> = client
+        .database()
+        .event_seen_on_relays(event_id)
+        .await
+        .ok()
+        .flatten()
+        .map(|relays| relays.into_iter().map(|url| url.to_string()).collect())
+        .unwrap_or_default();
+    relays.sort();
+    relays
+}
 
-    let event_id = output.id().to_hex();
-    log::info!("Note published successfully: {}", event_id);
-    Ok(event_id)
+/// A relay `event_id` was seen on, to use as a hint when referencing it
+pub async fn seen_relay_hint(event_id: &nostr::EventId) -> Option<RelayUrl> {
+    let client = get_client()?;
+    let relays = client.database().event_seen_on_relays(event_id).await.ok().flatten()?;
+    // Lowest URL, so the same event always gets the same hint
+    relays.into_iter().min_by(|a, b| a.as_str().cmp(b.as_str()))
+}
+
+/// NIP-10 tags for a kind 1 reply to `parent`, with relay hints filled in
+/// from where the root and parent were seen
+pub async fn reply_tags(parent: &nostr::Event) -> Vec<Vec<String>> {
+    use crate::utils::reply_tags::{build_reply_tags, thread_root};
+
+    let mut root = thread_root(parent);
+    if let Some(root) = root.as_mut().filter(|root| root.relay_hint.is_none()) {
+        root.relay_hint = seen_relay_hint(&root.id).await;
+    }
+    let parent_relay = seen_relay_hint(&parent.id).await;
+
+    build_reply_tags(root.as_ref(), parent, parent_relay.as_ref())
+        .into_iter()
+        .map(|tag| tag.to_vec())
+        .collect()
+}
+
+/// Publish a sequence of notes as a self-reply thread
+/// NIP-10: https://github.com/nostr-protocol/nips/blob/master/10.md
+///
+/// The first part becomes the thread root; every following part replies to the
+/// previous one with `root`/`reply` markers. `extra_tags` (e.g. a content
+/// warning) are added to every part. With `relays`, every part goes to
+/// those relays only. Returns the published event IDs in order.
+pub async fn publish_thread(
+    parts: Vec<String>,
+    extra_tags: Vec<Vec<String>>,
+    relays: Option<Vec<String>>,
+) -> std::result::Result<Vec<String>, String> {
+    if parts.is_empty() {
+        return Err("Nothing to publish".to_string());
+    }
+
+    let author = get_user_pubkey().await?.to_hex();
+    let total = parts.len();
+    let mut published: Vec<String> = Vec::with_capacity(total);
+
+    for (index, part) in parts.into_iter().enumerate() {
+        let mut tags: Vec<Vec<String>> = Vec::new();
+
+        if let Some(root_id) = published.first() {
+            tags.push(vec!["e".to_string(), root_id.clone(), "".to_string(), "root".to_string()]);
+            // Direct replies to the root only carry the root marker
+            if let Some(parent_id) = published.last().filter(|id| *id != root_id) {
+                tags.push(vec!["e".to_string(), parent_id.clone(), "".to_string(), "reply".to_string()]);
+            }
+            tags.push(vec!["p".to_string(), author.clone()]);
+        }
+        tags.extend(extra_tags.iter().cloned());
+
+        let result = match &relays {
+            Some(relays) => publish_note_to(part, tags, relays.clone()).await,
+            None => publish_note(part, tags).await,
+        };
+        let event_id = result.map_err(|e| {
+            format!("Failed to publish part {} of {} ({} published): {}", index + 1, total, published.len(), e)
+        })?;
+        published.push(event_id);
+    }
+
+    log::info!("Thread of {} notes published", total);
+    Ok(published)
 }
 
 /// Publish a reaction (kind 7 event) to another event
@@ -697,9 +1424,8 @@ pub async fn publish_reaction(
     log::info!("Publishing reaction to event: {}", event_id);
 
     // Parse event ID and author pubkey
-    use nostr::{EventId, PublicKey, Tag, Url};
+    use nostr::{EventId, PublicKey};
     use nostr::nips::nip25::ReactionTarget;
-    use nostr::event::tag::TagStandard;
     use nostr_sdk::nips::nip01::Coordinate;
 
     let target_event_id = EventId::from_hex(&event_id)
@@ -741,13 +1467,12 @@ pub async fn publish_reaction(
 
     // Add emoji tag for custom emojis (NIP-30)
     if let Some((shortcode, url_str)) = emoji_tag {
-        if let Ok(parsed_url) = Url::parse(&url_str) {
-            builder = builder.tag(Tag::from_standardized_without_cell(
-                TagStandard::Emoji { shortcode, url: parsed_url }
-            ));
-            log::info!("Added custom emoji tag to reaction");
-        } else {
-            log::warn!("Failed to parse custom emoji URL: {}", url_str);
+        match crate::utils::event::custom_emoji_tag(&shortcode, &url_str) {
+            Ok(tag) => {
+                builder = builder.tag(tag);
+                log::info!("Added custom emoji tag to reaction");
+            }
+            Err(e) => log::warn!("{}", e),
         }
     }
 
@@ -838,120 +1563,6 @@ async fn fetch_contacts_from_relay(pubkey_str: String) -> std::result::Result<Ve
     }
 }
 
-/// Publish a contact list (kind 3 event)
-/// NIP-02: https://github.com/nostr-protocol/nips/blob/master/02.md
-pub async fn publish_contacts(contacts: Vec<String>) -> std::result::Result<String, String> {
-    let client = get_client().ok_or("Client not initialized")?;
-
-    if !*HAS_SIGNER.read() {
-        return Err("No signer attached. Cannot publish events.".to_string());
-    }
-
-    log::info!("Publishing contact list with {} contacts", contacts.len());
-
-    // Parse contacts into Contact structs for proper NIP-02 compliance
-    use nostr::PublicKey;
-    use nostr_sdk::nips::nip02::Contact;
-    let contact_list: Vec<Contact> = contacts
-        .into_iter()
-        .filter_map(|contact_str| {
-            // Try to parse as hex or NIP-19
-            PublicKey::from_hex(&contact_str)
-                .or_else(|_| PublicKey::parse(&contact_str))
-                .ok()
-                .map(|pubkey| Contact::new(pubkey))
-        })
-        .collect();
-
-    log::info!("Parsed {} valid contacts", contact_list.len());
-
-    // Use EventBuilder::contact_list() for proper NIP-02 compliance
-    // This allows for relay URLs and petnames (aliases) to be added in the future
-    let builder = nostr::EventBuilder::contact_list(contact_list);
-
-    match client.send_event_builder(builder).await {
-        Ok(output) => {
-            let event_id = output.id().to_string();
-            log::info!("Contact list published successfully: {}", event_id);
-            Ok(event_id)
-        }
-        Err(e) => {
-            log::error!("Failed to publish contact list: {}", e);
-            Err(format!("Failed to publish contact list: {}", e))
-        }
-    }
-}
-
-/// Follow a user (adds to contact list and publishes)
-pub async fn follow_user(pubkey_to_follow: String) -> std::result::Result<(), String> {
-    // Invalidate contacts cache since we're modifying it
-    invalidate_contacts_cache();
-
-    // Normalize pubkey to canonical hex format
-    let normalized_pubkey = crate::utils::nip19::normalize_pubkey(&pubkey_to_follow)?;
-
-    // Get current user's pubkey
-    let current_pubkey = crate::stores::auth_store::get_pubkey()
-        .ok_or("Not logged in")?;
-
-    // Fetch current contacts
-    let mut contacts = fetch_contacts(current_pubkey.clone()).await?;
-
-    // Add new contact if not already following
-    if !contacts.contains(&normalized_pubkey) {
-        contacts.push(normalized_pubkey.clone());
-        log::info!("Following new user: {}", normalized_pubkey);
-
-        // Publish updated contact list
-        publish_contacts(contacts).await?;
-    } else {
-        log::info!("Already following: {}", normalized_pubkey);
-    }
-
-    Ok(())
-}
-
-/// Unfollow a user (removes from contact list and publishes)
-pub async fn unfollow_user(pubkey_to_unfollow: String) -> std::result::Result<(), String> {
-    // Invalidate contacts cache since we're modifying it
-    invalidate_contacts_cache();
-
-    // Normalize pubkey to canonical hex format
-    let normalized_pubkey = crate::utils::nip19::normalize_pubkey(&pubkey_to_unfollow)?;
-
-    // Get current user's pubkey
-    let current_pubkey = crate::stores::auth_store::get_pubkey()
-        .ok_or("Not logged in")?;
-
-    // Fetch current contacts
-    let mut contacts = fetch_contacts(current_pubkey.clone()).await?;
-
-    // Remove contact if following
-    if let Some(pos) = contacts.iter().position(|x| x == &normalized_pubkey) {
-        contacts.remove(pos);
-        log::info!("Unfollowing user: {}", normalized_pubkey);
-
-        // Publish updated contact list
-        publish_contacts(contacts).await?;
-    } else {
-        log::info!("Not following: {}", normalized_pubkey);
-    }
-
-    Ok(())
-}
-
-/// Check if current user is following a specific pubkey
-pub async fn is_following(pubkey: String) -> std::result::Result<bool, String> {
-    // Normalize pubkey to canonical hex format
-    let normalized_pubkey = crate::utils::nip19::normalize_pubkey(&pubkey)?;
-
-    let current_pubkey = crate::stores::auth_store::get_pubkey()
-        .ok_or("Not logged in")?;
-
-    let contacts = fetch_contacts(current_pubkey).await?;
-    Ok(contacts.contains(&normalized_pubkey))
-}
-
 /// Fetch the mute list (kind 10000) from relays
 /// NIP-51: https://github.com/nostr-protocol/nips/blob/master/51.md
 async fn fetch_mute_list() -> std::result::Result<Option<nostr::Event>, String> {
@@ -986,9 +1597,20 @@ pub async fn get_muted_posts() -> std::result::Result<Vec<String>, String> {
             let muted_posts: Vec<String> = event.tags.event_ids()
                 .map(|id| id.to_string())
                 .collect();
+            set_muted_threads(muted_posts.iter().cloned().collect());
             Ok(muted_posts)
         }
-        None => Ok(Vec::new()),
+        None => {
+            set_muted_threads(HashSet::new());
+            Ok(Vec::new())
+        }
+    }
+}
+
+/// Replace the cached mute list entries, only notifying readers on a change
+fn set_muted_threads(muted: HashSet<String>) {
+    if *MUTED_THREADS.peek() != muted {
+        *MUTED_THREADS.write() = muted;
     }
 }
 
@@ -1117,6 +1739,7 @@ pub async fn mute_post(event_id: String) -> std::result::Result<(), String> {
     client.send_event_builder(builder).await
         .map_err(|e| format!("Failed to publish mute list: {}", e))?;
 
+    MUTED_THREADS.write().insert(target_event_id.to_hex());
     log::info!("Post muted successfully");
     Ok(())
 }
@@ -1212,6 +1835,7 @@ pub async fn unmute_post(event_id: String) -> std::result::Result<(), String> {
     client.send_event_builder(builder).await
         .map_err(|e| format!("Failed to publish mute list: {}", e))?;
 
+    MUTED_THREADS.write().remove(&target_event_id.to_hex());
     log::info!("Post unmuted successfully");
     Ok(())
 }
@@ -1738,6 +2362,9 @@ pub async fn update_profile_banner(url: String) -> std::result::Result<(), Strin
 
 /// Publish a long-form article (Kind 30023)
 /// NIP-23: https://github.com/nostr-protocol/nips/blob/master/23.md
+///
+/// Publishing with the identifier of an existing article replaces it; pass
+/// its `published_at` to keep the original publication date.
 pub async fn publish_article(
     title: String,
     summary: String,
@@ -1745,6 +2372,7 @@ pub async fn publish_article(
     identifier: String,
     cover_image: String,
     hashtags: Vec<String>,
+    published_at: Option<u64>,
 ) -> std::result::Result<String, String> {
     let client = get_client().ok_or("Client not initialized")?;
 
@@ -1800,14 +2428,17 @@ pub async fn publish_article(
         ));
     }
 
-    // Add published_at timestamp
-    let timestamp = std::time::SystemTime::now()
-        .duration_since(std::time::UNIX_EPOCH)
-        .map(|d| d.as_secs().to_string())
-        .unwrap_or_else(|e| {
-            log::error!("Failed to get system time: {}", e);
-            "0".to_string()
-        });
+    // Add published_at timestamp (an edit keeps the original one)
+    let timestamp = match published_at {
+        Some(published_at) => published_at.to_string(),
+        None => std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs().to_string())
+            .unwrap_or_else(|e| {
+                log::error!("Failed to get system time: {}", e);
+                "0".to_string()
+            }),
+    };
 
     tags.push(Tag::custom(
         nostr::TagKind::Custom("published_at".into()),
@@ -1833,7 +2464,7 @@ pub async fn publish_article(
 }
 
 /// Detect MIME type from URL file extension
-fn detect_mime_type(url: &str) -> Option<String> {
+pub(crate) fn detect_mime_type(url: &str) -> Option<String> {
     let url_lower = url.to_lowercase();
 
     // Extract extension from URL (handles query params and fragments)
@@ -1872,7 +2503,7 @@ fn detect_mime_type(url: &str) -> Option<String> {
 pub async fn publish_picture(
     title: String,
     caption: String,
-    image_urls: Vec<String>,
+    imeta_tags: Vec<nostr::Tag>,
     hashtags: Vec<String>,
     location: String,
 ) -> std::result::Result<String, String> {
@@ -1882,7 +2513,7 @@ pub async fn publish_picture(
         return Err("No signer attached. Cannot publish events.".to_string());
     }
 
-    if image_urls.is_empty() {
+    if imeta_tags.is_empty() {
         return Err("At least one image is required".to_string());
     }
 
@@ -1894,21 +2525,8 @@ pub async fn publish_picture(
         Tag::title(title),
     ];
 
-    // Add imeta tags for each image
-    // Detect MIME type from extension or omit if unknown
-    for url in &image_urls {
-        let mut imeta_fields = vec![format!("url {}", url)];
-
-        // Add MIME type if we can detect it from the extension
-        if let Some(mime_type) = detect_mime_type(url) {
-            imeta_fields.push(format!("m {}", mime_type));
-        }
-
-        tags.push(Tag::custom(
-            nostr::TagKind::Custom("imeta".into()),
-            imeta_fields
-        ));
-    }
+    // One imeta tag per image, in order
+    tags.extend(imeta_tags);
 
     // Add location if provided
     if !location.is_empty() {
@@ -1941,7 +2559,7 @@ pub async fn publish_picture(
 pub async fn publish_video(
     title: String,
     description: String,
-    video_url: String,
+    mut video: UploadedMedia,
     thumbnail_url: String,
     hashtags: Vec<String>,
     is_portrait: bool,
@@ -1952,6 +2570,8 @@ pub async fn publish_video(
         return Err("No signer attached. Cannot publish events.".to_string());
     }
 
+    let video_url = video.url.clone();
+
     // Validate required fields
     if video_url.trim().is_empty() {
         return Err("Video URL is required".to_string());
@@ -1974,6 +2594,12 @@ pub async fn publish_video(
         ),
     ];
 
+    // NIP-71 describes the file itself with an imeta tag
+    if video.mime.is_none() {
+        video.mime = detect_mime_type(&video.url);
+    }
+    tags.push(video.imeta_tag());
+
     // Add thumbnail if provided
     if !thumbnail_url.is_empty() {
         tags.push(Tag::custom(
@@ -2297,58 +2923,27 @@ pub async fn publish_poll_vote(
 
     // NIP-88: Votes should be published to the relays specified in the poll
     let output = if !poll_relays.is_empty() {
-        // Track which relays we actually add (to clean up later)
-        let mut added_relays = Vec::new();
-        for relay_url in &poll_relays {
-            // add_relay returns Ok if added, Err if already present or failed
-            if client.add_relay(relay_url.as_str()).await.is_ok() {
-                added_relays.push(relay_url.clone());
-            }
-        }
-
-        // Use non-blocking relay ready check instead of blocking connect()
-        ensure_relays_ready(&client).await;
+        let relays: Vec<String> = poll_relays.iter().map(|url| url.to_string()).collect();
+        let temp_relays = add_temp_relays(&client, &relays).await;
 
         // Check if any poll relays are actually connected
         let relays_status = client.relays().await;
-        let connected_poll_relays: Vec<_> = poll_relays.iter()
-            .filter(|r| {
-                if let Ok(url) = nostr::RelayUrl::parse(r.as_str()) {
-                    relays_status.get(&url).map(|relay| relay.is_connected()).unwrap_or(false)
-                } else {
-                    false
-                }
-            })
-            .collect();
+        let connected_poll_relays = poll_relays.iter()
+            .filter(|url| relays_status.get(*url).map(|relay| relay.is_connected()).unwrap_or(false))
+            .count();
 
-        if connected_poll_relays.is_empty() {
-            log::warn!("None of the {} poll relays are connected, falling back to default relays", poll_relays.len());
+        if connected_poll_relays == 0 {
+            log::warn!("None of the {} poll relays are connected yet", poll_relays.len());
         } else {
-            log::debug!("{}/{} poll relays connected", connected_poll_relays.len(), poll_relays.len());
+            log::debug!("{}/{} poll relays connected", connected_poll_relays, poll_relays.len());
         }
 
         // Publish to poll-specified relays
-        let relay_urls: Vec<nostr::Url> = poll_relays.iter()
-            .filter_map(|r| nostr::Url::parse(r.as_str()).ok())
-            .collect();
-
-        let result = if !relay_urls.is_empty() {
-            log::info!("Publishing vote to {} poll-specified relays", relay_urls.len());
-            client.send_event_builder_to(relay_urls, builder).await
-                .map_err(|e| format!("Failed to publish poll vote to poll relays: {}", e))
-        } else {
-            // Fallback if URL parsing failed
-            client.send_event_builder(builder).await
-                .map_err(|e| format!("Failed to publish poll vote: {}", e))
-        };
-
-        // Cleanup: remove only the relays we added
-        for relay_url in added_relays {
-            if let Err(e) = client.remove_relay(relay_url.as_str()).await {
-                log::debug!("Could not remove poll relay {}: {}", relay_url, e);
-            }
-        }
+        log::info!("Publishing vote to {} poll-specified relays", relays.len());
+        let result = client.send_event_builder_to(relays, builder).await
+            .map_err(|e| format!("Failed to publish poll vote to poll relays: {}", e));
 
+        temp_relays.release().await;
         result?
     } else {
         // No poll relays specified, use default relays
@@ -2424,3 +3019,76 @@ pub async fn publish_poll(
     log::info!("Poll published successfully: {}", event_id);
     Ok(event_id)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protected_note_carries_dash_tag() {
+        use crate::utils::event::{is_protected, protected_tag};
+
+        let keys = nostr::Keys::generate();
+        let protected = build_text_note("only from me", vec![protected_tag()])
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert!(is_protected(&protected));
+
+        let open = build_text_note("anyone may rebroadcast", vec![]).sign_with_keys(&keys).unwrap();
+        assert!(!is_protected(&open));
+    }
+
+    #[test]
+    fn test_rolling_latency_average() {
+        let mut latency = RelayLatency::default();
+        assert_eq!(latency.avg_ms, None);
+
+        // The first sample is taken as is
+        latency.record(100.0);
+        assert_eq!(latency.avg_ms, Some(100.0));
+
+        // Each later sample moves the average 20% of the way towards it
+        latency.record(200.0);
+        assert_eq!(latency.avg_ms, Some(120.0));
+        latency.record(20.0);
+        assert_eq!(latency.avg_ms, Some(100.0));
+        latency.record(100.0);
+        assert_eq!(latency.avg_ms, Some(100.0));
+        assert_eq!(latency.samples, 4);
+
+        // A single slow fetch doesn't dominate the average
+        latency.record(1100.0);
+        assert!((latency.avg_ms.unwrap() - 300.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_reconnect_backoff_schedule_and_reset() {
+        let delays: Vec<u64> = (0..10).map(reconnect_delay_secs).collect();
+        assert_eq!(delays, vec![2, 4, 8, 16, 32, 64, 128, 256, 300, 300]);
+        assert_eq!(reconnect_delay_secs(u32::MAX), 300);
+
+        // Jitter stays within ±20%
+        assert_eq!(with_jitter(100, 0.0), 80);
+        assert_eq!(with_jitter(100, 0.5), 100);
+        assert_eq!(with_jitter(100, 0.999), 120);
+        assert_eq!(with_jitter(1, 0.0), 1);
+
+        let mut backoff = ReconnectBackoff::default();
+        backoff.schedule(1_000, 0.5);
+        assert_eq!(backoff.next_attempt_at, Some(1_002));
+        assert!(!backoff.is_due(1_001));
+        assert!(backoff.is_due(1_002));
+
+        backoff.record_failure(1_002, 0.5);
+        backoff.record_failure(1_006, 0.5);
+        assert_eq!(backoff.failed_attempts, 2);
+        assert_eq!(backoff.next_attempt_at, Some(1_014));
+
+        // Recovering starts the schedule over
+        backoff.reset();
+        assert_eq!(backoff, ReconnectBackoff::default());
+        assert!(!backoff.is_due(u64::MAX));
+        backoff.schedule(2_000, 0.5);
+        assert_eq!(backoff.next_attempt_at, Some(2_002));
+    }
+}
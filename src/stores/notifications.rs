@@ -1,9 +1,11 @@
 use dioxus::prelude::*;
 use dioxus::signals::ReadableExt;
-use nostr_sdk::{Filter, Kind, SubscriptionId, PublicKey, FromBech32};
+use nostr_sdk::{Event, EventId, Filter, Kind, SubscriptionId, PublicKey, FromBech32, Timestamp};
 use gloo_storage::{LocalStorage, Storage};
+use std::collections::HashSet;
 use crate::stores::{auth_store, nostr_client, settings_store};
 use crate::utils::notification_nip78;
+use crate::utils::reply_tags::thread_root;
 
 const NOTIFICATIONS_CHECKED_AT_KEY: &str = "notifications_checked_at";
 
@@ -23,8 +25,16 @@ pub static NOTIFICATIONS_CHECKED_AT: GlobalSignal<i64> = Signal::global(|| 0);
 /// Track when we last published a NIP-78 event (for throttling)
 pub static LAST_PUBLISHED_AT: GlobalSignal<i64> = Signal::global(|| 0);
 
+/// Notifications received live since the list was loaded, oldest first
+///
+/// The notifications page offers them with a "Show new" button instead of
+/// inserting them above what the user is reading.
+pub static LIVE_NOTIFICATIONS: GlobalSignal<Vec<NotificationType>> = Signal::global(Vec::new);
+
+/// Most live notifications kept waiting to be shown
+const MAX_LIVE_NOTIFICATIONS: usize = 200;
+
 /// Set the unread notification count
-#[allow(dead_code)]
 pub fn set_unread_count(count: usize) {
     *UNREAD_COUNT.write() = count;
 }
@@ -40,11 +50,205 @@ pub fn clear_unread_count() {
 }
 
 /// Increment unread count
-#[allow(dead_code)]
 pub fn increment_unread_count() {
     *UNREAD_COUNT.write() += 1;
 }
 
+/// Event kinds that can notify us (all tag our pubkey with `p`)
+pub const NOTIFICATION_KINDS: [Kind; 5] = [
+    Kind::TextNote,      // Mentions and replies
+    Kind::Repost,        // Reposts
+    Kind::Reaction,      // Reactions (likes)
+    Kind::ZapReceipt,    // Zap receipts
+    Kind::ContactList,   // Follows
+];
+
+/// Kinds watched live: a contact list arriving later tags us whether or not
+/// it added us, and relays only keep the latest one to compare against, so
+/// follows only come with the loaded list
+pub const REALTIME_NOTIFICATION_KINDS: [Kind; 4] = [
+    Kind::TextNote,
+    Kind::Repost,
+    Kind::Reaction,
+    Kind::ZapReceipt,
+];
+
+/// A notification, by type, with the event behind it
+#[derive(Clone, Debug, PartialEq)]
+pub enum NotificationType {
+    Mention(Event),
+    Reply(Event),
+    Reaction(Event),
+    Repost(Event),
+    Zap(Event),
+    /// A contact list that includes us
+    ///
+    /// Contact lists are replaceable, so an existing follower updating theirs
+    /// shows up here again.
+    Follow(Event),
+}
+
+impl NotificationType {
+    /// Classify an event that tags us; None for kinds we don't notify about
+    pub fn classify(event: Event) -> Option<Self> {
+        match event.kind {
+            Kind::TextNote => {
+                // A note with an `e` tag is a reply, otherwise a mention
+                let is_reply = event.tags.iter().any(|tag| {
+                    tag.kind() == nostr_sdk::TagKind::SingleLetter(
+                        nostr_sdk::SingleLetterTag::lowercase(nostr_sdk::Alphabet::E)
+                    )
+                });
+                Some(if is_reply { Self::Reply(event) } else { Self::Mention(event) })
+            }
+            Kind::Reaction => Some(Self::Reaction(event)),
+            Kind::Repost => Some(Self::Repost(event)),
+            Kind::ZapReceipt => Some(Self::Zap(event)),
+            Kind::ContactList => Some(Self::Follow(event)),
+            _ => None,
+        }
+    }
+
+    pub fn event(&self) -> &Event {
+        match self {
+            Self::Mention(e) | Self::Reply(e) | Self::Reaction(e)
+            | Self::Repost(e) | Self::Zap(e) | Self::Follow(e) => e,
+        }
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.event().created_at.as_secs()
+    }
+
+    /// Whether this arrived after notifications were last marked read
+    pub fn is_unread(&self, checked_at: i64) -> bool {
+        is_unread(self.timestamp() as i64, checked_at)
+    }
+}
+
+/// Notification tabs: everything, or a single type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotificationFilter {
+    All,
+    Replies,
+    Mentions,
+    Reactions,
+    Reposts,
+    Zaps,
+    Follows,
+}
+
+impl NotificationFilter {
+    pub const ALL: [NotificationFilter; 7] = [
+        NotificationFilter::All,
+        NotificationFilter::Replies,
+        NotificationFilter::Mentions,
+        NotificationFilter::Reactions,
+        NotificationFilter::Reposts,
+        NotificationFilter::Zaps,
+        NotificationFilter::Follows,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::All => "All",
+            Self::Replies => "Replies",
+            Self::Mentions => "Mentions",
+            Self::Reactions => "Reactions",
+            Self::Reposts => "Reposts",
+            Self::Zaps => "Zaps",
+            Self::Follows => "Follows",
+        }
+    }
+
+    pub fn matches(&self, notification: &NotificationType) -> bool {
+        match self {
+            Self::All => true,
+            Self::Replies => matches!(notification, NotificationType::Reply(_)),
+            Self::Mentions => matches!(notification, NotificationType::Mention(_)),
+            Self::Reactions => matches!(notification, NotificationType::Reaction(_)),
+            Self::Reposts => matches!(notification, NotificationType::Repost(_)),
+            Self::Zaps => matches!(notification, NotificationType::Zap(_)),
+            Self::Follows => matches!(notification, NotificationType::Follow(_)),
+        }
+    }
+}
+
+/// Notifications of one type, in their original order
+pub fn filter_by_kind(notifications: &[NotificationType], filter: NotificationFilter) -> Vec<NotificationType> {
+    notifications
+        .iter()
+        .filter(|n| filter.matches(n))
+        .cloned()
+        .collect()
+}
+
+/// The note a reaction is for (NIP-25: the last `e` tag)
+pub fn reacted_event_id(event: &Event) -> Option<EventId> {
+    event.tags.event_ids().last().copied()
+}
+
+/// Whether a reply or mention belongs to a thread we muted
+///
+/// `muted` holds the `e` entries of our NIP-51 mute list, which name thread
+/// roots. A note matches when it is a muted root itself or its NIP-10 root
+/// reference points at one.
+pub fn is_in_muted_thread(event: &Event, muted: &HashSet<String>) -> bool {
+    if event.kind != Kind::TextNote || muted.is_empty() {
+        return false;
+    }
+    muted.contains(&event.id.to_hex())
+        || thread_root(event).is_some_and(|root| muted.contains(&root.id.to_hex()))
+}
+
+/// Merge notification events fetched from several relays
+///
+/// An event seen on more than one relay is kept once, our own events are
+/// skipped and reactions to notes in `deleted` are dropped. Newest first.
+pub fn merge_relay_batches(
+    batches: impl IntoIterator<Item = Vec<Event>>,
+    my_pubkey: &PublicKey,
+    deleted: &HashSet<EventId>,
+) -> Vec<NotificationType> {
+    let mut seen = HashSet::new();
+    let mut merged: Vec<NotificationType> = batches
+        .into_iter()
+        .flatten()
+        .filter(|event| event.pubkey != *my_pubkey && seen.insert(event.id))
+        .filter(|event| {
+            event.kind != Kind::Reaction || !reacted_event_id(event).is_some_and(|id| deleted.contains(&id))
+        })
+        .filter_map(NotificationType::classify)
+        .collect();
+    merged.sort_by(|a, b| b.timestamp().cmp(&a.timestamp()));
+    merged
+}
+
+/// Add notifications to a list, skipping any already in it; newest first
+pub fn merge_into(list: &mut Vec<NotificationType>, more: Vec<NotificationType>) {
+    let known: HashSet<EventId> = list.iter().map(|n| n.event().id).collect();
+    list.extend(more.into_iter().filter(|n| !known.contains(&n.event().id)));
+    list.sort_by(|a, b| b.timestamp().cmp(&a.timestamp()));
+}
+
+/// Whether an event created at `created_at` is unread given the last checked time
+pub fn is_unread(created_at: i64, checked_at: i64) -> bool {
+    created_at > checked_at
+}
+
+/// How many of `notifications` are unread given the last checked time
+pub fn count_unread(notifications: &[NotificationType], checked_at: i64) -> usize {
+    notifications.iter().filter(|n| n.is_unread(checked_at)).count()
+}
+
+/// Mark every notification read as of now
+///
+/// Clears the unread badge; anything newer than this moment counts as
+/// unread again when it arrives.
+pub fn mark_all_read() {
+    set_checked_at(Timestamp::now().as_secs() as i64);
+}
+
 /// Load the last checked timestamp from localStorage and update the signal
 pub fn load_checked_at() {
     let timestamp = LocalStorage::get::<i64>(NOTIFICATIONS_CHECKED_AT_KEY).unwrap_or(0);
@@ -57,6 +261,18 @@ pub fn get_checked_at() -> i64 {
     *NOTIFICATIONS_CHECKED_AT.read()
 }
 
+/// Forget the checked_at timestamp and unread count (in memory only)
+pub fn reset_notification_state() {
+    *NOTIFICATIONS_CHECKED_AT.write() = 0;
+    clear_unread_count();
+    LIVE_NOTIFICATIONS.write().clear();
+}
+
+/// Delete the stored checked_at timestamp
+pub fn delete_checked_at() {
+    LocalStorage::delete(NOTIFICATIONS_CHECKED_AT_KEY);
+}
+
 /// Set the checked_at timestamp, updating both the signal and localStorage
 /// Optionally publishes to NIP-78 if sync is enabled (throttled to once per 10 min)
 pub fn set_checked_at(timestamp: i64) {
@@ -267,12 +483,7 @@ pub async fn start_realtime_subscription() {
     // Subscribe with limit 20 for real-time updates only
     // Use #p tag to match events that mention/tag our pubkey
     let filter = Filter::new()
-        .kinds(vec![
-            Kind::TextNote,      // For mentions and replies
-            Kind::Repost,        // Reposts
-            Kind::Reaction,      // Reactions (likes)
-            Kind::ZapReceipt,    // Zap receipts
-        ])
+        .kinds(REALTIME_NOTIFICATION_KINDS)
         .custom_tag(
             nostr_sdk::SingleLetterTag::lowercase(nostr_sdk::Alphabet::P),
             my_pubkey_str
@@ -312,11 +523,16 @@ pub async fn start_realtime_subscription() {
                             continue;
                         }
 
+                        // Nor for replies in threads we muted
+                        if is_in_muted_thread(&event, &nostr_client::MUTED_THREADS.read()) {
+                            continue;
+                        }
+
                         // Check if this event is newer than when we last checked
                         let checked_at = get_checked_at();
                         let event_timestamp = event.created_at.as_secs() as i64;
 
-                        if event_timestamp > checked_at {
+                        if is_unread(event_timestamp, checked_at) {
                             log::debug!(
                                 "New notification received: kind={}, from={}, created_at={}",
                                 event.kind,
@@ -327,6 +543,23 @@ pub async fn start_realtime_subscription() {
                             // Increment the unread count
                             increment_unread_count();
                         }
+
+                        // Hand it to the notifications page, unless it reacts to a note we deleted
+                        let deleted_target = event.kind == Kind::Reaction
+                            && reacted_event_id(&event).is_some_and(|id| {
+                                nostr_client::DELETED_EVENTS.read().contains(&id.to_hex())
+                            });
+                        if !deleted_target {
+                            if let Some(notification) = NotificationType::classify((*event).clone()) {
+                                let mut live = LIVE_NOTIFICATIONS.write();
+                                if !live.iter().any(|n| n.event().id == event.id) {
+                                    live.push(notification);
+                                    if live.len() > MAX_LIVE_NOTIFICATIONS {
+                                        live.remove(0);
+                                    }
+                                }
+                            }
+                        }
                     }
                 }
 
@@ -351,3 +584,133 @@ pub async fn stop_realtime_subscription() {
         *SUBSCRIPTION_ID.write() = None;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::{EventBuilder, Keys, Tag};
+
+    fn event(kind: Kind, tags: Vec<Vec<&str>>, created_at: u64) -> Event {
+        let tags: Vec<Tag> = tags
+            .into_iter()
+            .map(|t| Tag::parse(t.into_iter().map(String::from).collect::<Vec<_>>()).unwrap())
+            .collect();
+        EventBuilder::new(kind, "")
+            .tags(tags)
+            .custom_created_at(Timestamp::from(created_at))
+            .sign_with_keys(&Keys::generate())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_classify_and_filter_by_kind() {
+        let me = Keys::generate().public_key().to_hex();
+        let note_id = "a".repeat(64);
+        let events = vec![
+            event(Kind::TextNote, vec![vec!["p", &me]], 10),
+            event(Kind::TextNote, vec![vec!["e", &note_id], vec!["p", &me]], 20),
+            event(Kind::Reaction, vec![vec!["e", &note_id], vec!["p", &me]], 30),
+            event(Kind::Repost, vec![vec!["e", &note_id], vec!["p", &me]], 40),
+            event(Kind::ZapReceipt, vec![vec!["p", &me]], 50),
+            event(Kind::ContactList, vec![vec!["p", &me]], 60),
+            event(Kind::Metadata, vec![vec!["p", &me]], 70),
+        ];
+        let notifications: Vec<NotificationType> =
+            events.into_iter().filter_map(NotificationType::classify).collect();
+        assert_eq!(notifications.len(), 6);
+
+        assert_eq!(filter_by_kind(&notifications, NotificationFilter::All).len(), 6);
+        for filter in NotificationFilter::ALL.into_iter().skip(1) {
+            let matched = filter_by_kind(&notifications, filter);
+            assert_eq!(matched.len(), 1, "{}", filter.label());
+        }
+        assert!(matches!(filter_by_kind(&notifications, NotificationFilter::Mentions)[0], NotificationType::Mention(_)));
+        assert!(matches!(filter_by_kind(&notifications, NotificationFilter::Replies)[0], NotificationType::Reply(_)));
+        let follows = filter_by_kind(&notifications, NotificationFilter::Follows);
+        assert_eq!(follows[0].timestamp(), 60);
+    }
+
+    #[test]
+    fn test_mark_all_read_transition() {
+        let me = Keys::generate().public_key().to_hex();
+        let notifications: Vec<NotificationType> = [100, 200, 300]
+            .into_iter()
+            .filter_map(|ts| NotificationType::classify(event(Kind::Reaction, vec![vec!["p", &me]], ts)))
+            .collect();
+
+        // Before: last checked between the first and second
+        assert_eq!(count_unread(&notifications, 150), 2);
+
+        // Marking everything read at 300 clears the lot, including same-second events
+        assert_eq!(count_unread(&notifications, 300), 0);
+
+        // Something newer than the mark counts as unread again
+        let newer = NotificationType::classify(event(Kind::Reaction, vec![vec!["p", &me]], 301)).unwrap();
+        assert!(newer.is_unread(300));
+        assert!(!is_unread(300, 300));
+    }
+
+    #[test]
+    fn test_merge_relay_batches_dedups_overlap() {
+        let me = Keys::generate();
+        let my_hex = me.public_key().to_hex();
+        let kept_note = "a".repeat(64);
+        let deleted_note = EventId::from_hex(&"b".repeat(64)).unwrap();
+
+        let shared = event(Kind::Reaction, vec![vec!["e", &kept_note], vec!["p", &my_hex]], 30);
+        let only_first = event(Kind::TextNote, vec![vec!["p", &my_hex]], 10);
+        let only_second = event(Kind::Repost, vec![vec!["e", &kept_note], vec!["p", &my_hex]], 20);
+        let to_deleted = event(Kind::Reaction, vec![vec!["e", &deleted_note.to_hex()], vec!["p", &my_hex]], 40);
+        let own = EventBuilder::new(Kind::TextNote, "")
+            .tag(Tag::parse(vec!["p".to_string(), my_hex.clone()]).unwrap())
+            .sign_with_keys(&me)
+            .unwrap();
+
+        let relay_a = vec![shared.clone(), only_first.clone(), own];
+        let relay_b = vec![only_second.clone(), shared.clone(), to_deleted];
+        let deleted = HashSet::from([deleted_note]);
+
+        let merged = merge_relay_batches(vec![relay_a, relay_b], &me.public_key(), &deleted);
+        let ids: Vec<EventId> = merged.iter().map(|n| n.event().id).collect();
+        assert_eq!(ids, vec![shared.id, only_second.id, only_first.id]);
+
+        // A later page overlapping the first adds only what's new
+        let mut list = merged;
+        let older = event(Kind::TextNote, vec![vec!["p", &my_hex]], 5);
+        merge_into(&mut list, vec![
+            NotificationType::classify(only_first).unwrap(),
+            NotificationType::classify(older.clone()).unwrap(),
+        ]);
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.last().unwrap().event().id, older.id);
+    }
+
+    #[test]
+    fn test_reply_in_muted_thread() {
+        let me = Keys::generate().public_key().to_hex();
+        let root = "a".repeat(64);
+        let other_root = "b".repeat(64);
+        let muted = HashSet::from([root.clone()]);
+
+        let reply = event(Kind::TextNote, vec![vec!["e", &root, "", "root"], vec!["p", &me]], 10);
+        let deep_reply = event(Kind::TextNote, vec![
+            vec!["e", &root, "", "root"],
+            vec!["e", &other_root, "", "reply"],
+            vec!["p", &me],
+        ], 20);
+        let positional = event(Kind::TextNote, vec![vec!["e", &root], vec!["e", &other_root], vec!["p", &me]], 30);
+        let elsewhere = event(Kind::TextNote, vec![vec!["e", &other_root, "", "root"], vec!["p", &me]], 40);
+        let mention = event(Kind::TextNote, vec![vec!["p", &me]], 50);
+        let reaction = event(Kind::Reaction, vec![vec!["e", &root], vec!["p", &me]], 60);
+
+        assert!(is_in_muted_thread(&reply, &muted));
+        assert!(is_in_muted_thread(&deep_reply, &muted));
+        assert!(is_in_muted_thread(&positional, &muted));
+        assert!(!is_in_muted_thread(&elsewhere, &muted));
+        assert!(!is_in_muted_thread(&mention, &muted));
+        // Reactions aren't thread replies
+        assert!(!is_in_muted_thread(&reaction, &muted));
+        // Unmuting restores them
+        assert!(!is_in_muted_thread(&reply, &HashSet::new()));
+    }
+}
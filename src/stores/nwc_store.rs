@@ -31,6 +31,146 @@ pub static NWC_STATUS: GlobalSignal<ConnectionStatus> =
 /// Cached wallet balance in millisatoshis
 pub static NWC_BALANCE: GlobalSignal<Option<u64>> = Signal::global(|| None);
 
+/// URI of the current connection, for requests the nwc client has no helper for
+static NWC_URI: GlobalSignal<Option<NostrWalletConnectURI>> = Signal::global(|| None);
+
+/// What the connection's `get_budget` allowance has left, in millisatoshis
+///
+/// None when the connection has no budget or the wallet doesn't report it.
+static NWC_CONNECTION_BUDGET: GlobalSignal<Option<u64>> = Signal::global(|| None);
+
+/// One wallet transaction from `list_transactions`
+#[derive(Clone, Debug, PartialEq)]
+pub struct NwcTransaction {
+    /// Incoming or outgoing; None if the wallet doesn't say
+    pub direction: Option<TransactionType>,
+    pub amount_msats: u64,
+    pub fees_msats: u64,
+    pub description: Option<String>,
+    pub payment_hash: String,
+    pub created_at: Timestamp,
+    /// None while unpaid
+    pub settled_at: Option<Timestamp>,
+}
+
+impl From<LookupInvoiceResponse> for NwcTransaction {
+    fn from(tx: LookupInvoiceResponse) -> Self {
+        Self {
+            direction: tx.transaction_type,
+            amount_msats: tx.amount,
+            fees_msats: tx.fees_paid,
+            description: tx.description.filter(|d| !d.trim().is_empty()),
+            payment_hash: tx.payment_hash,
+            created_at: tx.created_at,
+            settled_at: tx.settled_at,
+        }
+    }
+}
+
+/// Number of transactions shown in the wallet area
+pub const TRANSACTION_LIST_LIMIT: u64 = 20;
+
+/// Recent transactions, newest first
+///
+/// None hides the list: not loaded yet, or the wallet doesn't implement
+/// `list_transactions`.
+pub static NWC_TRANSACTIONS: GlobalSignal<Option<Vec<NwcTransaction>>> = Signal::global(|| None);
+
+/// What this connection may still spend
+///
+/// A payment can exceed neither the wallet balance nor the connection's
+/// NIP-47 `get_budget` allowance, so this is the smaller of the two known.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SpendBudget {
+    /// Neither the balance nor a budget is known
+    Unknown,
+    /// Remaining spendable amount in millisatoshis
+    Remaining(u64),
+}
+
+/// Remaining budget of the connection
+pub static NWC_REMAINING_BUDGET: GlobalSignal<SpendBudget> = Signal::global(|| SpendBudget::Unknown);
+
+/// Default remaining budget below which zaps show a warning (1000 sats)
+pub const DEFAULT_BUDGET_WARNING_THRESHOLD_MSATS: u64 = 1_000_000;
+
+/// Remaining budget below which zaps show a warning, in millisatoshis
+pub static NWC_BUDGET_WARNING_THRESHOLD: GlobalSignal<u64> =
+    Signal::global(|| DEFAULT_BUDGET_WARNING_THRESHOLD_MSATS);
+
+/// Warning to show before paying from the connection
+#[derive(Clone, Debug, PartialEq)]
+pub enum BudgetWarning {
+    /// Enough budget left after the payment
+    None,
+    /// The budget can't be queried
+    Unknown,
+    /// The payment fits but leaves less than the threshold
+    Low { remaining_after_msats: u64 },
+    /// The payment is more than the remaining budget
+    Exceeds { remaining_msats: u64 },
+}
+
+/// Compare a payment against the remaining budget
+pub fn check_budget(budget: &SpendBudget, amount_msats: u64, threshold_msats: u64) -> BudgetWarning {
+    match *budget {
+        SpendBudget::Unknown => BudgetWarning::Unknown,
+        SpendBudget::Remaining(remaining) if amount_msats > remaining => {
+            BudgetWarning::Exceeds { remaining_msats: remaining }
+        }
+        SpendBudget::Remaining(remaining) => {
+            let remaining_after = remaining - amount_msats;
+            if remaining_after < threshold_msats {
+                BudgetWarning::Low { remaining_after_msats: remaining_after }
+            } else {
+                BudgetWarning::None
+            }
+        }
+    }
+}
+
+/// Spendable amount given the wallet balance and the connection's budget
+pub fn spend_budget(balance_msats: Option<u64>, budget_msats: Option<u64>) -> SpendBudget {
+    match balance_msats.into_iter().chain(budget_msats).min() {
+        Some(remaining) => SpendBudget::Remaining(remaining),
+        None => SpendBudget::Unknown,
+    }
+}
+
+fn update_remaining_budget() {
+    *NWC_REMAINING_BUDGET.write() = spend_budget(*NWC_BALANCE.peek(), *NWC_CONNECTION_BUDGET.peek());
+}
+
+/// Remaining allowance in a NIP-47 `get_budget` response, in millisatoshis
+///
+/// None when the connection has no budget, which wallets answer with an
+/// empty result.
+fn parse_budget_response(json: &str) -> std::result::Result<Option<u64>, String> {
+    let response: serde_json::Value = serde_json::from_str(json)
+        .map_err(|e| format!("Invalid get_budget response: {}", e))?;
+    if let Some(error) = response.get("error").filter(|error| !error.is_null()) {
+        let message = error.get("message").and_then(|m| m.as_str()).unwrap_or("unknown error");
+        return Err(format!("get_budget failed: {}", message));
+    }
+
+    let field = |name: &str| response.get("result").and_then(|r| r.get(name)).and_then(|v| v.as_u64());
+    Ok(field("total_budget").map(|total| total.saturating_sub(field("used_budget").unwrap_or(0))))
+}
+
+/// Remaining budget below which zaps show a warning, in millisatoshis
+pub fn budget_warning_threshold() -> u64 {
+    *NWC_BUDGET_WARNING_THRESHOLD.read()
+}
+
+/// Budget warning for paying `amount_sats` with the connected wallet
+pub fn budget_warning_for(amount_sats: u64) -> BudgetWarning {
+    check_budget(
+        &NWC_REMAINING_BUDGET.read(),
+        amount_sats.saturating_mul(1000),
+        budget_warning_threshold(),
+    )
+}
+
 /// Open or create IndexedDB for NWC settings
 async fn open_db() -> std::result::Result<IdbDatabase, String> {
     let mut db_req = IdbDatabase::open_u32(DB_NAME, DB_VERSION)
@@ -139,6 +279,8 @@ pub async fn connect_nwc(uri_string: &str) -> std::result::Result<(), String> {
         })?;
 
     // Create NWC client
+    *NWC_URI.write() = Some(uri.clone());
+    *NWC_CONNECTION_BUDGET.write() = None;
     let nwc = NWC::new(uri);
 
     // Test connection by getting wallet info
@@ -146,6 +288,8 @@ pub async fn connect_nwc(uri_string: &str) -> std::result::Result<(), String> {
         Ok(info) => {
             log::info!("Connected to NWC wallet: {}", info.alias.as_deref().unwrap_or("Unknown"));
 
+            let lists_transactions = info.methods.contains(&Method::ListTransactions);
+
             // Save URI to IndexedDB
             if let Err(e) = save_nwc_uri(uri_string.trim()).await {
                 log::warn!("Failed to save NWC URI to IndexedDB: {}", e);
@@ -155,9 +299,12 @@ pub async fn connect_nwc(uri_string: &str) -> std::result::Result<(), String> {
             *NWC_CLIENT.write() = Some(Arc::new(nwc));
             *NWC_STATUS.write() = ConnectionStatus::Connected;
 
-            // Fetch initial balance
-            spawn(async {
-                let _ = refresh_balance().await;
+            // Fetch initial balance and history
+            spawn(async move {
+                let _ = nwc_get_balance().await;
+                if lists_transactions {
+                    let _ = nwc_list_transactions(TRANSACTION_LIST_LIMIT).await;
+                }
             });
 
             Ok(())
@@ -176,6 +323,10 @@ pub fn disconnect_nwc() {
     *NWC_CLIENT.write() = None;
     *NWC_STATUS.write() = ConnectionStatus::Disconnected;
     *NWC_BALANCE.write() = None;
+    *NWC_URI.write() = None;
+    *NWC_CONNECTION_BUDGET.write() = None;
+    *NWC_REMAINING_BUDGET.write() = SpendBudget::Unknown;
+    *NWC_TRANSACTIONS.write() = None;
 
     // Clear IndexedDB (async, fire and forget)
     spawn(async {
@@ -208,29 +359,143 @@ pub async fn restore_connection() {
     }
 }
 
-/// Get wallet balance in millisatoshis
-pub async fn get_balance() -> std::result::Result<u64, String> {
+/// Fetch the wallet balance in millisatoshis
+///
+/// Updates the cached balance and remaining budget; the connection's budget
+/// is refreshed in the background.
+pub async fn nwc_get_balance() -> std::result::Result<u64, String> {
     let client = NWC_CLIENT
         .read()
         .clone()
         .ok_or("NWC not connected")?;
 
-    client
-        .get_balance()
-        .await
-        .map_err(|e| format!("Failed to get balance: {}", e))
-}
-
-/// Refresh the cached balance
-pub async fn refresh_balance() -> std::result::Result<(), String> {
-    match get_balance().await {
+    match client.get_balance().await {
         Ok(balance) => {
             *NWC_BALANCE.write() = Some(balance);
-            Ok(())
+            update_remaining_budget();
+            spawn(async {
+                if let Err(e) = nwc_get_budget().await {
+                    log::debug!("No connection budget: {}", e);
+                }
+            });
+            Ok(balance)
         }
         Err(e) => {
             log::error!("Failed to refresh balance: {}", e);
-            Err(e)
+            Err(format!("Failed to get balance: {}", e))
+        }
+    }
+}
+
+/// Ask the wallet what the connection's budget has left (NIP-47 `get_budget`)
+///
+/// The nwc client has no helper for this method, so the request is sent as
+/// a raw kind 23194 event on the connection's relays. Returns None when the
+/// connection has no budget.
+pub async fn nwc_get_budget() -> std::result::Result<Option<u64>, String> {
+    use nostr::nips::nip04;
+    use nostr_sdk::{Client, EventBuilder, Keys, Kind, Tag};
+
+    let uri = NWC_URI.peek().clone().ok_or("NWC not connected")?;
+    let keys = Keys::new(uri.secret.clone());
+    let content = nip04::encrypt(&uri.secret, &uri.public_key, r#"{"method":"get_budget","params":{}}"#)
+        .map_err(|e| format!("Failed to encrypt get_budget request: {}", e))?;
+    let request = EventBuilder::new(Kind::WalletConnectRequest, content)
+        .tag(Tag::public_key(uri.public_key))
+        .sign_with_keys(&keys)
+        .map_err(|e| format!("Failed to sign get_budget request: {}", e))?;
+
+    let client = Client::new(keys);
+    for relay in &uri.relays {
+        if let Err(e) = client.add_relay(relay.clone()).await {
+            log::warn!("Failed to add NWC relay {}: {}", relay, e);
+        }
+    }
+    client.connect().await;
+    let result = await_budget_response(&client, &uri, &request).await;
+    client.shutdown().await;
+
+    // Ignore answers for a connection that was swapped out meanwhile
+    if NWC_URI.peek().as_ref() == Some(&uri) {
+        *NWC_CONNECTION_BUDGET.write() = result.clone().ok().flatten();
+        update_remaining_budget();
+    }
+    result
+}
+
+/// Send a `get_budget` request and wait for the wallet's answer
+async fn await_budget_response(
+    client: &nostr_sdk::Client,
+    uri: &NostrWalletConnectURI,
+    request: &nostr_sdk::Event,
+) -> std::result::Result<Option<u64>, String> {
+    use futures::future::{select, Either};
+    use nostr::nips::nip04;
+    use nostr_sdk::{Filter, Kind, RelayPoolNotification};
+
+    // Subscribe before asking: responses are ephemeral, relays don't keep them
+    let filter = Filter::new()
+        .kind(Kind::WalletConnectResponse)
+        .author(uri.public_key)
+        .event(request.id);
+    let mut notifications = client.notifications();
+    client.subscribe(filter, None).await
+        .map_err(|e| format!("Failed to subscribe: {}", e))?;
+    client.send_event(request).await
+        .map_err(|e| format!("Failed to send get_budget request: {}", e))?;
+
+    let timeout_ms = crate::stores::nostr_client::default_fetch_timeout().as_millis() as u32;
+    let mut deadline = Box::pin(gloo_timers::future::TimeoutFuture::new(timeout_ms));
+    loop {
+        let next = Box::pin(notifications.recv());
+        match select(next, deadline).await {
+            Either::Left((Ok(RelayPoolNotification::Event { event, .. }), pending)) => {
+                deadline = pending;
+                if event.kind != Kind::WalletConnectResponse || event.pubkey != uri.public_key {
+                    continue;
+                }
+                let json = nip04::decrypt(&uri.secret, &uri.public_key, &event.content)
+                    .map_err(|e| format!("Failed to decrypt get_budget response: {}", e))?;
+                return parse_budget_response(&json);
+            }
+            Either::Left((Ok(_), pending)) => deadline = pending,
+            Either::Left((Err(_), _)) => return Err("Wallet relay connection closed".to_string()),
+            Either::Right(_) => return Err("Wallet didn't answer get_budget".to_string()),
+        }
+    }
+}
+
+/// Fetch the `limit` most recent transactions
+///
+/// Updates [`NWC_TRANSACTIONS`]; a wallet that doesn't implement the
+/// method clears it so the list stays hidden.
+pub async fn nwc_list_transactions(limit: u64) -> std::result::Result<Vec<NwcTransaction>, String> {
+    let client = NWC_CLIENT
+        .read()
+        .clone()
+        .ok_or("NWC not connected")?;
+
+    let request = ListTransactionsRequest {
+        limit: Some(limit),
+        ..Default::default()
+    };
+
+    match client.list_transactions(request).await {
+        Ok(transactions) => {
+            let mut transactions: Vec<NwcTransaction> =
+                transactions.into_iter().map(NwcTransaction::from).collect();
+            transactions.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+            *NWC_TRANSACTIONS.write() = Some(transactions.clone());
+            Ok(transactions)
+        }
+        Err(e) => {
+            if matches!(&e, nwc::Error::NIP47(err) if matches!(err.code, ErrorCode::NotImplemented)) {
+                log::info!("Wallet doesn't support list_transactions");
+            } else {
+                log::error!("Failed to list transactions: {}", e);
+            }
+            *NWC_TRANSACTIONS.write() = None;
+            Err(format_nwc_error(e))
         }
     }
 }
@@ -246,9 +511,12 @@ pub async fn pay_invoice(invoice: String) -> std::result::Result<PayInvoiceRespo
 
     match client.pay_invoice(request).await {
         Ok(response) => {
-            // Refresh balance after payment
+            // Refresh balance and history after payment
             spawn(async {
-                let _ = refresh_balance().await;
+                let _ = nwc_get_balance().await;
+                if NWC_TRANSACTIONS.peek().is_some() {
+                    let _ = nwc_list_transactions(TRANSACTION_LIST_LIMIT).await;
+                }
             });
             Ok(response)
         }
@@ -274,3 +542,121 @@ fn format_nwc_error(error: nwc::Error) -> String {
 pub fn is_connected() -> bool {
     NWC_CLIENT.read().is_some()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_budget_threshold_crossing() {
+        let threshold = 1_000_000; // 1000 sats
+        let budget = SpendBudget::Remaining(5_000_000); // 5000 sats
+
+        // Plenty left afterwards
+        assert_eq!(check_budget(&budget, 3_000_000, threshold), BudgetWarning::None);
+        // Exactly at the threshold is still fine
+        assert_eq!(check_budget(&budget, 4_000_000, threshold), BudgetWarning::None);
+        // Crossing below the threshold
+        assert_eq!(
+            check_budget(&budget, 4_500_000, threshold),
+            BudgetWarning::Low { remaining_after_msats: 500_000 }
+        );
+        // Spending everything
+        assert_eq!(
+            check_budget(&budget, 5_000_000, threshold),
+            BudgetWarning::Low { remaining_after_msats: 0 }
+        );
+        // More than what's left
+        assert_eq!(
+            check_budget(&budget, 5_000_001, threshold),
+            BudgetWarning::Exceeds { remaining_msats: 5_000_000 }
+        );
+    }
+
+    #[test]
+    fn test_parse_list_transactions_response() {
+        let json = r#"{
+            "result_type": "list_transactions",
+            "result": {
+                "transactions": [
+                    {
+                        "type": "incoming",
+                        "invoice": "lnbc210n1...",
+                        "description": "Zap!",
+                        "payment_hash": "b5c2f8ad3f7a2d7e0f7d6f1e5c4b3a29181706f5e4d3c2b1a0998877665544aa",
+                        "amount": 21000,
+                        "fees_paid": 0,
+                        "created_at": 1700000000,
+                        "settled_at": 1700000005
+                    },
+                    {
+                        "type": "outgoing",
+                        "description": "",
+                        "payment_hash": "0011223344556677889900112233445566778899001122334455667788990011",
+                        "amount": 1000000,
+                        "fees_paid": 3000,
+                        "created_at": 1700000100
+                    }
+                ]
+            }
+        }"#;
+
+        let transactions: Vec<NwcTransaction> = Response::from_json(json)
+            .unwrap()
+            .to_list_transactions()
+            .unwrap()
+            .into_iter()
+            .map(NwcTransaction::from)
+            .collect();
+
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[0].direction, Some(TransactionType::Incoming));
+        assert_eq!(transactions[0].amount_msats, 21_000);
+        assert_eq!(transactions[0].description.as_deref(), Some("Zap!"));
+        assert_eq!(transactions[0].settled_at, Some(Timestamp::from(1_700_000_005)));
+        assert_eq!(transactions[1].direction, Some(TransactionType::Outgoing));
+        assert_eq!(transactions[1].fees_msats, 3_000);
+        // Blank descriptions and unsettled payments
+        assert_eq!(transactions[1].description, None);
+        assert_eq!(transactions[1].settled_at, None);
+    }
+
+    #[test]
+    fn test_unknown_budget() {
+        assert_eq!(check_budget(&SpendBudget::Unknown, 21_000, 0), BudgetWarning::Unknown);
+    }
+
+    #[test]
+    fn test_spend_budget_is_capped_by_balance() {
+        assert_eq!(spend_budget(None, None), SpendBudget::Unknown);
+        assert_eq!(spend_budget(Some(5_000_000), None), SpendBudget::Remaining(5_000_000));
+        assert_eq!(spend_budget(Some(5_000_000), Some(2_000_000)), SpendBudget::Remaining(2_000_000));
+        assert_eq!(spend_budget(Some(1_000_000), Some(2_000_000)), SpendBudget::Remaining(1_000_000));
+
+        // A zap larger than the balance is flagged even without a budget
+        assert_eq!(
+            check_budget(&spend_budget(Some(10_000), None), 21_000, 0),
+            BudgetWarning::Exceeds { remaining_msats: 10_000 }
+        );
+    }
+
+    #[test]
+    fn test_parse_get_budget_response() {
+        let json = r#"{
+            "result_type": "get_budget",
+            "result": {
+                "used_budget": 10000,
+                "total_budget": 100000,
+                "renews_at": 1700000000,
+                "renewal_period": "monthly"
+            }
+        }"#;
+        assert_eq!(parse_budget_response(json), Ok(Some(90_000)));
+
+        // No budget on the connection
+        assert_eq!(parse_budget_response(r#"{"result_type":"get_budget","result":{}}"#), Ok(None));
+
+        let error = r#"{"result_type":"get_budget","error":{"code":"NOT_IMPLEMENTED","message":"unknown method"}}"#;
+        assert!(parse_budget_response(error).is_err());
+    }
+}
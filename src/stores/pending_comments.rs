@@ -11,7 +11,8 @@ use std::collections::HashMap;
 use std::str::FromStr;
 use dioxus_core::spawn_forever;
 
-use crate::stores::nostr_client::{get_client, publish_note};
+use crate::stores::nostr_client::{self, get_client, publish_note};
+use crate::utils::reply_tags::thread_root;
 use crate::utils::thread_tree::invalidate_thread_tree_cache;
 
 /// Status of a pending comment
@@ -244,61 +245,20 @@ pub fn retry_pending_comment(local_id: &str) {
             }
         } else {
             // NIP-10 Reply (Kind::TextNote)
-            // Build tags for reply following NIP-10
-            let mut tags = Vec::new();
-
-            // Get parent's author pubkey
-            let author_pk = target_event.pubkey.to_hex();
-            let event_id = target_event.id.to_hex();
-
-            // Check if the event we're replying to has a root marker
-            // Use as_slice() to avoid unnecessary cloning
-            let parent_root = target_event.tags.iter().find_map(|tag| {
-                let tag_slice = tag.as_slice();
-                if tag_slice.len() >= 4
-                    && tag_slice.first().map(|s| s.as_str()) == Some("e")
-                    && tag_slice.get(3).map(|s| s.as_str()) == Some("root")
-                {
-                    tag_slice.get(1).map(|s| s.to_string())
-                } else {
-                    None
-                }
-            });
-
             // Determine thread root for cache invalidation
-            let thread_root_id = parent_root.clone().unwrap_or_else(|| event_id.clone());
-
-            if let Some(root_id) = parent_root {
-                // This is a nested reply (replying to a reply)
-                tags.push(vec!["e".to_string(), root_id, "".to_string(), "root".to_string()]);
-                tags.push(vec!["e".to_string(), event_id.clone(), "".to_string(), "reply".to_string()]);
-            } else {
-                // This is a direct reply to root
-                tags.push(vec!["e".to_string(), event_id.clone(), "".to_string(), "root".to_string()]);
-            }
+            let thread_root_id = thread_root(&target_event)
+                .map(|root| root.id)
+                .unwrap_or(target_event.id);
 
-            // Add p tags: parent author + all p tags from parent event
-            // Use as_slice() to avoid unnecessary cloning
-            tags.push(vec!["p".to_string(), author_pk.clone()]);
-            for tag in target_event.tags.iter() {
-                let tag_slice = tag.as_slice();
-                if tag_slice.len() >= 2 && tag_slice.first().map(|s| s.as_str()) == Some("p") {
-                    if let Some(pubkey) = tag_slice.get(1) {
-                        if pubkey.as_str() != author_pk {
-                            tags.push(vec!["p".to_string(), pubkey.to_string()]);
-                        }
-                    }
-                }
-            }
+            // NIP-10 marked e tags and p tags, with relay hints
+            let tags = nostr_client::reply_tags(&target_event).await;
 
             match publish_note(content, tags).await {
                 Ok(published_event_id) => {
                     log::info!("Reply retry successful: {}", published_event_id);
 
                     // Invalidate thread tree cache
-                    if let Ok(root_event_id) = EventId::from_hex(&thread_root_id) {
-                        invalidate_thread_tree_cache(&root_event_id);
-                    }
+                    invalidate_thread_tree_cache(&thread_root_id);
 
                     // Update pending comment status
                     match EventId::from_hex(&published_event_id) {
@@ -0,0 +1,247 @@
+//! Picture post composer state and its autosaved draft
+//!
+//! Images are uploaded one after another as they are picked; each keeps its
+//! own status so one failed upload doesn't hold up the rest. The text fields
+//! and the images already uploaded (with their alt text) are saved to
+//! localStorage so an unsent post survives a reload.
+
+use gloo_storage::{LocalStorage, Storage};
+use nostr_sdk::{Tag, TagKind};
+use serde::{Deserialize, Serialize};
+
+use crate::stores::nostr_client;
+use crate::utils::file_metadata::UploadedMedia;
+
+const DRAFT_KEY: &str = "nostr_blue_photo_draft";
+
+/// Where one picked image is in its upload
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImageUploadStatus {
+    /// Waiting for the images before it
+    Queued,
+    Uploading,
+    Uploaded(UploadedMedia),
+    Failed(String),
+}
+
+/// One image in the composer
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComposerImage {
+    /// Stable key for the thumbnail list
+    pub id: String,
+    pub file_name: String,
+    pub status: ImageUploadStatus,
+    pub alt: String,
+}
+
+impl ComposerImage {
+    pub fn uploaded(&self) -> Option<&UploadedMedia> {
+        match &self.status {
+            ImageUploadStatus::Uploaded(media) => Some(media),
+            _ => None,
+        }
+    }
+
+    pub fn is_failed(&self) -> bool {
+        matches!(self.status, ImageUploadStatus::Failed(_))
+    }
+
+    pub fn is_pending(&self) -> bool {
+        matches!(self.status, ImageUploadStatus::Queued | ImageUploadStatus::Uploading)
+    }
+}
+
+/// NIP-92 `imeta` tags for the uploaded images, in composer order
+///
+/// Images still uploading or that failed are left out. Alt text becomes an
+/// `alt` field; a missing MIME type is guessed from the URL.
+pub fn picture_imeta_tags(images: &[ComposerImage]) -> Vec<Tag> {
+    images
+        .iter()
+        .filter_map(|image| {
+            let mut media = image.uploaded()?.clone();
+            if media.mime.is_none() {
+                media.mime = nostr_client::detect_mime_type(&media.url);
+            }
+            let mut fields = media.imeta_fields();
+            let alt = image.alt.trim();
+            if !alt.is_empty() {
+                fields.push(format!("alt {}", alt));
+            }
+            Some(Tag::custom(TagKind::Custom("imeta".into()), fields))
+        })
+        .collect()
+}
+
+/// Move the image at `from` to position `to`
+pub fn move_image(images: &mut Vec<ComposerImage>, from: usize, to: usize) {
+    if from < images.len() && to < images.len() && from != to {
+        let image = images.remove(from);
+        images.insert(to, image);
+    }
+}
+
+/// An uploaded image as saved in the draft
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DraftImage {
+    pub url: String,
+    #[serde(default)]
+    pub alt: String,
+    #[serde(default)]
+    pub sha256: Option<String>,
+    #[serde(default)]
+    pub mime: Option<String>,
+    #[serde(default)]
+    pub size: Option<usize>,
+    #[serde(default)]
+    pub dimensions: Option<(u32, u32)>,
+    #[serde(default)]
+    pub blurhash: Option<String>,
+}
+
+impl DraftImage {
+    fn from_composer(image: &ComposerImage) -> Option<Self> {
+        let media = image.uploaded()?;
+        Some(Self {
+            url: media.url.clone(),
+            alt: image.alt.clone(),
+            sha256: media.sha256.clone(),
+            mime: media.mime.clone(),
+            size: media.size,
+            dimensions: media.dimensions,
+            blurhash: media.blurhash.clone(),
+        })
+    }
+
+    pub fn into_composer(self) -> ComposerImage {
+        ComposerImage {
+            id: uuid::Uuid::new_v4().to_string(),
+            file_name: self.url.rsplit('/').next().unwrap_or_default().to_string(),
+            alt: self.alt,
+            status: ImageUploadStatus::Uploaded(UploadedMedia {
+                url: self.url,
+                sha256: self.sha256,
+                mime: self.mime,
+                size: self.size,
+                dimensions: self.dimensions,
+                blurhash: self.blurhash,
+            }),
+        }
+    }
+}
+
+/// Snapshot of the picture composer
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PhotoDraft {
+    pub title: String,
+    pub caption: String,
+    pub location: String,
+    /// Comma separated hashtags, as typed
+    pub hashtags: String,
+    /// Uploaded images only, in order
+    #[serde(default)]
+    pub images: Vec<DraftImage>,
+}
+
+impl PhotoDraft {
+    pub fn new(title: &str, caption: &str, location: &str, hashtags: &str, images: &[ComposerImage]) -> Self {
+        Self {
+            title: title.to_string(),
+            caption: caption.to_string(),
+            location: location.to_string(),
+            hashtags: hashtags.to_string(),
+            images: images.iter().filter_map(DraftImage::from_composer).collect(),
+        }
+    }
+
+    /// True when there's nothing worth saving
+    pub fn is_empty(&self) -> bool {
+        self.title.trim().is_empty()
+            && self.caption.trim().is_empty()
+            && self.location.trim().is_empty()
+            && self.hashtags.trim().is_empty()
+            && self.images.is_empty()
+    }
+}
+
+/// The saved draft, if any
+pub fn load_draft() -> Option<PhotoDraft> {
+    LocalStorage::get::<PhotoDraft>(DRAFT_KEY).ok().filter(|draft| !draft.is_empty())
+}
+
+/// Save the draft; an empty one removes the saved draft instead
+pub fn save_draft(draft: &PhotoDraft) {
+    if draft.is_empty() {
+        clear_draft();
+    } else if let Err(e) = LocalStorage::set(DRAFT_KEY, draft) {
+        log::warn!("Failed to save photo draft: {}", e);
+    }
+}
+
+pub fn clear_draft() {
+    LocalStorage::delete(DRAFT_KEY);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(id: &str, status: ImageUploadStatus, alt: &str) -> ComposerImage {
+        ComposerImage {
+            id: id.to_string(),
+            file_name: format!("{}.jpg", id),
+            status,
+            alt: alt.to_string(),
+        }
+    }
+
+    fn uploaded(url: &str) -> ImageUploadStatus {
+        ImageUploadStatus::Uploaded(UploadedMedia {
+            url: url.to_string(),
+            mime: Some("image/jpeg".to_string()),
+            dimensions: Some((800, 600)),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_imeta_tags_follow_composer_order() {
+        let mut images = vec![
+            image("a", uploaded("https://cdn.example.com/a.jpg"), "A sunset"),
+            image("b", ImageUploadStatus::Failed("timeout".to_string()), ""),
+            image("c", ImageUploadStatus::Uploaded(UploadedMedia::from_url("https://cdn.example.com/c.png")), "  "),
+            image("d", ImageUploadStatus::Uploading, ""),
+        ];
+        move_image(&mut images, 2, 0);
+
+        let tags: Vec<Vec<String>> = picture_imeta_tags(&images)
+            .iter()
+            .map(|tag| tag.as_slice().to_vec())
+            .collect();
+        assert_eq!(tags, vec![
+            vec![
+                "imeta".to_string(),
+                "url https://cdn.example.com/c.png".to_string(),
+                "m image/png".to_string(),
+            ],
+            vec![
+                "imeta".to_string(),
+                "url https://cdn.example.com/a.jpg".to_string(),
+                "m image/jpeg".to_string(),
+                "dim 800x600".to_string(),
+                "alt A sunset".to_string(),
+            ],
+        ]);
+
+        // Out of range moves do nothing
+        move_image(&mut images, 1, 9);
+        assert_eq!(images[1].id, "a");
+
+        // Only uploaded images are kept in the draft, and come back intact
+        let draft = PhotoDraft::new("Title", "", "", "", &images);
+        assert_eq!(draft.images.len(), 2);
+        let restored = draft.images[1].clone().into_composer();
+        assert_eq!(restored.uploaded(), images[1].uploaded());
+        assert_eq!(restored.alt, "A sunset");
+    }
+}
@@ -0,0 +1,335 @@
+//! Polls Store - NIP-88 poll votes and live results
+//!
+//! Votes (kind 1018) are tallied one per voter, keeping each voter's latest
+//! vote. Votes cast after the poll closed or naming no known option are ignored.
+
+use dioxus::prelude::*;
+use nostr_sdk::{
+    nips::nip88::{Poll, PollType},
+    Event, EventId, Filter, Kind, PublicKey, RelayPoolNotification, SubscriptionId, Timestamp,
+};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use crate::stores::nostr_client;
+
+/// Live vote subscriptions by poll ID, so they can be closed when leaving the poll
+pub static POLL_SUBSCRIPTIONS: GlobalSignal<HashMap<EventId, PollSubscription>> =
+    Signal::global(HashMap::new);
+
+/// A live vote subscription and the poll relays it borrowed
+pub struct PollSubscription {
+    id: SubscriptionId,
+    relays: Option<nostr_client::TempRelays>,
+}
+
+/// Running tally of a poll's votes
+#[derive(Clone, Debug, Default)]
+pub struct PollTally {
+    options: Vec<String>,
+    multiple_choice: bool,
+    ends_at: Option<Timestamp>,
+    /// Latest counted vote per voter
+    latest: HashMap<PublicKey, Event>,
+}
+
+impl PollTally {
+    pub fn new(options: Vec<String>, multiple_choice: bool, ends_at: Option<Timestamp>) -> Self {
+        Self {
+            options,
+            multiple_choice,
+            ends_at,
+            latest: HashMap::new(),
+        }
+    }
+
+    pub fn from_poll(poll: &Poll) -> Self {
+        Self::new(
+            poll.options.iter().map(|option| option.id.clone()).collect(),
+            matches!(poll.r#type, PollType::MultipleChoice),
+            poll.ends_at,
+        )
+    }
+
+    /// Option IDs this vote selects that exist in the poll (single choice: first only)
+    fn selected_options(&self, vote: &Event) -> Vec<String> {
+        let mut selected: Vec<String> = Vec::new();
+        for tag in vote.tags.iter() {
+            let parts = tag.as_slice();
+            if parts.len() >= 2
+                && parts[0] == "response"
+                && self.options.contains(&parts[1])
+                && !selected.contains(&parts[1])
+            {
+                selected.push(parts[1].clone());
+            }
+        }
+
+        if !self.multiple_choice {
+            selected.truncate(1);
+        }
+        selected
+    }
+
+    /// Count a vote, replacing the voter's earlier vote
+    ///
+    /// Returns true if the tally changed. Older votes, votes after the poll
+    /// closed and votes without a known option are ignored.
+    pub fn add_vote(&mut self, vote: &Event) -> bool {
+        if vote.kind != Kind::PollResponse {
+            return false;
+        }
+        if self.ends_at.is_some_and(|ends_at| vote.created_at > ends_at) {
+            return false;
+        }
+        if self.selected_options(vote).is_empty() {
+            return false;
+        }
+        if let Some(existing) = self.latest.get(&vote.pubkey) {
+            if existing.created_at >= vote.created_at {
+                return false;
+            }
+        }
+
+        self.latest.insert(vote.pubkey, vote.clone());
+        true
+    }
+
+    /// Votes per option ID (every option is present, possibly with 0)
+    pub fn counts(&self) -> HashMap<String, usize> {
+        let mut counts: HashMap<String, usize> =
+            self.options.iter().map(|option| (option.clone(), 0)).collect();
+
+        for vote in self.latest.values() {
+            for option in self.selected_options(vote) {
+                *counts.entry(option).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    /// The counted vote of each voter
+    pub fn votes(&self) -> Vec<Event> {
+        self.latest.values().cloned().collect()
+    }
+}
+
+/// Fetch votes for a poll, one per voter (latest wins)
+///
+/// NIP-88: votes should be fetched from the relays specified in the poll,
+/// falling back to the user's relays if it names none.
+pub async fn fetch_poll_votes(
+    poll_id: EventId,
+    ends_at: Option<Timestamp>,
+    poll_relays: Vec<nostr_sdk::RelayUrl>,
+) -> Result<Vec<Event>, String> {
+    let client = nostr_client::get_client().ok_or("Client not initialized")?;
+
+    let mut filter = Filter::new()
+        .kind(Kind::PollResponse)
+        .event(poll_id);
+
+    if let Some(until) = ends_at {
+        filter = filter.until(until);
+    }
+
+    let events = if !poll_relays.is_empty() {
+        let relays: Vec<String> = poll_relays.iter().map(|url| url.to_string()).collect();
+        let temp_relays = nostr_client::add_temp_relays(&client, &relays).await;
+        let result = client
+            .fetch_events_from(relays, filter, Duration::from_secs(10))
+            .await
+            .map_err(|e| format!("Failed to fetch votes from poll relays: {}", e));
+        temp_relays.release().await;
+        result?
+    } else {
+        nostr_client::ensure_relays_ready(&client).await;
+        client
+            .fetch_events(filter, Duration::from_secs(10))
+            .await
+            .map_err(|e| format!("Failed to fetch votes: {}", e))?
+    };
+
+    Ok(deduplicate_votes(events.into_iter().collect()))
+}
+
+/// One vote per pubkey, latest timestamp wins
+pub fn deduplicate_votes(events: Vec<Event>) -> Vec<Event> {
+    let mut map: HashMap<PublicKey, Event> = HashMap::new();
+
+    for event in events {
+        match map.get(&event.pubkey) {
+            Some(existing) if existing.created_at >= event.created_at => {}
+            _ => {
+                map.insert(event.pubkey, event);
+            }
+        }
+    }
+
+    map.into_values().collect()
+}
+
+/// Live results for a poll as option ID -> vote count
+///
+/// Must be called from a component (e.g. inside `use_hook`): the signal and
+/// the listener task belong to the calling scope. The map stays empty until
+/// the existing votes are loaded, then updates as new votes arrive. Call
+/// `unsubscribe_poll_results` when the poll is no longer shown.
+pub fn subscribe_poll_results(poll_id: EventId) -> Signal<HashMap<String, usize>> {
+    let results = Signal::new(HashMap::new());
+
+    spawn(async move {
+        if let Err(e) = run_poll_subscription(poll_id, results).await {
+            log::warn!("Live poll results for {} stopped: {}", poll_id, e);
+        }
+    });
+
+    results
+}
+
+async fn run_poll_subscription(
+    poll_id: EventId,
+    mut results: Signal<HashMap<String, usize>>,
+) -> Result<(), String> {
+    let client = nostr_client::get_client().ok_or("Client not initialized")?;
+
+    let poll_filter = Filter::new().id(poll_id).kind(Kind::Poll).limit(1);
+    let poll_event = nostr_client::fetch_events_aggregated(poll_filter, Duration::from_secs(10))
+        .await?
+        .into_iter()
+        .next()
+        .ok_or("Poll not found")?;
+    let poll = Poll::from_event(&poll_event).map_err(|e| format!("Invalid poll: {}", e))?;
+
+    // Votes cast while the existing ones load are caught by the subscription;
+    // one seen twice is ignored by the tally
+    let fetched_at = Timestamp::now();
+    let mut tally = PollTally::from_poll(&poll);
+    for vote in fetch_poll_votes(poll_id, poll.ends_at, poll.relays.clone()).await? {
+        tally.add_vote(&vote);
+    }
+    results.set(tally.counts());
+
+    // Closed polls can't receive new votes
+    if poll.ends_at.is_some_and(|ends_at| ends_at <= Timestamp::now()) {
+        return Ok(());
+    }
+
+    // Listen before subscribing so no vote slips in between
+    let mut notifications = client.notifications();
+    let filter = Filter::new()
+        .kind(Kind::PollResponse)
+        .event(poll_id)
+        .since(fetched_at);
+
+    // NIP-88: votes are published to the poll's relays, so listen there
+    let (subscribed, relays) = if poll.relays.is_empty() {
+        (client.subscribe(filter, None).await, None)
+    } else {
+        let urls: Vec<String> = poll.relays.iter().map(|url| url.to_string()).collect();
+        let temp_relays = nostr_client::add_temp_relays(&client, &urls).await;
+        (client.subscribe_to(urls, filter, None).await, Some(temp_relays))
+    };
+    let sub_id = match subscribed {
+        Ok(output) => output.val,
+        Err(e) => {
+            if let Some(relays) = relays {
+                relays.release().await;
+            }
+            return Err(format!("Failed to subscribe to poll votes: {}", e));
+        }
+    };
+    POLL_SUBSCRIPTIONS
+        .write()
+        .insert(poll_id, PollSubscription { id: sub_id.clone(), relays });
+    log::info!("Live poll results subscription started for {}", poll_id);
+
+    while let Ok(notification) = notifications.recv().await {
+        if let RelayPoolNotification::Event { subscription_id, event, .. } = notification {
+            if subscription_id != sub_id {
+                continue;
+            }
+            if tally.add_vote(&event) {
+                results.set(tally.counts());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Close the live vote subscription for a poll
+pub async fn unsubscribe_poll_results(poll_id: EventId) {
+    let Some(subscription) = POLL_SUBSCRIPTIONS.write().remove(&poll_id) else {
+        return;
+    };
+
+    if let Some(client) = nostr_client::get_client() {
+        client.unsubscribe(&subscription.id).await;
+        log::info!("Live poll results subscription closed for {}", poll_id);
+    }
+    if let Some(relays) = subscription.relays {
+        relays.release().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::{EventBuilder, Keys, Tag, TagKind};
+
+    fn vote(keys: &Keys, poll_id: EventId, options: &[&str], created_at: u64) -> Event {
+        let mut tags = vec![Tag::event(poll_id)];
+        for option in options {
+            tags.push(Tag::custom(TagKind::custom("response"), vec![option.to_string()]));
+        }
+        EventBuilder::new(Kind::PollResponse, "")
+            .tags(tags)
+            .custom_created_at(Timestamp::from(created_at))
+            .sign_with_keys(keys)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_latest_vote_per_voter_wins() {
+        let poll_id = EventId::all_zeros();
+        let alice = Keys::generate();
+        let bob = Keys::generate();
+        let mut tally = PollTally::new(vec!["yes".to_string(), "no".to_string()], false, None);
+
+        assert!(tally.add_vote(&vote(&alice, poll_id, &["yes"], 100)));
+        assert!(tally.add_vote(&vote(&bob, poll_id, &["yes"], 100)));
+        // Alice changes her mind
+        assert!(tally.add_vote(&vote(&alice, poll_id, &["no"], 200)));
+        // An older vote arriving late doesn't override the newer one
+        assert!(!tally.add_vote(&vote(&alice, poll_id, &["yes"], 150)));
+
+        let counts = tally.counts();
+        assert_eq!(counts["yes"], 1);
+        assert_eq!(counts["no"], 1);
+        assert_eq!(tally.votes().len(), 2);
+    }
+
+    #[test]
+    fn test_unknown_options_and_late_votes_ignored() {
+        let poll_id = EventId::all_zeros();
+        let alice = Keys::generate();
+        let bob = Keys::generate();
+        let mut tally = PollTally::new(
+            vec!["a".to_string(), "b".to_string()],
+            true,
+            Some(Timestamp::from(500)),
+        );
+
+        assert!(tally.add_vote(&vote(&alice, poll_id, &["a", "zzz"], 100)));
+        // A newer vote naming no known option doesn't replace the valid one
+        assert!(!tally.add_vote(&vote(&alice, poll_id, &["zzz"], 200)));
+        // Cast after the poll closed
+        assert!(!tally.add_vote(&vote(&bob, poll_id, &["b"], 600)));
+
+        let counts = tally.counts();
+        assert_eq!(counts["a"], 1);
+        assert_eq!(counts["b"], 0);
+        assert!(!counts.contains_key("zzz"));
+    }
+}
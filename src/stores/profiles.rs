@@ -1,12 +1,14 @@
 use dioxus::prelude::*;
 use dioxus::signals::ReadableExt;
-use nostr_sdk::{Event, Filter, Kind, PublicKey, FromBech32};
+use nostr_sdk::{Event, EventId, Filter, Kind, PublicKey, FromBech32};
 use crate::stores::nostr_client;
 use std::time::Duration;
 use std::collections::{HashMap, HashSet};
 use std::num::NonZeroUsize;
 use lru::LruCache;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
 
 /// User profile metadata from Kind 0 events
 #[derive(Clone, Debug, PartialEq)]
@@ -78,8 +80,106 @@ impl Profile {
 pub static PROFILE_CACHE: GlobalSignal<LruCache<String, Profile>> =
     Signal::global(|| LruCache::new(NonZeroUsize::new(5000).unwrap()));
 
-/// Cache TTL in seconds (5 minutes)
-const CACHE_TTL_SECONDS: i64 = 300;
+/// How long a cached profile is used before it is refreshed from relays (1 hour)
+pub const PROFILE_CACHE_TTL_SECS: u64 = 3600;
+
+thread_local! {
+    /// Pubkeys with a background refresh in flight
+    static REFRESHING: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+}
+
+/// Profile metadata as persisted in the app cache database (`profiles` store)
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct StoredProfile {
+    /// Hex pubkey
+    pub pubkey: String,
+    /// Raw kind 0 content
+    pub metadata_json: String,
+    /// When the metadata was fetched from relays, Unix seconds
+    pub fetched_at: u64,
+}
+
+/// Whether something fetched at `fetched_at` is still within `ttl_secs`
+///
+/// Timestamps in the future (clock changes) count as fresh.
+pub fn is_profile_fresh(fetched_at: u64, now: u64, ttl_secs: u64) -> bool {
+    now.saturating_sub(fetched_at) < ttl_secs
+}
+
+fn is_cached_fresh(profile: &Profile) -> bool {
+    let fetched_at = profile.fetched_at.timestamp().max(0) as u64;
+    is_profile_fresh(fetched_at, Utc::now().timestamp() as u64, PROFILE_CACHE_TTL_SECS)
+}
+
+fn to_hex_pubkey(pubkey: &str) -> Option<String> {
+    PublicKey::from_bech32(pubkey)
+        .or_else(|_| PublicKey::from_hex(pubkey))
+        .ok()
+        .map(|pk| pk.to_hex())
+}
+
+/// Load a profile from IndexedDB if it is younger than the TTL
+async fn load_persisted_profile(pubkey: &str) -> Option<Profile> {
+    let hex = to_hex_pubkey(pubkey)?;
+    let cache_db = crate::stores::cache_database::get_cache_db().await.ok()?;
+    let stored = match cache_db.get_profile(&hex).await {
+        Ok(stored) => stored?,
+        Err(e) => {
+            log::debug!("Profile cache read failed for {}: {}", hex, e);
+            return None;
+        }
+    };
+
+    let now = Utc::now().timestamp() as u64;
+    if !is_profile_fresh(stored.fetched_at, now, PROFILE_CACHE_TTL_SECS) {
+        return None;
+    }
+
+    let fetched_at = Utc.timestamp_opt(stored.fetched_at as i64, 0).single().unwrap_or_else(Utc::now);
+    let profile = parse_profile_json(pubkey.to_string(), &stored.metadata_json, fetched_at).ok()?;
+    PROFILE_CACHE.write().put(pubkey.to_string(), profile.clone());
+    Some(profile)
+}
+
+/// Persist a kind 0 event in IndexedDB (in the background)
+fn persist_profile_event(event: &Event) {
+    let stored = StoredProfile {
+        pubkey: event.pubkey.to_hex(),
+        metadata_json: event.content.clone(),
+        fetched_at: Utc::now().timestamp() as u64,
+    };
+
+    dioxus_core::spawn_forever(async move {
+        match crate::stores::cache_database::get_cache_db().await {
+            Ok(cache_db) => {
+                if let Err(e) = cache_db.save_profile(&stored).await {
+                    log::debug!("Failed to cache profile {}: {}", stored.pubkey, e);
+                }
+            }
+            Err(e) => log::debug!("Profile cache unavailable: {}", e),
+        }
+    });
+}
+
+/// Refresh an expired profile in the background, at most once at a time per pubkey
+///
+/// A fresher IndexedDB entry (e.g. saved by another tab) is used if there is
+/// one, otherwise relays are asked.
+fn refresh_profile_in_background(pubkey: String) {
+    let started = REFRESHING.with(|refreshing| refreshing.borrow_mut().insert(pubkey.clone()));
+    if !started {
+        return;
+    }
+
+    dioxus_core::spawn_forever(async move {
+        if load_persisted_profile(&pubkey).await.is_none() {
+            if let Err(e) = fetch_profile_from_relays(pubkey.clone()).await {
+                log::debug!("Background profile refresh failed for {}: {}", pubkey, e);
+            }
+        }
+        REFRESHING.with(|refreshing| refreshing.borrow_mut().remove(&pubkey));
+    });
+}
 
 /// Get a profile from cache only (synchronous)
 pub fn get_profile(pubkey: &str) -> Option<nostr_sdk::Metadata> {
@@ -119,17 +219,26 @@ pub fn get_profile(pubkey: &str) -> Option<nostr_sdk::Metadata> {
     })
 }
 
-/// Fetch a profile from relays by pubkey
+/// Fetch a profile by pubkey: memory cache, then IndexedDB, then relays
 pub async fn fetch_profile(pubkey: String) -> Result<Profile, String> {
     // Check cache first
     if let Some(cached_profile) = PROFILE_CACHE.read().peek(&pubkey) {
-        let age = Utc::now().signed_duration_since(cached_profile.fetched_at);
-        if age.num_seconds() < CACHE_TTL_SECONDS {
+        if is_cached_fresh(cached_profile) {
             log::debug!("Using cached profile for {}", pubkey);
             return Ok(cached_profile.clone());
         }
     }
 
+    if let Some(profile) = load_persisted_profile(&pubkey).await {
+        log::debug!("Using IndexedDB cached profile for {}", pubkey);
+        return Ok(profile);
+    }
+
+    fetch_profile_from_relays(pubkey).await
+}
+
+/// Fetch a profile from relays, bypassing the caches
+async fn fetch_profile_from_relays(pubkey: String) -> Result<Profile, String> {
     log::info!("Fetching profile from database/relays for {}", pubkey);
 
     let public_key = PublicKey::from_bech32(&pubkey)
@@ -145,10 +254,11 @@ pub async fn fetch_profile(pubkey: String) -> Result<Profile, String> {
     match nostr_client::fetch_events_aggregated(filter, Duration::from_secs(10)).await {
         Ok(events) => {
             if let Some(event) = events.into_iter().next() {
-                let profile = parse_profile_event(&event)?;
+                let profile = parse_profile_json(pubkey.clone(), &event.content, Utc::now())?;
 
                 // Cache the profile
                 PROFILE_CACHE.write().put(pubkey.clone(), profile.clone());
+                persist_profile_event(&event);
 
                 Ok(profile)
             } else {
@@ -196,12 +306,16 @@ pub async fn fetch_profile(pubkey: String) -> Result<Profile, String> {
 
 /// Parse a Kind 0 event into a Profile struct
 fn parse_profile_event(event: &Event) -> Result<Profile, String> {
-    let content = &event.content;
+    parse_profile_json(event.pubkey.to_string(), &event.content, Utc::now())
+}
+
+/// Parse Kind 0 metadata JSON into a Profile struct
+fn parse_profile_json(pubkey: String, content: &str, fetched_at: DateTime<Utc>) -> Result<Profile, String> {
     let metadata: serde_json::Value = serde_json::from_str(content)
         .map_err(|e| format!("Failed to parse metadata JSON: {}", e))?;
 
     Ok(Profile {
-        pubkey: event.pubkey.to_string(),
+        pubkey,
         name: metadata.get("name").and_then(|v| v.as_str()).map(String::from),
         display_name: metadata.get("display_name").and_then(|v| v.as_str()).map(String::from),
         about: metadata.get("about").and_then(|v| v.as_str()).map(String::from),
@@ -210,13 +324,152 @@ fn parse_profile_event(event: &Event) -> Result<Profile, String> {
         nip05: metadata.get("nip05").and_then(|v| v.as_str()).map(String::from),
         lud16: metadata.get("lud16").and_then(|v| v.as_str()).map(String::from),
         website: metadata.get("website").and_then(|v| v.as_str()).map(String::from),
-        fetched_at: Utc::now(),
+        fetched_at,
     })
 }
 
+/// Profile fields the editor can change
+///
+/// Everything else in the kind 0 (lud06, keys set by other clients) is kept
+/// from the metadata the edits are applied to.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProfileFields {
+    pub name: String,
+    pub display_name: String,
+    pub about: String,
+    pub picture: String,
+    pub banner: String,
+    pub website: String,
+    pub nip05: String,
+    pub lud16: String,
+    /// NIP-24 `bot`: the account is automated
+    pub bot: bool,
+    /// NIP-24 `birthday` as `YYYY-MM-DD`; empty if unset or missing a part
+    pub birthday: String,
+    pub pronouns: String,
+}
+
+/// `YYYY-MM-DD` for a NIP-24 birthday object with all three parts
+fn birthday_to_date(birthday: &serde_json::Value) -> Option<String> {
+    let part = |key: &str| birthday.get(key).and_then(|v| v.as_u64());
+    Some(format!("{:04}-{:02}-{:02}", part("year")?, part("month")?, part("day")?))
+}
+
+/// NIP-24 birthday object from a `YYYY-MM-DD` date input
+fn date_to_birthday(date: &str) -> Option<serde_json::Value> {
+    let mut parts = date.trim().splitn(3, '-').map(|part| part.parse::<u64>().ok());
+    let (Some(Some(year)), Some(Some(month)), Some(Some(day))) = (parts.next(), parts.next(), parts.next()) else {
+        return None;
+    };
+    Some(serde_json::json!({ "year": year, "month": month, "day": day }))
+}
+
+impl ProfileFields {
+    pub fn from_metadata(metadata: &nostr_sdk::Metadata) -> Self {
+        let text = |value: &Option<String>| value.clone().unwrap_or_default();
+        ProfileFields {
+            name: text(&metadata.name),
+            display_name: text(&metadata.display_name),
+            about: text(&metadata.about),
+            picture: text(&metadata.picture),
+            banner: text(&metadata.banner),
+            website: text(&metadata.website),
+            nip05: text(&metadata.nip05),
+            lud16: text(&metadata.lud16),
+            bot: metadata.custom.get("bot").and_then(|v| v.as_bool()).unwrap_or(false),
+            birthday: metadata.custom.get("birthday").and_then(birthday_to_date).unwrap_or_default(),
+            pronouns: metadata
+                .custom
+                .get("pronouns")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+        }
+    }
+
+    /// Apply the edits on top of `metadata`, keeping the fields they don't cover
+    ///
+    /// Empty fields are removed, as are URLs that don't parse. A birthday the
+    /// date input can't show (one without a year, say) is left alone unless
+    /// a new date was picked.
+    pub fn apply(&self, mut metadata: nostr_sdk::Metadata) -> nostr_sdk::Metadata {
+        let text = |value: &str| Some(value.trim().to_string()).filter(|v| !v.is_empty());
+        let url = |value: &str| text(value).filter(|v| nostr_sdk::Url::parse(v).is_ok());
+
+        metadata.name = text(&self.name);
+        metadata.display_name = text(&self.display_name);
+        metadata.about = text(&self.about);
+        metadata.picture = url(&self.picture);
+        metadata.banner = url(&self.banner);
+        metadata.website = url(&self.website);
+        metadata.nip05 = text(&self.nip05);
+        metadata.lud16 = text(&self.lud16);
+
+        let mut set_custom = |key: &str, value: Option<serde_json::Value>| match value {
+            Some(value) => {
+                metadata.custom.insert(key.to_string(), value);
+            }
+            None => {
+                metadata.custom.remove(key);
+            }
+        };
+        set_custom("bot", self.bot.then_some(serde_json::Value::Bool(true)));
+        set_custom("pronouns", text(&self.pronouns).map(serde_json::Value::String));
+
+        let stored_birthday = metadata.custom.get("birthday").and_then(birthday_to_date).unwrap_or_default();
+        if self.birthday.trim() != stored_birthday {
+            match date_to_birthday(&self.birthday) {
+                Some(birthday) => metadata.custom.insert("birthday".to_string(), birthday),
+                None => metadata.custom.remove("birthday"),
+            };
+        }
+
+        metadata
+    }
+}
+
+/// Fetch the newest kind 0 of `pubkey` as-is, for editing
+///
+/// Unlike [`fetch_profile`] this asks relays directly, skipping the caches,
+/// and keeps every field, so saving an edit doesn't drop keys this client
+/// doesn't know about. None when no relay returned one.
+pub async fn fetch_metadata_for_edit(pubkey: &str) -> Result<Option<nostr_sdk::Metadata>, String> {
+    use nostr_sdk::JsonUtil;
+
+    let public_key = PublicKey::from_bech32(pubkey)
+        .or_else(|_| PublicKey::from_hex(pubkey))
+        .map_err(|e| format!("Invalid pubkey: {}", e))?;
+    let filter = Filter::new()
+        .kind(Kind::Metadata)
+        .author(public_key)
+        .limit(1);
+
+    let client = nostr_client::get_client().ok_or("Client not initialized")?;
+    let events = client
+        .fetch_events(filter, Duration::from_secs(10))
+        .await
+        .map_err(|e| format!("Failed to fetch profile: {}", e))?;
+    events
+        .iter()
+        .max_by_key(|event| event.created_at)
+        .map(|event| {
+            nostr_sdk::Metadata::from_json(&event.content)
+                .map_err(|e| format!("Failed to parse profile metadata: {}", e))
+        })
+        .transpose()
+}
+
 /// Get a profile from cache (if available)
+///
+/// Never waits on the network: an expired profile is still returned while a
+/// background refresh runs. On a miss, callers fall back to `fetch_profile`,
+/// which checks IndexedDB before asking relays.
 pub fn get_cached_profile(pubkey: &str) -> Option<Profile> {
-    PROFILE_CACHE.read().peek(pubkey).cloned()
+    let cached = PROFILE_CACHE.read().peek(pubkey).cloned()?;
+    if !is_cached_fresh(&cached) {
+        refresh_profile_in_background(pubkey.to_string());
+    }
+    Some(cached)
 }
 
 /// Fetch multiple profiles in a single query (much more efficient than individual fetches)
@@ -232,8 +485,7 @@ pub async fn fetch_profiles_batch(pubkeys: Vec<String>) -> Result<HashMap<String
     // Check cache first
     for pk in &pubkeys {
         if let Some(cached) = PROFILE_CACHE.read().peek(pk) {
-            let age = Utc::now().signed_duration_since(cached.fetched_at);
-            if age.num_seconds() < CACHE_TTL_SECONDS {
+            if is_cached_fresh(cached) {
                 results.insert(pk.clone(), cached.clone());
                 continue;
             }
@@ -270,6 +522,7 @@ pub async fn fetch_profiles_batch(pubkeys: Vec<String>) -> Result<HashMap<String
             for event in events {
                 if let Ok(profile) = parse_profile_event(&event) {
                     PROFILE_CACHE.write().put(profile.pubkey.clone(), profile.clone());
+                    persist_profile_event(&event);
                     results.insert(profile.pubkey.clone(), profile);
                 }
             }
@@ -314,8 +567,7 @@ pub async fn fetch_profiles_batch_native(pubkeys: HashSet<PublicKey>) -> Result<
         for &pk in &pubkeys {
             let pk_str = pk.to_string();
             if let Some(cached) = cache.peek(&pk_str) {
-                let age = Utc::now().signed_duration_since(cached.fetched_at);
-                if age.num_seconds() < CACHE_TTL_SECONDS {
+                if is_cached_fresh(cached) {
                     results.insert(pk, cached.clone());
                     continue;
                 }
@@ -374,6 +626,7 @@ pub async fn fetch_profiles_batch_native(pubkeys: HashSet<PublicKey>) -> Result<
                     if let Ok(profile) = parse_profile_event(&event) {
                         let pk = event.pubkey;
                         PROFILE_CACHE.write().put(profile.pubkey.clone(), profile.clone());
+                        persist_profile_event(&event);
                         results.insert(pk, profile);
                     }
                 }
@@ -387,3 +640,315 @@ pub async fn fetch_profiles_batch_native(pubkeys: HashSet<PublicKey>) -> Result<
 
     Ok(results)
 }
+
+// =============================================================================
+// NIP-58 badges
+// =============================================================================
+
+/// `d` tag of the profile badges list (kind 30008)
+const PROFILE_BADGES_D_TAG: &str = "profile_badges";
+
+/// A badge a user accepted: its definition address and the award event
+#[derive(Clone, Debug, PartialEq)]
+pub struct AcceptedBadge {
+    /// `30009:<issuer>:<d>` address of the badge definition
+    pub address: String,
+    pub award_id: EventId,
+}
+
+/// A badge to show on a profile
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfileBadge {
+    pub address: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    pub thumb: Option<String>,
+}
+
+/// Accepted badges in a profile badges event, in the user's order
+///
+/// The list is `a` (definition) / `e` (award) pairs; an `a` without an `e`
+/// right after it is skipped.
+pub fn parse_profile_badges(event: &Event) -> Vec<AcceptedBadge> {
+    let mut accepted = Vec::new();
+    let mut pending_address: Option<String> = None;
+
+    for tag in event.tags.iter() {
+        let parts = tag.as_slice();
+        match (parts.first().map(|s| s.as_str()), parts.get(1)) {
+            (Some("a"), Some(address)) if address.starts_with("30009:") => {
+                pending_address = Some(address.clone());
+            }
+            (Some("e"), Some(id)) => {
+                if let (Some(address), Ok(award_id)) = (pending_address.take(), EventId::from_hex(id)) {
+                    accepted.push(AcceptedBadge { address, award_id });
+                }
+            }
+            _ => pending_address = None,
+        }
+    }
+
+    accepted
+}
+
+fn tag_value(event: &Event, name: &str) -> Option<String> {
+    event.tags.iter().find_map(|tag| {
+        let parts = tag.as_slice();
+        (parts.first().map(|s| s.as_str()) == Some(name))
+            .then(|| parts.get(1).cloned())
+            .flatten()
+            .filter(|value| !value.trim().is_empty())
+    })
+}
+
+/// Resolve accepted badges against fetched awards and definitions
+///
+/// A badge is shown only when its award was issued to `owner` by the
+/// definition's author for that definition, and the definition was found.
+/// Anything else is skipped.
+pub fn resolve_badges(
+    owner: &PublicKey,
+    accepted: &[AcceptedBadge],
+    awards: &[Event],
+    definitions: &[Event],
+) -> Vec<ProfileBadge> {
+    accepted
+        .iter()
+        .filter_map(|badge| {
+            let mut address_parts = badge.address.splitn(3, ':');
+            let (_, issuer, identifier) = (address_parts.next()?, address_parts.next()?, address_parts.next()?);
+            let issuer = PublicKey::from_hex(issuer).ok()?;
+
+            let award = awards.iter().find(|award| award.id == badge.award_id)?;
+            let award_valid = award.kind == Kind::BadgeAward
+                && award.pubkey == issuer
+                && award.tags.public_keys().any(|pk| pk == owner)
+                && award.tags.iter().any(|tag| {
+                    let parts = tag.as_slice();
+                    parts.first().map(|s| s.as_str()) == Some("a") && parts.get(1) == Some(&badge.address)
+                });
+            if !award_valid {
+                return None;
+            }
+
+            let definition = definitions
+                .iter()
+                .filter(|d| {
+                    d.kind == Kind::BadgeDefinition && d.pubkey == issuer && d.tags.identifier() == Some(identifier)
+                })
+                .max_by_key(|d| d.created_at)?;
+
+            Some(ProfileBadge {
+                address: badge.address.clone(),
+                name: tag_value(definition, "name").unwrap_or_else(|| identifier.to_string()),
+                description: tag_value(definition, "description"),
+                image: tag_value(definition, "image"),
+                thumb: tag_value(definition, "thumb"),
+            })
+        })
+        .collect()
+}
+
+/// Badges `pubkey` has accepted in their profile badges list
+///
+/// Badges whose award or definition can't be fetched are left out.
+pub async fn fetch_profile_badges(pubkey: String) -> Result<Vec<ProfileBadge>, String> {
+    let owner = to_hex_pubkey(&pubkey)
+        .and_then(|hex| PublicKey::from_hex(&hex).ok())
+        .ok_or_else(|| format!("Invalid pubkey: {}", pubkey))?;
+
+    let list_filter = Filter::new()
+        .author(owner)
+        .kind(Kind::ProfileBadges)
+        .identifier(PROFILE_BADGES_D_TAG)
+        .limit(1);
+    let lists = nostr_client::fetch_events_aggregated(list_filter, Duration::from_secs(5)).await?;
+    let Some(list) = lists.iter().max_by_key(|event| event.created_at) else {
+        return Ok(Vec::new());
+    };
+
+    let accepted = parse_profile_badges(list);
+    if accepted.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let award_filter = Filter::new()
+        .kind(Kind::BadgeAward)
+        .ids(accepted.iter().map(|badge| badge.award_id));
+    let awards = nostr_client::fetch_events_aggregated(award_filter, Duration::from_secs(5))
+        .await
+        .unwrap_or_default();
+
+    let mut issuers = HashSet::new();
+    let mut identifiers = HashSet::new();
+    for badge in &accepted {
+        let mut parts = badge.address.splitn(3, ':').skip(1);
+        if let (Some(Ok(issuer)), Some(identifier)) = (parts.next().map(PublicKey::from_hex), parts.next()) {
+            issuers.insert(issuer);
+            identifiers.insert(identifier.to_string());
+        }
+    }
+    let definition_filter = Filter::new()
+        .kind(Kind::BadgeDefinition)
+        .authors(issuers)
+        .identifiers(identifiers);
+    let definitions = nostr_client::fetch_events_aggregated(definition_filter, Duration::from_secs(5))
+        .await
+        .unwrap_or_default();
+
+    Ok(resolve_badges(&owner, &accepted, &awards, &definitions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_ttl_expiry() {
+        let ttl = PROFILE_CACHE_TTL_SECS;
+        assert!(is_profile_fresh(1_000, 1_000, ttl));
+        assert!(is_profile_fresh(1_000, 1_000 + ttl - 1, ttl));
+        assert!(!is_profile_fresh(1_000, 1_000 + ttl, ttl));
+        // Fetched "in the future" after a clock change
+        assert!(is_profile_fresh(5_000, 1_000, ttl));
+        // A zero TTL disables caching
+        assert!(!is_profile_fresh(1_000, 1_000, 0));
+    }
+
+    #[test]
+    fn test_stored_profile_round_trip() {
+        let stored = StoredProfile {
+            pubkey: "ab".repeat(32),
+            metadata_json: r#"{"name":"alice","picture":"https://example.com/a.png"}"#.to_string(),
+            fetched_at: 1_700_000_000,
+        };
+        let json = serde_json::to_string(&stored).unwrap();
+        let restored: StoredProfile = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, stored);
+
+        let fetched_at = Utc.timestamp_opt(stored.fetched_at as i64, 0).single().unwrap();
+        let profile = parse_profile_json(stored.pubkey.clone(), &stored.metadata_json, fetched_at).unwrap();
+        assert_eq!(profile.name.as_deref(), Some("alice"));
+        assert_eq!(profile.fetched_at.timestamp(), 1_700_000_000);
+    }
+
+    #[test]
+    fn test_profile_edit_keeps_unknown_fields() {
+        use nostr_sdk::JsonUtil;
+
+        let metadata = nostr_sdk::Metadata::from_json(
+            r#"{"name":"alice","lud06":"lnurl1xyz","birthday":{"month":4,"day":1},"client_theme":{"accent":"purple"}}"#,
+        )
+        .unwrap();
+
+        let mut fields = ProfileFields::from_metadata(&metadata);
+        assert_eq!(fields.name, "alice");
+        // No year, so the date input starts empty
+        assert_eq!(fields.birthday, "");
+
+        fields.name = "Alice".to_string();
+        fields.pronouns = "she/her".to_string();
+        fields.bot = true;
+        fields.website = "not a url".to_string();
+
+        let saved = nostr_sdk::Metadata::from_json(fields.apply(metadata).as_json()).unwrap();
+        assert_eq!(saved.name.as_deref(), Some("Alice"));
+        assert_eq!(saved.website, None);
+        assert_eq!(saved.lud06.as_deref(), Some("lnurl1xyz"));
+        assert_eq!(saved.custom["client_theme"], serde_json::json!({ "accent": "purple" }));
+        assert_eq!(saved.custom["birthday"], serde_json::json!({ "month": 4, "day": 1 }));
+        assert_eq!(saved.custom["pronouns"], "she/her");
+        assert_eq!(saved.custom["bot"], true);
+
+        let mut fields = ProfileFields::from_metadata(&saved);
+        fields.birthday = "1990-04-01".to_string();
+        fields.bot = false;
+        let saved = fields.apply(saved);
+        assert_eq!(saved.custom["birthday"], serde_json::json!({ "year": 1990, "month": 4, "day": 1 }));
+        assert!(!saved.custom.contains_key("bot"));
+    }
+
+    #[test]
+    fn test_profile_fields_create_new_profile() {
+        use nostr_sdk::{EventBuilder, JsonUtil, Keys};
+
+        let fields = ProfileFields {
+            name: "alice".to_string(),
+            about: "First post soon".to_string(),
+            website: "https://example.com".to_string(),
+            ..Default::default()
+        };
+        let event = EventBuilder::metadata(&fields.apply(nostr_sdk::Metadata::new()))
+            .sign_with_keys(&Keys::generate())
+            .unwrap();
+
+        assert_eq!(event.kind, Kind::Metadata);
+        assert!(event.verify().is_ok());
+        let saved = nostr_sdk::Metadata::from_json(&event.content).unwrap();
+        assert_eq!(saved.name.as_deref(), Some("alice"));
+        assert_eq!(saved.about.as_deref(), Some("First post soon"));
+        assert_eq!(saved.website.as_deref(), Some("https://example.com"));
+        assert_eq!(saved.picture, None);
+        assert!(saved.custom.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_accepted_badges() {
+        use nostr_sdk::{EventBuilder, Keys, Tag};
+
+        let tag = |parts: &[&str]| Tag::parse(parts.iter().map(|p| p.to_string()).collect::<Vec<_>>()).unwrap();
+        let issuer = Keys::generate();
+        let user = Keys::generate();
+        let definition = |d: &str, name: &str| {
+            EventBuilder::new(Kind::BadgeDefinition, "")
+                .tags(vec![tag(&["d", d]), tag(&["name", name]), tag(&["image", "https://example.com/badge.png", "1024x1024"])])
+                .sign_with_keys(&issuer)
+                .unwrap()
+        };
+        let address = |d: &str| format!("30009:{}:{}", issuer.public_key().to_hex(), d);
+        let award = |d: &str, to: &PublicKey| {
+            EventBuilder::new(Kind::BadgeAward, "")
+                .tags(vec![tag(&["a", &address(d)]), tag(&["p", &to.to_hex()])])
+                .sign_with_keys(&issuer)
+                .unwrap()
+        };
+
+        let early = definition("early", "Early Adopter");
+        let zapper = definition("zapper", "Zapper");
+        let early_award = award("early", &user.public_key());
+        let zapper_award = award("zapper", &user.public_key());
+        let lost_award = award("lost", &user.public_key());
+        let someone_elses = award("early", &Keys::generate().public_key());
+        let unaccepted = award("zapper", &user.public_key());
+
+        let list = EventBuilder::new(Kind::ProfileBadges, "")
+            .tags(vec![
+                tag(&["d", "profile_badges"]),
+                tag(&["a", &address("zapper")]),
+                tag(&["e", &zapper_award.id.to_hex()]),
+                tag(&["a", &address("early")]),
+                tag(&["e", &early_award.id.to_hex()]),
+                // Definition can't be fetched
+                tag(&["a", &address("lost")]),
+                tag(&["e", &lost_award.id.to_hex()]),
+                // Award for someone else
+                tag(&["a", &address("early")]),
+                tag(&["e", &someone_elses.id.to_hex()]),
+                // Dangling `a` without its award
+                tag(&["a", &address("zapper")]),
+            ])
+            .sign_with_keys(&user)
+            .unwrap();
+
+        let accepted = parse_profile_badges(&list);
+        assert_eq!(accepted.len(), 4);
+
+        let awards = vec![early_award, zapper_award, lost_award, someone_elses, unaccepted];
+        let badges = resolve_badges(&user.public_key(), &accepted, &awards, &[early, zapper]);
+        let names: Vec<&str> = badges.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["Zapper", "Early Adopter"]);
+        assert_eq!(badges[0].image.as_deref(), Some("https://example.com/badge.png"));
+        assert_eq!(badges[0].thumb, None);
+    }
+}
@@ -1,8 +1,10 @@
 /// NIP-78: Preferred Reactions Storage
 /// Stores user's preferred reaction emojis on Nostr relays using kind 30078 events
 use dioxus::prelude::*;
-use nostr_sdk::{EventBuilder, Filter, Kind, Tag, FromBech32};
+use nostr_sdk::{Event, EventBuilder, EventId, Filter, Kind, PublicKey, Tag, Timestamp, FromBech32};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
 use std::time::Duration;
 
 use crate::stores::{auth_store, nostr_client};
@@ -235,3 +237,206 @@ pub async fn save_preferred_reactions(reactions: Vec<PreferredReaction>) -> Resu
 
     Ok(())
 }
+
+/// Most reactions fetched for an event
+pub const MAX_REACTIONS_FETCH: usize = 500;
+
+/// How long fetched reactions are reused, in seconds
+const REACTIONS_CACHE_SECS: u64 = 60;
+
+#[derive(Clone)]
+struct CachedReactions {
+    events: Vec<Event>,
+    fetched_at: u64,
+}
+
+/// Reactions per event, shared by the reaction count and the breakdown
+static REACTIONS_CACHE: OnceLock<Mutex<HashMap<EventId, CachedReactions>>> = OnceLock::new();
+
+fn reactions_cache() -> &'static Mutex<HashMap<EventId, CachedReactions>> {
+    REACTIONS_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Everyone who reacted to an event with the same emoji (NIP-25)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReactionGroup {
+    pub emoji: ReactionEmoji,
+    pub count: usize,
+    /// Reactors, most recent first
+    pub pubkeys: Vec<PublicKey>,
+}
+
+/// The emoji a kind 7 reaction event reacts with
+///
+/// Empty content and `+` are a like, `-` a dislike. `:shortcode:` resolves
+/// to a NIP-30 custom emoji when the event has a matching `emoji` tag.
+pub fn reaction_emoji(event: &Event) -> ReactionEmoji {
+    let content = event.content.trim();
+    match content {
+        "" | "+" => ReactionEmoji::Like,
+        "-" => ReactionEmoji::Unlike,
+        _ if content.len() > 2 && content.starts_with(':') && content.ends_with(':') => {
+            let shortcode = &content[1..content.len() - 1];
+            let url = event.tags.iter().find_map(|tag| {
+                let parts = tag.as_slice();
+                (parts.len() >= 3 && parts[0] == "emoji" && parts[1] == shortcode)
+                    .then(|| parts[2].clone())
+            });
+            match url {
+                Some(url) => ReactionEmoji::Custom { shortcode: shortcode.to_string(), url },
+                None => ReactionEmoji::Standard(content.to_string()),
+            }
+        }
+        _ => ReactionEmoji::Standard(content.to_string()),
+    }
+}
+
+/// Group reactions by emoji, most used first
+///
+/// Only each user's latest reaction counts, so changing a reaction doesn't
+/// count twice.
+pub fn group_reactions(events: &[Event]) -> Vec<ReactionGroup> {
+    let mut events: Vec<&Event> = events.iter().filter(|e| e.kind == Kind::Reaction).collect();
+    events.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+
+    let mut seen = HashSet::new();
+    let mut groups: Vec<ReactionGroup> = Vec::new();
+    let mut index_by_content: HashMap<String, usize> = HashMap::new();
+
+    for event in events {
+        if !seen.insert(event.pubkey) {
+            continue;
+        }
+        let emoji = reaction_emoji(event);
+        let index = *index_by_content.entry(emoji.content()).or_insert_with(|| {
+            groups.push(ReactionGroup { emoji, count: 0, pubkeys: Vec::new() });
+            groups.len() - 1
+        });
+        groups[index].count += 1;
+        groups[index].pubkeys.push(event.pubkey);
+    }
+
+    // Stable, so ties keep the most recently used emoji first
+    groups.sort_by(|a, b| b.count.cmp(&a.count));
+    groups
+}
+
+/// Number of users whose latest reaction is positive (anything but `-`)
+pub fn positive_total(groups: &[ReactionGroup]) -> usize {
+    groups
+        .iter()
+        .filter(|group| group.emoji != ReactionEmoji::Unlike)
+        .map(|group| group.count)
+        .sum()
+}
+
+/// Fetch the reactions to an event, reusing a recent fetch
+pub async fn fetch_reactions(event_id: EventId) -> Result<Vec<Event>, String> {
+    let now = Timestamp::now().as_u64();
+    let cached = reactions_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&event_id)
+        .filter(|cached| now.saturating_sub(cached.fetched_at) < REACTIONS_CACHE_SECS)
+        .cloned();
+    if let Some(cached) = cached {
+        return Ok(cached.events);
+    }
+
+    let filter = Filter::new()
+        .kind(Kind::Reaction)
+        .event(event_id)
+        .limit(MAX_REACTIONS_FETCH);
+    let events = nostr_client::fetch_events_aggregated(filter, Duration::from_secs(5)).await?;
+    reactions_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(event_id, CachedReactions { events: events.clone(), fetched_at: now });
+    Ok(events)
+}
+
+/// Drop the cached reactions of an event, e.g. after the user reacted
+pub fn invalidate_reactions(event_id: &EventId) {
+    reactions_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .remove(event_id);
+}
+
+/// Fetch the reactions to an event, grouped by emoji
+pub async fn get_reaction_breakdown(event_id: EventId) -> Result<Vec<ReactionGroup>, String> {
+    Ok(group_reactions(&fetch_reactions(event_id).await?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::Keys;
+
+    fn reaction(keys: &Keys, content: &str, tags: Vec<Vec<&str>>, created_at: u64) -> Event {
+        let tags: Vec<Tag> = tags.into_iter().map(|tag| Tag::parse(tag).unwrap()).collect();
+        EventBuilder::new(Kind::Reaction, content)
+            .tags(tags)
+            .custom_created_at(Timestamp::from(created_at))
+            .sign_with_keys(keys)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_group_reactions_by_emoji() {
+        let (alice, bob, carol, dave) = (Keys::generate(), Keys::generate(), Keys::generate(), Keys::generate());
+        let soapbox = vec![vec!["emoji", "soapbox", "https://example.com/soapbox.png"]];
+        let events = vec![
+            reaction(&alice, "+", vec![], 100),
+            reaction(&bob, "", vec![], 101),
+            reaction(&carol, "🔥", vec![], 102),
+            reaction(&dave, ":soapbox:", soapbox, 103),
+            // Alice changed her mind: only her latest reaction counts
+            reaction(&alice, "-", vec![], 104),
+        ];
+
+        let groups = group_reactions(&events);
+        let summary: Vec<(String, usize)> = groups.iter().map(|g| (g.emoji.content(), g.count)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("-".to_string(), 1),
+                (":soapbox:".to_string(), 1),
+                ("🔥".to_string(), 1),
+                ("+".to_string(), 1),
+            ]
+        );
+        assert_eq!(groups[0].emoji, ReactionEmoji::Unlike);
+        assert_eq!(groups[0].pubkeys, vec![alice.public_key()]);
+        // Alice's dislike isn't part of the total shown on the button
+        assert_eq!(positive_total(&groups), 3);
+        assert_eq!(groups[3].emoji, ReactionEmoji::Like);
+        assert_eq!(groups[3].pubkeys, vec![bob.public_key()]);
+        assert_eq!(
+            groups[1].emoji,
+            ReactionEmoji::Custom {
+                shortcode: "soapbox".to_string(),
+                url: "https://example.com/soapbox.png".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_group_reactions_counts_and_order() {
+        let keys: Vec<Keys> = (0..4).map(|_| Keys::generate()).collect();
+        let events = vec![
+            reaction(&keys[0], "🔥", vec![], 100),
+            reaction(&keys[1], "+", vec![], 101),
+            reaction(&keys[2], "🔥", vec![], 102),
+            // No emoji tag: the shortcode is shown as text
+            reaction(&keys[3], ":unknown:", vec![], 103),
+        ];
+
+        let groups = group_reactions(&events);
+        assert_eq!(groups[0].emoji, ReactionEmoji::Standard("🔥".to_string()));
+        assert_eq!(groups[0].count, 2);
+        assert_eq!(groups[0].pubkeys, vec![keys[2].public_key(), keys[0].public_key()]);
+        assert_eq!(groups[1].emoji, ReactionEmoji::Standard(":unknown:".to_string()));
+        assert_eq!(groups[2].emoji, ReactionEmoji::Like);
+    }
+}
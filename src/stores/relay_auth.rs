@@ -0,0 +1,254 @@
+//! NIP-42 relay authentication
+//!
+//! Relays can challenge us with AUTH before serving DMs or accepting events.
+//! Answering reveals our pubkey to the relay, so challenges are only answered
+//! for relays the user allowed in Settings. Challenges from other relays are
+//! remembered and answered as soon as the relay is allowed.
+
+use std::collections::HashMap;
+
+use dioxus::prelude::*;
+use dioxus::signals::ReadableExt;
+use gloo_storage::{LocalStorage, Storage};
+use nostr_sdk::{Client, Event, EventBuilder, RelayUrl};
+
+use crate::stores::nostr_client;
+
+const STORAGE_KEY: &str = "nostr_blue_auth_relays";
+
+/// Prefix of OK/CLOSED messages rejecting an unauthenticated request
+const AUTH_REQUIRED_PREFIX: &str = "auth-required:";
+
+/// Where a relay stands with NIP-42 authentication
+#[derive(Clone, Debug, PartialEq)]
+pub enum RelayAuthState {
+    /// Asked for AUTH, waiting for the user to allow the relay
+    AwaitingApproval,
+    Authenticated,
+    Failed(String),
+}
+
+/// Relays the user allowed to authenticate with
+pub static AUTH_ALLOWED_RELAYS: GlobalSignal<Vec<String>> = Signal::global(load_allowed_relays);
+
+/// Authentication state per relay (only relays that asked for AUTH)
+pub static RELAY_AUTH_STATE: GlobalSignal<HashMap<String, RelayAuthState>> = Signal::global(HashMap::new);
+
+/// Latest unanswered challenge per relay
+static PENDING_CHALLENGES: GlobalSignal<HashMap<String, String>> = Signal::global(HashMap::new);
+
+fn relay_key(url: &str) -> String {
+    url.trim().trim_end_matches('/').to_string()
+}
+
+fn load_allowed_relays() -> Vec<String> {
+    LocalStorage::get::<Vec<String>>(STORAGE_KEY).unwrap_or_default()
+}
+
+/// Whether the user allowed `relay_url` to authenticate us
+pub fn is_auth_allowed(relay_url: &str) -> bool {
+    let key = relay_key(relay_url);
+    AUTH_ALLOWED_RELAYS.read().iter().any(|allowed| *allowed == key)
+}
+
+/// Allow or disallow authenticating with a relay
+///
+/// Allowing a relay answers its pending challenge, if it sent one.
+pub fn set_auth_allowed(relay_url: &str, allowed: bool) {
+    let key = relay_key(relay_url);
+    {
+        let mut relays = AUTH_ALLOWED_RELAYS.write();
+        relays.retain(|existing| *existing != key);
+        if allowed {
+            relays.push(key.clone());
+        }
+        if let Err(e) = LocalStorage::set(STORAGE_KEY, &*relays) {
+            log::warn!("Failed to save AUTH relays: {}", e);
+        }
+    }
+
+    if !allowed {
+        return;
+    }
+    let Some(challenge) = PENDING_CHALLENGES.read().get(&key).cloned() else {
+        return;
+    };
+    let (Some(client), Ok(relay_url)) = (nostr_client::get_client(), RelayUrl::parse(&key)) else {
+        return;
+    };
+    spawn(async move {
+        answer_challenge(&client, relay_url, challenge).await;
+    });
+}
+
+/// The unsigned kind 22242 event answering `challenge` from `relay_url`
+pub fn auth_event_builder(challenge: &str, relay_url: &RelayUrl) -> EventBuilder {
+    EventBuilder::auth(challenge, relay_url.clone())
+}
+
+/// Handle an AUTH challenge, answering it only if the relay is allowed
+pub async fn handle_auth_challenge(client: &Client, relay_url: RelayUrl, challenge: String) {
+    let key = relay_key(relay_url.as_str());
+    PENDING_CHALLENGES.write().insert(key.clone(), challenge.clone());
+
+    if !is_auth_allowed(&key) {
+        log::info!("{} requested AUTH; not answering until the relay is allowed", key);
+        RELAY_AUTH_STATE.write().insert(key, RelayAuthState::AwaitingApproval);
+        return;
+    }
+
+    answer_challenge(client, relay_url, challenge).await;
+}
+
+async fn answer_challenge(client: &Client, relay_url: RelayUrl, challenge: String) {
+    let key = relay_key(relay_url.as_str());
+    let state = match authenticate(client, &relay_url, &challenge).await {
+        Ok(()) => {
+            log::info!("Authenticated with {}", key);
+            PENDING_CHALLENGES.write().remove(&key);
+            RelayAuthState::Authenticated
+        }
+        Err(e) => {
+            log::warn!("AUTH with {} failed: {}", key, e);
+            RelayAuthState::Failed(e)
+        }
+    };
+    RELAY_AUTH_STATE.write().insert(key, state);
+}
+
+async fn authenticate(client: &Client, relay_url: &RelayUrl, challenge: &str) -> Result<(), String> {
+    if !*nostr_client::HAS_SIGNER.read() {
+        return Err("Relay requires authentication but no signer is attached".to_string());
+    }
+
+    let event = client
+        .sign_event_builder(auth_event_builder(challenge, relay_url))
+        .await
+        .map_err(|e| format!("Failed to sign AUTH event: {}", e))?;
+    let relay = client
+        .relay(relay_url.as_str())
+        .await
+        .map_err(|e| format!("Relay not in pool: {}", e))?;
+    relay
+        .auth(event)
+        .await
+        .map_err(|e| format!("Relay rejected AUTH: {}", e))
+}
+
+/// Record an OK/CLOSED rejection that asked for authentication
+///
+/// Such requests failed silently before; this makes them visible in
+/// Settings next to the relay.
+pub fn note_auth_rejection(relay_url: &RelayUrl, message: &str) {
+    if !message.starts_with(AUTH_REQUIRED_PREFIX) {
+        return;
+    }
+
+    let key = relay_key(relay_url.as_str());
+    let state = if !*nostr_client::HAS_SIGNER.read() {
+        RelayAuthState::Failed("Relay requires authentication but no signer is attached".to_string())
+    } else if !is_auth_allowed(&key) {
+        RelayAuthState::AwaitingApproval
+    } else {
+        RelayAuthState::Failed(message.trim_start_matches(AUTH_REQUIRED_PREFIX).trim().to_string())
+    };
+    log::warn!("{} rejected a request: {}", key, message);
+    RELAY_AUTH_STATE.write().insert(key, state);
+}
+
+/// Relays that rejected an event only because we weren't authenticated
+pub fn auth_required_relays<'a>(failed: impl IntoIterator<Item = (&'a RelayUrl, &'a String)>) -> Vec<RelayUrl> {
+    failed
+        .into_iter()
+        .filter(|(_, message)| message.starts_with(AUTH_REQUIRED_PREFIX))
+        .map(|(relay_url, _)| relay_url.clone())
+        .collect()
+}
+
+/// How long to wait for a relay to accept our AUTH before giving up, in ms
+const AUTH_WAIT_MS: u32 = 3_000;
+const AUTH_POLL_MS: u32 = 250;
+
+/// Send `event` again to relays that rejected it with `auth-required`
+///
+/// NIP-70 protected events are refused until we authenticate as the author.
+/// Only relays the user allowed to authenticate are retried, once their AUTH
+/// goes through. Returns the relays that accepted the event on retry.
+pub async fn retry_after_auth(client: &Client, event: &Event, relays: Vec<RelayUrl>) -> Vec<RelayUrl> {
+    let mut accepted = Vec::new();
+    for relay_url in relays {
+        let key = relay_key(relay_url.as_str());
+        if !is_auth_allowed(&key) {
+            log::info!("{} wants AUTH for this event; allow it in Settings to publish there", key);
+            continue;
+        }
+
+        // The rejection usually comes with a challenge that is being answered now
+        let mut waited = 0;
+        while RELAY_AUTH_STATE.peek().get(&key) != Some(&RelayAuthState::Authenticated) && waited < AUTH_WAIT_MS {
+            gloo_timers::future::TimeoutFuture::new(AUTH_POLL_MS).await;
+            waited += AUTH_POLL_MS;
+        }
+        if RELAY_AUTH_STATE.peek().get(&key) != Some(&RelayAuthState::Authenticated) {
+            log::warn!("{} didn't authenticate us; not retrying", key);
+            continue;
+        }
+
+        match client.send_event_to([relay_url.clone()], event).await {
+            Ok(output) if output.success.contains(&relay_url) => {
+                log::info!("{} accepted event {} after AUTH", key, event.id);
+                accepted.push(relay_url);
+            }
+            Ok(output) => log::warn!("{} still rejected event {}: {:?}", key, event.id, output.failed.get(&relay_url)),
+            Err(e) => log::warn!("Retry to {} failed: {}", key, e),
+        }
+    }
+    accepted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::{Keys, Kind};
+
+    #[test]
+    fn test_auth_event_has_relay_and_challenge_tags() {
+        let relay_url = RelayUrl::parse("wss://auth.example.com").unwrap();
+        let event = auth_event_builder("challenge-123", &relay_url)
+            .sign_with_keys(&Keys::generate())
+            .unwrap();
+
+        assert_eq!(event.kind, Kind::Authentication);
+        assert_eq!(event.kind.as_u16(), 22242);
+
+        let tag_values = |name: &str| -> Vec<String> {
+            event
+                .tags
+                .iter()
+                .filter(|tag| tag.as_slice().first().map(|s| s.as_str()) == Some(name))
+                .filter_map(|tag| tag.as_slice().get(1).cloned())
+                .collect()
+        };
+        assert_eq!(tag_values("challenge"), vec!["challenge-123"]);
+        assert_eq!(
+            tag_values("relay").iter().map(|url| relay_key(url)).collect::<Vec<_>>(),
+            vec!["wss://auth.example.com"]
+        );
+        assert!(event.verify().is_ok());
+    }
+
+    #[test]
+    fn test_auth_required_relays_picks_only_auth_rejections() {
+        let protected = RelayUrl::parse("wss://protected.example.com").unwrap();
+        let paid = RelayUrl::parse("wss://paid.example.com").unwrap();
+        let blocked = RelayUrl::parse("wss://blocked.example.com").unwrap();
+        let failed = HashMap::from([
+            (protected.clone(), "auth-required: this event may only be published by its author".to_string()),
+            (paid.clone(), "restricted: not a paying member".to_string()),
+            (blocked.clone(), "blocked: you are banned, auth-required: won't help".to_string()),
+        ]);
+
+        assert_eq!(auth_required_relays(&failed), vec![protected]);
+        assert!(auth_required_relays(&HashMap::<RelayUrl, String>::new()).is_empty());
+    }
+}
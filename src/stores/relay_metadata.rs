@@ -8,8 +8,10 @@ use dioxus::prelude::*;
 use dioxus::signals::ReadableExt;
 use nostr_sdk::{Client, EventBuilder, Filter, Kind, PublicKey, Tag, TagKind};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::Duration;
+use crate::stores::nostr_client;
 
 #[cfg(target_arch = "wasm32")]
 use js_sys;
@@ -60,35 +62,40 @@ pub fn default_relays() -> Vec<RelayConfig> {
 /// - ["r", "wss://relay.url"] = both read and write
 /// - ["r", "wss://relay.url", "read"] = read only
 /// - ["r", "wss://relay.url", "write"] = write only
+///
+/// Duplicate URLs (ignoring a trailing slash) are merged, combining their permissions.
 pub fn parse_relay_list_event(event: &nostr_sdk::Event) -> Vec<RelayConfig> {
-    let mut relays = Vec::new();
+    let mut relays: Vec<RelayConfig> = Vec::new();
 
     for tag in event.tags.iter() {
-        // Try to extract relay URL from the tag
-        // For NIP-65, we're looking for tags like ["r", "wss://relay.url", "read"|"write"]
-        if let Some(standardized) = tag.as_standardized() {
-            // Check if this is a Relay tag
-            if let nostr_sdk::TagStandard::Relay(relay_url) = standardized {
-                log::debug!("Found relay tag: {}", relay_url);
-                relays.push(RelayConfig {
-                    url: relay_url.to_string(),
-                    read: true,
-                    write: true,
-                });
-                continue;
-            }
+        // Read the raw tag: the SDK may standardize 'r' tags as references
+        let slice = tag.as_slice();
+        if slice.first().map(|k| k.as_str()) != Some("r") {
+            continue;
+        }
+        let Some(url) = slice.get(1).map(|u| u.trim().trim_end_matches('/')) else {
+            continue;
+        };
+        if !url.starts_with("wss://") && !url.starts_with("ws://") {
+            log::debug!("Skipping non-relay 'r' tag: {}", url);
+            continue;
         }
 
-        // Fallback: try parsing as custom 'r' tag
-        if tag.kind() == TagKind::Custom("r".into()) {
-            if let Some(url) = tag.content() {
-                log::debug!("Found 'r' tag: {}", url);
-                relays.push(RelayConfig {
-                    url: url.to_string(),
-                    read: true,
-                    write: true,
-                });
-            }
+        let (read, write) = match slice.get(2).map(|m| m.as_str()) {
+            Some("read") => (true, false),
+            Some("write") => (false, true),
+            _ => (true, true),
+        };
+
+        if let Some(existing) = relays.iter_mut().find(|r| r.url == url) {
+            existing.read |= read;
+            existing.write |= write;
+        } else {
+            relays.push(RelayConfig {
+                url: url.to_string(),
+                read,
+                write,
+            });
         }
     }
 
@@ -96,17 +103,72 @@ pub fn parse_relay_list_event(event: &nostr_sdk::Event) -> Vec<RelayConfig> {
     relays
 }
 
+/// Write (outbox) relays from a relay list
+pub fn write_relays(relays: &[RelayConfig]) -> Vec<String> {
+    relays.iter().filter(|r| r.write).map(|r| r.url.clone()).collect()
+}
+
+/// Relay hints for sharing an event: where it was seen, else the user's write relays
+pub fn share_relay_hints(seen_relays: &[String]) -> Vec<String> {
+    if !seen_relays.is_empty() {
+        return seen_relays.to_vec();
+    }
+    USER_RELAY_METADATA
+        .read()
+        .as_ref()
+        .map(|metadata| write_relays(&metadata.relays))
+        .unwrap_or_default()
+}
+
+/// Relays offered by the composer's "post to" selector: the user's write relays
+pub fn publish_relay_choices() -> Vec<String> {
+    USER_RELAY_METADATA
+        .read()
+        .as_ref()
+        .map(|metadata| write_relays(&metadata.relays))
+        .filter(|relays| !relays.is_empty())
+        .unwrap_or_else(|| write_relays(&default_relays()))
+}
+
+/// Relay URLs to publish to for a "post to" selection
+///
+/// Trailing slashes are trimmed, non-websocket URLs dropped and duplicates
+/// removed, keeping the selection's order.
+pub fn resolve_publish_relays(selected: &[String]) -> Vec<String> {
+    let mut relays: Vec<String> = Vec::new();
+    for url in selected {
+        let url = url.trim().trim_end_matches('/');
+        if !url.starts_with("wss://") && !url.starts_with("ws://") {
+            continue;
+        }
+        if !relays.iter().any(|r| r == url) {
+            relays.push(url.to_string());
+        }
+    }
+    relays
+}
+
 /// Parse DM relay list from kind 10050 event
 /// NIP-17 tag format: ["relay", "wss://relay.url"]
+///
+/// Non-websocket URLs are skipped and duplicates (ignoring a trailing slash) dropped.
 pub fn parse_dm_relay_list(event: &nostr_sdk::Event) -> Vec<String> {
-    let mut dm_relays = Vec::new();
+    let mut dm_relays: Vec<String> = Vec::new();
 
     for tag in event.tags.iter() {
-        // Check if this is a custom "relay" tag
-        if tag.kind() == TagKind::Custom("relay".into()) {
-            if let Some(content) = tag.content() {
-                dm_relays.push(content.to_string());
-            }
+        // Read the raw tag: the SDK may standardize "relay" tags
+        let slice = tag.as_slice();
+        if slice.first().map(|k| k.as_str()) != Some("relay") {
+            continue;
+        }
+        let Some(url) = slice.get(1).map(|u| u.trim().trim_end_matches('/')) else {
+            continue;
+        };
+        if !url.starts_with("wss://") && !url.starts_with("ws://") {
+            continue;
+        }
+        if !dm_relays.iter().any(|existing| existing == url) {
+            dm_relays.push(url.to_string());
         }
     }
 
@@ -314,3 +376,187 @@ pub async fn init_user_relay_lists(client: Arc<Client>) -> Result<(), String> {
         Ok(())
     })
 }
+
+/// Most of an author's write relays queried for their events
+const MAX_AUTHOR_WRITE_RELAYS: usize = 5;
+
+/// Resolved outbox relay sets per author (hex pubkey), kept for the session
+static AUTHOR_RELAYS_CACHE: OnceLock<Mutex<HashMap<String, Vec<String>>>> = OnceLock::new();
+
+fn author_relays_cache() -> &'static Mutex<HashMap<String, Vec<String>>> {
+    AUTHOR_RELAYS_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Merge an author's write relays with our own relays
+///
+/// The author's relays come first (capped at `MAX_AUTHOR_WRITE_RELAYS`);
+/// URLs are deduplicated ignoring a trailing slash.
+pub fn merge_relay_sets(author_write: &[String], local: &[String]) -> Vec<String> {
+    let mut merged: Vec<String> = Vec::new();
+    let authors = author_write.iter().take(MAX_AUTHOR_WRITE_RELAYS);
+
+    for url in authors.chain(local.iter()) {
+        let url = url.trim().trim_end_matches('/');
+        if url.is_empty() || merged.iter().any(|existing| existing == url) {
+            continue;
+        }
+        merged.push(url.to_string());
+    }
+    merged
+}
+
+/// Relays to read an author's events from: their NIP-65 write relays plus ours
+///
+/// Authors without a relay list get the default relays. The result is cached
+/// per author for the session.
+pub async fn resolve_author_relays(author: PublicKey, client: Arc<Client>) -> Vec<String> {
+    let key = author.to_hex();
+    if let Some(cached) = author_relays_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&key)
+    {
+        return cached.clone();
+    }
+
+    let filter = Filter::new().author(author).kind(Kind::RelayList).limit(1);
+    let author_write = match client.fetch_events(filter, Duration::from_secs(5)).await {
+        Ok(events) => events
+            .into_iter()
+            .max_by_key(|event| event.created_at)
+            .map(|event| write_relays(&parse_relay_list_event(&event)))
+            .unwrap_or_default(),
+        Err(e) => {
+            log::warn!("Failed to fetch relay list for {}: {}", key, e);
+            Vec::new()
+        }
+    };
+
+    let author_write = if author_write.is_empty() {
+        log::debug!("No write relays published by {}, using defaults", key);
+        write_relays(&default_relays())
+    } else {
+        author_write
+    };
+
+    // Relays other fetches borrowed for the moment aren't ours
+    let local: Vec<String> = client
+        .relays()
+        .await
+        .keys()
+        .map(|url| url.to_string())
+        .filter(|url| !nostr_client::is_temp_relay(url))
+        .collect();
+    let merged = merge_relay_sets(&author_write, &local);
+    log::info!("Resolved {} outbox relays for {}", merged.len(), key);
+
+    author_relays_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(key, merged.clone());
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::Keys;
+
+    fn relay_list_event(tags: Vec<Vec<&str>>) -> nostr_sdk::Event {
+        list_event(Kind::RelayList, tags)
+    }
+
+    fn list_event(kind: Kind, tags: Vec<Vec<&str>>) -> nostr_sdk::Event {
+        let tags: Vec<Tag> = tags
+            .into_iter()
+            .map(|t| Tag::parse(t.into_iter().map(String::from).collect::<Vec<_>>()).unwrap())
+            .collect();
+        EventBuilder::new(kind, "")
+            .tags(tags)
+            .sign_with_keys(&Keys::generate())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_markers_and_duplicates() {
+        let event = relay_list_event(vec![
+            vec!["r", "wss://both.example.com"],
+            vec!["r", "wss://read.example.com", "read"],
+            vec!["r", "wss://write.example.com", "write"],
+            // Duplicate of the read-only relay with a write marker and trailing slash
+            vec!["r", "wss://read.example.com/", "write"],
+            // Duplicate with no marker stays read+write
+            vec!["r", "wss://both.example.com", "read"],
+        ]);
+
+        let relays = parse_relay_list_event(&event);
+        assert_eq!(
+            relays,
+            vec![
+                RelayConfig { url: "wss://both.example.com".to_string(), read: true, write: true },
+                RelayConfig { url: "wss://read.example.com".to_string(), read: true, write: true },
+                RelayConfig { url: "wss://write.example.com".to_string(), read: false, write: true },
+            ]
+        );
+        assert_eq!(write_relays(&relays).len(), 3);
+    }
+
+    #[test]
+    fn test_parse_dm_relay_list() {
+        let event = list_event(
+            Kind::from(10050),
+            vec![
+                vec!["relay", "wss://inbox.example.com"],
+                vec!["relay", "wss://dm.example.com/"],
+                // Duplicate of the first with a trailing slash
+                vec!["relay", "wss://inbox.example.com/"],
+                vec!["relay", "https://not-a-relay.example.com"],
+                vec!["r", "wss://general.example.com"],
+            ],
+        );
+
+        assert_eq!(
+            parse_dm_relay_list(&event),
+            vec!["wss://inbox.example.com", "wss://dm.example.com"]
+        );
+    }
+
+    #[test]
+    fn test_merge_relay_sets() {
+        let author = vec![
+            "wss://author.example.com/".to_string(),
+            "wss://shared.example.com".to_string(),
+            "wss://author.example.com".to_string(),
+        ];
+        let local = vec!["wss://shared.example.com/".to_string(), "wss://mine.example.com".to_string()];
+
+        assert_eq!(
+            merge_relay_sets(&author, &local),
+            vec!["wss://author.example.com", "wss://shared.example.com", "wss://mine.example.com"]
+        );
+
+        // No relay list: just our own relays
+        assert_eq!(merge_relay_sets(&[], &local), vec!["wss://shared.example.com", "wss://mine.example.com"]);
+
+        // Authors with very long lists are capped
+        let many: Vec<String> = (0..10).map(|i| format!("wss://r{}.example.com", i)).collect();
+        assert_eq!(merge_relay_sets(&many, &[]).len(), MAX_AUTHOR_WRITE_RELAYS);
+    }
+
+    #[test]
+    fn test_resolve_publish_relays() {
+        let selected = vec![
+            "wss://community.example.com/".to_string(),
+            " wss://nos.lol ".to_string(),
+            "https://not-a-relay.example.com".to_string(),
+            "wss://community.example.com".to_string(),
+            "".to_string(),
+        ];
+
+        assert_eq!(
+            resolve_publish_relays(&selected),
+            vec!["wss://community.example.com", "wss://nos.lol"]
+        );
+        assert!(resolve_publish_relays(&[]).is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+//! Scheduled Notes - notes signed now and published later
+//!
+//! A scheduled note is signed immediately with its publication time as
+//! `created_at`, then queued on the pending-event queue (IndexedDB
+//! `pending_events`) with `publish_at` set. A background checker publishes
+//! queued notes once they are due, reusing the queue's retry handling.
+
+use chrono::{Local, NaiveDateTime, TimeZone};
+use dioxus::prelude::*;
+
+use crate::stores::cashu::events::{
+    due_events, process_pending_events, queue_scheduled_event, remove_pending_event,
+};
+use crate::stores::cashu::internal::get_shared_localstore;
+use crate::stores::cashu::{PendingEventType, PendingNostrEvent, PENDING_NOSTR_EVENTS};
+use crate::stores::nostr_client;
+use crate::utils::time::now_secs;
+
+/// How often queued notes are checked for being due
+const CHECK_INTERVAL_MS: u32 = 30_000;
+
+/// Whether the background checker is running
+static CHECKER_STARTED: GlobalSignal<bool> = Signal::global(|| false);
+
+/// Parse a `datetime-local` input value (local time) into a Unix timestamp
+pub fn parse_schedule_input(value: &str) -> Result<u64, String> {
+    let naive = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M")
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S"))
+        .map_err(|_| "Pick a date and time to schedule the note".to_string())?;
+
+    let local = Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .ok_or("That time doesn't exist in your timezone")?;
+
+    u64::try_from(local.timestamp()).map_err(|_| "Invalid scheduled time".to_string())
+}
+
+/// Check that a scheduled time lies in the future
+pub fn validate_publish_at(publish_at: u64, now: u64) -> Result<(), String> {
+    if publish_at <= now {
+        return Err("Scheduled time must be in the future".to_string());
+    }
+    Ok(())
+}
+
+/// Sign a note for `publish_at` and queue it
+///
+/// Returns the queue ID of the scheduled note.
+pub async fn schedule_note(content: String, publish_at: u64) -> Result<String, String> {
+    validate_publish_at(publish_at, now_secs())?;
+
+    // Make sure the queue is persisted even if the wallet was never opened
+    get_shared_localstore().await?;
+
+    let event = nostr_client::sign_scheduled_note(content, publish_at).await?;
+    let event_json = serde_json::to_string(&event)
+        .map_err(|e| format!("Failed to serialize scheduled note: {}", e))?;
+
+    let queue_id = queue_scheduled_event(event_json, PendingEventType::ScheduledNote, publish_at).await?;
+    log::info!("Scheduled note {} for {}", event.id, publish_at);
+
+    start_scheduled_notes_checker();
+    Ok(queue_id)
+}
+
+/// Notes still waiting for their publication time, soonest first
+pub fn get_scheduled_notes() -> Vec<PendingNostrEvent> {
+    let mut notes: Vec<PendingNostrEvent> = PENDING_NOSTR_EVENTS
+        .read()
+        .iter()
+        .filter(|event| matches!(event.event_type, PendingEventType::ScheduledNote))
+        .cloned()
+        .collect();
+    notes.sort_by_key(|event| event.publish_at);
+    notes
+}
+
+/// Drop a scheduled note before it is published
+pub async fn cancel_scheduled_note(queue_id: &str) -> Result<(), String> {
+    remove_pending_event(queue_id).await
+}
+
+/// Load queued notes from IndexedDB into the in-memory queue
+async fn load_scheduled_notes() -> Result<(), String> {
+    let localstore = get_shared_localstore().await?;
+    let stored = localstore
+        .get_all_pending_events()
+        .await
+        .map_err(|e| format!("Failed to load scheduled notes: {}", e))?;
+
+    let mut queue = PENDING_NOSTR_EVENTS.write();
+    for event in stored {
+        if matches!(event.event_type, PendingEventType::ScheduledNote)
+            && !queue.iter().any(|queued| queued.id == event.id)
+        {
+            queue.push(event);
+        }
+    }
+    Ok(())
+}
+
+/// Start the background task publishing due scheduled notes
+///
+/// Safe to call repeatedly; only the first call starts the task.
+pub fn start_scheduled_notes_checker() {
+    if *CHECKER_STARTED.read() {
+        return;
+    }
+    *CHECKER_STARTED.write() = true;
+
+    dioxus_core::spawn_forever(async move {
+        loop {
+            // Reload each round: the wallet resets the in-memory queue on logout
+            if let Err(e) = load_scheduled_notes().await {
+                log::warn!("{}", e);
+            }
+
+            let due_notes = due_events(&get_scheduled_notes(), now_secs());
+            if !due_notes.is_empty() {
+                log::info!("{} scheduled notes due, publishing", due_notes.len());
+                if let Err(e) = process_pending_events().await {
+                    log::error!("Failed to publish scheduled notes: {}", e);
+                }
+            }
+
+            gloo_timers::future::TimeoutFuture::new(CHECK_INTERVAL_MS).await;
+        }
+    });
+
+    log::info!("Started scheduled notes checker");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_publish_at() {
+        assert!(validate_publish_at(1_000, 999).is_ok());
+        assert!(validate_publish_at(1_000, 1_000).is_err());
+        assert!(validate_publish_at(1_000, 2_000).is_err());
+    }
+
+    #[test]
+    fn test_parse_schedule_input() {
+        let parsed = parse_schedule_input("2030-01-02T03:04").unwrap();
+        let expected = Local
+            .with_ymd_and_hms(2030, 1, 2, 3, 4, 0)
+            .earliest()
+            .unwrap()
+            .timestamp() as u64;
+        assert_eq!(parsed, expected);
+        assert!(parse_schedule_input("").is_err());
+    }
+}
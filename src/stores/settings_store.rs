@@ -2,12 +2,13 @@
 /// Stores user settings on Nostr relays using kind 30078 events
 use dioxus::prelude::*;
 use dioxus::signals::ReadableExt;
-use nostr_sdk::{EventBuilder, Filter, Kind, Tag, FromBech32};
+use nostr_sdk::{Event, EventBuilder, Filter, Kind, Tag, FromBech32};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 use crate::stores::{auth_store, nostr_client, theme_store, blossom_store};
 use crate::stores::blossom_store::BlossomServersStoreStoreExt;
+use crate::services::trending::TrendingConfig;
 
 /// App settings stored on Nostr via NIP-78
 /// Note: Relay configuration is now stored via NIP-65 (kind 10002) and NIP-17 (kind 10050)
@@ -21,10 +22,134 @@ pub struct AppSettings {
     pub sync_notifications: bool, // Sync notification read status across devices via NIP-78
     #[serde(default)]
     pub payment_method_preference: String, // "nwc_first", "webln_first", "manual_only", "always_ask"
+    #[serde(default = "default_fetch_timeout_secs")]
+    pub fetch_timeout_secs: u64, // Relay fetch timeout in seconds (5-30)
+    #[serde(default)]
+    pub muted_words: Vec<MutedWord>, // Keywords hiding notes from feeds
+    #[serde(default)]
+    pub trending: TrendingConfig, // Trending window and ranking weights
+    #[serde(default = "default_strip_image_metadata")]
+    pub strip_image_metadata: bool, // Remove EXIF/GPS data from images before upload
+    #[serde(default = "default_share_music_status")]
+    pub share_music_status: bool, // Publish a NIP-38 "music" status while playing
+    #[serde(default = "default_zap_presets")]
+    pub zap_presets: Vec<u64>, // Amount buttons in the zap dialog, in sats
+    #[serde(default)]
+    pub fiat_currency: String, // Currency for fiat equivalents of zaps; empty hides them
+    #[serde(default)]
+    pub default_zap_comment: String, // Prefilled message in the zap dialog
+    #[serde(default)]
+    pub anonymous_zaps: bool, // Zap anonymously by default (NIP-57 anon zap requests)
+    #[serde(default = "default_translation_language")]
+    pub translation_language: String, // ISO 639-1 target language for note translations
+    #[serde(default)]
+    pub auto_sweep_dust: bool, // Consolidate Cashu dust automatically after receiving or minting
     #[serde(default)]
     pub version: u32, // Settings schema version
 }
 
+/// A muted keyword; a leading `#` mutes the hashtag
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MutedWord {
+    pub word: String,
+    /// Only match whole words ("art" doesn't mute "party")
+    #[serde(default)]
+    pub whole_word: bool,
+}
+
+impl MutedWord {
+    /// Whether this mute matches the text (case-insensitive)
+    pub fn matches_text(&self, text: &str) -> bool {
+        let needle = self.word.trim().to_lowercase();
+        if needle.is_empty() {
+            return false;
+        }
+        let haystack = text.to_lowercase();
+
+        if !self.whole_word {
+            return haystack.contains(&needle);
+        }
+
+        let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+        haystack.match_indices(&needle).any(|(start, found)| {
+            let before = haystack[..start].chars().next_back();
+            let after = haystack[start + found.len()..].chars().next();
+            !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+        })
+    }
+
+    /// Whether this mute matches one of a note's `t` tags
+    pub fn matches_hashtag(&self, hashtag: &str) -> bool {
+        let word = self.word.trim().trim_start_matches('#');
+        if word.is_empty() {
+            return false;
+        }
+        let hashtag = hashtag.trim_start_matches('#').to_lowercase();
+        let word = word.to_lowercase();
+        if self.whole_word {
+            hashtag == word
+        } else {
+            hashtag.contains(&word)
+        }
+    }
+}
+
+fn default_fetch_timeout_secs() -> u64 {
+    nostr_client::DEFAULT_FETCH_TIMEOUT_SECS
+}
+
+fn default_strip_image_metadata() -> bool {
+    true
+}
+
+fn default_share_music_status() -> bool {
+    true
+}
+
+fn default_translation_language() -> String {
+    "en".to_string()
+}
+
+fn default_zap_presets() -> Vec<u64> {
+    vec![21, 100, 1000]
+}
+
+/// Most preset buttons the zap dialog shows
+pub const MAX_ZAP_PRESETS: usize = 6;
+
+/// Parse zap presets typed as "21, 100, 1000"
+///
+/// Commas or spaces separate amounts. Duplicates are dropped and the rest
+/// sorted ascending.
+pub fn parse_zap_presets(input: &str) -> Result<Vec<u64>, String> {
+    let mut presets = Vec::new();
+    for part in input.split(|c: char| c == ',' || c.is_whitespace()).filter(|p| !p.is_empty()) {
+        let amount = part
+            .parse::<u64>()
+            .ok()
+            .filter(|amount| *amount > 0)
+            .ok_or_else(|| format!("\"{}\" is not a whole number of sats", part))?;
+        if !presets.contains(&amount) {
+            presets.push(amount);
+        }
+    }
+    if presets.is_empty() {
+        return Err("Enter at least one amount".to_string());
+    }
+    if presets.len() > MAX_ZAP_PRESETS {
+        return Err(format!("At most {} amounts", MAX_ZAP_PRESETS));
+    }
+    presets.sort_unstable();
+    Ok(presets)
+}
+
+impl AppSettings {
+    /// Relay fetch timeout in seconds, clamped to the supported range
+    pub fn fetch_timeout(&self) -> u64 {
+        nostr_client::clamp_fetch_timeout_secs(self.fetch_timeout_secs)
+    }
+}
+
 impl Default for AppSettings {
     fn default() -> Self {
         Self {
@@ -33,7 +158,18 @@ impl Default for AppSettings {
             blossom_servers: vec![blossom_store::DEFAULT_SERVER.to_string()],
             sync_notifications: false, // Privacy-first: opt-in by default
             payment_method_preference: "nwc_first".to_string(), // Default to NWC if connected
-            version: 3, // Incremented for payment_method_preference addition
+            fetch_timeout_secs: default_fetch_timeout_secs(),
+            muted_words: Vec::new(),
+            trending: TrendingConfig::default(),
+            strip_image_metadata: default_strip_image_metadata(), // Privacy-first: on by default
+            share_music_status: default_share_music_status(),
+            zap_presets: default_zap_presets(),
+            fiat_currency: String::new(),
+            default_zap_comment: String::new(),
+            anonymous_zaps: false,
+            translation_language: default_translation_language(),
+            auto_sweep_dust: false,
+            version: 4, // Incremented for fetch_timeout_secs addition
         }
     }
 }
@@ -108,6 +244,9 @@ pub async fn load_settings() -> Result<(), String> {
                             *blossom_store::BLOSSOM_SERVERS.read().data().write() = settings.blossom_servers.clone();
                         }
 
+                        // Apply relay fetch timeout
+                        nostr_client::set_default_fetch_timeout(settings.fetch_timeout());
+
                         // Update global settings
                         SETTINGS.write().clone_from(&settings);
                         SETTINGS_LOADING.write().clone_from(&false);
@@ -213,3 +352,247 @@ pub async fn update_payment_method_preference(preference: String) {
         log::error!("Failed to save payment method preference: {}", e);
     }
 }
+
+/// Update relay fetch timeout (clamped to 5-30 seconds) and save to Nostr
+pub async fn update_fetch_timeout(secs: u64) {
+    let secs = nostr_client::clamp_fetch_timeout_secs(secs);
+
+    // Apply immediately, even if saving fails
+    nostr_client::set_default_fetch_timeout(secs);
+
+    let mut settings = SETTINGS.read().clone();
+    settings.fetch_timeout_secs = secs;
+    SETTINGS.write().fetch_timeout_secs = secs;
+
+    // Save to Nostr
+    if let Err(e) = save_settings(&settings).await {
+        log::error!("Failed to save fetch timeout setting: {}", e);
+    }
+}
+
+/// Update the trending window and weights and save to Nostr
+pub async fn update_trending_config(config: TrendingConfig) {
+    SETTINGS.write().trending = config;
+    let settings = SETTINGS.read().clone();
+
+    // Save to Nostr
+    if let Err(e) = save_settings(&settings).await {
+        log::error!("Failed to save trending settings: {}", e);
+    }
+}
+
+/// Update whether images are stripped of metadata before upload and save to Nostr
+pub async fn update_strip_image_metadata(enabled: bool) {
+    SETTINGS.write().strip_image_metadata = enabled;
+    let settings = SETTINGS.read().clone();
+
+    // Save to Nostr
+    if let Err(e) = save_settings(&settings).await {
+        log::error!("Failed to save image metadata setting: {}", e);
+    }
+}
+
+/// Update whether the music player publishes a "now playing" status and save to Nostr
+pub async fn update_share_music_status(enabled: bool) {
+    SETTINGS.write().share_music_status = enabled;
+    let settings = SETTINGS.read().clone();
+
+    // Save to Nostr
+    if let Err(e) = save_settings(&settings).await {
+        log::error!("Failed to save music status setting: {}", e);
+    }
+}
+
+/// Update the zap dialog's preset amounts and save to Nostr
+pub async fn update_zap_presets(presets: Vec<u64>) {
+    SETTINGS.write().zap_presets = presets;
+    let settings = SETTINGS.read().clone();
+
+    // Save to Nostr
+    if let Err(e) = save_settings(&settings).await {
+        log::error!("Failed to save zap presets: {}", e);
+    }
+}
+
+/// Update the currency for fiat zap amounts ("" to hide them) and save to Nostr
+pub async fn update_fiat_currency(currency: String) {
+    SETTINGS.write().fiat_currency = currency;
+    let settings = SETTINGS.read().clone();
+
+    // Save to Nostr
+    if let Err(e) = save_settings(&settings).await {
+        log::error!("Failed to save fiat currency: {}", e);
+    }
+}
+
+/// Update the zap dialog's default comment and anonymous choice and save to Nostr
+pub async fn update_zap_defaults(comment: String, anonymous: bool) {
+    {
+        let mut settings = SETTINGS.write();
+        settings.default_zap_comment = comment.trim().to_string();
+        settings.anonymous_zaps = anonymous;
+    }
+    let settings = SETTINGS.read().clone();
+
+    // Save to Nostr
+    if let Err(e) = save_settings(&settings).await {
+        log::error!("Failed to save zap defaults: {}", e);
+    }
+}
+
+/// Update the language notes are translated into and save to Nostr
+pub async fn update_translation_language(language: String) {
+    SETTINGS.write().translation_language = language;
+    let settings = SETTINGS.read().clone();
+
+    // Save to Nostr
+    if let Err(e) = save_settings(&settings).await {
+        log::error!("Failed to save translation language: {}", e);
+    }
+}
+
+/// Update whether Cashu dust is swept automatically and save to Nostr
+pub async fn update_auto_sweep_dust(enabled: bool) {
+    SETTINGS.write().auto_sweep_dust = enabled;
+    let settings = SETTINGS.read().clone();
+
+    // Save to Nostr
+    if let Err(e) = save_settings(&settings).await {
+        log::error!("Failed to save dust sweep setting: {}", e);
+    }
+}
+
+/// Whether text matches any muted keyword
+pub fn is_muted_by_keyword(content: &str) -> bool {
+    SETTINGS.read().muted_words.iter().any(|muted| muted.matches_text(content))
+}
+
+/// Whether a note's content or hashtags (`t` tags) match any muted keyword
+pub fn is_event_muted_by_keyword(event: &Event) -> bool {
+    let settings = SETTINGS.read();
+    if settings.muted_words.is_empty() {
+        return false;
+    }
+    event_matches_muted_words(event, &settings.muted_words)
+}
+
+fn event_matches_muted_words(event: &Event, muted_words: &[MutedWord]) -> bool {
+    muted_words.iter().any(|muted| {
+        muted.matches_text(&event.content)
+            || event.tags.hashtags().any(|hashtag| muted.matches_hashtag(hashtag))
+    })
+}
+
+/// Add a muted keyword (ignored if empty or already muted) and save to Nostr
+pub async fn add_muted_word(word: String, whole_word: bool) -> Result<(), String> {
+    let word = word.trim().to_string();
+    if word.is_empty() {
+        return Err("Enter a word to mute".to_string());
+    }
+
+    let mut settings = SETTINGS.read().clone();
+    if settings.muted_words.iter().any(|muted| muted.word.eq_ignore_ascii_case(&word)) {
+        return Ok(());
+    }
+    settings.muted_words.push(MutedWord { word, whole_word });
+    SETTINGS.write().muted_words = settings.muted_words.clone();
+
+    save_settings(&settings).await
+}
+
+/// Remove a muted keyword and save to Nostr
+pub async fn remove_muted_word(word: String) -> Result<(), String> {
+    let mut settings = SETTINGS.read().clone();
+    settings.muted_words.retain(|muted| muted.word != word);
+    SETTINGS.write().muted_words = settings.muted_words.clone();
+
+    save_settings(&settings).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fetch_timeout_read_from_settings() {
+        let settings: AppSettings = serde_json::from_str(
+            r#"{"theme":"dark","fetch_timeout_secs":20,"version":4}"#,
+        ).unwrap();
+        assert_eq!(settings.fetch_timeout(), 20);
+    }
+
+    #[test]
+    fn test_fetch_timeout_defaults_for_older_settings() {
+        let settings: AppSettings = serde_json::from_str(r#"{"theme":"dark","version":3}"#).unwrap();
+        assert_eq!(settings.fetch_timeout(), nostr_client::DEFAULT_FETCH_TIMEOUT_SECS);
+        assert_eq!(settings.trending, TrendingConfig::default());
+        // Metadata stripping stays on for settings saved before it existed
+        assert!(settings.strip_image_metadata);
+    }
+
+    fn muted(word: &str, whole_word: bool) -> MutedWord {
+        MutedWord { word: word.to_string(), whole_word }
+    }
+
+    #[test]
+    fn test_muted_word_whole_word_vs_substring() {
+        let substring = muted("Art", false);
+        assert!(substring.matches_text("What a PARTY"));
+        assert!(substring.matches_text("art."));
+
+        let whole = muted("Art", true);
+        assert!(!whole.matches_text("What a PARTY"));
+        assert!(whole.matches_text("Modern ART, again"));
+        assert!(whole.matches_text("art"));
+        assert!(!whole.matches_text("art_deco"));
+    }
+
+    #[test]
+    fn test_muted_word_unicode_and_hashtags() {
+        let whole = muted("café", true);
+        assert!(whole.matches_text("☕ CAFÉ ☕"));
+        assert!(!whole.matches_text("cafés 🎉"));
+        assert!(!muted("🎉", true).matches_text("ünïcödé"));
+        assert!(!muted("  ", false).matches_text("anything"));
+
+        let hashtag = muted("#Bitcoin", true);
+        assert!(hashtag.matches_text("gm #bitcoin"));
+        assert!(hashtag.matches_hashtag("bitcoin"));
+        assert!(!hashtag.matches_hashtag("bitcoiner"));
+
+        let keys = nostr_sdk::Keys::generate();
+        let event = EventBuilder::text_note("no tags in the text")
+            .tag(Tag::hashtag("bitcoin"))
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert!(event_matches_muted_words(&event, &[hashtag]));
+        assert!(!event_matches_muted_words(&event, &[muted("nostr", false)]));
+    }
+
+    #[test]
+    fn test_parse_zap_presets() {
+        assert_eq!(parse_zap_presets("21, 100, 1000"), Ok(vec![21, 100, 1000]));
+        assert_eq!(parse_zap_presets(" 1000 21,,100 21 "), Ok(vec![21, 100, 1000]));
+        assert!(parse_zap_presets("21, lots").is_err());
+        assert!(parse_zap_presets("0").is_err());
+        assert!(parse_zap_presets("-5").is_err());
+        assert!(parse_zap_presets("  ").is_err());
+        assert!(parse_zap_presets("1 2 3 4 5 6 7").is_err());
+
+        // Settings saved before presets existed get the defaults
+        let settings: AppSettings = serde_json::from_str(r#"{"theme":"dark","version":4}"#).unwrap();
+        assert_eq!(settings.zap_presets, vec![21, 100, 1000]);
+        assert!(settings.fiat_currency.is_empty());
+    }
+
+    #[test]
+    fn test_fetch_timeout_out_of_range_clamps() {
+        let mut settings = AppSettings::default();
+
+        settings.fetch_timeout_secs = 1;
+        assert_eq!(settings.fetch_timeout(), nostr_client::MIN_FETCH_TIMEOUT_SECS);
+
+        settings.fetch_timeout_secs = 120;
+        assert_eq!(settings.fetch_timeout(), nostr_client::MAX_FETCH_TIMEOUT_SECS);
+    }
+}
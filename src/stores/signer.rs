@@ -217,3 +217,70 @@ pub async fn restore_session() -> Result<(), String> {
     }
     Ok(())
 }
+
+/// Decrypt NIP-44 or NIP-04 content for `public_key`
+///
+/// Older wallet events and DMs may still be NIP-04 encrypted. The scheme is
+/// told from the payload: NIP-04 ciphertext carries an `?iv=` suffix, which
+/// NIP-44's base64 can't contain, so each payload is decrypted once (one
+/// prompt with a remote signer). Legacy content is only read here; whatever
+/// gets written back is encrypted with NIP-44 by the caller.
+pub async fn decrypt_with_fallback<T>(signer: &T, public_key: &PublicKey, content: &str) -> Result<String, String>
+where
+    T: ?Sized + nostr::signer::NostrSigner,
+{
+    if is_nip04_payload(content) {
+        let decrypted = signer
+            .nip04_decrypt(public_key, content)
+            .await
+            .map_err(|e| format!("NIP-04: {}", e))?;
+        log::info!("Decrypted legacy content with NIP-04");
+        return Ok(decrypted);
+    }
+
+    let decrypted = signer
+        .nip44_decrypt(public_key, content)
+        .await
+        .map_err(|e| format!("NIP-44: {}", e))?;
+    log::debug!("Decrypted content with NIP-44");
+    Ok(decrypted)
+}
+
+/// Whether `content` is a NIP-04 payload (`<base64>?iv=<base64>`)
+fn is_nip04_payload(content: &str) -> bool {
+    content.contains("?iv=")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::nips::{nip04, nip44};
+    use crate::utils::testing::block_on;
+
+    #[test]
+    fn test_decrypt_falls_back_to_nip04() {
+        let alice = Keys::generate();
+        let bob = Keys::generate();
+
+        let legacy = nip04::encrypt(alice.secret_key(), &bob.public_key(), "old secret").unwrap();
+        let decrypted = block_on(decrypt_with_fallback(&bob, &alice.public_key(), &legacy));
+        assert_eq!(decrypted.unwrap(), "old secret");
+
+        let current = nip44::encrypt(alice.secret_key(), &bob.public_key(), "new secret", nip44::Version::default()).unwrap();
+        let decrypted = block_on(decrypt_with_fallback(&bob, &alice.public_key(), &current));
+        assert_eq!(decrypted.unwrap(), "new secret");
+
+        assert!(block_on(decrypt_with_fallback(&bob, &alice.public_key(), "not encrypted")).is_err());
+    }
+
+    #[test]
+    fn test_scheme_is_read_from_payload() {
+        let alice = Keys::generate();
+        let bob = Keys::generate();
+
+        let legacy = nip04::encrypt(alice.secret_key(), &bob.public_key(), "old").unwrap();
+        let current = nip44::encrypt(alice.secret_key(), &bob.public_key(), "new", nip44::Version::default()).unwrap();
+        assert!(is_nip04_payload(&legacy));
+        assert!(!is_nip04_payload(&current));
+    }
+}
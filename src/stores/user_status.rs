@@ -0,0 +1,312 @@
+//! User Status Store - NIP-38 user statuses
+//!
+//! A status is an addressable kind 30315 event; the `d` tag is the status
+//! type (`general`, `music`, ...), the content is the text and an optional
+//! `r` tag links somewhere. An `expiration` tag (NIP-40) ends it and empty
+//! content clears it. Statuses are fetched in batches: components ask for a
+//! pubkey and a short-delayed flush fetches everyone asked for at once.
+
+use dioxus::prelude::*;
+use nostr_sdk::{Event, EventBuilder, Filter, Kind, PublicKey, Tag, TagKind, Timestamp};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use crate::stores::music_player::MusicTrack;
+use crate::stores::nostr_music::TrackSource;
+use crate::stores::{auth_store, nostr_client};
+use crate::utils::time::now_secs;
+
+/// NIP-38 user status
+pub const KIND_USER_STATUS: u16 = 30315;
+
+/// Status type for free-form text
+pub const STATUS_GENERAL: &str = "general";
+
+/// Status type for the track being listened to
+pub const STATUS_MUSIC: &str = "music";
+
+/// How long fetched statuses are reused before asking relays again
+const STATUS_TTL_SECS: u64 = 300;
+
+/// Delay before a batch of requested pubkeys is fetched
+const BATCH_DELAY_MS: u32 = 300;
+
+/// A user's current status of one type
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserStatus {
+    pub status_type: String,
+    pub content: String,
+    /// Link from the `r` tag
+    pub reference: Option<String>,
+    pub expiration: Option<Timestamp>,
+    pub created_at: Timestamp,
+}
+
+impl UserStatus {
+    /// Parse a kind 30315 event; None for cleared (empty) statuses
+    pub fn from_event(event: &Event) -> Option<Self> {
+        if event.kind != Kind::from(KIND_USER_STATUS) || event.content.trim().is_empty() {
+            return None;
+        }
+        let tag = |name: &str| {
+            event.tags.iter().find_map(|tag| {
+                let parts = tag.as_slice();
+                (parts.len() >= 2 && parts[0] == name).then(|| parts[1].clone())
+            })
+        };
+
+        Some(Self {
+            status_type: tag("d").unwrap_or_default(),
+            content: event.content.trim().to_string(),
+            reference: tag("r").filter(|url| url.starts_with("https://") || url.starts_with("http://")),
+            expiration: tag("expiration").and_then(|t| t.parse::<u64>().ok()).map(Timestamp::from),
+            created_at: event.created_at,
+        })
+    }
+
+    pub fn is_expired(&self, now: Timestamp) -> bool {
+        self.expiration.is_some_and(|expiration| expiration <= now)
+    }
+}
+
+/// Current statuses from a user's status events, general first
+///
+/// Only the newest event per status type counts; cleared and expired
+/// statuses are dropped.
+pub fn current_statuses(events: &[Event], now: Timestamp) -> Vec<UserStatus> {
+    let mut newest: HashMap<String, &Event> = HashMap::new();
+    for event in events.iter().filter(|e| e.kind == Kind::from(KIND_USER_STATUS)) {
+        let d_tag = event.tags.identifier().unwrap_or_default().to_string();
+        let slot = newest.entry(d_tag).or_insert(event);
+        if event.created_at > slot.created_at {
+            *slot = event;
+        }
+    }
+
+    let mut statuses: Vec<UserStatus> = newest
+        .values()
+        .filter_map(|event| UserStatus::from_event(event))
+        .filter(|status| !status.is_expired(now))
+        .collect();
+    statuses.sort_by_key(|status| (status.status_type != STATUS_GENERAL, status.status_type.clone()));
+    statuses
+}
+
+/// Build a status event; empty `content` clears the status of that type
+pub fn build_status(
+    status_type: &str,
+    content: &str,
+    reference: Option<&str>,
+    expiration: Option<Timestamp>,
+) -> EventBuilder {
+    let mut tags = vec![Tag::identifier(status_type)];
+    if let Some(reference) = reference {
+        tags.push(Tag::custom(TagKind::custom("r"), [reference.to_string()]));
+    }
+    if let Some(expiration) = expiration {
+        tags.push(Tag::expiration(expiration));
+    }
+    EventBuilder::new(Kind::from(KIND_USER_STATUS), content.trim()).tags(tags)
+}
+
+/// Link to the playing track on nostr.blue
+fn track_reference(track: &MusicTrack) -> String {
+    match &track.source {
+        TrackSource::Wavlake { .. } => format!("https://nostr.blue/music/track/{}", track.id),
+        TrackSource::Nostr { pubkey, d_tag, .. } => format!(
+            "https://nostr.blue/music/playlist/{}:{}:{}",
+            crate::stores::nostr_music::KIND_MUSIC_TRACK,
+            pubkey,
+            d_tag
+        ),
+    }
+}
+
+async fn publish_status(builder: EventBuilder) -> Result<(), String> {
+    if !auth_store::is_authenticated() {
+        return Err("Not authenticated".to_string());
+    }
+    let client = nostr_client::get_client().ok_or("Client not initialized")?;
+    let event = client
+        .sign_event_builder(builder)
+        .await
+        .map_err(|e| format!("Failed to sign status: {}", e))?;
+    client
+        .send_event(&event)
+        .await
+        .map_err(|e| format!("Failed to publish status: {}", e))?;
+
+    // Show our own status right away
+    let pubkey = event.pubkey.to_hex();
+    let mut cache = STATUS_CACHE.write();
+    if let Some(entry) = cache.get_mut(&pubkey) {
+        entry.events.retain(|e| e.tags.identifier() != event.tags.identifier());
+        entry.events.push(event);
+    }
+    Ok(())
+}
+
+/// Set (or, with empty text, clear) the general status
+pub async fn set_status(general_text: &str, expiration: Option<Timestamp>) -> Result<(), String> {
+    publish_status(build_status(STATUS_GENERAL, general_text, None, expiration)).await
+}
+
+/// Set the music status to `track`, expiring when the track would end
+pub async fn set_music_status(track: &MusicTrack) -> Result<(), String> {
+    let content = format!("{} - {}", track.title, track.artist);
+    let expiration = track
+        .duration
+        .filter(|duration| *duration > 0)
+        .map(|duration| Timestamp::now() + Duration::from_secs(duration as u64));
+    publish_status(build_status(STATUS_MUSIC, &content, Some(&track_reference(track)), expiration)).await
+}
+
+/// Clear the music status
+pub async fn clear_music_status() -> Result<(), String> {
+    publish_status(build_status(STATUS_MUSIC, "", None, None)).await
+}
+
+// =============================================================================
+// Batched fetching
+// =============================================================================
+
+#[derive(Clone, Debug)]
+struct CachedStatuses {
+    events: Vec<Event>,
+    fetched_at: u64,
+}
+
+/// Status events per author (hex pubkey)
+static STATUS_CACHE: GlobalSignal<HashMap<String, CachedStatuses>> = Signal::global(HashMap::new);
+
+/// Pubkeys waiting for the next batch fetch
+static PENDING: GlobalSignal<HashSet<String>> = Signal::global(HashSet::new);
+
+/// Whether a batch fetch is scheduled
+static FLUSH_SCHEDULED: GlobalSignal<bool> = Signal::global(|| false);
+
+/// A user's current statuses (reactive); call `request_statuses` to load them
+pub fn get_statuses(pubkey: &str) -> Vec<UserStatus> {
+    STATUS_CACHE
+        .read()
+        .get(pubkey)
+        .map(|cached| current_statuses(&cached.events, Timestamp::now()))
+        .unwrap_or_default()
+}
+
+/// Queue a fetch of `pubkey`'s statuses unless they were fetched recently
+pub fn request_statuses(pubkey: &str) {
+    let fresh = STATUS_CACHE
+        .peek()
+        .get(pubkey)
+        .is_some_and(|cached| now_secs().saturating_sub(cached.fetched_at) < STATUS_TTL_SECS);
+    if fresh || !PENDING.write().insert(pubkey.to_string()) {
+        return;
+    }
+
+    if *FLUSH_SCHEDULED.peek() {
+        return;
+    }
+    *FLUSH_SCHEDULED.write() = true;
+
+    dioxus_core::spawn_forever(async move {
+        gloo_timers::future::TimeoutFuture::new(BATCH_DELAY_MS).await;
+        *FLUSH_SCHEDULED.write() = false;
+        let pubkeys: Vec<String> = PENDING.write().drain().collect();
+        if let Err(e) = fetch_statuses(pubkeys).await {
+            log::warn!("Failed to fetch user statuses: {}", e);
+        }
+    });
+}
+
+async fn fetch_statuses(pubkeys: Vec<String>) -> Result<(), String> {
+    let authors: Vec<PublicKey> = pubkeys.iter().filter_map(|pk| PublicKey::from_hex(pk).ok()).collect();
+    if authors.is_empty() {
+        return Ok(());
+    }
+
+    let filter = Filter::new()
+        .kind(Kind::from(KIND_USER_STATUS))
+        .authors(authors)
+        .since(Timestamp::now() - Duration::from_secs(30 * 24 * 60 * 60));
+    let events = nostr_client::fetch_events_aggregated(filter, Duration::from_secs(5)).await?;
+
+    let mut by_author: HashMap<String, Vec<Event>> = HashMap::new();
+    for event in events {
+        by_author.entry(event.pubkey.to_hex()).or_default().push(event);
+    }
+
+    let fetched_at = now_secs();
+    let mut cache = STATUS_CACHE.write();
+    for pubkey in pubkeys {
+        let events = by_author.remove(&pubkey).unwrap_or_default();
+        cache.insert(pubkey, CachedStatuses { events, fetched_at });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::Keys;
+
+    fn status_event(keys: &Keys, status_type: &str, content: &str, expiration: Option<u64>, created_at: u64) -> Event {
+        build_status(status_type, content, None, expiration.map(Timestamp::from))
+            .custom_created_at(Timestamp::from(created_at))
+            .sign_with_keys(keys)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_build_status_tags() {
+        let keys = Keys::generate();
+        let event = build_status(
+            STATUS_MUSIC,
+            " Song - Artist ",
+            Some("https://nostr.blue/music/track/1"),
+            Some(Timestamp::from(1_700_000_300)),
+        )
+        .sign_with_keys(&keys)
+        .unwrap();
+
+        assert_eq!(event.kind, Kind::from(KIND_USER_STATUS));
+        assert_eq!(event.content, "Song - Artist");
+        let tags: Vec<Vec<String>> = event.tags.iter().map(|tag| tag.as_slice().to_vec()).collect();
+        assert_eq!(
+            tags,
+            vec![
+                vec!["d".to_string(), "music".to_string()],
+                vec!["r".to_string(), "https://nostr.blue/music/track/1".to_string()],
+                vec!["expiration".to_string(), "1700000300".to_string()],
+            ]
+        );
+
+        let status = UserStatus::from_event(&event).unwrap();
+        assert_eq!(status.status_type, STATUS_MUSIC);
+        assert_eq!(status.reference.as_deref(), Some("https://nostr.blue/music/track/1"));
+    }
+
+    #[test]
+    fn test_current_statuses_drops_expired_and_cleared() {
+        let keys = Keys::generate();
+        let now = Timestamp::from(1_700_000_000);
+        let events = vec![
+            status_event(&keys, STATUS_MUSIC, "Old song - Artist", Some(1_699_999_999), 1_699_999_000),
+            status_event(&keys, STATUS_GENERAL, "Working", None, 1_699_000_000),
+            status_event(&keys, STATUS_GENERAL, "On vacation", Some(1_700_100_000), 1_699_900_000),
+        ];
+
+        // The music status has expired; the newer general status wins
+        let statuses = current_statuses(&events, now);
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].content, "On vacation");
+
+        // Once its expiration passes it's gone too
+        assert!(current_statuses(&events, Timestamp::from(1_700_100_000)).is_empty());
+
+        // An empty status clears the older one
+        let mut cleared = events.clone();
+        cleared.push(status_event(&keys, STATUS_GENERAL, "", None, 1_699_950_000));
+        assert!(current_statuses(&cleared, now).is_empty());
+    }
+}
@@ -1,5 +1,18 @@
 use dioxus::prelude::*;
 use nostr_sdk::EventId;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use wasm_bindgen::JsCast;
+
+/// Bars in a playback waveform
+pub const WAVEFORM_BARS: usize = 200;
+
+/// Decoded waveform amplitudes per audio URL; None when the audio couldn't be decoded
+static WAVEFORM_CACHE: OnceLock<Mutex<HashMap<String, Option<Vec<u8>>>>> = OnceLock::new();
+
+fn waveform_cache() -> &'static Mutex<HashMap<String, Option<Vec<u8>>>> {
+    WAVEFORM_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
 
 /// Voice message playback state
 #[derive(Clone, Debug, PartialEq)]
@@ -144,7 +157,6 @@ pub fn update_recording_duration(duration: f64) {
 
 /// Generate waveform data from audio samples
 /// Returns a vector of amplitude values (0-100) suitable for NIP-92 imeta tag
-#[allow(dead_code)]
 pub fn generate_waveform(samples: &[f32], target_points: usize) -> Vec<u8> {
     // Guard against zero target_points
     if target_points == 0 {
@@ -182,6 +194,65 @@ pub fn generate_waveform(samples: &[f32], target_points: usize) -> Vec<u8> {
     waveform
 }
 
+/// Playback position for a click `x` pixels into a waveform `width` pixels wide
+pub fn seek_time(x: f64, width: f64, duration: f64) -> f64 {
+    if width <= 0.0 || !duration.is_finite() || duration <= 0.0 {
+        return 0.0;
+    }
+    (x / width).clamp(0.0, 1.0) * duration
+}
+
+/// Waveform already computed for `url`: Some(None) if decoding failed before
+pub fn cached_waveform(url: &str) -> Option<Option<Vec<u8>>> {
+    waveform_cache().lock().unwrap().get(url).cloned()
+}
+
+/// Download and decode a voice message into waveform amplitudes, cached per URL
+pub async fn load_waveform(url: &str) -> Result<Vec<u8>, String> {
+    if let Some(cached) = cached_waveform(url) {
+        return cached.ok_or_else(|| "Audio could not be decoded".to_string());
+    }
+
+    let result = decode_waveform(url).await;
+    waveform_cache()
+        .lock()
+        .unwrap()
+        .insert(url.to_string(), result.as_ref().ok().cloned());
+    result
+}
+
+async fn decode_waveform(url: &str) -> Result<Vec<u8>, String> {
+    let response = gloo_net::http::Request::get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch audio: {}", e))?;
+    if !response.ok() {
+        return Err(format!("Failed to fetch audio: HTTP {}", response.status()));
+    }
+    let bytes = response
+        .binary()
+        .await
+        .map_err(|e| format!("Failed to read audio: {}", e))?;
+
+    // An offline context decodes without needing a user gesture or audio output
+    let context = web_sys::OfflineAudioContext::new_with_number_of_channels_and_length_and_sample_rate(1, 1, 44_100.0)
+        .map_err(|e| format!("Web Audio unavailable: {:?}", e))?;
+    let array = js_sys::Uint8Array::from(bytes.as_slice());
+    let promise = context
+        .decode_audio_data(&array.buffer())
+        .map_err(|e| format!("Failed to decode audio: {:?}", e))?;
+    let buffer: web_sys::AudioBuffer = wasm_bindgen_futures::JsFuture::from(promise)
+        .await
+        .map_err(|e| format!("Failed to decode audio: {:?}", e))?
+        .dyn_into()
+        .map_err(|_| "Decoded audio is not an AudioBuffer".to_string())?;
+    let samples = buffer
+        .get_channel_data(0)
+        .map_err(|e| format!("Failed to read audio samples: {:?}", e))?;
+
+    Ok(generate_waveform(&samples, WAVEFORM_BARS))
+}
+
 /// Format time as M:SS
 pub fn format_time(seconds: f64) -> String {
     if seconds.is_nan() || seconds < 0.0 {
@@ -191,3 +262,36 @@ pub fn format_time(seconds: f64) -> String {
     let secs = (seconds % 60.0).floor() as u32;
     format!("{}:{:02}", mins, secs)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_waveform() {
+        // Loud first half, silent second half
+        let mut samples = vec![0.0f32; 1000];
+        for sample in samples.iter_mut().take(500) {
+            *sample = -0.5;
+        }
+
+        let waveform = generate_waveform(&samples, 10);
+        assert_eq!(waveform.len(), 10);
+        assert!(waveform[..5].iter().all(|&amplitude| amplitude == 50));
+        assert!(waveform[5..].iter().all(|&amplitude| amplitude == 0));
+
+        // Fewer samples than points pads with silence
+        assert_eq!(generate_waveform(&[1.0, 1.0], 4), vec![100, 100, 0, 0]);
+        assert_eq!(generate_waveform(&[], 3), vec![0; 3]);
+        assert!(generate_waveform(&[0.3], 0).is_empty());
+    }
+
+    #[test]
+    fn test_seek_time() {
+        assert_eq!(seek_time(50.0, 200.0, 8.0), 2.0);
+        assert_eq!(seek_time(-10.0, 200.0, 8.0), 0.0);
+        assert_eq!(seek_time(250.0, 200.0, 8.0), 8.0);
+        assert_eq!(seek_time(50.0, 0.0, 8.0), 0.0);
+        assert_eq!(seek_time(50.0, 200.0, f64::NAN), 0.0);
+    }
+}
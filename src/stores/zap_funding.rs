@@ -0,0 +1,123 @@
+//! Zap funding source - which wallet pays for zaps
+//!
+//! The zap modal can pay through the connected NWC wallet or send a nutzap
+//! from the NIP-60 Cashu wallet. The last source used is remembered in
+//! localStorage.
+
+use dioxus::prelude::*;
+use gloo_storage::{LocalStorage, Storage};
+
+use crate::stores::cashu::{self, WALLET_BALANCE};
+use crate::stores::nwc_store;
+
+const STORAGE_KEY: &str = "nostr_blue_zap_funding_source";
+
+/// Wallet a zap is paid from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZapFundingSource {
+    /// Lightning invoice paid by the NWC wallet
+    Nwc,
+    /// NIP-61 nutzap from the Cashu wallet
+    Cashu,
+}
+
+impl ZapFundingSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Nwc => "nwc",
+            Self::Cashu => "cashu",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "nwc" => Some(Self::Nwc),
+            "cashu" => Some(Self::Cashu),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Nwc => "Lightning wallet (NWC)",
+            Self::Cashu => "Cashu wallet (nutzap)",
+        }
+    }
+}
+
+/// Pick the funding source to preselect
+///
+/// The remembered source wins while it is still usable; otherwise the only
+/// configured source, or NWC when both are. None when neither is set up, in
+/// which case zaps fall back to WebLN or a manual invoice.
+pub fn default_funding_source(
+    remembered: Option<ZapFundingSource>,
+    nwc_available: bool,
+    cashu_available: bool,
+) -> Option<ZapFundingSource> {
+    let is_available = |source: ZapFundingSource| match source {
+        ZapFundingSource::Nwc => nwc_available,
+        ZapFundingSource::Cashu => cashu_available,
+    };
+
+    match remembered.filter(|source| is_available(*source)) {
+        Some(source) => Some(source),
+        None if nwc_available => Some(ZapFundingSource::Nwc),
+        None if cashu_available => Some(ZapFundingSource::Cashu),
+        None => None,
+    }
+}
+
+/// Whether the Cashu wallet can fund a nutzap
+pub fn is_cashu_available() -> bool {
+    cashu::init::is_wallet_initialized() && *WALLET_BALANCE.read() > 0
+}
+
+/// Last funding source used, if any
+pub fn load_last_source() -> Option<ZapFundingSource> {
+    LocalStorage::get::<String>(STORAGE_KEY)
+        .ok()
+        .and_then(|s| ZapFundingSource::parse(&s))
+}
+
+/// Remember the funding source for the next zap
+pub fn save_last_source(source: ZapFundingSource) {
+    if let Err(e) = LocalStorage::set(STORAGE_KEY, source.as_str()) {
+        log::warn!("Failed to save zap funding source: {}", e);
+    }
+}
+
+/// Funding source to preselect in the zap modal
+pub fn initial_source() -> Option<ZapFundingSource> {
+    default_funding_source(load_last_source(), nwc_store::is_connected(), is_cashu_available())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_configured_source_is_default() {
+        assert_eq!(default_funding_source(None, true, false), Some(ZapFundingSource::Nwc));
+        assert_eq!(default_funding_source(None, false, true), Some(ZapFundingSource::Cashu));
+        // A remembered source that is no longer configured is ignored
+        assert_eq!(
+            default_funding_source(Some(ZapFundingSource::Cashu), true, false),
+            Some(ZapFundingSource::Nwc)
+        );
+        assert_eq!(
+            default_funding_source(Some(ZapFundingSource::Nwc), false, true),
+            Some(ZapFundingSource::Cashu)
+        );
+        assert_eq!(default_funding_source(Some(ZapFundingSource::Nwc), false, false), None);
+    }
+
+    #[test]
+    fn test_remembered_source_wins_when_both_configured() {
+        assert_eq!(default_funding_source(None, true, true), Some(ZapFundingSource::Nwc));
+        assert_eq!(
+            default_funding_source(Some(ZapFundingSource::Cashu), true, true),
+            Some(ZapFundingSource::Cashu)
+        );
+    }
+}
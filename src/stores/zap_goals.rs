@@ -0,0 +1,334 @@
+//! Zap Goals Store - NIP-75 fundraising goals
+//!
+//! A goal is a kind 9041 event with a target `amount` in msats and the
+//! description as content. Progress is the sum of the verified zap receipts
+//! that reference the goal with an `e` tag, tallied from the relays in the
+//! goal's `relays` tag. Goals are regular events, so they
+//! are linked by event ID (`note`/`nevent`), and other events point at one
+//! with a `goal` tag.
+
+use nostr_sdk::nips::nip19::Nip19;
+use nostr_sdk::{Event, EventId, Filter, FromBech32, Kind, PublicKey, Timestamp};
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::services::aggregation::{verify_zap_receipt_for, zapper_pubkey};
+use crate::stores::nostr_client;
+
+/// NIP-75 zap goal
+pub const KIND_ZAP_GOAL: u16 = 9041;
+
+/// Most zap receipts counted towards one goal
+const MAX_GOAL_RECEIPTS: usize = 1000;
+
+/// A parsed kind 9041 goal
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZapGoal {
+    pub id: EventId,
+    pub author: PublicKey,
+    pub description: String,
+    pub summary: Option<String>,
+    pub image: Option<String>,
+    pub target_msats: u64,
+    /// Relays the zaps are sent to and tallied from
+    pub relays: Vec<String>,
+    /// After this the goal stops counting zaps
+    pub closed_at: Option<Timestamp>,
+    pub created_at: Timestamp,
+}
+
+impl ZapGoal {
+    /// Parse a kind 9041 event; the `amount` tag is required
+    pub fn from_event(event: &Event) -> Result<Self, String> {
+        if event.kind != Kind::from(KIND_ZAP_GOAL) {
+            return Err(format!("Not a zap goal (kind {})", event.kind));
+        }
+
+        let tag = |name: &str| {
+            event.tags.iter().find_map(|tag| {
+                let parts = tag.as_slice();
+                (parts.len() >= 2 && parts[0] == name && !parts[1].trim().is_empty())
+                    .then(|| parts[1].trim().to_string())
+            })
+        };
+
+        let target_msats = tag("amount")
+            .ok_or("Zap goal has no amount")?
+            .parse::<u64>()
+            .map_err(|_| "Zap goal amount is not a number".to_string())?;
+        if target_msats == 0 {
+            return Err("Zap goal amount must be positive".to_string());
+        }
+
+        Ok(Self {
+            id: event.id,
+            author: event.pubkey,
+            description: event.content.trim().to_string(),
+            summary: tag("summary"),
+            image: tag("image").filter(|url| url.starts_with("https://") || url.starts_with("http://")),
+            target_msats,
+            relays: event
+                .tags
+                .iter()
+                .filter(|tag| tag.as_slice().first().map(|name| name.as_str()) == Some("relays"))
+                .flat_map(|tag| tag.as_slice().iter().skip(1))
+                .filter(|url| url.starts_with("wss://") || url.starts_with("ws://"))
+                .cloned()
+                .collect(),
+            closed_at: tag("closed_at").and_then(|t| t.parse::<u64>().ok()).map(Timestamp::from),
+            created_at: event.created_at,
+        })
+    }
+
+    /// Whether the goal still takes zaps at `now`
+    pub fn is_open(&self, now: Timestamp) -> bool {
+        self.closed_at.is_none_or(|closed_at| closed_at > now)
+    }
+
+    pub fn target_sats(&self) -> u64 {
+        self.target_msats / 1000
+    }
+}
+
+/// Sum the verified zap receipts made to `goal`, in msats
+///
+/// `zapper` is the pubkey the goal author's LNURL server signs receipts with;
+/// without one nothing is counted. Receipts that don't verify (see
+/// `verify_zap_receipt_for`), don't reference the goal, were made after it
+/// closed or are duplicates are skipped.
+pub fn sum_goal_zaps(goal: &ZapGoal, receipts: &[Event], zapper: Option<&PublicKey>) -> u64 {
+    let Some(zapper) = zapper else {
+        return 0;
+    };
+    let goal_id = goal.id.to_hex();
+    let mut seen = HashSet::new();
+    receipts
+        .iter()
+        .filter(|receipt| receipt.kind == Kind::ZapReceipt && seen.insert(receipt.id))
+        .filter(|receipt| goal.closed_at.is_none_or(|closed_at| receipt.created_at <= closed_at))
+        .filter(|receipt| {
+            receipt.tags.iter().any(|tag| {
+                let parts = tag.as_slice();
+                parts.len() >= 2 && parts[0] == "e" && parts[1] == goal_id
+            })
+        })
+        .filter_map(|receipt| verify_zap_receipt_for(receipt, &goal.author, zapper).ok())
+        .sum()
+}
+
+/// How far along a goal is, as a whole percentage
+///
+/// Not capped: a goal raising twice its target is at 200%.
+pub fn progress_percent(raised_msats: u64, target_msats: u64) -> u64 {
+    if target_msats == 0 {
+        return 0;
+    }
+    let percent = raised_msats as u128 * 100 / target_msats as u128;
+    percent.min(u64::MAX as u128) as u64
+}
+
+/// The goal event a note points at: its own ID for a goal, else its `goal` tag
+pub fn linked_goal_id(event: &Event) -> Option<EventId> {
+    if event.kind == Kind::from(KIND_ZAP_GOAL) {
+        return Some(event.id);
+    }
+    event.tags.iter().find_map(|tag| {
+        let parts = tag.as_slice();
+        if parts.len() >= 2 && parts[0] == "goal" {
+            EventId::from_hex(&parts[1]).ok()
+        } else {
+            None
+        }
+    })
+}
+
+/// Resolve a goal reference (`note1…`, `nevent1…` or hex event ID)
+pub fn parse_goal_reference(reference: &str) -> Result<EventId, String> {
+    let reference = reference.trim().trim_start_matches("nostr:");
+    if let Ok(id) = EventId::from_hex(reference) {
+        return Ok(id);
+    }
+    match Nip19::from_bech32(reference).map_err(|e| format!("Invalid goal reference: {}", e))? {
+        Nip19::EventId(id) => Ok(id),
+        Nip19::Event(event) => Ok(event.event_id),
+        Nip19::Coordinate(_) => {
+            Err("Zap goals are regular events; link them with a note or nevent".to_string())
+        }
+        _ => Err("Not a zap goal reference".to_string()),
+    }
+}
+
+/// Fetch a goal by reference along with the msats raised so far
+pub async fn fetch_zap_goal(reference: &str) -> Result<(ZapGoal, u64), String> {
+    let goal = fetch_goal_by_id(parse_goal_reference(reference)?).await?;
+    let raised = fetch_goal_raised(&goal).await?;
+    Ok((goal, raised))
+}
+
+/// Fetch and parse the goal event with `id`
+pub async fn fetch_goal_by_id(id: EventId) -> Result<ZapGoal, String> {
+    let filter = Filter::new().id(id).kind(Kind::from(KIND_ZAP_GOAL)).limit(1);
+    let event = nostr_client::fetch_events_aggregated(filter, Duration::from_secs(5))
+        .await?
+        .into_iter()
+        .next()
+        .ok_or("Zap goal not found")?;
+    ZapGoal::from_event(&event)
+}
+
+/// Msats zapped to a goal so far
+///
+/// Receipts come from the goal's `relays`, or from ours when it lists none.
+pub async fn fetch_goal_raised(goal: &ZapGoal) -> Result<u64, String> {
+    let filter = Filter::new()
+        .kind(Kind::ZapReceipt)
+        .event(goal.id)
+        .limit(MAX_GOAL_RECEIPTS);
+    let timeout = Duration::from_secs(5);
+
+    let receipts: Vec<Event> = if goal.relays.is_empty() {
+        nostr_client::fetch_events_aggregated(filter, timeout).await?
+    } else {
+        let client = nostr_client::get_client().ok_or("Client not initialized")?;
+        let temp_relays = nostr_client::add_temp_relays(&client, &goal.relays).await;
+        let result = client
+            .fetch_events_from(goal.relays.clone(), filter, timeout)
+            .await
+            .map(|events| events.into_iter().collect())
+            .map_err(|e| format!("Failed to fetch zap receipts: {}", e));
+        temp_relays.release().await;
+        result?
+    };
+
+    let zapper = zapper_pubkey(&goal.author).await;
+    Ok(sum_goal_zaps(goal, &receipts, zapper.as_ref()))
+}
+
+/// Goals by `author` that are still open, newest first
+pub async fn fetch_active_goals(author: PublicKey) -> Result<Vec<ZapGoal>, String> {
+    let filter = Filter::new()
+        .author(author)
+        .kind(Kind::from(KIND_ZAP_GOAL))
+        .limit(20);
+    let events = nostr_client::fetch_events_aggregated(filter, Duration::from_secs(5)).await?;
+
+    let now = Timestamp::now();
+    let mut goals: Vec<ZapGoal> = events
+        .iter()
+        .filter_map(|event| ZapGoal::from_event(event).ok())
+        .filter(|goal| goal.is_open(now))
+        .collect();
+    goals.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(goals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::{EventBuilder, Keys, Tag};
+
+    fn goal_event(author: &Keys, amount_msats: &str, extra: Vec<Vec<&str>>) -> Event {
+        let mut tags = vec![
+            Tag::parse(["amount", amount_msats]).unwrap(),
+            Tag::parse(["relays", "wss://relay.example"]).unwrap(),
+        ];
+        tags.extend(extra.into_iter().map(|tag| Tag::parse(tag).unwrap()));
+        EventBuilder::new(Kind::from(KIND_ZAP_GOAL), "New recording gear")
+            .tags(tags)
+            .sign_with_keys(author)
+            .unwrap()
+    }
+
+    fn zap_receipt(goal: &ZapGoal, amount_msats: u64, bolt11: &str, zapper: &Keys) -> Event {
+        let request = EventBuilder::new(Kind::ZapRequest, "")
+            .tags([
+                Tag::public_key(goal.author),
+                Tag::event(goal.id),
+                Tag::parse(["amount", amount_msats.to_string().as_str()]).unwrap(),
+            ])
+            .sign_with_keys(&Keys::generate())
+            .unwrap();
+        EventBuilder::new(Kind::ZapReceipt, "")
+            .tags([
+                Tag::public_key(goal.author),
+                Tag::event(goal.id),
+                Tag::parse(["bolt11", bolt11]).unwrap(),
+                Tag::parse(["description", request.as_json().as_str()]).unwrap(),
+            ])
+            .sign_with_keys(zapper)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_goal() {
+        let author = Keys::generate();
+        let goal = ZapGoal::from_event(&goal_event(&author, "21000000", vec![vec!["closed_at", "1700000000"]])).unwrap();
+        assert_eq!(goal.target_msats, 21_000_000);
+        assert_eq!(goal.target_sats(), 21_000);
+        assert_eq!(goal.description, "New recording gear");
+        assert_eq!(goal.relays, vec!["wss://relay.example".to_string()]);
+        assert!(goal.is_open(Timestamp::from(1_600_000_000)));
+        assert!(!goal.is_open(Timestamp::from(1_800_000_000)));
+
+        assert!(ZapGoal::from_event(&goal_event(&author, "lots", vec![])).is_err());
+        assert!(ZapGoal::from_event(&goal_event(&author, "0", vec![])).is_err());
+    }
+
+    #[test]
+    fn test_sum_goal_zaps() {
+        let goal = ZapGoal::from_event(&goal_event(&Keys::generate(), "10000000", vec![])).unwrap();
+        let zapper = Keys::generate();
+        let first = zap_receipt(&goal, 2_100_000, "lnbc21u1ptestdata", &zapper);
+        let second = zap_receipt(&goal, 5_000_000, "lnbc50u1ptestdata", &zapper);
+        // Invoice amount doesn't match the request: not counted
+        let mismatched = zap_receipt(&goal, 1_000, "lnbc50u1ptestdata", &zapper);
+        // Zaps to some other goal don't count either
+        let other_goal = ZapGoal::from_event(&goal_event(&Keys::generate(), "1000", vec![])).unwrap();
+        let elsewhere = zap_receipt(&other_goal, 2_100_000, "lnbc21u1ptestdata", &zapper);
+
+        let receipts = vec![first.clone(), second, mismatched, elsewhere, first];
+        let raised = sum_goal_zaps(&goal, &receipts, Some(&zapper.public_key()));
+        assert_eq!(raised, 7_100_000);
+        assert_eq!(progress_percent(raised, goal.target_msats), 71);
+
+        // Without the author's zapper pubkey nothing can be verified
+        assert_eq!(sum_goal_zaps(&goal, &receipts, None), 0);
+    }
+
+    #[test]
+    fn test_sum_goal_zaps_skips_forged_receipts() {
+        let goal = ZapGoal::from_event(&goal_event(&Keys::generate(), "10000000", vec![])).unwrap();
+        let zapper = Keys::generate();
+        let genuine = zap_receipt(&goal, 2_100_000, "lnbc21u1ptestdata", &zapper);
+        // Well-formed, but signed by someone other than the author's LNURL server
+        let forged = zap_receipt(&goal, 5_000_000, "lnbc50u1ptestdata", &Keys::generate());
+
+        let raised = sum_goal_zaps(&goal, &[genuine, forged], Some(&zapper.public_key()));
+        assert_eq!(raised, 2_100_000);
+    }
+
+    #[test]
+    fn test_progress_percent() {
+        assert_eq!(progress_percent(0, 1000), 0);
+        assert_eq!(progress_percent(999, 1000), 99);
+        assert_eq!(progress_percent(1000, 1000), 100);
+        // Overfunded goals go past 100%; the bar caps it, the label doesn't
+        assert_eq!(progress_percent(2500, 1000), 250);
+        assert_eq!(progress_percent(u64::MAX, 1), u64::MAX);
+        assert_eq!(progress_percent(5, 0), 0);
+    }
+
+    #[test]
+    fn test_linked_goal_id() {
+        let goal = goal_event(&Keys::generate(), "1000", vec![]);
+        assert_eq!(linked_goal_id(&goal), Some(goal.id));
+
+        let goal_hex = goal.id.to_hex();
+        let stream = EventBuilder::new(Kind::TextNote, "Support the stream!")
+            .tags([Tag::parse(["goal", goal_hex.as_str()]).unwrap()])
+            .sign_with_keys(&Keys::generate())
+            .unwrap();
+        assert_eq!(linked_goal_id(&stream), Some(goal.id));
+        assert_eq!(parse_goal_reference(&goal_hex), Ok(goal.id));
+    }
+}
@@ -0,0 +1,205 @@
+use nostr_sdk::{Event, Kind, PublicKey, TagKind};
+use std::collections::HashMap;
+
+use crate::services::aggregation::verify_zap_receipt_for;
+
+/// Maximum number of follow posts highlighted in the digest
+pub const TOP_POSTS_LIMIT: usize = 3;
+
+/// Summary of account activity between two timestamps
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ActivityDigest {
+    /// Start of the summarized window (exclusive), Unix seconds
+    pub since: u64,
+    /// End of the summarized window (inclusive), Unix seconds
+    pub until: u64,
+    pub mentions: usize,
+    pub replies: usize,
+    pub reactions: usize,
+    pub zaps: usize,
+    pub zap_sats: u64,
+    pub direct_messages: usize,
+    /// Most interacted-with posts from follows, with their interaction count
+    pub top_posts: Vec<(Event, usize)>,
+}
+
+impl ActivityDigest {
+    /// True when nothing happened in the window
+    pub fn is_empty(&self) -> bool {
+        self.mentions == 0
+            && self.replies == 0
+            && self.reactions == 0
+            && self.zaps == 0
+            && self.direct_messages == 0
+            && self.top_posts.is_empty()
+    }
+}
+
+/// Whether a timestamp falls inside the digest window `(since, until]`
+pub fn in_window(created_at: u64, since: u64, until: u64) -> bool {
+    created_at > since && created_at <= until
+}
+
+/// Count mentions, replies, reactions and zaps addressed to `me`
+///
+/// Events outside `(since, until]`, events authored by `me` and events that
+/// don't tag `me` are ignored. Text notes with an `e` tag count as replies,
+/// others as mentions. Zap receipts are counted by their own timestamp, and
+/// only when they verify against `zapper`, the pubkey of our LNURL server (see
+/// `verify_zap_receipt_for`). Without one no zaps are counted.
+pub fn summarize_notifications(
+    events: &[Event],
+    me: &PublicKey,
+    zapper: Option<&PublicKey>,
+    since: u64,
+    until: u64,
+) -> ActivityDigest {
+    let mut digest = ActivityDigest {
+        since,
+        until,
+        ..Default::default()
+    };
+
+    for event in events {
+        if !in_window(event.created_at.as_secs(), since, until) {
+            continue;
+        }
+        // Zap receipts are authored by the LNURL server, so only self-authored
+        // text notes and reactions are excluded
+        if event.pubkey == *me && event.kind != Kind::ZapReceipt {
+            continue;
+        }
+        if !event.tags.public_keys().any(|pk| pk == me) {
+            continue;
+        }
+
+        match event.kind {
+            Kind::TextNote => {
+                if event.tags.find(TagKind::e()).is_some() {
+                    digest.replies += 1;
+                } else {
+                    digest.mentions += 1;
+                }
+            }
+            Kind::Reaction => digest.reactions += 1,
+            Kind::ZapReceipt => {
+                if let Some(Ok(msats)) = zapper.map(|zapper| verify_zap_receipt_for(event, me, zapper)) {
+                    digest.zaps += 1;
+                    digest.zap_sats += msats / 1000;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    digest
+}
+
+/// Rank `posts` by how many of `interactions` (reactions, reposts, zaps) reference them
+///
+/// Posts without any interaction are dropped; ties keep the newer post first.
+pub fn rank_top_posts(posts: &[Event], interactions: &[Event], limit: usize) -> Vec<(Event, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for interaction in interactions {
+        // The last e tag is the interacted-with event (NIP-25)
+        if let Some(target) = interaction.tags.event_ids().last() {
+            *counts.entry(target.to_hex()).or_insert(0) += 1;
+        }
+    }
+
+    let mut ranked: Vec<(&Event, usize)> = posts
+        .iter()
+        .filter_map(|post| {
+            let count = counts.get(&post.id.to_hex()).copied().unwrap_or(0);
+            (count > 0).then_some((post, count))
+        })
+        .collect();
+
+    ranked.sort_by(|(a, count_a), (b, count_b)| {
+        count_b.cmp(count_a).then(b.created_at.cmp(&a.created_at))
+    });
+
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(post, count)| (post.clone(), count))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::{EventBuilder, Keys, Tag, Timestamp};
+
+    fn note(keys: &Keys, tags: Vec<Tag>, created_at: u64) -> Event {
+        EventBuilder::text_note("hi")
+            .tags(tags)
+            .custom_created_at(Timestamp::from(created_at))
+            .sign_with_keys(keys)
+            .unwrap()
+    }
+
+    fn reaction(keys: &Keys, target: &Event, created_at: u64) -> Event {
+        EventBuilder::new(Kind::Reaction, "+")
+            .tags(vec![Tag::event(target.id), Tag::public_key(target.pubkey)])
+            .custom_created_at(Timestamp::from(created_at))
+            .sign_with_keys(keys)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_window_boundaries() {
+        assert!(!in_window(100, 100, 200));
+        assert!(in_window(101, 100, 200));
+        assert!(in_window(200, 100, 200));
+        assert!(!in_window(201, 100, 200));
+    }
+
+    #[test]
+    fn test_summarize_counts_mentions_replies_reactions() {
+        let me = Keys::generate();
+        let other = Keys::generate();
+        let my_post = note(&me, vec![], 50);
+
+        let events = vec![
+            note(&other, vec![Tag::public_key(me.public_key())], 150),
+            note(&other, vec![Tag::event(my_post.id), Tag::public_key(me.public_key())], 160),
+            reaction(&other, &my_post, 170),
+            // Before the window
+            note(&other, vec![Tag::public_key(me.public_key())], 90),
+            // Authored by me
+            note(&me, vec![Tag::public_key(me.public_key())], 150),
+            // Not tagging me
+            note(&other, vec![], 150),
+        ];
+
+        let digest = summarize_notifications(&events, &me.public_key(), None, 100, 200);
+        assert_eq!(digest.mentions, 1);
+        assert_eq!(digest.replies, 1);
+        assert_eq!(digest.reactions, 1);
+        assert_eq!(digest.zaps, 0);
+        assert!(!digest.is_empty());
+    }
+
+    #[test]
+    fn test_rank_top_posts() {
+        let author = Keys::generate();
+        let fan = Keys::generate();
+        let quiet = note(&author, vec![], 110);
+        let popular = note(&author, vec![], 120);
+        let liked = note(&author, vec![], 130);
+
+        let interactions = vec![
+            reaction(&fan, &popular, 140),
+            reaction(&fan, &popular, 141),
+            reaction(&fan, &liked, 142),
+        ];
+
+        let ranked = rank_top_posts(&[quiet, popular.clone(), liked.clone()], &interactions, 5);
+        let ranked: Vec<(String, usize)> = ranked
+            .into_iter()
+            .map(|(post, count)| (post.id.to_hex(), count))
+            .collect();
+        assert_eq!(ranked, vec![(popular.id.to_hex(), 2), (liked.id.to_hex(), 1)]);
+    }
+}
@@ -0,0 +1,112 @@
+//! Editing addressable events (articles, playlists)
+//!
+//! Relays replace an addressable event when the same author publishes the
+//! same kind with the same d-tag. Editors therefore publish an edited event
+//! under the d-tag it was loaded with; only new events get a fresh one.
+
+use nostr::nips::nip19::{FromBech32, Nip19Coordinate};
+
+use super::slugify;
+
+/// What an editor publishes: a new event or a new version of a loaded one
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum EditTarget {
+    #[default]
+    New,
+    /// Replacing the event with this d-tag
+    Existing { identifier: String },
+}
+
+impl EditTarget {
+    pub fn is_edit(&self) -> bool {
+        matches!(self, Self::Existing { .. })
+    }
+
+    /// d-tag to publish under
+    ///
+    /// An edit always keeps the loaded d-tag, whatever the form says; `fresh`
+    /// only supplies the d-tag of a new event.
+    pub fn identifier_for_publish(&self, fresh: impl FnOnce() -> String) -> Result<String, String> {
+        match self {
+            Self::Existing { identifier } => Ok(identifier.clone()),
+            Self::New => {
+                let identifier = fresh().trim().to_string();
+                if identifier.is_empty() {
+                    return Err("Identifier cannot be empty".to_string());
+                }
+                Ok(identifier)
+            }
+        }
+    }
+}
+
+/// d-tag for a new event that can't collide with an earlier one of the same title
+pub fn fresh_unique_identifier(title: &str, now: i64) -> String {
+    format!("{}-{}", slugify(title), now)
+}
+
+/// An naddr decoded for editing
+#[derive(Clone, Debug, PartialEq)]
+pub struct EditAddress {
+    pub kind: u16,
+    /// Author (hex)
+    pub pubkey: String,
+    pub identifier: String,
+}
+
+/// Decode the naddr of an event to edit, checking its kind and that it's ours
+pub fn decode_edit_naddr(naddr: &str, kind: u16, my_pubkey: &str) -> Result<EditAddress, String> {
+    let coordinate = Nip19Coordinate::from_bech32(naddr).map_err(|e| format!("Invalid naddr: {}", e))?;
+
+    let address = EditAddress {
+        kind: coordinate.kind.as_u16(),
+        pubkey: coordinate.public_key.to_hex(),
+        identifier: coordinate.identifier.clone(),
+    };
+    if address.kind != kind {
+        return Err(format!("Expected a kind {} event, got kind {}", kind, address.kind));
+    }
+    if address.pubkey != my_pubkey {
+        return Err("You can only edit your own events".to_string());
+    }
+    Ok(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr::{Keys, Kind};
+
+    #[test]
+    fn test_edit_keeps_identifier() {
+        let target = EditTarget::Existing { identifier: "my-first-article".to_string() };
+        assert!(target.is_edit());
+
+        // The title and the identifier field changed, the d-tag doesn't
+        let identifier = target.identifier_for_publish(|| slugify("A Renamed Article")).unwrap();
+        assert_eq!(identifier, "my-first-article");
+    }
+
+    #[test]
+    fn test_create_generates_slug() {
+        let target = EditTarget::New;
+        assert_eq!(
+            target.identifier_for_publish(|| slugify("Hello, Nostr World!")).unwrap(),
+            "hello-nostr-world"
+        );
+        assert!(target.identifier_for_publish(|| slugify("???")).is_err());
+        assert_eq!(fresh_unique_identifier("Road Trip Mix", 1_700_000_000), "road-trip-mix-1700000000");
+    }
+
+    #[test]
+    fn test_decode_edit_naddr() {
+        let keys = Keys::generate();
+        let me = keys.public_key().to_hex();
+        let naddr = crate::utils::nip19::encode_naddr(Kind::LongFormTextNote, keys.public_key(), "draft-1", &[]).unwrap();
+
+        let address = decode_edit_naddr(&naddr, 30023, &me).unwrap();
+        assert_eq!(address.identifier, "draft-1");
+        assert!(decode_edit_naddr(&naddr, 34139, &me).is_err());
+        assert!(decode_edit_naddr(&naddr, 30023, &Keys::generate().public_key().to_hex()).is_err());
+    }
+}
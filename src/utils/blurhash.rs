@@ -0,0 +1,288 @@
+//! Blurhash encoding and decoding
+//!
+//! A blurhash is a short string describing a blurred version of an image.
+//! Uploads get one computed from the image; notes carry it in `imeta` tags
+//! and it is shown while the real image loads.
+
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::io::Cursor;
+use std::sync::{Mutex, OnceLock};
+
+use base64::Engine;
+
+const BASE83_CHARS: &[u8] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz#$%*+,-.:;=?@[]^_{|}~";
+
+/// Size placeholders are decoded at; the browser stretches the result
+const PLACEHOLDER_SIZE: u32 = 32;
+
+/// Placeholder data URLs by blurhash (None when the hash is invalid)
+static PLACEHOLDER_CACHE: OnceLock<Mutex<HashMap<String, Option<String>>>> = OnceLock::new();
+
+fn placeholder_cache() -> &'static Mutex<HashMap<String, Option<String>>> {
+    PLACEHOLDER_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Encode RGB pixels (3 bytes each, row by row) as a blurhash
+///
+/// None for an empty image or a component count outside 1-9.
+pub fn encode_blurhash(
+    rgb: &[u8],
+    width: u32,
+    height: u32,
+    components_x: u32,
+    components_y: u32,
+) -> Option<String> {
+    let (w, h) = (width as usize, height as usize);
+    if w == 0 || h == 0 || rgb.len() < w * h * 3 {
+        return None;
+    }
+    if !(1..=9).contains(&components_x) || !(1..=9).contains(&components_y) {
+        return None;
+    }
+
+    let mut factors = Vec::with_capacity((components_x * components_y) as usize);
+    for j in 0..components_y {
+        for i in 0..components_x {
+            let normalisation = if i == 0 && j == 0 { 1.0 } else { 2.0 };
+            let mut sum = [0.0f64; 3];
+            for y in 0..h {
+                let basis_y = (PI * j as f64 * y as f64 / h as f64).cos();
+                for x in 0..w {
+                    let basis = normalisation
+                        * (PI * i as f64 * x as f64 / w as f64).cos()
+                        * basis_y;
+                    let pixel = &rgb[(y * w + x) * 3..(y * w + x) * 3 + 3];
+                    for (channel, value) in sum.iter_mut().zip(pixel) {
+                        *channel += basis * srgb_to_linear(*value);
+                    }
+                }
+            }
+            let scale = 1.0 / (w * h) as f64;
+            factors.push(sum.map(|channel| channel * scale));
+        }
+    }
+
+    let (dc, ac) = factors.split_first()?;
+    let mut hash = String::new();
+    hash.push_str(&encode_base83((components_x - 1) + (components_y - 1) * 9, 1));
+
+    let max_value = if ac.is_empty() {
+        hash.push_str(&encode_base83(0, 1));
+        1.0
+    } else {
+        let actual_max = ac
+            .iter()
+            .flat_map(|factor| factor.iter())
+            .fold(0.0f64, |max, value| max.max(value.abs()));
+        let quantised_max = ((actual_max * 166.0 - 0.5).floor()).clamp(0.0, 82.0) as u32;
+        hash.push_str(&encode_base83(quantised_max, 1));
+        (quantised_max + 1) as f64 / 166.0
+    };
+
+    let dc_value = (linear_to_srgb(dc[0]) << 16) + (linear_to_srgb(dc[1]) << 8) + linear_to_srgb(dc[2]);
+    hash.push_str(&encode_base83(dc_value, 4));
+
+    for factor in ac {
+        let quantise = |value: f64| {
+            (sign_pow(value / max_value, 0.5) * 9.0 + 9.5).floor().clamp(0.0, 18.0) as u32
+        };
+        let value = quantise(factor[0]) * 19 * 19 + quantise(factor[1]) * 19 + quantise(factor[2]);
+        hash.push_str(&encode_base83(value, 2));
+    }
+
+    Some(hash)
+}
+
+/// Decode a blurhash into `width` x `height` RGBA pixels, row by row
+pub fn decode_blurhash(hash: &str, width: u32, height: u32) -> Result<Vec<u8>, String> {
+    if width == 0 || height == 0 {
+        return Err("Image size must not be zero".to_string());
+    }
+    if hash.len() < 6 || !hash.is_ascii() {
+        return Err("Blurhash is too short".to_string());
+    }
+
+    let size_flag = decode_base83(&hash[0..1])?;
+    let components_x = (size_flag % 9 + 1) as usize;
+    let components_y = (size_flag / 9 + 1) as usize;
+    let expected_len = 4 + 2 * components_x * components_y;
+    if hash.len() != expected_len {
+        return Err(format!("Blurhash should be {} characters, got {}", expected_len, hash.len()));
+    }
+
+    let max_value = (decode_base83(&hash[1..2])? + 1) as f64 / 166.0;
+
+    let mut colors = Vec::with_capacity(components_x * components_y);
+    let dc = decode_base83(&hash[2..6])?;
+    colors.push([
+        srgb_to_linear((dc >> 16) as u8),
+        srgb_to_linear((dc >> 8) as u8),
+        srgb_to_linear(dc as u8),
+    ]);
+    for i in 1..components_x * components_y {
+        let value = decode_base83(&hash[4 + i * 2..6 + i * 2])?;
+        let unquantise = |q: u32| sign_pow((q as f64 - 9.0) / 9.0, 2.0) * max_value;
+        colors.push([
+            unquantise(value / (19 * 19)),
+            unquantise((value / 19) % 19),
+            unquantise(value % 19),
+        ]);
+    }
+
+    let (w, h) = (width as usize, height as usize);
+    let mut pixels = Vec::with_capacity(w * h * 4);
+    for y in 0..h {
+        for x in 0..w {
+            let mut pixel = [0.0f64; 3];
+            for j in 0..components_y {
+                let basis_y = (PI * y as f64 * j as f64 / h as f64).cos();
+                for i in 0..components_x {
+                    let basis = (PI * x as f64 * i as f64 / w as f64).cos() * basis_y;
+                    let color = &colors[i + j * components_x];
+                    for (channel, value) in pixel.iter_mut().zip(color) {
+                        *channel += value * basis;
+                    }
+                }
+            }
+            pixels.extend(pixel.iter().map(|channel| linear_to_srgb(*channel) as u8));
+            pixels.push(255);
+        }
+    }
+    Ok(pixels)
+}
+
+/// A `data:` URL of the decoded blurhash, to use as an image placeholder
+///
+/// None if the blurhash is invalid. Results are cached per hash.
+pub fn placeholder_data_url(hash: &str) -> Option<String> {
+    if let Some(cached) = placeholder_cache().lock().unwrap().get(hash) {
+        return cached.clone();
+    }
+
+    let url = match encode_placeholder_png(hash) {
+        Ok(png) => Some(format!(
+            "data:image/png;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(png)
+        )),
+        Err(e) => {
+            log::debug!("Invalid blurhash {}: {}", hash, e);
+            None
+        }
+    };
+    placeholder_cache().lock().unwrap().insert(hash.to_string(), url.clone());
+    url
+}
+
+fn encode_placeholder_png(hash: &str) -> Result<Vec<u8>, String> {
+    let pixels = decode_blurhash(hash, PLACEHOLDER_SIZE, PLACEHOLDER_SIZE)?;
+    let image = image::RgbaImage::from_raw(PLACEHOLDER_SIZE, PLACEHOLDER_SIZE, pixels)
+        .ok_or("Decoded blurhash has the wrong size")?;
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to encode placeholder: {}", e))?;
+    Ok(png)
+}
+
+fn srgb_to_linear(value: u8) -> f64 {
+    let v = value as f64 / 255.0;
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f64) -> u32 {
+    let v = value.clamp(0.0, 1.0);
+    let srgb = if v <= 0.003_130_8 {
+        v * 12.92 * 255.0 + 0.5
+    } else {
+        (1.055 * v.powf(1.0 / 2.4) - 0.055) * 255.0 + 0.5
+    };
+    srgb as u32
+}
+
+fn sign_pow(value: f64, exp: f64) -> f64 {
+    value.abs().powf(exp).copysign(value)
+}
+
+fn encode_base83(value: u32, length: u32) -> String {
+    (1..=length)
+        .map(|i| {
+            let digit = (value / 83u32.pow(length - i)) % 83;
+            BASE83_CHARS[digit as usize] as char
+        })
+        .collect()
+}
+
+fn decode_base83(chars: &str) -> Result<u32, String> {
+    chars.bytes().try_fold(0u32, |value, c| {
+        let digit = BASE83_CHARS
+            .iter()
+            .position(|&b| b == c)
+            .ok_or_else(|| format!("Invalid blurhash character '{}'", c as char))?;
+        Ok(value * 83 + digit as u32)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn average_color(pixels: &[u8]) -> [f64; 3] {
+        let count = (pixels.len() / 4) as f64;
+        let mut sum = [0.0; 3];
+        for pixel in pixels.chunks(4) {
+            for (channel, value) in sum.iter_mut().zip(pixel) {
+                *channel += *value as f64;
+            }
+        }
+        sum.map(|channel| channel / count)
+    }
+
+    #[test]
+    fn test_decode_known_blurhash_average_color() {
+        let pixels = decode_blurhash("LEHV6nWB2yk8pyo0adR*.7kCMdnj", 32, 32).unwrap();
+        assert_eq!(pixels.len(), 32 * 32 * 4);
+        assert!(pixels.chunks(4).all(|pixel| pixel[3] == 255));
+
+        // The average is close to the DC component, a greyish rgb(151, 150, 149)
+        let average = average_color(&pixels);
+        for (channel, expected) in average.iter().zip([151.0, 150.0, 149.0]) {
+            assert!((channel - expected).abs() < 4.0, "{:?}", average);
+        }
+    }
+
+    #[test]
+    fn test_round_trip_flat_color() {
+        let rgb: Vec<u8> = [200u8, 40, 90].repeat(16 * 8);
+        let hash = encode_blurhash(&rgb, 16, 8, 4, 3).unwrap();
+        let pixels = decode_blurhash(&hash, 4, 4).unwrap();
+        for pixel in pixels.chunks(4) {
+            assert_eq!(pixel, &[200, 40, 90, 255]);
+        }
+    }
+
+    #[test]
+    fn test_invalid_blurhash() {
+        assert!(decode_blurhash("", 32, 32).is_err());
+        // Size flag says 4x3 components but the hash is too short
+        assert!(decode_blurhash("LEHV6nWB2yk8", 32, 32).is_err());
+        assert!(decode_blurhash("LEHV6nWB2yk8pyo0adR*.7kCMdn\"", 32, 32).is_err());
+        assert!(decode_blurhash("LEHV6nWB2yk8pyo0adR*.7kCMdnj", 0, 32).is_err());
+        assert_eq!(placeholder_data_url("not a blurhash"), None);
+        assert!(placeholder_data_url("LEHV6nWB2yk8pyo0adR*.7kCMdnj")
+            .unwrap()
+            .starts_with("data:image/png;base64,"));
+    }
+
+    #[test]
+    fn test_encode_rejects_bad_input() {
+        assert_eq!(encode_blurhash(&[], 0, 0, 4, 3), None);
+        assert_eq!(encode_blurhash(&[0, 0, 0], 1, 1, 10, 3), None);
+        assert_eq!(encode_blurhash(&[0, 0, 0], 2, 2, 4, 3), None);
+    }
+}
@@ -3,6 +3,9 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use ::url::Url;
 
+use crate::utils::event::custom_emoji_urls;
+use crate::utils::nip19::decode_nostr_uri;
+
 // Precompiled regexes for content parsing - compiled once at startup
 static URL_PATTERN: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"https?://[^\s]+").expect("Failed to compile URL regex")
@@ -28,12 +31,18 @@ static CASHU_PATTERN: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"cashu[AB][A-Za-z0-9_=-]+").expect("Failed to compile cashu regex")
 });
 
+// NIP-30 custom emoji shortcodes, resolved against the event's emoji tags
+static EMOJI_SHORTCODE_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r":([A-Za-z0-9_-]+):").expect("Failed to compile emoji shortcode regex")
+});
+
 /// Represents different types of content tokens that can appear in a note
 #[derive(Debug, Clone, PartialEq)]
 pub enum ContentToken {
     Text(String),
     Link(String),
     Image(String),
+    AnimatedImage(String),   // GIF, APNG, or WebP/AVIF from a GIF host
     Video(String),
     // Wavlake - rendered with in-house player
     WavlakeTrack(String),    // Track ID from wavlake.com/track/{id}
@@ -73,14 +82,19 @@ pub enum ContentToken {
     ZapStream(String),       // naddr from zap.stream URL
     // Cashu ecash tokens
     CashuToken(String),      // cashuA.../cashuB... token string
+    // NIP-30 custom emoji
+    CustomEmoji(String, String), // (shortcode, image URL)
 }
 
 /// Parse note content into structured tokens
-pub fn parse_content(content: &str, _tags: &[Tag]) -> Vec<ContentToken> {
+pub fn parse_content(content: &str, tags: &[Tag]) -> Vec<ContentToken> {
     let mut tokens = Vec::new();
     let mut last_end = 0;
     let mut matches: Vec<(usize, usize, ContentToken)> = Vec::new();
 
+    // NIP-92 media types, so files without a telling extension still render
+    let mime_types = imeta_mime_types(tags);
+
     // Find all URLs (using precompiled static regex)
     for mat in URL_PATTERN.find_iter(content) {
         let raw_url = mat.as_str();
@@ -88,12 +102,17 @@ pub fn parse_content(content: &str, _tags: &[Tag]) -> Vec<ContentToken> {
         let url = clean_url_trailing_punctuation(raw_url).to_string();
         // Adjust the end position if we trimmed punctuation
         let actual_end = mat.start() + url.len();
-        let token = if is_image_url(&url) {
-            ContentToken::Image(url)
+        let media = classify_media(&url, mime_types.get(&url).map(String::as_str));
+        let token = if let Some(MediaKind::Image { animated }) = media {
+            if animated {
+                ContentToken::AnimatedImage(url)
+            } else {
+                ContentToken::Image(url)
+            }
         } else if let Some(video_id) = extract_youtube_id(&url) {
             // YouTube before generic video check
             ContentToken::YouTube(video_id)
-        } else if is_video_url(&url) {
+        } else if media == Some(MediaKind::Video) {
             ContentToken::Video(url)
         } else if let Some(track_id) = extract_wavlake_track_id(&url) {
             ContentToken::WavlakeTrack(track_id)
@@ -137,24 +156,14 @@ pub fn parse_content(content: &str, _tags: &[Tag]) -> Vec<ContentToken> {
         // Extract the bech32 part after "nostr:"
         let bech32_part = mention.strip_prefix("nostr:").unwrap_or(&mention);
 
-        // Use SDK's NIP-19 parser for type-safe detection
-        let token = match nostr_sdk::nips::nip19::Nip19::from_bech32(bech32_part) {
-            Ok(nip19) => match nip19 {
-                nostr_sdk::nips::nip19::Nip19::Pubkey(_) |
-                nostr_sdk::nips::nip19::Nip19::Profile(_) => ContentToken::Mention(mention),
-                nostr_sdk::nips::nip19::Nip19::EventId(_) |
-                nostr_sdk::nips::nip19::Nip19::Event(_) |
-                nostr_sdk::nips::nip19::Nip19::Coordinate(_) => ContentToken::EventMention(mention),
-                _ => ContentToken::Mention(mention), // Default for unknown types
-            },
-            Err(_) => {
-                // Fallback to string matching if parsing fails
-                if mention.contains("npub1") || mention.contains("nprofile1") {
-                    ContentToken::Mention(mention)
-                } else {
-                    ContentToken::EventMention(mention)
-                }
+        // NIP-21: profiles render as mentions, notes and addresses as embeds;
+        // malformed bech32 stays plain text
+        let token = match decode_nostr_uri(&mention) {
+            Some(Nip19::Pubkey(_)) | Some(Nip19::Profile(_)) => ContentToken::Mention(mention),
+            Some(Nip19::EventId(_)) | Some(Nip19::Event(_)) | Some(Nip19::Coordinate(_)) => {
+                ContentToken::EventMention(mention)
             }
+            _ => ContentToken::Text(mention),
         };
         matches.push((mat.start(), mat.end(), token));
     }
@@ -171,6 +180,23 @@ pub fn parse_content(content: &str, _tags: &[Tag]) -> Vec<ContentToken> {
         matches.push((mat.start(), mat.end(), ContentToken::CashuToken(token_str)));
     }
 
+    // Find custom emoji shortcodes; ones without an emoji tag stay literal text
+    let emoji_urls = custom_emoji_urls(tags);
+    if !emoji_urls.is_empty() {
+        for caps in EMOJI_SHORTCODE_PATTERN.captures_iter(content) {
+            let (Some(full), Some(shortcode)) = (caps.get(0), caps.get(1)) else {
+                continue;
+            };
+            if let Some(url) = emoji_urls.get(shortcode.as_str()) {
+                matches.push((
+                    full.start(),
+                    full.end(),
+                    ContentToken::CustomEmoji(shortcode.as_str().to_string(), url.clone()),
+                ));
+            }
+        }
+    }
+
     // Sort matches by position
     matches.sort_by_key(|m| m.0);
 
@@ -209,22 +235,111 @@ pub fn parse_content(content: &str, _tags: &[Tag]) -> Vec<ContentToken> {
     tokens
 }
 
-/// Check if a URL points to an image
-fn is_image_url(url: &str) -> bool {
+/// What a media URL points at
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MediaKind {
+    /// `animated` when the image is likely to be animated
+    Image { animated: bool },
+    Video,
+}
+
+/// Hosts whose WebP/AVIF files are animated GIF conversions
+const ANIMATED_IMAGE_HOSTS: &[&str] = &["giphy.com", "tenor.com"];
+
+/// Classify a URL as image or video, by its `imeta` mime type when known,
+/// else by extension
+///
+/// A mime type wins over the extension, so a Blossom URL with no extension
+/// still renders. GIF and APNG are always animated; WebP and AVIF can be
+/// either, and count as animated when they come from a GIF host.
+pub fn classify_media(url: &str, mime: Option<&str>) -> Option<MediaKind> {
     let lower = url.to_lowercase();
+    // Remove query parameters and fragment to check extension
+    let path = lower.split(['?', '#']).next().unwrap_or(&lower);
+    let extension = path
+        .rsplit('/')
+        .next()
+        .and_then(|file| file.rsplit_once('.'))
+        .map(|(_, ext)| ext)
+        .unwrap_or("");
+
+    let from_gif_host = Url::parse(url)
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(str::to_lowercase))
+        .is_some_and(|host| {
+            ANIMATED_IMAGE_HOSTS
+                .iter()
+                .any(|gif_host| host == *gif_host || host.ends_with(&format!(".{}", gif_host)))
+        });
+
+    let mime = mime.map(|m| m.trim().to_lowercase());
+    match mime.as_deref() {
+        Some("image/gif" | "image/apng") => return Some(MediaKind::Image { animated: true }),
+        Some("image/webp" | "image/avif") => return Some(MediaKind::Image { animated: from_gif_host }),
+        Some(m) if m.starts_with("image/") => return Some(MediaKind::Image { animated: false }),
+        Some(m) if m.starts_with("video/") => return Some(MediaKind::Video),
+        _ => {}
+    }
 
-    // Remove query parameters to check extension
-    let path = lower.split('?').next().unwrap_or(&lower);
+    match extension {
+        "gif" | "apng" => Some(MediaKind::Image { animated: true }),
+        "webp" | "avif" => Some(MediaKind::Image { animated: from_gif_host }),
+        "jpg" | "jpeg" | "png" | "svg" | "bmp" => Some(MediaKind::Image { animated: false }),
+        _ if lower.contains("image") => Some(MediaKind::Image { animated: false }),
+        _ if is_video_url(url) => Some(MediaKind::Video),
+        _ => None,
+    }
+}
 
-    path.ends_with(".jpg") ||
-    path.ends_with(".jpeg") ||
-    path.ends_with(".png") ||
-    path.ends_with(".gif") ||
-    path.ends_with(".webp") ||
-    path.ends_with(".svg") ||
-    path.ends_with(".bmp") ||
-    lower.contains("/image/") ||
-    lower.contains("image")
+/// Images and videos a note can show in a media grid, in order
+///
+/// URLs in the content are classified as they are for rendering; `imeta`
+/// entries whose URL isn't in the content are added after them, in tag order.
+pub fn note_media(content: &str, tags: &[Tag]) -> Vec<(String, MediaKind)> {
+    let mut media: Vec<(String, MediaKind)> = parse_content(content, tags)
+        .into_iter()
+        .filter_map(|token| match token {
+            ContentToken::Image(url) => Some((url, MediaKind::Image { animated: false })),
+            ContentToken::AnimatedImage(url) => Some((url, MediaKind::Image { animated: true })),
+            ContentToken::Video(url) => Some((url, MediaKind::Video)),
+            _ => None,
+        })
+        .collect();
+
+    for (url, mime) in imeta_entries(tags) {
+        if media.iter().any(|(known, _)| *known == url) {
+            continue;
+        }
+        if let Some(kind) = classify_media(&url, Some(&mime)) {
+            media.push((url, kind));
+        }
+    }
+    media
+}
+
+/// Media types (`m`) of NIP-92 `imeta` tags, by URL
+fn imeta_mime_types(tags: &[Tag]) -> std::collections::HashMap<String, String> {
+    imeta_entries(tags).into_iter().collect()
+}
+
+/// URL and media type (`m`) of each NIP-92 `imeta` tag, in tag order
+fn imeta_entries(tags: &[Tag]) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+    for tag in tags {
+        let fields = tag.as_slice();
+        if fields.first().map(String::as_str) != Some("imeta") {
+            continue;
+        }
+        let field = |name: &str| {
+            fields[1..].iter().find_map(|f| {
+                f.split_once(' ').filter(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+            })
+        };
+        if let (Some(url), Some(mime)) = (field("url"), field("m")) {
+            entries.push((url, mime));
+        }
+    }
+    entries
 }
 
 /// Extract track ID from Wavlake URLs
@@ -764,6 +879,40 @@ mod tests {
         assert!(matches!(tokens[0], ContentToken::Text(_)));
     }
 
+    #[test]
+    fn test_parse_inline_nostr_uris() {
+        let keys = Keys::generate();
+        let npub = keys.public_key().to_bech32().unwrap();
+        let nevent = Nip19Event::new(EventId::all_zeros())
+            .author(keys.public_key())
+            .to_bech32()
+            .unwrap();
+        let coordinate = Coordinate::new(Kind::LongFormTextNote, keys.public_key())
+            .identifier("my-article");
+        let naddr = Nip19Coordinate::new(coordinate, Vec::<RelayUrl>::new())
+            .to_bech32()
+            .unwrap();
+
+        let content = format!("hi nostr:{} see nostr:{}, and nostr:{}!", npub, nevent, naddr);
+        let tokens = parse_content(&content, &[]);
+        assert_eq!(tokens, vec![
+            ContentToken::Text("hi ".to_string()),
+            ContentToken::Mention(format!("nostr:{}", npub)),
+            ContentToken::Text(" see ".to_string()),
+            ContentToken::EventMention(format!("nostr:{}", nevent)),
+            ContentToken::Text(", and ".to_string()),
+            ContentToken::EventMention(format!("nostr:{}", naddr)),
+            ContentToken::Text("!".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_malformed_nostr_uri_is_text() {
+        let tokens = parse_content("broken nostr:npub1notvalidbech32", &[]);
+        assert!(tokens.iter().all(|t| matches!(t, ContentToken::Text(_))));
+        assert!(!tokens.iter().any(|t| matches!(t, ContentToken::Mention(_) | ContentToken::EventMention(_))));
+    }
+
     #[test]
     fn test_parse_with_url() {
         let tokens = parse_content("Check out https://example.com for more info", &[]);
@@ -794,6 +943,35 @@ mod tests {
         assert!(tokens.iter().any(|t| matches!(t, ContentToken::Image(_))));
     }
 
+    #[test]
+    fn test_note_media() {
+        assert_eq!(note_media("sunset https://example.com/sunset.jpg", &[]).len(), 1);
+        assert_eq!(note_media("https://example.com/clip.mp4", &[]).len(), 1);
+        assert!(note_media("just words and https://example.com/page", &[]).is_empty());
+        // YouTube links are embeds, not media files
+        assert!(note_media("https://youtube.com/watch?v=dQw4w9WgXcQ", &[]).is_empty());
+
+        // An imeta entry counts even when its URL has no extension
+        let imeta = Tag::parse(["imeta", "url https://blossom.example/abc123", "m image/png"]).unwrap();
+        assert_eq!(note_media("", &[imeta.clone()]).len(), 1);
+        assert_eq!(note_media("https://blossom.example/abc123", &[imeta]).len(), 1);
+
+        // Entries only in imeta tags keep the tag order
+        let tags: Vec<Tag> = ["c", "a", "b"]
+            .iter()
+            .map(|name| {
+                let url = format!("url https://blossom.example/{}", name);
+                Tag::parse(["imeta", url.as_str(), "m image/png"]).unwrap()
+            })
+            .collect();
+        let urls: Vec<String> = note_media("", &tags).into_iter().map(|(url, _)| url).collect();
+        assert_eq!(urls, vec![
+            "https://blossom.example/c",
+            "https://blossom.example/a",
+            "https://blossom.example/b",
+        ]);
+    }
+
     #[test]
     fn test_parse_multiple_images() {
         let content = "Look at these cats!\n\
@@ -805,6 +983,46 @@ mod tests {
         assert_eq!(image_count, 3);
     }
 
+    #[test]
+    fn test_classify_media() {
+        let still = Some(MediaKind::Image { animated: false });
+        let animated = Some(MediaKind::Image { animated: true });
+
+        assert_eq!(classify_media("https://example.com/cat.jpg", None), still);
+        assert_eq!(classify_media("https://example.com/cat.JPEG?w=600", None), still);
+        assert_eq!(classify_media("https://example.com/dance.gif", None), animated);
+        assert_eq!(classify_media("https://example.com/photo.webp", None), still);
+        assert_eq!(classify_media("https://media.giphy.com/media/abc/giphy.webp", None), animated);
+        assert_eq!(classify_media("https://example.com/photo.avif#x", None), still);
+        assert_eq!(classify_media("https://media.tenor.com/xyz/loop.avif", None), animated);
+        assert_eq!(classify_media("https://example.com/clip.mp4", None), Some(MediaKind::Video));
+        assert_eq!(classify_media("https://example.com/readme.txt", None), None);
+
+        // Unknown extension: the imeta mime type decides
+        let blossom = "https://blossom.example/4c8e1f9a0b2d";
+        assert_eq!(classify_media(blossom, None), None);
+        assert_eq!(classify_media(blossom, Some("image/png")), still);
+        assert_eq!(classify_media(blossom, Some("image/gif")), animated);
+        assert_eq!(classify_media(blossom, Some("video/mp4")), Some(MediaKind::Video));
+        // The mime type wins over a misleading extension
+        assert_eq!(classify_media("https://example.com/clip.bin", Some("Image/WebP")), still);
+    }
+
+    #[test]
+    fn test_parse_media_with_imeta_mime() {
+        let url = "https://blossom.example/4c8e1f9a0b2d";
+        let tags = vec![Tag::parse(["imeta", format!("url {}", url).as_str(), "m image/gif"]).unwrap()];
+        let content = format!("look {} and https://example.com/pic.avif", url);
+
+        let tokens = parse_content(&content, &tags);
+        assert!(tokens.contains(&ContentToken::AnimatedImage(url.to_string())));
+        assert!(tokens.contains(&ContentToken::Image("https://example.com/pic.avif".to_string())));
+
+        // Without the imeta tag it's just a link
+        let tokens = parse_content(&content, &[]);
+        assert!(tokens.contains(&ContentToken::Link(url.to_string())));
+    }
+
     #[test]
     fn test_parse_cashu_token_v3() {
         // V3 tokens start with cashuA
@@ -829,4 +1047,23 @@ mod tests {
         assert!(matches!(&tokens[1], ContentToken::CashuToken(_)));
         assert!(matches!(&tokens[2], ContentToken::Text(_)));
     }
+
+    #[test]
+    fn test_parse_custom_emoji_shortcodes() {
+        let tags = vec![Tag::parse(["emoji", "soapbox", "https://example.com/soapbox.png"]).unwrap()];
+        let tokens = parse_content("hi :soapbox: and :unknown:", &tags);
+        assert_eq!(
+            tokens,
+            vec![
+                ContentToken::Text("hi ".to_string()),
+                ContentToken::CustomEmoji("soapbox".to_string(), "https://example.com/soapbox.png".to_string()),
+                // No emoji tag for this one, so it renders literally
+                ContentToken::Text(" and :unknown:".to_string()),
+            ]
+        );
+
+        // Without emoji tags shortcodes are plain text
+        let tokens = parse_content(":soapbox:", &[]);
+        assert_eq!(tokens, vec![ContentToken::Text(":soapbox:".to_string())]);
+    }
 }
@@ -1,9 +1,268 @@
 // Event utility functions
 // Helper functions for working with Nostr events
 
-use nostr_sdk::Kind;
+use nostr_sdk::nips::nip09::EventDeletionRequest;
+use nostr_sdk::{Coordinate, EventBuilder, EventId, Kind, Tag, TagKind};
+use std::collections::HashMap;
+
+use crate::stores::nostr_client::{self, DELETED_EVENTS, HAS_SIGNER};
 
 /// Check if an event is a voice message (Kind::VoiceMessage or Kind::VoiceMessageReply)
 pub fn is_voice_message(event: &nostr_sdk::Event) -> bool {
     event.kind == Kind::VoiceMessage || event.kind == Kind::VoiceMessageReply
 }
+
+/// NIP-36 content warning attached to an event
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContentWarning {
+    /// Optional reason given by the author (e.g. "spoiler", "nsfw")
+    pub reason: Option<String>,
+}
+
+/// Find a NIP-36 `content-warning` tag; an empty reason counts as no reason
+pub fn content_warning<'a>(tags: impl IntoIterator<Item = &'a Tag>) -> Option<ContentWarning> {
+    tags.into_iter().find_map(|tag| {
+        let parts = tag.as_slice();
+        if parts.first().map(String::as_str) != Some("content-warning") {
+            return None;
+        }
+        let reason = parts
+            .get(1)
+            .map(|reason| reason.trim())
+            .filter(|reason| !reason.is_empty())
+            .map(str::to_string);
+        Some(ContentWarning { reason })
+    })
+}
+
+/// Build a NIP-36 `content-warning` tag for publishing
+pub fn content_warning_tag(reason: &str) -> Vec<String> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        vec!["content-warning".to_string()]
+    } else {
+        vec!["content-warning".to_string(), reason.to_string()]
+    }
+}
+
+/// "Expire after" choices offered by the composer, in seconds
+pub const EXPIRATION_CHOICES: &[(u64, &str)] = &[
+    (60 * 60, "1 hour"),
+    (24 * 60 * 60, "1 day"),
+    (7 * 24 * 60 * 60, "1 week"),
+    (30 * 24 * 60 * 60, "30 days"),
+];
+
+/// NIP-40 expiration time of an event, as a unix timestamp
+///
+/// A malformed value counts as no expiration.
+pub fn expiration(event: &nostr_sdk::Event) -> Option<u64> {
+    event.tags.iter().find_map(|tag| {
+        let parts = tag.as_slice();
+        (parts.first().map(String::as_str) == Some("expiration"))
+            .then(|| parts.get(1).and_then(|value| value.trim().parse::<u64>().ok()))
+            .flatten()
+    })
+}
+
+/// Whether an event's NIP-40 expiration has passed
+pub fn is_expired(event: &nostr_sdk::Event) -> bool {
+    is_expired_at(event, nostr_sdk::Timestamp::now().as_u64())
+}
+
+fn is_expired_at(event: &nostr_sdk::Event, now: u64) -> bool {
+    expiration(event).is_some_and(|expiration| expiration <= now)
+}
+
+/// Build a NIP-40 `expiration` tag for a note expiring `after_secs` after `now`
+pub fn expiration_tag(now: u64, after_secs: u64) -> Vec<String> {
+    vec!["expiration".to_string(), now.saturating_add(after_secs).to_string()]
+}
+
+/// NIP-70 `-` tag: only the author may publish the event
+pub fn protected_tag() -> Vec<String> {
+    vec!["-".to_string()]
+}
+
+/// Whether an event is NIP-70 protected (carries a `-` tag)
+pub fn is_protected(event: &nostr_sdk::Event) -> bool {
+    event.tags.iter().any(|tag| tag.as_slice() == ["-"])
+}
+
+/// Whether a NIP-30 shortcode is valid (alphanumerics, `_` and `-`)
+pub fn is_valid_shortcode(shortcode: &str) -> bool {
+    !shortcode.is_empty()
+        && shortcode.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Build a NIP-30 `emoji` tag for a custom emoji
+pub fn custom_emoji_tag(shortcode: &str, url: &str) -> Result<Tag, String> {
+    if !is_valid_shortcode(shortcode) {
+        return Err(format!("Invalid emoji shortcode: {}", shortcode));
+    }
+    let parsed = ::url::Url::parse(url).map_err(|e| format!("Invalid emoji URL: {}", e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!("Invalid emoji URL scheme: {}", parsed.scheme()));
+    }
+    Tag::parse(["emoji", shortcode, url]).map_err(|e| format!("Failed to build emoji tag: {}", e))
+}
+
+/// Custom emojis declared by an event's NIP-30 `emoji` tags, shortcode -> image URL
+pub fn custom_emoji_urls<'a>(tags: impl IntoIterator<Item = &'a Tag>) -> HashMap<String, String> {
+    tags.into_iter()
+        .filter_map(|tag| {
+            let parts = tag.as_slice();
+            (parts.len() >= 3 && parts[0] == "emoji" && is_valid_shortcode(&parts[1]))
+                .then(|| (parts[1].clone(), parts[2].clone()))
+        })
+        .collect()
+}
+
+/// Build a NIP-09 deletion request (kind 5) for one of our own events
+///
+/// The target gets an `e` tag and its kind a `k` tag. Addressable events can
+/// pass their coordinate too, so an `a` tag also covers later versions.
+pub fn deletion_request(
+    event_id: EventId,
+    kind: Kind,
+    coordinate: Option<Coordinate>,
+    reason: &str,
+) -> EventBuilder {
+    let mut request = EventDeletionRequest::new().id(event_id);
+    if let Some(coordinate) = coordinate {
+        request = request.coordinate(coordinate);
+    }
+    let reason = reason.trim();
+    if !reason.is_empty() {
+        request = request.reason(reason);
+    }
+    EventBuilder::delete(request)
+        .tag(Tag::custom(TagKind::k(), vec![kind.as_u16().to_string()]))
+}
+
+/// Ask relays to delete one of our own events
+///
+/// The event is hidden from local feeds straight away and shown again if
+/// the deletion request can't be published.
+pub async fn request_deletion(
+    event_id: &str,
+    kind: Kind,
+    coordinate: Option<Coordinate>,
+    reason: &str,
+) -> Result<(), String> {
+    let client = nostr_client::get_client().ok_or("Client not initialized")?;
+
+    if !*HAS_SIGNER.read() {
+        return Err("No signer attached. Cannot delete events.".to_string());
+    }
+
+    let target = EventId::from_hex(event_id)
+        .map_err(|e| format!("Invalid event ID: {}", e))?;
+
+    DELETED_EVENTS.write().insert(event_id.to_string());
+
+    let builder = deletion_request(target, kind, coordinate, reason);
+    if let Err(e) = client.send_event_builder(builder).await {
+        DELETED_EVENTS.write().remove(event_id);
+        return Err(format!("Failed to publish deletion: {}", e));
+    }
+
+    log::info!("Requested deletion of {} (kind {})", event_id, kind.as_u16());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::{EventBuilder, Keys};
+
+    fn note_with_tags(tags: Vec<Vec<String>>) -> nostr_sdk::Event {
+        let tags: Vec<Tag> = tags.into_iter().map(|tag| Tag::parse(tag).unwrap()).collect();
+        EventBuilder::text_note("spicy")
+            .tags(tags)
+            .sign_with_keys(&Keys::generate())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_content_warning_without_reason() {
+        let event = note_with_tags(vec![content_warning_tag("")]);
+        assert_eq!(content_warning(event.tags.iter()), Some(ContentWarning { reason: None }));
+    }
+
+    #[test]
+    fn test_content_warning_with_reason() {
+        let event = note_with_tags(vec![
+            vec!["t".to_string(), "movies".to_string()],
+            content_warning_tag(" spoilers "),
+        ]);
+        assert_eq!(
+            content_warning(event.tags.iter()),
+            Some(ContentWarning { reason: Some("spoilers".to_string()) })
+        );
+        assert_eq!(content_warning(note_with_tags(vec![]).tags.iter()), None);
+    }
+
+    #[test]
+    fn test_is_expired() {
+        let event = note_with_tags(vec![expiration_tag(1_700_000_000, 3_600)]);
+        assert_eq!(expiration(&event), Some(1_700_003_600));
+        assert!(!is_expired_at(&event, 1_700_003_599));
+        assert!(is_expired_at(&event, 1_700_003_600));
+
+        // Malformed or missing expirations never expire
+        let malformed = note_with_tags(vec![vec!["expiration".to_string(), "tomorrow".to_string()]]);
+        assert_eq!(expiration(&malformed), None);
+        assert!(!is_expired_at(&malformed, u64::MAX));
+        assert!(!is_expired_at(&note_with_tags(vec![]), u64::MAX));
+    }
+
+    #[test]
+    fn test_expiration_tag_for_composer_choices() {
+        for (secs, _) in EXPIRATION_CHOICES {
+            let tag = expiration_tag(1_700_000_000, *secs);
+            assert_eq!(tag[0], "expiration");
+            assert_eq!(tag[1].parse::<u64>().unwrap(), 1_700_000_000 + secs);
+        }
+        assert_eq!(expiration_tag(u64::MAX, 60)[1], u64::MAX.to_string());
+    }
+
+    #[test]
+    fn test_custom_emoji_tag() {
+        let tag = custom_emoji_tag("soapbox", "https://example.com/soapbox.png").unwrap();
+        assert_eq!(tag.as_slice(), &["emoji", "soapbox", "https://example.com/soapbox.png"]);
+
+        let event = EventBuilder::new(Kind::Reaction, ":soapbox:")
+            .tag(tag)
+            .sign_with_keys(&Keys::generate())
+            .unwrap();
+        let urls = custom_emoji_urls(event.tags.iter());
+        assert_eq!(urls.get("soapbox").map(String::as_str), Some("https://example.com/soapbox.png"));
+
+        assert!(custom_emoji_tag("bad code", "https://example.com/x.png").is_err());
+        assert!(custom_emoji_tag("", "https://example.com/x.png").is_err());
+        assert!(custom_emoji_tag("ok", "javascript:alert(1)").is_err());
+    }
+
+    #[test]
+    fn test_deletion_request_tags_target_kind() {
+        let keys = Keys::generate();
+        let article_id = EventId::all_zeros();
+        let coordinate = Coordinate::new(Kind::LongFormTextNote, keys.public_key())
+            .identifier("my-article");
+
+        let event = deletion_request(article_id, Kind::LongFormTextNote, Some(coordinate), " outdated ")
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        assert_eq!(event.kind, Kind::EventDeletion);
+        assert_eq!(event.content, "outdated");
+        let tags: Vec<Vec<String>> = event.tags.iter().map(|tag| tag.as_slice().to_vec()).collect();
+        assert!(tags.contains(&vec!["e".to_string(), article_id.to_hex()]));
+        assert!(tags.contains(&vec![
+            "a".to_string(),
+            format!("30023:{}:my-article", keys.public_key().to_hex()),
+        ]));
+        assert!(tags.contains(&vec!["k".to_string(), "30023".to_string()]));
+    }
+}
@@ -0,0 +1,166 @@
+//! NIP-94 file metadata for uploaded media
+//!
+//! Media posts describe each file with an `imeta` tag (NIP-92) holding the
+//! NIP-94 fields: `url`, `m` (MIME type), `x` (SHA-256), `size`, `dim` and
+//! `blurhash`. Fields that can't be worked out are left out rather than sent
+//! empty.
+
+use image::GenericImageView;
+use nostr::{Tag, TagKind};
+use sha2::{Digest, Sha256};
+
+use crate::utils::blurhash::encode_blurhash;
+
+/// Blurhash components across and down, as most clients use
+const BLURHASH_COMPONENTS: (u32, u32) = (4, 3);
+
+/// Images are shrunk to at most this size before computing the blurhash
+const BLURHASH_SAMPLE_SIZE: u32 = 32;
+
+/// What is known about a file after uploading it
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UploadedMedia {
+    pub url: String,
+    /// Lowercase hex SHA-256 of the uploaded bytes
+    pub sha256: Option<String>,
+    pub mime: Option<String>,
+    pub size: Option<usize>,
+    /// Width and height in pixels
+    pub dimensions: Option<(u32, u32)>,
+    pub blurhash: Option<String>,
+}
+
+impl UploadedMedia {
+    /// Media known only by its URL
+    pub fn from_url(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Describe the bytes uploaded to `url`
+    ///
+    /// Dimensions and blurhash are only filled in for images the app can
+    /// decode.
+    pub fn describe(url: impl Into<String>, data: &[u8], mime: &str) -> Self {
+        let mut media = Self {
+            url: url.into(),
+            sha256: Some(format!("{:x}", Sha256::digest(data))),
+            mime: Some(mime.trim().to_string()).filter(|m| !m.is_empty()),
+            size: Some(data.len()),
+            ..Default::default()
+        };
+
+        if mime.starts_with("image/") {
+            match image::load_from_memory(data) {
+                Ok(img) => {
+                    media.dimensions = Some(img.dimensions());
+                    media.blurhash = image_blurhash(&img);
+                }
+                Err(e) => log::debug!("Can't decode {} for dimensions: {}", mime, e),
+            }
+        }
+        media
+    }
+
+    /// The `imeta` tag values, e.g. `["url https://…", "m image/jpeg", …]`
+    pub fn imeta_fields(&self) -> Vec<String> {
+        let mut fields = vec![format!("url {}", self.url)];
+        if let Some(mime) = &self.mime {
+            fields.push(format!("m {}", mime));
+        }
+        if let Some(sha256) = &self.sha256 {
+            fields.push(format!("x {}", sha256));
+        }
+        if let Some(size) = self.size {
+            fields.push(format!("size {}", size));
+        }
+        if let Some((width, height)) = self.dimensions {
+            fields.push(format!("dim {}x{}", width, height));
+        }
+        if let Some(blurhash) = &self.blurhash {
+            fields.push(format!("blurhash {}", blurhash));
+        }
+        fields
+    }
+
+    /// NIP-92 `imeta` tag for this file
+    pub fn imeta_tag(&self) -> Tag {
+        Tag::custom(TagKind::Custom("imeta".into()), self.imeta_fields())
+    }
+}
+
+fn image_blurhash(img: &image::DynamicImage) -> Option<String> {
+    let sample = img
+        .thumbnail(BLURHASH_SAMPLE_SIZE, BLURHASH_SAMPLE_SIZE)
+        .to_rgb8();
+    let (components_x, components_y) = BLURHASH_COMPONENTS;
+    encode_blurhash(sample.as_raw(), sample.width(), sample.height(), components_x, components_y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_imeta_from_upload_result() {
+        let media = UploadedMedia {
+            url: "https://blossom.example/abc.jpg".to_string(),
+            sha256: Some("abc123".to_string()),
+            mime: Some("image/jpeg".to_string()),
+            size: Some(2048),
+            dimensions: Some((1920, 1080)),
+            blurhash: Some("LEHV6nWB2yk8pyo0adR*.7kCMdnj".to_string()),
+        };
+        assert_eq!(
+            media.imeta_tag().as_slice(),
+            &[
+                "imeta",
+                "url https://blossom.example/abc.jpg",
+                "m image/jpeg",
+                "x abc123",
+                "size 2048",
+                "dim 1920x1080",
+                "blurhash LEHV6nWB2yk8pyo0adR*.7kCMdnj",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_imeta_skips_unknown_fields() {
+        let media = UploadedMedia::from_url("https://example.com/video.mp4");
+        assert_eq!(media.imeta_fields(), vec!["url https://example.com/video.mp4"]);
+
+        // Not an image: hash, type and size only
+        let media = UploadedMedia::describe("https://example.com/a.mp4", b"not really a video", "video/mp4");
+        let fields = media.imeta_fields();
+        assert_eq!(fields.len(), 4);
+        assert!(fields.iter().any(|f| f.starts_with("x ") && f.len() == 2 + 64));
+        assert!(fields.iter().all(|f| !f.starts_with("dim") && !f.starts_with("blurhash")));
+
+        // An image that can't be decoded gets no dimensions either
+        let media = UploadedMedia::describe("https://example.com/a.png", b"garbage", "image/png");
+        assert_eq!(media.dimensions, None);
+        assert_eq!(media.blurhash, None);
+    }
+
+    #[test]
+    fn test_describe_png() {
+        let img = image::RgbImage::from_pixel(8, 4, image::Rgb([255, 255, 255]));
+        let mut png = Vec::new();
+        image::DynamicImage::ImageRgb8(img)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        let media = UploadedMedia::describe("https://example.com/white.png", &png, "image/png");
+        assert_eq!(media.dimensions, Some((8, 4)));
+        assert_eq!(media.size, Some(png.len()));
+        let blurhash = media.blurhash.unwrap();
+        // 1 size + 1 max + 4 DC + 2 per AC component
+        assert_eq!(blurhash.len(), 2 + 4 + 2 * 11);
+        // A flat white image: white DC, every AC component at zero
+        assert!(blurhash.starts_with("L0TSUA"), "{}", blurhash);
+        assert!(blurhash[6..].chars().eq("fQ".repeat(11).chars()));
+    }
+}
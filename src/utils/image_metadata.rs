@@ -0,0 +1,399 @@
+//! Image metadata stripping
+//!
+//! Photos carry EXIF/XMP/IPTC blocks with GPS coordinates, camera serials and
+//! capture times. Before upload those blocks are cut out of the file without
+//! re-encoding the pixels, so nothing is lost in quality. JPEG orientation is
+//! the one EXIF field kept, in a minimal block of its own, so photos aren't
+//! shown rotated. The format is detected from the file signature.
+
+const JPEG_SOI: [u8; 2] = [0xFF, 0xD8];
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// JPEG markers
+const SOS: u8 = 0xDA;
+const EOI: u8 = 0xD9;
+const APP1: u8 = 0xE1; // EXIF, XMP
+const APP2: u8 = 0xE2; // ICC profile, MPF
+const APP13: u8 = 0xED; // IPTC
+const COM: u8 = 0xFE;
+
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+const MPF_HEADER: &[u8] = b"MPF\0";
+const EXIF_ORIENTATION_TAG: u16 = 0x0112;
+
+/// PNG chunks with text, EXIF or timestamps
+const PNG_METADATA_CHUNKS: [&[u8; 4]; 5] = [b"eXIf", b"tEXt", b"iTXt", b"zTXt", b"tIME"];
+
+/// Bits of the WebP VP8X flags byte announcing metadata chunks
+const VP8X_EXIF_FLAG: u8 = 0x08;
+const VP8X_XMP_FLAG: u8 = 0x04;
+
+/// Remove metadata from a JPEG, PNG or WebP image
+///
+/// Fails for other formats and for files too malformed to rewrite safely.
+pub fn strip_metadata(data: &[u8]) -> Result<Vec<u8>, String> {
+    if data.starts_with(&JPEG_SOI) {
+        strip_jpeg(data)
+    } else if data.starts_with(&PNG_SIGNATURE) {
+        strip_png(data)
+    } else if data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        strip_webp(data)
+    } else {
+        Err("unsupported image format".to_string())
+    }
+}
+
+/// Bytes to upload for a file of `content_type`
+///
+/// Non-image files are returned untouched. Images that can't be stripped are
+/// uploaded as they are, with a warning in the log.
+pub fn strip_for_upload(data: Vec<u8>, content_type: &str) -> Vec<u8> {
+    if !content_type.starts_with("image/") {
+        return data;
+    }
+
+    match strip_metadata(&data) {
+        Ok(stripped) => {
+            log::info!(
+                "Stripped {} bytes of metadata from {}",
+                data.len().saturating_sub(stripped.len()),
+                content_type
+            );
+            stripped
+        }
+        Err(e) => {
+            log::warn!("Uploading {} with its metadata ({})", content_type, e);
+            data
+        }
+    }
+}
+
+fn strip_jpeg(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(&JPEG_SOI);
+    let mut orientation = None;
+    let mut pos = 2;
+
+    loop {
+        // Markers may be preceded by any number of fill bytes
+        while data.get(pos) == Some(&0xFF) && data.get(pos + 1) == Some(&0xFF) {
+            pos += 1;
+        }
+        if data.get(pos) != Some(&0xFF) {
+            return Err("malformed JPEG marker".to_string());
+        }
+        let marker = *data.get(pos + 1).ok_or("truncated JPEG")?;
+
+        if marker == EOI {
+            // Anything after EOI (MPF images, vendor trailers) is dropped
+            out.extend_from_slice(&[0xFF, EOI]);
+            break;
+        }
+        if (0xD0..=0xD7).contains(&marker) || marker == 0x01 {
+            out.extend_from_slice(&[0xFF, marker]);
+            pos += 2;
+            continue;
+        }
+
+        let length = u16::from_be_bytes([
+            *data.get(pos + 2).ok_or("truncated JPEG")?,
+            *data.get(pos + 3).ok_or("truncated JPEG")?,
+        ]) as usize;
+        let end = pos + 2 + length;
+        if length < 2 || end > data.len() {
+            return Err("truncated JPEG segment".to_string());
+        }
+        let payload = &data[pos + 4..end];
+
+        let is_metadata = match marker {
+            APP1 => {
+                if payload.starts_with(EXIF_HEADER) {
+                    orientation = orientation.or_else(|| exif_orientation(&payload[EXIF_HEADER.len()..]));
+                }
+                true
+            }
+            APP2 => payload.starts_with(MPF_HEADER),
+            APP13 | COM => true,
+            _ => false,
+        };
+        if !is_metadata {
+            out.extend_from_slice(&data[pos..end]);
+        }
+        pos = end;
+
+        if marker == SOS {
+            // Copy entropy-coded data up to the next marker; 0xFF bytes in
+            // it are stuffed as FF 00, restart markers are part of the scan
+            let scan_start = pos;
+            while pos + 1 < data.len() {
+                if data[pos] == 0xFF && data[pos + 1] != 0x00 && !(0xD0..=0xD7).contains(&data[pos + 1]) {
+                    break;
+                }
+                pos += 1;
+            }
+            if pos + 1 >= data.len() {
+                return Err("JPEG ends inside a scan".to_string());
+            }
+            out.extend_from_slice(&data[scan_start..pos]);
+        }
+    }
+
+    if let Some(orientation) = orientation.filter(|&orientation| orientation != 1) {
+        let rest = out.split_off(JPEG_SOI.len());
+        out.extend(orientation_segment(orientation));
+        out.extend(rest);
+    }
+
+    Ok(out)
+}
+
+/// Orientation (1-8) from a TIFF-structured EXIF block
+fn exif_orientation(tiff: &[u8]) -> Option<u16> {
+    let big_endian = match tiff.get(0..2)? {
+        b"MM" => true,
+        b"II" => false,
+        _ => return None,
+    };
+    let read_u16 = |at: usize| -> Option<u16> {
+        let bytes = [*tiff.get(at)?, *tiff.get(at + 1)?];
+        Some(if big_endian { u16::from_be_bytes(bytes) } else { u16::from_le_bytes(bytes) })
+    };
+    let read_u32 = |at: usize| -> Option<u32> {
+        let bytes = [*tiff.get(at)?, *tiff.get(at + 1)?, *tiff.get(at + 2)?, *tiff.get(at + 3)?];
+        Some(if big_endian { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) })
+    };
+
+    let ifd0 = read_u32(4)? as usize;
+    let entries = read_u16(ifd0)? as usize;
+    (0..entries)
+        .map(|i| ifd0 + 2 + i * 12)
+        .find(|&entry| read_u16(entry) == Some(EXIF_ORIENTATION_TAG))
+        .and_then(|entry| read_u16(entry + 8))
+        .filter(|orientation| (1..=8).contains(orientation))
+}
+
+/// APP1 segment whose EXIF block holds only the orientation
+fn orientation_segment(orientation: u16) -> Vec<u8> {
+    let mut payload = EXIF_HEADER.to_vec();
+    payload.extend_from_slice(b"MM\0\x2A"); // Big-endian TIFF header
+    payload.extend_from_slice(&8u32.to_be_bytes()); // IFD0 right after it
+    payload.extend_from_slice(&1u16.to_be_bytes()); // One entry
+    payload.extend_from_slice(&EXIF_ORIENTATION_TAG.to_be_bytes());
+    payload.extend_from_slice(&3u16.to_be_bytes()); // SHORT
+    payload.extend_from_slice(&1u32.to_be_bytes()); // Count
+    payload.extend_from_slice(&orientation.to_be_bytes());
+    payload.extend_from_slice(&[0, 0]); // Value padding
+    payload.extend_from_slice(&0u32.to_be_bytes()); // No next IFD
+
+    let mut segment = vec![0xFF, APP1];
+    segment.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+    segment.extend_from_slice(&payload);
+    segment
+}
+
+fn strip_png(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(&PNG_SIGNATURE);
+    let mut pos = PNG_SIGNATURE.len();
+
+    loop {
+        let header = data.get(pos..pos + 8).ok_or("truncated PNG chunk")?;
+        let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let chunk_type = &header[4..8];
+        // Length, type, data, CRC
+        let end = pos + 12 + length;
+        if end > data.len() {
+            return Err("truncated PNG chunk".to_string());
+        }
+
+        if !PNG_METADATA_CHUNKS.iter().any(|metadata| &metadata[..] == chunk_type) {
+            out.extend_from_slice(&data[pos..end]);
+        }
+        pos = end;
+
+        if chunk_type == b"IEND" {
+            break;
+        }
+    }
+
+    Ok(out)
+}
+
+fn strip_webp(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::with_capacity(data.len());
+    out.extend_from_slice(&data[0..12]);
+    let mut pos = 12;
+
+    while pos < data.len() {
+        let header = data.get(pos..pos + 8).ok_or("truncated WebP chunk")?;
+        let fourcc = &header[0..4];
+        let size = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        // Chunks are padded to an even size
+        let end = pos + 8 + size + (size & 1);
+        if end > data.len() {
+            return Err("truncated WebP chunk".to_string());
+        }
+
+        match fourcc {
+            b"EXIF" | b"XMP " => {}
+            b"VP8X" if size > 0 => {
+                let start = out.len();
+                out.extend_from_slice(&data[pos..end]);
+                out[start + 8] &= !(VP8X_EXIF_FLAG | VP8X_XMP_FLAG);
+            }
+            _ => out.extend_from_slice(&data[pos..end]),
+        }
+        pos = end;
+    }
+
+    let riff_size = (out.len() - 8) as u32;
+    out[4..8].copy_from_slice(&riff_size.to_le_bytes());
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(marker: u8, payload: &[u8]) -> Vec<u8> {
+        let mut segment = vec![0xFF, marker];
+        segment.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+        segment.extend_from_slice(payload);
+        segment
+    }
+
+    /// EXIF block with GPS latitude ref and an orientation of 6
+    fn exif_payload() -> Vec<u8> {
+        let mut payload = EXIF_HEADER.to_vec();
+        payload.extend_from_slice(b"II\x2A\0");
+        payload.extend_from_slice(&8u32.to_le_bytes());
+        payload.extend_from_slice(&2u16.to_le_bytes());
+        // Orientation: SHORT 6
+        payload.extend_from_slice(&EXIF_ORIENTATION_TAG.to_le_bytes());
+        payload.extend_from_slice(&3u16.to_le_bytes());
+        payload.extend_from_slice(&1u32.to_le_bytes());
+        payload.extend_from_slice(&[6, 0, 0, 0]);
+        // GPS IFD pointer, standing in for the location data
+        payload.extend_from_slice(&0x8825u16.to_le_bytes());
+        payload.extend_from_slice(&4u16.to_le_bytes());
+        payload.extend_from_slice(&1u32.to_le_bytes());
+        payload.extend_from_slice(&38u32.to_le_bytes());
+        payload.extend_from_slice(&0u32.to_le_bytes());
+        payload.extend_from_slice(b"GPS 52.5200N 13.4050E");
+        payload
+    }
+
+    fn sample_jpeg(exif: &[u8]) -> Vec<u8> {
+        let mut jpeg = JPEG_SOI.to_vec();
+        jpeg.extend(segment(0xE0, b"JFIF\0\x01\x01\0\0\x01\0\x01\0\0"));
+        jpeg.extend(segment(APP1, exif));
+        jpeg.extend(segment(COM, b"shot on a phone"));
+        jpeg.extend(segment(0xDB, &[0; 65])); // Quantization table
+        jpeg.extend(segment(SOS, &[1, 1, 0, 0, 63, 0]));
+        // Scan data with a stuffed 0xFF and a restart marker
+        jpeg.extend_from_slice(&[0x12, 0xFF, 0x00, 0x34, 0xFF, 0xD0, 0x56]);
+        jpeg.extend_from_slice(&[0xFF, EOI]);
+        jpeg
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack.windows(needle.len()).any(|window| window == needle)
+    }
+
+    #[test]
+    fn test_strip_jpeg_removes_exif() {
+        let jpeg = sample_jpeg(&exif_payload());
+        assert!(contains(&jpeg, b"GPS 52.5200N"));
+
+        let stripped = strip_metadata(&jpeg).unwrap();
+        assert!(!contains(&stripped, b"GPS 52.5200N"));
+        assert!(!contains(&stripped, b"shot on a phone"));
+        assert!(stripped.starts_with(&JPEG_SOI));
+        assert!(stripped.ends_with(&[0xFF, EOI]));
+        // Image data is untouched
+        assert!(contains(&stripped, &[0x12, 0xFF, 0x00, 0x34, 0xFF, 0xD0, 0x56]));
+        assert!(contains(&stripped, b"JFIF"));
+
+        // Only the orientation survives, in a block of its own
+        assert_eq!(&stripped[2..4], &[0xFF, APP1]);
+        assert_eq!(exif_orientation(&stripped[4 + 2 + EXIF_HEADER.len()..]), Some(6));
+        assert_eq!(strip_metadata(&stripped).unwrap(), stripped);
+    }
+
+    #[test]
+    fn test_strip_jpeg_without_orientation() {
+        let mut exif = exif_payload();
+        // Orientation 1 is the default, so no EXIF block is needed at all
+        let value_at = EXIF_HEADER.len() + 8 + 2 + 8;
+        exif[value_at] = 1;
+
+        let stripped = strip_metadata(&sample_jpeg(&exif)).unwrap();
+        assert!(!contains(&stripped, EXIF_HEADER));
+        assert_eq!(&stripped[2..4], &[0xFF, 0xE0]);
+    }
+
+    #[test]
+    fn test_strip_png_text_chunks() {
+        let chunk = |kind: &[u8], data: &[u8]| {
+            let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+            chunk.extend_from_slice(kind);
+            chunk.extend_from_slice(data);
+            chunk.extend_from_slice(&[0; 4]); // CRC isn't checked here
+            chunk
+        };
+        let mut png = PNG_SIGNATURE.to_vec();
+        png.extend(chunk(b"IHDR", &[0; 13]));
+        png.extend(chunk(b"tEXt", b"Comment\0home address"));
+        png.extend(chunk(b"eXIf", b"MM\0*"));
+        png.extend(chunk(b"IDAT", &[1, 2, 3]));
+        png.extend(chunk(b"IEND", &[]));
+
+        let stripped = strip_metadata(&png).unwrap();
+        assert!(!contains(&stripped, b"home address"));
+        assert!(!contains(&stripped, b"eXIf"));
+        assert!(contains(&stripped, b"IHDR"));
+        assert!(contains(&stripped, &[b'I', b'D', b'A', b'T', 1, 2, 3]));
+    }
+
+    #[test]
+    fn test_strip_webp_exif_chunk() {
+        let chunk = |fourcc: &[u8], data: &[u8]| {
+            let mut chunk = fourcc.to_vec();
+            chunk.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            chunk.extend_from_slice(data);
+            if data.len() % 2 == 1 {
+                chunk.push(0);
+            }
+            chunk
+        };
+        let mut body = b"WEBP".to_vec();
+        body.extend(chunk(b"VP8X", &[VP8X_EXIF_FLAG | VP8X_XMP_FLAG, 0, 0, 0, 0, 0, 0, 0, 0, 0]));
+        body.extend(chunk(b"VP8L", &[7, 7, 7]));
+        body.extend(chunk(b"EXIF", b"GPS here"));
+        body.extend(chunk(b"XMP ", b"<x:xmpmeta/>"));
+        let mut webp = b"RIFF".to_vec();
+        webp.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        webp.extend(body);
+
+        let stripped = strip_metadata(&webp).unwrap();
+        assert!(!contains(&stripped, b"GPS here"));
+        assert!(!contains(&stripped, b"xmpmeta"));
+        assert_eq!(stripped[20] & (VP8X_EXIF_FLAG | VP8X_XMP_FLAG), 0);
+        assert_eq!(u32::from_le_bytes(stripped[4..8].try_into().unwrap()) as usize, stripped.len() - 8);
+    }
+
+    #[test]
+    fn test_unsupported_and_non_image_pass_through() {
+        let gif = b"GIF89a\x01\0\x01\0".to_vec();
+        assert!(strip_metadata(&gif).is_err());
+        assert_eq!(strip_for_upload(gif.clone(), "image/gif"), gif);
+
+        // Video bytes are never parsed, even if they look like a JPEG
+        let jpeg = sample_jpeg(&exif_payload());
+        assert_eq!(strip_for_upload(jpeg.clone(), "video/mp4"), jpeg);
+
+        // A broken JPEG goes up as-is rather than failing the upload
+        let truncated = jpeg[..20].to_vec();
+        assert_eq!(strip_for_upload(truncated.clone(), "image/jpeg"), truncated);
+    }
+}
@@ -0,0 +1,111 @@
+//! Keyboard shortcuts
+//!
+//! `j`/`k` move the selection through the notes on screen, Enter opens the
+//! selected note, `n` starts a new note and `/` jumps to search. Shortcuts
+//! never fire while the user is typing.
+
+/// Action bound to a key
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shortcut {
+    NextItem,
+    PreviousItem,
+    OpenItem,
+    NewNote,
+    FocusSearch,
+}
+
+/// The shortcut for a `KeyboardEvent.key`, if any
+///
+/// Keys pressed with Ctrl, Alt or Meta are left to the browser.
+pub fn shortcut_for_key(key: &str, has_modifier: bool) -> Option<Shortcut> {
+    if has_modifier {
+        return None;
+    }
+    match key {
+        "j" => Some(Shortcut::NextItem),
+        "k" => Some(Shortcut::PreviousItem),
+        "Enter" => Some(Shortcut::OpenItem),
+        "n" => Some(Shortcut::NewNote),
+        "/" => Some(Shortcut::FocusSearch),
+        _ => None,
+    }
+}
+
+/// Input types that don't take text, so shortcuts still work on them
+const NON_TEXT_INPUT_TYPES: &[&str] = &["checkbox", "radio", "button", "submit", "reset", "range", "color", "file"];
+
+/// Whether a key press on this element is the user typing
+///
+/// `tag_name` is the element's tag in any case, `input_type` its `type`
+/// attribute for inputs.
+pub fn is_typing_target(tag_name: &str, input_type: Option<&str>, is_content_editable: bool) -> bool {
+    if is_content_editable {
+        return true;
+    }
+    match tag_name.to_ascii_lowercase().as_str() {
+        "textarea" | "select" => true,
+        "input" => {
+            let input_type = input_type.unwrap_or("text").to_ascii_lowercase();
+            !NON_TEXT_INPUT_TYPES.contains(&input_type.as_str())
+        }
+        _ => false,
+    }
+}
+
+/// Index to select after moving one step from `current` through `len` items
+///
+/// Without a selection, moving forward starts at the first item and moving
+/// back at the last; both ends wrap around.
+pub fn step_index(current: Option<usize>, len: usize, forward: bool) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let next = match current.filter(|&index| index < len) {
+        None if forward => 0,
+        None => len - 1,
+        Some(index) if forward => (index + 1) % len,
+        Some(index) => (index + len - 1) % len,
+    };
+    Some(next)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typing_targets_are_ignored() {
+        assert!(is_typing_target("TEXTAREA", None, false));
+        assert!(is_typing_target("INPUT", None, false));
+        assert!(is_typing_target("input", Some("search"), false));
+        assert!(is_typing_target("SELECT", None, false));
+        assert!(is_typing_target("DIV", None, true));
+
+        assert!(!is_typing_target("INPUT", Some("checkbox"), false));
+        assert!(!is_typing_target("BODY", None, false));
+        assert!(!is_typing_target("ARTICLE", None, false));
+        assert!(!is_typing_target("BUTTON", None, false));
+    }
+
+    #[test]
+    fn test_step_index_wraps() {
+        assert_eq!(step_index(None, 3, true), Some(0));
+        assert_eq!(step_index(None, 3, false), Some(2));
+        assert_eq!(step_index(Some(0), 3, true), Some(1));
+        assert_eq!(step_index(Some(2), 3, true), Some(0));
+        assert_eq!(step_index(Some(0), 3, false), Some(2));
+        assert_eq!(step_index(Some(1), 3, false), Some(0));
+        // The selected item scrolled away or the feed shrank
+        assert_eq!(step_index(Some(7), 3, true), Some(0));
+        assert_eq!(step_index(Some(0), 0, true), None);
+    }
+
+    #[test]
+    fn test_shortcut_keys() {
+        assert_eq!(shortcut_for_key("j", false), Some(Shortcut::NextItem));
+        assert_eq!(shortcut_for_key("k", false), Some(Shortcut::PreviousItem));
+        assert_eq!(shortcut_for_key("/", false), Some(Shortcut::FocusSearch));
+        assert_eq!(shortcut_for_key("n", true), None);
+        assert_eq!(shortcut_for_key("J", false), None);
+    }
+}
@@ -1,5 +1,5 @@
 /// Markdown rendering utilities for NIP-23 long-form content
-use pulldown_cmark::{Parser, Options, html};
+use pulldown_cmark::{Parser, Options, html, Event, Tag, TagEnd};
 
 /// Render markdown to safe HTML
 /// Uses pulldown-cmark for parsing and ammonia for sanitization
@@ -13,14 +13,81 @@ pub fn render_markdown(markdown: &str) -> String {
     options.insert(Options::ENABLE_HEADING_ATTRIBUTES);
 
     // Parse markdown to HTML
-    let parser = Parser::new_ext(markdown, options);
+    let events = degrade_ragged_tables(markdown, options);
     let mut html_output = String::new();
-    html::push_html(&mut html_output, parser);
+    html::push_html(&mut html_output, events.into_iter());
 
     // Sanitize HTML to prevent XSS
     sanitize_html(&html_output)
 }
 
+/// Parse markdown, turning tables with ragged rows back into paragraphs
+///
+/// GFM pads short rows and drops extra cells, which silently mangles tables
+/// that weren't meant as tables (or were typed wrong). Those are rendered as
+/// the plain text they were written as instead.
+fn degrade_ragged_tables(markdown: &str, options: Options) -> Vec<Event<'_>> {
+    let mut events = Vec::new();
+    let mut parser = Parser::new_ext(markdown, options).into_offset_iter();
+
+    while let Some((event, range)) = parser.next() {
+        if !matches!(event, Event::Start(Tag::Table(_))) {
+            events.push(event);
+            continue;
+        }
+
+        let mut table = vec![event];
+        for (event, _) in parser.by_ref() {
+            let done = matches!(event, Event::End(TagEnd::Table));
+            table.push(event);
+            if done {
+                break;
+            }
+        }
+
+        let source = &markdown[range];
+        if is_ragged_table(source) {
+            let mut without_tables = options;
+            without_tables.remove(Options::ENABLE_TABLES);
+            events.extend(Parser::new_ext(source, without_tables));
+        } else {
+            events.extend(table);
+        }
+    }
+
+    events
+}
+
+/// Whether the rows of a pipe table (header, delimiter, body) differ in cell count
+fn is_ragged_table(source: &str) -> bool {
+    let counts: Vec<usize> = source
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(count_table_cells)
+        .collect();
+    counts.windows(2).any(|pair| pair[0] != pair[1])
+}
+
+/// Number of cells in a pipe table row (escaped `\|` doesn't split cells)
+fn count_table_cells(line: &str) -> usize {
+    let mut row = line.trim();
+    row = row.strip_prefix('|').unwrap_or(row);
+    if row.ends_with('|') && !row.ends_with("\\|") {
+        row = &row[..row.len() - 1];
+    }
+
+    let mut cells = 1;
+    let mut escaped = false;
+    for c in row.chars() {
+        match c {
+            '\\' if !escaped => escaped = true,
+            '|' if !escaped => cells += 1,
+            _ => escaped = false,
+        }
+    }
+    cells
+}
+
 /// Sanitize HTML using ammonia
 /// Allows safe tags and attributes while removing potentially dangerous content
 pub fn sanitize_html(html: &str) -> String {
@@ -41,6 +108,8 @@ pub fn sanitize_html(html: &str) -> String {
             "img",
             "div", "span",
             "sup", "sub",
+            // Task list checkboxes
+            "input",
         ])
         // Allow specific attributes on specific tags
         // Note: "rel" is NOT in the "a" attributes because link_rel() handles it automatically
@@ -53,7 +122,11 @@ pub fn sanitize_html(html: &str) -> String {
             "span" => hashset!["class"],
             "th" => hashset!["align"],
             "td" => hashset!["align"],
+            "input" => hashset!["checked"],
         ])
+        // Only read-only checkboxes, whatever the source asked for
+        .set_tag_attribute_value("input", "type", "checkbox")
+        .set_tag_attribute_value("input", "disabled", "")
         // Allow all http(s) URL schemes
         .url_schemes(hashset!["http", "https", "mailto"])
         // Set rel="noopener noreferrer" for external links (handled automatically)
@@ -66,8 +139,6 @@ pub fn sanitize_html(html: &str) -> String {
 /// Extract plain text from markdown (for previews)
 #[allow(dead_code)]
 pub fn markdown_to_text(markdown: &str) -> String {
-    use pulldown_cmark::{Event, Tag};
-
     let parser = Parser::new(markdown);
     let mut text = String::new();
 
@@ -102,6 +173,90 @@ pub fn wrap_with_prose_styles(html: &str) -> String {
     )
 }
 
+/// Formatting applied by the article editor's toolbar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkdownFormat {
+    Bold,
+    Italic,
+    Link,
+    Heading,
+    List,
+    Quote,
+    Code,
+}
+
+/// Text after applying a toolbar format, with the selection to restore
+///
+/// Selection bounds are UTF-8 byte offsets into `text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownEdit {
+    pub text: String,
+    pub selection_start: usize,
+    pub selection_end: usize,
+}
+
+/// Apply `format` to the selection `start..end` (UTF-8 byte offsets)
+///
+/// Inline formats wrap the selection and keep the wrapped text selected; with
+/// nothing selected, the cursor lands between the markers. Links select the
+/// URL placeholder so it can be typed over. Block formats prefix every line
+/// the selection touches and select those lines.
+pub fn apply_markdown_format(text: &str, start: usize, end: usize, format: MarkdownFormat) -> MarkdownEdit {
+    let clamp = |pos: usize| {
+        let mut pos = pos.min(text.len());
+        while !text.is_char_boundary(pos) {
+            pos -= 1;
+        }
+        pos
+    };
+    let (start, end) = (clamp(start.min(end)), clamp(start.max(end)));
+    let selected = &text[start..end];
+
+    let wrap = |before: &str, after: &str| MarkdownEdit {
+        text: format!("{}{}{}{}{}", &text[..start], before, selected, after, &text[end..]),
+        selection_start: start + before.len(),
+        selection_end: end + before.len(),
+    };
+
+    match format {
+        MarkdownFormat::Bold => wrap("**", "**"),
+        MarkdownFormat::Italic => wrap("*", "*"),
+        MarkdownFormat::Code if selected.contains('\n') => wrap("```\n", "\n```"),
+        MarkdownFormat::Code => wrap("`", "`"),
+        MarkdownFormat::Link => {
+            let label = if selected.is_empty() { "link text" } else { selected };
+            let link = format!("[{}](url)", label);
+            let url_start = start + label.len() + 3;
+            MarkdownEdit {
+                text: format!("{}{}{}", &text[..start], link, &text[end..]),
+                selection_start: url_start,
+                selection_end: url_start + "url".len(),
+            }
+        }
+        MarkdownFormat::Heading => prefix_lines(text, start, end, "## "),
+        MarkdownFormat::List => prefix_lines(text, start, end, "- "),
+        MarkdownFormat::Quote => prefix_lines(text, start, end, "> "),
+    }
+}
+
+/// Prefix each line touched by `start..end`, selecting the changed lines
+fn prefix_lines(text: &str, start: usize, end: usize, prefix: &str) -> MarkdownEdit {
+    let line_start = text[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = text[end..].find('\n').map_or(text.len(), |i| end + i);
+
+    let block = text[line_start..line_end]
+        .split('\n')
+        .map(|line| format!("{}{}", prefix, line))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    MarkdownEdit {
+        selection_start: line_start,
+        selection_end: line_start + block.len(),
+        text: format!("{}{}{}", &text[..line_start], block, &text[line_end..]),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -147,6 +302,69 @@ mod tests {
         assert!(html.contains("alt=\"Alt text\""));
     }
 
+    /// Opening and closing tags in document order, e.g. `["table", "/table"]`
+    fn element_tree(html: &str) -> Vec<String> {
+        html.split('<')
+            .skip(1)
+            .map(|tag| {
+                tag.split(|c: char| c.is_whitespace() || c == '>' || c == '/' && !tag.starts_with('/'))
+                    .next()
+                    .unwrap_or_default()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_render_2x2_table_tree() {
+        let md = "| A | B |\n|---|---|\n| 1 | 2 |\n| 3 | 4 |";
+        let html = render_markdown(md);
+        assert_eq!(
+            element_tree(&html),
+            vec![
+                "table", "thead", "tr", "th", "/th", "th", "/th", "/tr", "/thead",
+                "tbody",
+                "tr", "td", "/td", "td", "/td", "/tr",
+                "tr", "td", "/td", "td", "/td", "/tr",
+                "/tbody", "/table",
+            ]
+        );
+        assert!(html.contains("<td>4</td>"));
+    }
+
+    #[test]
+    fn test_ragged_table_degrades_to_paragraph() {
+        let md = "| A | B |\n|---|---|\n| 1 | 2 | 3 |\n| 4 |";
+        let html = render_markdown(md);
+        assert!(!html.contains("<table"));
+        assert_eq!(element_tree(&html), vec!["p", "/p"]);
+        assert!(html.contains("| 1 | 2 | 3 |"));
+
+        // Escaped pipes don't count as cell separators
+        assert!(!is_ragged_table("| a \\| b | c |\n|---|---|\n| 1 | 2 |"));
+    }
+
+    #[test]
+    fn test_render_task_list_tree() {
+        let md = "- [ ] todo\n- [x] done\n- plain item";
+        let html = render_markdown(md);
+        assert_eq!(
+            element_tree(&html),
+            vec!["ul", "li", "input", "/li", "li", "input", "/li", "li", "/li", "/ul"]
+        );
+        assert_eq!(html.matches("type=\"checkbox\"").count(), 2);
+        assert_eq!(html.matches("disabled").count(), 2);
+        assert_eq!(html.matches("checked").count(), 1);
+    }
+
+    #[test]
+    fn test_non_checkbox_inputs_neutralized() {
+        let html = sanitize_html(r#"<input type="text" value="x" onfocus="alert(1)">"#);
+        assert!(html.contains("type=\"checkbox\""));
+        assert!(!html.contains("text"));
+        assert!(!html.contains("onfocus"));
+    }
+
     #[test]
     fn test_render_table() {
         let md = "| Header 1 | Header 2 |\n|----------|----------|\n| Cell 1   | Cell 2   |";
@@ -155,4 +373,28 @@ mod tests {
         assert!(html.contains("<th>"));
         assert!(html.contains("<td>"));
     }
+
+    #[test]
+    fn test_format_wraps_selection() {
+        let edit = apply_markdown_format("say foo now", 4, 7, MarkdownFormat::Bold);
+        assert_eq!(edit.text, "say **foo** now");
+        assert_eq!(&edit.text[edit.selection_start..edit.selection_end], "foo");
+        assert_eq!((edit.selection_start, edit.selection_end), (6, 9));
+
+        // Empty selection: cursor between the markers
+        let edit = apply_markdown_format("ab", 1, 1, MarkdownFormat::Italic);
+        assert_eq!(edit.text, "a**b");
+        assert_eq!((edit.selection_start, edit.selection_end), (2, 2));
+
+        let edit = apply_markdown_format("see docs", 4, 8, MarkdownFormat::Link);
+        assert_eq!(edit.text, "see [docs](url)");
+        assert_eq!(&edit.text[edit.selection_start..edit.selection_end], "url");
+    }
+
+    #[test]
+    fn test_format_prefixes_selected_lines() {
+        let edit = apply_markdown_format("intro\none\ntwo", 7, 11, MarkdownFormat::List);
+        assert_eq!(edit.text, "intro\n- one\n- two");
+        assert_eq!(&edit.text[edit.selection_start..edit.selection_end], "- one\n- two");
+    }
 }
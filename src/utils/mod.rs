@@ -19,12 +19,27 @@ pub mod profile_prefetch;
 pub mod repost;
 pub mod event;
 pub mod clipboard;
+pub mod note_split;
+pub mod text_length;
+pub mod activity_digest;
+pub mod search_query;
+pub mod addressable;
+pub mod image_metadata;
+pub mod keyboard_nav;
+pub mod blurhash;
+pub mod file_metadata;
+pub mod reply_tags;
+pub mod nip98;
+pub mod nip22;
+pub mod video_variants;
+#[cfg(test)]
+pub mod testing;
 
-pub use thread_tree::{ThreadNode, ThreadNodeSource, build_thread_tree, merge_pending_into_tree};
+pub use thread_tree::{ThreadNode, ThreadNodeSource, SortMode, build_thread_tree, merge_pending_into_tree, apply_thread_scores, sort_thread, filter_thread_nodes};
 pub use list_kinds::{get_list_type_name, get_list_icon, get_item_count};
 pub use data_state::DataState;
 pub use format::{format_sats_with_separator, format_sats_compact, truncate_pubkey, shorten_url};
-pub use repost::{FeedItem, extract_reposted_event};
+pub use repost::{FeedItem, extract_reposted_event, blockquote_seed};
 pub use validation::{SignerValidationResult, get_current_user_pubkey};
 
 /// Generate a random alphanumeric ID (9 characters)
@@ -19,3 +19,143 @@ pub fn normalize_pubkey(pubkey_str: &str) -> Result<String, String> {
         }
     }
 }
+
+/// Decode a NIP-21 `nostr:` URI (or a bare bech32 entity)
+///
+/// Returns None for malformed bech32 and for `nsec`, which must never be
+/// treated as a shareable reference.
+pub fn decode_nostr_uri(uri: &str) -> Option<Nip19> {
+    let bech32 = uri.strip_prefix("nostr:").unwrap_or(uri);
+    match Nip19::from_bech32(bech32).ok()? {
+        Nip19::Secret(_) => None,
+        entity => Some(entity),
+    }
+}
+
+/// Relay hints embedded in an nprofile, nevent or naddr
+///
+/// Empty for entities that carry none, including npub and note.
+pub fn relay_hints(entity: &Nip19) -> Vec<String> {
+    let relays = match entity {
+        Nip19::Profile(nprofile) => &nprofile.relays,
+        Nip19::Event(nevent) => &nevent.relays,
+        Nip19::Coordinate(naddr) => &naddr.relays,
+        _ => return Vec::new(),
+    };
+    relays.iter().map(|relay| relay.to_string()).collect()
+}
+
+/// Maximum number of relay hints put into shareable entities
+pub const MAX_RELAY_HINTS: usize = 3;
+
+/// Valid, distinct relay URLs to embed as hints
+fn relay_hint_urls(relays: &[String]) -> Vec<RelayUrl> {
+    let mut urls: Vec<RelayUrl> = Vec::new();
+    for relay in relays {
+        if let Ok(url) = RelayUrl::parse(relay) {
+            if !urls.contains(&url) {
+                urls.push(url);
+            }
+        }
+        if urls.len() == MAX_RELAY_HINTS {
+            break;
+        }
+    }
+    urls
+}
+
+/// Encode a shareable `nevent` with relay hints and an optional author
+pub fn encode_nevent(event_id: EventId, relays: &[String], author: Option<PublicKey>) -> Result<String, String> {
+    let mut nevent = Nip19Event::new(event_id);
+    nevent.author = author;
+    nevent.relays = relay_hint_urls(relays);
+    nevent.to_bech32().map_err(|e| format!("Failed to encode nevent: {}", e))
+}
+
+/// Encode a shareable `naddr` for a replaceable event with relay hints
+pub fn encode_naddr(kind: Kind, pubkey: PublicKey, identifier: &str, relays: &[String]) -> Result<String, String> {
+    let coordinate = Coordinate::new(kind, pubkey).identifier(identifier);
+    Nip19Coordinate::new(coordinate, relay_hint_urls(relays))
+        .to_bech32()
+        .map_err(|e| format!("Failed to encode naddr: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nevent_round_trip() {
+        let keys = Keys::generate();
+        let event_id = EventId::all_zeros();
+        let relays = vec![
+            "wss://relay.damus.io".to_string(),
+            "not a relay".to_string(),
+            "wss://relay.damus.io".to_string(),
+            "wss://nos.lol".to_string(),
+        ];
+
+        let encoded = encode_nevent(event_id, &relays, Some(keys.public_key())).unwrap();
+        match decode_nostr_uri(&format!("nostr:{}", encoded)) {
+            Some(Nip19::Event(nevent)) => {
+                assert_eq!(nevent.event_id, event_id);
+                assert_eq!(nevent.author, Some(keys.public_key()));
+                let relays: Vec<String> = nevent.relays.iter().map(|r| r.to_string()).collect();
+                assert_eq!(relays.len(), 2);
+                assert!(relays[0].starts_with("wss://relay.damus.io"));
+                assert!(relays[1].starts_with("wss://nos.lol"));
+            }
+            other => panic!("Expected nevent, got {:?}", other),
+        }
+
+        let bare = encode_nevent(event_id, &[], None).unwrap();
+        match decode_nostr_uri(&bare) {
+            Some(Nip19::Event(nevent)) => {
+                assert_eq!(nevent.event_id, event_id);
+                assert_eq!(nevent.author, None);
+                assert!(nevent.relays.is_empty());
+            }
+            other => panic!("Expected nevent, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_naddr_round_trip() {
+        let keys = Keys::generate();
+        let relays = vec!["wss://relay.example.com".to_string()];
+
+        for hints in [relays.as_slice(), &[]] {
+            let encoded = encode_naddr(Kind::LongFormTextNote, keys.public_key(), "my-article", hints).unwrap();
+            match decode_nostr_uri(&encoded) {
+                Some(Nip19::Coordinate(naddr)) => {
+                    assert_eq!(naddr.coordinate.kind, Kind::LongFormTextNote);
+                    assert_eq!(naddr.coordinate.public_key, keys.public_key());
+                    assert_eq!(naddr.coordinate.identifier, "my-article");
+                    assert_eq!(naddr.relays.len(), hints.len());
+                }
+                other => panic!("Expected naddr, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_relay_hints_from_nevent() {
+        let mut nevent = Nip19Event::new(EventId::all_zeros());
+        nevent.relays = vec![
+            RelayUrl::parse("wss://niche.relay.example").unwrap(),
+            RelayUrl::parse("wss://nos.lol").unwrap(),
+        ];
+        let encoded = nevent.to_bech32().unwrap();
+
+        let hints = relay_hints(&decode_nostr_uri(&encoded).unwrap());
+        assert_eq!(hints.len(), 2);
+        assert!(hints[0].starts_with("wss://niche.relay.example"));
+        assert!(hints[1].starts_with("wss://nos.lol"));
+
+        // No hints in the TLV, or an entity type without hints
+        let bare = encode_nevent(EventId::all_zeros(), &[], None).unwrap();
+        assert!(relay_hints(&decode_nostr_uri(&bare).unwrap()).is_empty());
+        let note = EventId::all_zeros().to_bech32().unwrap();
+        assert!(relay_hints(&decode_nostr_uri(&note).unwrap()).is_empty());
+    }
+}
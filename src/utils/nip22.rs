@@ -0,0 +1,174 @@
+//! NIP-22 comments (kind 1111)
+//!
+//! A comment names its root scope with uppercase tags (`A` for an
+//! addressable root such as an article, `E` otherwise, plus `K` for the root
+//! kind and `P` for its author) and the item it answers with the lowercase
+//! equivalents. A top-level comment's parent is the root itself; a reply's
+//! parent is the comment it answers.
+
+use nostr_sdk::{Alphabet, Event, Filter, Kind, SingleLetterTag, Tag};
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::stores::nostr_client;
+
+/// `kind:pubkey:d` address of an addressable event
+pub fn event_address(event: &Event) -> Option<String> {
+    if !event.kind.is_addressable() {
+        return None;
+    }
+    let identifier = event.tags.identifier().unwrap_or_default();
+    Some(format!("{}:{}:{}", event.kind.as_u16(), event.pubkey.to_hex(), identifier))
+}
+
+fn tag(parts: &[&str]) -> Tag {
+    Tag::parse(parts.iter().map(|p| p.to_string()).collect::<Vec<_>>()).expect("non-empty tag")
+}
+
+/// Tags for a comment in the thread rooted at `root`
+///
+/// `parent` is the comment being answered, or None for a top-level comment
+/// on the root.
+pub fn build_nip22_comment_tags(root: &Event, parent: Option<&Event>) -> Vec<Tag> {
+    let root_id = root.id.to_hex();
+    let root_author = root.pubkey.to_hex();
+    let root_kind = root.kind.as_u16().to_string();
+    let root_address = event_address(root);
+
+    // Root scope
+    let mut tags = vec![match &root_address {
+        Some(address) => tag(&["A", address]),
+        None => tag(&["E", &root_id, "", &root_author]),
+    }];
+    tags.push(tag(&["K", &root_kind]));
+    tags.push(tag(&["P", &root_author]));
+
+    // Parent item
+    match parent.filter(|parent| parent.id != root.id) {
+        Some(parent) => {
+            let parent_author = parent.pubkey.to_hex();
+            tags.push(tag(&["e", &parent.id.to_hex(), "", &parent_author]));
+            tags.push(tag(&["k", &parent.kind.as_u16().to_string()]));
+            tags.push(tag(&["p", &parent_author]));
+        }
+        None => {
+            if let Some(address) = &root_address {
+                tags.push(tag(&["a", address]));
+            }
+            tags.push(tag(&["e", &root_id, "", &root_author]));
+            tags.push(tag(&["k", &root_kind]));
+            tags.push(tag(&["p", &root_author]));
+        }
+    }
+
+    tags
+}
+
+/// Filters for every comment on `root`
+///
+/// The root scope filter finds comments at any depth. The lowercase `e`
+/// filter also catches kind 1 replies and comments from clients that only
+/// tag the parent.
+pub fn comment_filters(root: &Event) -> Vec<Filter> {
+    let scope = match event_address(root) {
+        Some(address) => Filter::new()
+            .kind(Kind::Comment)
+            .custom_tag(SingleLetterTag::uppercase(Alphabet::A), address),
+        None => Filter::new()
+            .kind(Kind::Comment)
+            .custom_tag(SingleLetterTag::uppercase(Alphabet::E), root.id.to_hex()),
+    };
+    let direct = Filter::new()
+        .kinds(vec![Kind::TextNote, Kind::Comment])
+        .event(root.id);
+
+    vec![scope.limit(500), direct.limit(500)]
+}
+
+/// All comments on `root`, oldest first
+pub async fn fetch_comments(root: &Event) -> Vec<Event> {
+    let mut seen = HashSet::new();
+    let mut comments = Vec::new();
+
+    for filter in comment_filters(root) {
+        match nostr_client::fetch_events_aggregated(filter, Duration::from_secs(10)).await {
+            Ok(events) => {
+                comments.extend(events.into_iter().filter(|event| seen.insert(event.id)));
+            }
+            Err(e) => log::warn!("Failed to fetch comments for {}: {}", root.id, e),
+        }
+    }
+
+    comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    comments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::{EventBuilder, Keys};
+
+    fn tag_values(tags: &[Tag]) -> Vec<Vec<String>> {
+        tags.iter().map(|tag| tag.as_slice().to_vec()).collect()
+    }
+
+    fn article(author: &Keys) -> Event {
+        EventBuilder::new(Kind::LongFormTextNote, "# Hello")
+            .tag(Tag::identifier("hello-world"))
+            .sign_with_keys(author)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_comment_on_article() {
+        let author = Keys::generate();
+        let article = article(&author);
+        let address = format!("30023:{}:hello-world", author.public_key().to_hex());
+        let id = article.id.to_hex();
+        let pubkey = author.public_key().to_hex();
+
+        let tags = tag_values(&build_nip22_comment_tags(&article, None));
+        assert_eq!(tags, vec![
+            vec!["A".to_string(), address.clone()],
+            vec!["K".to_string(), "30023".to_string()],
+            vec!["P".to_string(), pubkey.clone()],
+            vec!["a".to_string(), address],
+            vec!["e".to_string(), id, String::new(), pubkey.clone()],
+            vec!["k".to_string(), "30023".to_string()],
+            vec!["p".to_string(), pubkey],
+        ]);
+    }
+
+    #[test]
+    fn test_reply_to_comment() {
+        let author = Keys::generate();
+        let commenter = Keys::generate();
+        let article = article(&author);
+        let comment = EventBuilder::new(Kind::Comment, "Nice")
+            .tags(build_nip22_comment_tags(&article, None))
+            .sign_with_keys(&commenter)
+            .unwrap();
+
+        let tags = tag_values(&build_nip22_comment_tags(&article, Some(&comment)));
+        let address = format!("30023:{}:hello-world", author.public_key().to_hex());
+        let commenter_hex = commenter.public_key().to_hex();
+        assert_eq!(tags, vec![
+            // Root stays the article
+            vec!["A".to_string(), address],
+            vec!["K".to_string(), "30023".to_string()],
+            vec!["P".to_string(), author.public_key().to_hex()],
+            // Parent is the comment
+            vec!["e".to_string(), comment.id.to_hex(), String::new(), commenter_hex.clone()],
+            vec!["k".to_string(), "1111".to_string()],
+            vec!["p".to_string(), commenter_hex],
+        ]);
+
+        // Regular (non-addressable) roots are scoped by event ID
+        let video = EventBuilder::new(Kind::from(21_u16), "clip").sign_with_keys(&author).unwrap();
+        let tags = tag_values(&build_nip22_comment_tags(&video, Some(&video)));
+        assert_eq!(tags[0], vec!["E".to_string(), video.id.to_hex(), String::new(), author.public_key().to_hex()]);
+        assert_eq!(tags[1], vec!["K".to_string(), "21".to_string()]);
+        assert!(tags.iter().all(|t| t[0] != "A" && t[0] != "a"));
+        assert_eq!(tags[3][1], video.id.to_hex());
+    }
+}
@@ -0,0 +1,106 @@
+//! NIP-98 HTTP Auth
+//!
+//! Services like NIP-96 file servers and some DVMs authenticate requests with
+//! a signed kind 27235 event naming the URL (`u`), the HTTP method and,
+//! optionally, the SHA-256 of the body (`payload`). The event is base64
+//! encoded into an `Authorization: Nostr <base64>` header.
+
+use nostr_sdk::nips::nip98::{HttpData, HttpMethod};
+use nostr_sdk::Url;
+use std::str::FromStr;
+
+use crate::stores::nostr_client;
+use crate::stores::signer::SignerType;
+
+/// `Authorization` header value for a request, signed with the current signer
+///
+/// `payload_sha256` is the hex SHA-256 of the request body, if it should be
+/// bound to the header.
+pub async fn build_nip98_auth(
+    method: HttpMethod,
+    url: &str,
+    payload_sha256: Option<String>,
+) -> Result<String, String> {
+    let signer = nostr_client::get_signer().ok_or("Not authenticated. Please sign in first.")?;
+    sign_nip98_auth(&signer, method, url, payload_sha256.as_deref()).await
+}
+
+/// `Authorization` header value signed with `signer`
+async fn sign_nip98_auth(
+    signer: &SignerType,
+    method: HttpMethod,
+    url: &str,
+    payload_sha256: Option<&str>,
+) -> Result<String, String> {
+    let url = Url::parse(url).map_err(|e| format!("Invalid URL: {}", e))?;
+
+    let mut http_data = HttpData::new(url, method);
+    if let Some(hash) = payload_sha256 {
+        let hash = nostr_sdk::hashes::sha256::Hash::from_str(hash)
+            .map_err(|e| format!("Invalid payload hash: {}", e))?;
+        http_data = http_data.payload(hash);
+    }
+
+    let authorization = match signer {
+        SignerType::Keys(keys) => http_data.to_authorization(keys).await,
+        #[cfg(target_family = "wasm")]
+        SignerType::BrowserExtension(browser_signer) => http_data.to_authorization(browser_signer.as_ref()).await,
+        SignerType::NostrConnect(nostr_connect) => http_data.to_authorization(nostr_connect.as_ref()).await,
+    };
+
+    authorization.map_err(|e| format!("Failed to create NIP-98 auth: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::Engine;
+    use nostr_sdk::{Event, JsonUtil, Keys, Kind};
+    use crate::utils::testing::block_on;
+
+    fn decode(header: &str) -> Event {
+        let encoded = header.strip_prefix("Nostr ").expect("Nostr auth scheme");
+        let json = base64::engine::general_purpose::STANDARD.decode(encoded).unwrap();
+        Event::from_json(json).unwrap()
+    }
+
+    fn tag_value(event: &Event, name: &str) -> Option<String> {
+        event
+            .tags
+            .iter()
+            .map(|tag| tag.as_slice())
+            .find(|parts| parts.len() >= 2 && parts[0] == name)
+            .map(|parts| parts[1].clone())
+    }
+
+    #[test]
+    fn test_nip98_auth_header() {
+        let keys = Keys::generate();
+        let signer = SignerType::Keys(keys.clone());
+        let url = "https://nostr.build/api/v2/nip96/upload/abc123";
+        let payload = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+        let event = decode(&block_on(sign_nip98_auth(&signer, HttpMethod::DELETE, url, Some(payload))).unwrap());
+
+        assert!(event.verify().is_ok());
+        assert_eq!(event.kind, Kind::HttpAuth);
+        assert_eq!(event.pubkey, keys.public_key());
+        assert_eq!(tag_value(&event, "u").as_deref(), Some(url));
+        assert_eq!(tag_value(&event, "method").as_deref(), Some("DELETE"));
+        assert_eq!(tag_value(&event, "payload").as_deref(), Some(payload));
+    }
+
+    #[test]
+    fn test_nip98_auth_header_without_payload() {
+        let signer = SignerType::Keys(Keys::generate());
+        let url = "https://dvm.example.com/api/job";
+
+        let event = decode(&block_on(sign_nip98_auth(&signer, HttpMethod::POST, url, None)).unwrap());
+
+        assert_eq!(tag_value(&event, "u").as_deref(), Some(url));
+        assert_eq!(tag_value(&event, "method").as_deref(), Some("POST"));
+        assert_eq!(tag_value(&event, "payload"), None);
+
+        assert!(block_on(sign_nip98_auth(&signer, HttpMethod::GET, "not a url", None)).is_err());
+    }
+}
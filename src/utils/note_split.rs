@@ -0,0 +1,158 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Notes longer than this (in characters) are flagged as unusually long in the composer
+pub const LONG_NOTE_THRESHOLD: usize = 1000;
+
+/// Default maximum characters per thread part, including the "(n/m)" counter
+pub const DEFAULT_PART_LENGTH: usize = 500;
+
+/// Characters reserved at the end of each part for the "\n\n(nn/nn)" counter
+const COUNTER_RESERVE: usize = 10;
+
+/// Spans that must never be split: fenced code blocks (closed or running to the
+/// end of the note), inline code, URLs and nostr: URIs
+static ATOMIC_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)```.*?```|```.*|`[^`\n]+`|https?://\S+|nostr:[a-z0-9]+")
+        .expect("Failed to compile atomic span regex")
+});
+
+/// Separators tried (in order of preference) when looking for a split point.
+/// Sentence separators keep their punctuation on the preceding part.
+const BREAK_SEPARATORS: &[&str] = &["\n\n", "\n", ". ", "! ", "? ", " "];
+
+/// Split long note content into numbered parts suitable for publishing as a thread
+///
+/// Each part is at most `max_chars` characters (including its counter) unless a
+/// single URL, nostr: URI or code block is longer than that on its own, in which
+/// case it is kept whole in an oversized part. Returns the content unchanged as a
+/// single part when it already fits.
+pub fn split_into_thread(content: &str, max_chars: usize) -> Vec<String> {
+    let content = content.trim();
+    if content.is_empty() {
+        return Vec::new();
+    }
+    if content.chars().count() <= max_chars {
+        return vec![content.to_string()];
+    }
+
+    let budget = max_chars.saturating_sub(COUNTER_RESERVE).max(1);
+    let atomic: Vec<(usize, usize)> = ATOMIC_PATTERN
+        .find_iter(content)
+        .map(|m| (m.start(), m.end()))
+        .collect();
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+
+    while start < content.len() {
+        let rest = &content[start..];
+        let window_end = match rest.char_indices().nth(budget) {
+            Some((i, _)) => start + i,
+            None => content.len(),
+        };
+
+        let cut = if window_end == content.len() {
+            window_end
+        } else {
+            find_break(content, start, window_end, &atomic)
+        };
+
+        let chunk = content[start..cut].trim();
+        if !chunk.is_empty() {
+            chunks.push(chunk.to_string());
+        }
+
+        // Skip whitespace between parts
+        start = cut;
+        while let Some(c) = content[start..].chars().next() {
+            if !c.is_whitespace() {
+                break;
+            }
+            start += c.len_utf8();
+        }
+    }
+
+    let total = chunks.len();
+    if total <= 1 {
+        return chunks;
+    }
+
+    chunks
+        .into_iter()
+        .enumerate()
+        .map(|(i, chunk)| format!("{}\n\n({}/{})", chunk, i + 1, total))
+        .collect()
+}
+
+/// Return the atomic span strictly containing `pos`, if any
+fn atomic_span_at(pos: usize, atomic: &[(usize, usize)]) -> Option<(usize, usize)> {
+    atomic.iter().copied().find(|&(s, e)| s < pos && pos < e)
+}
+
+/// Find the best byte offset in `content[start..window_end]` to end a part at
+fn find_break(content: &str, start: usize, window_end: usize, atomic: &[(usize, usize)]) -> usize {
+    let window = &content[start..window_end];
+    // Avoid producing tiny parts when the only natural break is near the start
+    let min_len = window.len() / 3;
+
+    for sep in BREAK_SEPARATORS {
+        let mut search_end = window.len();
+        while let Some(idx) = window[..search_end].rfind(sep) {
+            if idx < min_len {
+                break;
+            }
+            let cut = start + idx + sep.trim_end().len();
+            if cut > start && atomic_span_at(cut, atomic).is_none() {
+                return cut;
+            }
+            search_end = idx;
+        }
+    }
+
+    // No safe break found: never cut through an atomic span
+    match atomic_span_at(window_end, atomic) {
+        Some((s, _)) if s > start => s,
+        Some((_, e)) => e,
+        None => window_end,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_note_is_single_part() {
+        let parts = split_into_thread("Hello nostr", 500);
+        assert_eq!(parts, vec!["Hello nostr".to_string()]);
+    }
+
+    #[test]
+    fn test_long_note_is_numbered_and_within_limit() {
+        let content = "This is a sentence that repeats. ".repeat(60);
+        let parts = split_into_thread(&content, 200);
+        assert!(parts.len() > 1);
+        let total = parts.len();
+        for (i, part) in parts.iter().enumerate() {
+            assert!(part.chars().count() <= 200, "part too long: {}", part.len());
+            assert!(part.ends_with(&format!("({}/{})", i + 1, total)));
+        }
+    }
+
+    #[test]
+    fn test_never_splits_url() {
+        let url = "https://example.com/a/very/long/path/that/should/not/be/split/anywhere";
+        let content = format!("{} {} {}", "word ".repeat(20), url, "word ".repeat(20));
+        let parts = split_into_thread(&content, 120);
+        assert!(parts.iter().any(|p| p.contains(url)));
+    }
+
+    #[test]
+    fn test_never_splits_code_block() {
+        let code = "```\nfn main() {\n    println!(\"hello world\");\n}\n```";
+        let content = format!("{}\n{}\n{}", "intro ".repeat(15), code, "outro ".repeat(15));
+        let parts = split_into_thread(&content, 100);
+        assert!(parts.iter().any(|p| p.contains(code)));
+    }
+}
@@ -0,0 +1,213 @@
+//! NIP-10 reply tags
+//!
+//! A kind 1 reply carries marked `e` tags (`root` for the thread root,
+//! `reply` for the note answered) with relay hints and author pubkeys, so
+//! other clients can fetch the thread. It also tags everyone in the parent's
+//! `p` tags plus the parent author.
+
+use nostr_sdk::nips::nip10::Marker;
+use nostr_sdk::{Event, EventId, PublicKey, RelayUrl, Tag, TagStandard};
+use std::collections::HashSet;
+
+/// An event referenced by a reply: its ID, where to find it, who wrote it
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThreadRef {
+    pub id: EventId,
+    pub relay_hint: Option<RelayUrl>,
+    pub author: Option<PublicKey>,
+}
+
+/// The thread root `parent` replies to, or None when `parent` is the root
+///
+/// Uses the `root`-marked `e` tag; notes using the deprecated positional
+/// scheme (no markers) have their first `e` tag taken as the root.
+pub fn thread_root(parent: &Event) -> Option<ThreadRef> {
+    // (reference, Some(is_root) when marked)
+    let event_tags: Vec<(ThreadRef, Option<bool>)> = parent
+        .tags
+        .iter()
+        .filter_map(|tag| match tag.as_standardized() {
+            Some(TagStandard::Event { event_id, relay_url, marker, public_key, uppercase: false }) => Some((
+                ThreadRef { id: *event_id, relay_hint: relay_url.clone(), author: *public_key },
+                marker.as_ref().map(|marker| *marker == Marker::Root),
+            )),
+            _ => None,
+        })
+        .collect();
+
+    if let Some((root, _)) = event_tags.iter().find(|(_, is_root)| *is_root == Some(true)) {
+        return Some(root.clone());
+    }
+    if event_tags.iter().all(|(_, is_root)| is_root.is_none()) {
+        return event_tags.into_iter().next().map(|(root, _)| root);
+    }
+    None
+}
+
+/// Tags for a kind 1 reply to `parent`, in thread `root`
+///
+/// A direct reply to the root (`root` None or the parent itself) gets a
+/// single `root`-marked `e` tag; a deeper reply gets `root` and `reply`.
+/// `parent_relay` is where the parent was seen. `p` tags list the parent
+/// author first, then the parent's own `p` tags with their relay hints.
+pub fn build_reply_tags(root: Option<&ThreadRef>, parent: &Event, parent_relay: Option<&RelayUrl>) -> Vec<Tag> {
+    let mut tags = Vec::new();
+
+    let event_tag = |id: EventId, relay_url: Option<RelayUrl>, marker: Marker, author: Option<PublicKey>| {
+        Tag::from_standardized(TagStandard::Event {
+            event_id: id,
+            relay_url,
+            marker: Some(marker),
+            public_key: author,
+            uppercase: false,
+        })
+    };
+
+    match root {
+        Some(root) if root.id != parent.id => {
+            tags.push(event_tag(root.id, root.relay_hint.clone(), Marker::Root, root.author));
+            tags.push(event_tag(parent.id, parent_relay.cloned(), Marker::Reply, Some(parent.pubkey)));
+        }
+        _ => {
+            // Replying to the root itself: its own hint does as well
+            let relay_url = parent_relay.cloned().or_else(|| root.and_then(|root| root.relay_hint.clone()));
+            tags.push(event_tag(parent.id, relay_url, Marker::Root, Some(parent.pubkey)));
+        }
+    }
+
+    let mut tagged: HashSet<PublicKey> = HashSet::new();
+    tagged.insert(parent.pubkey);
+    tags.push(Tag::public_key(parent.pubkey));
+    for tag in parent.tags.iter() {
+        if let Some(TagStandard::PublicKey { public_key, relay_url, uppercase: false, .. }) = tag.as_standardized() {
+            if tagged.insert(*public_key) {
+                tags.push(Tag::from_standardized(TagStandard::PublicKey {
+                    public_key: *public_key,
+                    relay_url: relay_url.clone(),
+                    alias: None,
+                    uppercase: false,
+                }));
+            }
+        }
+    }
+
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::{EventBuilder, Keys};
+
+    fn relay(url: &str) -> RelayUrl {
+        RelayUrl::parse(url).unwrap()
+    }
+
+    fn e_tags(tags: &[Tag]) -> Vec<Vec<String>> {
+        tags.iter()
+            .map(|tag| tag.as_slice().to_vec())
+            .filter(|tag| tag[0] == "e")
+            .collect()
+    }
+
+    fn p_tags(tags: &[Tag]) -> Vec<Vec<String>> {
+        tags.iter()
+            .map(|tag| tag.as_slice().to_vec())
+            .filter(|tag| tag[0] == "p")
+            .collect()
+    }
+
+    #[test]
+    fn test_top_level_reply() {
+        let alice = Keys::generate();
+        let root = EventBuilder::text_note("gm").sign_with_keys(&alice).unwrap();
+        assert_eq!(thread_root(&root), None);
+
+        let tags = build_reply_tags(thread_root(&root).as_ref(), &root, Some(&relay("wss://relay.alice.example")));
+        assert_eq!(
+            e_tags(&tags),
+            vec![vec![
+                "e".to_string(),
+                root.id.to_hex(),
+                relay("wss://relay.alice.example").to_string(),
+                "root".to_string(),
+                alice.public_key().to_hex(),
+            ]]
+        );
+        assert_eq!(p_tags(&tags), vec![vec!["p".to_string(), alice.public_key().to_hex()]]);
+    }
+
+    #[test]
+    fn test_deep_reply() {
+        let alice = Keys::generate();
+        let bob = Keys::generate();
+        let carol = Keys::generate();
+        let root = EventBuilder::text_note("gm").sign_with_keys(&alice).unwrap();
+
+        // Bob replies to Alice, then Carol replies to Bob
+        let root_ref = ThreadRef {
+            id: root.id,
+            relay_hint: Some(relay("wss://relay.alice.example")),
+            author: Some(alice.public_key()),
+        };
+        let bob_reply = EventBuilder::text_note("gm alice")
+            .tags(build_reply_tags(Some(&root_ref), &root, None))
+            .sign_with_keys(&bob)
+            .unwrap();
+        assert_eq!(thread_root(&bob_reply), Some(root_ref.clone()));
+
+        let carol_reply_tags = build_reply_tags(
+            thread_root(&bob_reply).as_ref(),
+            &bob_reply,
+            Some(&relay("wss://relay.bob.example")),
+        );
+        let carol_reply = EventBuilder::text_note("gm both")
+            .tags(carol_reply_tags.clone())
+            .sign_with_keys(&carol)
+            .unwrap();
+
+        assert_eq!(
+            e_tags(&carol_reply_tags),
+            vec![
+                vec![
+                    "e".to_string(),
+                    root.id.to_hex(),
+                    relay("wss://relay.alice.example").to_string(),
+                    "root".to_string(),
+                    alice.public_key().to_hex(),
+                ],
+                vec![
+                    "e".to_string(),
+                    bob_reply.id.to_hex(),
+                    relay("wss://relay.bob.example").to_string(),
+                    "reply".to_string(),
+                    bob.public_key().to_hex(),
+                ],
+            ]
+        );
+        // Parent author first, then everyone the parent tagged, once each
+        assert_eq!(
+            p_tags(&carol_reply_tags),
+            vec![
+                vec!["p".to_string(), bob.public_key().to_hex()],
+                vec!["p".to_string(), alice.public_key().to_hex()],
+            ]
+        );
+        // The root carries through another level
+        assert_eq!(thread_root(&carol_reply).map(|r| r.id), Some(root.id));
+    }
+
+    #[test]
+    fn test_thread_root_positional_tags() {
+        let keys = Keys::generate();
+        let root_id = EventId::all_zeros();
+        let parent_id = EventBuilder::text_note("x").sign_with_keys(&keys).unwrap().id;
+        // Deprecated scheme: [root, ..., reply] without markers
+        let old_reply = EventBuilder::text_note("old client")
+            .tags([Tag::event(root_id), Tag::event(parent_id)])
+            .sign_with_keys(&keys)
+            .unwrap();
+
+        assert_eq!(thread_root(&old_reply).map(|r| r.id), Some(root_id));
+    }
+}
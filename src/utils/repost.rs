@@ -1,4 +1,5 @@
-use nostr_sdk::{Event, Kind, JsonUtil, PublicKey, Timestamp};
+use nostr_sdk::{Event, EventId, FromBech32, Kind, JsonUtil, PublicKey, Timestamp};
+use nostr_sdk::nips::nip19::Nip19Event;
 
 /// Check if an event is a repost (Kind 6 or Kind 16)
 pub fn is_repost(event: &Event) -> bool {
@@ -20,6 +21,112 @@ pub fn extract_reposted_event(repost: &Event) -> Result<Event, String> {
     })
 }
 
+/// How an event shares another one (NIP-18)
+#[derive(Clone, Debug, PartialEq)]
+pub enum RepostKind {
+    /// Kind 6 repost of a text note
+    Repost,
+    /// Kind 16 repost of any other kind
+    GenericRepost,
+    /// Kind 1 note quoting an event with a `q` tag, below its own commentary
+    Quote {
+        event_id: EventId,
+        relay_hint: Option<String>,
+    },
+}
+
+/// Classify an event as a repost or quote; None for anything else
+pub fn repost_kind(event: &Event) -> Option<RepostKind> {
+    if event.kind == Kind::Repost {
+        Some(RepostKind::Repost)
+    } else if event.kind == Kind::GenericRepost {
+        Some(RepostKind::GenericRepost)
+    } else {
+        quote_target(event).map(|(event_id, relay_hint)| RepostKind::Quote { event_id, relay_hint })
+    }
+}
+
+/// The event a text note quotes via its `q` tag, with the tag's relay hint
+///
+/// `q` tags naming an address (`kind:pubkey:d`) rather than an event ID are
+/// left to the content renderer.
+pub fn quote_target(event: &Event) -> Option<(EventId, Option<String>)> {
+    if event.kind != Kind::TextNote {
+        return None;
+    }
+
+    event.tags.iter().find_map(|tag| {
+        let parts = tag.as_slice();
+        if parts.len() < 2 || parts[0] != "q" {
+            return None;
+        }
+        let event_id = EventId::from_hex(&parts[1]).ok()?;
+        let relay_hint = parts.get(2).filter(|relay| !relay.is_empty()).cloned();
+        Some((event_id, relay_hint))
+    })
+}
+
+/// Remove `nostr:` mentions of the quoted event from a quote's commentary
+///
+/// The quoted event is rendered as its own card below the commentary, so the
+/// inline mention would show it twice.
+pub fn strip_quote_mention(content: &str, quoted_id: &EventId) -> String {
+    let mentions_quote = |word: &str| {
+        let Some(identifier) = word.strip_prefix("nostr:") else {
+            return false;
+        };
+        let identifier = identifier.trim_end_matches(|c: char| !c.is_ascii_alphanumeric());
+        let event_id = if identifier.starts_with("nevent1") {
+            Nip19Event::from_bech32(identifier).ok().map(|nip19| nip19.event_id)
+        } else if identifier.starts_with("note1") {
+            EventId::from_bech32(identifier).ok()
+        } else {
+            None
+        };
+        event_id.as_ref() == Some(quoted_id)
+    };
+
+    content
+        .lines()
+        .map(|line| {
+            line.split(' ')
+                .filter(|word| !mentions_quote(word))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+/// Longest quoted text seeded into a reply, in characters
+pub const QUOTE_SEED_MAX_CHARS: usize = 280;
+
+/// Composer text quoting `source` as a markdown blockquote
+///
+/// The note's text, cut to [`QUOTE_SEED_MAX_CHARS`] with an ellipsis, is
+/// followed by a `nostr:nevent` reference so the quote keeps its context.
+pub fn blockquote_seed(source: &Event) -> Result<String, String> {
+    let nevent = crate::utils::nip19::encode_nevent(source.id, &[], Some(source.pubkey))?;
+
+    let text = source.content.trim();
+    let text = if text.chars().count() > QUOTE_SEED_MAX_CHARS {
+        let cut: String = text.chars().take(QUOTE_SEED_MAX_CHARS).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        text.to_string()
+    };
+
+    let quote = text
+        .lines()
+        .map(|line| if line.trim().is_empty() { ">".to_string() } else { format!("> {}", line) })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    Ok(format!("{}\n\nnostr:{}\n\n", quote, nevent))
+}
+
 /// Represents a feed item that could be either an original post or a repost
 #[derive(Clone, Debug)]
 pub enum FeedItem {
@@ -34,14 +141,32 @@ pub enum FeedItem {
         /// Timestamp when the repost was made
         repost_timestamp: Timestamp,
     },
+    /// A note quoting another event (`q` tag), shown with the quoted event embedded
+    QuotedNote {
+        /// The quoting note with the author's commentary
+        note: Event,
+        /// The quoted event, fetched when rendered
+        quoted_id: EventId,
+        /// Relay hint from the `q` tag
+        relay_hint: Option<String>,
+    },
 }
 
 impl FeedItem {
-    /// Get the underlying event (original or reposted)
+    /// Wrap a text note, as a quote if it has a `q` tag
+    pub fn from_note(note: Event) -> Self {
+        match quote_target(&note) {
+            Some((quoted_id, relay_hint)) => FeedItem::QuotedNote { note, quoted_id, relay_hint },
+            None => FeedItem::OriginalPost(note),
+        }
+    }
+
+    /// Get the underlying event (original, reposted or quoting note)
     pub fn event(&self) -> &Event {
         match self {
             FeedItem::OriginalPost(event) => event,
             FeedItem::Repost { original, .. } => original,
+            FeedItem::QuotedNote { note, .. } => note,
         }
     }
 
@@ -50,18 +175,27 @@ impl FeedItem {
         match self {
             FeedItem::OriginalPost(event) => event.created_at,
             FeedItem::Repost { repost_timestamp, .. } => *repost_timestamp,
+            FeedItem::QuotedNote { note, .. } => note.created_at,
         }
     }
 
     /// Get repost metadata if this is a repost
     pub fn repost_info(&self) -> Option<(PublicKey, Timestamp)> {
         match self {
-            FeedItem::OriginalPost(_) => None,
+            FeedItem::OriginalPost(_) | FeedItem::QuotedNote { .. } => None,
             FeedItem::Repost { reposted_by, repost_timestamp, .. } => {
                 Some((*reposted_by, *repost_timestamp))
             }
         }
     }
+
+    /// Get the quoted event ID and relay hint if this is a quote
+    pub fn quote_info(&self) -> Option<(EventId, Option<String>)> {
+        match self {
+            FeedItem::QuotedNote { quoted_id, relay_hint, .. } => Some((*quoted_id, relay_hint.clone())),
+            _ => None,
+        }
+    }
 }
 
 /// Expand events to include original authors from reposts for metadata prefetching.
@@ -86,10 +220,86 @@ pub fn expand_events_for_prefetch(events: &[Event]) -> Vec<Event> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use nostr_sdk::{EventBuilder, Keys, Tag, ToBech32};
+
+    fn original_note(keys: &Keys) -> Event {
+        EventBuilder::text_note("original").sign_with_keys(keys).unwrap()
+    }
 
     #[test]
     fn test_is_repost() {
-        // These tests would require creating mock Event objects
-        // Left as a framework for future testing
+        let keys = Keys::generate();
+        let original = original_note(&keys);
+
+        let repost = EventBuilder::repost(&original, None).sign_with_keys(&keys).unwrap();
+        assert!(is_repost(&repost));
+        assert_eq!(repost_kind(&repost), Some(RepostKind::Repost));
+        assert_eq!(extract_reposted_event(&repost).unwrap().id, original.id);
+
+        assert!(!is_repost(&original));
+        assert_eq!(repost_kind(&original), None);
+        assert!(extract_reposted_event(&original).is_err());
+    }
+
+    #[test]
+    fn test_generic_repost() {
+        let keys = Keys::generate();
+        let picture = EventBuilder::new(Kind::Custom(20), "a photo").sign_with_keys(&keys).unwrap();
+
+        let repost = EventBuilder::new(Kind::GenericRepost, picture.as_json())
+            .tags([Tag::event(picture.id), Tag::parse(["k", "20"]).unwrap()])
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(repost_kind(&repost), Some(RepostKind::GenericRepost));
+        assert_eq!(extract_reposted_event(&repost).unwrap().id, picture.id);
+        // Reposts are not quotes even though they reference the event
+        assert_eq!(quote_target(&repost), None);
+    }
+
+    #[test]
+    fn test_quote_with_q_tag() {
+        let keys = Keys::generate();
+        let original = original_note(&keys);
+        let nevent = Nip19Event::new(original.id).to_bech32().unwrap();
+
+        let quote = EventBuilder::text_note(format!("So true\nnostr:{}", nevent))
+            .tags([Tag::parse(["q", &original.id.to_hex(), "wss://relay.example"]).unwrap()])
+            .sign_with_keys(&keys)
+            .unwrap();
+        assert_eq!(
+            repost_kind(&quote),
+            Some(RepostKind::Quote {
+                event_id: original.id,
+                relay_hint: Some("wss://relay.example".to_string()),
+            })
+        );
+        assert!(!is_repost(&quote));
+
+        let item = FeedItem::from_note(quote.clone());
+        assert_eq!(item.event().id, quote.id);
+        assert_eq!(item.quote_info(), Some((original.id, Some("wss://relay.example".to_string()))));
+        assert_eq!(item.repost_info(), None);
+
+        // The embedded mention is dropped from the commentary, other text stays
+        assert_eq!(strip_quote_mention(&quote.content, &original.id), "So true");
+        let other = EventId::all_zeros();
+        assert_eq!(strip_quote_mention(&quote.content, &other), quote.content);
+
+        // A plain note stays an original post
+        assert!(matches!(FeedItem::from_note(original), FeedItem::OriginalPost(_)));
+    }
+
+    #[test]
+    fn test_blockquote_seed() {
+        let keys = Keys::generate();
+        let note = EventBuilder::text_note("first line\n\nsecond line").sign_with_keys(&keys).unwrap();
+        let seed = blockquote_seed(&note).unwrap();
+        let nevent = crate::utils::nip19::encode_nevent(note.id, &[], Some(note.pubkey)).unwrap();
+        assert_eq!(seed, format!("> first line\n>\n> second line\n\nnostr:{}\n\n", nevent));
+
+        let long = EventBuilder::text_note("a".repeat(QUOTE_SEED_MAX_CHARS + 10)).sign_with_keys(&keys).unwrap();
+        let seed = blockquote_seed(&long).unwrap();
+        let quoted = seed.lines().next().unwrap();
+        assert_eq!(quoted, format!("> {}…", "a".repeat(QUOTE_SEED_MAX_CHARS)));
     }
 }
@@ -0,0 +1,190 @@
+//! Search query operators
+//!
+//! The search box understands `from:npub…`, `since:YYYY-MM-DD`,
+//! `until:YYYY-MM-DD` and `kind:N` alongside free text. Operators become
+//! `Filter` fields; the remaining text goes to the relay's NIP-50 search.
+//! Operators with invalid values are dropped with a hint instead of failing
+//! the whole query.
+
+use chrono::NaiveDate;
+use nostr_sdk::{Filter, Kind, PublicKey, Timestamp};
+
+/// A search box query split into free text and filter operators
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchQuery {
+    /// Free text for the NIP-50 `search` field
+    pub text: String,
+    /// `from:` authors
+    pub authors: Vec<PublicKey>,
+    /// `since:` start of day (UTC)
+    pub since: Option<Timestamp>,
+    /// `until:` end of day (UTC), inclusive
+    pub until: Option<Timestamp>,
+    /// `kind:` kinds, replacing the kinds of the active tab
+    pub kinds: Vec<Kind>,
+    /// Why operators were ignored, for display
+    pub hints: Vec<String>,
+}
+
+impl SearchQuery {
+    /// True when there's neither text nor any operator to search by
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+            && self.authors.is_empty()
+            && self.since.is_none()
+            && self.until.is_none()
+            && self.kinds.is_empty()
+    }
+
+    /// Build the relay filter, using `default_kinds` unless `kind:` was given
+    pub fn to_filter(&self, default_kinds: &[Kind], limit: usize) -> Filter {
+        let kinds = if self.kinds.is_empty() { default_kinds } else { &self.kinds };
+        let mut filter = Filter::new().kinds(kinds.iter().copied()).limit(limit);
+
+        if !self.text.is_empty() {
+            filter = filter.search(&self.text);
+        }
+        if !self.authors.is_empty() {
+            filter = filter.authors(self.authors.iter().copied());
+        }
+        if let Some(since) = self.since {
+            filter = filter.since(since);
+        }
+        if let Some(until) = self.until {
+            filter = filter.until(until);
+        }
+        filter
+    }
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+/// Split a search box query into operators and free text
+pub fn parse_search_query(input: &str) -> SearchQuery {
+    let mut query = SearchQuery::default();
+    let mut words = Vec::new();
+
+    for word in input.split_whitespace() {
+        let Some((operator, value)) = word.split_once(':') else {
+            words.push(word);
+            continue;
+        };
+
+        match operator.to_lowercase().as_str() {
+            "from" => match PublicKey::parse(value) {
+                Ok(pubkey) => query.authors.push(pubkey),
+                Err(_) => query.hints.push(format!("Ignored from:{} (not a valid npub)", value)),
+            },
+            "since" => match parse_date(value).and_then(|date| date.and_hms_opt(0, 0, 0)) {
+                Some(start) => query.since = Some(Timestamp::from(start.and_utc().timestamp() as u64)),
+                None => query.hints.push(format!("Ignored since:{} (use YYYY-MM-DD)", value)),
+            },
+            "until" => match parse_date(value).and_then(|date| date.and_hms_opt(23, 59, 59)) {
+                Some(end) => query.until = Some(Timestamp::from(end.and_utc().timestamp() as u64)),
+                None => query.hints.push(format!("Ignored until:{} (use YYYY-MM-DD)", value)),
+            },
+            "kind" => match value.parse::<u16>() {
+                Ok(kind) => query.kinds.push(Kind::from(kind)),
+                Err(_) => query.hints.push(format!("Ignored kind:{} (not a kind number)", value)),
+            },
+            // Not an operator (e.g. a URL), keep it as text
+            _ => words.push(word),
+        }
+    }
+
+    if let (Some(since), Some(until)) = (query.since, query.until) {
+        if since > until {
+            query.hints.push("Ignored since: and until: (since is after until)".to_string());
+            query.since = None;
+            query.until = None;
+        }
+    }
+
+    query.text = words.join(" ");
+    query
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::{Keys, ToBech32};
+
+    #[test]
+    fn test_plain_text() {
+        let query = parse_search_query("  bitcoin   meetup ");
+        assert_eq!(query.text, "bitcoin meetup");
+        assert!(query.hints.is_empty());
+        assert!(!query.is_empty());
+        assert!(parse_search_query("   ").is_empty());
+    }
+
+    #[test]
+    fn test_from_operator() {
+        let pubkey = Keys::generate().public_key();
+        let query = parse_search_query(&format!("from:{} zaps", pubkey.to_bech32().unwrap()));
+        assert_eq!(query.authors, vec![pubkey]);
+        assert_eq!(query.text, "zaps");
+
+        let query = parse_search_query("from:npub1nope zaps");
+        assert!(query.authors.is_empty());
+        assert_eq!(query.text, "zaps");
+        assert_eq!(query.hints.len(), 1);
+    }
+
+    #[test]
+    fn test_since_and_until_operators() {
+        let query = parse_search_query("since:2024-01-02 until:2024-01-03");
+        assert_eq!(query.since, Some(Timestamp::from(1_704_153_600)));
+        assert_eq!(query.until, Some(Timestamp::from(1_704_326_399)));
+        assert!(query.text.is_empty());
+        assert!(!query.is_empty());
+
+        let query = parse_search_query("since:yesterday until:2024-13-01 news");
+        assert_eq!(query.since, None);
+        assert_eq!(query.until, None);
+        assert_eq!(query.text, "news");
+        assert_eq!(query.hints.len(), 2);
+
+        // An empty range is dropped rather than returning nothing
+        let query = parse_search_query("since:2024-02-01 until:2024-01-01");
+        assert_eq!((query.since, query.until), (None, None));
+        assert_eq!(query.hints.len(), 1);
+    }
+
+    #[test]
+    fn test_kind_operator() {
+        let query = parse_search_query("kind:30023 KIND:1 rust");
+        assert_eq!(query.kinds, vec![Kind::LongFormTextNote, Kind::TextNote]);
+        assert_eq!(query.text, "rust");
+
+        let query = parse_search_query("kind:note rust");
+        assert!(query.kinds.is_empty());
+        assert_eq!(query.hints.len(), 1);
+    }
+
+    #[test]
+    fn test_combined_operators() {
+        let pubkey = Keys::generate().public_key();
+        let query = parse_search_query(&format!(
+            "nostr from:{} https://example.com kind:1 since:2024-01-02 dev",
+            pubkey.to_hex()
+        ));
+        assert_eq!(query.text, "nostr https://example.com dev");
+        assert_eq!(query.authors, vec![pubkey]);
+        assert_eq!(query.kinds, vec![Kind::TextNote]);
+        assert_eq!(query.since, Some(Timestamp::from(1_704_153_600)));
+        assert!(query.hints.is_empty());
+
+        let filter = query.to_filter(&[Kind::LongFormTextNote], 50);
+        assert_eq!(filter.search.as_deref(), Some("nostr https://example.com dev"));
+        assert!(filter.kinds.as_ref().unwrap().contains(&Kind::TextNote));
+        assert!(filter.authors.as_ref().unwrap().contains(&pubkey));
+
+        // Without kind: the tab's kinds apply, and no text means no search
+        let filter = parse_search_query("since:2024-01-02").to_filter(&[Kind::LongFormTextNote], 50);
+        assert!(filter.kinds.as_ref().unwrap().contains(&Kind::LongFormTextNote));
+        assert_eq!(filter.search, None);
+    }
+}
@@ -0,0 +1,15 @@
+//! Helpers shared by unit tests
+
+use std::future::Future;
+use std::task::{Context, Poll, Waker};
+
+/// Drive a future that never waits on I/O to completion
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = std::pin::pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
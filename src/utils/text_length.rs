@@ -0,0 +1,43 @@
+//! Length of a note as readers see it
+//!
+//! Counts user-perceived characters (grapheme clusters), so an emoji built
+//! from several code points, like a family or a flag, counts once.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Size above which some relays may reject a note (they commonly cap
+/// content or whole messages at a few tens of kilobytes)
+pub const RELAY_SOFT_LIMIT_BYTES: usize = 16 * 1024;
+
+/// Number of user-perceived characters in `text`
+pub fn grapheme_count(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+/// Whether `text` is big enough that relays may refuse it
+pub fn exceeds_relay_soft_limit(text: &str) -> bool {
+    text.len() > RELAY_SOFT_LIMIT_BYTES
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grapheme_count_handles_emoji() {
+        assert_eq!(grapheme_count(""), 0);
+        assert_eq!(grapheme_count("gm nostr"), 8);
+        // One family emoji: 7 code points, 25 bytes
+        let family = "👨‍👩‍👧‍👦";
+        assert_eq!(grapheme_count(family), 1);
+        assert_eq!(family.chars().count(), 7);
+        assert_eq!(family.len(), 25);
+        // Flags, skin tones and combining accents
+        assert_eq!(grapheme_count("🇯🇵👍🏽"), 2);
+        assert_eq!(grapheme_count("e\u{301}te\u{301}"), 3);
+        assert_eq!(grapheme_count("ok 🤙\r\n"), 5);
+
+        assert!(!exceeds_relay_soft_limit(&"a".repeat(RELAY_SOFT_LIMIT_BYTES)));
+        assert!(exceeds_relay_soft_limit(&family.repeat(700)));
+    }
+}
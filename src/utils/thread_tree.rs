@@ -1,6 +1,6 @@
 use lru::LruCache;
 use nostr_sdk::{Event, EventId, PublicKey, TagKind};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::num::NonZeroUsize;
 use std::sync::{Mutex, OnceLock};
 use instant::{Duration, Instant};
@@ -34,6 +34,10 @@ pub struct ThreadNode {
     pub children: Vec<ThreadNode>,
     /// Source of this node (confirmed or pending)
     pub source: ThreadNodeSource,
+    /// Whether the children of this node start hidden behind a "Show N replies" toggle
+    pub collapsed: bool,
+    /// Reactions plus zaps, used by `SortMode::Top`
+    pub score: u64,
 }
 
 impl ThreadNode {
@@ -43,6 +47,8 @@ impl ThreadNode {
             event,
             children: Vec::new(),
             source: ThreadNodeSource::Confirmed,
+            collapsed: false,
+            score: 0,
         }
     }
 
@@ -52,8 +58,120 @@ impl ThreadNode {
             event,
             children: Vec::new(),
             source: ThreadNodeSource::Pending { local_id, status, author_pubkey },
+            collapsed: false,
+            score: 0,
         }
     }
+
+    /// Count all replies below this node (children, grandchildren, ...)
+    pub fn count_descendants(&self) -> usize {
+        self.children
+            .iter()
+            .map(|child| 1 + child.count_descendants())
+            .sum()
+    }
+}
+
+/// Order of sibling replies at each level of a thread
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortMode {
+    /// Most reactions and zaps first
+    Top,
+    Newest,
+    /// Chronological, as threads are built
+    #[default]
+    Oldest,
+}
+
+impl SortMode {
+    pub const ALL: [SortMode; 3] = [SortMode::Top, SortMode::Newest, SortMode::Oldest];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortMode::Top => "Top",
+            SortMode::Newest => "Newest",
+            SortMode::Oldest => "Oldest",
+        }
+    }
+
+    /// Stable key for a `<select>` value
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortMode::Top => "top",
+            SortMode::Newest => "newest",
+            SortMode::Oldest => "oldest",
+        }
+    }
+
+    pub fn from_value(value: &str) -> Self {
+        match value {
+            "top" => SortMode::Top,
+            "newest" => SortMode::Newest,
+            _ => SortMode::Oldest,
+        }
+    }
+}
+
+/// Sort `nodes` and every level below them
+///
+/// The sort is stable, so siblings with equal keys keep their current
+/// (chronological) order.
+pub fn sort_thread(nodes: &mut [ThreadNode], mode: SortMode) {
+    match mode {
+        SortMode::Top => nodes.sort_by(|a, b| b.score.cmp(&a.score)),
+        SortMode::Newest => nodes.sort_by(|a, b| b.event.created_at.cmp(&a.event.created_at)),
+        SortMode::Oldest => nodes.sort_by(|a, b| a.event.created_at.cmp(&b.event.created_at)),
+    }
+    for node in nodes {
+        sort_thread_children(node, mode);
+    }
+}
+
+/// Sort the replies below `node`, recursively
+pub fn sort_thread_children(node: &mut ThreadNode, mode: SortMode) {
+    sort_thread(&mut node.children, mode);
+}
+
+/// Set each node's score from `scores` (event ID hex -> reactions + zaps)
+pub fn apply_thread_scores(nodes: &mut [ThreadNode], scores: &HashMap<String, u64>) {
+    for node in nodes {
+        node.score = scores.get(&node.event.id.to_hex()).copied().unwrap_or(0);
+        apply_thread_scores(&mut node.children, scores);
+    }
+}
+
+/// Author of a node; pending nodes may carry a placeholder event pubkey
+fn node_author(node: &ThreadNode) -> PublicKey {
+    match &node.source {
+        ThreadNodeSource::Pending { author_pubkey, .. } => *author_pubkey,
+        ThreadNodeSource::Confirmed => node.event.pubkey,
+    }
+}
+
+/// `node` with only the replies below it written by `authors`
+///
+/// A reply by someone else stays when a kept reply sits under it, so every
+/// kept reply keeps its path back to `node`. `node` itself is always kept.
+pub fn filter_thread_by_authors(node: &ThreadNode, authors: &HashSet<PublicKey>) -> ThreadNode {
+    ThreadNode {
+        event: node.event.clone(),
+        children: filter_thread_nodes(&node.children, authors),
+        source: node.source.clone(),
+        collapsed: node.collapsed,
+        score: node.score,
+    }
+}
+
+/// Top-level replies filtered with [`filter_thread_by_authors`], dropping
+/// those with no reply by `authors` at or below them
+pub fn filter_thread_nodes(nodes: &[ThreadNode], authors: &HashSet<PublicKey>) -> Vec<ThreadNode> {
+    nodes
+        .iter()
+        .filter_map(|node| {
+            let filtered = filter_thread_by_authors(node, authors);
+            (authors.contains(&node_author(node)) || !filtered.children.is_empty()).then_some(filtered)
+        })
+        .collect()
 }
 
 /// Get the parent event ID from a reply event
@@ -221,15 +339,35 @@ fn get_thread_tree_cache() -> &'static Mutex<ThreadTreeCache> {
 /// # Arguments
 /// * `replies` - Flat list of reply events
 /// * `root_event_id` - The ID of the root event being replied to
+/// * `max_depth` - If set, nodes at this depth or deeper (direct replies to the
+///   root are depth 1) start collapsed when they have replies of their own
 ///
 /// # Algorithm
 /// 1. Check L2 cache for existing tree (if valid)
 /// 2. Create a map of event ID to ThreadNode for fast lookup
 /// 3. For each reply, determine its parent using NIP-10 logic
 /// 4. Build parent-child relationships
-/// 5. Sort by timestamp (chronological order)
-/// 6. Cache result for future calls
-pub fn build_thread_tree(replies: Vec<Event>, root_event_id: &EventId) -> Vec<ThreadNode> {
+/// 5. Attach orphaned replies (parent not fetched) to the root level
+/// 6. Sort by timestamp (chronological order)
+/// 7. Cache result for future calls
+/// 8. Apply `max_depth` collapsing (the cached tree is always fully expanded)
+pub fn build_thread_tree(replies: Vec<Event>, root_event_id: &EventId, max_depth: Option<usize>) -> Vec<ThreadNode> {
+    let mut tree = build_expanded_thread_tree(replies, root_event_id);
+    if let Some(max_depth) = max_depth {
+        collapse_below_depth(&mut tree, 1, max_depth);
+    }
+    tree
+}
+
+/// Mark nodes at `max_depth` or deeper that have replies as collapsed
+fn collapse_below_depth(nodes: &mut [ThreadNode], depth: usize, max_depth: usize) {
+    for node in nodes {
+        node.collapsed = depth >= max_depth && !node.children.is_empty();
+        collapse_below_depth(&mut node.children, depth + 1, max_depth);
+    }
+}
+
+fn build_expanded_thread_tree(replies: Vec<Event>, root_event_id: &EventId) -> Vec<ThreadNode> {
     let root_id_hex = root_event_id.to_hex();
 
     // Phase 3.5: Check L2 cache first
@@ -355,6 +493,22 @@ pub fn build_thread_tree(replies: Vec<Event>, root_event_id: &EventId) -> Vec<Th
         node.children = attach_children(&node.event.id, &replies, &mut node_map);
     }
 
+    // Orphaned replies: the parent wasn't fetched, so show them (and their own
+    // replies) at the root level instead of dropping them
+    let reply_ids: HashSet<EventId> = replies.iter().map(|reply| reply.id).collect();
+    for reply in &replies {
+        let is_orphan = matches!(
+            get_parent_id(reply),
+            Some(parent_id) if parent_id != *root_event_id && !reply_ids.contains(&parent_id)
+        );
+        if is_orphan {
+            if let Some(mut node) = node_map.remove(&reply.id) {
+                node.children = attach_children(&reply.id, &replies, &mut node_map);
+                root_replies.push(node);
+            }
+        }
+    }
+
     // Sort root replies by timestamp
     root_replies.sort_by(|a, b| a.event.created_at.cmp(&b.event.created_at));
 
@@ -464,6 +618,8 @@ pub fn merge_pending_into_tree(
             fn insert_as_child(nodes: &mut Vec<ThreadNode>, parent_id: &EventId, mut node: ThreadNode) -> Option<ThreadNode> {
                 for existing in nodes.iter_mut() {
                     if existing.event.id == *parent_id {
+                        // Keep the user's own reply visible
+                        existing.collapsed = false;
                         existing.children.push(node);
                         // Sort children by timestamp
                         existing.children.sort_by(|a, b| a.event.created_at.cmp(&b.event.created_at));
@@ -473,7 +629,11 @@ pub fn merge_pending_into_tree(
                 // Try children - pass ownership through each subtree
                 for existing in nodes.iter_mut() {
                     match insert_as_child(&mut existing.children, parent_id, node) {
-                        None => return None, // Found and consumed in subtree
+                        None => {
+                            // Found and consumed in subtree, expand the path to it
+                            existing.collapsed = false;
+                            return None;
+                        }
                         Some(returned) => node = returned, // Not found, continue with ownership
                     }
                 }
@@ -495,3 +655,169 @@ pub fn merge_pending_into_tree(
 
     confirmed_tree
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::{EventBuilder, Keys, Tag, Timestamp};
+
+    fn reply_to(keys: &Keys, parent: EventId, created_at: u64) -> Event {
+        EventBuilder::text_note("reply")
+            .tags(vec![Tag::event(parent)])
+            .custom_created_at(Timestamp::from(created_at))
+            .sign_with_keys(keys)
+            .unwrap()
+    }
+
+    fn find<'a>(nodes: &'a [ThreadNode], id: &EventId) -> Option<&'a ThreadNode> {
+        nodes.iter().find_map(|node| {
+            if node.event.id == *id {
+                Some(node)
+            } else {
+                find(&node.children, id)
+            }
+        })
+    }
+
+    #[test]
+    fn test_count_descendants_and_max_depth() {
+        let keys = Keys::generate();
+        let root = EventBuilder::text_note("root").sign_with_keys(&keys).unwrap();
+
+        // root <- a <- b <- c <- d, and root <- e
+        let a = reply_to(&keys, root.id, 10);
+        let b = reply_to(&keys, a.id, 20);
+        let c = reply_to(&keys, b.id, 30);
+        let d = reply_to(&keys, c.id, 40);
+        let e = reply_to(&keys, root.id, 50);
+        let replies = vec![a.clone(), b.clone(), c.clone(), d.clone(), e.clone()];
+
+        let tree = build_thread_tree(replies.clone(), &root.id, None);
+        assert_eq!(tree.len(), 2);
+        assert_eq!(count_total_replies(&tree), 5);
+        assert_eq!(find(&tree, &a.id).unwrap().count_descendants(), 3);
+        assert_eq!(find(&tree, &c.id).unwrap().count_descendants(), 1);
+        assert_eq!(find(&tree, &e.id).unwrap().count_descendants(), 0);
+        assert!(!find(&tree, &b.id).unwrap().collapsed);
+
+        // Depth 1 (a) stays open, depth 2+ with replies (b, c) collapse,
+        // leaves (d, e) have nothing to hide
+        let tree = build_thread_tree(replies, &root.id, Some(2));
+        assert!(!find(&tree, &a.id).unwrap().collapsed);
+        assert!(find(&tree, &b.id).unwrap().collapsed);
+        assert!(find(&tree, &c.id).unwrap().collapsed);
+        assert!(!find(&tree, &d.id).unwrap().collapsed);
+        assert!(!find(&tree, &e.id).unwrap().collapsed);
+        assert_eq!(find(&tree, &b.id).unwrap().count_descendants(), 2);
+    }
+
+    #[test]
+    fn test_orphaned_replies_attach_to_root() {
+        let keys = Keys::generate();
+        let root = EventBuilder::text_note("root").sign_with_keys(&keys).unwrap();
+        let missing = EventBuilder::text_note("not fetched").sign_with_keys(&keys).unwrap();
+
+        let direct = reply_to(&keys, root.id, 10);
+        let orphan = reply_to(&keys, missing.id, 20);
+        let orphan_child = reply_to(&keys, orphan.id, 30);
+
+        let tree = build_thread_tree(vec![direct.clone(), orphan.clone(), orphan_child.clone()], &root.id, None);
+        let top_level: Vec<EventId> = tree.iter().map(|node| node.event.id).collect();
+        assert_eq!(top_level, vec![direct.id, orphan.id]);
+        assert_eq!(tree[1].children[0].event.id, orphan_child.id);
+    }
+
+    fn ids(nodes: &[ThreadNode]) -> Vec<EventId> {
+        nodes.iter().map(|node| node.event.id).collect()
+    }
+
+    #[test]
+    fn test_sort_thread_modes() {
+        let keys = Keys::generate();
+        let root = EventBuilder::text_note("root").sign_with_keys(&keys).unwrap();
+
+        // root <- a, b, c; a <- x, y
+        let a = reply_to(&keys, root.id, 10);
+        let b = reply_to(&keys, root.id, 20);
+        let c = reply_to(&keys, root.id, 30);
+        let x = reply_to(&keys, a.id, 40);
+        let y = reply_to(&keys, a.id, 50);
+        let replies = vec![c.clone(), a.clone(), y.clone(), b.clone(), x.clone()];
+        let scores: HashMap<String, u64> = [(b.id.to_hex(), 5), (c.id.to_hex(), 2), (y.id.to_hex(), 1)]
+            .into_iter()
+            .collect();
+
+        let mut tree = build_thread_tree(replies, &root.id, None);
+        apply_thread_scores(&mut tree, &scores);
+
+        sort_thread(&mut tree, SortMode::Newest);
+        assert_eq!(ids(&tree), vec![c.id, b.id, a.id]);
+        assert_eq!(ids(&tree[2].children), vec![y.id, x.id]);
+
+        sort_thread(&mut tree, SortMode::Oldest);
+        assert_eq!(ids(&tree), vec![a.id, b.id, c.id]);
+        assert_eq!(ids(&tree[0].children), vec![x.id, y.id]);
+
+        sort_thread(&mut tree, SortMode::Top);
+        assert_eq!(ids(&tree), vec![b.id, c.id, a.id]);
+        assert_eq!(ids(&tree[2].children), vec![y.id, x.id]);
+    }
+
+    #[test]
+    fn test_sort_thread_children_is_stable() {
+        let keys = Keys::generate();
+        let root = EventBuilder::text_note("root").sign_with_keys(&keys).unwrap();
+        let parent = reply_to(&keys, root.id, 10);
+
+        // Same timestamp and no score: order must not change in any mode
+        let siblings: Vec<Event> = (0..4)
+            .map(|i| {
+                EventBuilder::text_note(format!("reply {}", i))
+                    .tags(vec![Tag::event(parent.id)])
+                    .custom_created_at(Timestamp::from(20))
+                    .sign_with_keys(&keys)
+                    .unwrap()
+            })
+            .collect();
+
+        let mut node = ThreadNode::confirmed(parent);
+        node.children = siblings.into_iter().map(ThreadNode::confirmed).collect();
+        let original = ids(&node.children);
+
+        for mode in SortMode::ALL {
+            sort_thread_children(&mut node, mode);
+            assert_eq!(ids(&node.children), original, "{:?} reordered equal siblings", mode);
+        }
+    }
+
+    #[test]
+    fn test_filter_thread_keeps_ancestors_of_followed_replies() {
+        let stranger = Keys::generate();
+        let friend = Keys::generate();
+        let root = EventBuilder::text_note("root").sign_with_keys(&stranger).unwrap();
+
+        // root <- a (stranger) <- b (stranger) <- c (friend), root <- spam (stranger),
+        // root <- d (friend) <- e (stranger)
+        let a = reply_to(&stranger, root.id, 10);
+        let b = reply_to(&stranger, a.id, 20);
+        let c = reply_to(&friend, b.id, 30);
+        let spam = reply_to(&stranger, root.id, 40);
+        let d = reply_to(&friend, root.id, 50);
+        let e = reply_to(&stranger, d.id, 60);
+        let tree = build_thread_tree(vec![a.clone(), b.clone(), c.clone(), spam, d.clone(), e], &root.id, None);
+
+        let following = HashSet::from([friend.public_key()]);
+        let filtered = filter_thread_nodes(&tree, &following);
+        assert_eq!(ids(&filtered), vec![a.id, d.id]);
+        // The unfollowed ancestors of c stay so the reply keeps its context
+        assert_eq!(ids(&filtered[0].children), vec![b.id]);
+        assert_eq!(ids(&filtered[0].children[0].children), vec![c.id]);
+        // Unfollowed replies below a followed one are dropped
+        assert!(filtered[1].children.is_empty());
+
+        // Filtering a single node keeps the node itself, followed or not
+        let node = filter_thread_by_authors(&tree[1], &following);
+        assert_eq!(node.event.id, tree[1].event.id);
+        assert!(node.children.is_empty());
+    }
+}
@@ -1,6 +1,11 @@
 use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
 use nostr_sdk::Timestamp;
 
+/// Current Unix time in seconds
+pub fn now_secs() -> u64 {
+    Timestamp::now().as_secs()
+}
+
 /// Format a timestamp as relative time
 ///
 /// # Arguments
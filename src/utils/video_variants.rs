@@ -0,0 +1,215 @@
+//! NIP-71 video variants
+//!
+//! A video event can carry one `imeta` tag per rendition: different
+//! resolutions of the same MP4, an HLS manifest, each with optional
+//! `fallback` URLs. The player offers them as a quality list.
+
+use nostr_sdk::Event;
+
+/// Label for variants without dimensions (and adaptive HLS streams)
+pub const AUTO_QUALITY: &str = "auto";
+
+/// One playable rendition of a video
+#[derive(Clone, Debug, PartialEq)]
+pub struct VideoVariant {
+    pub url: String,
+    pub mime: Option<String>,
+    /// Width and height in pixels, from `dim`
+    pub dimensions: Option<(u32, u32)>,
+    /// Mirrors of the same file
+    pub fallbacks: Vec<String>,
+}
+
+impl VideoVariant {
+    /// Whether this is an HLS manifest
+    pub fn is_hls(&self) -> bool {
+        let by_mime = self.mime.as_deref().is_some_and(|m| {
+            let m = m.to_ascii_lowercase();
+            m == "application/x-mpegurl" || m == "application/vnd.apple.mpegurl"
+        });
+        let path = self.url.split(['?', '#']).next().unwrap_or_default();
+        by_mime || path.to_ascii_lowercase().ends_with(".m3u8")
+    }
+
+    /// Vertical resolution, the smaller side for portrait videos
+    pub fn height(&self) -> Option<u32> {
+        self.dimensions.map(|(w, h)| w.min(h))
+    }
+
+    /// Menu label: "1080p", or "auto" for HLS and variants without dimensions
+    pub fn label(&self) -> String {
+        match self.height() {
+            Some(height) if !self.is_hls() => format!("{}p", height),
+            _ => AUTO_QUALITY.to_string(),
+        }
+    }
+
+    /// `type` attribute for a `<source>` element
+    pub fn source_type(&self) -> Option<String> {
+        if self.is_hls() {
+            Some("application/x-mpegURL".to_string())
+        } else {
+            self.mime.clone()
+        }
+    }
+}
+
+fn parse_dimensions(value: &str) -> Option<(u32, u32)> {
+    let (w, h) = value.trim().split_once('x')?;
+    let (w, h) = (w.parse().ok()?, h.parse().ok()?);
+    (w > 0 && h > 0).then_some((w, h))
+}
+
+/// Every `imeta` variant on a video event, sorted for a quality menu
+///
+/// HLS comes first, then other "auto" variants, then resolutions from
+/// highest to lowest. Tags without a `url` are skipped, as are repeats of
+/// the same URL.
+pub fn parse_video_variants(event: &Event) -> Vec<VideoVariant> {
+    let mut variants: Vec<VideoVariant> = Vec::new();
+
+    for tag in event.tags.iter() {
+        let parts = tag.as_slice();
+        if parts.first().map(|s| s.as_str()) != Some("imeta") {
+            continue;
+        }
+
+        let mut url = None;
+        let mut variant = VideoVariant {
+            url: String::new(),
+            mime: None,
+            dimensions: None,
+            fallbacks: Vec::new(),
+        };
+        for field in parts.iter().skip(1) {
+            if let Some((key, value)) = field.split_once(' ') {
+                match key {
+                    "url" => url = Some(value.trim().to_string()),
+                    "m" => variant.mime = Some(value.trim().to_string()),
+                    "dim" => variant.dimensions = parse_dimensions(value),
+                    "fallback" => variant.fallbacks.push(value.trim().to_string()),
+                    _ => {}
+                }
+            }
+        }
+
+        match url {
+            Some(url) if !url.is_empty() && !variants.iter().any(|v| v.url == url) => {
+                variant.url = url;
+                variants.push(variant);
+            }
+            _ => {}
+        }
+    }
+
+    // Stable, so equal entries keep tag order
+    variants.sort_by_key(|v| {
+        let rank = if v.is_hls() { 0 } else if v.label() == AUTO_QUALITY { 1 } else { 2 };
+        (rank, std::cmp::Reverse(v.height().unwrap_or(0)))
+    });
+    variants
+}
+
+/// The variant to play: the preferred quality if offered, else the first
+/// (HLS when there is one)
+pub fn pick_variant<'a>(variants: &'a [VideoVariant], preferred: Option<&str>) -> Option<&'a VideoVariant> {
+    preferred
+        .and_then(|label| variants.iter().find(|v| v.label() == label))
+        .or_else(|| variants.first())
+}
+
+/// `<source>` list for a selection: its URL and mirrors, then the other
+/// variants as fallbacks (HLS, when not selected, last)
+///
+/// Browsers skip sources they can't play, so an HLS pick still plays as
+/// MP4 where HLS isn't supported.
+pub fn playback_sources(variants: &[VideoVariant], selected: &VideoVariant) -> Vec<(String, Option<String>)> {
+    let mut ordered: Vec<&VideoVariant> = vec![selected];
+    ordered.extend(variants.iter().filter(|v| v.url != selected.url && !v.is_hls()));
+    ordered.extend(variants.iter().filter(|v| v.url != selected.url && v.is_hls()));
+
+    let mut sources: Vec<(String, Option<String>)> = Vec::new();
+    for variant in ordered {
+        for url in std::iter::once(&variant.url).chain(variant.fallbacks.iter()) {
+            if !sources.iter().any(|(u, _)| u == url) {
+                sources.push((url.clone(), variant.source_type()));
+            }
+        }
+    }
+    sources
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nostr_sdk::{EventBuilder, Keys, Kind, Tag};
+
+    fn video_event(imetas: Vec<Vec<&str>>) -> Event {
+        let tags: Vec<Tag> = imetas
+            .into_iter()
+            .map(|fields| {
+                let mut parts = vec!["imeta".to_string()];
+                parts.extend(fields.into_iter().map(String::from));
+                Tag::parse(parts).unwrap()
+            })
+            .collect();
+        EventBuilder::new(Kind::from(21_u16), "")
+            .tags(tags)
+            .sign_with_keys(&Keys::generate())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_multiple_variants_sorted() {
+        let event = video_event(vec![
+            vec!["url https://cdn.example.com/480.mp4", "m video/mp4", "dim 854x480"],
+            vec!["url https://cdn.example.com/1080.mp4", "m video/mp4", "dim 1920x1080", "fallback https://mirror.example.com/1080.mp4"],
+            vec!["url https://cdn.example.com/unknown.mp4", "m video/mp4"],
+            vec!["url https://cdn.example.com/master.m3u8?token=1", "dim 1920x1080"],
+            vec!["url https://cdn.example.com/720.mp4", "m video/mp4", "dim 720x1280"],
+            vec!["m video/mp4", "dim 640x360"],
+            vec!["url https://cdn.example.com/480.mp4", "m video/mp4", "dim 854x480"],
+        ]);
+
+        let variants = parse_video_variants(&event);
+        let labels: Vec<String> = variants.iter().map(|v| v.label()).collect();
+        assert_eq!(labels, vec!["auto", "auto", "1080p", "720p", "480p"]);
+
+        assert!(variants[0].is_hls());
+        assert_eq!(variants[0].source_type().as_deref(), Some("application/x-mpegURL"));
+        assert_eq!(variants[1].url, "https://cdn.example.com/unknown.mp4");
+        assert_eq!(variants[2].fallbacks, vec!["https://mirror.example.com/1080.mp4"]);
+        // Portrait video: 720 wide is 720p
+        assert_eq!(variants[3].dimensions, Some((720, 1280)));
+    }
+
+    #[test]
+    fn test_pick_variant_and_sources() {
+        let event = video_event(vec![
+            vec!["url https://cdn.example.com/720.mp4", "m video/mp4", "dim 1280x720"],
+            vec!["url https://cdn.example.com/master.m3u8", "m application/x-mpegURL"],
+            vec!["url https://cdn.example.com/360.mp4", "m video/mp4", "dim 640x360"],
+        ]);
+        let variants = parse_video_variants(&event);
+
+        // HLS by default, remembered quality when this video offers it
+        assert!(pick_variant(&variants, None).unwrap().is_hls());
+        assert_eq!(pick_variant(&variants, Some("360p")).unwrap().url, "https://cdn.example.com/360.mp4");
+        assert!(pick_variant(&variants, Some("1080p")).unwrap().is_hls());
+        assert!(pick_variant(&[], Some("720p")).is_none());
+
+        // HLS falls back to the MP4s
+        let sources = playback_sources(&variants, &variants[0]);
+        let urls: Vec<&str> = sources.iter().map(|(u, _)| u.as_str()).collect();
+        assert_eq!(urls, vec![
+            "https://cdn.example.com/master.m3u8",
+            "https://cdn.example.com/720.mp4",
+            "https://cdn.example.com/360.mp4",
+        ]);
+
+        // An MP4 pick keeps HLS as the last resort
+        let sources = playback_sources(&variants, &variants[2]);
+        assert_eq!(sources[0].0, "https://cdn.example.com/360.mp4");
+        assert_eq!(sources.last().unwrap().0, "https://cdn.example.com/master.m3u8");
+    }
+}